@@ -1,4 +1,4 @@
-use chrono::{DateTime, NaiveTime, Utc, naive::NaiveDate};
+use chrono::{DateTime, FixedOffset, NaiveTime, Utc, naive::NaiveDate};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -11,6 +11,12 @@ pub struct Event {
     #[serde(rename = "endDate")]
     pub end_date: NaiveDate,
     pub slug: Option<String>,
+    #[serde(default, rename = "beginTime")]
+    pub begin_time: Option<NaiveTime>,
+    #[serde(default, rename = "endTime")]
+    pub end_time: Option<NaiveTime>,
+    #[serde(default)]
+    pub archived: bool,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -30,6 +36,55 @@ pub struct ExtendedEvent {
     pub entry_submission_mode: EntrySubmissionMode,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct EventDay {
+    pub date: NaiveDate,
+    pub label: String,
+    #[serde(rename = "beginUtc")]
+    pub begin_utc: DateTime<Utc>,
+    #[serde(rename = "endUtc")]
+    pub end_utc: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GridDay {
+    #[serde(flatten)]
+    pub day: EventDay,
+    pub columns: u32,
+    pub entries: Vec<GridEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GridEntry {
+    pub entry: Entry,
+    pub begin: DateTime<FixedOffset>,
+    pub end: DateTime<FixedOffset>,
+    #[serde(rename = "beginMinute")]
+    pub begin_minute: i64,
+    #[serde(rename = "endMinute")]
+    pub end_minute: i64,
+    pub column: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EntryWithScheduleSection {
+    pub entry: Entry,
+    #[serde(rename = "scheduleSection")]
+    pub schedule_section: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct OverviewDay {
+    pub day: NaiveDate,
+    pub entries: Vec<Entry>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DailyEntryCount {
+    pub day: NaiveDate,
+    pub count: u32,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct EventDayTimeSchedule {
     pub sections: Vec<EventDayScheduleSection>,
@@ -84,6 +139,12 @@ pub struct Entry {
     pub is_exclusive: bool,
     #[serde(default, skip_serializing_if = "not", rename = "isCancelled")]
     pub is_cancelled: bool,
+    #[serde(default, skip_serializing_if = "not", rename = "isTentative")]
+    pub is_tentative: bool,
+    #[serde(default, skip_serializing_if = "not", rename = "orgaOnly")]
+    pub orga_only: bool,
+    #[serde(default, skip_serializing_if = "not", rename = "openEnded")]
+    pub open_ended: bool,
     #[serde(default, skip_serializing_if = "not", rename = "isRoomReservation")]
     pub is_room_reservation: bool,
     pub category: Uuid,
@@ -95,10 +156,80 @@ pub struct Entry {
         rename = "orgaComment"
     )]
     pub orga_comment: Option<String>,
+    /// Role of the client which modified the entry most recently. Only present for clients with
+    /// orga privileges and ignored when creating or updating an entry.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        rename = "lastUpdatedByRole"
+    )]
+    pub last_updated_by_role: Option<AuthorizationRole>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        rename = "colorOverride"
+    )]
+    pub color_override: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<EntryLink>,
     #[serde(default, rename = "previousDates")]
     pub previous_dates: Vec<PreviousDate>,
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct EntryLink {
+    pub label: String,
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EntryMove {
+    #[serde(rename = "targetEventId")]
+    pub target_event_id: i32,
+    #[serde(rename = "targetCategory")]
+    pub target_category: Uuid,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EntryCopy {
+    #[serde(rename = "sourceEventId")]
+    pub source_event_id: i32,
+    #[serde(rename = "targetCategory")]
+    pub target_category: Uuid,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ResponsiblePersonReassignment {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EntryCategoryBulkUpdate {
+    #[serde(rename = "entryIds")]
+    pub entry_ids: Vec<Uuid>,
+    pub category: Uuid,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EntryShift {
+    #[serde(rename = "offsetMinutes")]
+    pub offset_minutes: i64,
+    #[serde(default)]
+    pub comment: String,
+    /// Must be set to confirm shifting all entries of the event, if no filter is given
+    #[serde(default)]
+    pub all: bool,
+}
+
+/// Result of an operation changing multiple entries at once, e.g. reassigning the responsible
+/// person, changing the category, shifting or cancelling entries
+#[derive(Serialize, Deserialize)]
+pub struct BulkUpdateResult {
+    #[serde(rename = "entryCount")]
+    pub entry_count: usize,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct EntryPatch {
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -143,6 +274,16 @@ pub struct EntryPatch {
         rename = "isCancelled"
     )]
     pub is_cancelled: Option<bool>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        rename = "isTentative"
+    )]
+    pub is_tentative: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none", rename = "orgaOnly")]
+    pub orga_only: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none", rename = "openEnded")]
+    pub open_ended: Option<bool>,
     #[serde(
         default,
         skip_serializing_if = "Option::is_none",
@@ -187,6 +328,55 @@ pub struct EntrySubmission {
     pub publish_without_review: bool,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct EntryTemplate {
+    pub id: Uuid,
+    pub name: String,
+    pub defaults: EntryTemplateDefaults,
+}
+
+/// Default values for the fields of new entries, created from an [EntryTemplate]. In contrast to
+/// [Entry], this does not include date and time.
+#[derive(Serialize, Deserialize, Default)]
+pub struct EntryTemplateDefaults {
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub title: String,
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub comment: String,
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub description: String,
+    #[serde(default)]
+    pub room: Vec<Uuid>,
+    #[serde(default, skip_serializing_if = "str::is_empty", rename = "roomComment")]
+    pub room_comment: String,
+    #[serde(default, skip_serializing_if = "str::is_empty", rename = "timeComment")]
+    pub time_comment: String,
+    #[serde(
+        default,
+        skip_serializing_if = "str::is_empty",
+        rename = "responsiblePerson"
+    )]
+    pub responsible_person: String,
+    #[serde(default, skip_serializing_if = "not", rename = "isExclusive")]
+    pub is_exclusive: bool,
+    #[serde(default, skip_serializing_if = "not", rename = "isTentative")]
+    pub is_tentative: bool,
+    #[serde(default, skip_serializing_if = "not", rename = "orgaOnly")]
+    pub orga_only: bool,
+    #[serde(default, skip_serializing_if = "not", rename = "isRoomReservation")]
+    pub is_room_reservation: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<Uuid>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        rename = "colorOverride"
+    )]
+    pub color_override: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<EntryLink>,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct PreviousDate {
     pub id: Uuid,
@@ -197,6 +387,16 @@ pub struct PreviousDate {
     pub room: Vec<Uuid>,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct EntryPreviousDates {
+    #[serde(rename = "entryId")]
+    pub entry_id: Uuid,
+    #[serde(rename = "entryTitle")]
+    pub entry_title: String,
+    #[serde(rename = "previousDates")]
+    pub previous_dates: Vec<PreviousDate>,
+}
+
 #[derive(Serialize, Deserialize)]
 pub enum EntryState {
     /// Normal public entry state, visible to all participants.
@@ -228,6 +428,51 @@ impl EntryState {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct EntryChanges {
+    #[serde(rename = "previousDateId")]
+    pub previous_date_id: Uuid,
+    #[serde(rename = "beginShiftMinutes")]
+    pub begin_shift_minutes: i64,
+    #[serde(rename = "endShiftMinutes")]
+    pub end_shift_minutes: i64,
+    #[serde(rename = "addedRooms")]
+    pub added_rooms: Vec<Uuid>,
+    #[serde(rename = "removedRooms")]
+    pub removed_rooms: Vec<Uuid>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EntryAuditLogRecord {
+    #[serde(rename = "entryId")]
+    pub entry_id: Uuid,
+    pub action: EntryAuditAction,
+    #[serde(rename = "passphraseId")]
+    pub passphrase_id: Option<i32>,
+    pub timestamp: DateTime<Utc>,
+    #[serde(rename = "changedFields")]
+    pub changed_fields: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum EntryAuditAction {
+    #[serde(rename = "create")]
+    Create,
+    #[serde(rename = "update")]
+    Update,
+    #[serde(rename = "delete")]
+    Delete,
+}
+
+/// Message pushed to clients of the live updates WebSocket, whenever an entry of the event has
+/// been changed
+#[derive(Serialize, Deserialize)]
+pub struct EntryChangeNotification {
+    #[serde(rename = "entryId")]
+    pub entry_id: Uuid,
+    pub action: EntryAuditAction,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Room {
     pub id: Uuid,
@@ -235,6 +480,26 @@ pub struct Room {
     pub description: String,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct EventRooms {
+    #[serde(rename = "eventId")]
+    pub event_id: i32,
+    pub rooms: Vec<Room>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AllEventsRooms {
+    pub events: Vec<EventRooms>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RoomPatch {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Category {
     pub id: Uuid,
@@ -246,6 +511,13 @@ pub struct Category {
     pub sort_key: i32,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct CategoryWithEntryCount {
+    pub category: Category,
+    #[serde(rename = "entryCount")]
+    pub entry_count: i64,
+}
+
 #[derive(Serialize, Deserialize)]
 pub enum AnnouncementType {
     #[serde(rename = "info")]
@@ -267,6 +539,10 @@ pub struct Announcement {
     pub begin_date: Option<NaiveDate>,
     #[serde(default, skip_serializing_if = "Option::is_none", rename = "endDate")]
     pub end_date: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none", rename = "beginTime")]
+    pub begin_time: Option<NaiveTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none", rename = "endTime")]
+    pub end_time: Option<NaiveTime>,
     #[serde(rename = "sortKey")]
     pub sort_key: i32,
     #[serde(default, rename = "showWithCategories")]
@@ -281,6 +557,12 @@ pub struct Announcement {
     pub rooms: Vec<Uuid>,
     #[serde(default, rename = "showWithAllRooms")]
     pub show_with_all_rooms: bool,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        rename = "lastUpdated"
+    )]
+    pub last_updated: Option<DateTime<Utc>>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -303,6 +585,10 @@ pub struct AnnouncementPatch {
     pub begin_date: Option<Option<NaiveDate>>,
     #[serde(default, skip_serializing_if = "Option::is_none", rename = "endDate")]
     pub end_date: Option<Option<NaiveDate>>,
+    #[serde(default, skip_serializing_if = "Option::is_none", rename = "beginTime")]
+    pub begin_time: Option<Option<NaiveTime>>,
+    #[serde(default, skip_serializing_if = "Option::is_none", rename = "endTime")]
+    pub end_time: Option<Option<NaiveTime>>,
     #[serde(default, skip_serializing_if = "Option::is_none", rename = "sortKey")]
     pub sort_key: Option<i32>,
     #[serde(
@@ -333,6 +619,83 @@ pub struct AnnouncementPatch {
         rename = "showWithAllRooms"
     )]
     pub show_with_all_rooms: Option<bool>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        rename = "expectedLastUpdate"
+    )]
+    pub expected_last_update: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EventNeighbors {
+    #[serde(rename = "precedingEvent")]
+    pub preceding_event: Option<Event>,
+    #[serde(rename = "subsequentEvent")]
+    pub subsequent_event: Option<Event>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EventStats {
+    #[serde(rename = "entryCount")]
+    pub entry_count: i64,
+    #[serde(rename = "cancelledCount")]
+    pub cancelled_count: i64,
+    #[serde(rename = "roomCount")]
+    pub room_count: i64,
+    #[serde(rename = "categoryCount")]
+    pub category_count: i64,
+    #[serde(rename = "totalProgrammeMinutes")]
+    pub total_programme_minutes: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RoomConflicts {
+    #[serde(rename = "roomId")]
+    pub room_id: Uuid,
+    #[serde(rename = "conflictingEntries")]
+    pub conflicting_entries: Vec<(Uuid, Uuid)>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EventValidationReport {
+    pub issues: Vec<ValidationIssue>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ValidationIssue {
+    pub severity: ValidationSeverity,
+    pub kind: ValidationIssueKind,
+    pub message: String,
+    /// Ids of the entries, rooms, categories or announcements affected by the issue
+    #[serde(rename = "entityIds")]
+    pub entity_ids: Vec<Uuid>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum ValidationSeverity {
+    #[serde(rename = "error")]
+    Error,
+    #[serde(rename = "warning")]
+    Warning,
+    #[serde(rename = "info")]
+    Info,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ValidationIssueKind {
+    #[serde(rename = "entry-outside-event-dates")]
+    EntryOutsideEventDates,
+    #[serde(rename = "exclusive-entry-conflict")]
+    ExclusiveEntryConflict,
+    #[serde(rename = "room-double-booking")]
+    RoomDoubleBooking,
+    #[serde(rename = "entry-without-title")]
+    EntryWithoutTitle,
+    #[serde(rename = "category-without-entries")]
+    CategoryWithoutEntries,
+    #[serde(rename = "announcement-outside-event-dates")]
+    AnnouncementOutsideEventDates,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -393,6 +756,56 @@ pub struct Passphrase {
     pub valid_from: Option<DateTime<Utc>>,
     #[serde(default, rename = "validUntil")]
     pub valid_until: Option<DateTime<Utc>>,
+    /// Point in time of the last successful authentication with this passphrase. Read-only.
+    #[serde(default, rename = "lastUsed", skip_serializing_if = "Option::is_none")]
+    pub last_used: Option<DateTime<Utc>>,
+}
+
+/// The role of a passphrase and the privileges, which are granted to users authenticated with it
+#[derive(Serialize, Deserialize)]
+pub struct PassphraseScope {
+    #[serde(rename = "passphraseId")]
+    pub passphrase_id: i32,
+    #[serde(rename = "eventId")]
+    pub event_id: i32,
+    pub role: AuthorizationRole,
+    pub privileges: Vec<Privilege>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Privilege {
+    #[serde(rename = "show-kuea-plan")]
+    ShowKueaPlan,
+    #[serde(rename = "show-config-area")]
+    ShowConfigArea,
+    #[serde(rename = "manage-entries")]
+    ManageEntries,
+    #[serde(rename = "submit-participant-entries")]
+    SubmitParticipantEntries,
+    #[serde(rename = "manage-categories")]
+    ManageCategories,
+    #[serde(rename = "manage-rooms")]
+    ManageRooms,
+    #[serde(rename = "list-rooms-of-multiple-events")]
+    ListRoomsOfMultipleEvents,
+    #[serde(rename = "edit-event-details")]
+    EditEventDetails,
+    #[serde(rename = "manage-passphrases")]
+    ManagePassphrases,
+    #[serde(rename = "manage-secure-passphrases")]
+    ManageSecurePassphrases,
+    #[serde(rename = "create-events")]
+    CreateEvents,
+    #[serde(rename = "delete-events")]
+    DeleteEvents,
+    #[serde(rename = "manage-announcements")]
+    ManageAnnouncements,
+    #[serde(rename = "show-kuea-plan-via-link")]
+    ShowKueaPlanViaLink,
+    #[serde(rename = "manage-api-keys")]
+    ManageApiKeys,
+    #[serde(rename = "manage-feed-tokens")]
+    ManageFeedTokens,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -0,0 +1,2 @@
+ALTER TABLE events
+    ADD COLUMN archived BOOLEAN NOT NULL DEFAULT FALSE;
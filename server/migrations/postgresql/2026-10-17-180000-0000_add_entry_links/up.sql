@@ -0,0 +1,2 @@
+ALTER TABLE entries
+    ADD COLUMN links JSONB;
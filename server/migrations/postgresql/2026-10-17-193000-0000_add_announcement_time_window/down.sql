@@ -0,0 +1,3 @@
+ALTER TABLE announcements
+    DROP COLUMN begin_time,
+    DROP COLUMN end_time;
@@ -0,0 +1,4 @@
+ALTER TABLE announcements
+    ADD COLUMN begin_time TIME NULL,
+    ADD COLUMN end_time TIME NULL,
+    ADD CONSTRAINT announcements_time_window CHECK (begin_time IS NULL OR end_time IS NULL OR begin_time <> end_time);
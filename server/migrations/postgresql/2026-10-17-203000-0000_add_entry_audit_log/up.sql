@@ -0,0 +1,13 @@
+CREATE TABLE entry_audit_log (
+    id SERIAL PRIMARY KEY,
+    entry_id UUID NOT NULL REFERENCES entries(id) ON DELETE CASCADE,
+    event_id INTEGER NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    action INTEGER NOT NULL,
+    passphrase_id INTEGER REFERENCES event_passphrases(id) ON DELETE SET NULL,
+    "timestamp" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    changed_fields VARCHAR[] NOT NULL DEFAULT '{}'
+);
+
+CREATE INDEX ON entry_audit_log (entry_id, "timestamp");
+
+comment on COLUMN entry_audit_log.passphrase_id is 'if NULL, the modification has been done via the command line interface or the passphrase has been deleted';
@@ -0,0 +1,3 @@
+ALTER TABLE events
+    DROP COLUMN begin_time,
+    DROP COLUMN end_time;
@@ -0,0 +1,6 @@
+ALTER TABLE events
+    ADD COLUMN begin_time TIME,
+    ADD COLUMN end_time TIME;
+
+comment on COLUMN events.begin_time is 'if not NULL, the event starts at this local time on its first (effective) day';
+comment on COLUMN events.end_time is 'if not NULL, the event ends at this local time on its last (effective) day';
@@ -0,0 +1,2 @@
+ALTER TABLE entries
+    ADD COLUMN is_tentative BOOL NOT NULL DEFAULT FALSE;
@@ -0,0 +1,6 @@
+-- Passphrases, for which only the hash is stored, cannot be used for login anymore after reverting
+-- this migration.
+ALTER TABLE event_passphrases
+    DROP COLUMN passphrase_hash;
+
+comment on COLUMN event_passphrases.passphrase is 'if NULL, this passphrase can only derived from another one';
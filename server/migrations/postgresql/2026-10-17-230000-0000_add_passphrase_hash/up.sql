@@ -0,0 +1,5 @@
+ALTER TABLE event_passphrases
+    ADD COLUMN passphrase_hash VARCHAR;
+
+comment on COLUMN event_passphrases.passphrase is 'plaintext passphrase, only kept for passphrases that are printed for participants. If NULL and passphrase_hash is NULL, this passphrase can only derived from another one';
+comment on COLUMN event_passphrases.passphrase_hash is 'argon2 hash of the passphrase in PHC string format';
@@ -0,0 +1,2 @@
+ALTER TABLE entries
+    ADD COLUMN orga_only BOOL NOT NULL DEFAULT FALSE;
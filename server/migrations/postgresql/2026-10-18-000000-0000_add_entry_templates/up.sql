@@ -0,0 +1,10 @@
+CREATE TABLE entry_templates (
+    id UUID PRIMARY KEY,
+    event_id INTEGER NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    name VARCHAR NOT NULL,
+    entry_data JSONB NOT NULL
+);
+
+CREATE INDEX ON entry_templates (event_id);
+
+comment on COLUMN entry_templates.entry_data is 'default values for the fields of new entries, without date and time';
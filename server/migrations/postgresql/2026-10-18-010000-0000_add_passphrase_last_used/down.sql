@@ -0,0 +1,2 @@
+ALTER TABLE event_passphrases
+    DROP COLUMN passphrase_last_used;
@@ -0,0 +1,4 @@
+ALTER TABLE event_passphrases
+    ADD COLUMN passphrase_last_used TIMESTAMP WITH TIME ZONE;
+
+comment on COLUMN event_passphrases.passphrase_last_used is 'point in time of the last successful authentication with this passphrase';
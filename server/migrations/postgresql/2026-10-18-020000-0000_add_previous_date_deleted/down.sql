@@ -0,0 +1,3 @@
+DELETE FROM previous_dates WHERE deleted;
+ALTER TABLE previous_dates
+    DROP COLUMN deleted;
@@ -0,0 +1,2 @@
+ALTER TABLE previous_dates
+    ADD COLUMN deleted BOOLEAN NOT NULL DEFAULT FALSE;
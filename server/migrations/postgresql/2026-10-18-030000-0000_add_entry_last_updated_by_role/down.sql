@@ -0,0 +1,2 @@
+ALTER TABLE entries
+    DROP COLUMN last_updated_by_role;
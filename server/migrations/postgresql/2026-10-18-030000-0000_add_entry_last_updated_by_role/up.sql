@@ -0,0 +1,2 @@
+ALTER TABLE entries
+    ADD COLUMN last_updated_by_role INTEGER;
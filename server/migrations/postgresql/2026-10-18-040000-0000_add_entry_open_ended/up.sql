@@ -0,0 +1,2 @@
+ALTER TABLE entries
+    ADD COLUMN open_ended BOOL NOT NULL DEFAULT FALSE;
@@ -0,0 +1,11 @@
+CREATE TABLE api_keys (
+    id SERIAL PRIMARY KEY,
+    event_id INTEGER NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    key_hash VARCHAR NOT NULL UNIQUE,
+    comment VARCHAR NOT NULL DEFAULT '',
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX ON api_keys (event_id);
+
+comment on COLUMN api_keys.key_hash is 'hex-encoded SHA-256 hash of the api key';
@@ -0,0 +1,12 @@
+CREATE TABLE feed_tokens (
+    id SERIAL PRIMARY KEY,
+    event_id INTEGER NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    privilege INTEGER NOT NULL,
+    comment VARCHAR NOT NULL DEFAULT '',
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    revoked BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX ON feed_tokens (event_id);
+
+comment on TABLE feed_tokens is 'revocable tokens for subscribing to the calendar feeds (iCal, Frab XML) of an event. The token string itself is signed with the application secret and not stored.';
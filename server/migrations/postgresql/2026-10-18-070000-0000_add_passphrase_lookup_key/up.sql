@@ -0,0 +1,13 @@
+ALTER TABLE event_passphrases
+    ADD COLUMN passphrase_lookup_key INTEGER;
+
+-- Must match `calculate_passphrase_lookup_key()` in the PostgreSQL data store implementation
+UPDATE event_passphrases
+    SET passphrase_lookup_key =
+        get_byte(sha256(convert_to('kueaplan-passphrase-lookup:' || passphrase, 'UTF8')), 0) * 256
+        + get_byte(sha256(convert_to('kueaplan-passphrase-lookup:' || passphrase, 'UTF8')), 1)
+    WHERE passphrase IS NOT NULL;
+
+CREATE INDEX ON event_passphrases (event_id, passphrase_lookup_key);
+
+comment on COLUMN event_passphrases.passphrase_lookup_key is 'first 16 bits of the SHA-256 hash of the passphrase, for narrowing down the candidates for verifying the passphrase_hash. NULL if not yet known (for passphrases hashed before this column was added).';
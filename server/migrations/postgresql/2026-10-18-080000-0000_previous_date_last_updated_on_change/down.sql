@@ -0,0 +1,6 @@
+DROP TRIGGER sync_lastmod ON previous_dates;
+CREATE TRIGGER sync_lastmod
+    BEFORE UPDATE ON previous_dates
+    FOR EACH ROW EXECUTE PROCEDURE sync_lastmod();
+
+DROP FUNCTION sync_lastmod_if_changed;
@@ -0,0 +1,15 @@
+--- only update `last_updated` of previous dates when they are actually changed, so that it can be used for finding the most recent previous date of an entry ---
+CREATE FUNCTION sync_lastmod_if_changed() RETURNS trigger AS $$
+BEGIN
+    IF NEW IS DISTINCT FROM OLD THEN
+        NEW.last_updated := NOW();
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER sync_lastmod ON previous_dates;
+CREATE TRIGGER sync_lastmod
+    BEFORE UPDATE ON previous_dates
+    FOR EACH ROW EXECUTE PROCEDURE sync_lastmod_if_changed();
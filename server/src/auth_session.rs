@@ -7,7 +7,14 @@
 //! been provided by this client in the past. Use
 //! [crate::data_store::KueaPlanStoreFacade::get_auth_token_for_session] to get the user roles and
 //! privileges implied by these passphrases, represented as a [crate::data_store::auth_token::AuthToken].
-use crate::data_store::PassphraseId;
+//!
+//! Additionally, a SessionToken may carry an API key, provided by an API client via the
+//! `Authorization: Bearer` header. The API key is only kept for the current request and never
+//! serialized into the token string.
+//!
+//! For subscribing to calendar feeds, a [FeedAccessToken] can be used instead. It refers to a
+//! revocable feed token in the database and does not expire.
+use crate::data_store::{EventId, FeedTokenId, PassphraseId};
 use base64::{DecodeError, Engine};
 use ring::hmac::Key;
 
@@ -15,10 +22,11 @@ static HMAC_ALGORITHM: ring::hmac::Algorithm = ring::hmac::HMAC_SHA256;
 const KEY_LENGTH: usize = 512 / 8;
 
 /// Client authorization state, represented as a list of database ids of passphrases that have
-/// been provided by the client
-#[derive(Debug)]
+/// been provided by the client and an optional API key
+#[derive(Debug, Clone)]
 pub struct SessionToken {
     authorized_passphrases: Vec<PassphraseId>,
+    api_key: Option<String>,
 }
 
 impl SessionToken {
@@ -26,6 +34,7 @@ impl SessionToken {
     pub fn new() -> Self {
         SessionToken {
             authorized_passphrases: vec![],
+            api_key: None,
         }
     }
 
@@ -49,6 +58,20 @@ impl SessionToken {
         &self.authorized_passphrases
     }
 
+    /// Attach the (unchecked) API key, provided by the client, to the SessionToken. It is
+    /// validated by the data store when retrieving the client's
+    /// [crate::data_store::auth_token::AuthToken].
+    ///
+    /// The API key is not included in the serialized SessionToken (see [as_string]).
+    pub fn set_api_key(&mut self, api_key: String) {
+        self.api_key = Some(api_key);
+    }
+
+    /// Get the API key, provided by the client, if any.
+    pub fn get_api_key(&self) -> Option<&str> {
+        self.api_key.as_deref()
+    }
+
     /// Serialize the client authorization state as a temper-proof string to be sent to the client.
     ///
     /// The result string is a base64-encoded binary string, composed of an HMAC signature of the
@@ -83,25 +106,27 @@ impl SessionToken {
     /// This function validates that
     /// * the string is valid base64 data
     /// * the length of the binary data (after decoding from base64) matches the expected structure
-    /// * the HMAC signature in the data is valid, assuming it has been generated with the provided
-    ///   `secret`.
+    /// * the HMAC signature in the data is valid, assuming it has been generated with one of the
+    ///   provided `secrets`.
     /// * the timestamp in the data is at least `max_age` old, compared to the current system clock
     ///   time.
     ///
     /// If any of those validations fail, a [SessionError] is returned, accordingly, instead of a
     /// SessionToken.
     ///
-    /// *Note*: After changing the `secret`, all serialized session tokens will fail the HMAC
-    /// validation!
-    pub fn from_string(
+    /// *Note*: After changing the secret, all serialized session tokens will fail the HMAC
+    /// validation, unless the previous secret is still included in `secrets`. This allows a
+    /// graceful rotation of the secret: New tokens are signed with the new secret (see
+    /// [as_string]), while tokens, signed with the previous secret, are still accepted during a
+    /// transition period.
+    pub fn from_string<S: AsRef<str>>(
         data: &str,
-        secret: &str,
+        secrets: &[S],
         max_age: std::time::Duration,
     ) -> Result<Self, SessionError> {
         let tag_len = HMAC_ALGORITHM.digest_algorithm().output_len();
         let timestamp_len = std::mem::size_of::<u64>();
         let passphrase_id_len = std::mem::size_of::<PassphraseId>();
-        let key = derive_key_from_secret(secret);
 
         let binary_data = base64::engine::general_purpose::STANDARD.decode(data)?;
         if binary_data.len() < tag_len + timestamp_len {
@@ -112,7 +137,9 @@ impl SessionToken {
         }
         let msg = &binary_data[tag_len..];
         let tag = &binary_data[0..tag_len];
-        if ring::hmac::verify(&key, msg, tag).is_err() {
+        if !secrets.iter().any(|secret| {
+            ring::hmac::verify(&derive_key_from_secret(secret.as_ref()), msg, tag).is_ok()
+        }) {
             return Err(SessionError::SignatureVerificationFailed);
         }
         let timestamp = std::time::UNIX_EPOCH
@@ -141,10 +168,129 @@ impl SessionToken {
 
         Ok(Self {
             authorized_passphrases,
+            api_key: None,
         })
     }
 }
 
+/// Calendar feed authorization, represented by the database id of a feed token (see
+/// [crate::data_store::KueaPlanStoreFacade::create_feed_token]) and the event it belongs to.
+///
+/// In contrast to the [SessionToken], the serialized string does not contain a timestamp and thus
+/// does not expire. Instead, the feed token can be revoked in the database. Use
+/// [crate::data_store::KueaPlanStoreFacade::get_auth_token_for_feed_token] to get the privileges
+/// granted by the feed token.
+#[derive(Debug, PartialEq, Eq)]
+pub struct FeedAccessToken {
+    event_id: EventId,
+    feed_token_id: FeedTokenId,
+}
+
+/// Prefix of serialized [FeedAccessToken]s to distinguish them from serialized [SessionToken]s.
+/// The standard base64 alphabet, used for SessionTokens, does not contain a '-'.
+const FEED_TOKEN_PREFIX: &str = "feed-";
+/// Domain separation for the HMAC signature, such that a signature of a FeedAccessToken can never
+/// be a valid signature for a SessionToken and vice versa.
+const FEED_TOKEN_SIGNATURE_DOMAIN: &[u8] = b"kueaplan-feed-token";
+
+impl FeedAccessToken {
+    /// Create a FeedAccessToken for the feed token with the given id.
+    ///
+    /// **This function should only be called after creating the feed token in the data store!**
+    pub fn new(event_id: EventId, feed_token_id: FeedTokenId) -> Self {
+        Self {
+            event_id,
+            feed_token_id,
+        }
+    }
+
+    pub fn get_event_id(&self) -> EventId {
+        self.event_id
+    }
+
+    pub fn get_feed_token_id(&self) -> FeedTokenId {
+        self.feed_token_id
+    }
+
+    /// Check if the given client-provided string looks like a serialized FeedAccessToken (in
+    /// contrast to a serialized SessionToken). This does not validate the token.
+    pub fn is_feed_token(data: &str) -> bool {
+        data.starts_with(FEED_TOKEN_PREFIX)
+    }
+
+    /// Serialize the FeedAccessToken as a temper-proof string to be handed out to the subscriber.
+    ///
+    /// The result string is the [FEED_TOKEN_PREFIX], followed by the url-safe base64-encoded HMAC
+    /// signature and the binary event id and feed token id.
+    ///
+    /// The `secret` is used for key derivation for the HMAC algorithm, like in
+    /// [SessionToken::as_string].
+    pub fn as_string(&self, secret: &str) -> String {
+        let key = derive_key_from_secret(secret);
+        let msg = self.message();
+        let tag = ring::hmac::sign(&key, &Self::signed_data(&msg));
+        let mut result: Vec<u8> = tag.as_ref().into();
+        result.extend(msg);
+        format!(
+            "{}{}",
+            FEED_TOKEN_PREFIX,
+            base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(&result)
+        )
+    }
+
+    /// Deserialize and validate a serialized FeedAccessToken, as generated by [as_string].
+    ///
+    /// Like [SessionToken::from_string], this validates the encoding, the structure and the HMAC
+    /// signature against all of the given `secrets`. It does not check whether the feed token has
+    /// been revoked.
+    pub fn from_string<S: AsRef<str>>(data: &str, secrets: &[S]) -> Result<Self, SessionError> {
+        let tag_len = HMAC_ALGORITHM.digest_algorithm().output_len();
+        let id_len = std::mem::size_of::<EventId>();
+
+        let encoded_data = data
+            .strip_prefix(FEED_TOKEN_PREFIX)
+            .ok_or(SessionError::InvalidTokenStructure)?;
+        let binary_data = base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(encoded_data)?;
+        if binary_data.len() != tag_len + 2 * id_len {
+            return Err(SessionError::InvalidTokenStructure);
+        }
+        let msg = &binary_data[tag_len..];
+        let tag = &binary_data[0..tag_len];
+        let signed_data = Self::signed_data(msg);
+        if !secrets.iter().any(|secret| {
+            ring::hmac::verify(&derive_key_from_secret(secret.as_ref()), &signed_data, tag).is_ok()
+        }) {
+            return Err(SessionError::SignatureVerificationFailed);
+        }
+
+        let (event_id_bytes, feed_token_id_bytes) = msg.split_at(id_len);
+        Ok(Self {
+            event_id: EventId::from_le_bytes(
+                event_id_bytes
+                    .try_into()
+                    .expect("We should have checked the message length before."),
+            ),
+            feed_token_id: FeedTokenId::from_le_bytes(
+                feed_token_id_bytes
+                    .try_into()
+                    .expect("We should have checked the message length before."),
+            ),
+        })
+    }
+
+    fn message(&self) -> Vec<u8> {
+        let mut msg: Vec<u8> = self.event_id.to_le_bytes().into();
+        msg.extend(self.feed_token_id.to_le_bytes());
+        msg
+    }
+
+    fn signed_data(msg: &[u8]) -> Vec<u8> {
+        let mut data: Vec<u8> = FEED_TOKEN_SIGNATURE_DOMAIN.into();
+        data.extend(msg);
+        data
+    }
+}
+
 /// Error type to be used when a session operation fails, especially for [SessionToken::from_string]
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
 pub enum SessionError {
@@ -199,7 +345,7 @@ mod tests {
     fn empty_session() {
         const SECRET: &str = "abcdef";
         let session_token_str = SessionToken::new().as_string(SECRET);
-        let decoded_token = SessionToken::from_string(&session_token_str, SECRET, MAX_AGE)
+        let decoded_token = SessionToken::from_string(&session_token_str, &[SECRET], MAX_AGE)
             .expect("Session token should be valid");
         let expected: &[PassphraseId] = &[];
         assert_eq!(decoded_token.get_passphrase_ids(), expected);
@@ -212,7 +358,7 @@ mod tests {
         token.add_authorization(314);
         token.add_authorization(1024);
         let session_token_str = token.as_string(SECRET);
-        let decoded_token = SessionToken::from_string(&session_token_str, SECRET, MAX_AGE)
+        let decoded_token = SessionToken::from_string(&session_token_str, &[SECRET], MAX_AGE)
             .expect("Session token should be valid");
         assert_eq!(decoded_token.get_passphrase_ids(), &[314, 1024]);
     }
@@ -224,7 +370,7 @@ mod tests {
         let mut token = SessionToken::new();
         token.add_authorization(314);
         let session_token_str = token.as_string(SECRET1);
-        let result = SessionToken::from_string(&session_token_str, SECRET2, MAX_AGE);
+        let result = SessionToken::from_string(&session_token_str, &[SECRET2], MAX_AGE);
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err(),
@@ -232,6 +378,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rotated_secret() {
+        const OLD_SECRET: &str = "abcdef";
+        const NEW_SECRET: &str = "abcdff";
+        let secrets = [NEW_SECRET, OLD_SECRET];
+        let mut token = SessionToken::new();
+        token.add_authorization(314);
+
+        // Tokens signed with the previous secret are still valid
+        let old_session_token_str = token.as_string(OLD_SECRET);
+        let decoded_token = SessionToken::from_string(&old_session_token_str, &secrets, MAX_AGE)
+            .expect("Session token signed with previous secret should be valid");
+        assert_eq!(decoded_token.get_passphrase_ids(), &[314]);
+
+        // New tokens are signed with the new secret
+        let new_session_token_str = decoded_token.as_string(secrets[0]);
+        SessionToken::from_string(&new_session_token_str, &[NEW_SECRET], MAX_AGE)
+            .expect("Session token should be valid with new secret");
+        assert_eq!(
+            SessionToken::from_string(&new_session_token_str, &[OLD_SECRET], MAX_AGE).unwrap_err(),
+            SessionError::SignatureVerificationFailed
+        );
+    }
+
     #[test]
     fn tempered_token_added_auth() {
         const SECRET: &str = "abcdef";
@@ -246,7 +416,7 @@ mod tests {
         data.extend(&315i32.to_le_bytes());
 
         let tempered_session_token_str = base64::engine::general_purpose::STANDARD.encode(data);
-        let result = SessionToken::from_string(&tempered_session_token_str, SECRET, MAX_AGE);
+        let result = SessionToken::from_string(&tempered_session_token_str, &[SECRET], MAX_AGE);
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err(),
@@ -270,7 +440,7 @@ mod tests {
         data.extend(&315i32.to_le_bytes());
 
         let tempered_session_token_str = base64::engine::general_purpose::STANDARD.encode(data);
-        let result = SessionToken::from_string(&tempered_session_token_str, SECRET, MAX_AGE);
+        let result = SessionToken::from_string(&tempered_session_token_str, &[SECRET], MAX_AGE);
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err(),
@@ -278,6 +448,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn feed_token() {
+        const SECRET: &str = "abcdef";
+        let token_str = FeedAccessToken::new(42, 1337).as_string(SECRET);
+        assert!(FeedAccessToken::is_feed_token(&token_str));
+        assert!(!FeedAccessToken::is_feed_token(
+            &SessionToken::new().as_string(SECRET)
+        ));
+        let decoded_token = FeedAccessToken::from_string(&token_str, &[SECRET])
+            .expect("Feed token should be valid");
+        assert_eq!(decoded_token.get_event_id(), 42);
+        assert_eq!(decoded_token.get_feed_token_id(), 1337);
+
+        assert_eq!(
+            FeedAccessToken::from_string(&token_str, &["abcdff"]).unwrap_err(),
+            SessionError::SignatureVerificationFailed
+        );
+    }
+
+    #[test]
+    fn tempered_feed_token() {
+        const SECRET: &str = "abcdef";
+        let token_str = FeedAccessToken::new(42, 1337).as_string(SECRET);
+        let mut data = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(token_str.strip_prefix(FEED_TOKEN_PREFIX).unwrap())
+            .expect("data should be base64-decodable");
+        // replacing the 1337 with a 1338
+        data.truncate(data.len() - 4);
+        data.extend(&1338i32.to_le_bytes());
+        let tempered_token_str = format!(
+            "{}{}",
+            FEED_TOKEN_PREFIX,
+            base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(data)
+        );
+        assert_eq!(
+            FeedAccessToken::from_string(&tempered_token_str, &[SECRET]).unwrap_err(),
+            SessionError::SignatureVerificationFailed
+        );
+        assert_eq!(
+            FeedAccessToken::from_string("feed-abc", &[SECRET]).unwrap_err(),
+            SessionError::InvalidTokenStructure
+        );
+    }
+
     #[test]
     fn expired_token() {
         const SECRET: &str = "abcdef";
@@ -287,7 +501,7 @@ mod tests {
         std::thread::sleep(std::time::Duration::from_millis(150));
         let result = SessionToken::from_string(
             &session_token_str,
-            SECRET,
+            &[SECRET],
             std::time::Duration::from_millis(100),
         );
         assert!(result.is_err());
@@ -2,11 +2,13 @@ use crate::cli::{CliAuthTokenKey, EventIdOrSlug};
 use crate::cli_error::CliError;
 use crate::data_store::auth_token::{AuthToken, GlobalAuthToken};
 use crate::data_store::models::EventWithContents;
-use crate::data_store::{CategoryId, RoomId, models};
-use crate::data_store::{EntryFilter, KuaPlanStore, get_store_from_env};
+use crate::data_store::{CategoryId, EventId, RoomId, StoreError, models};
+use crate::data_store::{EntryFilter, KuaPlanStore, KueaPlanStoreFacade, get_store_from_env};
+use crate::web::time_calculation::is_within_event_dates;
+use icalendar::{Component, EventLike};
 use kueaplan_api_types::{Announcement, Category, Entry, ExtendedEvent, Room};
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs::File;
 use std::io::{BufReader, BufWriter};
 use std::path::PathBuf;
@@ -22,15 +24,106 @@ struct SavedEvent {
     announcements: Vec<Announcement>,
 }
 
-pub fn load_event_from_file(path: &PathBuf, generate_new_uuids: bool) -> Result<(), CliError> {
-    let data_store_pool = get_store_from_env()?;
-    let mut data_store = data_store_pool.get_facade()?;
+/// Behaviour of the event import, when an imported room, category, entry or announcement has the
+/// same id as an existing one
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum ImportConflictMode {
+    /// Leave the existing element untouched and skip the imported one, as well as all imported
+    /// entries and announcements referencing it
+    Skip,
+    /// Abort the import without any changes
+    Fail,
+}
 
+impl From<ImportConflictMode> for models::ImportConflictMode {
+    fn from(value: ImportConflictMode) -> Self {
+        match value {
+            ImportConflictMode::Skip => Self::Skip,
+            ImportConflictMode::Fail => Self::Fail,
+        }
+    }
+}
+
+/// Import an event with all its contents from the given JSON file, as written by
+/// [export_event_to_file].
+///
+/// The file contents are validated with [validate_import_data] before importing. If `dry_run` is
+/// true, only a summary of the file contents and the validation result is printed, without writing
+/// to the database. If `preview` is true, the differences to the existing event with the same slug
+/// are printed (see [compute_import_diff]), without writing to the database. `on_conflict` defines
+/// the behaviour for elements with the id of an existing one, which is only possible if the UUIDs
+/// are not regenerated.
+pub fn load_event_from_file(
+    path: &PathBuf,
+    generate_new_uuids: bool,
+    dry_run: bool,
+    preview: bool,
+    on_conflict: ImportConflictMode,
+) -> Result<(), CliError> {
     let f = File::open(path).map_err(|e| {
         CliError::FileError(format!("Could not open {:?} for reading: {}", path, e))
     })?;
     let mut data: SavedEvent = serde_json::from_reader(BufReader::new(f))?;
 
+    let validation_errors = validate_import_data(&data);
+    if dry_run {
+        println!(
+            "Event '{}' ({} – {}) with {} entries, {} rooms, {} categories and {} announcements",
+            data.event.basic_data.title,
+            data.event.basic_data.begin_date,
+            data.event.basic_data.end_date,
+            data.entries.len(),
+            data.rooms.len(),
+            data.categories.len(),
+            data.announcements.len()
+        );
+        for error in validation_errors.iter() {
+            println!("Error: {}", error);
+        }
+        if !validation_errors.is_empty() {
+            return Err(CliError::DataError(format!(
+                "{} validation error(s) found",
+                validation_errors.len()
+            )));
+        }
+        println!("Data is valid. Nothing has been imported (dry run).");
+        return Ok(());
+    }
+    if !validation_errors.is_empty() {
+        return Err(CliError::DataError(validation_errors.join("; ")));
+    }
+
+    let data_store_pool = get_store_from_env()?;
+    let mut data_store = data_store_pool.get_facade()?;
+
+    if preview {
+        let existing_event = match &data.event.basic_data.slug {
+            Some(slug) => match data_store.get_event_by_slug(slug) {
+                Ok(event) => Some(event),
+                Err(StoreError::NotExisting) => None,
+                Err(e) => return Err(e.into()),
+            },
+            None => None,
+        };
+        let existing_data = match existing_event {
+            Some(event) => {
+                println!(
+                    "Comparing with existing event '{}' (id {}).",
+                    event.title, event.id
+                );
+                Some(load_saved_event(&mut *data_store, event.id)?)
+            }
+            None => {
+                println!("No existing event with the same slug found. All data would be created.");
+                None
+            }
+        };
+        let diff = compute_import_diff(&data, existing_data.as_ref());
+        print_import_diff(&diff);
+        println!("Nothing has been imported (preview).");
+        return Ok(());
+    }
+
     if generate_new_uuids {
         regenerate_uuids(&mut data)?;
     }
@@ -62,7 +155,8 @@ pub fn load_event_from_file(path: &PathBuf, generate_new_uuids: bool) -> Result<
             .collect(),
     };
 
-    let event_id = data_store.import_event_with_contents(&admin_auth_token, store_data)?;
+    let event_id =
+        data_store.import_event_with_contents(&admin_auth_token, store_data, on_conflict.into())?;
 
     println!("Event imported successfully with id {}.", event_id);
 
@@ -84,10 +178,28 @@ pub fn export_event_to_file(
         }
     };
 
+    let data = load_saved_event(&mut *data_store, event_id)?;
+
+    let f = File::create(path).map_err(|e| {
+        CliError::FileError(format!(
+            "Could not create or open {:?} for writing: {}",
+            path, e
+        ))
+    })?;
+    serde_json::to_writer(BufWriter::new(f), &data)?;
+
+    Ok(())
+}
+
+/// Load the event with all its contents from the database, in the format of the export file
+fn load_saved_event(
+    data_store: &mut dyn KueaPlanStoreFacade,
+    event_id: EventId,
+) -> Result<SavedEvent, CliError> {
     let auth_key = CliAuthTokenKey::new();
     let auth_token = AuthToken::create_for_cli(event_id, &auth_key);
 
-    let data = SavedEvent {
+    Ok(SavedEvent {
         event: data_store.get_extended_event(&auth_token, event_id)?.into(),
         entries: data_store
             .get_published_entries_filtered(&auth_token, event_id, EntryFilter::default())?
@@ -109,17 +221,242 @@ pub fn export_event_to_file(
             .into_iter()
             .map(|a| a.into())
             .collect(),
+    })
+}
+
+/// Differences between the entities of one type (e.g. rooms) in an import file and the database
+#[derive(Default, Debug)]
+pub struct EntityDiff {
+    /// Entities which are only present in the import file
+    pub created: Vec<DiffItem>,
+    /// Entities which are present in both, the import file and the database, but differ
+    pub updated: Vec<DiffItem>,
+    /// Entities which are only present in the database
+    pub orphaned: Vec<DiffItem>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct DiffItem {
+    pub id: Uuid,
+    pub title: String,
+    /// Names of the changed fields (as in the JSON file format); only filled for updated entities
+    pub changed_fields: Vec<String>,
+}
+
+/// Differences between the contents of an import file and an existing event in the database, as
+/// calculated by [compute_import_diff]
+#[derive(Default, Debug)]
+pub struct ImportDiff {
+    pub rooms: EntityDiff,
+    pub categories: EntityDiff,
+    pub entries: EntityDiff,
+}
+
+/// Compare the rooms, categories and entries of the import file `data` to the ones of the
+/// `existing` event from the database (as loaded by [load_saved_event]), identifying them by their
+/// UUIDs. If `existing` is None, all entities are considered to be created.
+fn compute_import_diff(data: &SavedEvent, existing: Option<&SavedEvent>) -> ImportDiff {
+    ImportDiff {
+        rooms: compute_entity_diff(&data.rooms, existing.map_or(&[], |e| &e.rooms), |r| {
+            (r.id, &r.title)
+        }),
+        categories: compute_entity_diff(
+            &data.categories,
+            existing.map_or(&[], |e| &e.categories),
+            |c| (c.id, &c.title),
+        ),
+        entries: compute_entity_diff(&data.entries, existing.map_or(&[], |e| &e.entries), |e| {
+            (e.id, &e.title)
+        }),
+    }
+}
+
+fn compute_entity_diff<T: Serialize>(
+    new: &[T],
+    existing: &[T],
+    id_and_title: impl Fn(&T) -> (Uuid, &String),
+) -> EntityDiff {
+    let existing_by_id: BTreeMap<Uuid, &T> =
+        existing.iter().map(|e| (id_and_title(e).0, e)).collect();
+    let new_ids: BTreeSet<Uuid> = new.iter().map(|e| id_and_title(e).0).collect();
+    let mut result = EntityDiff::default();
+    for entity in new {
+        let (id, title) = id_and_title(entity);
+        let item = |changed_fields| DiffItem {
+            id,
+            title: title.clone(),
+            changed_fields,
+        };
+        match existing_by_id.get(&id) {
+            None => result.created.push(item(vec![])),
+            Some(existing_entity) => {
+                let changed_fields = changed_fields(entity, *existing_entity);
+                if !changed_fields.is_empty() {
+                    result.updated.push(item(changed_fields));
+                }
+            }
+        }
+    }
+    for entity in existing {
+        let (id, title) = id_and_title(entity);
+        if !new_ids.contains(&id) {
+            result.orphaned.push(DiffItem {
+                id,
+                title: title.clone(),
+                changed_fields: vec![],
+            });
+        }
+    }
+    result
+}
+
+/// Get the names of all fields, whose values differ between the JSON representation of `a` and `b`
+fn changed_fields<T: Serialize>(a: &T, b: &T) -> Vec<String> {
+    let (Ok(serde_json::Value::Object(a)), Ok(serde_json::Value::Object(b))) =
+        (serde_json::to_value(a), serde_json::to_value(b))
+    else {
+        return vec![];
     };
+    let keys: BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+    keys.into_iter()
+        .filter(|key| a.get(*key) != b.get(*key))
+        .cloned()
+        .collect()
+}
 
-    let f = File::create(path).map_err(|e| {
-        CliError::FileError(format!(
-            "Could not create or open {:?} for writing: {}",
-            path, e
-        ))
-    })?;
-    serde_json::to_writer(BufWriter::new(f), &data)?;
+fn print_import_diff(diff: &ImportDiff) {
+    for (name, entity_diff) in [
+        ("Rooms", &diff.rooms),
+        ("Categories", &diff.categories),
+        ("Entries", &diff.entries),
+    ] {
+        println!(
+            "{}: {} to be created, {} to be updated, {} orphaned",
+            name,
+            entity_diff.created.len(),
+            entity_diff.updated.len(),
+            entity_diff.orphaned.len()
+        );
+        for item in entity_diff.created.iter() {
+            println!("  + {} {}", item.id, item.title);
+        }
+        for item in entity_diff.updated.iter() {
+            println!(
+                "  ~ {} {} (changed: {})",
+                item.id,
+                item.title,
+                item.changed_fields.join(", ")
+            );
+        }
+        for item in entity_diff.orphaned.iter() {
+            println!("  - {} {}", item.id, item.title);
+        }
+    }
+}
 
-    Ok(())
+/// Check the consistency of event data to be imported, without accessing the database: All
+/// references to rooms and categories must be resolvable within the data, date ranges must not be
+/// reversed and titles must not be empty.
+///
+/// Returns a list of human-readable descriptions of all problems found.
+fn validate_import_data(data: &SavedEvent) -> Vec<String> {
+    let mut errors = Vec::new();
+    let room_ids: BTreeSet<RoomId> = data.rooms.iter().map(|r| r.id).collect();
+    let category_ids: BTreeSet<CategoryId> = data.categories.iter().map(|c| c.id).collect();
+
+    let event = &data.event.basic_data;
+    if event.title.is_empty() {
+        errors.push("Title of event is empty".to_owned());
+    }
+    if event.end_date < event.begin_date {
+        errors.push("End date of event is before its begin date".to_owned());
+    }
+    if let Err(e) = data.event.timezone.parse::<chrono_tz::Tz>() {
+        errors.push(format!("Could not parse event's timezone: {}", e));
+    }
+    for room in data.rooms.iter() {
+        if room.title.is_empty() {
+            errors.push(format!("Title of room {} is empty", room.id));
+        }
+    }
+    for category in data.categories.iter() {
+        if category.title.is_empty() {
+            errors.push(format!("Title of category {} is empty", category.id));
+        }
+    }
+    for entry in data.entries.iter() {
+        if entry.title.is_empty() {
+            errors.push(format!("Title of entry {} is empty", entry.id));
+        }
+        if entry.end < entry.begin {
+            errors.push(format!("End of entry {} is before its begin", entry.id));
+        }
+        if !category_ids.contains(&entry.category) {
+            errors.push(format!(
+                "Category {} of entry {} does not exist",
+                entry.category, entry.id
+            ));
+        }
+        for entry_room in entry.room.iter() {
+            if !room_ids.contains(entry_room) {
+                errors.push(format!(
+                    "Room {} of entry {} does not exist",
+                    entry_room, entry.id
+                ));
+            }
+        }
+        for link in entry.links.iter() {
+            if !models::EntryLink::is_valid_url(&link.url) {
+                errors.push(format!(
+                    "Link URL '{}' of entry {} is not a valid http(s) URL",
+                    link.url, entry.id
+                ));
+            }
+        }
+        for previous_date in entry.previous_dates.iter() {
+            if previous_date.end < previous_date.begin {
+                errors.push(format!(
+                    "End of previous date {} of entry {} is before its begin",
+                    previous_date.id, entry.id
+                ));
+            }
+            for previous_date_room in previous_date.room.iter() {
+                if !room_ids.contains(previous_date_room) {
+                    errors.push(format!(
+                        "Room {} of previous date {} of entry {} does not exist",
+                        previous_date_room, previous_date.id, entry.id
+                    ));
+                }
+            }
+        }
+    }
+    for announcement in data.announcements.iter() {
+        if let (Some(begin_date), Some(end_date)) = (announcement.begin_date, announcement.end_date)
+            && end_date < begin_date
+        {
+            errors.push(format!(
+                "End date of announcement {} is before its begin date",
+                announcement.id
+            ));
+        }
+        for announcement_category in announcement.categories.iter() {
+            if !category_ids.contains(announcement_category) {
+                errors.push(format!(
+                    "Category {} of announcement {} does not exist",
+                    announcement_category, announcement.id
+                ));
+            }
+        }
+        for announcement_room in announcement.rooms.iter() {
+            if !room_ids.contains(announcement_room) {
+                errors.push(format!(
+                    "Room {} of announcement {} does not exist",
+                    announcement_room, announcement.id
+                ));
+            }
+        }
+    }
+    errors
 }
 
 fn regenerate_uuids(event_data: &mut SavedEvent) -> Result<(), CliError> {
@@ -187,3 +524,335 @@ fn regenerate_uuids(event_data: &mut SavedEvent) -> Result<(), CliError> {
 
     Ok(())
 }
+
+/// Namespace for deriving the (UUIDv5) ids of entries imported from iCalendar files from the
+/// event id and the VEVENT's UID
+const ICS_IMPORT_ID_NAMESPACE: Uuid = uuid::uuid!("2242767a-d158-4053-afb8-3e57c06f4af9");
+
+/// Create entries in the given event from the VEVENTs of the given iCalendar (ICS) file, e.g. to
+/// take over a draft schedule from a calendar app.
+///
+/// All entries are created as drafts in the given category, in a single transaction. VEVENTs,
+/// which cannot be converted or lie outside of the event's date range, are skipped with a warning
+/// (see [entries_from_ics]). The entry ids are derived from the VEVENTs' UIDs, so importing the
+/// same file again updates the previously imported entries instead of duplicating them.
+pub fn import_entries_from_ics_file(
+    event_id_or_slug: EventIdOrSlug,
+    path: &PathBuf,
+    category_id: CategoryId,
+) -> Result<(), CliError> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        CliError::FileError(format!("Could not open {:?} for reading: {}", path, e))
+    })?;
+
+    let data_store_pool = get_store_from_env()?;
+    let mut data_store = data_store_pool.get_facade()?;
+
+    let event_id = match event_id_or_slug {
+        EventIdOrSlug::Id(event_id) => event_id,
+        EventIdOrSlug::Slug(event_slug) => data_store.get_event_by_slug(&event_slug)?.id,
+    };
+    let auth_key = CliAuthTokenKey::new();
+    let auth_token = AuthToken::create_for_cli(event_id, &auth_key);
+    let event = data_store.get_extended_event(&auth_token, event_id)?;
+    if !data_store
+        .get_categories(&auth_token, event_id)?
+        .iter()
+        .any(|c| c.id == category_id)
+    {
+        return Err(CliError::DataError(format!(
+            "Category {} does not exist in event {}",
+            category_id, event.basic_data.title
+        )));
+    }
+
+    let (entries, warnings) = entries_from_ics(&content, &event, category_id)?;
+    for warning in warnings.iter() {
+        println!("Warning: {}", warning);
+    }
+    let count = entries.len();
+    let (created, updated) = data_store.import_entries(&auth_token, event_id, entries)?;
+    println!(
+        "Imported {} entries into {}: {} created, {} updated, {} skipped (deleted).",
+        count,
+        event.basic_data.title,
+        created,
+        updated,
+        count - created - updated
+    );
+
+    Ok(())
+}
+
+/// Convert the VEVENTs of the given iCalendar file content into new draft entries of the event in
+/// the given category.
+///
+/// SUMMARY is used as the entry's title, DESCRIPTION as its description and LOCATION as its room
+/// comment. DTSTART and DTEND are converted to UTC, using the event's timezone for floating times.
+/// The entry id is a UUIDv5 of the event id and the UID. VEVENTs without title, without DTEND,
+/// with all-day dates or outside of the event's date range are skipped. VEVENTs without UID get a
+/// random id, so they are duplicated when importing the file again.
+///
+/// Returns the new entries and a warning message for each skipped VEVENT.
+fn entries_from_ics(
+    content: &str,
+    event: &models::ExtendedEvent,
+    category_id: CategoryId,
+) -> Result<(Vec<models::FullNewEntry>, Vec<String>), CliError> {
+    let calendar: icalendar::Calendar = content
+        .parse()
+        .map_err(|e| CliError::DataError(format!("Could not parse iCalendar file: {}", e)))?;
+
+    let mut entries = Vec::new();
+    let mut warnings = Vec::new();
+    for (i, ics_event) in calendar.events().enumerate() {
+        let Some(title) = ics_event.get_summary().filter(|s| !s.is_empty()) else {
+            warnings.push(format!("Skipping event #{}: no SUMMARY", i + 1));
+            continue;
+        };
+        let timestamps = ics_event
+            .get_start()
+            .ok_or("no DTSTART".to_owned())
+            .and_then(|start| ics_timestamp_to_utc(start, &event.clock_info))
+            .and_then(|begin| {
+                ics_event
+                    .get_end()
+                    .ok_or("no DTEND".to_owned())
+                    .and_then(|end| ics_timestamp_to_utc(end, &event.clock_info))
+                    .map(|end| (begin, end))
+            });
+        let (begin, end) = match timestamps {
+            Ok(timestamps) => timestamps,
+            Err(e) => {
+                warnings.push(format!("Skipping '{}': {}", title, e));
+                continue;
+            }
+        };
+        if end < begin {
+            warnings.push(format!("Skipping '{}': DTEND is before DTSTART", title));
+            continue;
+        }
+        if !is_within_event_dates(&begin, &end, &event.basic_data, &event.clock_info) {
+            warnings.push(format!(
+                "Skipping '{}': outside of the event's date range",
+                title
+            ));
+            continue;
+        }
+
+        let entry_id = match ics_event.get_uid() {
+            Some(uid) => Uuid::new_v5(
+                &ICS_IMPORT_ID_NAMESPACE,
+                format!("{}:{}", event.basic_data.id, uid).as_bytes(),
+            ),
+            None => {
+                warnings.push(format!(
+                    "'{}' has no UID. It will be duplicated when importing the file again.",
+                    title
+                ));
+                Uuid::now_v7()
+            }
+        };
+
+        entries.push(models::FullNewEntry {
+            entry: models::NewEntry {
+                id: entry_id,
+                title: title.to_owned(),
+                description: ics_event.get_description().unwrap_or_default().to_owned(),
+                responsible_person: "".to_owned(),
+                is_room_reservation: false,
+                event_id: event.basic_data.id,
+                begin,
+                end,
+                category: category_id,
+                comment: "".to_owned(),
+                time_comment: "".to_owned(),
+                room_comment: ics_event.get_location().unwrap_or_default().to_owned(),
+                is_exclusive: false,
+                is_cancelled: false,
+                is_tentative: false,
+                orga_only: false,
+                open_ended: false,
+                state: models::EntryState::Draft,
+                orga_comment: "".to_owned(),
+                color_override: None,
+                links: None,
+            },
+            room_ids: vec![],
+            previous_dates: vec![],
+        });
+    }
+    Ok((entries, warnings))
+}
+
+/// Convert a DTSTART/DTEND value of an iCalendar event into a UTC timestamp. Floating times are
+/// interpreted in the event's timezone.
+fn ics_timestamp_to_utc(
+    value: icalendar::DatePerhapsTime,
+    clock_info: &models::EventClockInfo,
+) -> Result<chrono::DateTime<chrono::Utc>, String> {
+    let (naive, timezone) = match value {
+        icalendar::DatePerhapsTime::DateTime(icalendar::CalendarDateTime::Utc(timestamp)) => {
+            return Ok(timestamp);
+        }
+        icalendar::DatePerhapsTime::DateTime(icalendar::CalendarDateTime::Floating(naive)) => {
+            (naive, clock_info.timezone)
+        }
+        icalendar::DatePerhapsTime::DateTime(icalendar::CalendarDateTime::WithTimezone {
+            date_time,
+            tzid,
+        }) => (
+            date_time,
+            tzid.parse::<chrono_tz::Tz>()
+                .map_err(|_| format!("unknown timezone '{}'", tzid))?,
+        ),
+        icalendar::DatePerhapsTime::Date(_) => {
+            return Err("all-day events are not supported".to_owned());
+        }
+    };
+    naive
+        .and_local_timezone(timezone)
+        .earliest()
+        .map(|timestamp| timestamp.to_utc())
+        .ok_or(format!("{} does not exist in timezone {}", naive, timezone))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_entity_diff() {
+        let room = |id: u128, title: &str, description: &str| Room {
+            id: Uuid::from_u128(id),
+            title: title.to_owned(),
+            description: description.to_owned(),
+        };
+        let existing = vec![
+            room(1, "Unchanged", ""),
+            room(2, "Changed", ""),
+            room(3, "Orphaned", ""),
+        ];
+        let new = vec![
+            room(1, "Unchanged", ""),
+            room(2, "Changed title", "and description"),
+            room(4, "New", ""),
+        ];
+        let diff = compute_entity_diff(&new, &existing, |r| (r.id, &r.title));
+        assert_eq!(
+            diff.created,
+            vec![DiffItem {
+                id: Uuid::from_u128(4),
+                title: "New".to_owned(),
+                changed_fields: vec![],
+            }]
+        );
+        assert_eq!(
+            diff.updated,
+            vec![DiffItem {
+                id: Uuid::from_u128(2),
+                title: "Changed title".to_owned(),
+                changed_fields: vec!["description".to_owned(), "title".to_owned()],
+            }]
+        );
+        assert_eq!(
+            diff.orphaned,
+            vec![DiffItem {
+                id: Uuid::from_u128(3),
+                title: "Orphaned".to_owned(),
+                changed_fields: vec![],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_entries_from_ics() {
+        use chrono::TimeZone;
+
+        let category = Uuid::now_v7();
+        let event = models::ExtendedEvent {
+            basic_data: models::Event {
+                id: 1,
+                title: "TestEvent".to_owned(),
+                begin_date: "2025-03-28".parse().unwrap(),
+                end_date: "2025-04-02".parse().unwrap(),
+                slug: None,
+                begin_time: None,
+                end_time: None,
+                archived: false,
+            },
+            clock_info: models::EventClockInfo {
+                timezone: chrono_tz::Europe::Berlin,
+                effective_begin_of_day: chrono::NaiveTime::from_hms_opt(5, 30, 0).unwrap(),
+            },
+            default_time_schedule: models::EventDayTimeSchedule { sections: vec![] },
+            preceding_event_id: None,
+            subsequent_event_id: None,
+            entry_submission_mode: models::EntrySubmissionMode::Disabled,
+        };
+        let ics = "BEGIN:VCALENDAR\r\n\
+            VERSION:2.0\r\n\
+            PRODID:-//Test//Test//EN\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:1@example.com\r\n\
+            SUMMARY:Nachtwanderung\r\n\
+            DESCRIPTION:Mit Taschenlampen\r\n\
+            LOCATION:Am Lagerfeuer\r\n\
+            DTSTART:20250329T200000Z\r\n\
+            DTEND:20250329T220000Z\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:2@example.com\r\n\
+            SUMMARY:Frühsport\r\n\
+            DTSTART;TZID=Europe/Berlin:20250330T080000\r\n\
+            DTEND;TZID=Europe/Berlin:20250330T090000\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+
+        let (entries, warnings) = entries_from_ics(ics, &event, category).unwrap();
+        assert!(warnings.is_empty(), "Unexpected warnings: {:?}", warnings);
+        assert_eq!(entries.len(), 2);
+        let entry = &entries[0].entry;
+        assert_eq!(entry.title, "Nachtwanderung");
+        assert_eq!(entry.description, "Mit Taschenlampen");
+        assert_eq!(entry.room_comment, "Am Lagerfeuer");
+        assert_eq!(entry.category, category);
+        assert_eq!(entry.event_id, 1);
+        assert_eq!(entry.state, models::EntryState::Draft);
+        assert_eq!(
+            entry.begin,
+            chrono::Utc.with_ymd_and_hms(2025, 3, 29, 20, 0, 0).unwrap()
+        );
+        assert_eq!(
+            entry.end,
+            chrono::Utc.with_ymd_and_hms(2025, 3, 29, 22, 0, 0).unwrap()
+        );
+        // Daylight saving time has started in Berlin on 2025-03-30
+        let entry = &entries[1].entry;
+        assert_eq!(entry.title, "Frühsport");
+        assert_eq!(
+            entry.begin,
+            chrono::Utc.with_ymd_and_hms(2025, 3, 30, 6, 0, 0).unwrap()
+        );
+        assert_eq!(
+            entry.end,
+            chrono::Utc.with_ymd_and_hms(2025, 3, 30, 7, 0, 0).unwrap()
+        );
+
+        // The ids are derived from the UIDs, so they are stable when importing the file again
+        let first_ids: Vec<Uuid> = entries.iter().map(|e| e.entry.id).collect();
+        let (entries, _) = entries_from_ics(ics, &event, category).unwrap();
+        assert_eq!(
+            entries.iter().map(|e| e.entry.id).collect::<Vec<_>>(),
+            first_ids
+        );
+        assert_ne!(first_ids[0], first_ids[1]);
+
+        // Events outside of the event's date range are skipped
+        let ics = ics.replace("20250330T", "20250410T");
+        let (entries, warnings) = entries_from_ics(&ics, &event, category).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("Frühsport"));
+    }
+}
@@ -0,0 +1,293 @@
+//! Read-only consistency checks of the database contents, e.g. after manual modifications of the
+//! database.
+//!
+//! Each check is implemented as a separate function, returning a list of [IntegrityFinding]s.
+//! [check_integrity] runs all of them in a single read-only transaction and prints a report to
+//! stdout.
+//!
+//! Note that references from entries, previous dates and announcements to deleted rooms are kept
+//! intentionally when deleting a room (to allow recovery of the room) and are filtered out when
+//! reading the data. So, these findings are not caused by a corrupted database and are only
+//! reported as [FindingSeverity::Notice], separately from actual problems.
+use crate::cli_error::CliError;
+use crate::setup::get_database_url_from_env;
+use diesel::prelude::*;
+use diesel::sql_types;
+use uuid::Uuid;
+
+/// A single inconsistency found in the database by one of the integrity checks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntegrityFinding {
+    /// A (non-deleted) entry is assigned to a deleted room
+    OrphanedEntryRoom { entry_id: Uuid, room_id: Uuid },
+    /// A previous date of a (non-deleted) entry is assigned to a deleted room
+    OrphanedPreviousDateRoom {
+        previous_date_id: Uuid,
+        room_id: Uuid,
+    },
+    /// A (non-deleted) announcement is shown with a deleted room
+    OrphanedAnnouncementRoom {
+        announcement_id: Uuid,
+        room_id: Uuid,
+    },
+    /// A (non-deleted) entry belongs to a deleted category or a category of another event
+    EntryWithInvalidCategory { entry_id: Uuid, category_id: Uuid },
+    /// An event has no (non-deleted) categories, such that no entries can be created
+    EventWithoutCategories { event_id: i32 },
+}
+
+/// Severity of an [IntegrityFinding]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FindingSeverity {
+    /// An actual inconsistency, which should be fixed
+    Problem,
+    /// An expected state of the database, which is only reported for information
+    Notice,
+}
+
+impl IntegrityFinding {
+    pub fn severity(&self) -> FindingSeverity {
+        match self {
+            // References to deleted rooms are kept intentionally by `delete_room()`
+            IntegrityFinding::OrphanedEntryRoom { .. }
+            | IntegrityFinding::OrphanedPreviousDateRoom { .. }
+            | IntegrityFinding::OrphanedAnnouncementRoom { .. } => FindingSeverity::Notice,
+            IntegrityFinding::EntryWithInvalidCategory { .. }
+            | IntegrityFinding::EventWithoutCategories { .. } => FindingSeverity::Problem,
+        }
+    }
+}
+
+impl std::fmt::Display for IntegrityFinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IntegrityFinding::OrphanedEntryRoom { entry_id, room_id } => {
+                write!(f, "Entry {} references deleted room {}", entry_id, room_id)
+            }
+            IntegrityFinding::OrphanedPreviousDateRoom {
+                previous_date_id,
+                room_id,
+            } => write!(
+                f,
+                "Previous date {} references deleted room {}",
+                previous_date_id, room_id
+            ),
+            IntegrityFinding::OrphanedAnnouncementRoom {
+                announcement_id,
+                room_id,
+            } => write!(
+                f,
+                "Announcement {} references deleted room {}",
+                announcement_id, room_id
+            ),
+            IntegrityFinding::EntryWithInvalidCategory {
+                entry_id,
+                category_id,
+            } => write!(
+                f,
+                "Entry {} references deleted or foreign category {}",
+                entry_id, category_id
+            ),
+            IntegrityFinding::EventWithoutCategories { event_id } => {
+                write!(f, "Event {} has no categories", event_id)
+            }
+        }
+    }
+}
+
+/// Run all integrity checks on the database and print a report of the findings to stdout.
+///
+/// Problems and notices are counted separately, such that only actual problems are reported as
+/// integrity problems.
+///
+/// The database connection URL is taken from the environment variable, using
+/// [get_database_url_from_env]. The checks are executed in a read-only transaction, so this
+/// function never modifies the database.
+pub fn check_integrity() -> Result<(), CliError> {
+    let mut connection = PgConnection::establish(&get_database_url_from_env()?)?;
+    let findings = connection
+        .build_transaction()
+        .read_only()
+        .run(|connection| -> Result<_, diesel::result::Error> {
+            Ok([
+                ("Entry rooms", find_orphaned_entry_rooms(connection)?),
+                (
+                    "Previous date rooms",
+                    find_orphaned_previous_date_rooms(connection)?,
+                ),
+                (
+                    "Announcement rooms",
+                    find_orphaned_announcement_rooms(connection)?,
+                ),
+                (
+                    "Entry categories",
+                    find_entries_with_invalid_category(connection)?,
+                ),
+                (
+                    "Event categories",
+                    find_events_without_categories(connection)?,
+                ),
+            ])
+        })
+        .map_err(|e| CliError::UnexpectedStoreError(e.to_string()))?;
+
+    let mut total_problems = 0;
+    let mut total_notices = 0;
+    for (check_name, check_findings) in findings {
+        if check_findings.is_empty() {
+            println!("{}: OK", check_name);
+            continue;
+        }
+        let notices = check_findings
+            .iter()
+            .filter(|finding| finding.severity() == FindingSeverity::Notice)
+            .count();
+        let problems = check_findings.len() - notices;
+        println!(
+            "{}: {} problem(s), {} notice(s)",
+            check_name, problems, notices
+        );
+        for finding in check_findings.iter() {
+            match finding.severity() {
+                FindingSeverity::Problem => println!("  - {}", finding),
+                FindingSeverity::Notice => println!("  - (notice) {}", finding),
+            }
+        }
+        total_problems += problems;
+        total_notices += notices;
+    }
+    if total_problems == 0 {
+        println!("No integrity problems found.");
+    } else {
+        println!("Found {} integrity problem(s) in total.", total_problems);
+    }
+    if total_notices > 0 {
+        println!(
+            "Found {} notice(s) about references to deleted rooms, which are kept intentionally to \
+            allow restoring the rooms.",
+            total_notices
+        );
+    }
+    Ok(())
+}
+
+#[derive(QueryableByName)]
+struct ReferenceRow {
+    #[diesel(sql_type = sql_types::Uuid)]
+    referencing_id: Uuid,
+    #[diesel(sql_type = sql_types::Uuid)]
+    referenced_id: Uuid,
+}
+
+#[derive(QueryableByName)]
+struct EventIdRow {
+    #[diesel(sql_type = sql_types::Integer)]
+    event_id: i32,
+}
+
+/// Find room assignments of non-deleted entries, which point to deleted rooms
+pub fn find_orphaned_entry_rooms(
+    connection: &mut PgConnection,
+) -> Result<Vec<IntegrityFinding>, diesel::result::Error> {
+    Ok(diesel::sql_query(
+        "SELECT er.entry_id AS referencing_id, er.room_id AS referenced_id
+            FROM entry_rooms er
+            JOIN entries e ON e.id = er.entry_id
+            JOIN rooms r ON r.id = er.room_id
+            WHERE r.deleted AND NOT e.deleted
+            ORDER BY er.entry_id, er.room_id",
+    )
+    .load::<ReferenceRow>(connection)?
+    .into_iter()
+    .map(|row| IntegrityFinding::OrphanedEntryRoom {
+        entry_id: row.referencing_id,
+        room_id: row.referenced_id,
+    })
+    .collect())
+}
+
+/// Find room assignments of previous dates of non-deleted entries, which point to deleted rooms
+pub fn find_orphaned_previous_date_rooms(
+    connection: &mut PgConnection,
+) -> Result<Vec<IntegrityFinding>, diesel::result::Error> {
+    Ok(diesel::sql_query(
+        "SELECT pdr.previous_date_id AS referencing_id, pdr.room_id AS referenced_id
+            FROM previous_date_rooms pdr
+            JOIN previous_dates pd ON pd.id = pdr.previous_date_id
+            JOIN entries e ON e.id = pd.entry_id
+            JOIN rooms r ON r.id = pdr.room_id
+            WHERE r.deleted AND NOT e.deleted
+            ORDER BY pdr.previous_date_id, pdr.room_id",
+    )
+    .load::<ReferenceRow>(connection)?
+    .into_iter()
+    .map(|row| IntegrityFinding::OrphanedPreviousDateRoom {
+        previous_date_id: row.referencing_id,
+        room_id: row.referenced_id,
+    })
+    .collect())
+}
+
+/// Find room references of non-deleted announcements, which point to deleted rooms
+pub fn find_orphaned_announcement_rooms(
+    connection: &mut PgConnection,
+) -> Result<Vec<IntegrityFinding>, diesel::result::Error> {
+    Ok(diesel::sql_query(
+        "SELECT ar.announcement_id AS referencing_id, ar.room_id AS referenced_id
+            FROM announcement_rooms ar
+            JOIN announcements a ON a.id = ar.announcement_id
+            JOIN rooms r ON r.id = ar.room_id
+            WHERE r.deleted AND NOT a.deleted
+            ORDER BY ar.announcement_id, ar.room_id",
+    )
+    .load::<ReferenceRow>(connection)?
+    .into_iter()
+    .map(|row| IntegrityFinding::OrphanedAnnouncementRoom {
+        announcement_id: row.referencing_id,
+        room_id: row.referenced_id,
+    })
+    .collect())
+}
+
+/// Find non-deleted entries, whose category is deleted or belongs to another event.
+///
+/// Entries referencing a category, which does not exist at all, are prevented by the foreign key
+/// constraint.
+pub fn find_entries_with_invalid_category(
+    connection: &mut PgConnection,
+) -> Result<Vec<IntegrityFinding>, diesel::result::Error> {
+    Ok(diesel::sql_query(
+        "SELECT e.id AS referencing_id, e.category AS referenced_id
+            FROM entries e
+            JOIN categories c ON c.id = e.category
+            WHERE NOT e.deleted AND (c.deleted OR c.event_id <> e.event_id)
+            ORDER BY e.id",
+    )
+    .load::<ReferenceRow>(connection)?
+    .into_iter()
+    .map(|row| IntegrityFinding::EntryWithInvalidCategory {
+        entry_id: row.referencing_id,
+        category_id: row.referenced_id,
+    })
+    .collect())
+}
+
+/// Find events without any non-deleted category
+pub fn find_events_without_categories(
+    connection: &mut PgConnection,
+) -> Result<Vec<IntegrityFinding>, diesel::result::Error> {
+    Ok(diesel::sql_query(
+        "SELECT ev.id AS event_id
+            FROM events ev
+            WHERE NOT EXISTS (
+                SELECT 1 FROM categories c WHERE c.event_id = ev.id AND NOT c.deleted
+            )
+            ORDER BY ev.id",
+    )
+    .load::<EventIdRow>(connection)?
+    .into_iter()
+    .map(|row| IntegrityFinding::EventWithoutCategories {
+        event_id: row.event_id,
+    })
+    .collect())
+}
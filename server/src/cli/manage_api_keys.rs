@@ -0,0 +1,87 @@
+use crate::cli::{CliAuthTokenKey, EventIdOrSlug};
+use crate::cli_error::CliError;
+use crate::data_store::auth_token::AuthToken;
+use crate::data_store::models::NewApiKey;
+use crate::data_store::{ApiKeyId, KuaPlanStore, get_store_from_env};
+
+pub fn print_api_key_list(event_id_or_slug: EventIdOrSlug) -> Result<(), CliError> {
+    let data_store_pool = get_store_from_env()?;
+    let mut data_store = data_store_pool.get_facade()?;
+
+    let event = match event_id_or_slug {
+        EventIdOrSlug::Id(event_id) => data_store.get_event(event_id)?,
+        EventIdOrSlug::Slug(event_slug) => data_store.get_event_by_slug(&event_slug)?,
+    };
+
+    let auth_key = CliAuthTokenKey::new();
+    let auth_token = AuthToken::create_for_cli(event.id, &auth_key);
+    let api_keys = data_store.get_api_keys(&auth_token, event.id)?;
+
+    let mut table = comfy_table::Table::new();
+    table
+        .load_preset(comfy_table::presets::ASCII_BORDERS_ONLY_CONDENSED)
+        .set_header(vec!["id", "comment", "created at"])
+        .set_content_arrangement(comfy_table::ContentArrangement::Dynamic)
+        .add_rows(api_keys.into_iter().map(|api_key| {
+            [
+                api_key.id.to_string(),
+                api_key.comment,
+                api_key.created_at.to_string(),
+            ]
+        }));
+
+    println!("API keys of event {}:", event.title);
+    println!("{table}");
+    Ok(())
+}
+
+/// Create a new read-only API key for the given event and print it.
+///
+/// The key is printed only once, since only its hash is stored in the database.
+pub fn create_api_key(event_id_or_slug: EventIdOrSlug, comment: String) -> Result<(), CliError> {
+    let data_store_pool = get_store_from_env()?;
+    let mut data_store = data_store_pool.get_facade()?;
+
+    let event = match event_id_or_slug {
+        EventIdOrSlug::Id(event_id) => data_store.get_event(event_id)?,
+        EventIdOrSlug::Slug(event_slug) => data_store.get_event_by_slug(&event_slug)?,
+    };
+
+    let auth_key = CliAuthTokenKey::new();
+    let auth_token = AuthToken::create_for_cli(event.id, &auth_key);
+    let (api_key_id, api_key) = data_store.create_api_key(
+        &auth_token,
+        NewApiKey {
+            event_id: event.id,
+            comment,
+        },
+    )?;
+
+    println!(
+        "Created API key {} for event {}. Store the key now, it cannot be shown again:",
+        api_key_id, event.title
+    );
+    println!("{}", api_key);
+    Ok(())
+}
+
+/// Revoke (i.e. delete) the API key with the given id from the given event. API clients using the
+/// key lose access immediately.
+pub fn revoke_api_key(
+    event_id_or_slug: EventIdOrSlug,
+    api_key_id: ApiKeyId,
+) -> Result<(), CliError> {
+    let data_store_pool = get_store_from_env()?;
+    let mut data_store = data_store_pool.get_facade()?;
+
+    let event = match event_id_or_slug {
+        EventIdOrSlug::Id(event_id) => data_store.get_event(event_id)?,
+        EventIdOrSlug::Slug(event_slug) => data_store.get_event_by_slug(&event_slug)?,
+    };
+
+    let auth_key = CliAuthTokenKey::new();
+    let auth_token = AuthToken::create_for_cli(event.id, &auth_key);
+    data_store.delete_api_key(&auth_token, event.id, api_key_id)?;
+    println!("Revoked API key {} of event {}.", api_key_id, event.title);
+    Ok(())
+}
@@ -1,4 +1,6 @@
-use crate::cli::util::{query_user, query_user_and_check, query_user_bool};
+use crate::cli::util::{
+    make_slug_unique, query_user, query_user_and_check, query_user_bool, query_user_date, slugify,
+};
 use crate::cli::{CliAuthTokenKey, EventIdOrSlug};
 use crate::cli_error::CliError;
 use crate::data_store::auth_token::{AccessRole, AuthToken, GlobalAuthToken};
@@ -8,25 +10,42 @@ use crate::data_store::models::{
     ExtendedEvent, NewCategory, NewPassphrase,
 };
 use crate::data_store::{EventFilter, KuaPlanStore};
+use std::collections::HashSet;
 use uuid::Uuid;
 
-pub fn print_event_list() -> Result<(), CliError> {
+/// Print a table of all events (including archived ones), optionally filtered by a
+/// (case-insensitive) substring of the title and by date. The events are sorted by date.
+pub fn print_event_list(
+    title_filter: Option<String>,
+    after: Option<chrono::NaiveDate>,
+    before: Option<chrono::NaiveDate>,
+) -> Result<(), CliError> {
     let data_store_pool = get_store_from_env()?;
     let mut data_store = data_store_pool.get_facade()?;
 
-    let events = data_store.get_events(EventFilter::default())?;
+    let mut filter = EventFilter::builder().include_archived();
+    if let Some(title_filter) = title_filter {
+        filter = filter.matching_title(title_filter);
+    }
+    if let Some(after) = after {
+        filter = filter.after(after);
+    }
+    if let Some(before) = before {
+        filter = filter.before(before);
+    }
+    let events = data_store.get_events(filter.build())?;
 
     let mut table = comfy_table::Table::new();
     table
         .load_preset(comfy_table::presets::ASCII_BORDERS_ONLY_CONDENSED)
-        .set_header(vec!["id", "slug", "title", "begin", "end"])
+        .set_header(vec!["id", "slug", "title", "dates", "archived"])
         .add_rows(events.into_iter().map(|event| {
             [
                 event.id.to_string(),
                 event.slug.unwrap_or(String::new()),
                 event.title,
-                event.begin_date.to_string(),
-                event.end_date.to_string(),
+                format!("{} – {}", event.begin_date, event.end_date),
+                if event.archived { "yes" } else { "" }.to_owned(),
             ]
         }));
 
@@ -34,6 +53,74 @@ pub fn print_event_list() -> Result<(), CliError> {
     Ok(())
 }
 
+/// Print an overview of the event's configuration and contents as aligned key-value lines
+pub fn print_event_summary(event_id_or_slug: EventIdOrSlug) -> Result<(), CliError> {
+    let data_store_pool = get_store_from_env()?;
+    let mut data_store = data_store_pool.get_facade()?;
+    let event = match event_id_or_slug {
+        EventIdOrSlug::Id(event_id) => data_store.get_event(event_id)?,
+        EventIdOrSlug::Slug(event_slug) => data_store.get_event_by_slug(&event_slug)?,
+    };
+
+    let auth_key = CliAuthTokenKey::new();
+    let auth_token = AuthToken::create_for_cli(event.id, &auth_key);
+    let extended_event = data_store.get_extended_event(&auth_token, event.id)?;
+    let stats = data_store.get_event_stats(&auth_token, event.id)?;
+    let announcement_count = data_store
+        .get_announcements(&auth_token, event.id, None)?
+        .len();
+    let passphrase_count = data_store.get_passphrases(&auth_token, event.id)?.len();
+    let neighbors = data_store.get_neighbor_events(&auth_token, event.id)?;
+    let format_neighbor = |neighbor: Option<Event>| {
+        neighbor
+            .map(|e| format!("{} (id={})", e.title, e.id))
+            .unwrap_or("–".to_owned())
+    };
+
+    let lines = [
+        ("id", event.id.to_string()),
+        ("title", event.title),
+        ("slug", event.slug.unwrap_or("–".to_owned())),
+        (
+            "dates",
+            format!("{} – {}", event.begin_date, event.end_date),
+        ),
+        (
+            "archived",
+            if event.archived { "yes" } else { "no" }.to_owned(),
+        ),
+        ("timezone", extended_event.clock_info.timezone.to_string()),
+        (
+            "effective begin of day",
+            extended_event.clock_info.effective_begin_of_day.to_string(),
+        ),
+        ("entries", stats.entry_count.to_string()),
+        ("cancelled entries", stats.cancelled_count.to_string()),
+        ("rooms", stats.room_count.to_string()),
+        ("categories", stats.category_count.to_string()),
+        ("announcements", announcement_count.to_string()),
+        ("passphrases", passphrase_count.to_string()),
+        (
+            "preceding event",
+            format_neighbor(neighbors.preceding_event),
+        ),
+        (
+            "subsequent event",
+            format_neighbor(neighbors.subsequent_event),
+        ),
+    ];
+    let key_width = lines.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+    for (key, value) in lines {
+        println!(
+            "{:<width$}  {}",
+            format!("{key}:"),
+            value,
+            width = key_width + 1
+        );
+    }
+    Ok(())
+}
+
 pub fn create_event() -> Result<(), CliError> {
     let data_store_pool = get_store_from_env()?;
     let mut data_store = data_store_pool.get_facade()?;
@@ -49,8 +136,20 @@ pub fn create_event() -> Result<(), CliError> {
             }
         });
     let slug: String = query_user("Enter event slug (e.g. pa25)");
-    let begin_date: chrono::NaiveDate = query_user("Enter event begin (YYYY-MM-DD)");
-    let end_date: chrono::NaiveDate = query_user("Enter event end (YYYY-MM-DD)");
+    let begin_date = query_user_date(
+        "Enter event begin (DD.MM.YYYY, YYYY-MM-DD or DD.MM.)",
+        |_| Ok::<(), &str>(()),
+    );
+    let end_date = query_user_date(
+        "Enter event end (DD.MM.YYYY, YYYY-MM-DD or DD.MM.)",
+        |end_date| {
+            if *end_date < begin_date {
+                Err("event end must not be before event begin")
+            } else {
+                Ok(())
+            }
+        },
+    );
 
     let event = ExtendedEvent {
         basic_data: Event {
@@ -59,6 +158,9 @@ pub fn create_event() -> Result<(), CliError> {
             begin_date,
             end_date,
             slug: (!slug.is_empty()).then_some(slug),
+            begin_time: None,
+            end_time: None,
+            archived: false,
         },
         clock_info: EventClockInfo {
             timezone: chrono_tz::Tz::Europe__Berlin,
@@ -154,3 +256,63 @@ pub fn delete_event(event_id_or_slug: EventIdOrSlug) -> Result<(), CliError> {
     println!("Success");
     Ok(())
 }
+
+/// Toggle the `archived` flag of the event. Archived events are hidden from event listings by
+/// default.
+pub fn toggle_event_archived(event_id_or_slug: EventIdOrSlug) -> Result<(), CliError> {
+    let data_store_pool = get_store_from_env()?;
+    let mut data_store = data_store_pool.get_facade()?;
+    let event = match event_id_or_slug {
+        EventIdOrSlug::Id(event_id) => data_store.get_event(event_id)?,
+        EventIdOrSlug::Slug(event_slug) => data_store.get_event_by_slug(&event_slug)?,
+    };
+
+    let auth_key = CliAuthTokenKey::new();
+    let auth_token = AuthToken::create_for_cli(event.id, &auth_key);
+    data_store.set_event_archived(&auth_token, event.id, !event.archived)?;
+
+    println!(
+        "The event '{}' (id={}) is {} archived.",
+        event.title,
+        event.id,
+        if event.archived { "no longer" } else { "now" }
+    );
+    Ok(())
+}
+
+/// Generate a slug for each event without slug, derived from the event's title, and print the
+/// generated slugs. Generated slugs are made unique by appending a numeric suffix, if required.
+pub fn generate_missing_slugs() -> Result<(), CliError> {
+    let data_store_pool = get_store_from_env()?;
+    let mut data_store = data_store_pool.get_facade()?;
+    let auth_key = CliAuthTokenKey::new();
+
+    let events = data_store.get_events(EventFilter::builder().include_archived().build())?;
+    let mut existing_slugs: HashSet<String> =
+        events.iter().filter_map(|e| e.slug.clone()).collect();
+
+    let mut table = comfy_table::Table::new();
+    table
+        .load_preset(comfy_table::presets::ASCII_BORDERS_ONLY_CONDENSED)
+        .set_header(vec!["id", "title", "new slug"]);
+    for event in events.iter().filter(|e| e.slug.is_none()) {
+        let slug = make_slug_unique(slugify(&event.title), &existing_slugs);
+        let auth_token = AuthToken::create_for_cli(event.id, &auth_key);
+        let mut extended_event = data_store.get_extended_event(&auth_token, event.id)?;
+        extended_event.basic_data.slug = Some(slug.clone());
+        data_store.update_event(&auth_token, extended_event)?;
+        table.add_row(vec![
+            event.id.to_string(),
+            event.title.clone(),
+            slug.clone(),
+        ]);
+        existing_slugs.insert(slug);
+    }
+
+    if table.row_count() == 0 {
+        println!("All events have a slug already.");
+    } else {
+        println!("{table}");
+    }
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use crate::auth_session::FeedAccessToken;
+use crate::cli::manage_passphrases::PassphraseAccessRoleEntry;
+use crate::cli::{CliAuthTokenKey, EventIdOrSlug};
+use crate::cli_error::CliError;
+use crate::data_store::auth_token::AuthToken;
+use crate::data_store::models::NewFeedToken;
+use crate::data_store::{FeedTokenId, KuaPlanStore, get_store_from_env};
+use crate::setup::get_secrets_from_env;
+
+pub fn print_feed_token_list(event_id_or_slug: EventIdOrSlug) -> Result<(), CliError> {
+    let data_store_pool = get_store_from_env()?;
+    let mut data_store = data_store_pool.get_facade()?;
+
+    let event = match event_id_or_slug {
+        EventIdOrSlug::Id(event_id) => data_store.get_event(event_id)?,
+        EventIdOrSlug::Slug(event_slug) => data_store.get_event_by_slug(&event_slug)?,
+    };
+
+    let auth_key = CliAuthTokenKey::new();
+    let auth_token = AuthToken::create_for_cli(event.id, &auth_key);
+    let feed_tokens = data_store.get_feed_tokens(&auth_token, event.id)?;
+
+    let mut table = comfy_table::Table::new();
+    table
+        .load_preset(comfy_table::presets::ASCII_BORDERS_ONLY_CONDENSED)
+        .set_header(vec!["id", "role", "comment", "created at", "revoked"])
+        .set_content_arrangement(comfy_table::ContentArrangement::Dynamic)
+        .add_rows(feed_tokens.into_iter().map(|feed_token| {
+            [
+                feed_token.id.to_string(),
+                format!("{:?}", feed_token.privilege),
+                feed_token.comment,
+                feed_token.created_at.to_string(),
+                if feed_token.revoked { "yes" } else { "" }.to_string(),
+            ]
+        }));
+
+    println!("Feed tokens of event {}:", event.title);
+    println!("{table}");
+    Ok(())
+}
+
+/// Create a new feed token for the given event and role and print the signed token string.
+///
+/// The token string can be recreated from the id at any time (as long as the application SECRET is
+/// not changed), so it is not stored in the database.
+pub fn create_feed_token(
+    event_id_or_slug: EventIdOrSlug,
+    role: PassphraseAccessRoleEntry,
+    comment: String,
+) -> Result<(), CliError> {
+    let secrets = get_secrets_from_env()?;
+    let data_store_pool = get_store_from_env()?;
+    let mut data_store = data_store_pool.get_facade()?;
+
+    let event = match event_id_or_slug {
+        EventIdOrSlug::Id(event_id) => data_store.get_event(event_id)?,
+        EventIdOrSlug::Slug(event_slug) => data_store.get_event_by_slug(&event_slug)?,
+    };
+
+    let auth_key = CliAuthTokenKey::new();
+    let auth_token = AuthToken::create_for_cli(event.id, &auth_key);
+    let feed_token_id = data_store.create_feed_token(
+        &auth_token,
+        NewFeedToken {
+            event_id: event.id,
+            privilege: role.0,
+            comment,
+        },
+    )?;
+
+    println!(
+        "Created feed token {} for role {:?} on event {}. Use it as `token` query parameter of the \
+        calendar feed URLs:",
+        feed_token_id, role.0, event.title
+    );
+    println!(
+        "{}",
+        FeedAccessToken::new(event.id, feed_token_id).as_string(&secrets[0])
+    );
+    Ok(())
+}
+
+/// Revoke the feed token with the given id from the given event. Calendar clients using the token
+/// lose access immediately.
+pub fn revoke_feed_token(
+    event_id_or_slug: EventIdOrSlug,
+    feed_token_id: FeedTokenId,
+) -> Result<(), CliError> {
+    let data_store_pool = get_store_from_env()?;
+    let mut data_store = data_store_pool.get_facade()?;
+
+    let event = match event_id_or_slug {
+        EventIdOrSlug::Id(event_id) => data_store.get_event(event_id)?,
+        EventIdOrSlug::Slug(event_slug) => data_store.get_event_by_slug(&event_slug)?,
+    };
+
+    let auth_key = CliAuthTokenKey::new();
+    let auth_token = AuthToken::create_for_cli(event.id, &auth_key);
+    data_store.revoke_feed_token(&auth_token, event.id, feed_token_id)?;
+    println!(
+        "Revoked feed token {} of event {}.",
+        feed_token_id, event.title
+    );
+    Ok(())
+}
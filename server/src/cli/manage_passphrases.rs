@@ -1,10 +1,24 @@
-use crate::cli::util::{query_user, query_user_bool};
+use crate::cli::util::{
+    generate_distinct_passphrases, query_user, query_user_and_check, query_user_bool,
+};
 use crate::cli::{CliAuthTokenKey, EventIdOrSlug};
 use crate::cli_error::CliError;
 use crate::data_store::KuaPlanStore;
-use crate::data_store::auth_token::{AccessRole, AuthToken};
-use crate::data_store::models::{Event, NewPassphrase, Passphrase, PassphrasePatch};
+use crate::data_store::auth_token::{AccessRole, AuthToken, GlobalAuthToken};
+use crate::data_store::models::{
+    Event, NewPassphrase, Passphrase, PassphraseBackup, PassphrasePatch,
+};
 use crate::data_store::{PassphraseId, get_store_from_env};
+use base64::Engine;
+use ring::aead;
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, BufWriter};
+use std::num::NonZeroU32;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::PathBuf;
 use std::str::FromStr;
 
 pub fn print_passphrase_list(event_id_or_slug: EventIdOrSlug) -> Result<(), CliError> {
@@ -31,6 +45,7 @@ pub fn print_passphrase_list(event_id_or_slug: EventIdOrSlug) -> Result<(), CliE
             "comment",
             "valid from",
             "valid until",
+            "last used",
         ])
         .set_content_arrangement(comfy_table::ContentArrangement::Dynamic)
         .add_rows(passphrases.into_iter().map(|passphrase| {
@@ -54,6 +69,10 @@ pub fn print_passphrase_list(event_id_or_slug: EventIdOrSlug) -> Result<(), CliE
                     .valid_until
                     .map(|v| v.to_string())
                     .unwrap_or("∞".to_owned()),
+                passphrase
+                    .last_used
+                    .map(|v| v.to_string())
+                    .unwrap_or("never".to_owned()),
             ]
         }));
 
@@ -116,6 +135,70 @@ pub fn add_passphrase(event_id_or_slug: EventIdOrSlug) -> Result<(), CliError> {
     Ok(())
 }
 
+/// Minimum number of words of generated passphrases
+const MIN_GENERATED_PASSPHRASE_WORDS: usize = 3;
+/// Maximum number of passphrases to be created at once with [create_passphrase_batch]
+const MAX_PASSPHRASE_BATCH_SIZE: usize = 1000;
+
+/// Create `count` passphrases for the given access role with randomly generated, human-readable
+/// passphrases of `word_count` words each and print them.
+///
+/// The plaintext passphrases are printed only once, since they are obfuscated when listing the
+/// passphrases later. Each passphrase is printed right after it has been created.
+pub fn create_passphrase_batch(
+    event_id_or_slug: EventIdOrSlug,
+    count: usize,
+    role: PassphraseAccessRoleEntry,
+    word_count: usize,
+    comment: String,
+) -> Result<(), CliError> {
+    if count == 0 || count > MAX_PASSPHRASE_BATCH_SIZE {
+        return Err(CliError::DataError(format!(
+            "Number of passphrases must be between 1 and {MAX_PASSPHRASE_BATCH_SIZE}."
+        )));
+    }
+    if word_count < MIN_GENERATED_PASSPHRASE_WORDS {
+        return Err(CliError::DataError(format!(
+            "Generated passphrases must consist of at least {MIN_GENERATED_PASSPHRASE_WORDS} words."
+        )));
+    }
+
+    let data_store_pool = get_store_from_env()?;
+    let mut data_store = data_store_pool.get_facade()?;
+
+    let event = match event_id_or_slug {
+        EventIdOrSlug::Id(event_id) => data_store.get_event(event_id)?,
+        EventIdOrSlug::Slug(event_slug) => data_store.get_event_by_slug(&event_slug)?,
+    };
+    let auth_key = CliAuthTokenKey::new();
+    let auth_token = AuthToken::create_for_cli(event.id, &auth_key);
+
+    println!(
+        "Creating {} passphrases for role {:?} on {}. They will not be shown in plaintext again:",
+        count, role.0, event.title
+    );
+    println!("{:>6}  passphrase", "id");
+    for passphrase in generate_distinct_passphrases(count, word_count) {
+        let passphrase_id = data_store.create_passphrase(
+            &auth_token,
+            NewPassphrase {
+                event_id: event.id,
+                passphrase: Some(passphrase.clone()),
+                privilege: role.0,
+                derivable_from_passphrase: None,
+                comment: comment.clone(),
+                valid_from: None,
+                valid_until: None,
+            },
+        )?;
+        // Print each passphrase immediately, such that already created passphrases are not lost
+        // if creating one of the following passphrases fails.
+        println!("{:>6}  {}", passphrase_id, passphrase);
+    }
+    println!("Success.");
+    Ok(())
+}
+
 pub fn delete_passphrase(
     event_id_or_slug: EventIdOrSlug,
     passphrase_id: PassphraseId,
@@ -260,6 +343,216 @@ pub fn invalidate_passphrase(
     Ok(())
 }
 
+/// Hash all passphrases in the database, which are only stored in plaintext, and print the number
+/// of hashed passphrases.
+pub fn hash_existing_passphrases() -> Result<(), CliError> {
+    let data_store_pool = get_store_from_env()?;
+    let mut data_store = data_store_pool.get_facade()?;
+
+    let auth_key = CliAuthTokenKey::new();
+    let auth_token = GlobalAuthToken::create_for_cli(&auth_key);
+    let count = data_store.hash_plaintext_passphrases(&auth_token)?;
+    println!("Hashed {} passphrase(s).", count);
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedPassphrases {
+    passphrases: Vec<PassphraseBackup>,
+}
+
+/// Number of PBKDF2 iterations for deriving the encryption key of a passphrase backup from the
+/// user's password
+const BACKUP_KEY_ITERATIONS: u32 = 600_000;
+
+/// File format of a passphrase backup: The JSON-serialized [SavedPassphrases], encrypted with
+/// AES-256-GCM using a key, which is derived from a user-supplied password with
+/// PBKDF2-HMAC-SHA256. All binary values are base64 encoded.
+#[derive(Serialize, Deserialize)]
+struct EncryptedPassphraseBackup {
+    iterations: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Write all passphrases of the given event, including their hashes and the plaintext of
+/// passphrases for role 'user', to the given file, after confirmation by the user. The file is
+/// encrypted with a password, which is queried from the user.
+pub fn export_passphrases_to_file(
+    event_id_or_slug: EventIdOrSlug,
+    path: &PathBuf,
+) -> Result<(), CliError> {
+    let data_store_pool = get_store_from_env()?;
+    let mut data_store = data_store_pool.get_facade()?;
+
+    let event = match event_id_or_slug {
+        EventIdOrSlug::Id(event_id) => data_store.get_event(event_id)?,
+        EventIdOrSlug::Slug(event_slug) => data_store.get_event_by_slug(&event_slug)?,
+    };
+    let auth_key = CliAuthTokenKey::new();
+    let auth_token = AuthToken::create_for_cli(event.id, &auth_key);
+    let passphrases = data_store.get_passphrases_for_backup(&auth_token, event.id)?;
+
+    println!(
+        "Exporting {} passphrase(s) of {}. The file will contain passphrase hashes and plaintext \
+        passphrases, which can be used to gain access to the event. It is encrypted with a \
+        password, but make sure to store it securely anyway.",
+        passphrases.len(),
+        event.title
+    );
+    let confirm = query_user_bool("Do you want to write the passphrases to the file?", None);
+    if !confirm {
+        return Ok(());
+    }
+    let password: String =
+        query_user_and_check("Password for encrypting the file", |p: &String| {
+            if p.is_empty() {
+                Err("Password must not be empty.")
+            } else {
+                Ok(())
+            }
+        });
+    let _: String = query_user_and_check("Repeat the password", |p: &String| {
+        if *p != password {
+            Err("Passwords do not match.")
+        } else {
+            Ok(())
+        }
+    });
+
+    let backup = encrypt_passphrase_backup(
+        &SavedPassphrases { passphrases },
+        &password,
+        BACKUP_KEY_ITERATIONS,
+    )?;
+    let f = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)
+        .map_err(|e| {
+            CliError::FileError(format!(
+                "Could not create or open {:?} for writing: {}",
+                path, e
+            ))
+        })?;
+    serde_json::to_writer(BufWriter::new(f), &backup)?;
+    println!("Success.");
+    Ok(())
+}
+
+/// Recreate the passphrases from the given encrypted file, as written by
+/// [export_passphrases_to_file], for the given event, after confirmation by the user.
+pub fn import_passphrases_from_file(
+    event_id_or_slug: EventIdOrSlug,
+    path: &PathBuf,
+) -> Result<(), CliError> {
+    let f = File::open(path).map_err(|e| {
+        CliError::FileError(format!("Could not open {:?} for reading: {}", path, e))
+    })?;
+    let backup: EncryptedPassphraseBackup = serde_json::from_reader(BufReader::new(f))?;
+    let password: String = query_user("Password of the file");
+    let data = decrypt_passphrase_backup(&backup, &password)?;
+
+    let data_store_pool = get_store_from_env()?;
+    let mut data_store = data_store_pool.get_facade()?;
+
+    let event = match event_id_or_slug {
+        EventIdOrSlug::Id(event_id) => data_store.get_event(event_id)?,
+        EventIdOrSlug::Slug(event_slug) => data_store.get_event_by_slug(&event_slug)?,
+    };
+
+    let confirm = query_user_bool(
+        &format!(
+            "Do you want to add {} passphrase(s) to {}?",
+            data.passphrases.len(),
+            event.title
+        ),
+        None,
+    );
+    if !confirm {
+        return Ok(());
+    }
+
+    let auth_key = CliAuthTokenKey::new();
+    let auth_token = AuthToken::create_for_cli(event.id, &auth_key);
+    let count =
+        data_store.import_passphrases_from_backup(&auth_token, event.id, data.passphrases)?;
+    println!("Imported {} passphrase(s).", count);
+    Ok(())
+}
+
+fn derive_backup_key(password: &str, salt: &[u8], iterations: u32) -> aead::LessSafeKey {
+    let mut key = [0u8; 32];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        NonZeroU32::new(iterations.max(1)).unwrap(),
+        salt,
+        password.as_bytes(),
+        &mut key,
+    );
+    aead::LessSafeKey::new(
+        aead::UnboundKey::new(&aead::AES_256_GCM, &key).expect("Key length should be correct"),
+    )
+}
+
+fn encrypt_passphrase_backup(
+    data: &SavedPassphrases,
+    password: &str,
+    iterations: u32,
+) -> Result<EncryptedPassphraseBackup, CliError> {
+    let rng = SystemRandom::new();
+    let mut salt = [0u8; 16];
+    let mut nonce = [0u8; aead::NONCE_LEN];
+    rng.fill(&mut salt)
+        .and_then(|_| rng.fill(&mut nonce))
+        .expect("System random number generator should be available");
+
+    let mut in_out = serde_json::to_vec(data)?;
+    derive_backup_key(password, &salt, iterations)
+        .seal_in_place_append_tag(
+            aead::Nonce::assume_unique_for_key(nonce),
+            aead::Aad::empty(),
+            &mut in_out,
+        )
+        .map_err(|_| CliError::DataError("Could not encrypt passphrases.".to_owned()))?;
+
+    let engine = &base64::engine::general_purpose::STANDARD;
+    Ok(EncryptedPassphraseBackup {
+        iterations,
+        salt: engine.encode(salt),
+        nonce: engine.encode(nonce),
+        ciphertext: engine.encode(in_out),
+    })
+}
+
+fn decrypt_passphrase_backup(
+    backup: &EncryptedPassphraseBackup,
+    password: &str,
+) -> Result<SavedPassphrases, CliError> {
+    let engine = &base64::engine::general_purpose::STANDARD;
+    let decode = |value: &str| {
+        engine
+            .decode(value)
+            .map_err(|e| CliError::DataError(format!("Invalid passphrase backup file: {}", e)))
+    };
+    let salt = decode(&backup.salt)?;
+    let nonce = aead::Nonce::try_assume_unique_for_key(&decode(&backup.nonce)?)
+        .map_err(|_| CliError::DataError("Invalid passphrase backup file: nonce".to_owned()))?;
+    let mut in_out = decode(&backup.ciphertext)?;
+
+    let plaintext = derive_backup_key(password, &salt, backup.iterations)
+        .open_in_place(nonce, aead::Aad::empty(), &mut in_out)
+        .map_err(|_| {
+            CliError::DataError(
+                "Could not decrypt passphrase backup file. Wrong password?".to_owned(),
+            )
+        })?;
+    Ok(serde_json::from_slice(plaintext)?)
+}
+
 #[allow(unused_must_use)]
 fn write_passphrase_id(
     mut w: impl std::io::Write,
@@ -282,7 +575,9 @@ fn write_passphrase_id(
     Ok(())
 }
 
-struct PassphraseAccessRoleEntry(AccessRole);
+/// Access role of a passphrase, as entered on the command line ('user', 'orga' or 'admin')
+#[derive(Debug, Clone)]
+pub struct PassphraseAccessRoleEntry(pub(super) AccessRole);
 
 impl FromStr for PassphraseAccessRoleEntry {
     type Err = &'static str;
@@ -310,3 +605,55 @@ impl FromStr for IsoDateTime {
         })?)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_passphrase_backup_encryption_round_trip() {
+        let data = SavedPassphrases {
+            passphrases: vec![
+                PassphraseBackup {
+                    id: 1,
+                    privilege: AccessRole::User,
+                    passphrase: Some("user".to_owned()),
+                    passphrase_hash: Some("$argon2id$v=19$m=19456,t=2,p=1$abc$def".to_owned()),
+                    passphrase_lookup_key: Some(1234),
+                    derivable_from_passphrase: None,
+                    comment: "Participants".to_owned(),
+                    valid_from: None,
+                    valid_until: None,
+                },
+                PassphraseBackup {
+                    id: 2,
+                    privilege: AccessRole::SharableViewLink,
+                    passphrase: None,
+                    passphrase_hash: None,
+                    passphrase_lookup_key: None,
+                    derivable_from_passphrase: Some(1),
+                    comment: String::new(),
+                    valid_from: None,
+                    valid_until: None,
+                },
+            ],
+        };
+
+        let backup = encrypt_passphrase_backup(&data, "secret", 1000).unwrap();
+        let serialized = serde_json::to_string(&backup).unwrap();
+        assert!(!serialized.contains("user"));
+        assert!(!serialized.contains("argon2"));
+
+        let backup: EncryptedPassphraseBackup = serde_json::from_str(&serialized).unwrap();
+        let restored = decrypt_passphrase_backup(&backup, "secret").unwrap();
+        assert_eq!(
+            serde_json::to_value(&restored).unwrap(),
+            serde_json::to_value(&data).unwrap()
+        );
+
+        assert!(matches!(
+            decrypt_passphrase_backup(&backup, "wrong"),
+            Err(CliError::DataError(_))
+        ));
+    }
+}
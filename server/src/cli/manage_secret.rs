@@ -0,0 +1,50 @@
+use crate::cli_error::CliError;
+use crate::setup::get_secrets_from_env;
+use base64::Engine;
+use ring::rand::SecureRandom;
+
+/// Number of random bytes of a generated secret
+const GENERATED_SECRET_LENGTH: usize = 48;
+
+/// Print the environment configuration for replacing the application secret with a new one, while
+/// keeping the current (and all previous) secrets as `SECRET_PREVIOUS`, such that existing session
+/// tokens stay valid during a transition period.
+///
+/// The environment configuration cannot be changed by the server itself, so the administrator has
+/// to apply the printed configuration and restart the server. After the transition period, the
+/// previous secrets can be removed from `SECRET_PREVIOUS`.
+///
+/// If `new_secret` is `None`, a new random secret is generated.
+pub fn rotate_secret(new_secret: Option<String>) -> Result<(), CliError> {
+    let current_secrets = get_secrets_from_env()?;
+    let new_secret = match new_secret {
+        Some(secret) => secret,
+        None => generate_secret(),
+    };
+    if new_secret.is_empty() || new_secret.contains(',') {
+        return Err(CliError::DataError(
+            "The secret must not be empty and must not contain a comma.".to_owned(),
+        ));
+    }
+    if current_secrets.contains(&new_secret) {
+        return Err(CliError::DataError(
+            "The new secret must differ from the current and previous secrets.".to_owned(),
+        ));
+    }
+
+    println!(
+        "Update the environment configuration as follows and restart the server. Session tokens \
+        signed with the previous secrets stay valid until SECRET_PREVIOUS is removed.\n"
+    );
+    println!("SECRET={}", new_secret);
+    println!("SECRET_PREVIOUS={}", current_secrets.join(","));
+    Ok(())
+}
+
+fn generate_secret() -> String {
+    let mut data = [0u8; GENERATED_SECRET_LENGTH];
+    ring::rand::SystemRandom::new()
+        .fill(&mut data)
+        .expect("System random number generator should be available");
+    base64::engine::general_purpose::STANDARD.encode(data)
+}
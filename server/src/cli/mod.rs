@@ -1,7 +1,11 @@
 pub mod database_migration;
 pub mod file_io;
+pub mod integrity;
+pub mod manage_api_keys;
 pub mod manage_events;
+pub mod manage_feed_tokens;
 pub mod manage_passphrases;
+pub mod manage_secret;
 mod util;
 
 pub struct CliAuthTokenKey {
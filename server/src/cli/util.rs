@@ -1,6 +1,12 @@
+use chrono::Datelike;
+use ring::rand::SecureRandom;
+use std::collections::HashSet;
 use std::io::Write;
 use std::str::FromStr;
 
+/// Word list for generating human-readable passphrases (one word per line)
+const PASSPHRASE_WORDS: &str = include_str!("passphrase_words.txt");
+
 /// Ask the user interactively for some single-line value in the terminal. The user's input is
 /// converted to type [T]. In case of an error, the error is printed and the user is queried again
 /// and again with same prompt until the entered value is parsed successfully.
@@ -20,6 +26,38 @@ where
     <T as FromStr>::Err: std::fmt::Display,
     F: Fn(&T) -> Result<(), E>,
     E: std::fmt::Display,
+{
+    query_user_with_parser(prompt, |input| input.parse(), validation_function)
+}
+
+/// Ask the user interactively for a date in the terminal, using [parse_flexible_date] for parsing
+/// the input. The entered date is validated with the provided validation_function. In case of a
+/// parsing error or validation error, the error is printed and the user is queried again.
+pub fn query_user_date<F, E>(prompt: &str, validation_function: F) -> chrono::NaiveDate
+where
+    F: Fn(&chrono::NaiveDate) -> Result<(), E>,
+    E: std::fmt::Display,
+{
+    let today = chrono::Local::now().date_naive();
+    query_user_with_parser(
+        prompt,
+        |input| parse_flexible_date(input, today),
+        validation_function,
+    )
+}
+
+/// Generic implementation of [query_user_and_check] and [query_user_date], using the given
+/// parse_function to convert the user's (trimmed) input.
+fn query_user_with_parser<T, P, PE, F, E>(
+    prompt: &str,
+    parse_function: P,
+    validation_function: F,
+) -> T
+where
+    P: Fn(&str) -> Result<T, PE>,
+    PE: std::fmt::Display,
+    F: Fn(&T) -> Result<(), E>,
+    E: std::fmt::Display,
 {
     loop {
         println!("{}:", prompt);
@@ -30,7 +68,7 @@ where
             println!("Error: {}", e);
             continue;
         }
-        let value = match user_input.trim().parse() {
+        let value = match parse_function(user_input.trim()) {
             Ok(value) => value,
             Err(e) => {
                 println!("Error: {}", e);
@@ -46,6 +84,42 @@ where
     }
 }
 
+/// Parse a date, as entered by a user, in one of the formats `DD.MM.YYYY`, `YYYY-MM-DD` or
+/// `DD.MM.`. For the latter, the date is assumed to be in the year of `today` or in the next year,
+/// if the date has already passed this year.
+pub fn parse_flexible_date(
+    input: &str,
+    today: chrono::NaiveDate,
+) -> Result<chrono::NaiveDate, &'static str> {
+    const ERROR: &str = "invalid date. Please enter a date as DD.MM.YYYY, YYYY-MM-DD or DD.MM.";
+    let input = input.trim();
+    // chrono's `%Y` accepts any number of digits, so we need to check for a four-digit year
+    // ourselves, to not interpret e.g. `13.08.25` as a date in the year 25.
+    for (format, year) in [
+        ("%d.%m.%Y", input.rsplit('.').next()),
+        ("%Y-%m-%d", input.split('-').next()),
+    ] {
+        let is_four_digit_year =
+            year.is_some_and(|year| year.len() == 4 && year.bytes().all(|b| b.is_ascii_digit()));
+        if let Ok(date) = chrono::NaiveDate::parse_from_str(input, format)
+            && is_four_digit_year
+        {
+            return Ok(date);
+        }
+    }
+
+    let (day, month) = input
+        .strip_suffix('.')
+        .and_then(|day_month| day_month.split_once('.'))
+        .ok_or(ERROR)?;
+    let day: u32 = day.parse().map_err(|_| ERROR)?;
+    let month: u32 = month.parse().map_err(|_| ERROR)?;
+    chrono::NaiveDate::from_ymd_opt(today.year(), month, day)
+        .filter(|date| *date >= today)
+        .or_else(|| chrono::NaiveDate::from_ymd_opt(today.year() + 1, month, day))
+        .ok_or(ERROR)
+}
+
 /// Ask the user interactively for a boolean value in the terminal (entered as y/n). In case of an
 /// error, the error is printed and the user is queried again and again with same prompt until the
 /// entered value is parsed successfully.
@@ -76,3 +150,170 @@ pub fn query_user_bool(prompt: &str, default: Option<bool>) -> bool {
         println!("Error: unknown option. Please enter 'y' or 'n'.");
     }
 }
+
+/// Generate a random, human-readable passphrase, consisting of `word_count` words from a bundled
+/// word list, separated by dashes (e.g. "otter-lantern-maple-quartz").
+pub fn generate_passphrase(word_count: usize) -> String {
+    let words: Vec<&str> = PASSPHRASE_WORDS.lines().collect();
+    let rng = ring::rand::SystemRandom::new();
+    (0..word_count)
+        .map(|_| words[random_index(&rng, words.len())])
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Generate `count` distinct random passphrases with [generate_passphrase].
+pub fn generate_distinct_passphrases(count: usize, word_count: usize) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut result = Vec::with_capacity(count);
+    while result.len() < count {
+        let passphrase = generate_passphrase(word_count);
+        if seen.insert(passphrase.clone()) {
+            result.push(passphrase);
+        }
+    }
+    result
+}
+
+/// Derive a URL-safe event slug from the given `title`: The title is lowercased, German umlauts
+/// are transliterated (e.g. "Küche" → "kueche") and all other characters except for ASCII letters
+/// and digits are replaced by hyphens, with consecutive hyphens collapsed.
+///
+/// Since purely numeric slugs would be confused with event ids, they are prefixed with "event-".
+/// If no usable characters are left, "event" is returned.
+pub fn slugify(title: &str) -> String {
+    let mut result = String::with_capacity(title.len());
+    for c in title.to_lowercase().chars() {
+        match c {
+            'ä' => result.push_str("ae"),
+            'ö' => result.push_str("oe"),
+            'ü' => result.push_str("ue"),
+            'ß' => result.push_str("ss"),
+            c if c.is_ascii_alphanumeric() => result.push(c),
+            _ => {
+                if !result.is_empty() && !result.ends_with('-') {
+                    result.push('-');
+                }
+            }
+        }
+    }
+    let result = result.trim_end_matches('-');
+    if result.is_empty() {
+        "event".to_owned()
+    } else if result.chars().all(|c| c.is_ascii_digit()) {
+        format!("event-{}", result)
+    } else {
+        result.to_owned()
+    }
+}
+
+/// Make the given `slug` unique with respect to the `existing_slugs` by appending a numeric
+/// suffix ("-2", "-3", …), if required.
+pub fn make_slug_unique(slug: String, existing_slugs: &HashSet<String>) -> String {
+    if !existing_slugs.contains(&slug) {
+        return slug;
+    }
+    (2..)
+        .map(|i| format!("{}-{}", slug, i))
+        .find(|candidate| !existing_slugs.contains(candidate))
+        .expect("There should be an unused numeric suffix")
+}
+
+/// Get a uniformly distributed random number in the range `0..upper_bound`, using rejection
+/// sampling to avoid a modulo bias.
+fn random_index(rng: &ring::rand::SystemRandom, upper_bound: usize) -> usize {
+    let upper_bound = upper_bound as u32;
+    let limit = u32::MAX - u32::MAX % upper_bound;
+    loop {
+        let mut data = [0u8; 4];
+        rng.fill(&mut data)
+            .expect("System random number generator should be available");
+        let value = u32::from_le_bytes(data);
+        if value < limit {
+            return (value % upper_bound) as usize;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_passphrase() {
+        let passphrase = generate_passphrase(4);
+        let words: Vec<&str> = passphrase.split('-').collect();
+        assert_eq!(words.len(), 4);
+        for word in words {
+            assert!(PASSPHRASE_WORDS.lines().any(|w| w == word));
+        }
+    }
+
+    #[test]
+    fn test_generate_distinct_passphrases() {
+        let passphrases = generate_distinct_passphrases(50, 3);
+        assert_eq!(passphrases.len(), 50);
+        let distinct: HashSet<&String> = passphrases.iter().collect();
+        assert_eq!(distinct.len(), 50);
+        assert!(passphrases.iter().all(|p| p.split('-').count() == 3));
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Küche"), "kueche");
+        assert_eq!(slugify("Pfingstakademie 2025"), "pfingstakademie-2025");
+        assert_eq!(
+            slugify("  Große Öffnung: Übermorgen! "),
+            "grosse-oeffnung-uebermorgen"
+        );
+        assert_eq!(slugify("KüA -- Plan"), "kuea-plan");
+        assert_eq!(slugify("2025"), "event-2025");
+        assert_eq!(slugify("???"), "event");
+    }
+
+    #[test]
+    fn test_parse_flexible_date() {
+        let today: chrono::NaiveDate = "2025-06-15".parse().unwrap();
+        let date = |s: &str| -> chrono::NaiveDate { s.parse().unwrap() };
+        assert_eq!(
+            parse_flexible_date("13.08.2025", today),
+            Ok(date("2025-08-13"))
+        );
+        assert_eq!(
+            parse_flexible_date("1.8.2026", today),
+            Ok(date("2026-08-01"))
+        );
+        assert_eq!(
+            parse_flexible_date("2024-05-20", today),
+            Ok(date("2024-05-20"))
+        );
+        assert_eq!(
+            parse_flexible_date(" 13.08. ", today),
+            Ok(date("2025-08-13"))
+        );
+        assert_eq!(parse_flexible_date("15.06.", today), Ok(date("2025-06-15")));
+        // Dates which have already passed this year are assumed to be in the next year
+        assert_eq!(parse_flexible_date("20.05.", today), Ok(date("2026-05-20")));
+        assert!(parse_flexible_date("29.02.", today).is_err());
+        assert!(parse_flexible_date("13.08", today).is_err());
+        assert!(parse_flexible_date("31.02.2025", today).is_err());
+        // Years must be given with four digits
+        assert!(parse_flexible_date("13.08.25", today).is_err());
+        assert!(parse_flexible_date("13.08.02025", today).is_err());
+        assert!(parse_flexible_date("25-08-13", today).is_err());
+        assert!(parse_flexible_date("08/13/2025", today).is_err());
+        assert!(parse_flexible_date("", today).is_err());
+    }
+
+    #[test]
+    fn test_make_slug_unique() {
+        let mut existing = HashSet::new();
+        assert_eq!(make_slug_unique("pa25".to_owned(), &existing), "pa25");
+        existing.insert("pa25".to_owned());
+        assert_eq!(make_slug_unique("pa25".to_owned(), &existing), "pa25-2");
+        existing.insert("pa25-2".to_owned());
+        existing.insert("pa25-3".to_owned());
+        assert_eq!(make_slug_unique("pa25".to_owned(), &existing), "pa25-4");
+        assert_eq!(make_slug_unique("sa25".to_owned(), &existing), "sa25");
+    }
+}
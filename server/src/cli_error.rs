@@ -114,6 +114,10 @@ impl From<StoreError> for CliError {
                 Self::DataError(format!("Data violates policy: {}", p))
             }
             StoreError::InvalidInputData(e) => Self::DataError(e),
+            StoreError::RoomInUse { entry_count } => Self::DataError(format!(
+                "Room is still used by {} upcoming entries",
+                entry_count
+            )),
             StoreError::InvalidDataInDatabase(e) => Self::UnexpectedStoreError(e),
         }
     }
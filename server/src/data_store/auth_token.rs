@@ -1,5 +1,5 @@
 use crate::cli::CliAuthTokenKey;
-use crate::data_store::{EnumMemberNotExistingError, EventId, StoreError};
+use crate::data_store::{EnumMemberNotExistingError, EventId, PassphraseId, StoreError};
 use diesel::backend::Backend;
 use diesel::deserialize::FromSql;
 use diesel::query_builder::bind_collector::RawBytesBindCollector;
@@ -23,6 +23,9 @@ pub struct AuthToken {
     event_id: i32,
     roles: Vec<AccessRole>,
     expired_roles: Vec<AccessRole>,
+    /// The authenticated passphrases (with their role), which are currently valid. Empty for
+    /// AuthTokens created for the command line interface.
+    passphrases: Vec<(PassphraseId, AccessRole)>,
 }
 
 impl AuthToken {
@@ -38,11 +41,13 @@ impl AuthToken {
         event_id: i32,
         roles: Vec<AccessRole>,
         expired_roles: Vec<AccessRole>,
+        passphrases: Vec<(PassphraseId, AccessRole)>,
     ) -> Self {
         AuthToken {
             event_id,
             roles,
             expired_roles,
+            passphrases,
         }
     }
 
@@ -57,6 +62,7 @@ impl AuthToken {
             event_id,
             roles: vec![AccessRole::Admin, AccessRole::ServerAdmin],
             expired_roles: vec![],
+            passphrases: vec![],
         }
     }
 
@@ -98,6 +104,32 @@ impl AuthToken {
         }
     }
 
+    /// Get the id of the authenticated passphrase, which grants the given `privilege`, e.g. for
+    /// recording the acting passphrase of a modification.
+    ///
+    /// Returns None, if no such passphrase is present, in particular for AuthTokens created for
+    /// the command line interface.
+    pub fn acting_passphrase_id(&self, privilege: Privilege) -> Option<PassphraseId> {
+        let qualifying_roles = privilege.qualifying_roles();
+        self.passphrases
+            .iter()
+            .find(|(_, role)| qualifying_roles.contains(role))
+            .map(|(passphrase_id, _)| *passphrase_id)
+    }
+
+    /// Get the highest active access role, which qualifies for the given `privilege`, e.g. for
+    /// recording the role of the acting user of a modification.
+    ///
+    /// Returns None, if no such role is present.
+    pub fn acting_role(&self, privilege: Privilege) -> Option<AccessRole> {
+        let qualifying_roles = privilege.qualifying_roles();
+        self.roles
+            .iter()
+            .filter(|role| qualifying_roles.contains(role))
+            .max()
+            .copied()
+    }
+
     /// Get the list of active access roles in the API representation.
     ///
     /// This is used by the [crate::web::api::endpoints_auth::check_authorization] endpoint,
@@ -118,7 +150,8 @@ impl AuthToken {
 /// Together with [AuthToken], this structure is our main protection against accidental
 /// unauthorized-access bugs: All non-event-specific data_store access function require to pass a
 /// GlobalAuthToken and check its validity for the required privilege. An GlobalAuthToken can only
-/// be created by cli functions via [get_global_cli_authorization].
+/// be created by cli functions via [get_global_cli_authorization] and for web clients via
+/// [GlobalAuthToken::create_for_api_key].
 pub struct GlobalAuthToken {
     roles: Vec<AccessRole>,
 }
@@ -129,6 +162,28 @@ impl GlobalAuthToken {
         GlobalAuthToken { roles }
     }
 
+    /// Create a GlobalAuthToken for a web client, based on the API key sent by the client. The
+    /// token grants the [AccessRole::ServerAdmin] role, if the API key matches the configured
+    /// `server_admin_api_key` (see [crate::setup::get_server_admin_api_key_from_env]). Otherwise,
+    /// it does not grant any role.
+    pub fn create_for_api_key(api_key: Option<&str>, server_admin_api_key: Option<&str>) -> Self {
+        // Compare hashes instead of the keys themselves, to not leak the key via timing
+        let hash = |key: &str| ring::digest::digest(&ring::digest::SHA256, key.as_bytes());
+        let is_server_admin = match (api_key, server_admin_api_key) {
+            (Some(api_key), Some(server_admin_api_key)) => {
+                hash(api_key).as_ref() == hash(server_admin_api_key).as_ref()
+            }
+            _ => false,
+        };
+        GlobalAuthToken {
+            roles: if is_server_admin {
+                vec![AccessRole::ServerAdmin]
+            } else {
+                vec![]
+            },
+        }
+    }
+
     pub fn has_privilege(&self, privilege: Privilege) -> bool {
         privilege
             .qualifying_roles()
@@ -173,6 +228,7 @@ pub enum AccessRole {
     Admin = 3,
     SharableViewLink = 4,
     ServerAdmin = 5,
+    ApiReader = 6,
 }
 
 impl TryFrom<i32> for AccessRole {
@@ -235,7 +291,7 @@ impl From<AccessRole> for kueaplan_api_types::AuthorizationRole {
             AccessRole::Admin | AccessRole::ServerAdmin => {
                 kueaplan_api_types::AuthorizationRole::Admin
             }
-            AccessRole::SharableViewLink => {
+            AccessRole::SharableViewLink | AccessRole::ApiReader => {
                 kueaplan_api_types::AuthorizationRole::ParticipantSharable
             }
         }
@@ -263,6 +319,7 @@ impl AccessRole {
             AccessRole::Admin => "Admin",
             AccessRole::SharableViewLink => "Abruf per Link",
             AccessRole::ServerAdmin => "Server-Admin",
+            AccessRole::ApiReader => "API-Schlüssel",
         }
     }
 
@@ -273,7 +330,7 @@ impl AccessRole {
     pub fn can_be_managed_online(&self) -> bool {
         match self {
             AccessRole::User | AccessRole::Orga | AccessRole::SharableViewLink => true,
-            AccessRole::Admin | AccessRole::ServerAdmin => false,
+            AccessRole::Admin | AccessRole::ServerAdmin | AccessRole::ApiReader => false,
         }
     }
     /// If true, this role is a possible access role of a passphrase, which can be granted to web UI
@@ -285,7 +342,7 @@ impl AccessRole {
             | AccessRole::Orga
             | AccessRole::Admin
             | AccessRole::SharableViewLink => true,
-            AccessRole::ServerAdmin => false,
+            AccessRole::ServerAdmin | AccessRole::ApiReader => false,
         }
     }
 
@@ -296,6 +353,7 @@ impl AccessRole {
             Self::Admin,
             Self::SharableViewLink,
             Self::ServerAdmin,
+            Self::ApiReader,
         ]
         .iter()
     }
@@ -312,6 +370,7 @@ pub enum Privilege {
     SubmitParticipantEntries,
     ManageCategories,
     ManageRooms,
+    ListRoomsOfMultipleEvents,
     EditEventDetails,
     ManagePassphrases,
     ManageSecurePassphrases,
@@ -319,6 +378,8 @@ pub enum Privilege {
     DeleteEvents,
     ManageAnnouncements,
     ShowKueaPlanViaLink,
+    ManageApiKeys,
+    ManageFeedTokens,
 }
 
 impl Privilege {
@@ -335,6 +396,7 @@ impl Privilege {
                 AccessRole::Orga,
                 AccessRole::Admin,
                 AccessRole::SharableViewLink,
+                AccessRole::ApiReader,
             ],
             Privilege::ShowConfigArea => &[AccessRole::Orga, AccessRole::Admin],
             Privilege::ManageEntries => &[AccessRole::Orga, AccessRole::Admin],
@@ -343,13 +405,75 @@ impl Privilege {
             }
             Privilege::ManageCategories => &[AccessRole::Orga, AccessRole::Admin],
             Privilege::ManageRooms => &[AccessRole::Orga, AccessRole::Admin],
+            Privilege::ListRoomsOfMultipleEvents => &[AccessRole::ServerAdmin],
             Privilege::EditEventDetails => &[AccessRole::Admin],
             Privilege::ManagePassphrases => &[AccessRole::Admin],
             Privilege::ManageSecurePassphrases => &[AccessRole::ServerAdmin],
             Privilege::CreateEvents => &[AccessRole::ServerAdmin],
             Privilege::DeleteEvents => &[AccessRole::ServerAdmin],
+            Privilege::ManageApiKeys => &[AccessRole::ServerAdmin],
+            Privilege::ManageFeedTokens => &[AccessRole::ServerAdmin],
             Privilege::ManageAnnouncements => &[AccessRole::Orga, AccessRole::Admin],
             Privilege::ShowKueaPlanViaLink => &[AccessRole::SharableViewLink],
         }
     }
+
+    pub fn all() -> impl Iterator<Item = &'static Privilege> {
+        [
+            Self::ShowKueaPlan,
+            Self::ShowConfigArea,
+            Self::ManageEntries,
+            Self::SubmitParticipantEntries,
+            Self::ManageCategories,
+            Self::ManageRooms,
+            Self::ListRoomsOfMultipleEvents,
+            Self::EditEventDetails,
+            Self::ManagePassphrases,
+            Self::ManageSecurePassphrases,
+            Self::CreateEvents,
+            Self::DeleteEvents,
+            Self::ManageAnnouncements,
+            Self::ShowKueaPlanViaLink,
+            Self::ManageApiKeys,
+            Self::ManageFeedTokens,
+        ]
+        .iter()
+    }
+
+    /// Get all privileges for which the given role qualifies, according to
+    /// [Self::qualifying_roles].
+    pub fn granted_to(role: AccessRole) -> impl Iterator<Item = Privilege> {
+        Self::all()
+            .filter(move |privilege| privilege.qualifying_roles().contains(&role))
+            .copied()
+    }
+}
+
+impl From<Privilege> for kueaplan_api_types::Privilege {
+    fn from(value: Privilege) -> Self {
+        match value {
+            Privilege::ShowKueaPlan => kueaplan_api_types::Privilege::ShowKueaPlan,
+            Privilege::ShowConfigArea => kueaplan_api_types::Privilege::ShowConfigArea,
+            Privilege::ManageEntries => kueaplan_api_types::Privilege::ManageEntries,
+            Privilege::SubmitParticipantEntries => {
+                kueaplan_api_types::Privilege::SubmitParticipantEntries
+            }
+            Privilege::ManageCategories => kueaplan_api_types::Privilege::ManageCategories,
+            Privilege::ManageRooms => kueaplan_api_types::Privilege::ManageRooms,
+            Privilege::ListRoomsOfMultipleEvents => {
+                kueaplan_api_types::Privilege::ListRoomsOfMultipleEvents
+            }
+            Privilege::EditEventDetails => kueaplan_api_types::Privilege::EditEventDetails,
+            Privilege::ManagePassphrases => kueaplan_api_types::Privilege::ManagePassphrases,
+            Privilege::ManageSecurePassphrases => {
+                kueaplan_api_types::Privilege::ManageSecurePassphrases
+            }
+            Privilege::CreateEvents => kueaplan_api_types::Privilege::CreateEvents,
+            Privilege::DeleteEvents => kueaplan_api_types::Privilege::DeleteEvents,
+            Privilege::ManageAnnouncements => kueaplan_api_types::Privilege::ManageAnnouncements,
+            Privilege::ShowKueaPlanViaLink => kueaplan_api_types::Privilege::ShowKueaPlanViaLink,
+            Privilege::ManageApiKeys => kueaplan_api_types::Privilege::ManageApiKeys,
+            Privilege::ManageFeedTokens => kueaplan_api_types::Privilege::ManageFeedTokens,
+        }
+    }
 }
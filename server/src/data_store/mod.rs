@@ -20,6 +20,7 @@ use crate::cli_error::CliError::UnexpectedStoreError;
 use crate::data_store::auth_token::{AccessRole, Privilege};
 use crate::setup;
 use auth_token::{AuthToken, GlobalAuthToken};
+use serde::{Deserialize, Serialize};
 use std::fmt::{Debug, Display, Formatter};
 
 pub mod auth_token;
@@ -33,7 +34,13 @@ mod util;
 /// The DATABASE_URL must be a PosgreSQL connection url, following the schema
 /// "postgres://{user}:{password}@{host}/{database}".
 pub fn get_store_from_env() -> Result<impl KuaPlanStore, CliError> {
-    postgres::PgDataStore::new(&setup::get_database_url_from_env()?)
+    let pool_config = setup::get_db_pool_config_from_env()?;
+    log::debug!(
+        "Using database connection pool with max. {} connections and min. {} idle connections",
+        pool_config.max_size,
+        pool_config.min_idle
+    );
+    postgres::PgDataStore::new(&setup::get_database_url_from_env()?, &pool_config)
         .map_err(|err| UnexpectedStoreError(err.to_string()))
 }
 
@@ -43,9 +50,15 @@ pub type PreviousDateId = uuid::Uuid;
 pub type RoomId = uuid::Uuid;
 pub type CategoryId = uuid::Uuid;
 pub type AnnouncementId = uuid::Uuid;
+pub type EntryTemplateId = uuid::Uuid;
 pub type PassphraseId = i32;
+pub type ApiKeyId = i32;
+pub type FeedTokenId = i32;
 
 pub trait KueaPlanStoreFacade {
+    /// Check the database connection by executing a trivial query
+    fn ping(&mut self) -> Result<(), StoreError>;
+
     /// Get a filtered list of events
     ///
     /// Events are returned in chronological order, i.e. sorted by (begin, end)
@@ -57,6 +70,14 @@ pub trait KueaPlanStoreFacade {
         auth_token: &AuthToken,
         event_id: EventId,
     ) -> Result<models::ExtendedEvent, StoreError>;
+    /// Get the basic data of the events directly preceding and following the given event.
+    ///
+    /// The neighbors are `None` if the respective event id is not set for the event.
+    fn get_neighbor_events(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+    ) -> Result<models::EventNeighbors, StoreError>;
     fn create_event(
         &mut self,
         auth_token: &GlobalAuthToken,
@@ -71,15 +92,41 @@ pub trait KueaPlanStoreFacade {
     fn delete_event(&mut self, auth_token: &AuthToken, event_id: EventId)
     -> Result<(), StoreError>;
 
+    /// Set or unset the `archived` flag of the event, which hides it from event listings by default
+    fn set_event_archived(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+        archived: bool,
+    ) -> Result<(), StoreError>;
+
+    /// Create a new event with all the given rooms, categories, entries and announcements.
+    ///
+    /// `on_conflict` defines the behaviour for rooms, categories, entries, previous dates and
+    /// announcements with the id of an existing one (of any event). With
+    /// [ImportConflictMode::Skip](models::ImportConflictMode::Skip), the conflicting elements and
+    /// the entries and announcements referencing a conflicting room or category are not imported.
+    /// Otherwise, the import is aborted with `StoreError::InvalidInputData`, listing
+    /// the conflicting elements, and nothing is changed. Existing elements are never modified.
     fn import_event_with_contents(
         &mut self,
         auth_token: &GlobalAuthToken,
         data: models::EventWithContents,
+        on_conflict: models::ImportConflictMode,
     ) -> Result<EventId, StoreError>;
 
+    /// Get aggregated statistics of the event's (published, non-deleted) entries, rooms and
+    /// categories.
+    fn get_event_stats(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+    ) -> Result<models::EventStats, StoreError>;
+
     /// Get a filtered list of (published) entries of the event
     ///
-    /// Entries are returned in chronological order, i.e. sorted by (begin, end)
+    /// Entries are returned in the order given by the filter's `order_by` field, which defaults to
+    /// chronological order, i.e. sorted by (begin, end)
     fn get_published_entries_filtered(
         &mut self,
         auth_token: &AuthToken,
@@ -87,9 +134,25 @@ pub trait KueaPlanStoreFacade {
         filter: EntryFilter,
     ) -> Result<Vec<models::FullEntry>, StoreError>;
 
+    /// Get the same entries as [Self::get_published_entries_filtered], but pass them to the
+    /// `callback` in chunks of at most `chunk_size` entries, instead of collecting all entries in
+    /// memory. This is meant for very large entry lists.
+    ///
+    /// All chunks are loaded from a consistent snapshot of the database. If the callback returns
+    /// false, no further chunks are loaded.
+    fn for_each_published_entries_chunk(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+        filter: EntryFilter,
+        chunk_size: usize,
+        callback: &mut dyn FnMut(Vec<models::FullEntry>) -> bool,
+    ) -> Result<(), StoreError>;
+
     /// Get a (filtered) list of entries of the event, including entries in a non-published state.
     ///
-    /// Entries are returned in chronological order, i.e. sorted by (begin, end).
+    /// Entries are returned in the order given by the filter's `order_by` field, which defaults to
+    /// chronological order, i.e. sorted by (begin, end).
     /// In contrast to `get_published_entries_filtered()`, this function also returns entries in a
     /// non-public state (e.g. [Draft](models::EntryState::Draft) or
     /// [SubmittedForReview](models::EntryState::SubmittedForReview), but requires
@@ -113,6 +176,22 @@ pub trait KueaPlanStoreFacade {
         event_id: EventId,
     ) -> Result<Vec<(models::EntryState, i64)>, StoreError>;
 
+    /// Get the begin timestamps of all (non-deleted) entries of the event, matching the filter and
+    /// having one of the given states, in chronological order.
+    ///
+    /// This is a lightweight alternative to `get_published_entries_filtered()` and
+    /// `get_all_entries_filtered()` for statistics, which only need the entries' begin, e.g. for
+    /// counting the entries per day. Like `get_all_entries_filtered()`, requesting unpublished
+    /// states requires the [Privilege::ManageEntries]. Orga-only entries are only included for
+    /// clients with this privilege.
+    fn get_entry_begins(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+        filter: EntryFilter,
+        state_filter: &[models::EntryState],
+    ) -> Result<Vec<chrono::DateTime<chrono::Utc>>, StoreError>;
+
     fn get_entry(
         &mut self,
         auth_token: &AuthToken,
@@ -123,6 +202,11 @@ pub trait KueaPlanStoreFacade {
     /// If `extend_previous_dates` is true, the previous dates of the (existing) entry are not
     /// replaced with the given ones but instead extended by them.
     ///
+    /// If `record_previous_date` is not None and the entry exists already, a previous date with the
+    /// stored begin, end and rooms of the entry and the given comment is added, in case the update
+    /// changes any of them (see [models::FullNewEntry::record_previous_date]). The stored entry is
+    /// read within the same transaction as the update.
+    ///
     /// If `expected_last_update` is not None, it is checked against the current `last_updated`
     /// value of the entry is checked for equality before updating the entry with the given data. If
     /// it's not equal to the given value, the update is rejected with a `ConcurrentEditConflict`
@@ -144,8 +228,26 @@ pub trait KueaPlanStoreFacade {
         auth_token: &AuthToken,
         entry: models::FullNewEntry,
         extend_previous_dates: bool,
+        record_previous_date: Option<String>,
         expected_last_update: Option<chrono::DateTime<chrono::Utc>>,
     ) -> Result<bool, StoreError>;
+    /// Create or update the given entries of the event in a single transaction, e.g. when
+    /// importing entries from a file. This requires the [Privilege::ManageEntries] privilege.
+    ///
+    /// Existing entries with the same id are updated, but keep their current state, so that
+    /// importing the same file again does not revert e.g. the publishing of the entries. Entries,
+    /// which have been deleted in the meantime, are not restored but skipped.
+    ///
+    /// # return value
+    /// - `Ok((created, updated))` with the number of created and updated entries
+    /// - `Err(StoreError::InvalidInputData)` if any of the entries does not belong to the event
+    /// - `Err(_)` if something different went wrong, as usual. No entry is changed in this case.
+    fn import_entries(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+        entries: Vec<models::FullNewEntry>,
+    ) -> Result<(usize, usize), StoreError>;
     fn patch_entry(
         &mut self,
         auth_token: &AuthToken,
@@ -163,17 +265,201 @@ pub trait KueaPlanStoreFacade {
         event_id: EventId,
         entry_id: EntryId,
     ) -> Result<(), StoreError>;
+    /// Get the audit log of modifications of the given entry via [Self::create_or_update_entry],
+    /// [Self::patch_entry] and [Self::delete_entry], ordered by time.
+    ///
+    /// # return value
+    /// - `Err(StoreError::NotExisting)` if the entry does not exist in the given event
+    fn get_entry_audit_log(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+        entry_id: EntryId,
+    ) -> Result<Vec<models::EntryAuditLogRecord>, StoreError>;
+    /// Move an existing entry to a different event, assigning it to the given category of the
+    /// target event.
+    ///
+    /// Since rooms are specific to an event, all room associations of the entry and its previous
+    /// dates are removed. This requires the ManageEntries privilege for the source event (checked
+    /// with `auth_token`) as well as for the target event (checked with `target_auth_token`).
+    ///
+    /// # return value
+    /// - `Err(StoreError::NotExisting)` if the entry does not exist or has been deleted
+    /// - `Err(StoreError::InvalidInputData)` if the category does not belong to the target event
+    fn move_entry(
+        &mut self,
+        auth_token: &AuthToken,
+        target_auth_token: &AuthToken,
+        entry_id: EntryId,
+        target_event_id: EventId,
+        target_category_id: CategoryId,
+    ) -> Result<(), StoreError>;
+    /// Create a copy of an existing entry in a different event, assigned to the given category of
+    /// the target event, and return the id of the new entry.
+    ///
+    /// The copy gets a new id, as well as its previous dates. Since rooms are specific to an event,
+    /// the copy and its previous dates have no rooms. This requires read access to the entry
+    /// (checked with `auth_token`) and the ManageEntries privilege for the target event (checked
+    /// with `target_auth_token`). The orga comment is only copied if `auth_token` grants the
+    /// ManageEntries privilege for the source event.
+    ///
+    /// # return value
+    /// - `Err(StoreError::NotExisting)` if the entry does not exist or has been deleted
+    /// - `Err(StoreError::InvalidInputData)` if the category does not belong to the target event
+    fn copy_entry_to_event(
+        &mut self,
+        auth_token: &AuthToken,
+        target_auth_token: &AuthToken,
+        entry_id: EntryId,
+        target_event_id: EventId,
+        target_category_id: CategoryId,
+    ) -> Result<EntryId, StoreError>;
     fn create_or_update_previous_date(
         &mut self,
         auth_token: &AuthToken,
         previous_date: models::FullPreviousDate,
     ) -> Result<bool, StoreError>;
+    /// Delete a previous date of the entry. Previous dates are only marked as deleted, so they can
+    /// be restored via [restore_previous_date].
     fn delete_previous_date(
         &mut self,
         auth_token: &AuthToken,
         entry_id: EntryId,
         previous_date_id: PreviousDateId,
     ) -> Result<(), StoreError>;
+    /// Get all deleted previous dates of the entry, e.g. for restoring them via
+    /// [restore_previous_date]. Requires the [Privilege::ManageEntries] privilege.
+    fn get_deleted_previous_dates(
+        &mut self,
+        auth_token: &AuthToken,
+        entry_id: EntryId,
+    ) -> Result<Vec<models::FullPreviousDate>, StoreError>;
+    /// Restore a deleted previous date of the entry by resetting its `deleted` flag.
+    ///
+    /// # return value
+    /// - `Err(StoreError::NotExisting)` if the previous date does not exist in this entry
+    /// - `Err(StoreError::ConflictEntityExists)` if the previous date is not deleted
+    fn restore_previous_date(
+        &mut self,
+        auth_token: &AuthToken,
+        entry_id: EntryId,
+        previous_date_id: PreviousDateId,
+    ) -> Result<(), StoreError>;
+    /// Get the distinct non-empty `responsible_person` values of all (non-deleted) entries of the
+    /// event, e.g. for suggesting them when editing entries.
+    ///
+    /// The values are ordered by the number of entries using them (descending), then
+    /// alphabetically. Non-published entries are only taken into account if the `auth_token` has
+    /// the [Privilege::ManageEntries] privilege.
+    fn get_distinct_responsible_persons(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+    ) -> Result<Vec<String>, StoreError>;
+    /// Replace the `responsible_person` of all (non-deleted) entries of the event, which exactly
+    /// match `from`, with `to`, e.g. when a team member drops out. This requires the
+    /// [Privilege::ManageEntries] privilege.
+    ///
+    /// All entries are updated in a single transaction and a record is added to the entry audit log
+    /// for each of them.
+    ///
+    /// Returns the ids of the changed entries.
+    fn reassign_responsible_person(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<EntryId>, StoreError>;
+    /// Assign all given entries of the event to the category `category_id`, e.g. after
+    /// reorganising the categories. This requires the [Privilege::ManageEntries] privilege.
+    ///
+    /// Deleted entries and entries, which are already in the category, are left unchanged. All
+    /// other entries are updated in a single transaction and a record is added to the entry audit
+    /// log for each of them.
+    ///
+    /// # return value
+    /// - `Ok(entry_ids)` with the ids of the changed entries
+    /// - `Err(StoreError::InvalidInputData)` if any of the entries does not exist in the event or
+    ///   the category does not belong to the event or has been deleted. In this case, no entry is
+    ///   changed.
+    fn bulk_set_category(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+        entry_ids: &[EntryId],
+        category_id: CategoryId,
+    ) -> Result<Vec<EntryId>, StoreError>;
+    /// Set the `is_cancelled` flag of all (non-deleted) entries of the category `category_id` to
+    /// `cancelled`, e.g. when a whole activity track is dropped. Use `cancelled = false` for
+    /// un-cancelling the entries again. This requires the [Privilege::ManageEntries] privilege.
+    ///
+    /// Entries, which already have the requested flag value, are left unchanged. All other entries
+    /// are updated in a single transaction and a record is added to the entry audit log for each of
+    /// them.
+    ///
+    /// # return value
+    /// - `Ok(entry_ids)` with the ids of the changed entries
+    /// - `Err(StoreError::NotExisting)` if the category does not exist in the event
+    fn cancel_entries_in_category(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+        category_id: CategoryId,
+        cancelled: bool,
+    ) -> Result<Vec<EntryId>, StoreError>;
+    /// Shift the begin and end of all (non-deleted) entries of the event, which match the
+    /// `filter`, by `offset`, e.g. when the schedule of a whole day slips. This requires the
+    /// [Privilege::ManageEntries] privilege.
+    ///
+    /// Entries of all states are shifted. For each entry, a previous date with the pre-shift
+    /// begin, end and rooms and the given `previous_date_comment` is created. All entries are
+    /// updated in a single transaction and a record is added to the entry audit log for each of
+    /// them.
+    ///
+    /// Returns the ids of the changed entries.
+    fn shift_entries(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+        filter: EntryFilter,
+        offset: chrono::Duration,
+        previous_date_comment: &str,
+    ) -> Result<Vec<EntryId>, StoreError>;
+    /// Get the ids of those of the given entries, which are visible to all clients with
+    /// [Privilege::ShowKueaPlan], i.e. which are published and not orga-only (see
+    /// [models::Entry::is_public]). Deleted entries and entries of other events are included,
+    /// according to their last state. This is used for deciding which live update subscribers may
+    /// be notified about changes of the entries.
+    fn get_public_entry_ids(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+        entry_ids: &[EntryId],
+    ) -> Result<Vec<EntryId>, StoreError>;
+
+    /// Get all changes to the published entries and the rooms of the event since the given point
+    /// in time, e.g. for incrementally updating a client-side copy of the KüA plan.
+    ///
+    /// Entries which have been deleted or are not published anymore are reported in
+    /// `deleted_entries`. The full list of rooms is only included if any room has been changed.
+    /// If `since` is None, all published entries and all rooms are returned.
+    fn get_updates_since(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<models::Updates, StoreError>;
+    /// Get all previous dates of all (non-deleted) entries of the event, grouped by entry
+    ///
+    /// Entries are ordered by their (current) begin, previous dates of each entry by their begin.
+    /// Entries without previous dates are not included. Non-published entries are only included
+    /// if the `auth_token` has the [Privilege::ManageEntries] privilege.
+    fn get_all_previous_dates(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+    ) -> Result<Vec<models::EntryPreviousDates>, StoreError>;
     /// Cound the number of public non-canceled entries of the event per category
     ///
     /// The returned map only includes categories with at least one entry.
@@ -206,6 +492,18 @@ pub trait KueaPlanStoreFacade {
         auth_token: &AuthToken,
         event_id: EventId,
     ) -> Result<Vec<models::Room>, StoreError>;
+    /// Get the (non-deleted) rooms of multiple events at once, grouped by event, e.g. for an
+    /// overview of the room usage of adjacent events.
+    ///
+    /// The result contains one list of rooms for each of the given `event_ids` (in the given
+    /// order), even if the event has no rooms. Requires the global
+    /// [Privilege::ListRoomsOfMultipleEvents]. If any of the events does not exist, the request is
+    /// rejected as a whole with [StoreError::NotExisting].
+    fn get_rooms_for_events(
+        &mut self,
+        auth_token: &GlobalAuthToken,
+        event_ids: &[EventId],
+    ) -> Result<Vec<(EventId, Vec<models::Room>)>, StoreError>;
     /// Create a new room or update the existing entry with the same id.
     ///
     /// # return value
@@ -213,12 +511,33 @@ pub trait KueaPlanStoreFacade {
     /// - `Ok(false)` if an existing room has been updated, successfully
     /// - `Err(StoreError::ConflictEntityExists)` if the room exists but could not be updated
     ///   (assigned to another event or deleted already)
+    /// - `Err(StoreError::ConcurrentEditConflict)` if `expected_last_update` is given and does not
+    ///   match the `last_updated` timestamp of the existing room
     /// - `Err(_)` if something different went wrong, as usual
     fn create_or_update_room(
         &mut self,
         auth_token: &AuthToken,
         room: models::NewRoom,
+        expected_last_update: Option<chrono::DateTime<chrono::Utc>>,
     ) -> Result<bool, StoreError>;
+    /// Change the given fields of an existing room. Fields which are None in `room_data` are not
+    /// changed.
+    ///
+    /// # return value
+    /// - `Err(StoreError::NotExisting)` if the room does not exist or has been deleted
+    fn patch_room(
+        &mut self,
+        auth_token: &AuthToken,
+        room_id: RoomId,
+        room_data: models::RoomPatch,
+    ) -> Result<(), StoreError>;
+    /// Delete the room, replacing it with the `replace_with_rooms` in all entries and previous
+    /// dates, and appending `replace_with_room_comment` to the room comment of the affected
+    /// entries.
+    ///
+    /// If non-deleted entries, which have not ended yet, are assigned to the room, the deletion
+    /// needs to be confirmed, either by `force` or by giving any `replace_with_rooms`. Otherwise,
+    /// `Err(StoreError::RoomInUse)` is returned with the number of such entries.
     fn delete_room(
         &mut self,
         auth_token: &AuthToken,
@@ -226,15 +545,59 @@ pub trait KueaPlanStoreFacade {
         room_id: RoomId,
         replace_with_rooms: &[RoomId],
         replace_with_room_comment: &str,
+        force: bool,
+    ) -> Result<(), StoreError>;
+    /// Merge the room `source_room_id` into the room `target_room_id`.
+    ///
+    /// All entries, previous dates and announcements in the source room are assigned to the target
+    /// room (without creating duplicate room associations) and the source room is deleted. This
+    /// requires the ManageRooms and ManageEntries privileges.
+    ///
+    /// # return value
+    /// - `Err(StoreError::NotExisting)` if the source room does not exist or has been deleted
+    /// - `Err(StoreError::InvalidInputData)` if the target room does not belong to the event, has
+    ///   been deleted or is the source room itself
+    fn merge_rooms(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+        source_room_id: RoomId,
+        target_room_id: RoomId,
     ) -> Result<(), StoreError>;
 
+    /// Find all pairs of (non-deleted, non-cancelled) entries of the event, which share a room and
+    /// overlap in time, regardless of their `is_exclusive` flag.
+    ///
+    /// Entries which only touch each other (i.e. one ends when the other begins) are not
+    /// considered overlapping. The pairs are ordered by room title, then by the begin of the
+    /// entries. Non-published entries are only taken into account if the `auth_token` has the
+    /// [Privilege::ManageEntries] privilege.
+    fn find_room_double_bookings(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+    ) -> Result<Vec<models::RoomDoubleBooking>, StoreError>;
+
     fn get_categories(
         &mut self,
         auth_token: &AuthToken,
         event_id: EventId,
     ) -> Result<Vec<models::Category>, StoreError>;
+    /// Get all categories of the event together with the number of their (public, non-deleted)
+    /// entries.
+    ///
+    /// Categories are returned in the same order as by [Self::get_categories], including
+    /// categories without any entries.
+    fn get_category_entry_counts(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+    ) -> Result<Vec<(models::Category, i64)>, StoreError>;
     /// Create a new category or update the existing entry with the same id.
     ///
+    /// If the category's `sort_key` is already used by another (non-deleted) category of the event,
+    /// the next free value is used instead, to keep the order of the categories deterministic.
+    ///
     /// # return value
     /// - `Ok(true)` if the category has been created, successfully
     /// - `Ok(false)` if an existing category has been updated, successfully
@@ -246,6 +609,18 @@ pub trait KueaPlanStoreFacade {
         auth_token: &AuthToken,
         category: models::NewCategory,
     ) -> Result<bool, StoreError>;
+    /// Change the order of all categories of the event by setting their `sort_key` according to
+    /// their index in `ordered_ids`.
+    ///
+    /// # return value
+    /// - `Err(StoreError::InvalidInputData)` if `ordered_ids` does not contain exactly the
+    ///   (non-deleted) categories of the event, each of them once.
+    fn reorder_categories(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+        ordered_ids: Vec<CategoryId>,
+    ) -> Result<(), StoreError>;
     fn delete_category(
         &mut self,
         auth_token: &AuthToken,
@@ -253,6 +628,27 @@ pub trait KueaPlanStoreFacade {
         category_id: CategoryId,
         replacement_category: Option<CategoryId>,
     ) -> Result<(), StoreError>;
+    /// Get all deleted categories of the event, e.g. for restoring them via [restore_category].
+    /// Requires the [Privilege::ManageCategories] privilege.
+    fn get_deleted_categories(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+    ) -> Result<Vec<models::Category>, StoreError>;
+    /// Restore a deleted category of the event by resetting its `deleted` flag.
+    ///
+    /// If its `sort_key` has been taken by another category in the meantime, the next free value is
+    /// used instead (see [Self::create_or_update_category]).
+    ///
+    /// # return value
+    /// - `Err(StoreError::NotExisting)` if the category does not exist in this event
+    /// - `Err(StoreError::ConflictEntityExists)` if the category is not deleted
+    fn restore_category(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+        category_id: CategoryId,
+    ) -> Result<(), StoreError>;
 
     fn get_announcements(
         &mut self,
@@ -260,6 +656,16 @@ pub trait KueaPlanStoreFacade {
         event_id: EventId,
         filter: Option<AnnouncementFilter>,
     ) -> Result<Vec<models::FullAnnouncement>, StoreError>;
+    /// Get a single announcement of the event with its category and room associations.
+    ///
+    /// Returns `Err(StoreError::NotExisting)` if the announcement does not exist in this event or
+    /// has been deleted.
+    fn get_announcement(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+        announcement_id: AnnouncementId,
+    ) -> Result<models::FullAnnouncement, StoreError>;
     /// Create a new announcement or update the existing announcement with the same id.
     ///
     /// # return value
@@ -274,11 +680,17 @@ pub trait KueaPlanStoreFacade {
         announcement: models::FullNewAnnouncement,
         expected_last_update: Option<chrono::DateTime<chrono::Utc>>,
     ) -> Result<bool, StoreError>;
+    /// Change selected attributes of an existing announcement.
+    ///
+    /// If `expected_last_update` is not None, it is checked against the current `last_updated`
+    /// value of the announcement before applying the changes. If it's not equal, the update is
+    /// rejected with a `ConcurrentEditConflict` error.
     fn patch_announcement(
         &mut self,
         auth_token: &AuthToken,
         announcement_id: AnnouncementId,
         announcement_data: models::AnnouncementPatch,
+        expected_last_update: Option<chrono::DateTime<chrono::Utc>>,
     ) -> Result<(), StoreError>;
     fn delete_announcement(
         &mut self,
@@ -287,10 +699,40 @@ pub trait KueaPlanStoreFacade {
         announcement_id: AnnouncementId,
     ) -> Result<(), StoreError>;
 
+    /// Get all entry templates of the event, ordered by name
+    fn list_entry_templates(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+    ) -> Result<Vec<models::EntryTemplate>, StoreError>;
+    /// Create a new entry template.
+    ///
+    /// Returns `Err(StoreError::ConflictEntityExists)` if a template with the same id exists
+    /// already. Entry templates cannot be updated.
+    fn create_entry_template(
+        &mut self,
+        auth_token: &AuthToken,
+        template: models::EntryTemplate,
+    ) -> Result<(), StoreError>;
+    fn delete_entry_template(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+        template_id: EntryTemplateId,
+    ) -> Result<(), StoreError>;
+
     /// Try to authenticate a client as a new access role for the given event, using the given
     /// passphrase.
     ///
     /// On success, the given session token is updated with the new passphrase id.
+    ///
+    /// Since passphrases are stored as hash, this requires verifying the given passphrase against
+    /// the hash of each passphrase of the event (unless the plaintext is available), which is
+    /// intentionally slow.
+    ///
+    /// If the passphrase matches multiple valid passphrases of the event with different access
+    /// roles (which may only happen for legacy or imported data), the login is rejected with
+    /// `Err(StoreError::NotExisting)`.
     fn authenticate_with_passphrase(
         &mut self,
         event_id: i32,
@@ -307,12 +749,21 @@ pub trait KueaPlanStoreFacade {
         session_token: &mut SessionToken,
     ) -> Result<(), StoreError>;
 
+    /// List the access roles of the given `session_token` at all events, as (event id, role)
+    /// pairs, sorted by event id and role.
+    ///
+    /// All passphrases of the session token are resolved to their event and role in a single query.
+    /// Passphrases, which are not valid currently, and special roles, which cannot be granted by
+    /// passphrases, are omitted.
     fn list_all_access_roles(
         &mut self,
         session_token: &SessionToken,
     ) -> Result<Vec<(EventId, AccessRole)>, StoreError>;
 
-    /// Get an [AuthToken] instance for a client, representing the client's access roles
+    /// Get an [AuthToken] instance for a client, representing the client's access roles.
+    ///
+    /// If the SessionToken carries a valid API key of the event, the [AccessRole::ApiReader] role
+    /// is included.
     fn get_auth_token_for_session(
         &mut self,
         session_token: &SessionToken,
@@ -332,6 +783,12 @@ pub trait KueaPlanStoreFacade {
 
     /// Create a new passphrase
     ///
+    /// The passphrase is stored as argon2 hash. The plaintext is only kept for passphrases of role
+    /// [AccessRole::User], which are required in plaintext for the print templates.
+    ///
+    /// Returns `Err(StoreError::ConflictEntityExists)` if the event already has a passphrase with
+    /// the same secret (regardless of its role).
+    ///
     /// returns the id of the new passphrase.
     fn create_passphrase(
         &mut self,
@@ -355,7 +812,8 @@ pub trait KueaPlanStoreFacade {
 
     /// List all passphrases of the event, for management purposes. Requires
     /// [Privilege::ManagePassphrases]. Actual passphrase text is obfuscated (only final sixth of
-    /// the letters visible).
+    /// the letters visible). For passphrases, of which only the hash is stored, a fully obfuscated
+    /// placeholder text is returned.
     fn get_passphrases(
         &mut self,
         auth_token: &AuthToken,
@@ -370,12 +828,118 @@ pub trait KueaPlanStoreFacade {
         auth_token: &AuthToken,
         event_id: EventId,
     ) -> Result<Vec<models::Passphrase>, StoreError>;
+
+    /// List all passphrases of the event with their full data, including the hash and the
+    /// unobfuscated plaintext (if stored), for creating a backup. Requires
+    /// [Privilege::ManageSecurePassphrases], i.e. this is only available on the command line.
+    fn get_passphrases_for_backup(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+    ) -> Result<Vec<models::PassphraseBackup>, StoreError>;
+
+    /// Recreate the given passphrases (as returned by [get_passphrases_for_backup()]) for the
+    /// event. Requires [Privilege::ManageSecurePassphrases].
+    ///
+    /// The passphrases are created with new ids. References between the given passphrases
+    /// (`derivable_from_passphrase`) are mapped to the new ids accordingly. Stored hashes are
+    /// taken over as they are; passphrases with only a plaintext are hashed, like in
+    /// [create_passphrase()]. All passphrases are created in a single transaction.
+    ///
+    /// Like [create_passphrase()], fails with [StoreError::ConflictEntityExists] if a passphrase
+    /// matches an existing passphrase of the event. Passphrases, which are only available as hash,
+    /// can only be compared to existing plaintext passphrases and identical hashes.
+    ///
+    /// Returns the number of created passphrases.
+    fn import_passphrases_from_backup(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+        passphrases: Vec<models::PassphraseBackup>,
+    ) -> Result<usize, StoreError>;
+
+    /// Add the hash to all passphrases, which are only stored as plaintext (e.g. because they have
+    /// been created before passphrase hashing was introduced), and remove the plaintext where it is
+    /// not required. Requires global [Privilege::ManageSecurePassphrases].
+    ///
+    /// Returns the number of hashed passphrases.
+    fn hash_plaintext_passphrases(
+        &mut self,
+        auth_token: &GlobalAuthToken,
+    ) -> Result<usize, StoreError>;
+
+    /// List all API keys of the event. Requires [Privilege::ManageApiKeys], i.e. this is only
+    /// available on the command line.
+    fn get_api_keys(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+    ) -> Result<Vec<models::ApiKey>, StoreError>;
+
+    /// Create a new read-only API key for the event. Requires [Privilege::ManageApiKeys].
+    ///
+    /// The key is generated randomly and only its SHA-256 hash is stored. API clients using the
+    /// key (via the `Authorization: Bearer` header) are given the [AccessRole::ApiReader] role.
+    ///
+    /// Returns the id of the new API key and the key itself, which cannot be retrieved later.
+    fn create_api_key(
+        &mut self,
+        auth_token: &AuthToken,
+        api_key: models::NewApiKey,
+    ) -> Result<(ApiKeyId, String), StoreError>;
+
+    /// Revoke (i.e. delete) the API key of the event. Requires [Privilege::ManageApiKeys].
+    fn delete_api_key(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+        api_key_id: ApiKeyId,
+    ) -> Result<(), StoreError>;
+
+    /// List all feed tokens of the event, including revoked ones. Requires
+    /// [Privilege::ManageFeedTokens], i.e. this is only available on the command line.
+    fn get_feed_tokens(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+    ) -> Result<Vec<models::FeedToken>, StoreError>;
+
+    /// Create a new feed token for subscribing to the calendar feeds of the event with the given
+    /// role. Requires [Privilege::ManageFeedTokens].
+    ///
+    /// Only the token's metadata is stored. The token string to be handed out is created from the
+    /// returned id via [crate::auth_session::FeedAccessToken].
+    fn create_feed_token(
+        &mut self,
+        auth_token: &AuthToken,
+        feed_token: models::NewFeedToken,
+    ) -> Result<FeedTokenId, StoreError>;
+
+    /// Revoke the feed token of the event. Requires [Privilege::ManageFeedTokens].
+    ///
+    /// The token is kept in the database (marked as revoked), such that its id is never reused and
+    /// previously handed out token strings stay invalid.
+    fn revoke_feed_token(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+        feed_token_id: FeedTokenId,
+    ) -> Result<(), StoreError>;
+
+    /// Get an AuthToken for a client that has presented the (already signature-checked) feed token
+    /// with the given id. If the feed token does not exist, belongs to another event or has been
+    /// revoked, the returned AuthToken does not grant any role.
+    fn get_auth_token_for_feed_token(
+        &mut self,
+        event_id: EventId,
+        feed_token_id: FeedTokenId,
+    ) -> Result<AuthToken, StoreError>;
 }
 
 /// Filter options for retrieving entries from the store via KueaPlanStoreFacade::get_entries_filtered()
 ///
 /// Can be constructed through the EntryFilterBuilder
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct EntryFilter {
     /// Filter for entries that end after the given point in time (this includes entries that span
     /// over this point in time)
@@ -400,6 +964,32 @@ pub struct EntryFilter {
     pub rooms: Option<Vec<uuid::Uuid>>,
     /// If true, filter for entries without any room
     pub no_room: bool,
+    /// Filter for entries that contain the given text (case-insensitive) in their title,
+    /// description or comment
+    pub search: Option<String>,
+    /// If true, entries which are only room reservations (`is_room_reservation`) are excluded
+    pub exclude_reservations: bool,
+    /// Sort order of the resulting entries
+    pub order_by: EntryOrder,
+}
+
+/// Sort order for retrieving entries from the store via an [EntryFilter]
+///
+/// All orders fall back to sorting by begin, end and id for entries with equal sort keys, such
+/// that the order is always deterministic.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryOrder {
+    /// Sort by begin, end and id of the entries
+    #[default]
+    Begin,
+    /// Sort by title (case-insensitive)
+    Title,
+    /// Sort by the title of the entries' first room (in alphabetical order). Entries without
+    /// room are sorted last.
+    Room,
+    /// Sort by the entries' category, in the order of the categories' sort keys
+    Category,
 }
 
 impl EntryFilter {
@@ -408,6 +998,17 @@ impl EntryFilter {
             result: Self::default(),
         }
     }
+
+    /// Check if the filter restricts the set of entries by any criterion, i.e. if it does not
+    /// match all (non-reservation) entries of the event.
+    pub fn has_criteria(&self) -> bool {
+        self.after.is_some()
+            || self.before.is_some()
+            || self.categories.is_some()
+            || self.rooms.is_some()
+            || self.no_room
+            || self.search.is_some()
+    }
 }
 
 /// Builder for constructing EntryFilter objects
@@ -449,12 +1050,17 @@ impl EntryFilterBuilder {
     }
 
     /// Add filter to only include entries that don't have a room assigned
-    #[allow(dead_code)]
     pub fn without_room(mut self) -> Self {
         self.result.no_room = true;
         self
     }
 
+    /// Add filter to omit entries which are only room reservations
+    pub fn exclude_reservations(mut self) -> Self {
+        self.result.exclude_reservations = true;
+        self
+    }
+
     /// Create the EntryFilter object
     pub fn build(self) -> EntryFilter {
         self.result
@@ -472,6 +1078,10 @@ pub struct EventFilter {
     /// Filter for entries that begin at or before the given date (this includes events that span
     /// over this day)
     pub before: Option<chrono::NaiveDate>,
+    /// If true, archived events are included. Otherwise, they are excluded.
+    pub include_archived: bool,
+    /// Filter for events whose title contains the given text (case-insensitive)
+    pub title_search: Option<String>,
 }
 
 impl EventFilter {
@@ -500,6 +1110,16 @@ impl EventFilterBuilder {
         self.result.before = Some(before);
         self
     }
+    /// Include archived events, which are excluded by default
+    pub fn include_archived(mut self) -> Self {
+        self.result.include_archived = true;
+        self
+    }
+    /// Add filter, to only include events whose title contains the given text (case-insensitive)
+    pub fn matching_title(mut self, text: String) -> Self {
+        self.result.title_search = Some(text);
+        self
+    }
     /// Create the EventFilter object
     pub fn build(self) -> EventFilter {
         self.result
@@ -508,13 +1128,21 @@ impl EventFilterBuilder {
 
 #[allow(clippy::enum_variant_names)]
 pub enum AnnouncementFilter {
+    /// Announcements to be shown with the KüA-Plan of the given (effective) date at any time of
+    /// the day, i.e. including announcements with a time window (`begin_time` or `end_time`).
     ForDate(chrono::NaiveDate),
+    /// Announcements to be shown with the KüA-Plan at the given point in time, i.e. for the
+    /// effective date of the timestamp and, if the announcement has a time window, only when the
+    /// timestamp's local time (in the event's timezone) is within the window.
+    ForDateTime(chrono::DateTime<chrono::Utc>),
     ForCategory(CategoryId),
     ForRoom(RoomId),
 }
 
 pub trait KuaPlanStore: Send + Sync {
     fn get_facade<'a>(&'a self) -> Result<Box<dyn KueaPlanStoreFacade + 'a>, StoreError>;
+    /// Get the number of database connections of the connection pool, which are currently in use
+    fn connections_in_use(&self) -> u32;
 }
 
 #[derive(Debug)]
@@ -555,6 +1183,9 @@ pub enum StoreError {
     /// This is also used when the requested action would violate data integrity constraints (e.g.
     /// leave dangling references).
     InvalidInputData(String),
+    /// The room has not been deleted, because it is still used by `entry_count` upcoming entries
+    /// and deletion has not been confirmed.
+    RoomInUse { entry_count: i64 },
     /// Some data queried from the database could not be deserialized. See string description for
     /// details.
     InvalidDataInDatabase(String),
@@ -636,6 +1267,9 @@ impl std::fmt::Display for StoreError {
             Self::InvalidInputData(e) => {
                 write!(f, "Data to be stored in database is not valid: {}", e)
             }
+            Self::RoomInUse { entry_count } => {
+                write!(f, "Room is still used by {} upcoming entries.", entry_count)
+            }
             StoreError::InvalidDataInDatabase(e) => {
                 write!(f, "Data queried from database could not be deserialized: {}", e)
             },
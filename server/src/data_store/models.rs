@@ -1,5 +1,8 @@
 use crate::data_store::auth_token::AccessRole;
-use crate::data_store::{EntryId, EnumMemberNotExistingError, EventId, PassphraseId};
+use crate::data_store::{
+    ApiKeyId, CategoryId, EntryId, EntryTemplateId, EnumMemberNotExistingError, EventId,
+    FeedTokenId, PassphraseId, RoomId,
+};
 use chrono::{DateTime, Utc, naive::NaiveDate};
 use diesel::associations::BelongsTo;
 use diesel::deserialize::FromSql;
@@ -8,6 +11,7 @@ use diesel::query_builder::bind_collector::RawBytesBindCollector;
 use diesel::serialize::ToSql;
 use diesel::{AsExpression, FromSqlRow};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 use uuid::Uuid;
 
 macro_rules! impl_to_sql_for_enum {
@@ -41,6 +45,38 @@ macro_rules! impl_to_sql_for_enum {
     };
 }
 
+/// Implement diesel's ToSql and FromSql traits for a serde-serializable type, such that it can be
+/// stored in a JSONB column
+macro_rules! impl_sql_for_json {
+    ($T:ty) => {
+        impl<DB> FromSql<diesel::sql_types::Jsonb, DB> for $T
+        where
+            DB: diesel::backend::Backend,
+            serde_json::Value: FromSql<diesel::sql_types::Jsonb, DB>,
+        {
+            fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+                let value = serde_json::Value::from_sql(bytes)?;
+                Ok(serde_json::from_value(value)?)
+            }
+        }
+
+        impl<DB> ToSql<diesel::sql_types::Jsonb, DB> for $T
+        where
+            DB: diesel::backend::Backend,
+            for<'c> DB: diesel::backend::Backend<BindCollector<'c> = RawBytesBindCollector<DB>>,
+            serde_json::Value: ToSql<diesel::sql_types::Jsonb, DB>,
+        {
+            fn to_sql<'b>(
+                &'b self,
+                out: &mut diesel::serialize::Output<'b, '_, DB>,
+            ) -> diesel::serialize::Result {
+                let value = serde_json::to_value(self)?;
+                value.to_sql(&mut out.reborrow())
+            }
+        }
+    };
+}
+
 #[derive(Clone, Debug, Queryable, Selectable, Insertable, AsChangeset)]
 #[diesel(table_name=super::schema::events, treat_none_as_null=true)]
 pub struct Event {
@@ -50,6 +86,16 @@ pub struct Event {
     pub begin_date: NaiveDate,
     pub end_date: NaiveDate,
     pub slug: Option<String>,
+    /// Local time of the begin of the event on its first (effective) day. If None, the event
+    /// starts with the EFFECTIVE_BEGIN_OF_DAY.
+    pub begin_time: Option<chrono::NaiveTime>,
+    /// Local time of the end of the event on its last (effective) day. If None, the event ends
+    /// with the EFFECTIVE_BEGIN_OF_DAY of the following day.
+    pub end_time: Option<chrono::NaiveTime>,
+    /// Archived events are hidden from event listings by default. This flag can only be changed via
+    /// [KueaPlanStoreFacade::set_event_archived](super::KueaPlanStoreFacade::set_event_archived).
+    #[diesel(skip_update)]
+    pub archived: bool,
 }
 
 impl From<kueaplan_api_types::Event> for Event {
@@ -60,6 +106,9 @@ impl From<kueaplan_api_types::Event> for Event {
             begin_date: value.begin_date,
             end_date: value.end_date,
             slug: value.slug,
+            begin_time: value.begin_time,
+            end_time: value.end_time,
+            archived: value.archived,
         }
     }
 }
@@ -72,6 +121,9 @@ impl From<Event> for kueaplan_api_types::Event {
             begin_date: value.begin_date,
             end_date: value.end_date,
             slug: value.slug,
+            begin_time: value.begin_time,
+            end_time: value.end_time,
+            archived: value.archived,
         }
     }
 }
@@ -99,7 +151,7 @@ impl TryFrom<kueaplan_api_types::ExtendedEvent> for ExtendedEvent {
                 timezone: value
                     .timezone
                     .parse()
-                    .map_err(|e| format!("Could not parse event's timezone: {}", e))?,
+                    .map_err(|_| format!("Unbekannte Zeitzone: {}", value.timezone))?,
                 effective_begin_of_day: value.effective_begin_of_day,
             },
             default_time_schedule: value.default_time_schedule.into(),
@@ -259,6 +311,45 @@ impl From<EventDayTimeSchedule> for kueaplan_api_types::EventDayTimeSchedule {
     }
 }
 
+/// List of external links (e.g. to material) of an entry
+#[derive(Serialize, Deserialize, Clone, Debug, AsExpression, FromSqlRow)]
+#[diesel(sql_type = diesel::sql_types::Jsonb)]
+#[serde(transparent)]
+pub struct EntryLinks(pub Vec<EntryLink>);
+
+impl_sql_for_json!(EntryLinks);
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct EntryLink {
+    pub label: String,
+    pub url: String,
+}
+
+impl EntryLink {
+    /// Check if the given string is a valid absolute http(s) URL, as required for entry links
+    pub fn is_valid_url(url: &str) -> bool {
+        url::Url::parse(url).is_ok_and(|url| ["http", "https"].contains(&url.scheme()))
+    }
+}
+
+impl From<kueaplan_api_types::EntryLink> for EntryLink {
+    fn from(value: kueaplan_api_types::EntryLink) -> Self {
+        Self {
+            label: value.label,
+            url: value.url,
+        }
+    }
+}
+
+impl From<EntryLink> for kueaplan_api_types::EntryLink {
+    fn from(value: EntryLink) -> Self {
+        Self {
+            label: value.label,
+            url: value.url,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct EventDayScheduleSection {
     pub name: String,
@@ -398,7 +489,27 @@ pub struct Entry {
     pub room_comment: String,
     pub is_exclusive: bool,
     pub is_cancelled: bool,
+    pub is_tentative: bool,
+    pub orga_only: bool,
+    /// The entry has no fixed end. Its `end` is set to the end of the (effective) day of its begin.
+    pub open_ended: bool,
     pub state: EntryState,
+    pub color_override: Option<String>,
+    pub links: Option<EntryLinks>,
+}
+
+impl Entry {
+    /// Check if the entry is visible to all clients with
+    /// [Privilege::ShowKueaPlan](crate::data_store::auth_token::Privilege::ShowKueaPlan), i.e. it
+    /// is published and not orga-only
+    pub fn is_public(&self) -> bool {
+        self.state.is_published() && !self.orga_only
+    }
+
+    /// Check if the entry has any details to be shown in the entry description section
+    pub fn has_description_or_links(&self) -> bool {
+        !self.description.is_empty() || self.links.as_ref().is_some_and(|l| !l.0.is_empty())
+    }
 }
 
 #[derive(Clone, Queryable, Selectable)]
@@ -406,6 +517,8 @@ pub struct Entry {
 pub struct EntryInternalFields {
     #[diesel(column_name = "orga_comment")]
     pub comment: String,
+    /// The role of the client which created or changed the entry most recently, if known
+    pub last_updated_by_role: Option<AccessRole>,
 }
 
 #[derive(Clone)]
@@ -417,6 +530,101 @@ pub struct FullEntry {
     pub orga_internal: Option<EntryInternalFields>,
 }
 
+#[cfg(test)]
+impl FullEntry {
+    /// Create a builder for a published example entry in event 1 with the given begin and end, for
+    /// use in tests
+    pub fn test_builder(begin: &str, end: &str) -> FullEntryTestBuilder {
+        FullEntryTestBuilder {
+            result: FullEntry {
+                entry: Entry {
+                    id: Uuid::now_v7(),
+                    title: "Drachenfliegen".to_owned(),
+                    description: "".to_owned(),
+                    responsible_person: "".to_owned(),
+                    is_room_reservation: false,
+                    event_id: 1,
+                    begin: begin.parse().unwrap(),
+                    end: end.parse().unwrap(),
+                    category: Uuid::nil(),
+                    last_updated: Default::default(),
+                    comment: "".to_owned(),
+                    time_comment: "".to_owned(),
+                    room_comment: "".to_owned(),
+                    is_exclusive: false,
+                    is_cancelled: false,
+                    is_tentative: false,
+                    orga_only: false,
+                    open_ended: false,
+                    state: EntryState::Published,
+                    color_override: None,
+                    links: None,
+                },
+                room_ids: vec![],
+                previous_dates: vec![],
+                orga_internal: None,
+            },
+        }
+    }
+}
+
+/// Builder for constructing example FullEntry objects in tests
+#[cfg(test)]
+pub struct FullEntryTestBuilder {
+    result: FullEntry,
+}
+
+#[cfg(test)]
+impl FullEntryTestBuilder {
+    pub fn id(mut self, id: Uuid) -> Self {
+        self.result.entry.id = id;
+        for previous_date in self.result.previous_dates.iter_mut() {
+            previous_date.previous_date.entry_id = id;
+        }
+        self
+    }
+
+    pub fn title(mut self, title: &str) -> Self {
+        self.result.entry.title = title.to_owned();
+        self
+    }
+
+    pub fn responsible_person(mut self, responsible_person: &str) -> Self {
+        self.result.entry.responsible_person = responsible_person.to_owned();
+        self
+    }
+
+    pub fn category(mut self, category: Uuid) -> Self {
+        self.result.entry.category = category;
+        self
+    }
+
+    pub fn rooms(mut self, room_ids: Vec<Uuid>) -> Self {
+        self.result.room_ids = room_ids;
+        self
+    }
+
+    /// Add a previous date with the given begin, end and rooms to the entry
+    pub fn previous_date(mut self, id: Uuid, begin: &str, end: &str, room_ids: Vec<Uuid>) -> Self {
+        self.result.previous_dates.push(FullPreviousDate {
+            previous_date: PreviousDate {
+                id,
+                entry_id: self.result.entry.id,
+                comment: "".to_owned(),
+                begin: begin.parse().unwrap(),
+                end: end.parse().unwrap(),
+                last_updated: Default::default(),
+            },
+            room_ids,
+        });
+        self
+    }
+
+    pub fn build(self) -> FullEntry {
+        self.result
+    }
+}
+
 impl From<FullEntry> for kueaplan_api_types::Entry {
     fn from(value: FullEntry) -> Self {
         kueaplan_api_types::Entry {
@@ -434,12 +642,26 @@ impl From<FullEntry> for kueaplan_api_types::Entry {
             time_comment: value.entry.time_comment,
             is_exclusive: value.entry.is_exclusive,
             is_cancelled: value.entry.is_cancelled,
+            is_tentative: value.entry.is_tentative,
+            orga_only: value.entry.orga_only,
+            open_ended: value.entry.open_ended,
             state: value.entry.state.into(),
+            color_override: value.entry.color_override,
+            links: value
+                .entry
+                .links
+                .map(|l| l.0.into_iter().map(|l| l.into()).collect())
+                .unwrap_or_default(),
             previous_dates: value
                 .previous_dates
                 .into_iter()
                 .map(|pd| pd.into())
                 .collect(),
+            last_updated_by_role: value
+                .orga_internal
+                .as_ref()
+                .and_then(|i| i.last_updated_by_role)
+                .map(|role| role.into()),
             orga_comment: value.orga_internal.map(|i| i.comment),
         }
     }
@@ -462,8 +684,20 @@ pub struct NewEntry {
     pub room_comment: String,
     pub is_exclusive: bool,
     pub is_cancelled: bool,
+    pub is_tentative: bool,
+    pub orga_only: bool,
+    pub open_ended: bool,
     pub state: EntryState,
     pub orga_comment: String,
+    pub color_override: Option<String>,
+    pub links: Option<EntryLinks>,
+}
+
+impl NewEntry {
+    /// See [Entry::is_public]
+    pub fn is_public(&self) -> bool {
+        self.state.is_published() && !self.orga_only
+    }
 }
 
 #[derive(Clone)]
@@ -490,8 +724,14 @@ impl FullNewEntry {
                 time_comment: entry.time_comment,
                 is_exclusive: entry.is_exclusive,
                 is_cancelled: entry.is_cancelled,
+                is_tentative: entry.is_tentative,
+                orga_only: entry.orga_only,
+                open_ended: entry.open_ended,
                 state: entry.state.into(),
                 orga_comment: entry.orga_comment.unwrap_or_default(),
+                color_override: entry.color_override,
+                links: (!entry.links.is_empty())
+                    .then(|| EntryLinks(entry.links.into_iter().map(|l| l.into()).collect())),
             },
             room_ids: entry.room,
             previous_dates: entry
@@ -501,6 +741,40 @@ impl FullNewEntry {
                 .collect(),
         }
     }
+
+    /// Append a previous date with the begin, end and rooms of the stored `old_entry` to the
+    /// previous dates of this entry, if the begin, end or rooms have been changed with respect to
+    /// the `old_entry`.
+    ///
+    /// Returns true if a previous date has been appended.
+    pub fn record_previous_date(&mut self, old_entry: &FullEntry, comment: String) -> bool {
+        if self.entry.begin == old_entry.entry.begin
+            && self.entry.end == old_entry.entry.end
+            && unordered_equality(&self.room_ids, &old_entry.room_ids)
+        {
+            return false;
+        }
+        self.previous_dates.push(FullPreviousDate {
+            previous_date: PreviousDate {
+                id: Uuid::now_v7(),
+                entry_id: self.entry.id,
+                comment,
+                begin: old_entry.entry.begin,
+                end: old_entry.entry.end,
+                last_updated: Utc::now(),
+            },
+            room_ids: old_entry.room_ids.clone(),
+        });
+        true
+    }
+}
+
+pub(crate) fn unordered_equality<T: Eq + Ord>(a: &[T], b: &[T]) -> bool {
+    // Source: https://stackoverflow.com/a/42748484/10315508
+    let a: BTreeSet<_> = a.iter().collect();
+    let b: BTreeSet<_> = b.iter().collect();
+
+    a == b
 }
 
 impl From<FullEntry> for FullNewEntry {
@@ -521,8 +795,13 @@ impl From<FullEntry> for FullNewEntry {
                 room_comment: value.entry.room_comment,
                 is_exclusive: value.entry.is_exclusive,
                 is_cancelled: value.entry.is_cancelled,
+                is_tentative: value.entry.is_tentative,
+                orga_only: value.entry.orga_only,
+                open_ended: value.entry.open_ended,
                 state: value.entry.state,
                 orga_comment: value.orga_internal.map(|i| i.comment).unwrap_or_default(),
+                color_override: value.entry.color_override,
+                links: value.entry.links,
             },
             room_ids: value.room_ids,
             previous_dates: value.previous_dates,
@@ -545,6 +824,9 @@ pub struct EntryPatch {
     pub room_comment: Option<String>,
     pub is_exclusive: Option<bool>,
     pub is_cancelled: Option<bool>,
+    pub is_tentative: Option<bool>,
+    pub orga_only: Option<bool>,
+    pub open_ended: Option<bool>,
     pub state: Option<EntryState>,
     pub orga_comment: Option<String>,
     #[diesel(skip_update)]
@@ -679,6 +961,9 @@ impl From<kueaplan_api_types::EntryPatch> for EntryPatch {
             room_comment: value.room_comment,
             is_exclusive: value.is_exclusive,
             is_cancelled: value.is_cancelled,
+            is_tentative: value.is_tentative,
+            orga_only: value.orga_only,
+            open_ended: value.open_ended,
             room_ids: value.room,
             state: value.state.map(|s| s.into()),
             orga_comment: value.orga_comment,
@@ -686,6 +971,94 @@ impl From<kueaplan_api_types::EntryPatch> for EntryPatch {
     }
 }
 
+/// A named, reusable set of default values for new entries of an event
+#[derive(Clone, Debug, Queryable, Selectable, Insertable)]
+#[diesel(table_name=super::schema::entry_templates)]
+pub struct EntryTemplate {
+    pub id: EntryTemplateId,
+    pub event_id: EventId,
+    pub name: String,
+    pub entry_data: EntryTemplateData,
+}
+
+impl EntryTemplate {
+    pub fn from_api(template: kueaplan_api_types::EntryTemplate, event_id: EventId) -> Self {
+        let defaults = template.defaults;
+        Self {
+            id: template.id,
+            event_id,
+            name: template.name,
+            entry_data: EntryTemplateData {
+                title: defaults.title,
+                comment: defaults.comment,
+                description: defaults.description,
+                room_ids: defaults.room,
+                room_comment: defaults.room_comment,
+                time_comment: defaults.time_comment,
+                responsible_person: defaults.responsible_person,
+                is_exclusive: defaults.is_exclusive,
+                is_tentative: defaults.is_tentative,
+                orga_only: defaults.orga_only,
+                is_room_reservation: defaults.is_room_reservation,
+                category: defaults.category,
+                color_override: defaults.color_override,
+                links: defaults.links.into_iter().map(|l| l.into()).collect(),
+            },
+        }
+    }
+}
+
+impl From<EntryTemplate> for kueaplan_api_types::EntryTemplate {
+    fn from(value: EntryTemplate) -> Self {
+        let data = value.entry_data;
+        Self {
+            id: value.id,
+            name: value.name,
+            defaults: kueaplan_api_types::EntryTemplateDefaults {
+                title: data.title,
+                comment: data.comment,
+                description: data.description,
+                room: data.room_ids,
+                room_comment: data.room_comment,
+                time_comment: data.time_comment,
+                responsible_person: data.responsible_person,
+                is_exclusive: data.is_exclusive,
+                is_tentative: data.is_tentative,
+                orga_only: data.orga_only,
+                is_room_reservation: data.is_room_reservation,
+                category: data.category,
+                color_override: data.color_override,
+                links: data.links.into_iter().map(|l| l.into()).collect(),
+            },
+        }
+    }
+}
+
+/// Default values for the fields of new entries, stored as JSON in an [EntryTemplate]. Templates
+/// intentionally don't include the date and time of entries.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, AsExpression, FromSqlRow)]
+#[diesel(sql_type = diesel::sql_types::Jsonb)]
+#[serde(default)]
+pub struct EntryTemplateData {
+    pub title: String,
+    pub comment: String,
+    pub description: String,
+    pub room_ids: Vec<RoomId>,
+    pub room_comment: String,
+    pub time_comment: String,
+    pub responsible_person: String,
+    pub is_exclusive: bool,
+    pub is_tentative: bool,
+    pub orga_only: bool,
+    pub is_room_reservation: bool,
+    /// If None or not existing (anymore), the first category of the event is used
+    pub category: Option<CategoryId>,
+    pub color_override: Option<String>,
+    pub links: Vec<EntryLink>,
+}
+
+impl_sql_for_json!(EntryTemplateData);
+
 // Introduce type for Entry-Room-association, to simplify grouped retrieval of room_ids of an Entry
 // using Diesel's .grouped_by() method.
 #[derive(Queryable, Associations, Identifiable, Selectable)]
@@ -697,6 +1070,84 @@ pub struct EntryRoomMapping {
     pub room_id: Uuid,
 }
 
+#[derive(Clone, Queryable, Selectable)]
+#[diesel(table_name=super::schema::entry_audit_log)]
+pub struct EntryAuditLogRecord {
+    pub entry_id: EntryId,
+    pub action: EntryAuditAction,
+    /// Id of the passphrase, which has been used for the modification. None, if the modification
+    /// has been done via the command line interface or the passphrase has been deleted since.
+    pub passphrase_id: Option<PassphraseId>,
+    pub timestamp: DateTime<Utc>,
+    /// Names of the entry's fields (database columns and `room_ids`), which have been changed by
+    /// the modification.
+    pub changed_fields: Vec<String>,
+}
+
+impl From<EntryAuditLogRecord> for kueaplan_api_types::EntryAuditLogRecord {
+    fn from(value: EntryAuditLogRecord) -> Self {
+        Self {
+            entry_id: value.entry_id,
+            action: value.action.into(),
+            passphrase_id: value.passphrase_id,
+            timestamp: value.timestamp,
+            changed_fields: value.changed_fields,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name=super::schema::entry_audit_log)]
+pub struct NewEntryAuditLogRecord {
+    pub entry_id: EntryId,
+    pub event_id: EventId,
+    pub action: EntryAuditAction,
+    pub passphrase_id: Option<PassphraseId>,
+    pub changed_fields: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, FromSqlRow, AsExpression, Eq, Clone, Copy)]
+#[diesel(sql_type = diesel::sql_types::Integer)]
+#[repr(i32)]
+pub enum EntryAuditAction {
+    Create = 0,
+    Update = 1,
+    Delete = 2,
+}
+
+impl TryFrom<i32> for EntryAuditAction {
+    type Error = EnumMemberNotExistingError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Create),
+            1 => Ok(Self::Update),
+            2 => Ok(Self::Delete),
+            _ => Err(EnumMemberNotExistingError {
+                member_value: value,
+                enum_name: "EntryAuditAction",
+            }),
+        }
+    }
+}
+impl From<EntryAuditAction> for i32 {
+    fn from(value: EntryAuditAction) -> Self {
+        value as i32
+    }
+}
+
+impl From<EntryAuditAction> for kueaplan_api_types::EntryAuditAction {
+    fn from(value: EntryAuditAction) -> Self {
+        match value {
+            EntryAuditAction::Create => Self::Create,
+            EntryAuditAction::Update => Self::Update,
+            EntryAuditAction::Delete => Self::Delete,
+        }
+    }
+}
+
+impl_to_sql_for_enum!(EntryAuditAction);
+
 #[derive(Clone, Queryable, Identifiable, Selectable)]
 #[diesel(table_name=super::schema::rooms)]
 pub struct Room {
@@ -737,6 +1188,22 @@ impl NewRoom {
     }
 }
 
+#[derive(Clone, Default, AsChangeset)]
+#[diesel(table_name=super::schema::rooms)]
+pub struct RoomPatch {
+    pub title: Option<String>,
+    pub description: Option<String>,
+}
+
+impl From<kueaplan_api_types::RoomPatch> for RoomPatch {
+    fn from(value: kueaplan_api_types::RoomPatch) -> Self {
+        Self {
+            title: value.title,
+            description: value.description,
+        }
+    }
+}
+
 #[derive(Clone, Queryable, Selectable, Associations, Insertable, AsChangeset, Identifiable)]
 #[diesel(table_name=super::schema::previous_dates, treat_none_as_null=true)]
 #[diesel(belongs_to(Entry))]
@@ -746,6 +1213,10 @@ pub struct PreviousDate {
     pub comment: String,
     pub begin: DateTime<Utc>,
     pub end: DateTime<Utc>,
+    /// Time of the creation or last actual change of the previous date. Maintained by the
+    /// database, so it is ignored when writing the previous date.
+    #[diesel(skip_insertion, skip_update)]
+    pub last_updated: DateTime<Utc>,
 }
 
 #[derive(Clone)]
@@ -763,6 +1234,7 @@ impl FullPreviousDate {
                 comment: value.comment,
                 begin: value.begin,
                 end: value.end,
+                last_updated: Utc::now(),
             },
             room_ids: value.room,
         }
@@ -781,6 +1253,99 @@ impl From<FullPreviousDate> for kueaplan_api_types::PreviousDate {
     }
 }
 
+/// The previous dates of a single entry, together with the entry's current title
+pub struct EntryPreviousDates {
+    pub entry_id: EntryId,
+    pub entry_title: String,
+    pub previous_dates: Vec<FullPreviousDate>,
+}
+
+impl From<EntryPreviousDates> for kueaplan_api_types::EntryPreviousDates {
+    fn from(value: EntryPreviousDates) -> Self {
+        Self {
+            entry_id: value.entry_id,
+            entry_title: value.entry_title,
+            previous_dates: value
+                .previous_dates
+                .into_iter()
+                .map(|pd| pd.into())
+                .collect(),
+        }
+    }
+}
+
+/// The events directly preceding and following an event, as referenced by the
+/// [ExtendedEvent::preceding_event_id] and [ExtendedEvent::subsequent_event_id]
+pub struct EventNeighbors {
+    pub preceding_event: Option<Event>,
+    pub subsequent_event: Option<Event>,
+}
+
+impl From<EventNeighbors> for kueaplan_api_types::EventNeighbors {
+    fn from(value: EventNeighbors) -> Self {
+        Self {
+            preceding_event: value.preceding_event.map(|e| e.into()),
+            subsequent_event: value.subsequent_event.map(|e| e.into()),
+        }
+    }
+}
+
+/// Aggregated statistics of an event, e.g. for a post-event report
+pub struct EventStats {
+    /// Number of published entries, including cancelled ones
+    pub entry_count: i64,
+    /// Number of published, cancelled entries
+    pub cancelled_count: i64,
+    pub room_count: i64,
+    pub category_count: i64,
+    /// Sum of the durations of all published, non-cancelled entries in minutes
+    pub total_programme_minutes: i64,
+}
+
+impl From<EventStats> for kueaplan_api_types::EventStats {
+    fn from(value: EventStats) -> Self {
+        Self {
+            entry_count: value.entry_count,
+            cancelled_count: value.cancelled_count,
+            room_count: value.room_count,
+            category_count: value.category_count,
+            total_programme_minutes: value.total_programme_minutes,
+        }
+    }
+}
+
+/// A pair of entries which overlap in time and share the given room
+pub struct RoomDoubleBooking {
+    pub room_id: RoomId,
+    pub entry_ids: (EntryId, EntryId),
+}
+
+/// Changes of the (published) entries and rooms of an event since a given point in time
+pub struct Updates {
+    /// New and changed entries
+    pub changed_entries: Vec<FullEntry>,
+    /// Ids of entries which have been deleted or are not published anymore
+    pub deleted_entries: Vec<EntryId>,
+    /// The full list of rooms of the event, if any of them has been changed
+    pub rooms: Option<Vec<Room>>,
+}
+
+impl From<Updates> for kueaplan_api_types::Updates {
+    fn from(value: Updates) -> Self {
+        Self {
+            changed_entries: value
+                .changed_entries
+                .into_iter()
+                .map(|e| e.into())
+                .collect(),
+            deleted_entries: value.deleted_entries,
+            rooms: value
+                .rooms
+                .map(|rooms| rooms.into_iter().map(|r| r.into()).collect()),
+        }
+    }
+}
+
 impl BelongsTo<Entry> for FullPreviousDate {
     type ForeignKey = Uuid;
     type ForeignKeyColumn = super::schema::previous_dates::columns::entry_id;
@@ -865,6 +1430,8 @@ pub struct Announcement {
     pub show_with_days: bool,
     pub begin_date: Option<NaiveDate>,
     pub end_date: Option<NaiveDate>,
+    pub begin_time: Option<chrono::NaiveTime>,
+    pub end_time: Option<chrono::NaiveTime>,
     pub show_with_categories: bool,
     pub show_with_all_categories: bool,
     pub show_with_rooms: bool,
@@ -889,6 +1456,8 @@ impl From<FullAnnouncement> for kueaplan_api_types::Announcement {
             show_with_days: value.announcement.show_with_days,
             begin_date: value.announcement.begin_date,
             end_date: value.announcement.end_date,
+            begin_time: value.announcement.begin_time,
+            end_time: value.announcement.end_time,
             sort_key: value.announcement.sort_key,
             show_with_categories: value.announcement.show_with_categories,
             categories: value.category_ids,
@@ -896,6 +1465,7 @@ impl From<FullAnnouncement> for kueaplan_api_types::Announcement {
             show_with_rooms: value.announcement.show_with_rooms,
             rooms: value.room_ids,
             show_with_all_rooms: value.announcement.show_with_all_rooms,
+            last_updated: Some(value.announcement.last_updated),
         }
     }
 }
@@ -910,6 +1480,8 @@ pub struct NewAnnouncement {
     pub show_with_days: bool,
     pub begin_date: Option<NaiveDate>,
     pub end_date: Option<NaiveDate>,
+    pub begin_time: Option<chrono::NaiveTime>,
+    pub end_time: Option<chrono::NaiveTime>,
     pub show_with_categories: bool,
     pub show_with_all_categories: bool,
     pub show_with_rooms: bool,
@@ -935,6 +1507,8 @@ impl FullNewAnnouncement {
                 show_with_days: announcement.show_with_days,
                 begin_date: announcement.begin_date,
                 end_date: announcement.end_date,
+                begin_time: announcement.begin_time,
+                end_time: announcement.end_time,
                 show_with_categories: announcement.show_with_categories,
                 show_with_all_categories: announcement.show_with_all_categories,
                 show_with_rooms: announcement.show_with_rooms,
@@ -958,6 +1532,8 @@ impl From<FullAnnouncement> for FullNewAnnouncement {
                 show_with_days: value.announcement.show_with_days,
                 begin_date: value.announcement.begin_date,
                 end_date: value.announcement.end_date,
+                begin_time: value.announcement.begin_time,
+                end_time: value.announcement.end_time,
                 show_with_categories: value.announcement.show_with_categories,
                 show_with_all_categories: value.announcement.show_with_all_categories,
                 show_with_rooms: value.announcement.show_with_rooms,
@@ -978,6 +1554,8 @@ pub struct AnnouncementPatch {
     pub show_with_days: Option<bool>,
     pub begin_date: Option<Option<NaiveDate>>,
     pub end_date: Option<Option<NaiveDate>>,
+    pub begin_time: Option<Option<chrono::NaiveTime>>,
+    pub end_time: Option<Option<chrono::NaiveTime>>,
     pub show_with_categories: Option<bool>,
     pub show_with_all_categories: Option<bool>,
     pub show_with_rooms: Option<bool>,
@@ -997,6 +1575,8 @@ impl From<kueaplan_api_types::AnnouncementPatch> for AnnouncementPatch {
             show_with_days: value.show_with_days,
             begin_date: value.begin_date,
             end_date: value.end_date,
+            begin_time: value.begin_time,
+            end_time: value.end_time,
             show_with_categories: value.show_with_categories,
             show_with_all_categories: value.show_with_all_categories,
             show_with_rooms: value.show_with_rooms,
@@ -1087,6 +1667,8 @@ pub struct Passphrase {
     pub comment: String,
     pub valid_from: Option<DateTime<Utc>>,
     pub valid_until: Option<DateTime<Utc>>,
+    #[diesel(column_name = passphrase_last_used)]
+    pub last_used: Option<DateTime<Utc>>,
 }
 
 impl From<Passphrase> for kueaplan_api_types::Passphrase {
@@ -1099,6 +1681,7 @@ impl From<Passphrase> for kueaplan_api_types::Passphrase {
             comment: value.comment,
             valid_from: value.valid_from,
             valid_until: value.valid_until,
+            last_used: value.last_used,
         }
     }
 }
@@ -1129,6 +1712,25 @@ impl NewPassphrase {
     }
 }
 
+/// Full data of a passphrase, including its plaintext (if stored) and hash, for creating and
+/// restoring backups via the command line interface. This must never be exposed via the web
+/// interface.
+#[derive(Clone, Queryable, Selectable, Serialize, Deserialize)]
+#[diesel(table_name=super::schema::event_passphrases)]
+pub struct PassphraseBackup {
+    pub id: PassphraseId,
+    #[serde(rename = "role")]
+    pub privilege: AccessRole,
+    pub passphrase: Option<String>,
+    pub passphrase_hash: Option<String>,
+    #[serde(default)]
+    pub passphrase_lookup_key: Option<i32>,
+    pub derivable_from_passphrase: Option<PassphraseId>,
+    pub comment: String,
+    pub valid_from: Option<DateTime<Utc>>,
+    pub valid_until: Option<DateTime<Utc>>,
+}
+
 #[derive(Clone, Default, AsChangeset)]
 #[diesel(table_name=super::schema::event_passphrases)]
 pub struct PassphrasePatch {
@@ -1147,6 +1749,46 @@ impl From<kueaplan_api_types::PassphrasePatch> for PassphrasePatch {
     }
 }
 
+/// A read-only API key of an event. The key itself is only stored as hash and can therefore not
+/// be retrieved.
+#[derive(Clone, Queryable, Identifiable, Selectable)]
+#[diesel(table_name=super::schema::api_keys)]
+pub struct ApiKey {
+    pub id: ApiKeyId,
+    pub event_id: EventId,
+    pub comment: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Insertable)]
+#[diesel(table_name=super::schema::api_keys)]
+pub struct NewApiKey {
+    pub event_id: EventId,
+    pub comment: String,
+}
+
+/// A token for subscribing to the calendar feeds of an event. The token string, which is handed to
+/// the subscribers, is not stored, but derived from the id via
+/// [crate::auth_session::FeedAccessToken].
+#[derive(Clone, Queryable, Identifiable, Selectable)]
+#[diesel(table_name=super::schema::feed_tokens)]
+pub struct FeedToken {
+    pub id: FeedTokenId,
+    pub event_id: EventId,
+    pub privilege: AccessRole,
+    pub comment: String,
+    pub created_at: DateTime<Utc>,
+    pub revoked: bool,
+}
+
+#[derive(Clone, Insertable)]
+#[diesel(table_name=super::schema::feed_tokens)]
+pub struct NewFeedToken {
+    pub event_id: EventId,
+    pub privilege: AccessRole,
+    pub comment: String,
+}
+
 pub struct EventWithContents {
     pub event: ExtendedEvent,
     pub rooms: Vec<NewRoom>,
@@ -1154,3 +1796,14 @@ pub struct EventWithContents {
     pub entries: Vec<FullNewEntry>,
     pub announcements: Vec<FullNewAnnouncement>,
 }
+
+/// Behaviour of the event import, when an imported room, category, entry or announcement has the
+/// same id as an existing one
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImportConflictMode {
+    /// Leave the existing element untouched and skip the imported one, as well as all imported
+    /// elements referencing it
+    Skip,
+    /// Abort the import without any changes
+    Fail,
+}
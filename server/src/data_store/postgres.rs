@@ -1,16 +1,21 @@
 use super::{
-    AnnouncementFilter, AnnouncementId, CategoryId, DataPolicy, EntryFilter, EntryId, EventFilter,
-    EventId, KuaPlanStore, KueaPlanStoreFacade, PassphraseId, PreviousDateId, RoomId, StoreError,
-    models, schema,
+    AnnouncementFilter, AnnouncementId, ApiKeyId, CategoryId, DataPolicy, EntryFilter, EntryId,
+    EntryOrder, EntryTemplateId, EventFilter, EventId, FeedTokenId, KuaPlanStore,
+    KueaPlanStoreFacade, PassphraseId, PreviousDateId, RoomId, StoreError, models, schema,
 };
 use crate::auth_session::SessionToken;
 use crate::data_store::auth_token::{AccessRole, AuthToken, GlobalAuthToken, Privilege};
+use crate::setup::DbPoolConfig;
+use crate::web::time_calculation::get_effective_date;
+use argon2::{PasswordHasher, PasswordVerifier};
+use base64::Engine;
 use diesel::expression::AsExpression;
 use diesel::pg::PgConnection;
 use diesel::prelude::*;
 use diesel::r2d2::ConnectionManager;
 use r2d2::PooledConnection;
-use std::collections::HashMap;
+use ring::rand::SecureRandom;
+use std::collections::{BTreeSet, HashMap};
 use uuid::Uuid;
 
 #[derive(Clone)]
@@ -19,12 +24,13 @@ pub struct PgDataStore {
 }
 
 impl PgDataStore {
-    pub fn new(database_url: &str) -> Result<Self, StoreError> {
+    pub fn new(database_url: &str, pool_config: &DbPoolConfig) -> Result<Self, StoreError> {
         let connection_manager = diesel::r2d2::ConnectionManager::<PgConnection>::new(database_url);
         Ok(Self {
             pool: diesel::r2d2::Pool::builder()
                 .test_on_check_out(true)
-                .min_idle(Some(2))
+                .max_size(pool_config.max_size)
+                .min_idle(Some(pool_config.min_idle))
                 .build(connection_manager)?,
         })
     }
@@ -36,6 +42,11 @@ impl KuaPlanStore for PgDataStore {
             self.pool.get()?,
         )))
     }
+
+    fn connections_in_use(&self) -> u32 {
+        let state = self.pool.state();
+        state.connections - state.idle_connections
+    }
 }
 
 pub struct PgDataStoreFacade {
@@ -57,7 +68,34 @@ fn sql_upsert_is_updated() -> diesel::expression::SqlLiteral<diesel::sql_types::
     diesel::dsl::sql("xmax::text <> '0'")
 }
 
+/// Maximum number of attempts of [retry_on_transaction_conflict]
+const MAX_TRANSACTION_ATTEMPTS: u32 = 3;
+
+/// Run the given database operation and repeat it (with a short, increasing delay) when it fails
+/// with a [StoreError::TransactionConflict], up to [MAX_TRANSACTION_ATTEMPTS] times in total.
+///
+/// The operation must be a complete database transaction, such that it can be repeated safely.
+fn retry_on_transaction_conflict<T>(
+    mut operation: impl FnMut() -> Result<T, StoreError>,
+) -> Result<T, StoreError> {
+    let mut attempt = 1;
+    loop {
+        match operation() {
+            Err(StoreError::TransactionConflict) if attempt < MAX_TRANSACTION_ATTEMPTS => {
+                std::thread::sleep(std::time::Duration::from_millis(10 << attempt));
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
 impl KueaPlanStoreFacade for PgDataStoreFacade {
+    fn ping(&mut self) -> Result<(), StoreError> {
+        diesel::select(1.into_sql::<diesel::sql_types::Integer>()).execute(&mut self.connection)?;
+        Ok(())
+    }
+
     fn get_events(&mut self, filter: EventFilter) -> Result<Vec<models::Event>, StoreError> {
         use schema::events::dsl::*;
 
@@ -103,6 +141,40 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
             .map_err(|e| e.into())
     }
 
+    fn get_neighbor_events(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+    ) -> Result<models::EventNeighbors, StoreError> {
+        use schema::events::dsl::*;
+        auth_token.check_privilege(the_event_id, Privilege::ShowKueaPlan)?;
+
+        self.connection.transaction(|connection| {
+            let (preceding_id, subsequent_id) = events
+                .filter(id.eq(the_event_id))
+                .select((preceding_event_id, subsequent_event_id))
+                .first::<(Option<EventId>, Option<EventId>)>(connection)?;
+            let neighbor_ids: Vec<EventId> = [preceding_id, subsequent_id]
+                .into_iter()
+                .flatten()
+                .collect();
+            let neighbors = events
+                .filter(id.eq_any(&neighbor_ids))
+                .select(models::Event::as_select())
+                .load::<models::Event>(connection)?;
+            let find_neighbor = |neighbor_id: Option<EventId>| {
+                neighbors
+                    .iter()
+                    .find(|e| Some(e.id) == neighbor_id)
+                    .cloned()
+            };
+            Ok(models::EventNeighbors {
+                preceding_event: find_neighbor(preceding_id),
+                subsequent_event: find_neighbor(subsequent_id),
+            })
+        })
+    }
+
     fn create_event(
         &mut self,
         auth_token: &GlobalAuthToken,
@@ -157,16 +229,47 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
         Ok(())
     }
 
+    fn set_event_archived(
+        &mut self,
+        auth_token: &AuthToken,
+        event_id: EventId,
+        is_archived: bool,
+    ) -> Result<(), StoreError> {
+        use schema::events::dsl::*;
+        auth_token.check_privilege(event_id, Privilege::EditEventDetails)?;
+        let rows = diesel::update(events)
+            .filter(id.eq(event_id))
+            .set(archived.eq(is_archived))
+            .execute(&mut self.connection)?;
+        if rows == 0 {
+            return Err(StoreError::NotExisting);
+        }
+        Ok(())
+    }
+
     fn import_event_with_contents(
         &mut self,
         auth_token: &GlobalAuthToken,
         data: models::EventWithContents,
+        on_conflict: models::ImportConflictMode,
     ) -> Result<EventId, StoreError> {
         self.connection.transaction(|connection| {
+            auth_token.check_privilege(Privilege::CreateEvents)?;
+            let conflicts = find_import_conflicts(&data, connection)?;
+            if !conflicts.is_empty() {
+                match on_conflict {
+                    models::ImportConflictMode::Fail => {
+                        return Err(StoreError::InvalidInputData(format!(
+                            "The following imported elements exist already: {}",
+                            conflicts.describe()
+                        )));
+                    }
+                    models::ImportConflictMode::Skip => {}
+                }
+            }
+
             let event_id = {
                 use schema::events::dsl::*;
-                auth_token.check_privilege(Privilege::CreateEvents)?;
-
                 diesel::insert_into(events)
                     .values(data.event)
                     .returning(id)
@@ -174,6 +277,7 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
             };
 
             let mut rooms = data.rooms;
+            rooms.retain(|room| !conflicts.contains_room(room.id));
             for room in rooms.iter_mut() {
                 room.event_id = event_id;
             }
@@ -182,6 +286,7 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
                 .execute(connection)?;
 
             let mut categories = data.categories;
+            categories.retain(|category| !conflicts.contains_category(category.id));
             for category in categories.iter_mut() {
                 category.event_id = event_id;
             }
@@ -189,9 +294,21 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
                 .values(categories)
                 .execute(connection)?;
 
+            // Entries and announcements, which reference a skipped room or category, are skipped
+            // as well, since the skipped elements belong to other events.
             for full_entry in data.entries {
                 let mut entry = full_entry.entry;
                 let entry_id = entry.id;
+                if conflicts.contains_entry(entry_id)
+                    || conflicts.contains_category(entry.category)
+                    || full_entry
+                        .room_ids
+                        .iter()
+                        .chain(full_entry.previous_dates.iter().flat_map(|pd| &pd.room_ids))
+                        .any(|room_id| conflicts.contains_room(*room_id))
+                {
+                    continue;
+                }
                 entry.event_id = event_id;
                 check_categories_validity(&[entry.category], event_id, connection)?;
                 diesel::insert_into(schema::entries::table)
@@ -200,6 +317,9 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
                 check_rooms_validity(&full_entry.room_ids, event_id, connection)?;
                 update_entry_rooms(entry_id, &full_entry.room_ids, connection)?;
                 for previous_date in full_entry.previous_dates {
+                    if conflicts.contains_previous_date(previous_date.previous_date.id) {
+                        continue;
+                    }
                     check_rooms_validity(&previous_date.room_ids, event_id, connection)?;
                     update_or_insert_previous_date(&previous_date, entry_id, connection)?;
                 }
@@ -208,6 +328,18 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
             for full_announcement in data.announcements {
                 let mut announcement = full_announcement.announcement;
                 let announcement_id = announcement.id;
+                if conflicts.contains_announcement(announcement_id)
+                    || full_announcement
+                        .category_ids
+                        .iter()
+                        .any(|category_id| conflicts.contains_category(*category_id))
+                    || full_announcement
+                        .room_ids
+                        .iter()
+                        .any(|room_id| conflicts.contains_room(*room_id))
+                {
+                    continue;
+                }
                 announcement.event_id = event_id;
                 check_categories_validity(&full_announcement.category_ids, event_id, connection)?;
                 check_rooms_validity(&full_announcement.room_ids, event_id, connection)?;
@@ -230,6 +362,52 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
         })
     }
 
+    fn get_event_stats(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+    ) -> Result<models::EventStats, StoreError> {
+        use diesel::dsl::{count_star, not, sql};
+        use diesel::sql_types::BigInt;
+        use schema::entries::dsl::*;
+
+        auth_token.check_privilege(the_event_id, Privilege::ShowKueaPlan)?;
+
+        self.connection.transaction(|connection| {
+            let (entry_count, cancelled_count, total_programme_minutes) = entries
+                .filter(event_id.eq(the_event_id))
+                .filter(not(deleted))
+                .filter(state.eq_any(models::EntryState::all().filter(|s| s.is_published())))
+                .filter(orga_only.eq_any(visible_orga_only_values(auth_token, the_event_id)))
+                .select((
+                    count_star(),
+                    sql::<BigInt>("COUNT(*) FILTER (WHERE is_cancelled)"),
+                    sql::<BigInt>(
+                        "COALESCE(SUM(EXTRACT(EPOCH FROM \"end\" - \"begin\")) \
+                        FILTER (WHERE NOT is_cancelled), 0)::BIGINT / 60",
+                    ),
+                ))
+                .first::<(i64, i64, i64)>(connection)?;
+            let room_count = schema::rooms::table
+                .filter(schema::rooms::event_id.eq(the_event_id))
+                .filter(not(schema::rooms::deleted))
+                .count()
+                .get_result::<i64>(connection)?;
+            let category_count = schema::categories::table
+                .filter(schema::categories::event_id.eq(the_event_id))
+                .filter(not(schema::categories::deleted))
+                .count()
+                .get_result::<i64>(connection)?;
+            Ok(models::EventStats {
+                entry_count,
+                cancelled_count,
+                room_count,
+                category_count,
+                total_programme_minutes,
+            })
+        })
+    }
+
     fn get_published_entries_filtered(
         &mut self,
         auth_token: &AuthToken,
@@ -242,10 +420,57 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
             the_event_id,
             filter,
             models::EntryState::all().filter(|s| s.is_published()),
+            visible_orga_only_values(auth_token, the_event_id),
             false,
         )
     }
 
+    fn for_each_published_entries_chunk(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+        filter: EntryFilter,
+        chunk_size: usize,
+        callback: &mut dyn FnMut(Vec<models::FullEntry>) -> bool,
+    ) -> Result<(), StoreError> {
+        auth_token.check_privilege(the_event_id, Privilege::ShowKueaPlan)?;
+        let state_filter: Vec<_> = models::EntryState::all()
+            .filter(|s| s.is_published())
+            .collect();
+        let orga_only_filter = visible_orga_only_values(auth_token, the_event_id);
+
+        // The chunks are queried one after another with LIMIT and OFFSET, so we need a consistent
+        // snapshot of the entries across all queries.
+        self.connection
+            .build_transaction()
+            .repeatable_read()
+            .read_only()
+            .run(|connection| {
+                let mut offset = 0;
+                loop {
+                    let the_entries = entries_query(
+                        the_event_id,
+                        filter.clone(),
+                        state_filter.iter().copied(),
+                        orga_only_filter,
+                    )
+                    .limit(chunk_size as i64)
+                    .offset(offset as i64)
+                    .load::<models::Entry>(connection)?;
+                    let count = the_entries.len();
+                    if count == 0 {
+                        break;
+                    }
+                    let chunk = load_full_entries(connection, the_entries, false)?;
+                    if !callback(chunk) || count < chunk_size {
+                        break;
+                    }
+                    offset += count;
+                }
+                Ok(())
+            })
+    }
+
     fn get_all_entries_filtered(
         &mut self,
         auth_token: &AuthToken,
@@ -259,6 +484,7 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
             the_event_id,
             filter,
             state_filter.iter(),
+            &[false, true],
             true,
         )
     }
@@ -282,6 +508,36 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
         Ok(result)
     }
 
+    fn get_entry_begins(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+        filter: EntryFilter,
+        state_filter: &[models::EntryState],
+    ) -> Result<Vec<chrono::DateTime<chrono::Utc>>, StoreError> {
+        use diesel::dsl::not;
+        use schema::entries::dsl::*;
+
+        auth_token.check_privilege(
+            the_event_id,
+            if state_filter.iter().all(|s| s.is_published()) {
+                Privilege::ShowKueaPlan
+            } else {
+                Privilege::ManageEntries
+            },
+        )?;
+
+        Ok(entries
+            .select(begin)
+            .filter(event_id.eq(the_event_id))
+            .filter(not(deleted))
+            .filter(state.eq_any(state_filter))
+            .filter(orga_only.eq_any(visible_orga_only_values(auth_token, the_event_id)))
+            .filter(entry_filter_to_sql(filter))
+            .order(begin)
+            .load::<chrono::DateTime<chrono::Utc>>(&mut self.connection)?)
+    }
+
     fn get_entry(
         &mut self,
         auth_token: &AuthToken,
@@ -300,6 +556,12 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
                 .select(models::Entry::as_select())
                 .first::<models::Entry>(connection)?;
             auth_token.check_privilege(entry.event_id, Privilege::ShowKueaPlan)?;
+            // Orga-only entries are hidden completely from other clients
+            if entry.orga_only
+                && !auth_token.has_privilege(entry.event_id, Privilege::ManageEntries)
+            {
+                return Err(StoreError::NotExisting);
+            }
             if !entry.state.is_published() {
                 auth_token.check_privilege(entry.event_id, Privilege::ManageEntries)?;
             }
@@ -313,6 +575,7 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
 
             let previous_dates = previous_dates::table
                 .filter(previous_dates::entry_id.eq(entry.id))
+                .filter(not(previous_dates::deleted))
                 .select(models::PreviousDate::as_select())
                 .load::<models::PreviousDate>(connection)?;
 
@@ -360,79 +623,76 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
         auth_token: &AuthToken,
         entry: models::FullNewEntry,
         extend_previous_dates: bool,
+        record_previous_date: Option<String>,
         expected_last_update: Option<chrono::DateTime<chrono::Utc>>,
     ) -> Result<bool, StoreError> {
-        use diesel::dsl::not;
-        use schema::entries::dsl::*;
-        use schema::previous_dates;
-
         // The event_id of the existing entry is ensured to be the same (see below), so the
         // privilege level check holds for the existing and the new entry.
         auth_token.check_privilege(entry.entry.event_id, Privilege::ManageEntries)?;
 
-        self.connection.transaction(|connection| {
-            if let Some(expected_last_update) = expected_last_update {
-                let actual_last_update = entries
-                    .filter(id.eq(entry.entry.id))
-                    .filter(not(deleted))
-                    .select(last_updated)
-                    .first::<chrono::DateTime<chrono::Utc>>(connection)?;
-                if expected_last_update != actual_last_update {
-                    return Err(StoreError::ConcurrentEditConflict);
-                }
-            }
-
-            check_categories_validity(&[entry.entry.category], entry.entry.event_id, connection)?;
-
-            // entry
-            let upsert_result = {
-                // Unfortunately, `InsertStatement<_, OnConflictValues<...>>`, which is returned by
-                // `.on_onflict().do_update()`, does not implement the QueryDsl trait for
-                // `.filter()`, but only the `FilterDsl` trait directly. We import it locally here,
-                // to not make the .filter() method in the following query ambiguous.
-                use diesel::query_dsl::methods::FilterDsl;
-
-                diesel::insert_into(entries)
-                    .values(&entry.entry)
-                    .on_conflict(id)
-                    .do_update()
-                    // By limiting the search of existing entries to the same event, we prevent
-                    // changes of the event id (i.e. "moving" entries between events), which would
-                    // be a security loophole
-                    .set(&entry.entry)
-                    .filter(event_id.eq(entry.entry.event_id))
-                    .filter(not(deleted))
-                    .returning(sql_upsert_is_updated())
-                    .load::<bool>(connection)?
-            };
-            if upsert_result.is_empty() {
-                return Err(StoreError::ConflictEntityExists);
-            }
-            let is_updated = upsert_result[0];
-
-            // rooms
-            check_rooms_validity(&entry.room_ids, entry.entry.event_id, connection)?;
-            update_entry_rooms(entry.entry.id, &entry.room_ids, connection)?;
-
-            // previous dates
-            if !extend_previous_dates {
-                diesel::delete(
-                    previous_dates::table
-                        .filter(super::schema::previous_dates::entry_id.eq(entry.entry.id))
-                        .filter(
-                            previous_dates::id
-                                .ne_all(entry.previous_dates.iter().map(|pd| pd.previous_date.id)),
-                        ),
+        retry_on_transaction_conflict(|| {
+            self.connection.transaction(|connection| {
+                upsert_entry(
+                    auth_token,
+                    &entry,
+                    extend_previous_dates,
+                    record_previous_date.as_deref(),
+                    expected_last_update,
+                    connection,
                 )
-                .execute(connection)?;
-            }
+            })
+        })
+    }
 
-            for previous_date in entry.previous_dates {
-                check_rooms_validity(&previous_date.room_ids, entry.entry.event_id, connection)?;
-                update_or_insert_previous_date(&previous_date, entry.entry.id, connection)?;
-            }
+    fn import_entries(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+        new_entries: Vec<models::FullNewEntry>,
+    ) -> Result<(usize, usize), StoreError> {
+        use schema::entries::dsl::*;
 
-            Ok(!is_updated)
+        auth_token.check_privilege(the_event_id, Privilege::ManageEntries)?;
+        if let Some(entry) = new_entries
+            .iter()
+            .find(|e| e.entry.event_id != the_event_id)
+        {
+            return Err(StoreError::InvalidInputData(format!(
+                "Entry {} does not belong to event {}",
+                entry.entry.id, the_event_id
+            )));
+        }
+
+        retry_on_transaction_conflict(|| {
+            self.connection.transaction(|connection| {
+                let existing_entries = entries
+                    .filter(id.eq_any(new_entries.iter().map(|e| e.entry.id)))
+                    .select((id, event_id, deleted, state))
+                    .load::<(EntryId, EventId, bool, models::EntryState)>(connection)?;
+
+                let mut created = 0;
+                let mut updated = 0;
+                for new_entry in new_entries.iter() {
+                    let mut new_entry = new_entry.clone();
+                    match existing_entries.iter().find(|e| e.0 == new_entry.entry.id) {
+                        Some((_, existing_event_id, is_deleted, _))
+                            if *is_deleted || *existing_event_id != the_event_id =>
+                        {
+                            continue;
+                        }
+                        Some((_, _, _, existing_state)) => {
+                            new_entry.entry.state = *existing_state;
+                        }
+                        None => {}
+                    }
+                    if upsert_entry(auth_token, &new_entry, true, None, None, connection)? {
+                        created += 1;
+                    } else {
+                        updated += 1;
+                    }
+                }
+                Ok((created, updated))
+            })
         })
     }
 
@@ -452,6 +712,8 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
 
             auth_token.check_privilege(current_event_id, Privilege::ManageEntries)?;
 
+            let old_state = load_entry_audit_state(entry_id, connection)?;
+
             if let Some(room_ids) = entry_data.room_ids.as_ref() {
                 check_rooms_validity(room_ids, current_event_id, connection)?;
                 update_entry_rooms(entry_id, room_ids, connection)?;
@@ -461,9 +723,22 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
             }
             diesel::update(entries)
                 .filter(id.eq(entry_id))
-                .set((entry_data, last_updated.eq(diesel::dsl::now)))
+                .set((
+                    entry_data,
+                    last_updated.eq(diesel::dsl::now),
+                    last_updated_by_role.eq(auth_token.acting_role(Privilege::ManageEntries)),
+                ))
                 .execute(connection)?;
 
+            write_entry_audit_log(
+                auth_token,
+                current_event_id,
+                entry_id,
+                models::EntryAuditAction::Update,
+                old_state,
+                connection,
+            )?;
+
             Ok(())
         })
     }
@@ -486,7 +761,11 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
             check_submission_policies(&entry, connection, event_data.entry_submission_mode)?;
 
             diesel::insert_into(entries)
-                .values(&entry.entry)
+                .values((
+                    &entry.entry,
+                    last_updated_by_role
+                        .eq(auth_token.acting_role(Privilege::SubmitParticipantEntries)),
+                ))
                 .execute(connection)?;
 
             // rooms
@@ -514,6 +793,7 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
         auth_token.check_privilege(the_event_id, Privilege::ManageEntries)?;
 
         self.connection.transaction(|connection| {
+            let old_state = load_entry_audit_state(entry_id, connection)?;
             let count = diesel::update(entries)
                 .filter(id.eq(entry_id))
                 .filter(event_id.eq(the_event_id))
@@ -523,10 +803,147 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
                 return Err(StoreError::NotExisting);
             }
 
+            write_entry_audit_log(
+                auth_token,
+                the_event_id,
+                entry_id,
+                models::EntryAuditAction::Delete,
+                old_state,
+                connection,
+            )?;
+
+            Ok(())
+        })
+    }
+
+    fn get_entry_audit_log(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+        the_entry_id: EntryId,
+    ) -> Result<Vec<models::EntryAuditLogRecord>, StoreError> {
+        use schema::entry_audit_log::dsl::*;
+
+        auth_token.check_privilege(the_event_id, Privilege::ManageEntries)?;
+
+        self.connection.transaction(|connection| {
+            let entry_count = schema::entries::table
+                .filter(schema::entries::id.eq(the_entry_id))
+                .filter(schema::entries::event_id.eq(the_event_id))
+                .count()
+                .get_result::<i64>(connection)?;
+            if entry_count == 0 {
+                return Err(StoreError::NotExisting);
+            }
+
+            Ok(entry_audit_log
+                .filter(entry_id.eq(the_entry_id))
+                .filter(event_id.eq(the_event_id))
+                .order_by((timestamp, id))
+                .select(models::EntryAuditLogRecord::as_select())
+                .load(connection)?)
+        })
+    }
+
+    fn move_entry(
+        &mut self,
+        auth_token: &AuthToken,
+        target_auth_token: &AuthToken,
+        entry_id: EntryId,
+        target_event_id: EventId,
+        target_category_id: CategoryId,
+    ) -> Result<(), StoreError> {
+        use diesel::dsl::not;
+        use schema::entries::dsl::*;
+
+        target_auth_token.check_privilege(target_event_id, Privilege::ManageEntries)?;
+
+        self.connection.transaction(|connection| {
+            let current_event_id = entries
+                .select(event_id)
+                .filter(id.eq(entry_id))
+                .filter(not(deleted))
+                .first::<EventId>(connection)?;
+
+            auth_token.check_privilege(current_event_id, Privilege::ManageEntries)?;
+            check_categories_validity(&[target_category_id], target_event_id, connection)?;
+            let old_state = load_entry_audit_state(entry_id, connection)?;
+
+            // Rooms belong to a specific event, so they cannot be kept when moving the entry
+            update_entry_rooms(entry_id, &[], connection)?;
+            diesel::delete(
+                schema::previous_date_rooms::table.filter(
+                    schema::previous_date_rooms::previous_date_id.eq_any(
+                        schema::previous_dates::table
+                            .filter(schema::previous_dates::entry_id.eq(entry_id))
+                            .select(schema::previous_dates::id),
+                    ),
+                ),
+            )
+            .execute(connection)?;
+
+            diesel::update(entries)
+                .filter(id.eq(entry_id))
+                .set((
+                    event_id.eq(target_event_id),
+                    category.eq(target_category_id),
+                    last_updated.eq(diesel::dsl::now),
+                    last_updated_by_role
+                        .eq(target_auth_token.acting_role(Privilege::ManageEntries)),
+                ))
+                .execute(connection)?;
+
+            // The deletion record in the source event is also used for reporting the entry as
+            // deleted in the source event's updates
+            write_entry_audit_log(
+                auth_token,
+                current_event_id,
+                entry_id,
+                models::EntryAuditAction::Delete,
+                old_state,
+                connection,
+            )?;
+            write_entry_audit_log(
+                target_auth_token,
+                target_event_id,
+                entry_id,
+                models::EntryAuditAction::Create,
+                None,
+                connection,
+            )?;
+
             Ok(())
         })
     }
 
+    fn copy_entry_to_event(
+        &mut self,
+        auth_token: &AuthToken,
+        target_auth_token: &AuthToken,
+        entry_id: EntryId,
+        target_event_id: EventId,
+        target_category_id: CategoryId,
+    ) -> Result<EntryId, StoreError> {
+        target_auth_token.check_privilege(target_event_id, Privilege::ManageEntries)?;
+
+        let mut entry: models::FullNewEntry = self.get_entry(auth_token, entry_id)?.into();
+        let new_entry_id = Uuid::now_v7();
+        entry.entry.id = new_entry_id;
+        entry.entry.event_id = target_event_id;
+        entry.entry.category = target_category_id;
+        // Rooms belong to a specific event, so they cannot be kept when copying the entry
+        entry.room_ids = Vec::new();
+        for previous_date in entry.previous_dates.iter_mut() {
+            previous_date.previous_date.id = Uuid::now_v7();
+            previous_date.previous_date.entry_id = new_entry_id;
+            previous_date.room_ids = Vec::new();
+        }
+
+        // Checks the validity of the category for the target event
+        self.create_or_update_entry(target_auth_token, entry, false, None, None)?;
+        Ok(new_entry_id)
+    }
+
     fn create_or_update_previous_date(
         &mut self,
         auth_token: &AuthToken,
@@ -557,7 +974,8 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
         entry_id: EntryId,
         previous_date_id: PreviousDateId,
     ) -> Result<(), StoreError> {
-        use schema::entries;
+        use diesel::dsl::not;
+        use schema::previous_dates;
 
         self.connection.transaction(|connection| {
             // Check if referenced entry exists and get entry's event_id for auth check
@@ -568,39 +986,512 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
 
             auth_token.check_privilege(event_id, Privilege::ManageEntries)?;
 
-            diesel::delete(
-                schema::previous_dates::table
-                    .filter(schema::previous_dates::entry_id.eq(entry_id))
-                    .filter(schema::previous_dates::id.eq(previous_date_id)),
-            )
-            .execute(connection)?;
-            // We need to somehow mark the entry as changed, so clients using the sync API will be
-            // informed about the change. Since the previous_date itself does no longer exist, we
-            // cannot use it's last_updated field for this purpose, anymore.
-            diesel::update(entries::table)
-                .filter(entries::id.eq(entry_id))
-                .set(entries::last_updated.eq(diesel::dsl::now))
+            // The previous date is only marked as deleted, such that it can be restored. Its
+            // last_updated field is updated by the database trigger, so clients using the sync
+            // API will be informed about the change.
+            diesel::update(previous_dates::table)
+                .filter(previous_dates::entry_id.eq(entry_id))
+                .filter(previous_dates::id.eq(previous_date_id))
+                .filter(not(previous_dates::deleted))
+                .set(previous_dates::deleted.eq(true))
                 .execute(connection)?;
             Ok(())
         })
     }
 
-    fn get_entry_count_by_category(
+    fn get_deleted_previous_dates(
         &mut self,
         auth_token: &AuthToken,
-        the_event_id: EventId,
-    ) -> Result<Vec<(CategoryId, i64)>, StoreError> {
-        use diesel::dsl::{count_star, not};
-        use schema::entries::dsl::*;
+        entry_id: EntryId,
+    ) -> Result<Vec<models::FullPreviousDate>, StoreError> {
+        use diesel::dsl::not;
+        use schema::previous_dates;
 
-        auth_token.check_privilege(the_event_id, Privilege::ShowKueaPlan)?;
-        Ok(entries
-            .filter(event_id.eq(the_event_id))
-            .filter(not(deleted))
-            .filter(not(is_cancelled))
-            .filter(state.eq_any(models::EntryState::all().filter(|s| s.is_published())))
-            .group_by(category)
-            .select((category, count_star()))
+        self.connection.transaction(|connection| {
+            let event_id = schema::entries::table
+                .filter(schema::entries::id.eq(entry_id))
+                .filter(not(schema::entries::deleted))
+                .select(schema::entries::event_id)
+                .first::<EventId>(connection)?;
+
+            auth_token.check_privilege(event_id, Privilege::ManageEntries)?;
+
+            let the_previous_dates = previous_dates::table
+                .filter(previous_dates::entry_id.eq(entry_id))
+                .filter(previous_dates::deleted)
+                .order_by(previous_dates::begin)
+                .select(models::PreviousDate::as_select())
+                .load::<models::PreviousDate>(connection)?;
+
+            let the_previous_date_rooms =
+                models::PreviousDateRoomMapping::belonging_to(&the_previous_dates)
+                    .inner_join(schema::rooms::table)
+                    .filter(not(schema::rooms::deleted))
+                    .select(models::PreviousDateRoomMapping::as_select())
+                    .load::<models::PreviousDateRoomMapping>(connection)?
+                    .grouped_by(&the_previous_dates);
+
+            Ok(the_previous_dates
+                .into_iter()
+                .zip(the_previous_date_rooms)
+                .map(
+                    |(previous_date, previous_date_rooms)| models::FullPreviousDate {
+                        previous_date,
+                        room_ids: previous_date_rooms
+                            .into_iter()
+                            .map(|pdr| pdr.room_id)
+                            .collect(),
+                    },
+                )
+                .collect())
+        })
+    }
+
+    fn restore_previous_date(
+        &mut self,
+        auth_token: &AuthToken,
+        entry_id: EntryId,
+        previous_date_id: PreviousDateId,
+    ) -> Result<(), StoreError> {
+        use diesel::dsl::not;
+        use schema::previous_dates;
+
+        self.connection.transaction(|connection| {
+            let event_id = schema::entries::table
+                .filter(schema::entries::id.eq(entry_id))
+                .filter(not(schema::entries::deleted))
+                .select(schema::entries::event_id)
+                .first::<EventId>(connection)?;
+
+            auth_token.check_privilege(event_id, Privilege::ManageEntries)?;
+
+            let is_deleted = previous_dates::table
+                .select(previous_dates::deleted)
+                .filter(previous_dates::id.eq(previous_date_id))
+                .filter(previous_dates::entry_id.eq(entry_id))
+                .first::<bool>(connection)
+                .optional()?
+                .ok_or(StoreError::NotExisting)?;
+            if !is_deleted {
+                return Err(StoreError::ConflictEntityExists);
+            }
+
+            diesel::update(previous_dates::table)
+                .filter(previous_dates::id.eq(previous_date_id))
+                .set(previous_dates::deleted.eq(false))
+                .execute(connection)?;
+            Ok(())
+        })
+    }
+
+    fn get_distinct_responsible_persons(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+    ) -> Result<Vec<String>, StoreError> {
+        use diesel::dsl::{count_star, not};
+        use schema::entries::dsl::*;
+
+        auth_token.check_privilege(the_event_id, Privilege::ShowKueaPlan)?;
+        let states: Vec<&models::EntryState> =
+            if auth_token.has_privilege(the_event_id, Privilege::ManageEntries) {
+                models::EntryState::all().collect()
+            } else {
+                models::EntryState::all()
+                    .filter(|s| s.is_published())
+                    .collect()
+            };
+
+        Ok(entries
+            .filter(event_id.eq(the_event_id))
+            .filter(not(deleted))
+            .filter(state.eq_any(states))
+            .filter(orga_only.eq_any(visible_orga_only_values(auth_token, the_event_id)))
+            .filter(responsible_person.ne(""))
+            .group_by(responsible_person)
+            .order_by((count_star().desc(), responsible_person.asc()))
+            .select(responsible_person)
+            .load::<String>(&mut self.connection)?)
+    }
+
+    fn reassign_responsible_person(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<EntryId>, StoreError> {
+        use diesel::dsl::not;
+        use schema::entries::dsl::*;
+
+        auth_token.check_privilege(the_event_id, Privilege::ManageEntries)?;
+        if from == to {
+            return Ok(Vec::new());
+        }
+
+        self.connection.transaction(|connection| {
+            let entry_ids = entries
+                .select(id)
+                .filter(event_id.eq(the_event_id))
+                .filter(not(deleted))
+                .filter(responsible_person.eq(from))
+                .load::<EntryId>(connection)?;
+            bulk_update_entries(
+                auth_token,
+                the_event_id,
+                &entry_ids,
+                responsible_person.eq(to),
+                connection,
+            )?;
+            Ok(entry_ids)
+        })
+    }
+
+    fn bulk_set_category(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+        entry_ids: &[EntryId],
+        category_id: CategoryId,
+    ) -> Result<Vec<EntryId>, StoreError> {
+        use schema::entries::dsl::*;
+
+        auth_token.check_privilege(the_event_id, Privilege::ManageEntries)?;
+
+        self.connection.transaction(|connection| {
+            check_categories_validity(&[category_id], the_event_id, connection)?;
+
+            let existing_entries = entries
+                .select((id, deleted, category))
+                .filter(id.eq_any(entry_ids))
+                .filter(event_id.eq(the_event_id))
+                .load::<(EntryId, bool, CategoryId)>(connection)?;
+            if let Some(missing_id) = entry_ids
+                .iter()
+                .find(|entry_id| !existing_entries.iter().any(|(e, _, _)| e == *entry_id))
+            {
+                return Err(StoreError::InvalidInputData(format!(
+                    "Entry {missing_id} does not belong to event {the_event_id}."
+                )));
+            }
+
+            let changed_entry_ids: Vec<EntryId> = existing_entries
+                .into_iter()
+                .filter(|(_, entry_deleted, entry_category)| {
+                    !entry_deleted && *entry_category != category_id
+                })
+                .map(|(entry_id, _, _)| entry_id)
+                .collect();
+            bulk_update_entries(
+                auth_token,
+                the_event_id,
+                &changed_entry_ids,
+                category.eq(category_id),
+                connection,
+            )?;
+            Ok(changed_entry_ids)
+        })
+    }
+
+    fn cancel_entries_in_category(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+        category_id: CategoryId,
+        cancelled: bool,
+    ) -> Result<Vec<EntryId>, StoreError> {
+        use diesel::dsl::not;
+        use schema::entries::dsl::*;
+
+        auth_token.check_privilege(the_event_id, Privilege::ManageEntries)?;
+
+        self.connection.transaction(|connection| {
+            let category_event_id = schema::categories::table
+                .filter(schema::categories::id.eq(category_id))
+                .select(schema::categories::event_id)
+                .first::<EventId>(connection)?;
+            if category_event_id != the_event_id {
+                return Err(StoreError::NotExisting);
+            }
+
+            let changed_entry_ids = entries
+                .select(id)
+                .filter(event_id.eq(the_event_id))
+                .filter(category.eq(category_id))
+                .filter(not(deleted))
+                .filter(is_cancelled.ne(cancelled))
+                .load::<EntryId>(connection)?;
+            bulk_update_entries(
+                auth_token,
+                the_event_id,
+                &changed_entry_ids,
+                is_cancelled.eq(cancelled),
+                connection,
+            )?;
+            Ok(changed_entry_ids)
+        })
+    }
+
+    fn shift_entries(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+        filter: EntryFilter,
+        offset: chrono::Duration,
+        previous_date_comment: &str,
+    ) -> Result<Vec<EntryId>, StoreError> {
+        use diesel::dsl::not;
+        use schema::entries::dsl::*;
+
+        auth_token.check_privilege(the_event_id, Privilege::ManageEntries)?;
+        if offset.is_zero() {
+            return Ok(Vec::new());
+        }
+
+        self.connection.transaction(|connection| {
+            let shifted_entries = entries
+                .select((id, begin, end))
+                .filter(event_id.eq(the_event_id))
+                .filter(not(deleted))
+                .filter(entry_filter_to_sql(filter))
+                .load::<(
+                    EntryId,
+                    chrono::DateTime<chrono::Utc>,
+                    chrono::DateTime<chrono::Utc>,
+                )>(connection)?;
+            let entry_ids: Vec<EntryId> = shifted_entries.iter().map(|(e, _, _)| *e).collect();
+            let entry_rooms = schema::entry_rooms::table
+                .filter(schema::entry_rooms::entry_id.eq_any(&entry_ids))
+                .select((schema::entry_rooms::entry_id, schema::entry_rooms::room_id))
+                .load::<(EntryId, RoomId)>(connection)?;
+
+            // Keep the pre-shift dates of the entries as previous dates
+            for (entry_id, entry_begin, entry_end) in shifted_entries {
+                update_or_insert_previous_date(
+                    &models::FullPreviousDate {
+                        previous_date: models::PreviousDate {
+                            id: Uuid::now_v7(),
+                            entry_id,
+                            comment: previous_date_comment.to_owned(),
+                            begin: entry_begin,
+                            end: entry_end,
+                            last_updated: chrono::Utc::now(),
+                        },
+                        room_ids: entry_rooms
+                            .iter()
+                            .filter(|(e, _)| *e == entry_id)
+                            .map(|(_, room_id)| *room_id)
+                            .collect(),
+                    },
+                    entry_id,
+                    connection,
+                )?;
+            }
+
+            bulk_update_entries(
+                auth_token,
+                the_event_id,
+                &entry_ids,
+                (begin.eq(begin + offset), end.eq(end + offset)),
+                connection,
+            )?;
+            Ok(entry_ids)
+        })
+    }
+
+    fn get_public_entry_ids(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+        entry_ids: &[EntryId],
+    ) -> Result<Vec<EntryId>, StoreError> {
+        use schema::entries::dsl::*;
+        auth_token.check_privilege(the_event_id, Privilege::ShowKueaPlan)?;
+
+        Ok(entries
+            .filter(id.eq_any(entry_ids))
+            .filter(state.eq_any(models::EntryState::all().filter(|s| s.is_published())))
+            .filter(orga_only.eq(false))
+            .select(id)
+            .load::<EntryId>(&mut self.connection)?)
+    }
+
+    fn get_updates_since(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<models::Updates, StoreError> {
+        use diesel::dsl::{exists, not};
+        use schema::entries::dsl::*;
+        use schema::{previous_dates, rooms};
+        auth_token.check_privilege(the_event_id, Privilege::ShowKueaPlan)?;
+
+        self.connection.transaction(|connection| {
+            let Some(since) = since else {
+                return Ok(models::Updates {
+                    changed_entries: get_entries_generic(
+                        connection,
+                        the_event_id,
+                        EntryFilter::default(),
+                        models::EntryState::all().filter(|s| s.is_published()),
+                        visible_orga_only_values(auth_token, the_event_id),
+                        false,
+                    )?,
+                    deleted_entries: Vec::new(),
+                    rooms: Some(load_rooms(connection, the_event_id)?),
+                });
+            };
+
+            // Previous dates have their own last_updated field, so we need to check them as well
+            let changed_since = last_updated.gt(since).or(exists(
+                previous_dates::table
+                    .filter(previous_dates::entry_id.eq(id))
+                    .filter(previous_dates::last_updated.gt(since)),
+            ));
+            let orga_only_values = visible_orga_only_values(auth_token, the_event_id);
+            let changed_entries = entries
+                .filter(event_id.eq(the_event_id))
+                .filter(changed_since)
+                .filter(not(deleted))
+                .filter(state.eq_any(models::EntryState::all().filter(|s| s.is_published())))
+                .filter(orga_only.eq_any(orga_only_values))
+                .order_by((begin.asc(), end.asc(), id.asc()))
+                .select(models::Entry::as_select())
+                .load::<models::Entry>(connection)?;
+            let mut deleted_entries = entries
+                .filter(event_id.eq(the_event_id))
+                .filter(changed_since)
+                .filter(
+                    deleted
+                        .or(not(state.eq_any(
+                            models::EntryState::all().filter(|s| s.is_published()),
+                        )))
+                        .or(not(orga_only.eq_any(orga_only_values))),
+                )
+                .select(id)
+                .load::<EntryId>(connection)?;
+            // Entries which have been moved to another event are only found via the deletion
+            // record in this event's audit log
+            deleted_entries.extend(
+                schema::entry_audit_log::table
+                    .inner_join(entries)
+                    .filter(schema::entry_audit_log::event_id.eq(the_event_id))
+                    .filter(schema::entry_audit_log::action.eq(models::EntryAuditAction::Delete))
+                    .filter(schema::entry_audit_log::timestamp.gt(since))
+                    .filter(event_id.ne(the_event_id))
+                    .select(id)
+                    .load::<EntryId>(connection)?,
+            );
+            deleted_entries.sort();
+            deleted_entries.dedup();
+
+            let rooms_changed: bool = diesel::select(exists(
+                rooms::table
+                    .filter(rooms::event_id.eq(the_event_id))
+                    .filter(rooms::last_updated.gt(since)),
+            ))
+            .get_result(connection)?;
+
+            Ok(models::Updates {
+                changed_entries: load_full_entries(connection, changed_entries, false)?,
+                deleted_entries,
+                rooms: if rooms_changed {
+                    Some(load_rooms(connection, the_event_id)?)
+                } else {
+                    None
+                },
+            })
+        })
+    }
+
+    fn get_all_previous_dates(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+    ) -> Result<Vec<models::EntryPreviousDates>, StoreError> {
+        use diesel::dsl::not;
+        use schema::{entries, previous_dates};
+
+        auth_token.check_privilege(the_event_id, Privilege::ShowKueaPlan)?;
+        let states: Vec<&models::EntryState> =
+            if auth_token.has_privilege(the_event_id, Privilege::ManageEntries) {
+                models::EntryState::all().collect()
+            } else {
+                models::EntryState::all()
+                    .filter(|s| s.is_published())
+                    .collect()
+            };
+
+        self.connection.transaction(|connection| {
+            let result = previous_dates::table
+                .inner_join(entries::table)
+                .filter(entries::event_id.eq(the_event_id))
+                .filter(not(entries::deleted))
+                .filter(not(previous_dates::deleted))
+                .filter(entries::state.eq_any(states))
+                .filter(
+                    entries::orga_only.eq_any(visible_orga_only_values(auth_token, the_event_id)),
+                )
+                .order_by((entries::begin, entries::id, previous_dates::begin))
+                .select((entries::title, models::PreviousDate::as_select()))
+                .load::<(String, models::PreviousDate)>(connection)?;
+            let (titles, the_previous_dates): (Vec<String>, Vec<models::PreviousDate>) =
+                result.into_iter().unzip();
+
+            let the_previous_date_rooms =
+                models::PreviousDateRoomMapping::belonging_to(&the_previous_dates)
+                    .inner_join(schema::rooms::table)
+                    .filter(not(schema::rooms::deleted))
+                    .select(models::PreviousDateRoomMapping::as_select())
+                    .load::<models::PreviousDateRoomMapping>(connection)?
+                    .grouped_by(&the_previous_dates);
+
+            let mut grouped: Vec<models::EntryPreviousDates> = Vec::new();
+            for ((title, previous_date), previous_date_rooms) in titles
+                .into_iter()
+                .zip(the_previous_dates)
+                .zip(the_previous_date_rooms)
+            {
+                let full_previous_date = models::FullPreviousDate {
+                    previous_date,
+                    room_ids: previous_date_rooms
+                        .into_iter()
+                        .map(|pdr| pdr.room_id)
+                        .collect(),
+                };
+                match grouped.last_mut() {
+                    Some(group) if group.entry_id == full_previous_date.previous_date.entry_id => {
+                        group.previous_dates.push(full_previous_date)
+                    }
+                    _ => grouped.push(models::EntryPreviousDates {
+                        entry_id: full_previous_date.previous_date.entry_id,
+                        entry_title: title,
+                        previous_dates: vec![full_previous_date],
+                    }),
+                }
+            }
+            Ok(grouped)
+        })
+    }
+
+    fn get_entry_count_by_category(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+    ) -> Result<Vec<(CategoryId, i64)>, StoreError> {
+        use diesel::dsl::{count_star, not};
+        use schema::entries::dsl::*;
+
+        auth_token.check_privilege(the_event_id, Privilege::ShowKueaPlan)?;
+        Ok(entries
+            .filter(event_id.eq(the_event_id))
+            .filter(not(deleted))
+            .filter(not(is_cancelled))
+            .filter(state.eq_any(models::EntryState::all().filter(|s| s.is_published())))
+            .filter(orga_only.eq_any(visible_orga_only_values(auth_token, the_event_id)))
+            .group_by(category)
+            .select((category, count_star()))
             .load::<(CategoryId, i64)>(&mut self.connection)?)
     }
 
@@ -621,6 +1512,7 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
             .group_by(schema::entry_rooms::room_id)
             .select((schema::entry_rooms::room_id, count_star()))
             .filter(state.eq_any(models::EntryState::all().filter(|s| s.is_published())))
+            .filter(orga_only.eq_any(visible_orga_only_values(auth_token, the_event_id)))
             .load::<(RoomId, i64)>(&mut self.connection)?)
     }
 
@@ -641,6 +1533,7 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
                 schema::entry_rooms::table.filter(schema::entry_rooms::entry_id.eq(id)),
             )))
             .filter(state.eq_any(models::EntryState::all().filter(|s| s.is_published())))
+            .filter(orga_only.eq_any(visible_orga_only_values(auth_token, the_event_id)))
             .select(count_star())
             .first::<i64>(&mut self.connection)?)
     }
@@ -650,22 +1543,49 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
         auth_token: &AuthToken,
         the_event_id: i32,
     ) -> Result<Vec<models::Room>, StoreError> {
+        auth_token.check_privilege(the_event_id, Privilege::ShowKueaPlan)?;
+        load_rooms(&mut self.connection, the_event_id)
+    }
+
+    fn get_rooms_for_events(
+        &mut self,
+        auth_token: &GlobalAuthToken,
+        event_ids: &[EventId],
+    ) -> Result<Vec<(EventId, Vec<models::Room>)>, StoreError> {
         use diesel::dsl::not;
         use schema::rooms::dsl::*;
-        auth_token.check_privilege(the_event_id, Privilege::ShowKueaPlan)?;
+        auth_token.check_privilege(Privilege::ListRoomsOfMultipleEvents)?;
 
-        Ok(rooms
+        let existing_events = schema::events::table
+            .filter(schema::events::id.eq_any(event_ids))
+            .count()
+            .get_result::<i64>(&mut self.connection)?;
+        if existing_events != event_ids.len() as i64 {
+            return Err(StoreError::NotExisting);
+        }
+
+        let all_rooms = rooms
             .select(models::Room::as_select())
-            .filter(event_id.eq(the_event_id))
+            .filter(event_id.eq_any(event_ids))
             .filter(not(deleted))
             .order_by(title)
-            .load::<models::Room>(&mut self.connection)?)
+            .load::<models::Room>(&mut self.connection)?;
+
+        let mut result: Vec<(EventId, Vec<models::Room>)> =
+            event_ids.iter().map(|e| (*e, Vec::new())).collect();
+        for room in all_rooms {
+            if let Some((_, event_rooms)) = result.iter_mut().find(|(e, _)| *e == room.event_id) {
+                event_rooms.push(room);
+            }
+        }
+        Ok(result)
     }
 
     fn create_or_update_room(
         &mut self,
         auth_token: &AuthToken,
         room: models::NewRoom,
+        expected_last_update: Option<chrono::DateTime<chrono::Utc>>,
     ) -> Result<bool, StoreError> {
         use diesel::dsl::not;
         use schema::rooms::dsl::*;
@@ -674,31 +1594,71 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
         // privilege level check holds for both, the existing and the new room.
         auth_token.check_privilege(room.event_id, Privilege::ManageRooms)?;
 
-        let upsert_result = {
-            // Unfortunately, `InsertStatement<_, OnConflictValues<...>>`, which is returned by
-            // `.on_onflict().do_update()`, does not implement the QueryDsl trait for
-            // `.filter()`, but only the `FilterDsl` trait directly. We import it locally here,
-            // to not make the .filter() method in the following query ambiguous.
-            use diesel::query_dsl::methods::FilterDsl;
-
-            diesel::insert_into(rooms)
-                .values(&room)
-                .on_conflict(id)
-                .do_update()
-                // By limiting the search of existing rooms to the same event, we prevent changes
-                // of the event id (i.e. "moving" entries between events), which would be a security
-                // loophole
-                .set(&room)
-                .filter(event_id.eq(room.event_id))
+        self.connection.transaction(|connection| {
+            if let Some(expected_last_update) = expected_last_update {
+                let actual_last_update = rooms
+                    .filter(id.eq(room.id))
+                    .filter(not(deleted))
+                    .select(last_updated)
+                    .first::<chrono::DateTime<chrono::Utc>>(connection)?;
+                if expected_last_update != actual_last_update {
+                    return Err(StoreError::ConcurrentEditConflict);
+                }
+            }
+
+            let upsert_result = {
+                // Unfortunately, `InsertStatement<_, OnConflictValues<...>>`, which is returned by
+                // `.on_onflict().do_update()`, does not implement the QueryDsl trait for
+                // `.filter()`, but only the `FilterDsl` trait directly. We import it locally here,
+                // to not make the .filter() method in the following query ambiguous.
+                use diesel::query_dsl::methods::FilterDsl;
+
+                diesel::insert_into(rooms)
+                    .values(&room)
+                    .on_conflict(id)
+                    .do_update()
+                    // By limiting the search of existing rooms to the same event, we prevent
+                    // changes of the event id (i.e. "moving" entries between events), which would
+                    // be a security loophole
+                    .set(&room)
+                    .filter(event_id.eq(room.event_id))
+                    .filter(not(deleted))
+                    .returning(sql_upsert_is_updated())
+                    .load::<bool>(connection)?
+            };
+            if upsert_result.is_empty() {
+                return Err(StoreError::ConflictEntityExists);
+            }
+            let is_updated = upsert_result[0];
+            Ok(!is_updated)
+        })
+    }
+
+    fn patch_room(
+        &mut self,
+        auth_token: &AuthToken,
+        room_id: RoomId,
+        room_data: models::RoomPatch,
+    ) -> Result<(), StoreError> {
+        use diesel::dsl::not;
+        use schema::rooms::dsl::*;
+
+        self.connection.transaction(|connection| {
+            let current_event_id = rooms
+                .select(event_id)
+                .filter(id.eq(room_id))
                 .filter(not(deleted))
-                .returning(sql_upsert_is_updated())
-                .load::<bool>(&mut self.connection)?
-        };
-        if upsert_result.is_empty() {
-            return Err(StoreError::ConflictEntityExists);
-        }
-        let is_updated = upsert_result[0];
-        Ok(!is_updated)
+                .first::<EventId>(connection)?;
+
+            auth_token.check_privilege(current_event_id, Privilege::ManageRooms)?;
+
+            diesel::update(rooms)
+                .filter(id.eq(room_id))
+                .set((room_data, last_updated.eq(diesel::dsl::now)))
+                .execute(connection)?;
+
+            Ok(())
+        })
     }
 
     fn delete_room(
@@ -708,8 +1668,9 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
         room_id: uuid::Uuid,
         replace_with_rooms: &[RoomId],
         replace_with_room_comment: &str,
+        force: bool,
     ) -> Result<(), StoreError> {
-        use diesel::dsl::exists;
+        use diesel::dsl::{exists, not};
         use schema::rooms::dsl::*;
         use schema::{announcement_rooms, announcements};
 
@@ -720,6 +1681,20 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
         }
 
         self.connection.transaction(|connection| {
+            if !force && replace_with_rooms.is_empty() {
+                let entry_count = schema::entries::table
+                    .inner_join(schema::entry_rooms::table)
+                    .filter(schema::entry_rooms::room_id.eq(room_id))
+                    .filter(schema::entries::event_id.eq(the_event_id))
+                    .filter(not(schema::entries::deleted))
+                    .filter(schema::entries::end.gt(diesel::dsl::now))
+                    .count()
+                    .get_result::<i64>(connection)?;
+                if entry_count > 0 {
+                    return Err(StoreError::RoomInUse { entry_count });
+                }
+            }
+
             if !replace_with_room_comment.is_empty() {
                 use schema::entries::dsl::*;
 
@@ -768,6 +1743,157 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
             Ok(())
         })
     }
+
+    fn merge_rooms(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+        source_room_id: RoomId,
+        target_room_id: RoomId,
+    ) -> Result<(), StoreError> {
+        use schema::rooms::dsl::*;
+        use schema::{announcement_rooms, announcements};
+
+        auth_token.check_privilege(the_event_id, Privilege::ManageRooms)?;
+        auth_token.check_privilege(the_event_id, Privilege::ManageEntries)?;
+
+        self.connection.transaction(|connection| {
+            let count = diesel::update(rooms)
+                .filter(id.eq(source_room_id))
+                .filter(event_id.eq(the_event_id))
+                .filter(deleted.eq(false))
+                .set(deleted.eq(true))
+                .execute(connection)?;
+            if count == 0 {
+                return Err(StoreError::NotExisting);
+            }
+
+            // This is done after marking the source room as deleted, such that merging a room
+            // into itself is rejected, too.
+            let target_exists: bool = diesel::select(diesel::dsl::exists(
+                rooms
+                    .filter(id.eq(target_room_id))
+                    .filter(event_id.eq(the_event_id))
+                    .filter(deleted.eq(false)),
+            ))
+            .get_result(connection)?;
+            if !target_exists {
+                return Err(StoreError::InvalidInputData(format!(
+                    "Room {target_room_id} does not exist in event {the_event_id} or has been deleted."
+                )));
+            }
+
+            replace_room_with_other_rooms(
+                the_event_id,
+                source_room_id,
+                &[target_room_id],
+                connection,
+            )?;
+
+            let announcement_ids: Vec<AnnouncementId> = announcement_rooms::table
+                .filter(announcement_rooms::room_id.eq(source_room_id))
+                .select(announcement_rooms::announcement_id)
+                .get_results(connection)?;
+            diesel::insert_into(announcement_rooms::table)
+                .values(
+                    announcement_ids
+                        .iter()
+                        .map(|announcement_id| {
+                            (
+                                announcement_rooms::announcement_id.eq(*announcement_id),
+                                announcement_rooms::room_id.eq(target_room_id),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .on_conflict_do_nothing()
+                .execute(connection)?;
+            diesel::update(announcements::table)
+                .filter(announcements::id.eq_any(announcement_ids))
+                .set(announcements::last_updated.eq(diesel::dsl::now))
+                .execute(connection)?;
+            Ok(())
+        })
+    }
+
+    fn find_room_double_bookings(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+    ) -> Result<Vec<models::RoomDoubleBooking>, StoreError> {
+        use diesel::dsl::not;
+        use schema::{entries, entry_rooms, rooms};
+
+        auth_token.check_privilege(the_event_id, Privilege::ShowKueaPlan)?;
+        let states: Vec<&models::EntryState> =
+            if auth_token.has_privilege(the_event_id, Privilege::ManageEntries) {
+                models::EntryState::all().collect()
+            } else {
+                models::EntryState::all()
+                    .filter(|s| s.is_published())
+                    .collect()
+            };
+
+        let orga_only_values = visible_orga_only_values(auth_token, the_event_id);
+
+        // Self-join of entry_rooms (and entries) for finding pairs of entries in the same room.
+        // Each pair is only reported once, by requiring the first entry's id to be the lower one.
+        let (other_entries, other_entry_rooms) =
+            diesel::alias!(entries as other_entries, entry_rooms as other_entry_rooms);
+        let result = entry_rooms::table
+            .inner_join(entries::table)
+            .inner_join(rooms::table)
+            .inner_join(
+                other_entry_rooms.on(other_entry_rooms
+                    .field(entry_rooms::room_id)
+                    .eq(entry_rooms::room_id)
+                    .and(
+                        other_entry_rooms
+                            .field(entry_rooms::entry_id)
+                            .gt(entry_rooms::entry_id),
+                    )),
+            )
+            .inner_join(
+                other_entries.on(other_entries
+                    .field(entries::id)
+                    .eq(other_entry_rooms.field(entry_rooms::entry_id))),
+            )
+            .filter(entries::event_id.eq(the_event_id))
+            .filter(not(rooms::deleted))
+            .filter(not(entries::deleted))
+            .filter(not(entries::is_cancelled))
+            .filter(entries::state.eq_any(&states))
+            .filter(entries::orga_only.eq_any(orga_only_values))
+            .filter(not(other_entries.field(entries::deleted)))
+            .filter(not(other_entries.field(entries::is_cancelled)))
+            .filter(other_entries.field(entries::state).eq_any(&states))
+            .filter(
+                other_entries
+                    .field(entries::orga_only)
+                    .eq_any(orga_only_values),
+            )
+            .filter(entries::begin.lt(other_entries.field(entries::end)))
+            .filter(other_entries.field(entries::begin).lt(entries::end))
+            .order_by((
+                rooms::title,
+                rooms::id,
+                entries::begin,
+                other_entries.field(entries::begin),
+            ))
+            .select((rooms::id, entries::id, other_entries.field(entries::id)))
+            .load::<(RoomId, EntryId, EntryId)>(&mut self.connection)?;
+
+        Ok(result
+            .into_iter()
+            .map(
+                |(room_id, entry_id, other_entry_id)| models::RoomDoubleBooking {
+                    room_id,
+                    entry_ids: (entry_id, other_entry_id),
+                },
+            )
+            .collect())
+    }
+
     fn get_categories(
         &mut self,
         auth_token: &AuthToken,
@@ -785,41 +1911,125 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
             .load::<models::Category>(&mut self.connection)?)
     }
 
+    fn get_category_entry_counts(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+    ) -> Result<Vec<(models::Category, i64)>, StoreError> {
+        use diesel::dsl::{count, not};
+        use schema::categories::dsl::*;
+        use schema::entries;
+        auth_token.check_privilege(the_event_id, Privilege::ShowKueaPlan)?;
+        let states: Vec<&models::EntryState> =
+            if auth_token.has_privilege(the_event_id, Privilege::ManageEntries) {
+                models::EntryState::all().collect()
+            } else {
+                models::EntryState::all()
+                    .filter(|s| s.is_published())
+                    .collect()
+            };
+
+        Ok(categories
+            .left_join(
+                entries::table.on(entries::category
+                    .eq(id)
+                    .and(not(entries::deleted))
+                    .and(entries::state.eq_any(states))
+                    .and(
+                        entries::orga_only
+                            .eq_any(visible_orga_only_values(auth_token, the_event_id)),
+                    )),
+            )
+            .filter(event_id.eq(the_event_id))
+            .filter(not(deleted))
+            .group_by(id)
+            .order_by((sort_key, title))
+            .select((models::Category::as_select(), count(entries::id.nullable())))
+            .load::<(models::Category, i64)>(&mut self.connection)?)
+    }
+
     fn create_or_update_category(
         &mut self,
         auth_token: &AuthToken,
-        category: models::NewCategory,
+        mut category: models::NewCategory,
     ) -> Result<bool, StoreError> {
         use diesel::dsl::not;
         use schema::categories::dsl::*;
 
         auth_token.check_privilege(category.event_id, Privilege::ManageCategories)?;
 
-        let upsert_result = {
-            // Unfortunately, `InsertStatement<_, OnConflictValues<...>>`, which is returned by
-            // `.on_onflict().do_update()`, does not implement the QueryDsl trait for
-            // `.filter()`, but only the `FilterDsl` trait directly. We import it locally here,
-            // to not make the .filter() method in the following query ambiguous.
-            use diesel::query_dsl::methods::FilterDsl;
-
-            diesel::insert_into(categories)
-                .values(&category)
-                .on_conflict(id)
-                .do_update()
-                // By limiting the search of existing categories to the same event, we prevent
-                // changes of the event id (i.e. "moving" categories between events), which would be
-                // a security loophole
-                .set(&category)
-                .filter(event_id.eq(category.event_id))
+        self.connection.transaction(|connection| {
+            category.sort_key = free_category_sort_key(
+                category.event_id,
+                category.id,
+                category.sort_key,
+                connection,
+            )?;
+
+            let upsert_result = {
+                // Unfortunately, `InsertStatement<_, OnConflictValues<...>>`, which is returned by
+                // `.on_onflict().do_update()`, does not implement the QueryDsl trait for
+                // `.filter()`, but only the `FilterDsl` trait directly. We import it locally here,
+                // to not make the .filter() method in the following query ambiguous.
+                use diesel::query_dsl::methods::FilterDsl;
+
+                diesel::insert_into(categories)
+                    .values(&category)
+                    .on_conflict(id)
+                    .do_update()
+                    // By limiting the search of existing categories to the same event, we prevent
+                    // changes of the event id (i.e. "moving" categories between events), which
+                    // would be a security loophole
+                    .set(&category)
+                    .filter(event_id.eq(category.event_id))
+                    .filter(not(deleted))
+                    .returning(sql_upsert_is_updated())
+                    .load::<bool>(connection)?
+            };
+            if upsert_result.is_empty() {
+                return Err(StoreError::ConflictEntityExists);
+            }
+            let is_updated = upsert_result[0];
+            Ok(!is_updated)
+        })
+    }
+
+    fn reorder_categories(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+        ordered_ids: Vec<CategoryId>,
+    ) -> Result<(), StoreError> {
+        use diesel::dsl::not;
+        use schema::categories::dsl::*;
+
+        auth_token.check_privilege(the_event_id, Privilege::ManageCategories)?;
+
+        self.connection.transaction(|connection| {
+            let mut existing_ids = categories
+                .select(id)
+                .filter(event_id.eq(the_event_id))
                 .filter(not(deleted))
-                .returning(sql_upsert_is_updated())
-                .load::<bool>(&mut self.connection)?
-        };
-        if upsert_result.is_empty() {
-            return Err(StoreError::ConflictEntityExists);
-        }
-        let is_updated = upsert_result[0];
-        Ok(!is_updated)
+                .load::<CategoryId>(connection)?;
+            existing_ids.sort_unstable();
+            let mut given_ids = ordered_ids.clone();
+            given_ids.sort_unstable();
+            if given_ids != existing_ids {
+                return Err(StoreError::InvalidInputData(
+                    "The list of categories must contain each category of the event exactly once."
+                        .to_owned(),
+                ));
+            }
+
+            for (index, category_id) in ordered_ids.iter().enumerate() {
+                diesel::update(categories)
+                    .filter(id.eq(category_id))
+                    .set((sort_key.eq(index as i32), last_updated.eq(diesel::dsl::now)))
+                    .execute(connection)?;
+            }
+
+            Ok(())
+        })
     }
 
     fn delete_category(
@@ -889,12 +2099,83 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
                 };
             }
 
-            // update announcements for the deleted category
-            // We do not actually delete the announcement_categories references here. This is not
-            // necessary, because the related query function (get_announcements()) filters out
-            // references to deleted categories.
-            // On the other hand, keeping the references allows for recovery from an accidental
-            // deletion of a category.
+            // update announcements for the deleted category
+            // We do not actually delete the announcement_categories references here. This is not
+            // necessary, because the related query function (get_announcements()) filters out
+            // references to deleted categories.
+            // On the other hand, keeping the references allows for recovery from an accidental
+            // deletion of a category.
+            diesel::update(announcements::table)
+                .filter(exists(
+                    announcement_categories::table
+                        .select(0.as_sql::<diesel::sql_types::Integer>())
+                        .filter(announcement_categories::announcement_id.eq(announcements::id))
+                        .filter(announcement_categories::category_id.eq(category_id)),
+                ))
+                .set(announcements::last_updated.eq(diesel::dsl::now))
+                .execute(connection)?;
+
+            Ok(())
+        })
+    }
+
+    fn get_deleted_categories(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+    ) -> Result<Vec<models::Category>, StoreError> {
+        use schema::categories::dsl::*;
+        auth_token.check_privilege(the_event_id, Privilege::ManageCategories)?;
+
+        Ok(categories
+            .select(models::Category::as_select())
+            .filter(event_id.eq(the_event_id))
+            .filter(deleted)
+            .order_by((sort_key, title))
+            .load::<models::Category>(&mut self.connection)?)
+    }
+
+    fn restore_category(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+        category_id: CategoryId,
+    ) -> Result<(), StoreError> {
+        use diesel::dsl::exists;
+        use schema::{announcement_categories, announcements, categories};
+        auth_token.check_privilege(the_event_id, Privilege::ManageCategories)?;
+
+        self.connection.transaction(|connection| {
+            let is_deleted = categories::table
+                .select(categories::deleted)
+                .filter(categories::id.eq(category_id))
+                .filter(categories::event_id.eq(the_event_id))
+                .first::<bool>(connection)
+                .optional()?
+                .ok_or(StoreError::NotExisting)?;
+            if !is_deleted {
+                return Err(StoreError::ConflictEntityExists);
+            }
+
+            // Another category might have taken the sort key in the meantime
+            let current_sort_key = categories::table
+                .select(categories::sort_key)
+                .filter(categories::id.eq(category_id))
+                .first::<i32>(connection)?;
+            let new_sort_key =
+                free_category_sort_key(the_event_id, category_id, current_sort_key, connection)?;
+
+            diesel::update(categories::table)
+                .filter(categories::id.eq(category_id))
+                .set((
+                    categories::deleted.eq(false),
+                    categories::sort_key.eq(new_sort_key),
+                    categories::last_updated.eq(diesel::dsl::now),
+                ))
+                .execute(connection)?;
+
+            // The announcement_categories references have been kept on deletion (see
+            // delete_category()), so the affected announcements show the category again.
             diesel::update(announcements::table)
                 .filter(exists(
                     announcement_categories::table
@@ -920,14 +2201,15 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
         auth_token.check_privilege(the_event_id, Privilege::ShowKueaPlan)?;
 
         self.connection.transaction(|connection| {
+            let filter_expression = if let Some(filter) = filter {
+                announcement_filter_to_sql(filter, connection, the_event_id)?
+            } else {
+                Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"))
+            };
             let the_announcements = announcements
                 .filter(event_id.eq(the_event_id))
                 .filter(not(deleted))
-                .filter(if let Some(filter) = filter {
-                    announcement_filter_to_sql(filter)
-                } else {
-                    Box::new(diesel::dsl::sql::<diesel::sql_types::Bool>("TRUE"))
-                })
+                .filter(filter_expression)
                 .order_by(sort_key)
                 .select(models::Announcement::as_select())
                 .load::<models::Announcement>(connection)?;
@@ -968,6 +2250,44 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
         })
     }
 
+    fn get_announcement(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+        announcement_id: AnnouncementId,
+    ) -> Result<models::FullAnnouncement, StoreError> {
+        use diesel::dsl::not;
+        use schema::announcements::dsl::*;
+        auth_token.check_privilege(the_event_id, Privilege::ShowKueaPlan)?;
+
+        self.connection.transaction(|connection| {
+            let announcement = announcements
+                .filter(id.eq(announcement_id))
+                .filter(event_id.eq(the_event_id))
+                .filter(not(deleted))
+                .select(models::Announcement::as_select())
+                .first::<models::Announcement>(connection)?;
+
+            let category_ids = models::AnnouncementCategoryMapping::belonging_to(&announcement)
+                .inner_join(schema::categories::table)
+                .filter(not(schema::categories::deleted))
+                .select(schema::announcement_categories::category_id)
+                .load::<CategoryId>(connection)?;
+
+            let room_ids = models::AnnouncementRoomMapping::belonging_to(&announcement)
+                .inner_join(schema::rooms::table)
+                .filter(not(schema::rooms::deleted))
+                .select(schema::announcement_rooms::room_id)
+                .load::<RoomId>(connection)?;
+
+            Ok(models::FullAnnouncement {
+                announcement,
+                category_ids,
+                room_ids,
+            })
+        })
+    }
+
     fn create_or_update_announcement(
         &mut self,
         auth_token: &AuthToken,
@@ -1052,16 +2372,20 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
         auth_token: &AuthToken,
         announcement_id: AnnouncementId,
         announcement_data: models::AnnouncementPatch,
+        expected_last_update: Option<chrono::DateTime<chrono::Utc>>,
     ) -> Result<(), StoreError> {
         use schema::announcements::dsl::*;
 
         self.connection.transaction(|connection| {
-            let current_event_id = announcements
-                .select(event_id)
+            let (current_event_id, actual_last_update) = announcements
+                .select((event_id, last_updated))
                 .filter(id.eq(announcement_id))
-                .first::<EventId>(connection)?;
+                .first::<(EventId, chrono::DateTime<chrono::Utc>)>(connection)?;
 
             auth_token.check_privilege(current_event_id, Privilege::ManageAnnouncements)?;
+            if expected_last_update.is_some_and(|expected| expected != actual_last_update) {
+                return Err(StoreError::ConcurrentEditConflict);
+            }
 
             if let Some(room_ids) = announcement_data.room_ids.as_ref() {
                 check_categories_validity(room_ids, current_event_id, connection)?;
@@ -1104,6 +2428,63 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
         })
     }
 
+    fn list_entry_templates(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+    ) -> Result<Vec<models::EntryTemplate>, StoreError> {
+        use schema::entry_templates::dsl::*;
+
+        auth_token.check_privilege(the_event_id, Privilege::ManageEntries)?;
+
+        Ok(entry_templates
+            .filter(event_id.eq(the_event_id))
+            .order_by((name, id))
+            .select(models::EntryTemplate::as_select())
+            .load(&mut self.connection)?)
+    }
+
+    fn create_entry_template(
+        &mut self,
+        auth_token: &AuthToken,
+        template: models::EntryTemplate,
+    ) -> Result<(), StoreError> {
+        use schema::entry_templates::dsl::*;
+
+        auth_token.check_privilege(template.event_id, Privilege::ManageEntries)?;
+
+        let count = diesel::insert_into(entry_templates)
+            .values(&template)
+            .on_conflict(id)
+            .do_nothing()
+            .execute(&mut self.connection)?;
+        if count == 0 {
+            return Err(StoreError::ConflictEntityExists);
+        }
+        Ok(())
+    }
+
+    fn delete_entry_template(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+        template_id: EntryTemplateId,
+    ) -> Result<(), StoreError> {
+        use schema::entry_templates::dsl::*;
+
+        // The correctness of the given event_id is checked in the DELETE statement below
+        auth_token.check_privilege(the_event_id, Privilege::ManageEntries)?;
+
+        let count = diesel::delete(entry_templates)
+            .filter(id.eq(template_id))
+            .filter(event_id.eq(the_event_id))
+            .execute(&mut self.connection)?;
+        if count == 0 {
+            return Err(StoreError::NotExisting);
+        }
+        Ok(())
+    }
+
     fn authenticate_with_passphrase(
         &mut self,
         the_event_id: i32,
@@ -1111,30 +2492,58 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
         session_token: &mut SessionToken,
     ) -> Result<(), StoreError> {
         use schema::event_passphrases::dsl::*;
-        let passphrase_ids_and_validity = event_passphrases
-            .select((id, valid_from, valid_until))
-            .filter(event_id.eq(the_event_id))
-            .filter(passphrase.eq(the_passphrase))
-            .load::<(
-                i32,
-                Option<chrono::DateTime<chrono::Utc>>,
-                Option<chrono::DateTime<chrono::Utc>>,
-            )>(&mut self.connection)?;
-        if passphrase_ids_and_validity.is_empty() {
+        let matching_passphrases =
+            find_matching_passphrases(&mut self.connection, the_event_id, the_passphrase)?;
+        if matching_passphrases.is_empty() {
             return Err(StoreError::NotExisting);
         }
 
         let now = chrono::Utc::now();
-        let valid_passphrases: Vec<i32> = passphrase_ids_and_validity
+        let valid_passphrases: Vec<(PassphraseId, AccessRole)> = matching_passphrases
             .into_iter()
-            .filter(|(_pid, begin, end)| {
-                begin.is_none_or(|b| b <= now) && end.is_none_or(|e| e >= now)
+            .filter(|p| {
+                p.valid_from.is_none_or(|b| b <= now) && p.valid_until.is_none_or(|e| e >= now)
             })
-            .map(|(pid, _, _)| pid)
+            .map(|p| (p.id, p.privilege))
             .collect();
         if valid_passphrases.is_empty() {
             return Err(StoreError::NotValid);
         }
+        // Duplicate passphrases may only exist from times before we checked for them on creation
+        // or from importing hashed passphrases. If they grant different roles, we can't tell which
+        // one is meant, so we must not grant any of them (in particular not the more privileged
+        // one).
+        if valid_passphrases
+            .iter()
+            .any(|(_, role)| *role != valid_passphrases[0].1)
+        {
+            log::warn!(
+                "Rejected login to event {} with ambiguous passphrase, matching passphrases {:?} \
+                with different access roles.",
+                the_event_id,
+                valid_passphrases
+                    .iter()
+                    .map(|(pid, _)| pid)
+                    .collect::<Vec<_>>()
+            );
+            return Err(StoreError::NotExisting);
+        }
+        let valid_passphrases: Vec<PassphraseId> =
+            valid_passphrases.into_iter().map(|(pid, _)| pid).collect();
+        // Record usage of the passphrase with a separate single-row update (outside any
+        // transaction), such that concurrent logins don't run into serialization conflicts. The
+        // timestamp is only informational, so a failure should not prevent the login.
+        if let Err(e) = diesel::update(event_passphrases)
+            .filter(id.eq(valid_passphrases[0]))
+            .set(passphrase_last_used.eq(now))
+            .execute(&mut self.connection)
+        {
+            log::warn!(
+                "Could not update last usage of passphrase {}: {}",
+                valid_passphrases[0],
+                e
+            );
+        }
         session_token.add_authorization(valid_passphrases[0]);
         Ok(())
     }
@@ -1187,10 +2596,12 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
         use schema::event_passphrases::dsl::*;
 
         let data = event_passphrases
-            .select((privilege, valid_from, valid_until))
+            .select((id, privilege, valid_from, valid_until))
             .filter(event_id.eq(the_event_id))
             .filter(id.eq_any(session_token.get_passphrase_ids()))
+            .order_by(id)
             .load::<(
+                PassphraseId,
                 AccessRole,
                 Option<chrono::DateTime<chrono::Utc>>,
                 Option<chrono::DateTime<chrono::Utc>>,
@@ -1199,9 +2610,13 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
         let now = chrono::Utc::now();
         let mut roles = Vec::new();
         let mut expired_roles = Vec::new();
-        for (role, begin, end) in data {
+        let mut passphrases = Vec::new();
+        for (passphrase_id, role, begin, end) in data {
             if begin.is_none_or(|b| b <= now) && end.is_none_or(|e| e >= now) {
                 roles.push(role);
+                if role.can_be_granted_by_passphrase() {
+                    passphrases.push((passphrase_id, role));
+                }
             } else {
                 expired_roles.push(role);
             }
@@ -1213,10 +2628,23 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
         // special roles like [AccessRole::ServerAdmin] must never be given to web/API user
         roles.retain(|role| role.can_be_granted_by_passphrase());
 
+        if let Some(api_key) = session_token.get_api_key() {
+            let api_key_valid = diesel::select(diesel::dsl::exists(
+                schema::api_keys::table
+                    .filter(schema::api_keys::event_id.eq(the_event_id))
+                    .filter(schema::api_keys::key_hash.eq(hash_api_key(api_key))),
+            ))
+            .get_result::<bool>(&mut self.connection)?;
+            if api_key_valid {
+                roles.push(AccessRole::ApiReader);
+            }
+        }
+
         Ok(AuthToken::create_for_session(
             the_event_id,
             roles,
             expired_roles,
+            passphrases,
         ))
     }
 
@@ -1256,7 +2684,7 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
     fn create_passphrase(
         &mut self,
         auth_token: &AuthToken,
-        passphrase: models::NewPassphrase,
+        mut passphrase: models::NewPassphrase,
     ) -> Result<PassphraseId, StoreError> {
         auth_token.check_privilege(passphrase.event_id, Privilege::ManagePassphrases)?;
         if !(passphrase.privilege.can_be_managed_online()
@@ -1274,11 +2702,39 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
             )));
         }
 
-        let result = diesel::insert_into(schema::event_passphrases::table)
-            .values(passphrase)
-            .returning(schema::event_passphrases::id)
-            .get_result::<PassphraseId>(&mut self.connection)?;
-        Ok(result)
+        let hash = passphrase
+            .passphrase
+            .as_deref()
+            .map(hash_passphrase)
+            .transpose()?;
+        let lookup_key = passphrase
+            .passphrase
+            .as_deref()
+            .map(calculate_passphrase_lookup_key);
+
+        self.connection.transaction(|connection| {
+            // The unique index on (event_id, passphrase) does not cover passphrases, which are only
+            // stored as hash, so we need to check for duplicates by verifying the hashes.
+            if let Some(plaintext) = passphrase.passphrase.as_deref()
+                && !find_matching_passphrases(connection, passphrase.event_id, plaintext)?
+                    .is_empty()
+            {
+                return Err(StoreError::ConflictEntityExists);
+            }
+            if !keep_plaintext_passphrase(passphrase.privilege) {
+                passphrase.passphrase = None;
+            }
+
+            let result = diesel::insert_into(schema::event_passphrases::table)
+                .values((
+                    passphrase,
+                    schema::event_passphrases::passphrase_hash.eq(hash),
+                    schema::event_passphrases::passphrase_lookup_key.eq(lookup_key),
+                ))
+                .returning(schema::event_passphrases::id)
+                .get_result::<PassphraseId>(connection)?;
+            Ok(result)
+        })
     }
 
     fn patch_passphrase(
@@ -1348,15 +2804,25 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
         use schema::event_passphrases::dsl::*;
         auth_token.check_privilege(the_event_id, Privilege::ManagePassphrases)?;
 
-        let mut passphrases = event_passphrases
-            .select(models::Passphrase::as_select())
+        let passphrases = event_passphrases
+            .select((
+                models::Passphrase::as_select(),
+                passphrase_hash.is_not_null(),
+            ))
             .filter(event_id.eq(the_event_id))
             .order_by(privilege)
-            .load::<models::Passphrase>(&mut self.connection)?;
-        for p in passphrases.iter_mut() {
-            p.passphrase = p.passphrase.as_ref().map(|x| obfuscate_passphrase(x));
-        }
-        Ok(passphrases)
+            .load::<(models::Passphrase, bool)>(&mut self.connection)?;
+        Ok(passphrases
+            .into_iter()
+            .map(|(mut p, is_hashed)| {
+                p.passphrase = match p.passphrase {
+                    Some(x) => Some(obfuscate_passphrase(&x)),
+                    None if is_hashed => Some(HASHED_PASSPHRASE_PLACEHOLDER.to_owned()),
+                    None => None,
+                };
+                p
+            })
+            .collect())
     }
     fn get_full_user_passphrases(
         &mut self,
@@ -1374,6 +2840,276 @@ impl KueaPlanStoreFacade for PgDataStoreFacade {
             .load::<models::Passphrase>(&mut self.connection)?;
         Ok(passphrases)
     }
+
+    fn get_passphrases_for_backup(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+    ) -> Result<Vec<models::PassphraseBackup>, StoreError> {
+        use schema::event_passphrases::dsl::*;
+        auth_token.check_privilege(the_event_id, Privilege::ManageSecurePassphrases)?;
+
+        let passphrases = event_passphrases
+            .select(models::PassphraseBackup::as_select())
+            .filter(event_id.eq(the_event_id))
+            .order_by(id)
+            .load::<models::PassphraseBackup>(&mut self.connection)?;
+        Ok(passphrases)
+    }
+
+    fn import_passphrases_from_backup(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+        passphrases: Vec<models::PassphraseBackup>,
+    ) -> Result<usize, StoreError> {
+        use schema::event_passphrases::dsl::*;
+        auth_token.check_privilege(the_event_id, Privilege::ManageSecurePassphrases)?;
+        if let Some(p) = passphrases
+            .iter()
+            .find(|p| !p.privilege.can_be_granted_by_passphrase())
+        {
+            return Err(StoreError::InvalidInputData(format!(
+                "Cannot create a passphrase with special access role {:?}.",
+                p.privilege
+            )));
+        }
+
+        self.connection.transaction(|connection| {
+            // Passphrases are created in multiple rounds, such that the new id of the referenced
+            // passphrase is known when creating a derivable passphrase.
+            let mut new_ids: HashMap<PassphraseId, PassphraseId> = HashMap::new();
+            let mut count = 0;
+            let mut pending = passphrases;
+            while !pending.is_empty() {
+                let (ready, rest): (Vec<_>, Vec<_>) = pending.into_iter().partition(|p| {
+                    p.derivable_from_passphrase
+                        .is_none_or(|parent_id| new_ids.contains_key(&parent_id))
+                });
+                if ready.is_empty() {
+                    return Err(StoreError::InvalidInputData(format!(
+                        "Passphrase {} is derivable from passphrase {}, which is not included in \
+                        the imported passphrases.",
+                        rest[0].id,
+                        rest[0].derivable_from_passphrase.unwrap_or_default()
+                    )));
+                }
+                for p in ready {
+                    // Same check as in create_passphrase(). Imported passphrases, which are only
+                    // available as hash, can only be compared to existing plaintext passphrases
+                    // and identical hashes.
+                    let is_duplicate = match (&p.passphrase, &p.passphrase_hash) {
+                        (Some(plaintext), _) => {
+                            !find_matching_passphrases(connection, the_event_id, plaintext)?
+                                .is_empty()
+                        }
+                        (None, Some(hash)) => {
+                            passphrase_hash_exists(connection, the_event_id, hash)?
+                        }
+                        (None, None) => false,
+                    };
+                    if is_duplicate {
+                        return Err(StoreError::ConflictEntityExists);
+                    }
+                    let hash = match p.passphrase_hash {
+                        Some(hash) => Some(hash),
+                        None => p.passphrase.as_deref().map(hash_passphrase).transpose()?,
+                    };
+                    let lookup_key = match p.passphrase.as_deref() {
+                        Some(plaintext) => Some(calculate_passphrase_lookup_key(plaintext)),
+                        None => p.passphrase_lookup_key,
+                    };
+                    let new_passphrase = models::NewPassphrase {
+                        event_id: the_event_id,
+                        passphrase: p
+                            .passphrase
+                            .filter(|_| keep_plaintext_passphrase(p.privilege)),
+                        privilege: p.privilege,
+                        derivable_from_passphrase: p
+                            .derivable_from_passphrase
+                            .map(|parent_id| new_ids[&parent_id]),
+                        comment: p.comment,
+                        valid_from: p.valid_from,
+                        valid_until: p.valid_until,
+                    };
+                    let new_id = diesel::insert_into(event_passphrases)
+                        .values((
+                            new_passphrase,
+                            passphrase_hash.eq(hash),
+                            passphrase_lookup_key.eq(lookup_key),
+                        ))
+                        .returning(id)
+                        .get_result::<PassphraseId>(connection)?;
+                    new_ids.insert(p.id, new_id);
+                    count += 1;
+                }
+                pending = rest;
+            }
+            Ok(count)
+        })
+    }
+
+    fn hash_plaintext_passphrases(
+        &mut self,
+        auth_token: &GlobalAuthToken,
+    ) -> Result<usize, StoreError> {
+        use schema::event_passphrases::dsl::*;
+        auth_token.check_privilege(Privilege::ManageSecurePassphrases)?;
+
+        self.connection.transaction(|connection| {
+            let unhashed_passphrases = event_passphrases
+                .select((id, privilege, passphrase.assume_not_null()))
+                .filter(passphrase.is_not_null())
+                .filter(passphrase_hash.is_null())
+                .load::<(PassphraseId, AccessRole, String)>(connection)?;
+            for (passphrase_id, role, plaintext) in unhashed_passphrases.iter() {
+                diesel::update(event_passphrases)
+                    .filter(id.eq(passphrase_id))
+                    .set((
+                        passphrase_hash.eq(hash_passphrase(plaintext)?),
+                        passphrase_lookup_key.eq(calculate_passphrase_lookup_key(plaintext)),
+                        passphrase.eq(keep_plaintext_passphrase(*role).then_some(plaintext)),
+                    ))
+                    .execute(connection)?;
+            }
+            Ok(unhashed_passphrases.len())
+        })
+    }
+
+    fn get_api_keys(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+    ) -> Result<Vec<models::ApiKey>, StoreError> {
+        use schema::api_keys::dsl::*;
+        auth_token.check_privilege(the_event_id, Privilege::ManageApiKeys)?;
+
+        Ok(api_keys
+            .select(models::ApiKey::as_select())
+            .filter(event_id.eq(the_event_id))
+            .order_by(id)
+            .load(&mut self.connection)?)
+    }
+
+    fn create_api_key(
+        &mut self,
+        auth_token: &AuthToken,
+        api_key: models::NewApiKey,
+    ) -> Result<(ApiKeyId, String), StoreError> {
+        use schema::api_keys::dsl::*;
+        auth_token.check_privilege(api_key.event_id, Privilege::ManageApiKeys)?;
+
+        let key = generate_api_key();
+        let new_id = diesel::insert_into(api_keys)
+            .values((api_key, key_hash.eq(hash_api_key(&key))))
+            .returning(id)
+            .get_result::<ApiKeyId>(&mut self.connection)?;
+        Ok((new_id, key))
+    }
+
+    fn delete_api_key(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+        api_key_id: ApiKeyId,
+    ) -> Result<(), StoreError> {
+        use schema::api_keys::dsl::*;
+        auth_token.check_privilege(the_event_id, Privilege::ManageApiKeys)?;
+
+        let affected_rows = diesel::delete(api_keys)
+            .filter(id.eq(api_key_id))
+            .filter(event_id.eq(the_event_id))
+            .execute(&mut self.connection)?;
+        if affected_rows > 0 {
+            Ok(())
+        } else {
+            Err(StoreError::NotExisting)
+        }
+    }
+
+    fn get_feed_tokens(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+    ) -> Result<Vec<models::FeedToken>, StoreError> {
+        use schema::feed_tokens::dsl::*;
+        auth_token.check_privilege(the_event_id, Privilege::ManageFeedTokens)?;
+
+        Ok(feed_tokens
+            .select(models::FeedToken::as_select())
+            .filter(event_id.eq(the_event_id))
+            .order_by(id)
+            .load(&mut self.connection)?)
+    }
+
+    fn create_feed_token(
+        &mut self,
+        auth_token: &AuthToken,
+        feed_token: models::NewFeedToken,
+    ) -> Result<FeedTokenId, StoreError> {
+        use schema::feed_tokens::dsl::*;
+        auth_token.check_privilege(feed_token.event_id, Privilege::ManageFeedTokens)?;
+        if !feed_token.privilege.can_be_granted_by_passphrase() {
+            return Err(StoreError::InvalidInputData(format!(
+                "Role {:?} cannot be granted by a feed token",
+                feed_token.privilege
+            )));
+        }
+
+        Ok(diesel::insert_into(feed_tokens)
+            .values(feed_token)
+            .returning(id)
+            .get_result::<FeedTokenId>(&mut self.connection)?)
+    }
+
+    fn revoke_feed_token(
+        &mut self,
+        auth_token: &AuthToken,
+        the_event_id: EventId,
+        feed_token_id: FeedTokenId,
+    ) -> Result<(), StoreError> {
+        use schema::feed_tokens::dsl::*;
+        auth_token.check_privilege(the_event_id, Privilege::ManageFeedTokens)?;
+
+        let affected_rows = diesel::update(feed_tokens)
+            .filter(id.eq(feed_token_id))
+            .filter(event_id.eq(the_event_id))
+            .set(revoked.eq(true))
+            .execute(&mut self.connection)?;
+        if affected_rows > 0 {
+            Ok(())
+        } else {
+            Err(StoreError::NotExisting)
+        }
+    }
+
+    fn get_auth_token_for_feed_token(
+        &mut self,
+        the_event_id: EventId,
+        feed_token_id: FeedTokenId,
+    ) -> Result<AuthToken, StoreError> {
+        use schema::feed_tokens::dsl::*;
+
+        let role = feed_tokens
+            .select(privilege)
+            .filter(id.eq(feed_token_id))
+            .filter(event_id.eq(the_event_id))
+            .filter(revoked.eq(false))
+            .first::<AccessRole>(&mut self.connection)
+            .optional()?;
+        let roles = role
+            .into_iter()
+            // special roles like [AccessRole::ServerAdmin] must never be given to web/API user
+            .filter(|role| role.can_be_granted_by_passphrase())
+            .collect();
+
+        Ok(AuthToken::create_for_session(
+            the_event_id,
+            roles,
+            vec![],
+            vec![],
+        ))
+    }
 }
 
 fn get_entries_generic<'a, StateIter: Iterator<Item = &'a models::EntryState>>(
@@ -1381,88 +3117,163 @@ fn get_entries_generic<'a, StateIter: Iterator<Item = &'a models::EntryState>>(
     the_event_id: EventId,
     filter: EntryFilter,
     state_filter: StateIter,
+    orga_only_filter: &[bool],
     with_internal_fields: bool,
 ) -> Result<Vec<models::FullEntry>, StoreError> {
-    use diesel::dsl::not;
-    use schema::entries::dsl::*;
-
     connection.transaction(|connection| {
-        let the_entries = entries
-            .filter(event_id.eq(the_event_id))
-            .filter(not(deleted))
-            .filter(state.eq_any(state_filter))
-            .filter(entry_filter_to_sql(filter))
-            .order_by((begin.asc(), end.asc(), id.asc()))
-            .select(models::Entry::as_select())
+        let the_entries = entries_query(the_event_id, filter, state_filter, orga_only_filter)
             .load::<models::Entry>(connection)?;
 
-        let the_entry_rooms = models::EntryRoomMapping::belonging_to(&the_entries)
-            .inner_join(schema::rooms::table)
-            .filter(not(schema::rooms::deleted))
-            .select(models::EntryRoomMapping::as_select())
-            .load::<models::EntryRoomMapping>(connection)?
-            .grouped_by(&the_entries);
+        load_full_entries(connection, the_entries, with_internal_fields)
+    })
+}
+
+type EntriesQuery<'a> = schema::entries::BoxedQuery<
+    'a,
+    diesel::pg::Pg,
+    diesel::dsl::SqlTypeOf<diesel::dsl::AsSelect<models::Entry, diesel::pg::Pg>>,
+>;
 
-        let the_previous_dates = models::PreviousDate::belonging_to(&the_entries)
-            .select(models::PreviousDate::as_select())
-            .load::<models::PreviousDate>(connection)?;
+/// Build the query for the (non-deleted) entries of the event, which match the given filters, in
+/// the order requested by the `filter`. The order is always deterministic, so the query can be
+/// split into chunks with LIMIT and OFFSET.
+fn entries_query<'a, StateIter: Iterator<Item = &'a models::EntryState>>(
+    the_event_id: EventId,
+    filter: EntryFilter,
+    state_filter: StateIter,
+    orga_only_filter: &[bool],
+) -> EntriesQuery<'a> {
+    use diesel::dsl::not;
+    use schema::entries::dsl::*;
 
-        let the_previous_date_rooms =
-            models::PreviousDateRoomMapping::belonging_to(&the_previous_dates)
+    let order = filter.order_by;
+    let query = entries
+        .filter(event_id.eq(the_event_id))
+        .filter(not(deleted))
+        .filter(state.eq_any(state_filter))
+        .filter(orga_only.eq_any(orga_only_filter.to_vec()))
+        .filter(entry_filter_to_sql(filter))
+        .select(models::Entry::as_select())
+        .into_boxed();
+    let query = match order {
+        EntryOrder::Begin => query,
+        EntryOrder::Title => query.order_by(lower(title).asc()),
+        EntryOrder::Room => query.order_by(
+            // Title of the first (non-deleted) room of the entry in alphabetical order. This
+            // is NULL for entries without room, which are sorted last.
+            schema::entry_rooms::table
                 .inner_join(schema::rooms::table)
+                .filter(schema::entry_rooms::entry_id.eq(id))
                 .filter(not(schema::rooms::deleted))
-                .select(models::PreviousDateRoomMapping::as_select())
-                .load::<models::PreviousDateRoomMapping>(connection)?
-                .grouped_by(&the_previous_dates);
+                .select(diesel::dsl::min(lower(schema::rooms::title)))
+                .single_value()
+                .asc(),
+        ),
+        EntryOrder::Category => query.order_by((
+            schema::categories::table
+                .filter(schema::categories::id.eq(category))
+                .select(schema::categories::sort_key)
+                .single_value()
+                .asc(),
+            schema::categories::table
+                .filter(schema::categories::id.eq(category))
+                .select(schema::categories::title)
+                .single_value()
+                .asc(),
+            category.asc(),
+        )),
+    };
+    query.then_order_by((begin.asc(), end.asc(), id.asc()))
+}
+
+/// Get the values of the entries' `orga_only` flag which are visible for the given client, for
+/// filtering entry queries: Entries that are only visible to orgas are excluded, unless the client
+/// has the [Privilege::ManageEntries] for the event.
+fn visible_orga_only_values(auth_token: &AuthToken, the_event_id: EventId) -> &'static [bool] {
+    if auth_token.has_privilege(the_event_id, Privilege::ManageEntries) {
+        &[false, true]
+    } else {
+        &[false]
+    }
+}
+
+/// Load the rooms, previous dates and (optionally) orga-internal fields of the given entries from
+/// the database to create [models::FullEntry] objects. The order of the entries is retained.
+fn load_full_entries(
+    connection: &mut PgConnection,
+    the_entries: Vec<models::Entry>,
+    with_internal_fields: bool,
+) -> Result<Vec<models::FullEntry>, StoreError> {
+    use diesel::dsl::not;
+    use schema::entries::dsl::*;
+
+    let the_entry_rooms = models::EntryRoomMapping::belonging_to(&the_entries)
+        .inner_join(schema::rooms::table)
+        .filter(not(schema::rooms::deleted))
+        .select(models::EntryRoomMapping::as_select())
+        .load::<models::EntryRoomMapping>(connection)?
+        .grouped_by(&the_entries);
 
-        let the_previous_dates = the_previous_dates
-            .into_iter()
-            .zip(the_previous_date_rooms)
-            .map(
-                |(previous_date, previous_date_rooms)| models::FullPreviousDate {
-                    previous_date,
-                    room_ids: previous_date_rooms
-                        .into_iter()
-                        .map(|rm| rm.room_id)
-                        .collect(),
-                },
-            )
-            .grouped_by(&the_entries);
+    let the_previous_dates = models::PreviousDate::belonging_to(&the_entries)
+        .filter(not(schema::previous_dates::deleted))
+        .select(models::PreviousDate::as_select())
+        .load::<models::PreviousDate>(connection)?;
 
-        let mut the_entries = the_entries
-            .into_iter()
-            .zip(the_entry_rooms)
-            .zip(the_previous_dates)
-            .map(
-                |((entry, entry_rooms), entry_previous_dates)| models::FullEntry {
-                    entry,
-                    room_ids: entry_rooms.into_iter().map(|e| e.room_id).collect(),
-                    previous_dates: entry_previous_dates,
-                    orga_internal: None,
-                },
-            )
-            .collect::<Vec<_>>();
+    let the_previous_date_rooms =
+        models::PreviousDateRoomMapping::belonging_to(&the_previous_dates)
+            .inner_join(schema::rooms::table)
+            .filter(not(schema::rooms::deleted))
+            .select(models::PreviousDateRoomMapping::as_select())
+            .load::<models::PreviousDateRoomMapping>(connection)?
+            .grouped_by(&the_previous_dates);
+
+    let the_previous_dates = the_previous_dates
+        .into_iter()
+        .zip(the_previous_date_rooms)
+        .map(
+            |(previous_date, previous_date_rooms)| models::FullPreviousDate {
+                previous_date,
+                room_ids: previous_date_rooms
+                    .into_iter()
+                    .map(|rm| rm.room_id)
+                    .collect(),
+            },
+        )
+        .grouped_by(&the_entries);
+
+    let mut the_entries = the_entries
+        .into_iter()
+        .zip(the_entry_rooms)
+        .zip(the_previous_dates)
+        .map(
+            |((entry, entry_rooms), entry_previous_dates)| models::FullEntry {
+                entry,
+                room_ids: entry_rooms.into_iter().map(|e| e.room_id).collect(),
+                previous_dates: entry_previous_dates,
+                orga_internal: None,
+            },
+        )
+        .collect::<Vec<_>>();
 
-        if with_internal_fields {
-            let entry_index_by_id: HashMap<_, _> = the_entries
-                .iter()
-                .enumerate()
-                .map(|(i, u)| (u.entry.id, i))
-                .collect();
+    if with_internal_fields {
+        let entry_index_by_id: HashMap<_, _> = the_entries
+            .iter()
+            .enumerate()
+            .map(|(i, u)| (u.entry.id, i))
+            .collect();
 
-            let entries_internal_fields = entries
-                .filter(id.eq_any(the_entries.iter().map(|e| e.entry.id)))
-                .select((id, models::EntryInternalFields::as_select()))
-                .load::<(EntryId, models::EntryInternalFields)>(connection)?;
+        let entries_internal_fields = entries
+            .filter(id.eq_any(the_entries.iter().map(|e| e.entry.id)))
+            .select((id, models::EntryInternalFields::as_select()))
+            .load::<(EntryId, models::EntryInternalFields)>(connection)?;
 
-            for (entry_id, internal_fields) in entries_internal_fields {
-                the_entries[*entry_index_by_id.get(&entry_id).unwrap()].orga_internal =
-                    Some(internal_fields);
-            }
+        for (entry_id, internal_fields) in entries_internal_fields {
+            the_entries[*entry_index_by_id.get(&entry_id).unwrap()].orga_internal =
+                Some(internal_fields);
         }
+    }
 
-        Ok(the_entries)
-    })
+    Ok(the_entries)
 }
 
 fn update_entry_rooms(
@@ -1488,6 +3299,249 @@ fn update_entry_rooms(
         .map(|_| ())
 }
 
+/// Ids of imported rooms, categories, entries, previous dates and announcements, which exist
+/// already in the database (in any event, including deleted ones), as found by
+/// [find_import_conflicts].
+struct ImportConflicts {
+    rooms: Vec<(RoomId, EventId)>,
+    categories: Vec<(CategoryId, EventId)>,
+    entries: Vec<(EntryId, EventId)>,
+    previous_dates: Vec<(PreviousDateId, EventId)>,
+    announcements: Vec<(AnnouncementId, EventId)>,
+}
+
+impl ImportConflicts {
+    fn is_empty(&self) -> bool {
+        self.rooms.is_empty()
+            && self.categories.is_empty()
+            && self.entries.is_empty()
+            && self.previous_dates.is_empty()
+            && self.announcements.is_empty()
+    }
+
+    fn contains_room(&self, room_id: RoomId) -> bool {
+        self.rooms.iter().any(|(id, _)| *id == room_id)
+    }
+
+    fn contains_category(&self, category_id: CategoryId) -> bool {
+        self.categories.iter().any(|(id, _)| *id == category_id)
+    }
+
+    fn contains_entry(&self, entry_id: EntryId) -> bool {
+        self.entries.iter().any(|(id, _)| *id == entry_id)
+    }
+
+    fn contains_previous_date(&self, previous_date_id: PreviousDateId) -> bool {
+        self.previous_dates
+            .iter()
+            .any(|(id, _)| *id == previous_date_id)
+    }
+
+    fn contains_announcement(&self, announcement_id: AnnouncementId) -> bool {
+        self.announcements
+            .iter()
+            .any(|(id, _)| *id == announcement_id)
+    }
+
+    /// Human-readable list of the conflicting elements and the events they belong to
+    fn describe(&self) -> String {
+        let describe_kind = |kind: &'static str, elements: &[(uuid::Uuid, EventId)]| {
+            elements
+                .iter()
+                .map(move |(id, event_id)| format!("{} {} (event {})", kind, id, event_id))
+                .collect::<Vec<_>>()
+        };
+        [
+            describe_kind("room", &self.rooms),
+            describe_kind("category", &self.categories),
+            describe_kind("entry", &self.entries),
+            describe_kind("previous date", &self.previous_dates),
+            describe_kind("announcement", &self.announcements),
+        ]
+        .concat()
+        .join(", ")
+    }
+}
+
+/// Find the rooms, categories, entries, previous dates and announcements of the import data, whose
+/// ids exist already in the database.
+fn find_import_conflicts(
+    data: &models::EventWithContents,
+    connection: &mut PgConnection,
+) -> Result<ImportConflicts, StoreError> {
+    Ok(ImportConflicts {
+        rooms: schema::rooms::table
+            .filter(schema::rooms::id.eq_any(data.rooms.iter().map(|r| r.id)))
+            .select((schema::rooms::id, schema::rooms::event_id))
+            .load(connection)?,
+        categories: schema::categories::table
+            .filter(schema::categories::id.eq_any(data.categories.iter().map(|c| c.id)))
+            .select((schema::categories::id, schema::categories::event_id))
+            .load(connection)?,
+        entries: schema::entries::table
+            .filter(schema::entries::id.eq_any(data.entries.iter().map(|e| e.entry.id)))
+            .select((schema::entries::id, schema::entries::event_id))
+            .load(connection)?,
+        previous_dates: schema::previous_dates::table
+            .inner_join(schema::entries::table)
+            .filter(
+                schema::previous_dates::id.eq_any(
+                    data.entries
+                        .iter()
+                        .flat_map(|e| &e.previous_dates)
+                        .map(|pd| pd.previous_date.id),
+                ),
+            )
+            .select((schema::previous_dates::id, schema::entries::event_id))
+            .load(connection)?,
+        announcements: schema::announcements::table
+            .filter(
+                schema::announcements::id
+                    .eq_any(data.announcements.iter().map(|a| a.announcement.id)),
+            )
+            .select((schema::announcements::id, schema::announcements::event_id))
+            .load(connection)?,
+    })
+}
+
+/// Create a new entry or update the existing entry with the same id, within an existing
+/// transaction. See [KueaPlanStoreFacade::create_or_update_entry] for the semantics.
+///
+/// The caller is responsible for checking the [Privilege::ManageEntries] privilege.
+fn upsert_entry(
+    auth_token: &AuthToken,
+    entry: &models::FullNewEntry,
+    extend_previous_dates: bool,
+    record_previous_date: Option<&str>,
+    expected_last_update: Option<chrono::DateTime<chrono::Utc>>,
+    connection: &mut PgConnection,
+) -> Result<bool, StoreError> {
+    use diesel::dsl::not;
+    use schema::entries::dsl::*;
+    use schema::previous_dates;
+
+    if let Some(expected_last_update) = expected_last_update {
+        let actual_last_update = entries
+            .filter(id.eq(entry.entry.id))
+            .filter(not(deleted))
+            .select(last_updated)
+            .first::<chrono::DateTime<chrono::Utc>>(connection)?;
+        if expected_last_update != actual_last_update {
+            return Err(StoreError::ConcurrentEditConflict);
+        }
+    }
+
+    // The existing entry is locked until the end of the transaction, such that the previous date
+    // is recorded from exactly the data, which is replaced by this update.
+    let entry_with_previous_date;
+    let entry = match record_previous_date {
+        Some(previous_date_comment) => {
+            let old_entry = entries
+                .filter(id.eq(entry.entry.id))
+                .filter(event_id.eq(entry.entry.event_id))
+                .filter(not(deleted))
+                .select(models::Entry::as_select())
+                .for_update()
+                .first::<models::Entry>(connection)
+                .optional()?;
+            match old_entry {
+                Some(old_entry) => {
+                    let old_room_ids = schema::entry_rooms::table
+                        .inner_join(schema::rooms::table)
+                        .filter(schema::entry_rooms::entry_id.eq(old_entry.id))
+                        .filter(not(schema::rooms::deleted))
+                        .select(schema::entry_rooms::room_id)
+                        .load::<Uuid>(connection)?;
+                    let mut new_entry = entry.clone();
+                    new_entry.record_previous_date(
+                        &models::FullEntry {
+                            entry: old_entry,
+                            room_ids: old_room_ids,
+                            previous_dates: Vec::new(),
+                            orga_internal: None,
+                        },
+                        previous_date_comment.to_owned(),
+                    );
+                    entry_with_previous_date = new_entry;
+                    &entry_with_previous_date
+                }
+                // The entry is newly created (or the update will be rejected), so there is no
+                // previous date to be recorded
+                None => entry,
+            }
+        }
+        None => entry,
+    };
+
+    let old_state = load_entry_audit_state(entry.entry.id, connection)?;
+
+    check_categories_validity(&[entry.entry.category], entry.entry.event_id, connection)?;
+
+    // entry
+    let upsert_result = {
+        // Unfortunately, `InsertStatement<_, OnConflictValues<...>>`, which is returned by
+        // `.on_onflict().do_update()`, does not implement the QueryDsl trait for
+        // `.filter()`, but only the `FilterDsl` trait directly. We import it locally here,
+        // to not make the .filter() method in the following query ambiguous.
+        use diesel::query_dsl::methods::FilterDsl;
+
+        let acting_role = last_updated_by_role.eq(auth_token.acting_role(Privilege::ManageEntries));
+        diesel::insert_into(entries)
+            .values((&entry.entry, acting_role))
+            .on_conflict(id)
+            .do_update()
+            // By limiting the search of existing entries to the same event, we prevent
+            // changes of the event id (i.e. "moving" entries between events), which would
+            // be a security loophole
+            .set((&entry.entry, acting_role))
+            .filter(event_id.eq(entry.entry.event_id))
+            .filter(not(deleted))
+            .returning(sql_upsert_is_updated())
+            .load::<bool>(connection)?
+    };
+    if upsert_result.is_empty() {
+        return Err(StoreError::ConflictEntityExists);
+    }
+    let is_updated = upsert_result[0];
+
+    // rooms
+    check_rooms_validity(&entry.room_ids, entry.entry.event_id, connection)?;
+    update_entry_rooms(entry.entry.id, &entry.room_ids, connection)?;
+
+    // previous dates
+    if !extend_previous_dates {
+        diesel::update(previous_dates::table)
+            .filter(previous_dates::entry_id.eq(entry.entry.id))
+            .filter(
+                previous_dates::id
+                    .ne_all(entry.previous_dates.iter().map(|pd| pd.previous_date.id)),
+            )
+            .filter(not(previous_dates::deleted))
+            .set(previous_dates::deleted.eq(true))
+            .execute(connection)?;
+    }
+
+    for previous_date in &entry.previous_dates {
+        check_rooms_validity(&previous_date.room_ids, entry.entry.event_id, connection)?;
+        update_or_insert_previous_date(previous_date, entry.entry.id, connection)?;
+    }
+
+    write_entry_audit_log(
+        auth_token,
+        entry.entry.event_id,
+        entry.entry.id,
+        if is_updated {
+            models::EntryAuditAction::Update
+        } else {
+            models::EntryAuditAction::Create
+        },
+        old_state,
+        connection,
+    )?;
+
+    Ok(!is_updated)
+}
+
 fn update_or_insert_previous_date(
     previous_date: &models::FullPreviousDate,
     the_entry_id: EntryId,
@@ -1496,11 +3550,12 @@ fn update_or_insert_previous_date(
     use diesel::query_dsl::methods::FilterDsl;
     use schema::previous_dates::dsl::*;
 
+    // Updating a deleted previous date restores it, so that PUTting a previous date is idempotent
     let upsert_result = diesel::insert_into(previous_dates)
         .values(&previous_date.previous_date)
         .on_conflict(id)
         .do_update()
-        .set(&previous_date.previous_date)
+        .set((&previous_date.previous_date, deleted.eq(false)))
         .filter(entry_id.eq(the_entry_id))
         .returning(sql_upsert_is_updated())
         .load::<bool>(connection)?;
@@ -1509,22 +3564,40 @@ fn update_or_insert_previous_date(
     }
     let is_updated = upsert_result[0];
 
-    update_previous_date_rooms(
+    let rooms_changed = update_previous_date_rooms(
         previous_date.previous_date.id,
         &previous_date.room_ids,
         connection,
     )?;
+    // The trigger only updates `last_updated` when the previous_dates row itself is changed
+    if is_updated && rooms_changed {
+        diesel::update(previous_dates)
+            .filter(id.eq(previous_date.previous_date.id))
+            .set(last_updated.eq(diesel::dsl::now))
+            .execute(connection)?;
+    }
 
     Ok(!is_updated)
 }
 
+/// Replace the rooms of the given previous date with `room_ids`.
+///
+/// Returns true, if the set of rooms has been changed.
 fn update_previous_date_rooms(
     the_previous_date_id: uuid::Uuid,
     room_ids: &[uuid::Uuid],
     connection: &mut PgConnection,
-) -> Result<(), diesel::result::Error> {
+) -> Result<bool, diesel::result::Error> {
     use schema::previous_date_rooms::dsl::*;
 
+    let old_room_ids = previous_date_rooms
+        .filter(previous_date_id.eq(the_previous_date_id))
+        .select(room_id)
+        .load::<uuid::Uuid>(connection)?;
+    if models::unordered_equality(&old_room_ids, room_ids) {
+        return Ok(false);
+    }
+
     diesel::delete(previous_date_rooms.filter(previous_date_id.eq(the_previous_date_id)))
         .execute(connection)?;
 
@@ -1540,8 +3613,128 @@ fn update_previous_date_rooms(
                 })
                 .collect::<Vec<_>>(),
         )
-        .execute(connection)
-        .map(|_| ())
+        .execute(connection)?;
+    Ok(true)
+}
+
+/// Apply the `changeset` to all the given entries of the event as one update, set their
+/// `last_updated` fields and write a record to the entry audit log for each of them.
+///
+/// The entries are expected to be selected and checked by the caller.
+fn bulk_update_entries<Changeset>(
+    auth_token: &AuthToken,
+    the_event_id: EventId,
+    entry_ids: &[EntryId],
+    changeset: Changeset,
+    connection: &mut PgConnection,
+) -> Result<(), diesel::result::Error>
+where
+    Changeset: diesel::AsChangeset<Target = schema::entries::table>,
+    Changeset::Changeset: diesel::query_builder::QueryFragment<diesel::pg::Pg>,
+{
+    use schema::entries::dsl::*;
+
+    let old_states = entry_ids
+        .iter()
+        .map(|entry_id| load_entry_audit_state(*entry_id, connection))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    diesel::update(entries)
+        .filter(id.eq_any(entry_ids))
+        .set((
+            changeset,
+            last_updated.eq(diesel::dsl::now),
+            last_updated_by_role.eq(auth_token.acting_role(Privilege::ManageEntries)),
+        ))
+        .execute(connection)?;
+
+    for (entry_id, old_state) in entry_ids.iter().zip(old_states) {
+        write_entry_audit_log(
+            auth_token,
+            the_event_id,
+            *entry_id,
+            models::EntryAuditAction::Update,
+            old_state,
+            connection,
+        )?;
+    }
+    Ok(())
+}
+
+/// Load the current state of the given entry, including its room ids, as a JSON object for
+/// determining the changed fields for the entry audit log.
+///
+/// Returns None, if the entry does not exist (yet).
+fn load_entry_audit_state(
+    the_entry_id: EntryId,
+    connection: &mut PgConnection,
+) -> Result<Option<serde_json::Map<String, serde_json::Value>>, diesel::result::Error> {
+    let state = schema::entries::table
+        .filter(schema::entries::id.eq(the_entry_id))
+        .select(diesel::dsl::sql::<diesel::sql_types::Jsonb>(
+            "to_jsonb(entries)",
+        ))
+        .first::<serde_json::Value>(connection)
+        .optional()?;
+    let Some(serde_json::Value::Object(mut state)) = state else {
+        return Ok(None);
+    };
+    let room_ids = schema::entry_rooms::table
+        .filter(schema::entry_rooms::entry_id.eq(the_entry_id))
+        .select(schema::entry_rooms::room_id)
+        .order_by(schema::entry_rooms::room_id)
+        .load::<Uuid>(connection)?;
+    state.insert(
+        "room_ids".to_owned(),
+        room_ids
+            .iter()
+            .map(|room_id| serde_json::Value::String(room_id.to_string()))
+            .collect(),
+    );
+    Ok(Some(state))
+}
+
+/// Add a record to the entry audit log for a modification of the given entry, which changed the
+/// entry from `old_state` (as retrieved with [load_entry_audit_state] before the modification) to
+/// its current state.
+///
+/// The acting passphrase is taken from the `auth_token`.
+fn write_entry_audit_log(
+    auth_token: &AuthToken,
+    the_event_id: EventId,
+    the_entry_id: EntryId,
+    action: models::EntryAuditAction,
+    old_state: Option<serde_json::Map<String, serde_json::Value>>,
+    connection: &mut PgConnection,
+) -> Result<(), diesel::result::Error> {
+    let new_state = load_entry_audit_state(the_entry_id, connection)?.unwrap_or_default();
+    diesel::insert_into(schema::entry_audit_log::table)
+        .values(models::NewEntryAuditLogRecord {
+            entry_id: the_entry_id,
+            event_id: the_event_id,
+            action,
+            passphrase_id: auth_token.acting_passphrase_id(Privilege::ManageEntries),
+            changed_fields: changed_entry_fields(old_state.as_ref(), &new_state),
+        })
+        .execute(connection)?;
+    Ok(())
+}
+
+/// Get the names of the fields, which differ between the `old_state` and the `new_state` of an
+/// entry. If there is no `old_state` (i.e. the entry has been created), all fields are considered
+/// changed.
+///
+/// The fields `id` and `last_updated` are ignored, as they don't carry any relevant changes.
+fn changed_entry_fields(
+    old_state: Option<&serde_json::Map<String, serde_json::Value>>,
+    new_state: &serde_json::Map<String, serde_json::Value>,
+) -> Vec<String> {
+    new_state
+        .iter()
+        .filter(|(key, _)| *key != "id" && *key != "last_updated")
+        .filter(|(key, value)| old_state.and_then(|old| old.get(*key)) != Some(*value))
+        .map(|(key, _)| key.clone())
+        .collect()
 }
 
 fn update_announcement_categories(
@@ -1616,6 +3809,8 @@ fn replace_room_with_other_rooms(
     // rooms.
     // On the other hand, keeping the references allows for recovery from an accidental deletion of
     // a room.
+    // Entries and previous dates, which are already assigned to one of the replacement rooms, are
+    // silently skipped to avoid duplicate room associations.
 
     let entry_ids: Vec<EntryId> = entry_rooms::table
         .filter(entry_rooms::room_id.eq(room_id))
@@ -1635,6 +3830,7 @@ fn replace_room_with_other_rooms(
                 })
                 .collect::<Vec<_>>(),
         )
+        .on_conflict_do_nothing()
         .execute(connection)?;
     diesel::update(entries::table)
         .filter(entries::id.eq_any(entry_ids))
@@ -1659,6 +3855,7 @@ fn replace_room_with_other_rooms(
                 })
                 .collect::<Vec<_>>(),
         )
+        .on_conflict_do_nothing()
         .execute(connection)?;
     diesel::update(previous_dates::table)
         .filter(previous_dates::id.eq_any(previous_date_ids))
@@ -1667,6 +3864,21 @@ fn replace_room_with_other_rooms(
     Ok(())
 }
 
+fn load_rooms(
+    connection: &mut PgConnection,
+    the_event_id: EventId,
+) -> Result<Vec<models::Room>, StoreError> {
+    use diesel::dsl::not;
+    use schema::rooms::dsl::*;
+
+    Ok(rooms
+        .select(models::Room::as_select())
+        .filter(event_id.eq(the_event_id))
+        .filter(not(deleted))
+        .order_by(title)
+        .load::<models::Room>(connection)?)
+}
+
 fn check_categories_validity(
     category_ids: &[CategoryId],
     given_event_id: EventId,
@@ -1693,6 +3905,47 @@ fn check_categories_validity(
     Ok(())
 }
 
+/// Get a `sort_key` for the given category, which is not used by any other non-deleted category of
+/// the event, starting from the `requested_sort_key` (see [next_free_sort_key]).
+///
+/// Duplicate sort keys would result in a nondeterministic order of the categories. To prevent
+/// concurrent transactions from picking the same sort key, the event's row is locked until the end
+/// of the current transaction. Thus, this function must be called within a transaction, which
+/// then writes the category.
+fn free_category_sort_key(
+    the_event_id: EventId,
+    category_id: CategoryId,
+    requested_sort_key: i32,
+    connection: &mut PgConnection,
+) -> Result<i32, StoreError> {
+    use diesel::dsl::not;
+    use schema::categories::dsl::*;
+    schema::events::table
+        .filter(schema::events::id.eq(the_event_id))
+        .select(schema::events::id)
+        .for_update()
+        .first::<EventId>(connection)?;
+    let taken_sort_keys = categories
+        .filter(event_id.eq(the_event_id))
+        .filter(not(deleted))
+        .filter(id.ne(category_id))
+        .select(sort_key)
+        .load::<i32>(connection)?
+        .into_iter()
+        .collect::<BTreeSet<i32>>();
+    Ok(next_free_sort_key(&taken_sort_keys, requested_sort_key))
+}
+
+/// Get the lowest value, which is greater or equal to `requested_sort_key` and not contained in
+/// `taken_sort_keys`.
+///
+/// If all such values are taken (which is unrealistic), the `requested_sort_key` is returned.
+fn next_free_sort_key(taken_sort_keys: &BTreeSet<i32>, requested_sort_key: i32) -> i32 {
+    (requested_sort_key..=i32::MAX)
+        .find(|key| !taken_sort_keys.contains(key))
+        .unwrap_or(requested_sort_key)
+}
+
 fn check_rooms_validity(
     room_ids: &[RoomId],
     the_event_id: EventId,
@@ -1808,6 +4061,8 @@ fn check_submission_policies(
     Ok(())
 }
 
+define_sql_function!(fn lower(x: diesel::sql_types::Text) -> diesel::sql_types::Text);
+
 type BoxedBoolExpression<'a, Table> =
     Box<dyn BoxableExpression<Table, diesel::pg::Pg, SqlType = diesel::sql_types::Bool> + 'a>;
 
@@ -1822,6 +4077,13 @@ fn event_filter_to_sql<'a>(filter: EventFilter) -> BoxedBoolExpression<'a, schem
     if let Some(before) = filter.before {
         expression = Box::new(expression.as_expression().and(begin_date.lt(before)));
     }
+    if !filter.include_archived {
+        expression = Box::new(expression.as_expression().and(diesel::dsl::not(archived)));
+    }
+    if let Some(search) = filter.title_search {
+        let pattern = format!("%{}%", escape_like_pattern(&search));
+        expression = Box::new(expression.as_expression().and(title.ilike(pattern)));
+    }
     expression
 }
 
@@ -1864,7 +4126,7 @@ fn entry_filter_to_sql<'a>(filter: EntryFilter) -> BoxedBoolExpression<'a, schem
     {
         use schema::previous_dates::dsl::*;
         let mut sub_query_filter: BoxedBoolExpression<'_, _> =
-            Box::new(entry_id.eq(schema::entries::dsl::id));
+            Box::new(entry_id.eq(schema::entries::dsl::id).and(not(deleted)));
         if let Some(after) = filter.after {
             sub_query_filter = if filter.after_inclusive {
                 Box::new(sub_query_filter.and(end.ge(after)))
@@ -1907,24 +4169,58 @@ fn entry_filter_to_sql<'a>(filter: EntryFilter) -> BoxedBoolExpression<'a, schem
     if let Some(categories) = filter.categories {
         expression = Box::new(expression.as_expression().and(category.eq_any(categories)));
     }
+    if filter.exclude_reservations {
+        expression = Box::new(expression.as_expression().and(not(is_room_reservation)));
+    }
+    if let Some(search) = filter.search {
+        let pattern = format!("%{}%", escape_like_pattern(&search));
+        expression = Box::new(
+            expression.as_expression().and(
+                title
+                    .ilike(pattern.clone())
+                    .or(description.ilike(pattern.clone()))
+                    .or(comment.ilike(pattern)),
+            ),
+        );
+    }
     expression
 }
 
+/// Escape the special characters of Postgres' LIKE/ILIKE patterns (`%`, `_` and the backslash as
+/// escape character) in the given string, such that it is matched literally.
+fn escape_like_pattern(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Create an SQL expression for filtering announcements according to the given filter.
+///
+/// For [AnnouncementFilter::ForDateTime], the event's clock info is loaded from the database to
+/// calculate the effective date and local time of the timestamp.
 fn announcement_filter_to_sql<'a>(
     filter: AnnouncementFilter,
-) -> BoxedBoolExpression<'a, schema::announcements::table> {
+    connection: &mut PgConnection,
+    the_event_id: EventId,
+) -> Result<BoxedBoolExpression<'a, schema::announcements::table>, StoreError> {
     use diesel::dsl::exists;
     use schema::announcements::dsl::*;
 
-    match filter {
-        AnnouncementFilter::ForDate(date) => Box::new(
-            show_with_days.and(
-                begin_date
-                    .is_null()
-                    .or(begin_date.le(date).assume_not_null())
-                    .and(end_date.is_null().or(end_date.ge(date).assume_not_null())),
-            ),
-        ),
+    Ok(match filter {
+        // The time window of an announcement recurs every day, so it always overlaps the date.
+        AnnouncementFilter::ForDate(date) => announcement_date_to_sql(date),
+        AnnouncementFilter::ForDateTime(timestamp) => {
+            let clock_info = schema::events::table
+                .filter(schema::events::id.eq(the_event_id))
+                .select(models::EventClockInfo::as_select())
+                .first::<models::EventClockInfo>(connection)?;
+            let local_time = timestamp.with_timezone(&clock_info.timezone).time();
+            Box::new(
+                announcement_date_to_sql(get_effective_date(&timestamp, &clock_info))
+                    .and(announcement_time_window_to_sql(local_time)),
+            )
+        }
         AnnouncementFilter::ForCategory(category_id) => Box::new(
             show_with_categories.and(
                 show_with_all_categories.or(exists(
@@ -1943,7 +4239,50 @@ fn announcement_filter_to_sql<'a>(
                 )),
             ),
         ),
-    }
+    })
+}
+
+/// SQL expression for announcements to be shown with the KüA-Plan of the given (effective) date
+fn announcement_date_to_sql<'a>(
+    date: chrono::NaiveDate,
+) -> BoxedBoolExpression<'a, schema::announcements::table> {
+    use schema::announcements::dsl::*;
+
+    Box::new(
+        show_with_days.and(
+            begin_date
+                .is_null()
+                .or(begin_date.le(date).assume_not_null())
+                .and(end_date.is_null().or(end_date.ge(date).assume_not_null())),
+        ),
+    )
+}
+
+/// SQL expression for announcements whose time window contains the given local time.
+///
+/// Announcements without `begin_time` and `end_time` always match. The `end_time` is exclusive. If
+/// the `end_time` is earlier than the `begin_time`, the time window spans midnight.
+fn announcement_time_window_to_sql<'a>(
+    time: chrono::NaiveTime,
+) -> BoxedBoolExpression<'a, schema::announcements::table> {
+    use schema::announcements::dsl::*;
+
+    let after_begin = begin_time
+        .is_null()
+        .or(begin_time.le(time).assume_not_null());
+    let before_end = end_time.is_null().or(end_time.gt(time).assume_not_null());
+    let is_regular_window = begin_time
+        .is_null()
+        .or(end_time.is_null())
+        .or(begin_time.lt(end_time).assume_not_null());
+    Box::new(
+        is_regular_window
+            .and(after_begin.and(before_end))
+            .or(begin_time
+                .gt(end_time)
+                .assume_not_null()
+                .and(after_begin.or(before_end))),
+    )
 }
 
 /// Replace some characters of the passphrase with <DEL> characters to allow the user to recognize
@@ -1957,6 +4296,197 @@ fn obfuscate_passphrase(value: &str) -> String {
         .collect()
 }
 
+/// Obfuscated representation of passphrases, for which no plaintext is stored, to be used in place
+/// of [obfuscate_passphrase]'s output.
+const HASHED_PASSPHRASE_PLACEHOLDER: &str = "\x7f\x7f\x7f\x7f\x7f\x7f\x7f\x7f";
+
+/// Check if the plaintext of passphrases with the given role shall be kept in the database in
+/// addition to the hash.
+///
+/// This is only the case for participants' passphrases, which are shown in full in the print
+/// templates (see `get_full_user_passphrases()`). These passphrases are handed out to all
+/// participants and only grant read access, so they are not kept secret from the orga team anyway.
+/// Their hash is stored nevertheless and used for the duplicate check in
+/// [find_matching_passphrases].
+fn keep_plaintext_passphrase(role: AccessRole) -> bool {
+    role == AccessRole::User
+}
+
+/// Prefix of the hashed data in [calculate_passphrase_lookup_key], to not reuse plain SHA-256
+/// hashes of passphrases. Must match the data migration in the `add_passphrase_lookup_key`
+/// migration.
+const PASSPHRASE_LOOKUP_KEY_PREFIX: &str = "kueaplan-passphrase-lookup:";
+
+/// Calculate the lookup key of the passphrase, which is stored next to its hash, for quickly
+/// finding the candidates for verifying a passphrase in [find_matching_passphrases].
+///
+/// The lookup key consists of the first 16 bits of a SHA-256 hash of the passphrase. This is
+/// enough to rule out almost all other passphrases of an event, but far too little to identify a
+/// passphrase (or to considerably speed up brute-forcing the argon2 hash), so it is not secret.
+fn calculate_passphrase_lookup_key(value: &str) -> i32 {
+    let digest = ring::digest::digest(
+        &ring::digest::SHA256,
+        format!("{}{}", PASSPHRASE_LOOKUP_KEY_PREFIX, value).as_bytes(),
+    );
+    i32::from(u16::from_be_bytes([digest.as_ref()[0], digest.as_ref()[1]]))
+}
+
+/// Hash the passphrase with argon2 (using a random salt) for storing it in the database. Returns
+/// the hash in PHC string format.
+fn hash_passphrase(value: &str) -> Result<String, StoreError> {
+    let mut salt = [0u8; argon2::password_hash::Salt::RECOMMENDED_LENGTH];
+    ring::rand::SystemRandom::new()
+        .fill(&mut salt)
+        .expect("System random number generator should be available");
+    let salt = argon2::password_hash::SaltString::encode_b64(&salt)
+        .expect("Salt of recommended length should be encodable");
+    Ok(argon2::Argon2::default()
+        .hash_password(value.as_bytes(), &salt)
+        .map_err(|e| StoreError::InvalidInputData(format!("Could not hash passphrase: {}", e)))?
+        .to_string())
+}
+
+/// Id, validity and access role of a passphrase matching a client-provided passphrase, as returned
+/// by [find_matching_passphrases]
+struct MatchingPassphrase {
+    id: PassphraseId,
+    privilege: AccessRole,
+    valid_from: Option<chrono::DateTime<chrono::Utc>>,
+    valid_until: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Find all (non-derivable) passphrases of the event, which match the given plaintext passphrase,
+/// ordered by id.
+///
+/// Passphrases are stored as hash, so we can't filter them in the database. Instead, we narrow down
+/// the candidates by their lookup key (see [calculate_passphrase_lookup_key]) and check each of
+/// them, which is intentionally slow. Passphrases without a lookup key (hashed before the key was
+/// introduced) are always checked, and their lookup key is stored, when they match. The plaintext
+/// comparison is only used for passphrases, for which the plaintext is (still) available.
+fn find_matching_passphrases(
+    connection: &mut PgConnection,
+    the_event_id: EventId,
+    the_passphrase: &str,
+) -> Result<Vec<MatchingPassphrase>, StoreError> {
+    use schema::event_passphrases::dsl::*;
+    let lookup_key = calculate_passphrase_lookup_key(the_passphrase);
+    let candidates = event_passphrases
+        .select((
+            id,
+            privilege,
+            valid_from,
+            valid_until,
+            passphrase,
+            passphrase_hash,
+            passphrase_lookup_key.is_null(),
+        ))
+        .filter(event_id.eq(the_event_id))
+        .filter(passphrase.is_not_null().or(passphrase_hash.is_not_null()))
+        .filter(
+            passphrase_lookup_key
+                .eq(lookup_key)
+                .or(passphrase_lookup_key.is_null()),
+        )
+        .order_by(id)
+        .load::<(
+            PassphraseId,
+            AccessRole,
+            Option<chrono::DateTime<chrono::Utc>>,
+            Option<chrono::DateTime<chrono::Utc>>,
+            Option<String>,
+            Option<String>,
+            bool,
+        )>(connection)?;
+    let mut result = Vec::new();
+    let mut missing_lookup_keys = Vec::new();
+    for (pid, role, begin, end, plaintext, hash, lookup_key_missing) in candidates {
+        let matches = match (plaintext, hash) {
+            (Some(plaintext), _) => plaintext == the_passphrase,
+            (None, Some(hash)) => verify_passphrase_hash(the_passphrase, &hash)?,
+            (None, None) => false,
+        };
+        if matches {
+            if lookup_key_missing {
+                missing_lookup_keys.push(pid);
+            }
+            result.push(MatchingPassphrase {
+                id: pid,
+                privilege: role,
+                valid_from: begin,
+                valid_until: end,
+            });
+        }
+    }
+    if !missing_lookup_keys.is_empty() {
+        diesel::update(event_passphrases)
+            .filter(id.eq_any(missing_lookup_keys))
+            .set(passphrase_lookup_key.eq(lookup_key))
+            .execute(connection)?;
+    }
+    Ok(result)
+}
+
+/// Check if any passphrase of the event matches the given passphrase hash, i.e. it has the same
+/// hash or its stored plaintext verifies against the hash.
+fn passphrase_hash_exists(
+    connection: &mut PgConnection,
+    the_event_id: EventId,
+    the_hash: &str,
+) -> Result<bool, StoreError> {
+    use schema::event_passphrases::dsl::*;
+    argon2::PasswordHash::new(the_hash)
+        .map_err(|e| StoreError::InvalidInputData(format!("Invalid passphrase hash: {}", e)))?;
+    let candidates = event_passphrases
+        .select((passphrase, passphrase_hash))
+        .filter(event_id.eq(the_event_id))
+        .filter(passphrase.is_not_null().or(passphrase_hash.is_not_null()))
+        .load::<(Option<String>, Option<String>)>(connection)?;
+    for (plaintext, hash) in candidates {
+        if hash.as_deref() == Some(the_hash) {
+            return Ok(true);
+        }
+        if let Some(plaintext) = plaintext
+            && verify_passphrase_hash(&plaintext, the_hash)?
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Generate a new random API key (256 bits of entropy, url-safe base64 encoded)
+fn generate_api_key() -> String {
+    let mut data = [0u8; 32];
+    ring::rand::SystemRandom::new()
+        .fill(&mut data)
+        .expect("System random number generator should be available");
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(data)
+}
+
+/// Hash the API key with SHA-256 for storing it in the database and looking it up. Returns the
+/// hash as hex string.
+///
+/// In contrast to passphrases, API keys are always randomly generated with high entropy, so a
+/// fast unsalted hash is sufficient and allows for an indexed lookup on every API request.
+fn hash_api_key(value: &str) -> String {
+    ring::digest::digest(&ring::digest::SHA256, value.as_bytes())
+        .as_ref()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Check if the passphrase matches the given argon2 hash in PHC string format, as created by
+/// [hash_passphrase].
+fn verify_passphrase_hash(value: &str, hash: &str) -> Result<bool, StoreError> {
+    let hash = argon2::PasswordHash::new(hash).map_err(|e| {
+        StoreError::InvalidDataInDatabase(format!("Invalid passphrase hash: {}", e))
+    })?;
+    Ok(argon2::Argon2::default()
+        .verify_password(value.as_bytes(), &hash)
+        .is_ok())
+}
+
 /// Get a human-readable description of the consistency expectation that is checked by a specific
 /// constraint in our Postgres database schema by the constraint's name.
 ///
@@ -1976,6 +4506,9 @@ pub fn description_for_postgres_constraint(constraint_name: &str) -> Option<&'st
         "announcements_date_range" => {
             Some("Announcement's begin_date must be earlier or equal to end_date.")
         }
+        "announcements_time_window" => {
+            Some("Announcement's begin_time and end_time must not be equal.")
+        }
         "entries_category_fkey" => Some("Entry's category must reference an existing category."),
         "entries_time_range" => Some("Entry's begin must be earlier or equal to end."),
         "entry_rooms_room_id_fkey" => Some("Entry's rooms must reference existing rooms."),
@@ -1998,3 +4531,146 @@ pub fn description_for_postgres_constraint(constraint_name: &str) -> Option<&'st
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calculate_passphrase_lookup_key() {
+        let key = calculate_passphrase_lookup_key("user");
+        assert!((0..=0xffff).contains(&key));
+        assert_eq!(key, calculate_passphrase_lookup_key("user"));
+        assert_ne!(key, calculate_passphrase_lookup_key("orga"));
+    }
+
+    #[test]
+    fn test_retry_on_transaction_conflict() {
+        let mut attempts = 0;
+        let result = retry_on_transaction_conflict(|| {
+            attempts += 1;
+            if attempts == 1 {
+                Err(StoreError::TransactionConflict)
+            } else {
+                Ok(42)
+            }
+        });
+        assert!(matches!(result, Ok(42)));
+        assert_eq!(attempts, 2);
+
+        let mut attempts = 0;
+        let result: Result<(), _> = retry_on_transaction_conflict(|| {
+            attempts += 1;
+            Err(StoreError::TransactionConflict)
+        });
+        assert!(matches!(result, Err(StoreError::TransactionConflict)));
+        assert_eq!(attempts, MAX_TRANSACTION_ATTEMPTS);
+
+        // Other errors must not be retried
+        let mut attempts = 0;
+        let result: Result<(), _> = retry_on_transaction_conflict(|| {
+            attempts += 1;
+            Err(StoreError::NotExisting)
+        });
+        assert!(matches!(result, Err(StoreError::NotExisting)));
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn test_changed_entry_fields() {
+        let old_state = serde_json::json!({
+            "id": "0197d8e4-7e5a-7c5b-8ba5-2a4c2a2a6f1e",
+            "title": "Foo",
+            "comment": "",
+            "last_updated": "2025-01-01T10:00:00+00:00",
+            "room_ids": ["0197d8e4-7e5a-7c5b-8ba5-2a4c2a2a6f1f"],
+        });
+        let serde_json::Value::Object(old_state) = old_state else {
+            unreachable!()
+        };
+        let mut new_state = old_state.clone();
+        new_state.insert("title".to_owned(), "Bar".into());
+        new_state.insert(
+            "last_updated".to_owned(),
+            "2025-01-01T11:00:00+00:00".into(),
+        );
+        new_state.insert("room_ids".to_owned(), serde_json::Value::Array(vec![]));
+
+        let mut changed_fields = changed_entry_fields(Some(&old_state), &new_state);
+        changed_fields.sort();
+        assert_eq!(changed_fields, vec!["room_ids", "title"]);
+        assert!(changed_entry_fields(Some(&old_state), &old_state).is_empty());
+
+        let mut changed_fields = changed_entry_fields(None, &new_state);
+        changed_fields.sort();
+        assert_eq!(changed_fields, vec!["comment", "room_ids", "title"]);
+    }
+
+    #[test]
+    fn test_next_free_sort_key() {
+        let taken = BTreeSet::from([0, 1, 2, 5, 10]);
+        assert_eq!(next_free_sort_key(&taken, 3), 3);
+        assert_eq!(next_free_sort_key(&taken, 0), 3);
+        assert_eq!(next_free_sort_key(&taken, 5), 6);
+        assert_eq!(next_free_sort_key(&taken, -1), -1);
+        assert_eq!(next_free_sort_key(&BTreeSet::new(), 42), 42);
+        assert_eq!(
+            next_free_sort_key(&BTreeSet::from([i32::MAX - 1, i32::MAX]), i32::MAX - 1),
+            i32::MAX - 1
+        );
+    }
+
+    #[test]
+    fn test_passphrase_hash() {
+        let hash = hash_passphrase("Drachenfliegen").unwrap();
+        assert!(hash.starts_with("$argon2id$"));
+        assert!(verify_passphrase_hash("Drachenfliegen", &hash).unwrap());
+        assert!(!verify_passphrase_hash("drachenfliegen", &hash).unwrap());
+        assert!(!verify_passphrase_hash("", &hash).unwrap());
+        // Random salt
+        assert_ne!(hash, hash_passphrase("Drachenfliegen").unwrap());
+        assert!(matches!(
+            verify_passphrase_hash("Drachenfliegen", "Drachenfliegen"),
+            Err(StoreError::InvalidDataInDatabase(_))
+        ));
+    }
+
+    #[test]
+    fn test_api_key_hash() {
+        assert_eq!(
+            hash_api_key("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        let key = generate_api_key();
+        assert_eq!(key.len(), 43);
+        assert_ne!(key, generate_api_key());
+        assert_eq!(hash_api_key(&key), hash_api_key(&key));
+    }
+
+    #[test]
+    fn test_event_filter_to_sql() {
+        let event_filter_sql = |filter: EventFilter| {
+            let query = schema::events::table
+                .select(schema::events::id)
+                .filter(event_filter_to_sql(filter));
+            diesel::debug_query::<diesel::pg::Pg, _>(&query).to_string()
+        };
+
+        let sql = event_filter_sql(EventFilter::builder().build());
+        assert!(sql.contains("NOT (\"events\".\"archived\")"), "{}", sql);
+        assert!(!sql.contains("ILIKE"), "{}", sql);
+
+        let sql = event_filter_sql(
+            EventFilter::builder()
+                .include_archived()
+                .matching_title("50%_Pfingsten".to_owned())
+                .after("2025-01-01".parse().unwrap())
+                .build(),
+        );
+        assert!(!sql.contains("archived"), "{}", sql);
+        assert!(sql.contains("\"events\".\"title\" ILIKE $"), "{}", sql);
+        assert!(sql.contains("\"events\".\"end_date\" >= $"), "{}", sql);
+        // Special characters of the search text are matched literally
+        assert!(sql.contains(r#""%50\\%\\_Pfingsten%""#), "{}", sql);
+    }
+}
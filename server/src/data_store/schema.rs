@@ -30,6 +30,18 @@ diesel::table! {
         sort_key -> Int4,
         deleted -> Bool,
         last_updated -> Timestamptz,
+        begin_time -> Nullable<Time>,
+        end_time -> Nullable<Time>,
+    }
+}
+
+diesel::table! {
+    api_keys (id) {
+        id -> Int4,
+        event_id -> Int4,
+        key_hash -> Varchar,
+        comment -> Varchar,
+        created_at -> Timestamptz,
     }
 }
 
@@ -68,6 +80,24 @@ diesel::table! {
         is_cancelled -> Bool,
         state -> Int4,
         orga_comment -> Varchar,
+        color_override -> Nullable<Varchar>,
+        links -> Nullable<Jsonb>,
+        is_tentative -> Bool,
+        orga_only -> Bool,
+        last_updated_by_role -> Nullable<Int4>,
+        open_ended -> Bool,
+    }
+}
+
+diesel::table! {
+    entry_audit_log (id) {
+        id -> Int4,
+        entry_id -> Uuid,
+        event_id -> Int4,
+        action -> Int4,
+        passphrase_id -> Nullable<Int4>,
+        timestamp -> Timestamptz,
+        changed_fields -> Array<Varchar>,
     }
 }
 
@@ -78,6 +108,15 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    entry_templates (id) {
+        id -> Uuid,
+        event_id -> Int4,
+        name -> Varchar,
+        entry_data -> Jsonb,
+    }
+}
+
 diesel::table! {
     event_passphrases (id) {
         id -> Int4,
@@ -88,6 +127,9 @@ diesel::table! {
         comment -> Varchar,
         valid_from -> Nullable<Timestamptz>,
         valid_until -> Nullable<Timestamptz>,
+        passphrase_hash -> Nullable<Varchar>,
+        passphrase_last_used -> Nullable<Timestamptz>,
+        passphrase_lookup_key -> Nullable<Int4>,
     }
 }
 
@@ -104,6 +146,20 @@ diesel::table! {
         preceding_event_id -> Nullable<Int4>,
         subsequent_event_id -> Nullable<Int4>,
         entry_submission_mode -> Int4,
+        archived -> Bool,
+        begin_time -> Nullable<Time>,
+        end_time -> Nullable<Time>,
+    }
+}
+
+diesel::table! {
+    feed_tokens (id) {
+        id -> Int4,
+        event_id -> Int4,
+        privilege -> Int4,
+        comment -> Varchar,
+        created_at -> Timestamptz,
+        revoked -> Bool,
     }
 }
 
@@ -122,6 +178,7 @@ diesel::table! {
         begin -> Timestamptz,
         end -> Timestamptz,
         last_updated -> Timestamptz,
+        deleted -> Bool,
     }
 }
 
@@ -141,12 +198,18 @@ diesel::joinable!(announcement_categories -> categories (category_id));
 diesel::joinable!(announcement_rooms -> announcements (announcement_id));
 diesel::joinable!(announcement_rooms -> rooms (room_id));
 diesel::joinable!(announcements -> events (event_id));
+diesel::joinable!(api_keys -> events (event_id));
 diesel::joinable!(categories -> events (event_id));
 diesel::joinable!(entries -> categories (category));
 diesel::joinable!(entries -> events (event_id));
+diesel::joinable!(entry_audit_log -> entries (entry_id));
+diesel::joinable!(entry_audit_log -> event_passphrases (passphrase_id));
+diesel::joinable!(entry_audit_log -> events (event_id));
 diesel::joinable!(entry_rooms -> entries (entry_id));
 diesel::joinable!(entry_rooms -> rooms (room_id));
+diesel::joinable!(entry_templates -> events (event_id));
 diesel::joinable!(event_passphrases -> events (event_id));
+diesel::joinable!(feed_tokens -> events (event_id));
 diesel::joinable!(previous_date_rooms -> previous_dates (previous_date_id));
 diesel::joinable!(previous_date_rooms -> rooms (room_id));
 diesel::joinable!(previous_dates -> entries (entry_id));
@@ -156,11 +219,15 @@ diesel::allow_tables_to_appear_in_same_query!(
     announcement_categories,
     announcement_rooms,
     announcements,
+    api_keys,
     categories,
     entries,
+    entry_audit_log,
     entry_rooms,
+    entry_templates,
     event_passphrases,
     events,
+    feed_tokens,
     previous_date_rooms,
     previous_dates,
     rooms,
@@ -2,6 +2,8 @@ use clap::ArgAction;
 use clap::{Args, Parser, Subcommand};
 use dotenvy::dotenv;
 use kueaplan_server::cli::EventIdOrSlug;
+use kueaplan_server::cli::file_io::ImportConflictMode;
+use kueaplan_server::cli::manage_passphrases::PassphraseAccessRoleEntry;
 use kueaplan_server::cli_error::CliError;
 use log::{error, info, warn};
 use std::path::PathBuf;
@@ -37,11 +39,30 @@ fn main() {
 
 fn run_main_command(command: Command) -> Result<(), CliError> {
     match command {
-        Command::Event(EventCommand::List) => {
-            kueaplan_server::cli::manage_events::print_event_list()?;
+        Command::Event(EventCommand::List {
+            filter,
+            after,
+            before,
+        }) => {
+            kueaplan_server::cli::manage_events::print_event_list(filter, after, before)?;
         }
-        Command::Event(EventCommand::Import { path, keep_uuids }) => {
-            kueaplan_server::cli::file_io::load_event_from_file(&path, !keep_uuids)?;
+        Command::Event(EventCommand::Show { event_id_or_slug }) => {
+            kueaplan_server::cli::manage_events::print_event_summary(event_id_or_slug)?;
+        }
+        Command::Event(EventCommand::Import {
+            path,
+            keep_uuids,
+            dry_run,
+            preview,
+            on_conflict,
+        }) => {
+            kueaplan_server::cli::file_io::load_event_from_file(
+                &path,
+                !keep_uuids,
+                dry_run,
+                preview,
+                on_conflict,
+            )?;
         }
         Command::Event(EventCommand::Export {
             event_id_or_slug,
@@ -49,18 +70,50 @@ fn run_main_command(command: Command) -> Result<(), CliError> {
         }) => {
             kueaplan_server::cli::file_io::export_event_to_file(event_id_or_slug, &path)?;
         }
+        Command::Event(EventCommand::ImportIcs {
+            event_id_or_slug,
+            path,
+            default_category,
+        }) => {
+            kueaplan_server::cli::file_io::import_entries_from_ics_file(
+                event_id_or_slug,
+                &path,
+                default_category,
+            )?;
+        }
         Command::Event(EventCommand::Create) => {
             kueaplan_server::cli::manage_events::create_event()?;
         }
         Command::Event(EventCommand::Delete { event_id_or_slug }) => {
             kueaplan_server::cli::manage_events::delete_event(event_id_or_slug)?;
         }
+        Command::Event(EventCommand::Archive { event_id_or_slug }) => {
+            kueaplan_server::cli::manage_events::toggle_event_archived(event_id_or_slug)?;
+        }
+        Command::Event(EventCommand::GenerateSlugs) => {
+            kueaplan_server::cli::manage_events::generate_missing_slugs()?;
+        }
         Command::Passphrase(PassphraseCommand::List { event_id_or_slug }) => {
             kueaplan_server::cli::manage_passphrases::print_passphrase_list(event_id_or_slug)?;
         }
         Command::Passphrase(PassphraseCommand::Create { event_id_or_slug }) => {
             kueaplan_server::cli::manage_passphrases::add_passphrase(event_id_or_slug)?;
         }
+        Command::Passphrase(PassphraseCommand::CreateBatch {
+            event_id_or_slug,
+            count,
+            role,
+            words,
+            comment,
+        }) => {
+            kueaplan_server::cli::manage_passphrases::create_passphrase_batch(
+                event_id_or_slug,
+                count,
+                role,
+                words,
+                comment,
+            )?;
+        }
         Command::Passphrase(PassphraseCommand::Edit {
             event_id_or_slug,
             passphrase_id,
@@ -88,6 +141,65 @@ fn run_main_command(command: Command) -> Result<(), CliError> {
                 passphrase_id,
             )?;
         }
+        Command::Passphrase(PassphraseCommand::Export {
+            event_id_or_slug,
+            path,
+        }) => {
+            kueaplan_server::cli::manage_passphrases::export_passphrases_to_file(
+                event_id_or_slug,
+                &path,
+            )?;
+        }
+        Command::Passphrase(PassphraseCommand::Import {
+            event_id_or_slug,
+            path,
+        }) => {
+            kueaplan_server::cli::manage_passphrases::import_passphrases_from_file(
+                event_id_or_slug,
+                &path,
+            )?;
+        }
+        Command::Passphrase(PassphraseCommand::HashExisting) => {
+            kueaplan_server::cli::manage_passphrases::hash_existing_passphrases()?;
+        }
+        Command::ApiKey(ApiKeyCommand::List { event_id_or_slug }) => {
+            kueaplan_server::cli::manage_api_keys::print_api_key_list(event_id_or_slug)?;
+        }
+        Command::ApiKey(ApiKeyCommand::Create {
+            event_id_or_slug,
+            comment,
+        }) => {
+            kueaplan_server::cli::manage_api_keys::create_api_key(event_id_or_slug, comment)?;
+        }
+        Command::ApiKey(ApiKeyCommand::Revoke {
+            event_id_or_slug,
+            api_key_id,
+        }) => {
+            kueaplan_server::cli::manage_api_keys::revoke_api_key(event_id_or_slug, api_key_id)?;
+        }
+        Command::FeedToken(FeedTokenCommand::List { event_id_or_slug }) => {
+            kueaplan_server::cli::manage_feed_tokens::print_feed_token_list(event_id_or_slug)?;
+        }
+        Command::FeedToken(FeedTokenCommand::Create {
+            event_id_or_slug,
+            role,
+            comment,
+        }) => {
+            kueaplan_server::cli::manage_feed_tokens::create_feed_token(
+                event_id_or_slug,
+                role,
+                comment,
+            )?;
+        }
+        Command::FeedToken(FeedTokenCommand::Revoke {
+            event_id_or_slug,
+            feed_token_id,
+        }) => {
+            kueaplan_server::cli::manage_feed_tokens::revoke_feed_token(
+                event_id_or_slug,
+                feed_token_id,
+            )?;
+        }
         Command::Serve => {
             kueaplan_server::cli::database_migration::check_migration_state()?;
             kueaplan_server::web::serve()?;
@@ -95,6 +207,12 @@ fn run_main_command(command: Command) -> Result<(), CliError> {
         Command::MigrateDatabase => {
             kueaplan_server::cli::database_migration::run_migrations()?;
         }
+        Command::CheckIntegrity => {
+            kueaplan_server::cli::integrity::check_integrity()?;
+        }
+        Command::RotateSecret { new_secret } => {
+            kueaplan_server::cli::manage_secret::rotate_secret(new_secret)?;
+        }
     }
     Ok(())
 }
@@ -122,15 +240,46 @@ enum Command {
     /// Collection of sub commands for managing Passphrases of events
     #[clap(subcommand)]
     Passphrase(PassphraseCommand),
+    /// Collection of sub commands for managing read-only API keys of events
+    #[clap(subcommand)]
+    ApiKey(ApiKeyCommand),
+    /// Collection of sub commands for managing revocable tokens for the calendar feeds of events
+    #[clap(subcommand)]
+    FeedToken(FeedTokenCommand),
+    /// Check the database for inconsistencies, like references to deleted rooms or events without
+    /// categories, and print a report. The database is not modified.
+    CheckIntegrity,
+    /// Print the environment configuration for replacing the application SECRET, while keeping
+    /// existing session tokens valid during a transition period (via SECRET_PREVIOUS)
+    RotateSecret {
+        /// The new secret. If not given, a random secret is generated.
+        new_secret: Option<String>,
+    },
 }
 
 #[derive(Debug, Subcommand)]
 enum EventCommand {
-    /// List all events in the database
-    List,
+    /// List all events in the database, optionally filtered by title and date
+    List {
+        /// Only list events whose title contains the given text (case-insensitive)
+        #[clap(long)]
+        filter: Option<String>,
+        /// Only list events that end at or after the given date (YYYY-MM-DD)
+        #[clap(long)]
+        after: Option<chrono::NaiveDate>,
+        /// Only list events that begin before the given date (YYYY-MM-DD)
+        #[clap(long)]
+        before: Option<chrono::NaiveDate>,
+    },
+    /// Print an overview of the given event's (by event id or event slug) configuration and
+    /// contents
+    Show {
+        /// The id or slug of the event
+        event_id_or_slug: EventIdOrSlug,
+    },
     /// Load event data (except for passphrases) from JSON file
     Import {
-        /// The path of the JSON file to read from
+        /// The path of the file to read from
         path: PathBuf,
         /// Keep the entries', previous dates', rooms', categories' and announcements' UUIDs,
         /// instead of generating new ones. This may cause conflicts with existing data, when the
@@ -138,14 +287,39 @@ enum EventCommand {
         /// times.
         #[clap(long)]
         keep_uuids: bool,
+        /// Only validate the file and print a summary of its contents, without importing anything
+        /// into the database.
+        #[clap(long)]
+        dry_run: bool,
+        /// Only print the differences (created, updated and orphaned rooms, categories and
+        /// entries) to the existing event with the same slug, without importing anything into the
+        /// database.
+        #[clap(long, requires = "keep_uuids", conflicts_with = "dry_run")]
+        preview: bool,
+        /// Behaviour for imported rooms, categories, entries, previous dates and announcements with
+        /// the id of an already existing one (only possible with --keep-uuids)
+        #[clap(long, value_enum, default_value_t = ImportConflictMode::Fail)]
+        on_conflict: ImportConflictMode,
     },
     /// Export full event (except for passphrases) to JSON file
     Export {
         /// The id or slug of the event to be exported
         event_id_or_slug: EventIdOrSlug,
-        /// The path of the JSON file to read from
+        /// The path of the file to read from
         path: PathBuf,
     },
+    /// Create draft entries in the given event (by event id or event slug) from the events in an
+    /// iCalendar (ICS) file, e.g. from a draft schedule built in a calendar app. Events outside of
+    /// the event's date range and all-day events are skipped with a warning. Importing the same
+    /// file again updates the previously imported entries.
+    ImportIcs {
+        /// The id or slug of the event
+        event_id_or_slug: EventIdOrSlug,
+        /// The path of the ICS file to read from
+        path: PathBuf,
+        /// The id of the category for all new entries
+        default_category: uuid::Uuid,
+    },
     /// Create a new event. Basic event data is queried interactively in the terminal.
     Create,
     /// Delete an event with all associated data.
@@ -153,6 +327,14 @@ enum EventCommand {
         /// The id or slug of the event to be deleted
         event_id_or_slug: EventIdOrSlug,
     },
+    /// Archive the event (i.e. hide it from event listings) or un-archive it, if it is already
+    /// archived.
+    Archive {
+        /// The id or slug of the event to be archived or un-archived
+        event_id_or_slug: EventIdOrSlug,
+    },
+    /// Generate a slug for all events without slug, derived from their title
+    GenerateSlugs,
 }
 
 #[derive(Debug, Subcommand)]
@@ -167,6 +349,22 @@ enum PassphraseCommand {
         /// The id or slug of the event
         event_id_or_slug: EventIdOrSlug,
     },
+    /// Create multiple passphrases with randomly generated, human-readable passphrases for the
+    /// given event (by event id or event slug) and print them
+    CreateBatch {
+        /// The id or slug of the event
+        event_id_or_slug: EventIdOrSlug,
+        /// The number of passphrases to create
+        count: usize,
+        /// The access role of the new passphrases ('user', 'orga' or 'admin')
+        role: PassphraseAccessRoleEntry,
+        /// The number of words of each generated passphrase
+        #[clap(long, default_value_t = 4)]
+        words: usize,
+        /// Comment about the designated usage of the passphrases
+        #[clap(long, default_value = "")]
+        comment: String,
+    },
     /// Change comment or validity of the passphrase with given id from the given event (by event id
     /// or event slug)
     Edit {
@@ -190,6 +388,91 @@ enum PassphraseCommand {
         /// The id of the passphrase to be invalidated
         passphrase_id: i32,
     },
+    /// Hash all passphrases of all events, which are still stored in plaintext only. This is
+    /// required once after upgrading from a version without passphrase hashing. The plaintext is
+    /// only kept for passphrases of the 'user' role (for the print templates).
+    HashExisting,
+    /// Export all passphrases of the given event (by event id or event slug) to a file, for
+    /// backup purposes. The file is encrypted with a password, which is queried interactively.
+    ///
+    /// SECURITY NOTE: The file contains the hashes of all passphrases and the plaintext of the
+    /// 'user' passphrases. Anyone with access to the file and its password can log in with the
+    /// 'user' passphrases and may try to brute-force the hashes of the other passphrases offline.
+    /// Use a strong password, store the file securely and delete it when it is no longer needed.
+    Export {
+        /// The id or slug of the event
+        event_id_or_slug: EventIdOrSlug,
+        /// The path of the file to write to
+        path: PathBuf,
+    },
+    /// Recreate passphrases from a file, as written by `passphrase export`, for the given event
+    /// (by event id or event slug). The password of the file is queried interactively.
+    ///
+    /// The passphrases are created with new ids, in addition to the existing passphrases of the
+    /// event. The import is aborted if any passphrase matches an existing passphrase. Thus,
+    /// sessions and sharable links, which have been created with the original passphrases, are not
+    /// restored.
+    Import {
+        /// The id or slug of the event
+        event_id_or_slug: EventIdOrSlug,
+        /// The path of the file to read from
+        path: PathBuf,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum ApiKeyCommand {
+    /// List all API keys of the given event (by event id or event slug)
+    List {
+        /// The id or slug of the event
+        event_id_or_slug: EventIdOrSlug,
+    },
+    /// Create a new read-only API key for the given event (by event id or event slug) and print
+    /// it. API clients can use the key via the `Authorization: Bearer <key>` header to read the
+    /// event's KüA-Plan.
+    Create {
+        /// The id or slug of the event
+        event_id_or_slug: EventIdOrSlug,
+        /// Comment about the designated usage of the API key
+        #[clap(long, default_value = "")]
+        comment: String,
+    },
+    /// Revoke the API key with given id from the given event (by event id or event slug)
+    Revoke {
+        /// The id or slug of the event
+        event_id_or_slug: EventIdOrSlug,
+        /// The id of the API key to be revoked
+        api_key_id: i32,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum FeedTokenCommand {
+    /// List all feed tokens of the given event (by event id or event slug)
+    List {
+        /// The id or slug of the event
+        event_id_or_slug: EventIdOrSlug,
+    },
+    /// Create a new token for the calendar feeds (iCal, Frab XML) of the given event (by event id
+    /// or event slug) and print it. Calendar clients use the token via the `token` query parameter
+    /// and see the entries visible to the given role.
+    Create {
+        /// The id or slug of the event
+        event_id_or_slug: EventIdOrSlug,
+        /// The access role granted by the token ('user', 'orga' or 'admin')
+        #[clap(long, default_value = "user")]
+        role: PassphraseAccessRoleEntry,
+        /// Comment about the designated usage of the feed token
+        #[clap(long, default_value = "")]
+        comment: String,
+    },
+    /// Revoke the feed token with given id from the given event (by event id or event slug)
+    Revoke {
+        /// The id or slug of the event
+        event_id_or_slug: EventIdOrSlug,
+        /// The id of the feed token to be revoked
+        feed_token_id: i32,
+    },
 }
 
 #[derive(Debug, Args)]
@@ -12,6 +12,32 @@ pub fn get_secret_from_env() -> Result<String, SetupError> {
     env::var("SECRET").map_err(|e| SetupError::from_env_error(e, "SECRET"))
 }
 
+/// Get the list of previous application secrets, which are still accepted for validating secure
+/// tokens, from the comma-separated environment variable. Defaults to an empty list.
+pub fn get_previous_secrets_from_env() -> Result<Vec<String>, SetupError> {
+    match env::var("SECRET_PREVIOUS") {
+        Err(VarError::NotPresent) => Ok(vec![]),
+        v => v
+            .map_err(|e| SetupError::from_env_error(e, "SECRET_PREVIOUS"))
+            .map(|v| {
+                v.split(',')
+                    .map(|s| s.trim())
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.to_owned())
+                    .collect()
+            }),
+    }
+}
+
+/// Get all acceptable application secrets from the environment variables: The current secret
+/// (`SECRET`), to be used for signing, followed by the previous secrets (`SECRET_PREVIOUS`), which
+/// are only used for validation.
+pub fn get_secrets_from_env() -> Result<Vec<String>, SetupError> {
+    let mut secrets = vec![get_secret_from_env()?];
+    secrets.extend(get_previous_secrets_from_env()?);
+    Ok(secrets)
+}
+
 /// Get the web server TCP listening port from the environment variable
 pub fn get_listen_port_from_env() -> Result<u16, SetupError> {
     env::var("LISTEN_PORT")
@@ -38,8 +64,296 @@ pub fn get_admin_email_from_env() -> Result<String, SetupError> {
 }
 
 pub fn get_allow_api_cors_from_env() -> bool {
-    env::var("API_CORS_ALLOW_ANY_ORIGIN")
-        .is_ok_and(|v| ["1", "on", "true", "yes"].contains(&v.trim().to_lowercase().as_str()))
+    get_bool_from_env("API_CORS_ALLOW_ANY_ORIGIN")
+}
+
+/// Check if the Prometheus metrics endpoint `/metrics` is enabled via the environment variable.
+pub fn get_metrics_enabled_from_env() -> bool {
+    get_bool_from_env("METRICS_ENABLED")
+}
+
+/// (Lowercase) values of boolean environment variables, which are interpreted as true
+const TRUE_ENV_VALUES: [&str; 4] = ["1", "on", "true", "yes"];
+
+/// Check if the given environment variable is set to one of the [TRUE_ENV_VALUES]
+/// (case-insensitive). If it is not set or set to any other value, false is returned.
+fn get_bool_from_env(variable_name: &str) -> bool {
+    env::var(variable_name)
+        .is_ok_and(|v| TRUE_ENV_VALUES.contains(&v.trim().to_lowercase().as_str()))
+}
+
+/// Get the maximum number of failed passphrase authentication attempts per client within the
+/// rate limiting window from the environment variable. Defaults to 10.
+pub fn get_auth_rate_limit_max_attempts_from_env() -> Result<u32, SetupError> {
+    match env::var("AUTH_RATE_LIMIT_MAX_ATTEMPTS") {
+        Err(VarError::NotPresent) => Ok(10),
+        v => v
+            .map_err(|e| SetupError::from_env_error(e, "AUTH_RATE_LIMIT_MAX_ATTEMPTS"))
+            .and_then(|v| {
+                v.parse().map_err(|_| SetupError::EnvVariableInvalid {
+                    variable_name: "AUTH_RATE_LIMIT_MAX_ATTEMPTS",
+                    problem: "Not a valid uint32",
+                })
+            }),
+    }
+}
+
+/// Get the length of the rate limiting window for failed passphrase authentication attempts from
+/// the environment variable (in seconds). Defaults to 5 minutes.
+pub fn get_auth_rate_limit_window_from_env() -> Result<std::time::Duration, SetupError> {
+    match env::var("AUTH_RATE_LIMIT_WINDOW_SECONDS") {
+        Err(VarError::NotPresent) => Ok(std::time::Duration::from_secs(300)),
+        v => v
+            .map_err(|e| SetupError::from_env_error(e, "AUTH_RATE_LIMIT_WINDOW_SECONDS"))
+            .and_then(|v| {
+                v.parse().map(std::time::Duration::from_secs).map_err(|_| {
+                    SetupError::EnvVariableInvalid {
+                        variable_name: "AUTH_RATE_LIMIT_WINDOW_SECONDS",
+                        problem: "Not a valid uint64",
+                    }
+                })
+            }),
+    }
+}
+
+/// Get the list of IP addresses of trusted reverse proxies from the comma-separated environment
+/// variable `TRUSTED_PROXIES`. The `Forwarded` and `X-Forwarded-For` headers are only considered
+/// for identifying clients, when the request comes from one of these addresses. Defaults to an
+/// empty list.
+pub fn get_trusted_proxies_from_env() -> Result<Vec<std::net::IpAddr>, SetupError> {
+    match env::var("TRUSTED_PROXIES") {
+        Err(VarError::NotPresent) => Ok(vec![]),
+        v => v
+            .map_err(|e| SetupError::from_env_error(e, "TRUSTED_PROXIES"))?
+            .split(',')
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .map(|s| {
+                s.parse().map_err(|_| SetupError::EnvVariableInvalid {
+                    variable_name: "TRUSTED_PROXIES",
+                    problem: "Not a valid list of IP addresses",
+                })
+            })
+            .collect(),
+    }
+}
+
+/// Get the server admin API key from the environment variable `SERVER_ADMIN_API_KEY`. Web clients
+/// sending this key as `Authorization: Bearer` header are authorized for the (few) global
+/// [AccessRole::ServerAdmin](crate::data_store::auth_token::AccessRole::ServerAdmin) actions, which
+/// are available via the REST API. None, if not set or empty.
+pub fn get_server_admin_api_key_from_env() -> Option<String> {
+    env::var("SERVER_ADMIN_API_KEY")
+        .ok()
+        .filter(|key| !key.trim().is_empty())
+}
+
+/// Get the timeout for the graceful shutdown of the HTTP server from the environment variable
+/// `SHUTDOWN_TIMEOUT_SECS` (in seconds). Defaults to 30 seconds.
+pub fn get_shutdown_timeout_from_env() -> Result<std::time::Duration, SetupError> {
+    let value = match env::var("SHUTDOWN_TIMEOUT_SECS") {
+        Err(VarError::NotPresent) => None,
+        v => Some(v.map_err(|e| SetupError::from_env_error(e, "SHUTDOWN_TIMEOUT_SECS"))?),
+    };
+    parse_shutdown_timeout(value.as_deref())
+}
+
+/// Parse the graceful shutdown timeout from the (optional) value of the environment variable
+/// `SHUTDOWN_TIMEOUT_SECS`
+fn parse_shutdown_timeout(value: Option<&str>) -> Result<std::time::Duration, SetupError> {
+    match value {
+        None => Ok(std::time::Duration::from_secs(30)),
+        Some(v) => v
+            .trim()
+            .parse()
+            .map(std::time::Duration::from_secs)
+            .map_err(|_| SetupError::EnvVariableInvalid {
+                variable_name: "SHUTDOWN_TIMEOUT_SECS",
+                problem: "Not a valid uint64",
+            }),
+    }
+}
+
+/// Get the maximum size of JSON request bodies of the REST API (in bytes) from the environment
+/// variable `MAX_JSON_BODY_BYTES`. Defaults to 2 MiB.
+pub fn get_max_json_body_bytes_from_env() -> Result<usize, SetupError> {
+    let value = match env::var("MAX_JSON_BODY_BYTES") {
+        Err(VarError::NotPresent) => None,
+        v => Some(v.map_err(|e| SetupError::from_env_error(e, "MAX_JSON_BODY_BYTES"))?),
+    };
+    parse_max_json_body_bytes(value.as_deref())
+}
+
+/// Parse the maximum JSON request body size from the (optional) value of the environment variable
+/// `MAX_JSON_BODY_BYTES`
+fn parse_max_json_body_bytes(value: Option<&str>) -> Result<usize, SetupError> {
+    match value {
+        None => Ok(2 * 1024 * 1024),
+        Some(v) => match v.trim().parse() {
+            Ok(0) => Err(SetupError::EnvVariableInvalid {
+                variable_name: "MAX_JSON_BODY_BYTES",
+                problem: "must be greater than 0",
+            }),
+            Ok(limit) => Ok(limit),
+            Err(_) => Err(SetupError::EnvVariableInvalid {
+                variable_name: "MAX_JSON_BODY_BYTES",
+                problem: "Not a valid uint",
+            }),
+        },
+    }
+}
+
+/// Configuration of the database connection pool
+#[derive(Debug, PartialEq, Eq)]
+pub struct DbPoolConfig {
+    /// Maximum number of connections in the pool
+    pub max_size: u32,
+    /// Number of idle connections, the pool tries to maintain
+    pub min_idle: u32,
+}
+
+impl Default for DbPoolConfig {
+    fn default() -> Self {
+        Self {
+            max_size: 10,
+            min_idle: 2,
+        }
+    }
+}
+
+/// Get the database connection pool configuration from the environment variables
+/// `DB_POOL_MAX_SIZE` and `DB_POOL_MIN_IDLE`. Defaults to a maximum of 10 connections and 2 idle
+/// connections.
+pub fn get_db_pool_config_from_env() -> Result<DbPoolConfig, SetupError> {
+    let max_size = match env::var("DB_POOL_MAX_SIZE") {
+        Err(VarError::NotPresent) => None,
+        v => Some(v.map_err(|e| SetupError::from_env_error(e, "DB_POOL_MAX_SIZE"))?),
+    };
+    let min_idle = match env::var("DB_POOL_MIN_IDLE") {
+        Err(VarError::NotPresent) => None,
+        v => Some(v.map_err(|e| SetupError::from_env_error(e, "DB_POOL_MIN_IDLE"))?),
+    };
+    parse_db_pool_config(max_size.as_deref(), min_idle.as_deref())
+}
+
+/// Parse the database connection pool configuration from the (optional) values of the environment
+/// variables `DB_POOL_MAX_SIZE` and `DB_POOL_MIN_IDLE`, using the [DbPoolConfig::default] for
+/// missing values.
+fn parse_db_pool_config(
+    max_size: Option<&str>,
+    min_idle: Option<&str>,
+) -> Result<DbPoolConfig, SetupError> {
+    let default = DbPoolConfig::default();
+    let max_size = match max_size {
+        None => default.max_size,
+        Some(v) => match v.trim().parse() {
+            Ok(0) | Err(_) => {
+                return Err(SetupError::EnvVariableInvalid {
+                    variable_name: "DB_POOL_MAX_SIZE",
+                    problem: "Not a valid positive uint32",
+                });
+            }
+            Ok(v) => v,
+        },
+    };
+    let min_idle = match min_idle {
+        None => default.min_idle.min(max_size),
+        Some(v) => v
+            .trim()
+            .parse()
+            .map_err(|_| SetupError::EnvVariableInvalid {
+                variable_name: "DB_POOL_MIN_IDLE",
+                problem: "Not a valid uint32",
+            })?,
+    };
+    if max_size < min_idle {
+        return Err(SetupError::EnvVariableInvalid {
+            variable_name: "DB_POOL_MAX_SIZE",
+            problem: "Must not be smaller than DB_POOL_MIN_IDLE",
+        });
+    }
+    Ok(DbPoolConfig { max_size, min_idle })
+}
+
+/// Configuration of the security attributes of the session cookie
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionCookieConfig {
+    /// If true, the `Secure` attribute is set, such that the cookie is only sent via HTTPS
+    pub secure: bool,
+    pub same_site: actix_web::cookie::SameSite,
+    /// If given, the `Domain` attribute is set, such that the cookie is also sent to subdomains
+    pub domain: Option<String>,
+}
+
+impl Default for SessionCookieConfig {
+    fn default() -> Self {
+        Self {
+            secure: false,
+            same_site: actix_web::cookie::SameSite::Lax,
+            domain: None,
+        }
+    }
+}
+
+/// Get the session cookie configuration from the environment variables `COOKIE_SECURE`,
+/// `COOKIE_SAMESITE` ('lax' or 'strict') and `COOKIE_DOMAIN`. Defaults to a non-secure cookie with
+/// `SameSite=Lax` and without domain.
+pub fn get_session_cookie_config_from_env() -> Result<SessionCookieConfig, SetupError> {
+    let secure = match env::var("COOKIE_SECURE") {
+        Err(VarError::NotPresent) => None,
+        v => Some(v.map_err(|e| SetupError::from_env_error(e, "COOKIE_SECURE"))?),
+    };
+    let same_site = match env::var("COOKIE_SAMESITE") {
+        Err(VarError::NotPresent) => None,
+        v => Some(v.map_err(|e| SetupError::from_env_error(e, "COOKIE_SAMESITE"))?),
+    };
+    let domain = match env::var("COOKIE_DOMAIN") {
+        Err(VarError::NotPresent) => None,
+        v => Some(v.map_err(|e| SetupError::from_env_error(e, "COOKIE_DOMAIN"))?),
+    };
+    parse_session_cookie_config(secure.as_deref(), same_site.as_deref(), domain.as_deref())
+}
+
+/// Parse the session cookie configuration from the (optional) values of the environment variables
+/// `COOKIE_SECURE`, `COOKIE_SAMESITE` and `COOKIE_DOMAIN`, using the
+/// [SessionCookieConfig::default] for missing values.
+fn parse_session_cookie_config(
+    secure: Option<&str>,
+    same_site: Option<&str>,
+    domain: Option<&str>,
+) -> Result<SessionCookieConfig, SetupError> {
+    let default = SessionCookieConfig::default();
+    let secure = match secure.map(|v| v.trim().to_lowercase()) {
+        None => default.secure,
+        Some(v) if TRUE_ENV_VALUES.contains(&v.as_str()) => true,
+        Some(v) if ["", "0", "off", "false", "no"].contains(&v.as_str()) => false,
+        Some(_) => {
+            return Err(SetupError::EnvVariableInvalid {
+                variable_name: "COOKIE_SECURE",
+                problem: "Not a valid boolean value",
+            });
+        }
+    };
+    let same_site = match same_site.map(|v| v.trim().to_lowercase()) {
+        None => default.same_site,
+        Some(v) if v == "lax" => actix_web::cookie::SameSite::Lax,
+        Some(v) if v == "strict" => actix_web::cookie::SameSite::Strict,
+        Some(_) => {
+            return Err(SetupError::EnvVariableInvalid {
+                variable_name: "COOKIE_SAMESITE",
+                problem: "Must be 'lax' or 'strict'",
+            });
+        }
+    };
+    let domain = domain
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .map(|v| v.to_owned());
+    Ok(SessionCookieConfig {
+        secure,
+        same_site,
+        domain,
+    })
 }
 
 #[derive(Debug)]
@@ -82,3 +396,144 @@ impl Display for SetupError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_db_pool_config() {
+        assert_eq!(
+            parse_db_pool_config(None, None).unwrap(),
+            DbPoolConfig {
+                max_size: 10,
+                min_idle: 2
+            }
+        );
+        assert_eq!(
+            parse_db_pool_config(Some("20"), Some("5")).unwrap(),
+            DbPoolConfig {
+                max_size: 20,
+                min_idle: 5
+            }
+        );
+        assert_eq!(
+            parse_db_pool_config(Some("4"), Some("4")).unwrap(),
+            DbPoolConfig {
+                max_size: 4,
+                min_idle: 4
+            }
+        );
+        // Default min_idle is reduced to a smaller max_size
+        assert_eq!(
+            parse_db_pool_config(Some("1"), None).unwrap(),
+            DbPoolConfig {
+                max_size: 1,
+                min_idle: 1
+            }
+        );
+        assert!(matches!(
+            parse_db_pool_config(Some("3"), Some("5")),
+            Err(SetupError::EnvVariableInvalid {
+                variable_name: "DB_POOL_MAX_SIZE",
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse_db_pool_config(None, Some("11")),
+            Err(SetupError::EnvVariableInvalid {
+                variable_name: "DB_POOL_MAX_SIZE",
+                ..
+            })
+        ));
+        assert!(parse_db_pool_config(Some("0"), Some("0")).is_err());
+        assert!(parse_db_pool_config(Some("many"), None).is_err());
+        assert!(parse_db_pool_config(None, Some("-1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_shutdown_timeout() {
+        assert_eq!(
+            parse_shutdown_timeout(None).unwrap(),
+            std::time::Duration::from_secs(30)
+        );
+        assert_eq!(
+            parse_shutdown_timeout(Some("5")).unwrap(),
+            std::time::Duration::from_secs(5)
+        );
+        assert_eq!(
+            parse_shutdown_timeout(Some(" 120 ")).unwrap(),
+            std::time::Duration::from_secs(120)
+        );
+        // Zero means: abort all in-flight requests immediately
+        assert_eq!(
+            parse_shutdown_timeout(Some("0")).unwrap(),
+            std::time::Duration::ZERO
+        );
+        for invalid in ["", "-1", "2.5", "30s"] {
+            assert!(matches!(
+                parse_shutdown_timeout(Some(invalid)),
+                Err(SetupError::EnvVariableInvalid {
+                    variable_name: "SHUTDOWN_TIMEOUT_SECS",
+                    ..
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_max_json_body_bytes() {
+        assert_eq!(parse_max_json_body_bytes(None).unwrap(), 2_097_152);
+        assert_eq!(parse_max_json_body_bytes(Some("65536")).unwrap(), 65536);
+        assert_eq!(parse_max_json_body_bytes(Some(" 1024\n")).unwrap(), 1024);
+        for invalid in ["", "0", "-1", "1.5", "2MB"] {
+            assert!(matches!(
+                parse_max_json_body_bytes(Some(invalid)),
+                Err(SetupError::EnvVariableInvalid {
+                    variable_name: "MAX_JSON_BODY_BYTES",
+                    ..
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_session_cookie_config() {
+        assert_eq!(
+            parse_session_cookie_config(None, None, None).unwrap(),
+            SessionCookieConfig {
+                secure: false,
+                same_site: actix_web::cookie::SameSite::Lax,
+                domain: None,
+            }
+        );
+        assert_eq!(
+            parse_session_cookie_config(Some("yes"), Some(" Strict"), Some("example.com")).unwrap(),
+            SessionCookieConfig {
+                secure: true,
+                same_site: actix_web::cookie::SameSite::Strict,
+                domain: Some("example.com".to_owned()),
+            }
+        );
+        assert_eq!(
+            parse_session_cookie_config(Some("0"), Some("lax"), Some("")).unwrap(),
+            SessionCookieConfig::default()
+        );
+        assert!(matches!(
+            parse_session_cookie_config(Some("maybe"), None, None),
+            Err(SetupError::EnvVariableInvalid {
+                variable_name: "COOKIE_SECURE",
+                ..
+            })
+        ));
+        for invalid in ["none", "relaxed"] {
+            assert!(matches!(
+                parse_session_cookie_config(None, Some(invalid), None),
+                Err(SetupError::EnvVariableInvalid {
+                    variable_name: "COOKIE_SAMESITE",
+                    ..
+                })
+            ));
+        }
+    }
+}
@@ -1,12 +1,16 @@
+use crate::data_store::AnnouncementFilter;
 use crate::data_store::models::FullNewAnnouncement;
 use crate::web::AppState;
+use crate::web::api::endpoints_event_extended::current_programme_day;
 use crate::web::api::{APIError, SessionTokenHeader};
 use actix_web::{HttpResponse, Responder, delete, get, patch, put, web};
+use serde::Deserialize;
 use uuid::Uuid;
 
 #[get("/events/{event_id}/announcements")]
 async fn list_announcements(
     path: web::Path<i32>,
+    query: web::Query<AnnouncementsQuery>,
     state: web::Data<AppState>,
     session_token_header: Option<web::Header<SessionTokenHeader>>,
 ) -> Result<impl Responder, APIError> {
@@ -14,12 +18,16 @@ async fn list_announcements(
     let session_token = session_token_header
         .ok_or(APIError::NoSessionToken)?
         .into_inner()
-        .session_token(&state.secret)?;
+        .session_token(&state.secrets)?;
     let announcements: Vec<kueaplan_api_types::Announcement> =
         web::block(move || -> Result<_, APIError> {
             let mut store = state.store.get_facade()?;
             let auth = store.get_auth_token_for_session(&session_token, event_id)?;
-            Ok(store.get_announcements(&auth, event_id, None)?)
+            Ok(store.get_announcements(
+                &auth,
+                event_id,
+                query.visible_at.map(AnnouncementFilter::ForDateTime),
+            )?)
         })
         .await??
         .into_iter()
@@ -29,6 +37,70 @@ async fn list_announcements(
     Ok(web::Json(announcements))
 }
 
+#[derive(Deserialize)]
+struct AnnouncementsQuery {
+    #[serde(rename = "visibleAt")]
+    visible_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[get("/events/{event_id}/announcements/active")]
+async fn list_active_announcements(
+    path: web::Path<i32>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let event_id = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let announcements: Vec<kueaplan_api_types::Announcement> =
+        web::block(move || -> Result<_, APIError> {
+            let mut store = state.store.get_facade()?;
+            let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+            let event = store.get_extended_event(&auth, event_id)?;
+            // Within the event, time windows of announcements are taken into account. Before and
+            // after the event, the announcements of its first or last day are shown for the whole
+            // day.
+            let now = chrono::Utc::now();
+            let today = current_programme_day(&event)?;
+            let filter = if today.begin <= now && now < today.end {
+                AnnouncementFilter::ForDateTime(now)
+            } else {
+                AnnouncementFilter::ForDate(today.date)
+            };
+            Ok(store.get_announcements(&auth, event_id, Some(filter))?)
+        })
+        .await??
+        .into_iter()
+        .map(|e| e.into())
+        .collect();
+
+    Ok(web::Json(announcements))
+}
+
+#[get("/events/{event_id}/announcements/{announcement_id}")]
+async fn get_announcement(
+    path: web::Path<(i32, Uuid)>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let (event_id, announcement_id) = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let announcement: kueaplan_api_types::Announcement =
+        web::block(move || -> Result<_, APIError> {
+            let mut store = state.store.get_facade()?;
+            let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+            Ok(store.get_announcement(&auth, event_id, announcement_id)?)
+        })
+        .await??
+        .into();
+    Ok(web::Json(announcement))
+}
+
 #[put("/events/{event_id}/announcements/{announcement_id}")]
 async fn create_or_update_announcement(
     path: web::Path<(i32, Uuid)>,
@@ -40,7 +112,7 @@ async fn create_or_update_announcement(
     let session_token = session_token_header
         .ok_or(APIError::NoSessionToken)?
         .into_inner()
-        .session_token(&state.secret)?;
+        .session_token(&state.secrets)?;
     let announcement = data.into_inner();
     if announcement_id != announcement.id {
         return Err(APIError::EntityIdMissmatch);
@@ -74,12 +146,18 @@ async fn change_announcement(
     let session_token = session_token_header
         .ok_or(APIError::NoSessionToken)?
         .into_inner()
-        .session_token(&state.secret)?;
+        .session_token(&state.secrets)?;
     let announcement = data.into_inner();
+    let expected_last_update = announcement.expected_last_update;
     web::block(move || -> Result<_, APIError> {
         let mut store = state.store.get_facade()?;
         let auth = store.get_auth_token_for_session(&session_token, event_id)?;
-        Ok(store.patch_announcement(&auth, announcement_id, announcement.into())?)
+        Ok(store.patch_announcement(
+            &auth,
+            announcement_id,
+            announcement.into(),
+            expected_last_update,
+        )?)
     })
     .await??;
 
@@ -96,7 +174,7 @@ async fn delete_announcement(
     let session_token = session_token_header
         .ok_or(APIError::NoSessionToken)?
         .into_inner()
-        .session_token(&state.secret)?;
+        .session_token(&state.secrets)?;
     // TODO allow replacing announcement
     web::block(move || -> Result<_, APIError> {
         let mut store = state.store.get_facade()?;
@@ -1,8 +1,9 @@
 use crate::auth_session::SessionToken;
 use crate::data_store::StoreError;
+use crate::data_store::auth_token::Privilege;
 use crate::web::AppState;
 use crate::web::api::{APIError, SessionTokenHeader};
-use actix_web::{Responder, get, post, web};
+use actix_web::{HttpRequest, Responder, get, post, web};
 use kueaplan_api_types::{
     AllEventsAuthorizationInfo, Authorization, AuthorizationInfo, AuthorizationRole,
 };
@@ -14,7 +15,7 @@ async fn check_all_events_authorization(
     session_token_header: Option<web::Header<SessionTokenHeader>>,
 ) -> Result<impl Responder, APIError> {
     let session_token = session_token_header
-        .map(|token_header| token_header.into_inner().session_token(&state.secret))
+        .map(|token_header| token_header.into_inner().session_token(&state.secrets))
         .transpose()?;
     let mut raw_authorization_list = if let Some(token) = session_token {
         web::block(move || -> Result<_, APIError> {
@@ -57,7 +58,7 @@ async fn check_authorization(
 ) -> Result<impl Responder, APIError> {
     let event_id = path.into_inner();
     let session_token = session_token_header
-        .map(|token_header| token_header.into_inner().session_token(&state.secret))
+        .map(|token_header| token_header.into_inner().session_token(&state.secrets))
         .transpose()?;
     let authorization: Vec<kueaplan_api_types::Authorization> = if let Some(token) = session_token {
         web::block(move || -> Result<_, APIError> {
@@ -94,14 +95,25 @@ async fn authorize(
     body: web::Json<AuthorizeRequest>,
     state: web::Data<AppState>,
     session_token_header: Option<web::Header<SessionTokenHeader>>,
+    req: HttpRequest,
 ) -> Result<impl Responder, APIError> {
     let event_id = path.into_inner();
     let session_token = session_token_header
-        .map(|token_header| token_header.into_inner().session_token(&state.secret))
+        .map(|token_header| token_header.into_inner().session_token(&state.secrets))
         .transpose()?
         .unwrap_or_else(SessionToken::new);
+    let client_ip = state.auth_rate_limiter.client_ip_address(&req);
+    if let Some(ip) = client_ip {
+        state
+            .auth_rate_limiter
+            .check_and_record_attempt(ip)
+            .map_err(|retry_after| APIError::AuthenticationFailed {
+                passphrase_expired: false,
+                retry_after: Some(retry_after),
+            })?;
+    }
     let store = state.store.clone();
-    let (authorization, session_token) = {
+    let result = {
         web::block(move || -> Result<_, APIError> {
             let mut session_token = session_token;
             let mut store = store.get_facade()?;
@@ -110,23 +122,31 @@ async fn authorize(
                 .map_err(|e| match e {
                     StoreError::NotExisting => APIError::AuthenticationFailed {
                         passphrase_expired: false,
+                        retry_after: None,
                     },
                     StoreError::NotValid => APIError::AuthenticationFailed {
                         passphrase_expired: true,
+                        retry_after: None,
                     },
                     e => e.into(),
                 })?;
             let auth = store.get_auth_token_for_session(&session_token, event_id)?;
             Ok((auth.list_api_access_roles(), session_token))
         })
-        .await??
+        .await?
     };
+    if let Some(ip) = client_ip
+        && result.is_ok()
+    {
+        state.auth_rate_limiter.reset(ip);
+    }
+    let (authorization, session_token) = result?;
     Ok(web::Json(AuthorizeResponse {
         authorization_info: AuthorizationInfo {
             event_id,
             authorization,
         },
-        session_token: session_token.as_string(&state.secret),
+        session_token: session_token.as_string(state.signing_secret()),
     }))
 }
 
@@ -144,7 +164,7 @@ async fn drop_access_role(
 ) -> Result<impl Responder, APIError> {
     let event_id = path.into_inner();
     let session_token = session_token_header
-        .map(|token_header| token_header.into_inner().session_token(&state.secret))
+        .map(|token_header| token_header.into_inner().session_token(&state.secrets))
         .transpose()?
         .unwrap_or_else(SessionToken::new);
     let store = state.store.clone();
@@ -163,6 +183,63 @@ async fn drop_access_role(
             event_id,
             authorization,
         },
-        session_token: session_token.as_string(&state.secret),
+        session_token: session_token.as_string(state.signing_secret()),
+    }))
+}
+
+#[derive(Serialize)]
+struct ShareLinkResponse {
+    #[serde(rename = "sessionToken")]
+    session_token: String,
+    url: String,
+}
+
+/// Create a session token for sharing read-only access to the KüA-Plan, e.g. with calendar apps.
+///
+/// The token is only authenticated for a single passphrase with the sharable link role, which is
+/// derived from one of the client's passphrases. Thus, it never carries any of the client's further
+/// privileges.
+#[post("/events/{event_id}/shareLink")]
+async fn create_share_link(
+    path: web::Path<i32>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+    req: HttpRequest,
+) -> Result<impl Responder, APIError> {
+    let event_id = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let store = state.store.clone();
+    let shareable_session_token = web::block(move || -> Result<_, APIError> {
+        let mut store = store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        auth.check_privilege(event_id, Privilege::ShowKueaPlan)?;
+        store
+            .create_reduced_session_token(&session_token, event_id, Privilege::ShowKueaPlanViaLink)
+            .map_err(|e| match e {
+                StoreError::NotExisting => APIError::PermissionDenied {
+                    required_privilege: Privilege::ShowKueaPlanViaLink,
+                    privilege_expired: false,
+                },
+                e => e.into(),
+            })
+    })
+    .await??
+    .as_string(state.signing_secret());
+
+    let mut url = req
+        .url_for("ical", [event_id.to_string()])
+        .map_err(|e| APIError::InternalError(e.to_string()))?;
+    url.set_query(Some(
+        &serde_urlencoded::to_string(crate::web::ical::ICalQueryParams::with_session_token(
+            shareable_session_token.clone(),
+        ))
+        .map_err(|e| APIError::InternalError(e.to_string()))?,
+    ));
+    Ok(web::Json(ShareLinkResponse {
+        session_token: shareable_session_token,
+        url: url.to_string(),
     }))
 }
@@ -1,7 +1,8 @@
-use crate::data_store::models::NewCategory;
+use crate::data_store::models::{EntryAuditAction, NewCategory};
 use crate::web::AppState;
 use crate::web::api::{APIError, SessionTokenHeader};
-use actix_web::{HttpResponse, Responder, delete, get, put, web};
+use crate::web::ui::validation::IconName;
+use actix_web::{HttpResponse, Responder, delete, get, post, put, web};
 use serde::Deserialize;
 use uuid::Uuid;
 
@@ -15,7 +16,7 @@ async fn list_categories(
     let session_token = session_token_header
         .ok_or(APIError::NoSessionToken)?
         .into_inner()
-        .session_token(&state.secret)?;
+        .session_token(&state.secrets)?;
     let categories: Vec<kueaplan_api_types::Category> =
         web::block(move || -> Result<_, APIError> {
             let mut store = state.store.get_facade()?;
@@ -30,6 +31,60 @@ async fn list_categories(
     Ok(web::Json(categories))
 }
 
+#[get("/events/{event_id}/categories/stats")]
+async fn list_categories_with_entry_counts(
+    path: web::Path<i32>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let event_id = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let categories: Vec<kueaplan_api_types::CategoryWithEntryCount> =
+        web::block(move || -> Result<_, APIError> {
+            let mut store = state.store.get_facade()?;
+            let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+            Ok(store.get_category_entry_counts(&auth, event_id)?)
+        })
+        .await??
+        .into_iter()
+        .map(
+            |(category, entry_count)| kueaplan_api_types::CategoryWithEntryCount {
+                category: category.into(),
+                entry_count,
+            },
+        )
+        .collect();
+
+    Ok(web::Json(categories))
+}
+
+#[put("/events/{event_id}/categories/order")]
+async fn reorder_categories(
+    path: web::Path<i32>,
+    data: web::Json<Vec<Uuid>>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let event_id = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let ordered_ids = data.into_inner();
+    web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        store.reorder_categories(&auth, event_id, ordered_ids)?;
+        Ok(())
+    })
+    .await??;
+
+    Ok(HttpResponse::NoContent())
+}
+
 #[put("/events/{event_id}/categories/{category_id}")]
 async fn create_or_update_category(
     path: web::Path<(i32, Uuid)>,
@@ -41,11 +96,16 @@ async fn create_or_update_category(
     let session_token = session_token_header
         .ok_or(APIError::NoSessionToken)?
         .into_inner()
-        .session_token(&state.secret)?;
+        .session_token(&state.secrets)?;
     let category = data.into_inner();
     if category_id != category.id {
         return Err(APIError::EntityIdMissmatch);
     }
+    if !IconName::is_valid(&category.icon) {
+        return Err(APIError::InvalidData(
+            "Category icon must be empty or a single emoji or symbol.".to_owned(),
+        ));
+    }
     let created = web::block(move || -> Result<_, APIError> {
         let mut store = state.store.get_facade()?;
         let auth = store.get_auth_token_for_session(&session_token, event_id)?;
@@ -71,7 +131,7 @@ async fn delete_category(
     let session_token = session_token_header
         .ok_or(APIError::NoSessionToken)?
         .into_inner()
-        .session_token(&state.secret)?;
+        .session_token(&state.secrets)?;
     let data = data.map(web::Json::<_>::into_inner);
     web::block(move || -> Result<_, APIError> {
         let mut store = state.store.get_facade()?;
@@ -90,6 +150,106 @@ async fn delete_category(
     Ok(HttpResponse::NoContent())
 }
 
+#[get("/events/{event_id}/categories/deleted")]
+async fn list_deleted_categories(
+    path: web::Path<i32>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let event_id = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let categories: Vec<kueaplan_api_types::Category> =
+        web::block(move || -> Result<_, APIError> {
+            let mut store = state.store.get_facade()?;
+            let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+            Ok(store.get_deleted_categories(&auth, event_id)?)
+        })
+        .await??
+        .into_iter()
+        .map(|e| e.into())
+        .collect();
+
+    Ok(web::Json(categories))
+}
+
+#[post("/events/{event_id}/categories/{category_id}/restore")]
+async fn restore_category(
+    path: web::Path<(i32, Uuid)>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let (event_id, category_id) = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        store.restore_category(&auth, event_id, category_id)?;
+        Ok(())
+    })
+    .await??;
+
+    Ok(HttpResponse::NoContent())
+}
+
+#[post("/events/{event_id}/categories/{category_id}/cancelEntries")]
+async fn cancel_category_entries(
+    path: web::Path<(i32, Uuid)>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    set_category_entries_cancelled(path, state, session_token_header, true).await
+}
+
+#[post("/events/{event_id}/categories/{category_id}/uncancelEntries")]
+async fn uncancel_category_entries(
+    path: web::Path<(i32, Uuid)>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    set_category_entries_cancelled(path, state, session_token_header, false).await
+}
+
+/// Common implementation of [cancel_category_entries] and [uncancel_category_entries]
+async fn set_category_entries_cancelled(
+    path: web::Path<(i32, Uuid)>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+    cancelled: bool,
+) -> Result<web::Json<kueaplan_api_types::BulkUpdateResult>, APIError> {
+    let (event_id, category_id) = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let entry_ids = web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        let entry_ids =
+            store.cancel_entries_in_category(&auth, event_id, category_id, cancelled)?;
+        let public_entry_ids = store.get_public_entry_ids(&auth, event_id, &entry_ids)?;
+        for entry_id in entry_ids.iter() {
+            state.live_updates.publish_entry_change(
+                event_id,
+                *entry_id,
+                EntryAuditAction::Update,
+                public_entry_ids.contains(entry_id),
+            );
+        }
+        Ok(entry_ids)
+    })
+    .await??;
+
+    Ok(web::Json(kueaplan_api_types::BulkUpdateResult {
+        entry_count: entry_ids.len(),
+    }))
+}
+
 #[derive(Deserialize)]
 struct DeleteCategoryBody {
     #[serde(default, rename = "replaceCategory")]
@@ -1,10 +1,24 @@
-use crate::data_store::models::{EntryState, FullNewEntry, NewEntry};
+use crate::data_store::models::{
+    EntryAuditAction, EntryLink, EntryState, FullEntry, FullNewEntry, FullPreviousDate, NewEntry,
+};
+use crate::data_store::{EntryFilter, StoreError};
 use crate::web::AppState;
 use crate::web::api::{APIError, SessionTokenHeader};
-use crate::web::util::{EntryFilterAsQuery, format_submitter_comment};
-use actix_web::{HttpResponse, Responder, delete, get, patch, post, put, web};
+use crate::web::time_calculation::{
+    get_effective_date, is_within_event_dates, schedule_section_for_timestamp,
+};
+use crate::web::util::{
+    EntryFilterAsQuery, MAX_ENTRY_TITLE_LENGTH, deserialize_bool_from_string,
+    format_submitter_comment,
+};
+use actix_web::http::header::{
+    self, Accept, ContentDisposition, DispositionParam, DispositionType,
+};
+use actix_web::web::Bytes;
+use actix_web::{HttpResponse, Responder, delete, get, mime, patch, post, put, web};
+use futures_util::{Stream, StreamExt, stream};
 use serde::de::{Error, Unexpected};
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize};
 use uuid::Uuid;
 
 #[get("/events/{event_id}/entries")]
@@ -13,23 +27,166 @@ async fn list_entries(
     query: web::Query<EntryFilterAsQuery>,
     state: web::Data<AppState>,
     session_token_header: Option<web::Header<SessionTokenHeader>>,
+    accept: Option<web::Header<Accept>>,
+) -> Result<HttpResponse, APIError> {
+    let event_id = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    if accept.is_some_and(|accept| prefers_icalendar(&accept)) {
+        let (event, entries, rooms, categories) = web::block(move || -> Result<_, APIError> {
+            let mut store = state.store.get_facade()?;
+            let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+            Ok((
+                store.get_event(event_id)?,
+                store.get_published_entries_filtered(&auth, event_id, query.into_inner().into())?,
+                store.get_rooms(&auth, event_id)?,
+                store.get_categories(&auth, event_id)?,
+            ))
+        })
+        .await??;
+        return Ok(HttpResponse::Ok()
+            .content_type("text/calendar; charset=utf-8")
+            .append_header((header::VARY, "Accept"))
+            .append_header(ContentDisposition {
+                disposition: DispositionType::Inline,
+                parameters: vec![DispositionParam::Filename(String::from("kueaplan.ics"))],
+            })
+            .body(crate::web::ical::entries_to_ics(
+                event, entries, rooms, categories, None,
+            )));
+    }
+
+    // The entry list may be very large, so we load the entries from the database in chunks and
+    // convert and serialize them chunk by chunk while streaming the response, instead of building
+    // the full list and JSON document in memory. The bounded channel makes the database thread
+    // wait for the client to consume the response.
+    let (sender, mut receiver) = tokio::sync::mpsc::channel(1);
+    actix_web::rt::task::spawn_blocking(move || {
+        let result = (|| -> Result<(), APIError> {
+            let mut store = state.store.get_facade()?;
+            let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+            store.for_each_published_entries_chunk(
+                &auth,
+                event_id,
+                query.into_inner().into(),
+                ENTRY_STREAM_CHUNK_SIZE,
+                &mut |chunk| {
+                    let chunk: Vec<kueaplan_api_types::Entry> =
+                        chunk.into_iter().map(|e| e.into()).collect();
+                    // Stop loading further chunks, when the client has gone away
+                    sender.blocking_send(Ok(chunk)).is_ok()
+                },
+            )?;
+            Ok(())
+        })();
+        if let Err(e) = result {
+            let _ = sender.blocking_send(Err(e));
+        }
+    });
+
+    // Errors before the first chunk (e.g. missing privileges) result in a normal error response.
+    // Later errors can only abort the response.
+    let first_chunk = match receiver.recv().await {
+        Some(Err(e)) => return Err(e),
+        first_chunk => first_chunk,
+    };
+    let chunks = stream::iter(first_chunk)
+        .chain(stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|chunk| (chunk, receiver))
+        }));
+    // The response format depends on the Accept header, so caches must not mix them up
+    Ok(HttpResponse::Ok()
+        .content_type(mime::APPLICATION_JSON)
+        .append_header((header::VARY, "Accept"))
+        .streaming(json_array_stream(chunks)))
+}
+
+/// Number of entries to load from the database at once for streaming entry lists
+const ENTRY_STREAM_CHUNK_SIZE: usize = 500;
+
+/// Create a stream of JSON chunks from the given stream of item chunks, which form a JSON array
+/// with the same content as `serde_json::to_vec()` of all items. Each chunk of items is serialized
+/// only when the respective part of the stream is polled.
+fn json_array_stream<S, T>(
+    chunks: S,
+) -> impl Stream<Item = Result<Bytes, actix_web::Error>> + 'static
+where
+    S: Stream<Item = Result<Vec<T>, APIError>> + 'static,
+    T: Serialize,
+{
+    let mut is_first = true;
+    let elements = chunks.map(move |chunk| -> Result<Bytes, actix_web::Error> {
+        let mut buffer = Vec::new();
+        for item in chunk? {
+            if !is_first {
+                buffer.push(b',');
+            }
+            is_first = false;
+            serde_json::to_writer(&mut buffer, &item)
+                .map_err(|e| APIError::InternalError(e.to_string()))?;
+        }
+        Ok(Bytes::from(buffer))
+    });
+    stream::once(async { Ok(Bytes::from_static(b"[")) })
+        .chain(elements)
+        .chain(stream::once(async { Ok(Bytes::from_static(b"]")) }))
+}
+
+/// Check if the client prefers an iCalendar document over JSON, according to the given `Accept`
+/// header. Wildcards are treated as a preference for JSON, which is our default format.
+fn prefers_icalendar(accept: &Accept) -> bool {
+    accept
+        .ranked()
+        .into_iter()
+        .find(|mime_type| {
+            mime_type.essence_str() == "text/calendar"
+                || mime_type.subtype() == mime::JSON
+                || mime_type.subtype() == mime::STAR
+        })
+        .is_some_and(|mime_type| mime_type.essence_str() == "text/calendar")
+}
+
+/// List the (published) entries of the event like [list_entries], together with the name of the
+/// section of the event's default time schedule, which each entry begins in, such that clients can
+/// group the entries by schedule section.
+#[get("/events/{event_id}/entries/withScheduleSections")]
+async fn list_entries_with_schedule_sections(
+    path: web::Path<i32>,
+    query: web::Query<EntryFilterAsQuery>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
 ) -> Result<impl Responder, APIError> {
     let event_id = path.into_inner();
     let session_token = session_token_header
         .ok_or(APIError::NoSessionToken)?
         .into_inner()
-        .session_token(&state.secret)?;
-    let entries: Vec<kueaplan_api_types::Entry> = web::block(move || -> Result<_, APIError> {
+        .session_token(&state.secrets)?;
+    let (event, entries) = web::block(move || -> Result<_, APIError> {
         let mut store = state.store.get_facade()?;
         let auth = store.get_auth_token_for_session(&session_token, event_id)?;
-        Ok(store.get_published_entries_filtered(&auth, event_id, query.into_inner().into())?)
+        Ok((
+            store.get_extended_event(&auth, event_id)?,
+            store.get_published_entries_filtered(&auth, event_id, query.into_inner().into())?,
+        ))
     })
-    .await??
-    .into_iter()
-    .map(|e| e.into())
-    .collect();
+    .await??;
 
-    Ok(web::Json(entries))
+    let result: Vec<kueaplan_api_types::EntryWithScheduleSection> = entries
+        .into_iter()
+        .map(|entry| kueaplan_api_types::EntryWithScheduleSection {
+            schedule_section: schedule_section_for_timestamp(
+                &entry.entry.begin,
+                get_effective_date(&entry.entry.begin, &event.clock_info),
+                &event.default_time_schedule,
+                &event.clock_info,
+            )
+            .map(|section| section.name.clone()),
+            entry: entry.into(),
+        })
+        .collect();
+    Ok(web::Json(result))
 }
 
 #[get("/events/{event_id}/allEntries")]
@@ -43,7 +200,7 @@ async fn list_all_entries(
     let session_token = session_token_header
         .ok_or(APIError::NoSessionToken)?
         .into_inner()
-        .session_token(&state.secret)?;
+        .session_token(&state.secrets)?;
     let query_data = query.into_inner();
     let filter = query_data.generic_filter.into();
     let states_filter = query_data
@@ -63,6 +220,240 @@ async fn list_all_entries(
     Ok(web::Json(entries))
 }
 
+#[get("/events/{event_id}/entries/current")]
+async fn list_current_entries(
+    path: web::Path<i32>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let event_id = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let filter = current_entries_filter(chrono::Utc::now());
+    let entries: Vec<kueaplan_api_types::Entry> = web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        Ok(store.get_published_entries_filtered(&auth, event_id, filter)?)
+    })
+    .await??
+    .into_iter()
+    .map(|e| e.into())
+    .collect();
+
+    Ok(web::Json(entries))
+}
+
+/// Build the EntryFilter for selecting all entries, which are running at the given point in time
+/// `now`, i.e. `begin <= now < end`.
+fn current_entries_filter(now: chrono::DateTime<chrono::Utc>) -> EntryFilter {
+    EntryFilter::builder()
+        .after(now, false)
+        .before(now, true)
+        .build()
+}
+
+#[get("/events/{event_id}/rooms/{room_id}/entries")]
+async fn list_room_entries(
+    path: web::Path<(i32, Uuid)>,
+    query: web::Query<RoomEntriesQuery>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let (event_id, room_id) = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let filter = room_entries_filter(room_id, query.into_inner());
+    let entries: Vec<kueaplan_api_types::Entry> = web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        if !store
+            .get_rooms(&auth, event_id)?
+            .iter()
+            .any(|room| room.id == room_id)
+        {
+            return Err(APIError::NotExisting);
+        }
+        Ok(store.get_published_entries_filtered(&auth, event_id, filter)?)
+    })
+    .await??
+    .into_iter()
+    .map(|e| e.into())
+    .collect();
+
+    Ok(web::Json(entries))
+}
+
+#[derive(Deserialize, Default)]
+struct RoomEntriesQuery {
+    #[serde(default)]
+    after: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    before: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default, deserialize_with = "deserialize_bool_from_string")]
+    include_previous: bool,
+}
+
+/// Build the EntryFilter for selecting the entries in the given room, which overlap with the time
+/// window from the query, i.e. `begin < before` and `end > after`.
+fn room_entries_filter(room_id: Uuid, query: RoomEntriesQuery) -> EntryFilter {
+    let mut builder = EntryFilter::builder().in_one_of_these_rooms(vec![room_id]);
+    if let Some(after) = query.after {
+        builder = builder.after(after, false);
+    }
+    if let Some(before) = query.before {
+        builder = builder.before(before, false);
+    }
+    if query.include_previous {
+        builder = builder.include_previous_date_matches();
+    }
+    builder.build()
+}
+
+#[get("/events/{event_id}/responsiblePersons")]
+async fn list_responsible_persons(
+    path: web::Path<i32>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let event_id = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let responsible_persons = web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        Ok(store.get_distinct_responsible_persons(&auth, event_id)?)
+    })
+    .await??;
+
+    Ok(web::Json(responsible_persons))
+}
+
+#[post("/events/{event_id}/reassignResponsible")]
+async fn reassign_responsible_person(
+    path: web::Path<i32>,
+    data: web::Json<kueaplan_api_types::ResponsiblePersonReassignment>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let event_id = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let reassignment = data.into_inner();
+    let entry_ids = web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        let entry_ids = store.reassign_responsible_person(
+            &auth,
+            event_id,
+            &reassignment.from,
+            &reassignment.to,
+        )?;
+        let public_entry_ids = store.get_public_entry_ids(&auth, event_id, &entry_ids)?;
+        for entry_id in entry_ids.iter() {
+            state.live_updates.publish_entry_change(
+                event_id,
+                *entry_id,
+                EntryAuditAction::Update,
+                public_entry_ids.contains(entry_id),
+            );
+        }
+        Ok(entry_ids)
+    })
+    .await??;
+
+    Ok(web::Json(kueaplan_api_types::BulkUpdateResult {
+        entry_count: entry_ids.len(),
+    }))
+}
+
+#[post("/events/{event_id}/entries/bulkCategory")]
+async fn bulk_set_entry_category(
+    path: web::Path<i32>,
+    data: web::Json<kueaplan_api_types::EntryCategoryBulkUpdate>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let event_id = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let update = data.into_inner();
+    let entry_ids = web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        let entry_ids =
+            store.bulk_set_category(&auth, event_id, &update.entry_ids, update.category)?;
+        let public_entry_ids = store.get_public_entry_ids(&auth, event_id, &entry_ids)?;
+        for entry_id in entry_ids.iter() {
+            state.live_updates.publish_entry_change(
+                event_id,
+                *entry_id,
+                EntryAuditAction::Update,
+                public_entry_ids.contains(entry_id),
+            );
+        }
+        Ok(entry_ids)
+    })
+    .await??;
+
+    Ok(web::Json(kueaplan_api_types::BulkUpdateResult {
+        entry_count: entry_ids.len(),
+    }))
+}
+
+#[post("/events/{event_id}/entries/shift")]
+async fn shift_entries(
+    path: web::Path<i32>,
+    query: web::Query<EntryFilterAsQuery>,
+    data: web::Json<kueaplan_api_types::EntryShift>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let event_id = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let shift = data.into_inner();
+    let offset = chrono::Duration::try_minutes(shift.offset_minutes)
+        .ok_or_else(|| APIError::InvalidData("offsetMinutes is out of range".to_owned()))?;
+    let filter: EntryFilter = query.into_inner().into();
+    if !filter.has_criteria() && !shift.all {
+        return Err(APIError::InvalidQuery(
+            "No filter is given. Set `all` to shift all entries of the event.".to_owned(),
+        ));
+    }
+    let entry_ids = web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        let entry_ids = store.shift_entries(&auth, event_id, filter, offset, &shift.comment)?;
+        let public_entry_ids = store.get_public_entry_ids(&auth, event_id, &entry_ids)?;
+        for entry_id in entry_ids.iter() {
+            state.live_updates.publish_entry_change(
+                event_id,
+                *entry_id,
+                EntryAuditAction::Update,
+                public_entry_ids.contains(entry_id),
+            );
+        }
+        Ok(entry_ids)
+    })
+    .await??;
+
+    Ok(web::Json(kueaplan_api_types::BulkUpdateResult {
+        entry_count: entry_ids.len(),
+    }))
+}
+
 #[derive(Deserialize, Default)]
 pub struct AllEntriesQuery {
     #[serde(flatten)]
@@ -85,7 +476,7 @@ async fn get_entry(
     let session_token = session_token_header
         .ok_or(APIError::NoSessionToken)?
         .into_inner()
-        .session_token(&state.secret)?;
+        .session_token(&state.secrets)?;
     let entry: kueaplan_api_types::Entry = web::block(move || -> Result<_, APIError> {
         let mut store = state.store.get_facade()?;
         let auth = store.get_auth_token_for_session(&session_token, event_id)?;
@@ -96,9 +487,23 @@ async fn get_entry(
     Ok(web::Json(entry))
 }
 
+#[derive(Deserialize, Default)]
+pub struct CreateOrUpdateEntryQuery {
+    #[serde(default, rename = "allowOutsideRange")]
+    pub allow_outside_range: bool,
+    /// If true, a previous date with the stored begin, end and rooms of the entry is added when
+    /// they are changed by the update
+    #[serde(default, rename = "recordPreviousDate")]
+    pub record_previous_date: bool,
+    /// Comment of the previous date created via `record_previous_date`
+    #[serde(default, rename = "previousDateComment")]
+    pub previous_date_comment: String,
+}
+
 #[put("/events/{event_id}/entries/{entry_id}")]
 async fn create_or_update_entry(
     path: web::Path<(i32, Uuid)>,
+    query: web::Query<CreateOrUpdateEntryQuery>,
     data: web::Json<kueaplan_api_types::Entry>,
     state: web::Data<AppState>,
     session_token_header: Option<web::Header<SessionTokenHeader>>,
@@ -107,20 +512,69 @@ async fn create_or_update_entry(
     let session_token = session_token_header
         .ok_or(APIError::NoSessionToken)?
         .into_inner()
-        .session_token(&state.secret)?;
+        .session_token(&state.secrets)?;
     let entry = data.into_inner();
     if entry_id != entry.id {
         return Err(APIError::EntityIdMissmatch);
     }
+    if let Some(link) = entry
+        .links
+        .iter()
+        .find(|link| !EntryLink::is_valid_url(&link.url))
+    {
+        return Err(APIError::InvalidData(format!(
+            "Link URL '{}' is not a valid http(s) URL.",
+            link.url
+        )));
+    }
+    check_entry_title_length(&entry.title)?;
+    if let Some(color) = entry.color_override.as_ref() {
+        check_color_format(color)?;
+    }
+    let query = query.into_inner();
     let created = web::block(move || -> Result<_, APIError> {
         let mut store = state.store.get_facade()?;
         let auth = store.get_auth_token_for_session(&session_token, event_id)?;
-        Ok(store.create_or_update_entry(
+        if !query.allow_outside_range {
+            let event = store.get_extended_event(&auth, event_id)?;
+            if !is_within_event_dates(
+                &entry.begin,
+                &entry.end,
+                &event.basic_data,
+                &event.clock_info,
+            ) {
+                return Err(StoreError::InvalidInputData(
+                    "The entry must lie within the event's date range.".to_owned(),
+                )
+                .into());
+            }
+        }
+        let entry = FullNewEntry::from_api(entry, event_id);
+        // The change is public if the entry is public before (if existing) or after the update
+        let public = entry.entry.is_public()
+            || !store
+                .get_public_entry_ids(&auth, event_id, &[entry_id])?
+                .is_empty();
+        let created = store.create_or_update_entry(
             &auth,
-            FullNewEntry::from_api(entry, event_id),
+            entry,
             false,
+            query
+                .record_previous_date
+                .then_some(query.previous_date_comment),
             None, // TODO allow using E-Tag for conflict checking
-        )?)
+        )?;
+        state.live_updates.publish_entry_change(
+            event_id,
+            entry_id,
+            if created {
+                EntryAuditAction::Create
+            } else {
+                EntryAuditAction::Update
+            },
+            public,
+        );
+        Ok(created)
     })
     .await??;
 
@@ -142,12 +596,141 @@ async fn change_entry(
     let session_token = session_token_header
         .ok_or(APIError::NoSessionToken)?
         .into_inner()
-        .session_token(&state.secret)?;
+        .session_token(&state.secrets)?;
     let entry = data.into_inner();
+    if let Some(title) = entry.title.as_ref() {
+        check_entry_title_length(title)?;
+    }
     web::block(move || -> Result<_, APIError> {
         let mut store = state.store.get_facade()?;
         let auth = store.get_auth_token_for_session(&session_token, event_id)?;
-        Ok(store.patch_entry(&auth, entry_id, entry.into())?)
+        // The patch may change the state of the entry, so we need to check visibility before and
+        // after the change
+        let was_public = !store
+            .get_public_entry_ids(&auth, event_id, &[entry_id])?
+            .is_empty();
+        store.patch_entry(&auth, entry_id, entry.into())?;
+        let is_public = !store
+            .get_public_entry_ids(&auth, event_id, &[entry_id])?
+            .is_empty();
+        state.live_updates.publish_entry_change(
+            event_id,
+            entry_id,
+            EntryAuditAction::Update,
+            was_public || is_public,
+        );
+        Ok(())
+    })
+    .await??;
+
+    Ok(HttpResponse::NoContent())
+}
+
+/// Copy an entry of another event (or the same event) into this event. The source event must be
+/// given in the request body, since the client's authorization is checked per event.
+#[post("/events/{event_id}/entries/copyFrom/{entry_id}")]
+async fn copy_entry_from_event(
+    path: web::Path<(i32, Uuid)>,
+    data: web::Json<kueaplan_api_types::EntryCopy>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let (event_id, source_entry_id) = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let source = data.into_inner();
+    let new_entry: kueaplan_api_types::Entry = web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let source_auth =
+            store.get_auth_token_for_session(&session_token, source.source_event_id)?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        let new_entry_id = store.copy_entry_to_event(
+            &source_auth,
+            &auth,
+            source_entry_id,
+            event_id,
+            source.target_category,
+        )?;
+        let new_entry = store.get_entry(&auth, new_entry_id)?;
+        state.live_updates.publish_entry_change(
+            event_id,
+            new_entry_id,
+            EntryAuditAction::Create,
+            new_entry.entry.is_public(),
+        );
+        Ok(new_entry)
+    })
+    .await??
+    .into();
+
+    Ok(HttpResponse::Created().json(new_entry))
+}
+
+#[derive(Deserialize, Default)]
+pub struct MoveEntryQuery {
+    #[serde(default, rename = "allowOutsideRange")]
+    pub allow_outside_range: bool,
+}
+
+#[post("/events/{event_id}/entries/{entry_id}/move")]
+async fn move_entry(
+    path: web::Path<(i32, Uuid)>,
+    query: web::Query<MoveEntryQuery>,
+    data: web::Json<kueaplan_api_types::EntryMove>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let (event_id, entry_id) = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let target = data.into_inner();
+    web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        let target_auth =
+            store.get_auth_token_for_session(&session_token, target.target_event_id)?;
+        if !query.allow_outside_range {
+            let entry = store.get_entry(&auth, entry_id)?;
+            let target_event = store.get_extended_event(&target_auth, target.target_event_id)?;
+            if !is_within_event_dates(
+                &entry.entry.begin,
+                &entry.entry.end,
+                &target_event.basic_data,
+                &target_event.clock_info,
+            ) {
+                return Err(StoreError::InvalidInputData(
+                    "The entry must lie within the target event's date range.".to_owned(),
+                )
+                .into());
+            }
+        }
+        store.move_entry(
+            &auth,
+            &target_auth,
+            entry_id,
+            target.target_event_id,
+            target.target_category,
+        )?;
+        let public = !store
+            .get_public_entry_ids(&auth, event_id, &[entry_id])?
+            .is_empty();
+        state.live_updates.publish_entry_change(
+            event_id,
+            entry_id,
+            EntryAuditAction::Delete,
+            public,
+        );
+        state.live_updates.publish_entry_change(
+            target.target_event_id,
+            entry_id,
+            EntryAuditAction::Create,
+            public,
+        );
+        Ok(())
     })
     .await??;
 
@@ -165,8 +748,9 @@ async fn submit_entry(
     let session_token = session_token_header
         .ok_or(APIError::NoSessionToken)?
         .into_inner()
-        .session_token(&state.secret)?;
+        .session_token(&state.secrets)?;
     let submission = data.into_inner();
+    check_entry_title_length(&submission.title)?;
     let entry = FullNewEntry {
         entry: NewEntry {
             id: submission.id,
@@ -183,12 +767,17 @@ async fn submit_entry(
             room_comment: submission.room_comment,
             is_exclusive: false,
             is_cancelled: false,
+            is_tentative: false,
+            orga_only: false,
+            open_ended: false,
             state: if submission.publish_without_review {
                 EntryState::PreliminaryPublished
             } else {
                 EntryState::SubmittedForReview
             },
             orga_comment: format_submitter_comment(&submission.submitter_comment),
+            color_override: None,
+            links: None,
         },
         room_ids: submission.room,
         previous_dates: vec![],
@@ -196,13 +785,44 @@ async fn submit_entry(
     web::block(move || -> Result<_, APIError> {
         let mut store = state.store.get_facade()?;
         let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        let entry_id = entry.entry.id;
+        let public = entry.entry.is_public();
         store.submit_entry_by_participant(&auth, entry)?;
+        state.live_updates.publish_entry_change(
+            event_id,
+            entry_id,
+            EntryAuditAction::Create,
+            public,
+        );
         Ok(())
     })
     .await??;
     Ok(HttpResponse::Ok())
 }
 
+/// Check that the entry title is not longer than [MAX_ENTRY_TITLE_LENGTH] (Unicode) characters
+pub(super) fn check_entry_title_length(title: &str) -> Result<(), APIError> {
+    if title.chars().count() > MAX_ENTRY_TITLE_LENGTH {
+        return Err(APIError::InvalidData(format!(
+            "Entry title must not be longer than {} characters.",
+            MAX_ENTRY_TITLE_LENGTH
+        )));
+    }
+    Ok(())
+}
+
+/// Check that the given color is a hexadecimal RGB color code with six digits, as expected for
+/// entries' and entry templates' `colorOverride`
+pub(super) fn check_color_format(color: &str) -> Result<(), APIError> {
+    if color.len() != 6 || !color.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(APIError::InvalidFormat(format!(
+            "Color '{}' is not a six-digit hexadecimal RGB color code.",
+            color
+        )));
+    }
+    Ok(())
+}
+
 #[delete("/events/{event_id}/entries/{entry_id}")]
 async fn delete_entry(
     path: web::Path<(i32, Uuid)>,
@@ -213,11 +833,21 @@ async fn delete_entry(
     let session_token = session_token_header
         .ok_or(APIError::NoSessionToken)?
         .into_inner()
-        .session_token(&state.secret)?;
+        .session_token(&state.secrets)?;
     web::block(move || -> Result<_, APIError> {
         let mut store = state.store.get_facade()?;
         let auth = store.get_auth_token_for_session(&session_token, event_id)?;
         store.delete_entry(&auth, event_id, entry_id)?;
+        // Deleted entries keep their state, so we can still check whether they had been public
+        let public = !store
+            .get_public_entry_ids(&auth, event_id, &[entry_id])?
+            .is_empty();
+        state.live_updates.publish_entry_change(
+            event_id,
+            entry_id,
+            EntryAuditAction::Delete,
+            public,
+        );
         Ok(())
     })
     .await?
@@ -226,6 +856,95 @@ async fn delete_entry(
     Ok(HttpResponse::NoContent())
 }
 
+#[get("/events/{event_id}/entries/{entry_id}/auditLog")]
+async fn get_entry_audit_log(
+    path: web::Path<(i32, Uuid)>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let (event_id, entry_id) = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let records: Vec<kueaplan_api_types::EntryAuditLogRecord> =
+        web::block(move || -> Result<_, APIError> {
+            let mut store = state.store.get_facade()?;
+            let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+            Ok(store.get_entry_audit_log(&auth, event_id, entry_id)?)
+        })
+        .await??
+        .into_iter()
+        .map(|record| record.into())
+        .collect();
+    Ok(web::Json(records))
+}
+
+#[get("/events/{event_id}/entries/{entry_id}/changes")]
+async fn get_entry_changes(
+    path: web::Path<(i32, Uuid)>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let (event_id, entry_id) = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let entry = web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        Ok(store.get_entry(&auth, entry_id)?)
+    })
+    .await??;
+    if entry.entry.event_id != event_id {
+        return Err(APIError::NotExisting);
+    }
+
+    // The most recent previous date is the one created or changed last. The id only breaks ties
+    // of previous dates created in the same transaction.
+    match entry.previous_dates.iter().max_by_key(|previous_date| {
+        (
+            previous_date.previous_date.last_updated,
+            previous_date.previous_date.id,
+        )
+    }) {
+        Some(previous_date) => {
+            Ok(HttpResponse::Ok().json(diff_entry_vs_previous_date(&entry, previous_date)))
+        }
+        None => Ok(HttpResponse::NoContent().finish()),
+    }
+}
+
+/// Calculate the changes of the entry's begin, end and rooms in comparison to the given previous
+/// date of the entry.
+///
+/// Positive shifts mean that the entry takes place later than at the previous date. Added rooms
+/// are the entry's rooms which have not been used at the previous date; removed rooms are the
+/// previous date's rooms which are not used by the entry anymore.
+fn diff_entry_vs_previous_date(
+    entry: &FullEntry,
+    previous_date: &FullPreviousDate,
+) -> kueaplan_api_types::EntryChanges {
+    kueaplan_api_types::EntryChanges {
+        previous_date_id: previous_date.previous_date.id,
+        begin_shift_minutes: (entry.entry.begin - previous_date.previous_date.begin).num_minutes(),
+        end_shift_minutes: (entry.entry.end - previous_date.previous_date.end).num_minutes(),
+        added_rooms: entry
+            .room_ids
+            .iter()
+            .filter(|room_id| !previous_date.room_ids.contains(room_id))
+            .copied()
+            .collect(),
+        removed_rooms: previous_date
+            .room_ids
+            .iter()
+            .filter(|room_id| !entry.room_ids.contains(room_id))
+            .copied()
+            .collect(),
+    }
+}
+
 fn deserialize_optional_comma_separated_list_of_event_states<'de, D>(
     deserializer: D,
 ) -> Result<Option<Vec<kueaplan_api_types::EntryState>>, D::Error>
@@ -246,3 +965,209 @@ where
         })?;
     Ok(Some(result))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_entries_filter() {
+        let now: chrono::DateTime<chrono::Utc> = "2025-01-04 14:00:00+00:00".parse().unwrap();
+        let filter = current_entries_filter(now);
+        // Entries ending exactly now are not running anymore
+        assert_eq!(filter.after, Some(now));
+        assert!(!filter.after_inclusive);
+        // Entries beginning exactly now are running already
+        assert_eq!(filter.before, Some(now));
+        assert!(filter.before_inclusive);
+        assert!(!filter.include_previous_date_matches);
+        assert_eq!(filter.categories, None);
+        assert_eq!(filter.rooms, None);
+    }
+
+    #[test]
+    fn test_room_entries_filter() {
+        let room_id = Uuid::parse_str("01977478-9bd7-7a19-9b7a-c5b9f1e4e0c1").unwrap();
+        let after: chrono::DateTime<chrono::Utc> = "2025-01-04 08:00:00+00:00".parse().unwrap();
+        let before: chrono::DateTime<chrono::Utc> = "2025-01-05 04:30:00+00:00".parse().unwrap();
+        let filter = room_entries_filter(
+            room_id,
+            RoomEntriesQuery {
+                after: Some(after),
+                before: Some(before),
+                include_previous: false,
+            },
+        );
+        assert_eq!(filter.rooms, Some(vec![room_id]));
+        // Only entries overlapping with the time window are included
+        assert_eq!(filter.after, Some(after));
+        assert!(!filter.after_inclusive);
+        assert_eq!(filter.before, Some(before));
+        assert!(!filter.before_inclusive);
+        assert!(!filter.include_previous_date_matches);
+
+        let filter = room_entries_filter(
+            room_id,
+            RoomEntriesQuery {
+                include_previous: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(filter.rooms, Some(vec![room_id]));
+        assert_eq!(filter.after, None);
+        assert_eq!(filter.before, None);
+        assert!(filter.include_previous_date_matches);
+    }
+
+    #[test]
+    fn test_entry_filter_sort_query() {
+        use crate::data_store::EntryOrder;
+        let parse = |query: &str| -> Result<EntryFilter, _> {
+            web::Query::<EntryFilterAsQuery>::from_query(query).map(|q| q.into_inner().into())
+        };
+        assert_eq!(parse("").unwrap().order_by, EntryOrder::Begin);
+        assert_eq!(parse("sort=begin").unwrap().order_by, EntryOrder::Begin);
+        assert_eq!(parse("sort=title").unwrap().order_by, EntryOrder::Title);
+        assert_eq!(parse("sort=room").unwrap().order_by, EntryOrder::Room);
+        assert_eq!(
+            parse("sort=category&q=foo").unwrap().order_by,
+            EntryOrder::Category
+        );
+        assert!(parse("sort=duration").is_err());
+    }
+
+    #[test]
+    fn test_entry_filter_reservations_query() {
+        let parse = |query: &str| -> EntryFilterAsQuery {
+            web::Query::<EntryFilterAsQuery>::from_query(query)
+                .unwrap()
+                .into_inner()
+        };
+        assert!(!parse("").into_filter(true).exclude_reservations);
+        assert!(parse("").into_filter(false).exclude_reservations);
+        assert!(
+            parse("include_reservations=false")
+                .into_filter(true)
+                .exclude_reservations
+        );
+        assert!(
+            !parse("include_reservations=true")
+                .into_filter(false)
+                .exclude_reservations
+        );
+        let filter: EntryFilter = parse("include_reservations=false").into();
+        assert!(filter.exclude_reservations);
+    }
+
+    #[test]
+    fn test_prefers_icalendar() {
+        let parse = |value: &str| -> Accept {
+            let request = actix_web::test::TestRequest::default()
+                .insert_header(("Accept", value))
+                .to_http_request();
+            actix_web::http::header::Header::parse(&request).unwrap()
+        };
+        assert!(prefers_icalendar(&parse("text/calendar")));
+        assert!(prefers_icalendar(&parse(
+            "application/json;q=0.5, text/calendar"
+        )));
+        assert!(!prefers_icalendar(&parse("application/json")));
+        assert!(!prefers_icalendar(&parse("*/*")));
+        assert!(!prefers_icalendar(&parse("text/html, */*;q=0.8")));
+        assert!(!prefers_icalendar(&parse(
+            "application/json, text/calendar;q=0.9"
+        )));
+    }
+
+    #[actix_web::test]
+    async fn test_json_array_stream() {
+        let entries = || -> Vec<kueaplan_api_types::Entry> {
+            serde_json::from_value(serde_json::json!([
+                {
+                    "id": "01977478-9bd7-7a19-9b7a-c5b9f1e4e0c1",
+                    "title": "Drachenfliegen",
+                    "comment": "wir lassen Drachen steigen",
+                    "room": ["01977478-9bd7-7a19-9b7a-c5b9f1e4e0c2"],
+                    "begin": "2025-01-04T12:00:00Z",
+                    "end": "2025-01-04T13:30:00Z",
+                    "category": "019774dc-81c4-7862-a9ba-63de3d726010",
+                    "isCancelled": true,
+                    "previousDates": [],
+                },
+                {
+                    "id": "01977478-9bd7-7a19-9b7a-c5b9f1e4e0c3",
+                    "title": "Singen \"am\" Lagerfeuer",
+                    "room": [],
+                    "begin": "2025-01-04T20:00:00Z",
+                    "end": "2025-01-04T22:00:00Z",
+                    "category": "019774dc-81c4-7862-a9ba-63de3d726010",
+                    "previousDates": [],
+                },
+            ]))
+            .unwrap()
+        };
+        let stream_to_bytes = |chunks: Vec<Vec<kueaplan_api_types::Entry>>| {
+            actix_web::body::to_bytes(actix_web::body::BodyStream::new(json_array_stream(
+                stream::iter(chunks.into_iter().map(Ok)),
+            )))
+        };
+
+        let expected = serde_json::to_vec(&entries()).unwrap();
+        let streamed = stream_to_bytes(vec![entries()]).await.unwrap();
+        assert_eq!(streamed, expected);
+        let parsed: Vec<kueaplan_api_types::Entry> = serde_json::from_slice(&streamed).unwrap();
+        assert_eq!(
+            serde_json::to_value(parsed).unwrap(),
+            serde_json::to_value(entries()).unwrap()
+        );
+
+        // Splitting the entries into multiple (and empty) chunks does not change the result
+        let mut split_entries = entries();
+        let second_chunk = split_entries.split_off(1);
+        let streamed = stream_to_bytes(vec![split_entries, vec![], second_chunk])
+            .await
+            .unwrap();
+        assert_eq!(streamed, expected);
+
+        assert_eq!(stream_to_bytes(vec![]).await.unwrap(), "[]");
+        assert_eq!(stream_to_bytes(vec![vec![]]).await.unwrap(), "[]");
+    }
+
+    #[test]
+    fn test_diff_entry_vs_previous_date_time_change() {
+        let room_id = Uuid::from_u128(10);
+        let entry = FullEntry::test_builder("2025-01-04T14:30:00Z", "2025-01-04T16:00:00Z")
+            .rooms(vec![room_id])
+            .previous_date(
+                Uuid::from_u128(2),
+                "2025-01-04T13:00:00Z",
+                "2025-01-04T15:00:00Z",
+                vec![room_id],
+            )
+            .build();
+        let changes = diff_entry_vs_previous_date(&entry, &entry.previous_dates[0]);
+        assert_eq!(changes.previous_date_id, Uuid::from_u128(2));
+        assert_eq!(changes.begin_shift_minutes, 90);
+        assert_eq!(changes.end_shift_minutes, 60);
+        assert!(changes.added_rooms.is_empty());
+        assert!(changes.removed_rooms.is_empty());
+    }
+
+    #[test]
+    fn test_diff_entry_vs_previous_date_room_change() {
+        let entry = FullEntry::test_builder("2025-01-04T13:00:00Z", "2025-01-04T15:00:00Z")
+            .rooms(vec![Uuid::from_u128(10), Uuid::from_u128(12)])
+            .previous_date(
+                Uuid::from_u128(2),
+                "2025-01-04T13:00:00Z",
+                "2025-01-04T15:00:00Z",
+                vec![Uuid::from_u128(11), Uuid::from_u128(10)],
+            )
+            .build();
+        let changes = diff_entry_vs_previous_date(&entry, &entry.previous_dates[0]);
+        assert_eq!(changes.begin_shift_minutes, 0);
+        assert_eq!(changes.end_shift_minutes, 0);
+        assert_eq!(changes.added_rooms, vec![Uuid::from_u128(12)]);
+        assert_eq!(changes.removed_rooms, vec![Uuid::from_u128(11)]);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::data_store::models::EntryTemplate;
+use crate::data_store::{EntryTemplateId, EventId};
+use crate::web::AppState;
+use crate::web::api::endpoints_entry::{check_color_format, check_entry_title_length};
+use crate::web::api::{APIError, SessionTokenHeader};
+use actix_web::{HttpResponse, Responder, delete, get, put, web};
+
+#[get("/events/{event_id}/entryTemplates")]
+async fn list_entry_templates(
+    path: web::Path<EventId>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let event_id = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let templates: Vec<kueaplan_api_types::EntryTemplate> =
+        web::block(move || -> Result<_, APIError> {
+            let mut store = state.store.get_facade()?;
+            let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+            Ok(store.list_entry_templates(&auth, event_id)?)
+        })
+        .await??
+        .into_iter()
+        .map(|t| t.into())
+        .collect();
+
+    Ok(web::Json(templates))
+}
+
+#[put("/events/{event_id}/entryTemplates/{template_id}")]
+async fn create_entry_template(
+    path: web::Path<(EventId, EntryTemplateId)>,
+    data: web::Json<kueaplan_api_types::EntryTemplate>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let (event_id, template_id) = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let template = data.into_inner();
+    if template_id != template.id {
+        return Err(APIError::EntityIdMissmatch);
+    }
+    if template.name.trim().is_empty() {
+        return Err(APIError::InvalidData(
+            "Entry template name must not be empty.".to_owned(),
+        ));
+    }
+    check_entry_title_length(&template.defaults.title)?;
+    if let Some(color) = template.defaults.color_override.as_ref() {
+        check_color_format(color)?;
+    }
+    web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        Ok(store.create_entry_template(&auth, EntryTemplate::from_api(template, event_id))?)
+    })
+    .await??;
+
+    Ok(HttpResponse::Created())
+}
+
+#[delete("/events/{event_id}/entryTemplates/{template_id}")]
+async fn delete_entry_template(
+    path: web::Path<(EventId, EntryTemplateId)>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let (event_id, template_id) = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        store.delete_entry_template(&auth, event_id, template_id)?;
+        Ok(())
+    })
+    .await?
+    .map_err(APIError::for_delete_endpoint)?;
+
+    Ok(HttpResponse::NoContent())
+}
@@ -1,6 +1,6 @@
 use crate::data_store::EventFilter;
 use crate::web::AppState;
-use crate::web::api::APIError;
+use crate::web::api::{APIError, SessionTokenHeader};
 use actix_web::{Responder, get, web};
 use serde::{Deserialize, Serialize};
 
@@ -26,6 +26,8 @@ struct EventFilterAsQuery {
     after: Option<chrono::NaiveDate>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     before: Option<chrono::NaiveDate>,
+    #[serde(default)]
+    include_archived: bool,
 }
 
 impl From<EventFilterAsQuery> for EventFilter {
@@ -33,6 +35,8 @@ impl From<EventFilterAsQuery> for EventFilter {
         Self {
             after: value.after,
             before: value.before,
+            include_archived: value.include_archived,
+            title_search: None,
         }
     }
 }
@@ -51,3 +55,46 @@ async fn get_event_info(
     .into();
     Ok(web::Json(event))
 }
+
+#[get("/events/{event_id}/stats")]
+async fn get_event_stats(
+    path: web::Path<i32>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let event_id = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let stats: kueaplan_api_types::EventStats = web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        Ok(store.get_event_stats(&auth, event_id)?)
+    })
+    .await??
+    .into();
+    Ok(web::Json(stats))
+}
+
+#[get("/events/{event_id}/neighbors")]
+async fn get_event_neighbors(
+    path: web::Path<i32>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let event_id = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let neighbors: kueaplan_api_types::EventNeighbors =
+        web::block(move || -> Result<_, APIError> {
+            let mut store = state.store.get_facade()?;
+            let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+            Ok(store.get_neighbor_events(&auth, event_id)?)
+        })
+        .await??
+        .into();
+    Ok(web::Json(neighbors))
+}
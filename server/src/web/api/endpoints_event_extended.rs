@@ -2,6 +2,10 @@ use crate::data_store::EventId;
 use crate::data_store::models::ExtendedEvent;
 use crate::web::AppState;
 use crate::web::api::{APIError, SessionTokenHeader};
+use crate::web::time_calculation::{
+    EventDayBoundaries, current_event_day, event_boundaries, event_day_boundaries,
+};
+use crate::web::util::weekday_short;
 use actix_web::{HttpResponse, Responder, get, put, web};
 
 #[get("/events/{event_id}/extended")]
@@ -14,7 +18,7 @@ async fn get_extended_event_info(
     let session_token = session_token_header
         .ok_or(APIError::NoSessionToken)?
         .into_inner()
-        .session_token(&state.secret)?;
+        .session_token(&state.secrets)?;
     let event: kueaplan_api_types::ExtendedEvent = web::block(move || -> Result<_, APIError> {
         let mut store = state.store.get_facade()?;
         let auth = store.get_auth_token_for_session(&session_token, event_id)?;
@@ -25,6 +29,69 @@ async fn get_extended_event_info(
     Ok(web::Json(event))
 }
 
+#[get("/events/{event_id}/days")]
+async fn get_event_days(
+    path: web::Path<EventId>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let event_id = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let event = web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        Ok(store.get_extended_event(&auth, event_id)?)
+    })
+    .await??;
+    let days: Vec<kueaplan_api_types::EventDay> =
+        event_day_boundaries(&event.basic_data, &event.clock_info)
+            .iter()
+            .map(api_event_day)
+            .collect();
+    Ok(web::Json(days))
+}
+
+#[get("/events/{event_id}/currentDay")]
+async fn get_current_event_day(
+    path: web::Path<EventId>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let event_id = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let event = web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        Ok(store.get_extended_event(&auth, event_id)?)
+    })
+    .await??;
+    Ok(web::Json(api_event_day(&current_programme_day(&event)?)))
+}
+
+/// Get the event's current programme day, i.e. the event day which the current time falls into or,
+/// outside the event, the nearest event day (see [current_event_day]).
+pub(super) fn current_programme_day(event: &ExtendedEvent) -> Result<EventDayBoundaries, APIError> {
+    current_event_day(&event.basic_data, &event.clock_info, &chrono::Utc::now())
+        .ok_or_else(|| APIError::InternalError("Event does not have any days".to_owned()))
+}
+
+/// Convert the boundaries of an event day into the API representation, including a human-readable
+/// label of the day
+pub(super) fn api_event_day(day: &EventDayBoundaries) -> kueaplan_api_types::EventDay {
+    kueaplan_api_types::EventDay {
+        date: day.date,
+        label: format!("{} {}", weekday_short(&day.date), day.date.format("%d.%m.")),
+        begin_utc: day.begin,
+        end_utc: day.end,
+    }
+}
+
 #[put("/events/{event_id}/extended")]
 async fn update_extended_event(
     path: web::Path<EventId>,
@@ -36,18 +103,25 @@ async fn update_extended_event(
     let session_token = session_token_header
         .ok_or(APIError::NoSessionToken)?
         .into_inner()
-        .session_token(&state.secret)?;
+        .session_token(&state.secrets)?;
     let event = data.into_inner();
     if event_id != event.basic_data.id {
         return Err(APIError::EntityIdMissmatch);
     }
+    let event = ExtendedEvent::try_from(event).map_err(|e| APIError::InvalidData(e.to_string()))?;
+    // An inverted date range is reported by the database constraint, so we only check the time
+    // range here, if it's restricted by the begin or end time
+    let (event_begin, event_end) = event_boundaries(&event.basic_data, &event.clock_info);
+    let has_times = event.basic_data.begin_time.is_some() || event.basic_data.end_time.is_some();
+    if has_times && event_end <= event_begin {
+        return Err(APIError::InvalidData(
+            "The event's end must be after its begin.".to_owned(),
+        ));
+    }
     web::block(move || -> Result<_, APIError> {
         let mut store = state.store.get_facade()?;
         let auth = store.get_auth_token_for_session(&session_token, event_id)?;
-        store.update_event(
-            &auth,
-            ExtendedEvent::try_from(event).map_err(|e| APIError::InvalidData(e.to_string()))?,
-        )?;
+        store.update_event(&auth, event)?;
         Ok(())
     })
     .await??;
@@ -0,0 +1,445 @@
+use crate::data_store::auth_token::Privilege;
+use crate::data_store::models::{EntryState, Event, EventClockInfo, FullEntry};
+use crate::data_store::{EntryFilter, EventId};
+use crate::web::AppState;
+use crate::web::api::endpoints_event_extended::api_event_day;
+use crate::web::api::{APIError, SessionTokenHeader};
+use crate::web::time_calculation::{
+    EventDayBoundaries, effective_day_boundaries, event_boundaries, event_day_boundaries,
+};
+use actix_web::{Responder, get, web};
+use chrono::{DateTime, Utc};
+
+#[get("/events/{event_id}/grid")]
+async fn get_event_grid(
+    path: web::Path<EventId>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let event_id = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let (event, entries) = web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        let event = store.get_extended_event(&auth, event_id)?;
+        let (begin, end) = event_boundaries(&event.basic_data, &event.clock_info);
+        let filter = EntryFilter::builder()
+            .after(begin, false)
+            .before(end, false)
+            .build();
+        let entries = store.get_published_entries_filtered(&auth, event_id, filter)?;
+        Ok((event, entries))
+    })
+    .await??;
+
+    Ok(web::Json(grid_days(
+        &event.basic_data,
+        &event.clock_info,
+        &entries,
+    )))
+}
+
+#[get("/events/{event_id}/overview")]
+async fn get_event_overview(
+    path: web::Path<EventId>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let event_id = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let (event, entries) = web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        let event = store.get_extended_event(&auth, event_id)?;
+        let (begin, end) = event_boundaries(&event.basic_data, &event.clock_info);
+        let filter = EntryFilter::builder()
+            .after(begin, false)
+            .before(end, false)
+            .build();
+        let entries = store.get_published_entries_filtered(&auth, event_id, filter)?;
+        Ok((event, entries))
+    })
+    .await??;
+
+    Ok(web::Json(overview_days(
+        &event.basic_data,
+        &event.clock_info,
+        entries,
+    )))
+}
+
+#[get("/events/{event_id}/entries/dailyCounts")]
+async fn get_daily_entry_counts(
+    path: web::Path<EventId>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let event_id = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let (event, entry_begins) = web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        let event = store.get_extended_event(&auth, event_id)?;
+        // Orgas also see the entries which are not published (yet), like in the entry list
+        let states: Vec<EntryState> = EntryState::all()
+            .filter(|s| {
+                if auth.has_privilege(event_id, Privilege::ManageEntries) {
+                    !s.is_dismissed()
+                } else {
+                    s.is_published()
+                }
+            })
+            .copied()
+            .collect();
+        let (begin, end) = event_boundaries(&event.basic_data, &event.clock_info);
+        let filter = EntryFilter::builder()
+            .after(begin, false)
+            .before(end, false)
+            .build();
+        let entry_begins = store.get_entry_begins(&auth, event_id, filter, &states)?;
+        Ok((event, entry_begins))
+    })
+    .await??;
+
+    Ok(web::Json(daily_entry_counts(
+        &event.basic_data,
+        &event.clock_info,
+        &entry_begins,
+    )))
+}
+
+/// Count the entries per (effective) day of the event, given the entries' begin timestamps.
+///
+/// Each entry is counted for the day in which it begins, according to [day_index_of_timestamp],
+/// i.e. in the same way as the entries are distributed in [overview_days]. `entry_begins` should
+/// only contain entries overlapping the event's [event_boundaries]. The result contains every day
+/// of the event, including days without any entries.
+fn daily_entry_counts(
+    event: &Event,
+    clock_info: &EventClockInfo,
+    entry_begins: &[DateTime<Utc>],
+) -> Vec<kueaplan_api_types::DailyEntryCount> {
+    let days = event_day_boundaries(event, clock_info);
+    let mut counts = vec![0; days.len()];
+    for begin in entry_begins {
+        if let Some(count) = counts.get_mut(day_index_of_timestamp(&days, begin)) {
+            *count += 1;
+        }
+    }
+    days.into_iter()
+        .zip(counts)
+        .map(|(day, count)| kueaplan_api_types::DailyEntryCount {
+            day: day.date,
+            count,
+        })
+        .collect()
+}
+
+/// Get the index of the day in `days` (as returned by [event_day_boundaries]), in which the
+/// given timestamp lies. Timestamps before the event's first day are assigned to the first day,
+/// timestamps after the last day to the last day.
+fn day_index_of_timestamp(days: &[EventDayBoundaries], timestamp: &DateTime<Utc>) -> usize {
+    days.iter()
+        .rposition(|day| day.begin <= *timestamp)
+        .unwrap_or(0)
+}
+
+/// Distribute the entries to the (effective) days of the event for the overview of the whole
+/// event.
+///
+/// In contrast to [grid_days], each entry is included in exactly one day: the day in which the
+/// entry begins, according to [event_day_boundaries]. Entries beginning before the event's first
+/// day (but ending within the event) are included in the first day. Within each day, the entries
+/// keep the order of `entries`.
+fn overview_days(
+    event: &Event,
+    clock_info: &EventClockInfo,
+    entries: Vec<FullEntry>,
+) -> Vec<kueaplan_api_types::OverviewDay> {
+    let days = event_day_boundaries(event, clock_info);
+    let mut result: Vec<kueaplan_api_types::OverviewDay> = days
+        .iter()
+        .map(|day| kueaplan_api_types::OverviewDay {
+            day: day.date,
+            entries: vec![],
+        })
+        .collect();
+    for entry in entries {
+        if let Some(day) = result.get_mut(day_index_of_timestamp(&days, &entry.entry.begin)) {
+            day.entries.push(entry.into());
+        }
+    }
+    result
+}
+
+/// Distribute the entries to the (effective) days of the event for displaying them in a calendar
+/// grid.
+///
+/// Entries spanning multiple days are included in each of these days. The entries' positions are
+/// given in minutes from the effective begin of the respective day and clamped to the day. Within
+/// each day, overlapping entries are assigned to different columns, using [assign_columns].
+fn grid_days(
+    event: &Event,
+    clock_info: &EventClockInfo,
+    entries: &[FullEntry],
+) -> Vec<kueaplan_api_types::GridDay> {
+    event_day_boundaries(event, clock_info)
+        .into_iter()
+        .map(|day| {
+            let (day_origin, _) = effective_day_boundaries(day.date, clock_info);
+            let mut day_entries: Vec<&FullEntry> =
+                entries.iter().filter(|e| is_within_day(e, &day)).collect();
+            day_entries.sort_by_key(|e| (e.entry.begin, e.entry.end));
+            let intervals: Vec<_> = day_entries
+                .iter()
+                .map(|e| (e.entry.begin.max(day.begin), e.entry.end.min(day.end)))
+                .collect();
+            let (columns, num_columns) = assign_columns(&intervals);
+
+            kueaplan_api_types::GridDay {
+                day: api_event_day(&day),
+                columns: num_columns,
+                entries: day_entries
+                    .into_iter()
+                    .zip(intervals)
+                    .zip(columns)
+                    .map(
+                        |((entry, (begin, end)), column)| kueaplan_api_types::GridEntry {
+                            begin: entry
+                                .entry
+                                .begin
+                                .with_timezone(&clock_info.timezone)
+                                .fixed_offset(),
+                            end: entry
+                                .entry
+                                .end
+                                .with_timezone(&clock_info.timezone)
+                                .fixed_offset(),
+                            begin_minute: (begin - day_origin).num_minutes(),
+                            end_minute: (end - day_origin).num_minutes(),
+                            column,
+                            entry: entry.clone().into(),
+                        },
+                    )
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// Check if the entry takes place (at least partially) within the given day. Entries without
+/// duration are considered to be within the day, if they begin within the day.
+fn is_within_day(entry: &FullEntry, day: &EventDayBoundaries) -> bool {
+    entry.entry.begin < day.end && (entry.entry.end > day.begin || entry.entry.begin >= day.begin)
+}
+
+/// Assign the given intervals to columns, such that overlapping intervals are placed in different
+/// columns, while using as few columns as possible.
+///
+/// Each interval is placed in the first column, which is free at the interval's begin (greedy
+/// lane assignment). Intervals touching each other (i.e. one ends when the other begins) are not
+/// considered to overlap. The `intervals` must be sorted by their begin.
+///
+/// Returns the column index of each interval and the total number of columns used.
+fn assign_columns<T: Ord + Copy>(intervals: &[(T, T)]) -> (Vec<u32>, u32) {
+    // End of the last interval in each column
+    let mut column_ends: Vec<T> = Vec::new();
+    let columns = intervals
+        .iter()
+        .map(|(begin, end)| {
+            match column_ends
+                .iter()
+                .position(|column_end| column_end <= begin)
+            {
+                Some(column) => {
+                    column_ends[column] = *end;
+                    column as u32
+                }
+                None => {
+                    column_ends.push(*end);
+                    (column_ends.len() - 1) as u32
+                }
+            }
+        })
+        .collect();
+    (columns, column_ends.len() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEFAULT_CLOCK_INFO: EventClockInfo = EventClockInfo {
+        timezone: chrono_tz::Tz::Europe__Berlin,
+        effective_begin_of_day: chrono::NaiveTime::from_hms_opt(5, 30, 0).unwrap(),
+    };
+
+    #[test]
+    fn test_assign_columns() {
+        assert_eq!(assign_columns::<i32>(&[]), (vec![], 0));
+        assert_eq!(assign_columns(&[(0, 10), (5, 15)]), (vec![0, 1], 2));
+        // Touching intervals do not overlap
+        assert_eq!(assign_columns(&[(0, 10), (10, 15)]), (vec![0, 0], 1));
+        // Free columns are reused
+        assert_eq!(
+            assign_columns(&[(0, 10), (2, 4), (3, 12), (5, 8), (11, 13)]),
+            (vec![0, 1, 2, 1, 0], 3)
+        );
+    }
+
+    #[test]
+    fn test_grid_days() {
+        let event = Event {
+            id: 1,
+            title: "Test".to_owned(),
+            begin_date: "2025-01-03".parse().unwrap(),
+            end_date: "2025-01-04".parse().unwrap(),
+            slug: None,
+            begin_time: None,
+            end_time: None,
+            archived: false,
+        };
+        let entries = vec![
+            FullEntry::test_builder("2025-01-03T13:00:00Z", "2025-01-03T15:00:00Z")
+                .id(uuid::Uuid::from_u128(2))
+                .build(),
+            FullEntry::test_builder("2025-01-03T12:00:00Z", "2025-01-03T14:00:00Z")
+                .id(uuid::Uuid::from_u128(1))
+                .build(),
+            // Spans the boundary between both days (at 04:30 UTC)
+            FullEntry::test_builder("2025-01-04T03:00:00Z", "2025-01-04T05:00:00Z")
+                .id(uuid::Uuid::from_u128(3))
+                .build(),
+        ];
+
+        let result = grid_days(&event, &DEFAULT_CLOCK_INFO, &entries);
+        assert_eq!(result.len(), 2);
+
+        let day = &result[0];
+        assert_eq!(
+            day.day.date,
+            "2025-01-03".parse::<chrono::NaiveDate>().unwrap()
+        );
+        assert_eq!(day.columns, 2);
+        assert_eq!(day.entries.len(), 3);
+        assert_eq!(day.entries[0].entry.id, uuid::Uuid::from_u128(1));
+        assert_eq!(day.entries[0].column, 0);
+        // 13:00 local time is 7.5 hours after the effective begin of day
+        assert_eq!(day.entries[0].begin_minute, 450);
+        assert_eq!(day.entries[0].end_minute, 570);
+        assert_eq!(
+            day.entries[0].begin.to_rfc3339(),
+            "2025-01-03T13:00:00+01:00"
+        );
+        assert_eq!(day.entries[1].entry.id, uuid::Uuid::from_u128(2));
+        assert_eq!(day.entries[1].column, 1);
+        assert_eq!(day.entries[2].entry.id, uuid::Uuid::from_u128(3));
+        assert_eq!(day.entries[2].end_minute, 24 * 60);
+
+        let day = &result[1];
+        assert_eq!(day.columns, 1);
+        assert_eq!(day.entries.len(), 1);
+        assert_eq!(day.entries[0].begin_minute, 0);
+        assert_eq!(day.entries[0].end_minute, 30);
+        assert_eq!(day.entries[0].column, 0);
+    }
+
+    #[test]
+    fn test_daily_entry_counts() {
+        let event = Event {
+            id: 1,
+            title: "Test".to_owned(),
+            begin_date: "2025-01-03".parse().unwrap(),
+            end_date: "2025-01-05".parse().unwrap(),
+            slug: None,
+            begin_time: None,
+            end_time: None,
+            archived: false,
+        };
+        let entry_begins: Vec<DateTime<Utc>> = [
+            "2025-01-03T12:00:00Z",
+            "2025-01-03T18:00:00Z",
+            // 05:29 local time on 2025-01-04, i.e. still on the effective date 2025-01-03
+            "2025-01-04T04:29:00Z",
+            // 05:30 local time, i.e. exactly at the begin of the effective date 2025-01-04
+            "2025-01-04T04:30:00Z",
+            "2025-01-05T10:00:00Z",
+            "2025-01-05T20:00:00Z",
+            // Before the event's first day, i.e. counted for the first day
+            "2025-01-03T03:00:00Z",
+        ]
+        .iter()
+        .map(|t| t.parse().unwrap())
+        .collect();
+
+        let result = daily_entry_counts(&event, &DEFAULT_CLOCK_INFO, &entry_begins);
+        assert_eq!(
+            result
+                .iter()
+                .map(|d| (d.day.to_string(), d.count))
+                .collect::<Vec<_>>(),
+            vec![
+                ("2025-01-03".to_owned(), 4),
+                ("2025-01-04".to_owned(), 1),
+                ("2025-01-05".to_owned(), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_overview_days() {
+        let event = Event {
+            id: 1,
+            title: "Test".to_owned(),
+            begin_date: "2025-01-03".parse().unwrap(),
+            end_date: "2025-01-05".parse().unwrap(),
+            slug: None,
+            begin_time: None,
+            end_time: None,
+            archived: false,
+        };
+        let entries = vec![
+            FullEntry::test_builder("2025-01-03T12:00:00Z", "2025-01-03T14:00:00Z")
+                .id(uuid::Uuid::from_u128(1))
+                .build(),
+            // Begins at 23:30 local time, i.e. still on 2025-01-03 (calendar and effective date)
+            FullEntry::test_builder("2025-01-03T22:30:00Z", "2025-01-04T01:00:00Z")
+                .id(uuid::Uuid::from_u128(2))
+                .build(),
+            // Begins at 03:00 local time on 2025-01-05, i.e. on the effective date 2025-01-04,
+            // and spans the boundary between the days (at 04:30 UTC)
+            FullEntry::test_builder("2025-01-05T02:00:00Z", "2025-01-05T06:00:00Z")
+                .id(uuid::Uuid::from_u128(3))
+                .build(),
+            // Begins before the event's first day
+            FullEntry::test_builder("2025-01-03T03:00:00Z", "2025-01-03T05:00:00Z")
+                .id(uuid::Uuid::from_u128(4))
+                .build(),
+        ];
+
+        let result = overview_days(&event, &DEFAULT_CLOCK_INFO, entries);
+        let ids = |i: usize| {
+            result[i]
+                .entries
+                .iter()
+                .map(|e| e.id.as_u128())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            result.iter().map(|d| d.day.to_string()).collect::<Vec<_>>(),
+            vec!["2025-01-03", "2025-01-04", "2025-01-05"]
+        );
+        assert_eq!(ids(0), vec![1, 2, 4]);
+        assert_eq!(ids(1), vec![3]);
+        assert_eq!(ids(2), Vec::<u128>::new());
+    }
+}
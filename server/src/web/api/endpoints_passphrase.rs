@@ -1,3 +1,4 @@
+use crate::data_store::auth_token::Privilege;
 use crate::data_store::models::NewPassphrase;
 use crate::data_store::{EventId, PassphraseId};
 use crate::web::AppState;
@@ -14,7 +15,7 @@ async fn list_passphrases(
     let session_token = session_token_header
         .ok_or(APIError::NoSessionToken)?
         .into_inner()
-        .session_token(&state.secret)?;
+        .session_token(&state.secrets)?;
     let passphrases: Vec<kueaplan_api_types::Passphrase> =
         web::block(move || -> Result<_, APIError> {
             let mut store = state.store.get_facade()?;
@@ -29,6 +30,38 @@ async fn list_passphrases(
     Ok(web::Json(passphrases))
 }
 
+#[get("/events/{event_id}/passphrases/{passphrase_id}/scope")]
+async fn get_passphrase_scope(
+    path: web::Path<(EventId, PassphraseId)>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let (event_id, passphrase_id) = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let passphrase = web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        store
+            .get_passphrases(&auth, event_id)?
+            .into_iter()
+            .find(|p| p.id == passphrase_id)
+            .ok_or(APIError::NotExisting)
+    })
+    .await??;
+
+    Ok(web::Json(kueaplan_api_types::PassphraseScope {
+        passphrase_id,
+        event_id,
+        role: passphrase.privilege.into(),
+        privileges: Privilege::granted_to(passphrase.privilege)
+            .map(|p| p.into())
+            .collect(),
+    }))
+}
+
 #[post("/events/{event_id}/passphrases")]
 async fn create_passphrase(
     path: web::Path<i32>,
@@ -40,7 +73,7 @@ async fn create_passphrase(
     let session_token = session_token_header
         .ok_or(APIError::NoSessionToken)?
         .into_inner()
-        .session_token(&state.secret)?;
+        .session_token(&state.secrets)?;
     let passphrase = data.into_inner();
     if passphrase.id.is_some() {
         return Err(APIError::InvalidData(
@@ -73,7 +106,7 @@ async fn change_passphrase(
     let session_token = session_token_header
         .ok_or(APIError::NoSessionToken)?
         .into_inner()
-        .session_token(&state.secret)?;
+        .session_token(&state.secrets)?;
     let passphrase = data.into_inner();
     web::block(move || -> Result<_, APIError> {
         let mut store = state.store.get_facade()?;
@@ -95,7 +128,7 @@ async fn delete_passphrase(
     let session_token = session_token_header
         .ok_or(APIError::NoSessionToken)?
         .into_inner()
-        .session_token(&state.secret)?;
+        .session_token(&state.secrets)?;
     web::block(move || -> Result<_, APIError> {
         let mut store = state.store.get_facade()?;
         let auth = store.get_auth_token_for_session(&session_token, event_id)?;
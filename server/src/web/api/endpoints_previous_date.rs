@@ -1,9 +1,31 @@
 use crate::data_store::models::FullPreviousDate;
 use crate::web::AppState;
 use crate::web::api::{APIError, SessionTokenHeader};
-use actix_web::{HttpResponse, Responder, delete, put, web};
+use actix_web::{HttpResponse, Responder, delete, get, post, put, web};
 use uuid::Uuid;
 
+#[get("/events/{event_id}/previousDates")]
+async fn list_all_previous_dates(
+    path: web::Path<i32>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let event_id = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let previous_dates = web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        Ok(store.get_all_previous_dates(&auth, event_id)?)
+    })
+    .await??;
+    let previous_dates: Vec<kueaplan_api_types::EntryPreviousDates> =
+        previous_dates.into_iter().map(|e| e.into()).collect();
+    Ok(web::Json(previous_dates))
+}
+
 #[put("/events/{event_id}/entries/{entry_id}/previousDates/{previous_date_id}")]
 async fn create_or_update_previous_date(
     path: web::Path<(i32, Uuid, Uuid)>,
@@ -15,7 +37,7 @@ async fn create_or_update_previous_date(
     let session_token = session_token_header
         .ok_or(APIError::NoSessionToken)?
         .into_inner()
-        .session_token(&state.secret)?;
+        .session_token(&state.secrets)?;
     let previous_date = data.into_inner();
     if previous_date_id != previous_date.id {
         return Err(APIError::EntityIdMissmatch);
@@ -47,7 +69,7 @@ async fn delete_previous_date(
     let session_token = session_token_header
         .ok_or(APIError::NoSessionToken)?
         .into_inner()
-        .session_token(&state.secret)?;
+        .session_token(&state.secrets)?;
     web::block(move || -> Result<_, APIError> {
         let mut store = state.store.get_facade()?;
         let auth = store.get_auth_token_for_session(&session_token, event_id)?;
@@ -59,3 +81,50 @@ async fn delete_previous_date(
 
     Ok(HttpResponse::NoContent())
 }
+
+#[get("/events/{event_id}/entries/{entry_id}/previousDates/deleted")]
+async fn list_deleted_previous_dates(
+    path: web::Path<(i32, Uuid)>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let (event_id, entry_id) = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let previous_dates: Vec<kueaplan_api_types::PreviousDate> =
+        web::block(move || -> Result<_, APIError> {
+            let mut store = state.store.get_facade()?;
+            let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+            Ok(store.get_deleted_previous_dates(&auth, entry_id)?)
+        })
+        .await??
+        .into_iter()
+        .map(|e| e.into())
+        .collect();
+
+    Ok(web::Json(previous_dates))
+}
+
+#[post("/events/{event_id}/entries/{entry_id}/previousDates/{previous_date_id}/restore")]
+async fn restore_previous_date(
+    path: web::Path<(i32, Uuid, Uuid)>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let (event_id, entry_id, previous_date_id) = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        store.restore_previous_date(&auth, entry_id, previous_date_id)?;
+        Ok(())
+    })
+    .await??;
+
+    Ok(HttpResponse::NoContent())
+}
@@ -1,7 +1,8 @@
-use crate::data_store::models::NewRoom;
+use crate::data_store::auth_token::GlobalAuthToken;
+use crate::data_store::models::{NewRoom, RoomDoubleBooking};
 use crate::web::AppState;
 use crate::web::api::{APIError, SessionTokenHeader};
-use actix_web::{HttpResponse, Responder, delete, get, put, web};
+use actix_web::{HttpRequest, HttpResponse, Responder, delete, get, patch, post, put, web};
 use serde::Deserialize;
 use uuid::Uuid;
 
@@ -15,7 +16,7 @@ async fn list_rooms(
     let session_token = session_token_header
         .ok_or(APIError::NoSessionToken)?
         .into_inner()
-        .session_token(&state.secret)?;
+        .session_token(&state.secrets)?;
     let rooms: Vec<kueaplan_api_types::Room> = web::block(move || -> Result<_, APIError> {
         let mut store = state.store.get_facade()?;
         let auth = store.get_auth_token_for_session(&session_token, event_id)?;
@@ -29,6 +30,54 @@ async fn list_rooms(
     Ok(web::Json(rooms))
 }
 
+#[get("/rooms")]
+async fn list_rooms_of_events(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    // The events are given as repeated `event` query parameters, which can not be deserialized
+    // into a list by `web::Query`. Thus, we parse the query string as a sequence of pairs.
+    let query_pairs: Vec<(String, String)> = serde_urlencoded::from_str(req.query_string())
+        .map_err(|e| APIError::InvalidData(e.to_string()))?;
+    let mut event_ids: Vec<i32> = Vec::new();
+    for (key, value) in query_pairs {
+        if key != "event" {
+            continue;
+        }
+        let event_id = value
+            .parse()
+            .map_err(|_| APIError::InvalidData(format!("Invalid event id '{}'", value)))?;
+        if !event_ids.contains(&event_id) {
+            event_ids.push(event_id);
+        }
+    }
+    // This is a global action, which is only available with the server admin API key
+    let auth_token = GlobalAuthToken::create_for_api_key(
+        session_token_header
+            .ok_or(APIError::NoSessionToken)?
+            .into_inner()
+            .api_key
+            .as_deref(),
+        state.server_admin_api_key.as_deref(),
+    );
+    let rooms_by_event = web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        Ok(store.get_rooms_for_events(&auth_token, &event_ids)?)
+    })
+    .await??;
+
+    Ok(web::Json(kueaplan_api_types::AllEventsRooms {
+        events: rooms_by_event
+            .into_iter()
+            .map(|(event_id, rooms)| kueaplan_api_types::EventRooms {
+                event_id,
+                rooms: rooms.into_iter().map(|r| r.into()).collect(),
+            })
+            .collect(),
+    }))
+}
+
 #[put("/events/{event_id}/rooms/{room_id}")]
 async fn create_or_update_room(
     path: web::Path<(i32, Uuid)>,
@@ -40,7 +89,7 @@ async fn create_or_update_room(
     let session_token = session_token_header
         .ok_or(APIError::NoSessionToken)?
         .into_inner()
-        .session_token(&state.secret)?;
+        .session_token(&state.secrets)?;
     let room = data.into_inner();
     if room_id != room.id {
         return Err(APIError::EntityIdMissmatch);
@@ -48,7 +97,7 @@ async fn create_or_update_room(
     let created = web::block(move || -> Result<_, APIError> {
         let mut store = state.store.get_facade()?;
         let auth = store.get_auth_token_for_session(&session_token, event_id)?;
-        Ok(store.create_or_update_room(&auth, NewRoom::from_api(room, event_id))?)
+        Ok(store.create_or_update_room(&auth, NewRoom::from_api(room, event_id), None)?)
     })
     .await??;
 
@@ -59,6 +108,29 @@ async fn create_or_update_room(
     }
 }
 
+#[patch("/events/{event_id}/rooms/{room_id}")]
+async fn change_room(
+    path: web::Path<(i32, Uuid)>,
+    data: web::Json<kueaplan_api_types::RoomPatch>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let (event_id, room_id) = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let room = data.into_inner();
+    web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        Ok(store.patch_room(&auth, room_id, room.into())?)
+    })
+    .await??;
+
+    Ok(HttpResponse::NoContent())
+}
+
 #[delete("/events/{event_id}/rooms/{room_id}")]
 async fn delete_room(
     path: web::Path<(i32, Uuid)>,
@@ -70,7 +142,7 @@ async fn delete_room(
     let session_token = session_token_header
         .ok_or(APIError::NoSessionToken)?
         .into_inner()
-        .session_token(&state.secret)?;
+        .session_token(&state.secrets)?;
     let data = data.map(web::Json::<_>::into_inner);
     web::block(move || -> Result<_, APIError> {
         let mut store = state.store.get_facade()?;
@@ -85,6 +157,7 @@ async fn delete_room(
             data.as_ref()
                 .map(|data| data.add_room_comment.as_str())
                 .unwrap_or(""),
+            data.as_ref().is_some_and(|data| data.force),
         )?;
         Ok(())
     })
@@ -94,10 +167,116 @@ async fn delete_room(
     Ok(HttpResponse::NoContent())
 }
 
+#[post("/events/{event_id}/rooms/{room_id}/mergeInto/{target_room_id}")]
+async fn merge_room(
+    path: web::Path<(i32, Uuid, Uuid)>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let (event_id, room_id, target_room_id) = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        store.merge_rooms(&auth, event_id, room_id, target_room_id)?;
+        Ok(())
+    })
+    .await??;
+
+    Ok(HttpResponse::NoContent())
+}
+
+#[get("/events/{event_id}/conflicts")]
+async fn list_room_conflicts(
+    path: web::Path<i32>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let event_id = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let double_bookings = web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        Ok(store.find_room_double_bookings(&auth, event_id)?)
+    })
+    .await??;
+
+    Ok(web::Json(group_double_bookings_by_room(double_bookings)))
+}
+
+/// Group the given room double bookings, which are expected to be ordered by room, into one
+/// [kueaplan_api_types::RoomConflicts] object per room.
+fn group_double_bookings_by_room(
+    double_bookings: Vec<RoomDoubleBooking>,
+) -> Vec<kueaplan_api_types::RoomConflicts> {
+    let mut result: Vec<kueaplan_api_types::RoomConflicts> = Vec::new();
+    for double_booking in double_bookings {
+        match result.last_mut() {
+            Some(room_conflicts) if room_conflicts.room_id == double_booking.room_id => {
+                room_conflicts
+                    .conflicting_entries
+                    .push(double_booking.entry_ids);
+            }
+            _ => result.push(kueaplan_api_types::RoomConflicts {
+                room_id: double_booking.room_id,
+                conflicting_entries: vec![double_booking.entry_ids],
+            }),
+        }
+    }
+    result
+}
+
 #[derive(Deserialize)]
 struct DeleteRoomBody {
     #[serde(default, rename = "replaceRooms")]
     replace_rooms: Vec<Uuid>,
     #[serde(default, rename = "addRoomComment")]
     add_room_comment: String,
+    #[serde(default)]
+    force: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_double_bookings_by_room() {
+        let room_a = Uuid::now_v7();
+        let room_b = Uuid::now_v7();
+        let entries: Vec<Uuid> = (0..4).map(|_| Uuid::now_v7()).collect();
+        let double_bookings = vec![
+            RoomDoubleBooking {
+                room_id: room_a,
+                entry_ids: (entries[0], entries[1]),
+            },
+            RoomDoubleBooking {
+                room_id: room_a,
+                entry_ids: (entries[1], entries[2]),
+            },
+            RoomDoubleBooking {
+                room_id: room_b,
+                entry_ids: (entries[0], entries[3]),
+            },
+        ];
+
+        let result = group_double_bookings_by_room(double_bookings);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].room_id, room_a);
+        assert_eq!(
+            result[0].conflicting_entries,
+            vec![(entries[0], entries[1]), (entries[1], entries[2])]
+        );
+        assert_eq!(result[1].room_id, room_b);
+        assert_eq!(
+            result[1].conflicting_entries,
+            vec![(entries[0], entries[3])]
+        );
+    }
 }
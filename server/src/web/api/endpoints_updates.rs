@@ -0,0 +1,108 @@
+use crate::auth_session::SessionToken;
+use crate::data_store::EventId;
+use crate::data_store::auth_token::Privilege;
+use crate::web::AppState;
+use crate::web::api::{APIError, SessionTokenHeader};
+use crate::web::live_updates::{LiveUpdatesAccess, live_updates_websocket_stream};
+use actix_http::ws;
+use actix_web::body::BodyStream;
+use actix_web::{HttpRequest, HttpResponse, Responder, get, web};
+use serde::Deserialize;
+
+#[get("/events/{event_id}/updates")]
+async fn get_updates(
+    path: web::Path<i32>,
+    query: web::Query<UpdatesQuery>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let event_id = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let since = query.into_inner().since;
+    let updates: kueaplan_api_types::Updates = web::block(move || -> Result<_, APIError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        Ok(store.get_updates_since(&auth, event_id, since)?)
+    })
+    .await??
+    .into();
+
+    Ok(web::Json(updates))
+}
+
+#[derive(Deserialize)]
+struct UpdatesQuery {
+    since: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// WebSocket endpoint, pushing a notification to the client whenever an entry of the event is
+/// created, updated or deleted
+#[get("/events/{event_id}/live")]
+async fn live_updates(
+    path: web::Path<i32>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+    req: HttpRequest,
+    payload: web::Payload,
+) -> Result<HttpResponse, APIError> {
+    let event_id = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let store = state.store.clone();
+    let token = session_token.clone();
+    web::block(move || -> Result<_, APIError> {
+        let mut store = store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&token, event_id)?;
+        Ok(auth.check_privilege(event_id, Privilege::ShowKueaPlan)?)
+    })
+    .await??;
+
+    let mut response = ws::handshake(req.head())
+        .map_err(|e| APIError::InvalidData(format!("Invalid WebSocket handshake: {}", e)))?;
+    let store = state.store.clone();
+    let check_access = move || {
+        let store = store.clone();
+        let session_token = session_token.clone();
+        async move {
+            web::block(move || live_updates_access(&store, &session_token, event_id))
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or(LiveUpdatesAccess::None)
+        }
+    };
+    let body = BodyStream::new(live_updates_websocket_stream(
+        event_id,
+        check_access,
+        state.live_updates.subscribe(),
+        payload,
+    ));
+    Ok(HttpResponse::from(
+        response.body(body).map_into_boxed_body(),
+    ))
+}
+
+/// Determine the current access level of the client with the given session token to the live
+/// updates of the event. Returns None if the access level cannot be determined due to an error.
+fn live_updates_access(
+    store: &std::sync::Arc<dyn crate::data_store::KuaPlanStore>,
+    session_token: &SessionToken,
+    event_id: EventId,
+) -> Option<LiveUpdatesAccess> {
+    let mut store = store.get_facade().ok()?;
+    let auth = store
+        .get_auth_token_for_session(session_token, event_id)
+        .ok()?;
+    Some(if auth.has_privilege(event_id, Privilege::ManageEntries) {
+        LiveUpdatesAccess::Internal
+    } else if auth.has_privilege(event_id, Privilege::ShowKueaPlan) {
+        LiveUpdatesAccess::Public
+    } else {
+        LiveUpdatesAccess::None
+    })
+}
@@ -0,0 +1,365 @@
+use crate::data_store::auth_token::Privilege;
+use crate::data_store::models::{
+    Category, EntryState, ExtendedEvent, FullAnnouncement, FullEntry, RoomDoubleBooking,
+};
+use crate::data_store::{EntryFilter, EntryId, EventId};
+use crate::web::AppState;
+use crate::web::api::{APIError, SessionTokenHeader};
+use crate::web::time_calculation::is_within_event_dates;
+use actix_web::{Responder, get, web};
+use kueaplan_api_types::{ValidationIssue, ValidationIssueKind, ValidationSeverity};
+use std::collections::{HashMap, HashSet};
+
+#[get("/events/{event_id}/validate")]
+async fn validate_event(
+    path: web::Path<EventId>,
+    state: web::Data<AppState>,
+    session_token_header: Option<web::Header<SessionTokenHeader>>,
+) -> Result<impl Responder, APIError> {
+    let event_id = path.into_inner();
+    let session_token = session_token_header
+        .ok_or(APIError::NoSessionToken)?
+        .into_inner()
+        .session_token(&state.secrets)?;
+    let (event, entries, double_bookings, categories, announcements) =
+        web::block(move || -> Result<_, APIError> {
+            let mut store = state.store.get_facade()?;
+            let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+            auth.check_privilege(event_id, Privilege::ManageEntries)?;
+            // Only entries which will be shown in the KüA-Plan are relevant
+            let published_states: Vec<EntryState> = EntryState::all()
+                .filter(|s| s.is_published())
+                .copied()
+                .collect();
+            Ok((
+                store.get_extended_event(&auth, event_id)?,
+                store.get_all_entries_filtered(
+                    &auth,
+                    event_id,
+                    EntryFilter::default(),
+                    &published_states,
+                )?,
+                store.find_room_double_bookings(&auth, event_id)?,
+                store.get_categories(&auth, event_id)?,
+                store.get_announcements(&auth, event_id, None)?,
+            ))
+        })
+        .await??;
+
+    Ok(web::Json(kueaplan_api_types::EventValidationReport {
+        issues: validate_event_contents(
+            &event,
+            &entries,
+            &double_bookings,
+            &categories,
+            &announcements,
+        ),
+    }))
+}
+
+/// Check the event's (non-deleted) entries, categories and announcements for issues which should be
+/// resolved before publishing the KüA-Plan.
+///
+/// `entries` should contain the entries of all states, which will be shown in the KüA-Plan.
+/// `double_bookings` (as returned by
+/// [find_room_double_bookings](crate::data_store::KueaPlanStoreFacade::find_room_double_bookings))
+/// referencing other entries are ignored. Cancelled entries are not considered for time overlaps.
+/// The issues are sorted by severity (most severe first).
+fn validate_event_contents(
+    event: &ExtendedEvent,
+    entries: &[FullEntry],
+    double_bookings: &[RoomDoubleBooking],
+    categories: &[Category],
+    announcements: &[FullAnnouncement],
+) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    for entry in entries {
+        if !is_within_event_dates(
+            &entry.entry.begin,
+            &entry.entry.end,
+            &event.basic_data,
+            &event.clock_info,
+        ) {
+            issues.push(ValidationIssue {
+                severity: ValidationSeverity::Error,
+                kind: ValidationIssueKind::EntryOutsideEventDates,
+                message: format!(
+                    "Entry '{}' is not within the event's dates",
+                    entry.entry.title
+                ),
+                entity_ids: vec![entry.entry.id],
+            });
+        }
+        if entry.entry.title.trim().is_empty() {
+            issues.push(ValidationIssue {
+                severity: ValidationSeverity::Warning,
+                kind: ValidationIssueKind::EntryWithoutTitle,
+                message: "Entry has an empty title".to_owned(),
+                entity_ids: vec![entry.entry.id],
+            });
+        }
+    }
+
+    // An exclusive entry must not overlap with any other entry, regardless of their rooms
+    let active_entries: Vec<&FullEntry> =
+        entries.iter().filter(|e| !e.entry.is_cancelled).collect();
+    let mut exclusive_conflicts: HashSet<(EntryId, EntryId)> = HashSet::new();
+    for (i, entry1) in active_entries.iter().enumerate() {
+        for entry2 in &active_entries[i + 1..] {
+            if (entry1.entry.is_exclusive || entry2.entry.is_exclusive)
+                && entry1.entry.begin < entry2.entry.end
+                && entry2.entry.begin < entry1.entry.end
+            {
+                exclusive_conflicts.insert((entry1.entry.id, entry2.entry.id));
+                exclusive_conflicts.insert((entry2.entry.id, entry1.entry.id));
+                issues.push(ValidationIssue {
+                    severity: ValidationSeverity::Error,
+                    kind: ValidationIssueKind::ExclusiveEntryConflict,
+                    message: format!(
+                        "Entries '{}' and '{}' overlap, but at least one of them is exclusive",
+                        entry1.entry.title, entry2.entry.title
+                    ),
+                    entity_ids: vec![entry1.entry.id, entry2.entry.id],
+                });
+            }
+        }
+    }
+
+    let entries_by_id: HashMap<EntryId, &FullEntry> =
+        entries.iter().map(|e| (e.entry.id, e)).collect();
+    for double_booking in double_bookings {
+        let (Some(entry1), Some(entry2)) = (
+            entries_by_id.get(&double_booking.entry_ids.0),
+            entries_by_id.get(&double_booking.entry_ids.1),
+        ) else {
+            continue;
+        };
+        // Don't report the same overlap twice
+        if exclusive_conflicts.contains(&(entry1.entry.id, entry2.entry.id)) {
+            continue;
+        }
+        issues.push(ValidationIssue {
+            severity: ValidationSeverity::Warning,
+            kind: ValidationIssueKind::RoomDoubleBooking,
+            message: format!(
+                "Entries '{}' and '{}' overlap in the same room",
+                entry1.entry.title, entry2.entry.title
+            ),
+            entity_ids: vec![double_booking.room_id, entry1.entry.id, entry2.entry.id],
+        });
+    }
+
+    let used_categories: HashSet<_> = entries.iter().map(|e| e.entry.category).collect();
+    for category in categories {
+        if !used_categories.contains(&category.id) {
+            issues.push(ValidationIssue {
+                severity: ValidationSeverity::Info,
+                kind: ValidationIssueKind::CategoryWithoutEntries,
+                message: format!("Category '{}' has no entries", category.title),
+                entity_ids: vec![category.id],
+            });
+        }
+    }
+
+    let event_dates = event.basic_data.begin_date..=event.basic_data.end_date;
+    for announcement in announcements {
+        let announcement = &announcement.announcement;
+        let outside_of_event = [announcement.begin_date, announcement.end_date]
+            .iter()
+            .flatten()
+            .any(|date| !event_dates.contains(date));
+        if outside_of_event {
+            issues.push(ValidationIssue {
+                severity: ValidationSeverity::Warning,
+                kind: ValidationIssueKind::AnnouncementOutsideEventDates,
+                message: "Announcement is shown on dates outside the event".to_owned(),
+                entity_ids: vec![announcement.id],
+            });
+        }
+    }
+
+    issues.sort_by_key(|issue| issue.severity);
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_store::models::{
+        Announcement, AnnouncementType, EntrySubmissionMode, Event, EventClockInfo,
+        EventDayTimeSchedule,
+    };
+    use uuid::Uuid;
+
+    fn make_category(title: &str) -> Category {
+        Category {
+            id: Uuid::now_v7(),
+            title: title.to_string(),
+            icon: "".to_string(),
+            color: "000000".to_string(),
+            event_id: 1,
+            is_official: false,
+            last_updated: Default::default(),
+            sort_key: 0,
+        }
+    }
+
+    fn make_announcement(begin_date: Option<&str>, end_date: Option<&str>) -> FullAnnouncement {
+        FullAnnouncement {
+            announcement: Announcement {
+                id: Uuid::now_v7(),
+                event_id: 1,
+                announcement_type: AnnouncementType::Info,
+                text: "Test".to_string(),
+                show_with_days: true,
+                begin_date: begin_date.map(|d| d.parse().unwrap()),
+                end_date: end_date.map(|d| d.parse().unwrap()),
+                begin_time: None,
+                end_time: None,
+                show_with_categories: false,
+                show_with_all_categories: true,
+                show_with_rooms: false,
+                show_with_all_rooms: true,
+                sort_key: 0,
+                last_updated: Default::default(),
+            },
+            category_ids: vec![],
+            room_ids: vec![],
+        }
+    }
+
+    #[test]
+    fn test_validate_event_contents() {
+        let event = ExtendedEvent {
+            basic_data: Event {
+                id: 1,
+                title: "Test".to_owned(),
+                begin_date: "2025-08-13".parse().unwrap(),
+                end_date: "2025-08-15".parse().unwrap(),
+                slug: None,
+                begin_time: None,
+                end_time: None,
+                archived: false,
+            },
+            clock_info: EventClockInfo {
+                timezone: chrono_tz::Tz::Europe__Berlin,
+                effective_begin_of_day: "05:30".parse().unwrap(),
+            },
+            default_time_schedule: EventDayTimeSchedule { sections: vec![] },
+            preceding_event_id: None,
+            subsequent_event_id: None,
+            entry_submission_mode: EntrySubmissionMode::Disabled,
+        };
+        let used_category = make_category("Sport");
+        let unused_category = make_category("Musik");
+        let room_id = Uuid::now_v7();
+
+        let valid_entry = FullEntry::test_builder("2025-08-13T10:00:00Z", "2025-08-13T12:00:00Z")
+            .title("Volleyball")
+            .category(used_category.id)
+            .build();
+        let outside_entry = FullEntry::test_builder("2025-08-16T10:00:00Z", "2025-08-16T12:00:00Z")
+            .title("Abreise")
+            .category(used_category.id)
+            .build();
+        let untitled_entry =
+            FullEntry::test_builder("2025-08-14T10:00:00Z", "2025-08-14T12:00:00Z")
+                .title(" ")
+                .category(used_category.id)
+                .build();
+        let mut exclusive_entry =
+            FullEntry::test_builder("2025-08-13T11:00:00Z", "2025-08-13T13:00:00Z")
+                .title("Chorprobe")
+                .category(used_category.id)
+                .build();
+        exclusive_entry.entry.is_exclusive = true;
+        // Cancelled entries don't conflict with exclusive entries
+        let mut cancelled_entry =
+            FullEntry::test_builder("2025-08-13T12:00:00Z", "2025-08-13T14:00:00Z")
+                .title("Schach")
+                .category(used_category.id)
+                .build();
+        cancelled_entry.entry.is_cancelled = true;
+        let non_exclusive_entry =
+            FullEntry::test_builder("2025-08-14T11:00:00Z", "2025-08-14T13:00:00Z")
+                .title("Tischtennis")
+                .category(used_category.id)
+                .build();
+        let double_bookings = [
+            // Already reported as conflict with the exclusive entry
+            RoomDoubleBooking {
+                room_id,
+                entry_ids: (valid_entry.entry.id, exclusive_entry.entry.id),
+            },
+            RoomDoubleBooking {
+                room_id,
+                entry_ids: (untitled_entry.entry.id, non_exclusive_entry.entry.id),
+            },
+        ];
+        let announcements = [
+            make_announcement(Some("2025-08-13"), Some("2025-08-15")),
+            make_announcement(None, None),
+            make_announcement(Some("2025-08-14"), Some("2025-08-20")),
+        ];
+
+        let issues = validate_event_contents(
+            &event,
+            &[
+                valid_entry.clone(),
+                outside_entry.clone(),
+                untitled_entry.clone(),
+                exclusive_entry.clone(),
+                cancelled_entry,
+                non_exclusive_entry.clone(),
+            ],
+            &double_bookings,
+            &[used_category, unused_category.clone()],
+            &announcements,
+        );
+
+        let summary: Vec<(ValidationSeverity, ValidationIssueKind, Vec<Uuid>)> = issues
+            .into_iter()
+            .map(|issue| (issue.severity, issue.kind, issue.entity_ids))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    ValidationSeverity::Error,
+                    ValidationIssueKind::EntryOutsideEventDates,
+                    vec![outside_entry.entry.id]
+                ),
+                (
+                    ValidationSeverity::Error,
+                    ValidationIssueKind::ExclusiveEntryConflict,
+                    vec![valid_entry.entry.id, exclusive_entry.entry.id]
+                ),
+                (
+                    ValidationSeverity::Warning,
+                    ValidationIssueKind::EntryWithoutTitle,
+                    vec![untitled_entry.entry.id]
+                ),
+                (
+                    ValidationSeverity::Warning,
+                    ValidationIssueKind::RoomDoubleBooking,
+                    vec![
+                        room_id,
+                        untitled_entry.entry.id,
+                        non_exclusive_entry.entry.id
+                    ]
+                ),
+                (
+                    ValidationSeverity::Warning,
+                    ValidationIssueKind::AnnouncementOutsideEventDates,
+                    vec![announcements[2].announcement.id]
+                ),
+                (
+                    ValidationSeverity::Info,
+                    ValidationIssueKind::CategoryWithoutEntries,
+                    vec![unused_category.id]
+                ),
+            ]
+        );
+    }
+}
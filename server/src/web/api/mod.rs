@@ -4,11 +4,16 @@ mod endpoints_announcement;
 mod endpoints_auth;
 mod endpoints_category;
 mod endpoints_entry;
+mod endpoints_entry_template;
 mod endpoints_event;
 mod endpoints_event_extended;
+mod endpoints_grid;
 mod endpoints_passphrase;
 mod endpoints_previous_date;
 mod endpoints_room;
+mod endpoints_updates;
+mod endpoints_validation;
+mod openapi;
 
 use crate::auth_session::SessionToken;
 use crate::data_store::StoreError;
@@ -18,13 +23,18 @@ use actix_web::error::JsonPayloadError;
 use actix_web::{
     HttpResponse,
     error::ResponseError,
-    http::{StatusCode, header::ContentType},
+    http::{
+        StatusCode,
+        header::{ContentType, RETRY_AFTER},
+    },
     web,
 };
 use serde_json::json;
 
-pub fn configure_app(cfg: &mut web::ServiceConfig) {
-    let api = get_api_service();
+/// Register the REST API service. JSON request bodies larger than `max_json_body_bytes` are
+/// rejected.
+pub fn configure_app(cfg: &mut web::ServiceConfig, max_json_body_bytes: usize) {
+    let api = get_api_service(max_json_body_bytes);
 
     if get_allow_api_cors_from_env() {
         // Enable Cross-Origin Resource Sharing from any Origin for REST API.
@@ -34,6 +44,7 @@ pub fn configure_app(cfg: &mut web::ServiceConfig) {
             .allow_any_origin()
             .allowed_methods(vec!["GET", "POST", "PUT", "DELETE"])
             .allowed_header(<SessionTokenHeader as actix_web::http::header::Header>::name())
+            .allowed_header(actix_web::http::header::AUTHORIZATION)
             .allowed_header(actix_web::http::header::CONTENT_TYPE)
             .max_age(3600);
 
@@ -43,42 +54,94 @@ pub fn configure_app(cfg: &mut web::ServiceConfig) {
     }
 }
 
-fn get_api_service() -> actix_web::Scope {
-    let json_config =
-        web::JsonConfig::default().error_handler(|err, _req| APIError::InvalidJson(err).into());
+fn get_api_service(max_json_body_bytes: usize) -> actix_web::Scope {
+    let json_config = web::JsonConfig::default()
+        .limit(max_json_body_bytes)
+        .error_handler(|err, _req| APIError::InvalidJson(err).into());
     web::scope("/api/v1")
         .app_data(json_config)
         .service(endpoints_auth::check_all_events_authorization)
         .service(endpoints_auth::check_authorization)
         .service(endpoints_event::list_events)
         .service(endpoints_event::get_event_info)
+        .service(endpoints_event::get_event_stats)
+        .service(endpoints_event::get_event_neighbors)
         .service(endpoints_event_extended::get_extended_event_info)
         .service(endpoints_event_extended::update_extended_event)
+        .service(endpoints_event_extended::get_event_days)
+        .service(endpoints_event_extended::get_current_event_day)
+        .service(endpoints_grid::get_event_grid)
+        .service(endpoints_grid::get_event_overview)
         .service(endpoints_auth::authorize)
         .service(endpoints_auth::drop_access_role)
+        .service(endpoints_auth::create_share_link)
         .service(endpoints_entry::list_entries)
         .service(endpoints_entry::list_all_entries)
+        // must be registered before get_entry to not be shadowed by its {entry_id} path
+        .service(endpoints_entry::list_current_entries)
+        .service(endpoints_entry::list_entries_with_schedule_sections)
+        .service(endpoints_grid::get_daily_entry_counts)
+        .service(endpoints_entry::list_room_entries)
         .service(endpoints_entry::get_entry)
+        .service(endpoints_entry::list_responsible_persons)
+        .service(endpoints_entry::reassign_responsible_person)
+        .service(endpoints_entry::bulk_set_entry_category)
+        .service(endpoints_entry::shift_entries)
         .service(endpoints_entry::create_or_update_entry)
         .service(endpoints_entry::change_entry)
+        .service(endpoints_entry::move_entry)
+        .service(endpoints_entry::copy_entry_from_event)
         .service(endpoints_entry::submit_entry)
         .service(endpoints_entry::delete_entry)
+        .service(endpoints_entry::get_entry_audit_log)
+        .service(endpoints_entry::get_entry_changes)
+        .service(endpoints_entry_template::list_entry_templates)
+        .service(endpoints_entry_template::create_entry_template)
+        .service(endpoints_entry_template::delete_entry_template)
+        .service(endpoints_previous_date::list_all_previous_dates)
         .service(endpoints_previous_date::create_or_update_previous_date)
         .service(endpoints_previous_date::delete_previous_date)
+        .service(endpoints_previous_date::list_deleted_previous_dates)
+        .service(endpoints_previous_date::restore_previous_date)
         .service(endpoints_room::list_rooms)
+        .service(endpoints_room::list_rooms_of_events)
         .service(endpoints_room::create_or_update_room)
+        .service(endpoints_room::change_room)
         .service(endpoints_room::delete_room)
+        .service(endpoints_room::merge_room)
+        .service(endpoints_room::list_room_conflicts)
+        .service(endpoints_validation::validate_event)
         .service(endpoints_category::list_categories)
+        .service(endpoints_category::list_categories_with_entry_counts)
+        // Must be registered before create_or_update_category to not be shadowed by it
+        .service(endpoints_category::reorder_categories)
         .service(endpoints_category::create_or_update_category)
         .service(endpoints_category::delete_category)
+        .service(endpoints_category::list_deleted_categories)
+        .service(endpoints_category::restore_category)
+        .service(endpoints_category::cancel_category_entries)
+        .service(endpoints_category::uncancel_category_entries)
         .service(endpoints_announcement::list_announcements)
+        .service(endpoints_announcement::list_active_announcements)
+        .service(endpoints_announcement::get_announcement)
         .service(endpoints_announcement::create_or_update_announcement)
         .service(endpoints_announcement::change_announcement)
         .service(endpoints_announcement::delete_announcement)
         .service(endpoints_passphrase::list_passphrases)
+        .service(endpoints_passphrase::get_passphrase_scope)
         .service(endpoints_passphrase::create_passphrase)
         .service(endpoints_passphrase::change_passphrase)
         .service(endpoints_passphrase::delete_passphrase)
+        .service(endpoints_updates::get_updates)
+        .service(endpoints_updates::live_updates)
+        .service(openapi::get_openapi_document)
+        .default_service(web::to(not_found_handler))
+}
+
+/// Fallback handler for unknown API routes, to respond with a JSON error body (instead of actix'
+/// default plain text response), like all other API endpoints.
+async fn not_found_handler() -> Result<HttpResponse, APIError> {
+    Err(APIError::NotExisting)
 }
 
 #[derive(Debug)]
@@ -93,11 +156,21 @@ pub enum APIError {
     InvalidSessionToken,
     AuthenticationFailed {
         passphrase_expired: bool,
+        /// Set if the client has been blocked due to too many failed attempts
+        retry_after: Option<std::time::Duration>,
     },
     InvalidJson(actix_web::error::JsonPayloadError),
     InvalidData(String),
+    /// The query parameters of the request are invalid or incomplete
+    InvalidQuery(String),
+    /// A value of the request data does not have the required format, e.g. an invalid color code
+    InvalidFormat(String),
     ViolatingDataIntegrity(String),
     ViolatingDataPolicy(String),
+    /// The room cannot be deleted without confirmation, because it is used by upcoming entries
+    RoomInUse {
+        entry_count: i64,
+    },
     EntityIdMissmatch,
     TransactionConflict,
     ConcurrentEditConflict,
@@ -118,6 +191,39 @@ impl APIError {
     }
 }
 
+impl APIError {
+    /// Stable, machine-readable identifier of the error type, to be used by clients for
+    /// distinguishing errors without parsing the human-readable message.
+    fn error_code(&self) -> &'static str {
+        match self {
+            Self::NotExisting => "NOT_EXISTING",
+            Self::AlreadyExisting => "ALREADY_EXISTING",
+            Self::PermissionDenied { .. } => "PERMISSION_DENIED",
+            Self::NoSessionToken => "NO_SESSION_TOKEN",
+            Self::InvalidSessionToken => "INVALID_SESSION_TOKEN",
+            Self::AuthenticationFailed {
+                retry_after: Some(_),
+                ..
+            } => "TOO_MANY_AUTHENTICATION_ATTEMPTS",
+            Self::AuthenticationFailed { .. } => "AUTHENTICATION_FAILED",
+            Self::InvalidJson(
+                JsonPayloadError::Overflow { .. } | JsonPayloadError::OverflowKnownLength { .. },
+            ) => "PAYLOAD_TOO_LARGE",
+            Self::InvalidJson(_) => "INVALID_JSON",
+            Self::InvalidData(_) => "INVALID_DATA",
+            Self::InvalidQuery(_) => "INVALID_QUERY",
+            Self::InvalidFormat(_) => "INVALID_FORMAT",
+            Self::ViolatingDataIntegrity(_) => "VIOLATING_DATA_INTEGRITY",
+            Self::ViolatingDataPolicy(_) => "VIOLATING_DATA_POLICY",
+            Self::RoomInUse { .. } => "ROOM_IN_USE",
+            Self::EntityIdMissmatch => "ENTITY_ID_MISMATCH",
+            Self::TransactionConflict => "TRANSACTION_CONFLICT",
+            Self::ConcurrentEditConflict => "CONCURRENT_EDIT_CONFLICT",
+            Self::InternalError(_) => "INTERNAL_ERROR",
+        }
+    }
+}
+
 impl Display for APIError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -141,28 +247,47 @@ impl Display for APIError {
             Self::InvalidSessionToken => {
                 f.write_str("This action requires authentication, but client authentication session given by the client is not valid.")?
             },
-            Self::AuthenticationFailed{passphrase_expired} => {
-                f.write_str("Authentication with the given passphrase failed.")?;
-                if *passphrase_expired {
-                    f.write_str(" The passphrase is not yet or no longer valid.")?;
+            Self::AuthenticationFailed{passphrase_expired, retry_after} => {
+                if retry_after.is_some() {
+                    f.write_str("Too many failed authentication attempts. Please retry later.")?;
+                } else {
+                    f.write_str("Authentication with the given passphrase failed.")?;
+                    if *passphrase_expired {
+                        f.write_str(" The passphrase is not yet or no longer valid.")?;
+                    }
                 }
             }
             Self::InternalError(s) => {
                 f.write_str("Internal error: ")?;
                 f.write_str(s)?;
             },
+            Self::InvalidJson(
+                JsonPayloadError::Overflow { limit }
+                | JsonPayloadError::OverflowKnownLength { limit, .. },
+            ) => {
+                write!(f, "Request body is too large. JSON request data must not be larger than {} bytes.", limit)?;
+            },
             Self::InvalidJson(e) => {
                 write!(f, "Invalid JSON request data: {}", e)?;
             },
             Self::InvalidData(e) => {
                 write!(f, "Invalid request data: {}", e)?;
             },
+            Self::InvalidQuery(e) => {
+                write!(f, "Invalid query parameters: {}", e)?;
+            },
+            Self::InvalidFormat(e) => {
+                write!(f, "Invalid format of request data: {}", e)?;
+            },
             Self::ViolatingDataIntegrity(e) => {
                 write!(f, "Operation cannot be performed: {}", e)?;
             },
             Self::ViolatingDataPolicy(e) => {
                 write!(f, "Posted data violates a policy: {}", e)?;
             },
+            Self::RoomInUse { entry_count } => {
+                write!(f, "The room is still used by {} upcoming entries. Set `force` or give replacement rooms to delete it anyway.", entry_count)?;
+            },
             Self::EntityIdMissmatch => {
                 f.write_str("Entity id in given data does not match URL")?;
             },
@@ -181,12 +306,43 @@ impl ResponseError for APIError {
     fn error_response(&self) -> HttpResponse {
         let message = format!("{}", self);
 
-        HttpResponse::build(self.status_code())
-            .insert_header(ContentType::json())
-            .json(json!({
-                "httpCode": self.status_code().as_u16(),
-                "message": message
-            }))
+        let mut response = HttpResponse::build(self.status_code());
+        if let Self::AuthenticationFailed {
+            retry_after: Some(retry_after),
+            ..
+        } = self
+        {
+            response.insert_header((
+                RETRY_AFTER,
+                retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0),
+            ));
+        }
+        if let Self::TransactionConflict = self {
+            response.insert_header((RETRY_AFTER, 1));
+        }
+        let mut body = json!({
+            "httpCode": self.status_code().as_u16(),
+            "errorCode": self.error_code(),
+            "message": message
+        });
+        if let Self::PermissionDenied {
+            required_privilege, ..
+        } = self
+        {
+            let mut required_roles: Vec<serde_json::Value> = Vec::new();
+            for role in required_privilege.qualifying_roles() {
+                let role: kueaplan_api_types::AuthorizationRole = (*role).into();
+                let role = json!(role);
+                if !required_roles.contains(&role) {
+                    required_roles.push(role);
+                }
+            }
+            body["requiredRoles"] = required_roles.into();
+        }
+        if let Self::RoomInUse { entry_count } = self {
+            body["entryCount"] = (*entry_count).into();
+        }
+        response.insert_header(ContentType::json()).json(body)
     }
     fn status_code(&self) -> StatusCode {
         match self {
@@ -195,18 +351,27 @@ impl ResponseError for APIError {
             Self::PermissionDenied { .. } => StatusCode::FORBIDDEN,
             Self::NoSessionToken => StatusCode::FORBIDDEN,
             Self::InvalidSessionToken => StatusCode::FORBIDDEN,
+            Self::AuthenticationFailed {
+                retry_after: Some(_),
+                ..
+            } => StatusCode::TOO_MANY_REQUESTS,
             Self::AuthenticationFailed { .. } => StatusCode::FORBIDDEN,
             Self::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Self::InvalidJson(e) => match e {
                 JsonPayloadError::ContentType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                JsonPayloadError::Overflow { .. }
+                | JsonPayloadError::OverflowKnownLength { .. } => StatusCode::PAYLOAD_TOO_LARGE,
                 JsonPayloadError::Deserialize(json_error) if json_error.is_data() => {
                     StatusCode::UNPROCESSABLE_ENTITY
                 }
                 _ => StatusCode::BAD_REQUEST,
             },
             &APIError::InvalidData(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::InvalidQuery(_) => StatusCode::BAD_REQUEST,
+            Self::InvalidFormat(_) => StatusCode::BAD_REQUEST,
             Self::ViolatingDataIntegrity(_) => StatusCode::CONFLICT,
             Self::ViolatingDataPolicy(_) => StatusCode::CONFLICT,
+            Self::RoomInUse { .. } => StatusCode::CONFLICT,
             &APIError::EntityIdMissmatch => StatusCode::UNPROCESSABLE_ENTITY,
             &APIError::TransactionConflict => StatusCode::SERVICE_UNAVAILABLE,
             Self::ConcurrentEditConflict => StatusCode::CONFLICT,
@@ -239,6 +404,7 @@ impl From<StoreError> for APIError {
             },
             StoreError::PolicyViolation(p) => Self::ViolatingDataPolicy(p.to_string()),
             StoreError::InvalidInputData(e) => Self::InvalidData(e),
+            StoreError::RoomInUse { entry_count } => Self::RoomInUse { entry_count },
             StoreError::InvalidDataInDatabase(e) => Self::InternalError(format!(
                 "Data queried from database could not be deserialized: {}",
                 e
@@ -261,16 +427,28 @@ impl From<crate::auth_session::SessionError> for APIError {
     }
 }
 
-struct SessionTokenHeader(String);
+/// Authentication information of an API client, taken from the `X-SESSION-TOKEN` header and/or an
+/// API key in the `Authorization: Bearer` header. At least one of them must be present.
+struct SessionTokenHeader {
+    token: Option<String>,
+    api_key: Option<String>,
+}
 #[allow(clippy::identity_op)] // We want to explicitly state that it's "1" year
 const SESSION_TOKEN_MAX_AGE: std::time::Duration = std::time::Duration::from_secs(1 * 86400 * 365);
 
 impl SessionTokenHeader {
     fn session_token(
         &self,
-        secret: &str,
+        secrets: &[String],
     ) -> Result<crate::auth_session::SessionToken, crate::auth_session::SessionError> {
-        SessionToken::from_string(&self.0, secret, SESSION_TOKEN_MAX_AGE)
+        let mut session_token = match &self.token {
+            Some(token) => SessionToken::from_string(token, secrets, SESSION_TOKEN_MAX_AGE)?,
+            None => SessionToken::new(),
+        };
+        if let Some(api_key) = &self.api_key {
+            session_token.set_api_key(api_key.clone());
+        }
+        Ok(session_token)
     }
 }
 
@@ -278,7 +456,7 @@ impl actix_web::http::header::TryIntoHeaderValue for SessionTokenHeader {
     type Error = actix_web::http::header::InvalidHeaderValue;
 
     fn try_into_value(self) -> Result<actix_web::http::header::HeaderValue, Self::Error> {
-        self.0.parse()
+        self.token.unwrap_or_default().parse()
     }
 }
 
@@ -290,13 +468,21 @@ impl actix_web::http::header::Header for SessionTokenHeader {
     }
 
     fn parse<M: actix_web::HttpMessage>(msg: &M) -> Result<Self, actix_web::error::ParseError> {
-        Ok(Self(
-            msg.headers()
-                .get(Self::name())
-                .ok_or(actix_web::error::ParseError::Header)?
-                .to_str()
-                .unwrap_or("")
-                .to_owned(),
-        ))
+        let token = msg
+            .headers()
+            .get(Self::name())
+            .map(|value| value.to_str().unwrap_or("").to_owned());
+        let api_key = msg
+            .headers()
+            .get(actix_web::http::header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            // The authentication scheme is case-insensitive (RFC 9110, section 11.1)
+            .and_then(|value| value.trim_start().split_once(' '))
+            .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("Bearer"))
+            .map(|(_, key)| key.trim().to_owned());
+        if token.is_none() && api_key.is_none() {
+            return Err(actix_web::error::ParseError::Header);
+        }
+        Ok(Self { token, api_key })
     }
 }
@@ -0,0 +1,171 @@
+//! Serving of the OpenAPI description of this API.
+//!
+//! The OpenAPI document is maintained by hand in `etc/spec/`, split into multiple files which
+//! reference each other via relative `$ref`s. For serving it as a single document, the files are
+//! embedded into the binary and bundled on first use: The JSON schemas from `etc/spec/schema/`
+//! (which correspond to the types in `kueaplan_api_types`) are placed in `components/schemas` and
+//! referenced from there. All other referenced files are inlined.
+
+use actix_web::{Responder, get, web};
+use rust_embed::Embed;
+use serde_json::{Map, Value};
+use std::sync::LazyLock;
+
+#[derive(Embed)]
+#[folder = "../etc/spec/"]
+struct SpecFiles;
+
+static OPENAPI_DOCUMENT: LazyLock<Value> = LazyLock::new(|| {
+    bundle_openapi_document().expect("Embedded OpenAPI specification should be bundleable")
+});
+
+#[get("/openapi.json")]
+async fn get_openapi_document() -> impl Responder {
+    web::Json(&*OPENAPI_DOCUMENT)
+}
+
+/// Load the embedded `openapi.json` and resolve all references to other files of the
+/// specification.
+fn bundle_openapi_document() -> Result<Value, String> {
+    let mut schemas = Map::new();
+    let mut document = resolve_references(load_spec_file("openapi.json")?, "", &mut schemas)?;
+    let components = document
+        .as_object_mut()
+        .ok_or("OpenAPI document is not a JSON object")?
+        .entry("components")
+        .or_insert_with(|| Value::Object(Map::new()))
+        .as_object_mut()
+        .ok_or("'components' of OpenAPI document is not a JSON object")?;
+    components.insert("schemas".to_owned(), Value::Object(schemas));
+    Ok(document)
+}
+
+fn load_spec_file(path: &str) -> Result<Value, String> {
+    let file = SpecFiles::get(path).ok_or_else(|| format!("File {} not found", path))?;
+    serde_json::from_slice(&file.data).map_err(|e| format!("Could not parse {}: {}", path, e))
+}
+
+/// Recursively replace all references to other files in `value`, which has been loaded from a file
+/// in the directory `base_dir`.
+///
+/// References to schema files are replaced by references to `#/components/schemas/{name}` and the
+/// (resolved) schema is added to `schemas`, if not present yet. References to all other files are
+/// replaced with the file's resolved content. Document-internal references (`#/…`) are kept.
+fn resolve_references(
+    value: Value,
+    base_dir: &str,
+    schemas: &mut Map<String, Value>,
+) -> Result<Value, String> {
+    match value {
+        Value::Object(object) => {
+            if let Some(Value::String(reference)) = object.get("$ref")
+                && !reference.starts_with('#')
+            {
+                let path = join_path(base_dir, reference)?;
+                let (dir, file_name) = path.rsplit_once('/').unwrap_or(("", &path));
+                if dir == "schema" {
+                    let name = file_name.trim_end_matches(".json").to_owned();
+                    if !schemas.contains_key(&name) {
+                        // Insert a placeholder first to terminate recursion on cyclic references
+                        schemas.insert(name.clone(), Value::Null);
+                        let mut schema = resolve_references(load_spec_file(&path)?, dir, schemas)?;
+                        if let Some(schema) = schema.as_object_mut() {
+                            // JSON Schema meta data is not allowed in OpenAPI schema objects
+                            schema.remove("$schema");
+                            schema.remove("$id");
+                        }
+                        schemas.insert(name.clone(), schema);
+                    }
+                    let mut result = Map::new();
+                    result.insert(
+                        "$ref".to_owned(),
+                        Value::String(format!("#/components/schemas/{}", name)),
+                    );
+                    return Ok(Value::Object(result));
+                }
+                return resolve_references(load_spec_file(&path)?, dir, schemas);
+            }
+            Ok(Value::Object(
+                object
+                    .into_iter()
+                    .map(|(key, value)| Ok((key, resolve_references(value, base_dir, schemas)?)))
+                    .collect::<Result<_, String>>()?,
+            ))
+        }
+        Value::Array(array) => Ok(Value::Array(
+            array
+                .into_iter()
+                .map(|value| resolve_references(value, base_dir, schemas))
+                .collect::<Result<_, String>>()?,
+        )),
+        value => Ok(value),
+    }
+}
+
+/// Join the relative `reference` path to the `base_dir` (both using '/' as separator), resolving
+/// `.` and `..` segments.
+fn join_path(base_dir: &str, reference: &str) -> Result<String, String> {
+    let mut segments: Vec<&str> = base_dir.split('/').filter(|s| !s.is_empty()).collect();
+    for segment in reference.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments
+                    .pop()
+                    .ok_or_else(|| format!("Reference {} leaves the spec directory", reference))?;
+            }
+            segment => segments.push(segment),
+        }
+    }
+    Ok(segments.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_join_path() {
+        assert_eq!(
+            join_path("", "./paths/entries.json").unwrap(),
+            "paths/entries.json"
+        );
+        assert_eq!(
+            join_path("paths", "../schema/Entry.json").unwrap(),
+            "schema/Entry.json"
+        );
+        assert_eq!(
+            join_path("schema", "./Category.json").unwrap(),
+            "schema/Category.json"
+        );
+        assert!(join_path("", "../foo.json").is_err());
+    }
+
+    #[test]
+    fn test_bundle_openapi_document() {
+        let document = bundle_openapi_document().unwrap();
+        assert!(document["paths"]["/events/{eventId}/entries"]["get"].is_object());
+        assert_eq!(
+            document["components"]["securitySchemes"]["sessionTokenAuth"]["name"],
+            "X-SESSION-TOKEN"
+        );
+        assert!(document["components"]["schemas"]["Entry"]["properties"].is_object());
+        assert!(document["components"]["schemas"]["Error"].is_object());
+
+        // No references to other files should be left
+        fn check_references(value: &Value) {
+            match value {
+                Value::Object(object) => {
+                    if let Some(reference) = object.get("$ref") {
+                        let reference = reference.as_str().unwrap();
+                        assert!(reference.starts_with("#/"), "unresolved $ref {}", reference);
+                    }
+                    object.values().for_each(check_references);
+                }
+                Value::Array(array) => array.iter().for_each(check_references),
+                _ => {}
+            }
+        }
+        check_references(&document);
+    }
+}
@@ -1,8 +1,7 @@
-use crate::auth_session::SessionToken;
-use crate::data_store::auth_token::Privilege;
 use crate::data_store::models::{Category, EventClockInfo, ExtendedEvent, FullEntry, Room};
 use crate::data_store::{CategoryId, EntryFilter, EntryId, EventId, RoomId};
 use crate::web::AppState;
+use crate::web::ical::FeedAuthentication;
 use crate::web::time_calculation::get_effective_date;
 use crate::web::ui::error::AppError;
 use actix_web::error::UrlGenerationError;
@@ -12,10 +11,6 @@ use actix_web::{HttpRequest, HttpResponseBuilder, Responder, get, web};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
-#[allow(clippy::identity_op)] // We want to explicitly state that it's "1" year
-pub const SESSION_COOKIE_MAX_AGE: std::time::Duration =
-    std::time::Duration::from_secs(1 * 86400 * 365);
-
 #[get("/events/{event_id}/frab-xml")]
 async fn frab_xml(
     path: web::Path<i32>,
@@ -25,14 +20,8 @@ async fn frab_xml(
 ) -> Result<impl Responder, AppError> {
     let event_id = path.into_inner();
     let query = query.into_inner();
-    let session_token =
-        SessionToken::from_string(&query.session_token, &state.secret, SESSION_COOKIE_MAX_AGE)
-            .map_err(|session_error| AppError::PermissionDenied {
-                required_privilege: Privilege::ShowKueaPlan,
-                event_id,
-                session_error: Some(session_error),
-                privilege_expired: false,
-            })?;
+    let feed_authentication =
+        FeedAuthentication::from_query_token(&query.session_token, &state.secrets, event_id)?;
 
     let url_for_event = |event_id: &EventId| {
         http_request
@@ -53,7 +42,7 @@ async fn frab_xml(
 
     let (event, entries, rooms, categories) = web::block(move || -> Result<_, AppError> {
         let mut store = state.store.get_facade()?;
-        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        let auth = feed_authentication.get_auth_token(&mut *store, event_id)?;
         Ok((
             store.get_extended_event(&auth, event_id)?,
             store.get_published_entries_filtered(&auth, event_id, EntryFilter::default())?,
@@ -82,7 +71,7 @@ async fn frab_xml(
 
 #[derive(Deserialize, Serialize)]
 pub struct FrabXmlQueryParams {
-    #[serde(rename = "token")]
+    #[serde(default, rename = "token")]
     pub session_token: String,
 }
 
@@ -0,0 +1,27 @@
+use crate::data_store::StoreError;
+use crate::web::AppState;
+use actix_web::{HttpResponse, Responder, get, web};
+use serde_json::json;
+
+/// Liveness/readiness probe for container orchestration: Checks the database connectivity.
+///
+/// Does not require authentication.
+#[get("/healthz")]
+async fn healthz(state: web::Data<AppState>) -> impl Responder {
+    let result = web::block(move || -> Result<_, StoreError> {
+        let mut store = state.store.get_facade()?;
+        store.ping()
+    })
+    .await;
+    match result {
+        Ok(Ok(())) => HttpResponse::Ok().json(json!({"status": "ok"})),
+        Ok(Err(e)) => HttpResponse::ServiceUnavailable().json(json!({
+            "status": "error",
+            "message": format!("Database is not available: {}", e),
+        })),
+        Err(_) => HttpResponse::ServiceUnavailable().json(json!({
+            "status": "error",
+            "message": "Could not get thread from thread pool for database operation.",
+        })),
+    }
+}
@@ -127,7 +127,9 @@ pub async fn error_logging_middleware<B: actix_web::body::MessageBody>(
                             .unwrap_or("unknown"),
                     );
                 }
-                APIError::AuthenticationFailed { passphrase_expired } => {
+                APIError::AuthenticationFailed {
+                    passphrase_expired, ..
+                } => {
                     warn!(
                         "HTTP {} authentication failed. Client: <{}>{}",
                         response.response().status(),
@@ -147,8 +149,11 @@ pub async fn error_logging_middleware<B: actix_web::body::MessageBody>(
                 | APIError::AlreadyExisting
                 | APIError::InvalidJson(_)
                 | APIError::InvalidData(_)
+                | APIError::InvalidQuery(_)
+                | APIError::InvalidFormat(_)
                 | APIError::ViolatingDataIntegrity(_)
                 | APIError::ViolatingDataPolicy(_)
+                | APIError::RoomInUse { .. }
                 | APIError::EntityIdMissmatch
                 | APIError::TransactionConflict
                 | APIError::ConcurrentEditConflict => {}
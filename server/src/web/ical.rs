@@ -1,7 +1,7 @@
-use crate::auth_session::SessionToken;
-use crate::data_store::auth_token::Privilege;
+use crate::auth_session::{FeedAccessToken, SessionToken};
+use crate::data_store::auth_token::{AuthToken, Privilege};
 use crate::data_store::models::{Category, Event, FullEntry, Room};
-use crate::data_store::{CategoryId, RoomId};
+use crate::data_store::{CategoryId, EventId, KueaPlanStoreFacade, RoomId, StoreError};
 use crate::web::AppState;
 use crate::web::ui::error::AppError;
 use crate::web::util::EntryFilterAsQuery;
@@ -24,21 +24,21 @@ async fn ical(
 ) -> Result<impl Responder, AppError> {
     let event_id = path.into_inner();
     let query = query.into_inner();
-    let session_token =
-        SessionToken::from_string(&query.session_token, &state.secret, SESSION_COOKIE_MAX_AGE)
-            .map_err(|session_error| AppError::PermissionDenied {
-                required_privilege: Privilege::ShowKueaPlan,
-                event_id,
-                session_error: Some(session_error),
-                privilege_expired: false,
-            })?;
+    let reminder_minutes = query.reminder_minutes;
+    let feed_authentication =
+        FeedAuthentication::from_query_token(&query.session_token, &state.secrets, event_id)?;
 
     let (event, entries, rooms, categories) = web::block(move || -> Result<_, AppError> {
         let mut store = state.store.get_facade()?;
-        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        let auth = feed_authentication.get_auth_token(&mut *store, event_id)?;
         Ok((
             store.get_event(event_id)?,
-            store.get_published_entries_filtered(&auth, event_id, query.entry_filter.into())?,
+            // Room reservations are no events to be attended, so they are omitted by default
+            store.get_published_entries_filtered(
+                &auth,
+                event_id,
+                query.entry_filter.into_filter(false),
+            )?,
             store.get_rooms(&auth, event_id)?,
             store.get_categories(&auth, event_id)?,
         ))
@@ -51,15 +51,82 @@ async fn ical(
             disposition: actix_web::http::header::DispositionType::Inline,
             parameters: vec![DispositionParam::Filename(String::from("kueaplan.ics"))],
         })
-        .body(generate_ical(event, entries, rooms, categories)))
+        .body(entries_to_ics(
+            event,
+            entries,
+            rooms,
+            categories,
+            reminder_minutes,
+        )))
+}
+
+/// Authentication of a calendar feed client, given by the `token` query parameter.
+///
+/// The token is either a revocable [FeedAccessToken], created on the command line, or a serialized
+/// [SessionToken], as used in the calendar links shown in the web UI.
+pub enum FeedAuthentication {
+    FeedToken(FeedAccessToken),
+    Session(SessionToken),
+}
+
+impl FeedAuthentication {
+    /// Parse and validate the signature of the client-provided token string. A missing, invalid or
+    /// expired token or a feed token for another event results in a "permission denied" error.
+    pub fn from_query_token(
+        token: &str,
+        secrets: &[String],
+        event_id: EventId,
+    ) -> Result<Self, AppError> {
+        let permission_denied = |session_error| AppError::PermissionDenied {
+            required_privilege: Privilege::ShowKueaPlan,
+            event_id,
+            session_error,
+            privilege_expired: false,
+        };
+        if FeedAccessToken::is_feed_token(token) {
+            let feed_token = FeedAccessToken::from_string(token, secrets)
+                .map_err(|e| permission_denied(Some(e)))?;
+            if feed_token.get_event_id() != event_id {
+                return Err(permission_denied(None));
+            }
+            Ok(Self::FeedToken(feed_token))
+        } else {
+            Ok(Self::Session(
+                SessionToken::from_string(token, secrets, SESSION_COOKIE_MAX_AGE)
+                    .map_err(|e| permission_denied(Some(e)))?,
+            ))
+        }
+    }
+
+    /// Get the client's AuthToken from the store. For revoked feed tokens, the AuthToken does not
+    /// grant any privileges.
+    pub fn get_auth_token(
+        &self,
+        store: &mut dyn KueaPlanStoreFacade,
+        event_id: EventId,
+    ) -> Result<AuthToken, StoreError> {
+        match self {
+            Self::FeedToken(feed_token) => {
+                store.get_auth_token_for_feed_token(event_id, feed_token.get_feed_token_id())
+            }
+            Self::Session(session_token) => {
+                store.get_auth_token_for_session(session_token, event_id)
+            }
+        }
+    }
 }
 
 #[derive(Deserialize, Serialize)]
 pub struct ICalQueryParams {
-    #[serde(rename = "token")]
+    // A missing token is treated like an invalid one, resulting in a "permission denied" error
+    #[serde(default, rename = "token")]
     session_token: String,
     #[serde(flatten)]
     entry_filter: EntryFilterAsQuery,
+    /// If given, a display alarm is added to each calendar event, triggering the given number of
+    /// minutes before the entry's begin.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    reminder_minutes: Option<u32>,
 }
 
 impl ICalQueryParams {
@@ -67,15 +134,22 @@ impl ICalQueryParams {
         Self {
             session_token,
             entry_filter: EntryFilterAsQuery::default(),
+            reminder_minutes: None,
         }
     }
 }
 
-fn generate_ical(
+/// Generate an iCalendar (ICS) document with one calendar event for each of the given entries.
+///
+/// Cancelled entries are skipped. Rooms and categories are used for looking up the location and
+/// category names of the entries. If `reminder_minutes` is given, a display alarm is added to each
+/// calendar event.
+pub fn entries_to_ics(
     event: Event,
     entries: Vec<FullEntry>,
     rooms: Vec<Room>,
     categories: Vec<Category>,
+    reminder_minutes: Option<u32>,
 ) -> String {
     let mut calendar = icalendar::Calendar::new()
         .name(&format!("KüA-Plan {}", event.title))
@@ -100,12 +174,27 @@ fn generate_ical(
         if let Some(category) = categories_by_id.get(&entry.entry.category) {
             event.append_property(icalendar::Property::new("CATEGORIES", &category.title));
         }
+        if entry.entry.is_tentative {
+            event.status(icalendar::EventStatus::Tentative);
+        }
+        if let Some(reminder_minutes) = reminder_minutes {
+            event.alarm(generate_ical_alarm(&entry, reminder_minutes));
+        }
         calendar.push(event);
     }
 
     calendar.to_string()
 }
 
+fn generate_ical_alarm(entry: &FullEntry, reminder_minutes: u32) -> icalendar::Alarm {
+    let trigger = chrono::Duration::minutes(-i64::from(reminder_minutes));
+    icalendar::Alarm::display(&entry.entry.title, trigger)
+        // icalendar formats the trigger duration in seconds ("-PT900S"), which is valid, but we
+        // prefer the more readable (and more commonly used) notation in minutes
+        .add_property("TRIGGER", format!("-PT{}M", reminder_minutes))
+        .done()
+}
+
 fn generate_ical_description(entry: &FullEntry) -> String {
     let mut description = entry.entry.comment.clone();
     if !entry.entry.responsible_person.is_empty() {
@@ -130,21 +219,138 @@ fn generate_ical_description(entry: &FullEntry) -> String {
     description
 }
 
+/// Generate the LOCATION of the calendar event from the titles of the entry's rooms, e.g.
+/// "Aula, Sportplatz (nur bei gutem Wetter)". The entry's room comment is appended in
+/// parentheses or used alone, if the entry has no rooms.
 fn generate_ical_location(entry: &FullEntry, rooms: &BTreeMap<RoomId, &Room>) -> String {
-    let room_names: Vec<String> = entry
+    let room_names: Vec<&str> = entry
         .room_ids
         .iter()
         .filter_map(|room_id| rooms.get(room_id))
-        .map(|r| r.title.clone())
+        .map(|r| r.title.as_str())
         .collect();
 
-    let mut location = room_names.join("\n");
-    if !entry.entry.room_comment.is_empty() {
-        if !location.is_empty() {
-            location.push_str("; ");
+    let location = room_names.join(", ");
+    match (location.is_empty(), entry.entry.room_comment.is_empty()) {
+        (_, true) => location,
+        (true, false) => entry.entry.room_comment.clone(),
+        (false, false) => format!("{} ({})", location, entry.entry.room_comment),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_entry() -> FullEntry {
+        FullEntry::test_builder("2025-01-06T12:00:00Z", "2025-01-06T13:30:00Z")
+            .id(uuid::Uuid::nil())
+            .responsible_person("Max Mustermann")
+            .build()
+    }
+
+    fn example_event() -> Event {
+        Event {
+            id: 1,
+            title: "TestAka".to_owned(),
+            begin_date: "2025-01-05".parse().unwrap(),
+            end_date: "2025-01-10".parse().unwrap(),
+            slug: None,
+            begin_time: None,
+            end_time: None,
+            archived: false,
         }
-        location.push_str(&entry.entry.room_comment);
     }
 
-    location
+    #[test]
+    fn test_generate_ical_with_reminder() {
+        let result = entries_to_ics(
+            example_event(),
+            vec![example_entry()],
+            vec![],
+            vec![],
+            Some(15),
+        );
+        assert!(result.contains("BEGIN:VALARM\r\n"));
+        assert!(result.contains("ACTION:DISPLAY\r\n"));
+        assert!(result.contains("TRIGGER:-PT15M\r\n"));
+        assert!(result.contains("DESCRIPTION:Drachenfliegen\r\n"));
+    }
+
+    #[test]
+    fn test_generate_ical_without_reminder() {
+        let result = entries_to_ics(example_event(), vec![example_entry()], vec![], vec![], None);
+        assert!(result.contains("BEGIN:VEVENT"));
+        assert!(!result.contains("VALARM"));
+    }
+
+    #[test]
+    fn test_generate_ical_tentative() {
+        let result = entries_to_ics(example_event(), vec![example_entry()], vec![], vec![], None);
+        assert!(!result.contains("STATUS:"));
+
+        let mut entry = example_entry();
+        entry.entry.is_tentative = true;
+        let result = entries_to_ics(example_event(), vec![entry], vec![], vec![], None);
+        assert!(result.contains("STATUS:TENTATIVE\r\n"));
+    }
+
+    #[test]
+    fn test_generate_ical_location() {
+        let room = |title: &str| Room {
+            id: uuid::Uuid::now_v7(),
+            title: title.to_owned(),
+            description: "".to_owned(),
+            event_id: 1,
+            last_updated: chrono::Utc::now(),
+        };
+        let rooms = vec![room("Aula"), room("Sportplatz")];
+        let rooms_by_id: BTreeMap<RoomId, &Room> = rooms.iter().map(|r| (r.id, r)).collect();
+
+        let mut entry = example_entry();
+        assert_eq!(generate_ical_location(&entry, &rooms_by_id), "");
+        entry.room_ids = vec![rooms[1].id, rooms[0].id];
+        assert_eq!(
+            generate_ical_location(&entry, &rooms_by_id),
+            "Sportplatz, Aula"
+        );
+        entry.entry.room_comment = "bei Regen in der Turnhalle".to_owned();
+        assert_eq!(
+            generate_ical_location(&entry, &rooms_by_id),
+            "Sportplatz, Aula (bei Regen in der Turnhalle)"
+        );
+        let result = entries_to_ics(
+            example_event(),
+            vec![entry.clone()],
+            rooms.clone(),
+            vec![],
+            None,
+        );
+        assert!(result.contains("LOCATION:Sportplatz\\, Aula (bei Regen in der Turnhalle)\r\n"));
+
+        entry.room_ids = vec![];
+        assert_eq!(
+            generate_ical_location(&entry, &rooms_by_id),
+            "bei Regen in der Turnhalle"
+        );
+    }
+
+    #[test]
+    fn test_ical_query_params_reminder_minutes() {
+        let query: ICalQueryParams =
+            serde_urlencoded::from_str("token=abc&reminder_minutes=15").unwrap();
+        assert_eq!(query.reminder_minutes, Some(15));
+        assert!(
+            serde_urlencoded::from_str::<ICalQueryParams>("token=abc&reminder_minutes=-5").is_err()
+        );
+        assert!(
+            serde_urlencoded::from_str::<ICalQueryParams>("token=abc&reminder_minutes=x").is_err()
+        );
+    }
+
+    #[test]
+    fn test_ical_query_params_missing_token() {
+        let query: ICalQueryParams = serde_urlencoded::from_str("reminder_minutes=15").unwrap();
+        assert_eq!(query.session_token, "");
+    }
 }
@@ -0,0 +1,204 @@
+//! Push notifications about changed entries to connected clients
+//!
+//! Every change of an entry is published to a [LiveUpdates] broadcast channel, which is part of the
+//! [AppState](super::AppState). The live updates WebSocket endpoint subscribes to this channel and
+//! forwards the notifications for the requested event to the client, using
+//! [live_updates_websocket_stream].
+//!
+//! Changes of entries, which are not visible to every participant (unpublished or orga-only
+//! entries), are only forwarded to clients with the
+//! [ManageEntries](crate::data_store::auth_token::Privilege::ManageEntries) privilege. The
+//! client's privileges are checked again for every notification, so that the connection is closed
+//! as soon as the client's session does not allow to see the KüA-Plan anymore.
+use crate::data_store::models::EntryAuditAction;
+use crate::data_store::{EntryId, EventId};
+use actix_codec::{Decoder, Encoder};
+use actix_http::ws;
+use actix_web::web::{Bytes, BytesMut, Payload};
+use futures_util::{Stream, StreamExt, stream};
+use std::future::Future;
+use tokio::sync::{broadcast, mpsc};
+
+/// Number of notifications, which are buffered for each subscriber. If a (slow) subscriber lags
+/// behind by more notifications, the oldest ones are dropped for it.
+const CHANNEL_CAPACITY: usize = 64;
+
+/// A single change of an entry, published via [LiveUpdates]
+#[derive(Clone, Debug)]
+pub struct EntryChange {
+    pub event_id: EventId,
+    pub entry_id: EntryId,
+    pub action: EntryAuditAction,
+    /// The entry is published and not orga-only before or after the change, i.e. the change is
+    /// relevant for all clients, which may see the KüA-Plan. Otherwise, it is only sent to clients
+    /// with the [ManageEntries](crate::data_store::auth_token::Privilege::ManageEntries)
+    /// privilege.
+    pub public: bool,
+}
+
+/// Broadcast channel for publishing changes of entries to all subscribed live update clients
+#[derive(Clone)]
+pub struct LiveUpdates {
+    sender: broadcast::Sender<EntryChange>,
+}
+
+impl LiveUpdates {
+    pub fn new() -> Self {
+        Self {
+            sender: broadcast::Sender::new(CHANNEL_CAPACITY),
+        }
+    }
+
+    /// Notify all current subscribers, which are allowed to see the entry, about a change of the
+    /// given entry. See [EntryChange::public] for the meaning of `public`.
+    pub fn publish_entry_change(
+        &self,
+        event_id: EventId,
+        entry_id: EntryId,
+        action: EntryAuditAction,
+        public: bool,
+    ) {
+        // Sending only fails if there are no subscribers, in which case, nobody is interested.
+        let _ = self.sender.send(EntryChange {
+            event_id,
+            entry_id,
+            action,
+            public,
+        });
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<EntryChange> {
+        self.sender.subscribe()
+    }
+}
+
+/// Access level of a live updates client, which determines the notifications sent to it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LiveUpdatesAccess {
+    /// The client may not see the KüA-Plan (anymore), so the connection is closed
+    None,
+    /// The client may only see changes of public entries
+    Public,
+    /// The client may see changes of all entries, i.e. has the ManageEntries privilege
+    Internal,
+}
+
+impl Default for LiveUpdates {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Create the response body stream of a live updates WebSocket connection for the given event.
+///
+/// The stream contains the encoded WebSocket frames of all notifications about changes of the
+/// event's entries, as JSON-encoded [kueaplan_api_types::EntryChangeNotification]s. Before sending
+/// a notification, the client's current access level is determined with `check_access`: Changes of
+/// non-public entries are only included for [LiveUpdatesAccess::Internal]. If the client has lost
+/// access to the event, the connection is closed with a policy violation Close frame.
+/// Incoming frames from the client are read from the request `payload`: Pings are answered and a
+/// Close frame (or the end of the payload) closes the connection. Any other incoming messages are
+/// ignored.
+pub fn live_updates_websocket_stream<F, Fut>(
+    event_id: EventId,
+    check_access: F,
+    changes: broadcast::Receiver<EntryChange>,
+    payload: Payload,
+) -> impl Stream<Item = Result<Bytes, ws::ProtocolError>> + 'static
+where
+    F: Fn() -> Fut + 'static,
+    Fut: Future<Output = LiveUpdatesAccess> + 'static,
+{
+    let (control_sender, control_receiver) = mpsc::unbounded_channel();
+    actix_web::rt::spawn(handle_incoming_frames(payload, control_sender));
+
+    let notifications = stream::unfold(changes, |mut changes| async move {
+        loop {
+            match changes.recv().await {
+                Ok(change) => return Some((change, changes)),
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    })
+    .filter(move |change| std::future::ready(change.event_id == event_id))
+    .filter_map(move |change| {
+        let access = check_access();
+        async move {
+            match access.await {
+                LiveUpdatesAccess::None => Some(ws::Message::Close(Some(ws::CloseReason {
+                    code: ws::CloseCode::Policy,
+                    description: Some("Not authorized to see the KüA-Plan anymore".to_owned()),
+                }))),
+                LiveUpdatesAccess::Public if !change.public => None,
+                LiveUpdatesAccess::Public | LiveUpdatesAccess::Internal => {
+                    let notification = kueaplan_api_types::EntryChangeNotification {
+                        entry_id: change.entry_id,
+                        action: change.action.into(),
+                    };
+                    Some(ws::Message::Text(
+                        serde_json::to_string(&notification)
+                            .expect("Notification should be serializable")
+                            .into(),
+                    ))
+                }
+            }
+        }
+    });
+    let control_messages = stream::unfold(control_receiver, |mut receiver| async move {
+        receiver.recv().await.map(|message| (message, receiver))
+    });
+
+    // End the stream directly after sending a Close frame
+    let messages = Box::pin(stream::select(notifications, control_messages));
+    let messages = stream::unfold((messages, false), |(mut messages, closed)| async move {
+        if closed {
+            return None;
+        }
+        let message = messages.next().await?;
+        let closed = matches!(message, ws::Message::Close(_));
+        Some((message, (messages, closed)))
+    });
+
+    let mut codec = ws::Codec::new();
+    messages.map(move |message| {
+        let mut buffer = BytesMut::new();
+        codec.encode(message, &mut buffer)?;
+        Ok(buffer.freeze())
+    })
+}
+
+/// Read and decode the WebSocket frames from the client and send the required responses to
+/// `control_sender`.
+async fn handle_incoming_frames(
+    mut payload: Payload,
+    control_sender: mpsc::UnboundedSender<ws::Message>,
+) {
+    let mut codec = ws::Codec::new();
+    let mut buffer = BytesMut::new();
+    loop {
+        match codec.decode(&mut buffer) {
+            Ok(Some(ws::Frame::Ping(data))) => {
+                let _ = control_sender.send(ws::Message::Pong(data));
+            }
+            Ok(Some(ws::Frame::Close(reason))) => {
+                let _ = control_sender.send(ws::Message::Close(reason));
+                return;
+            }
+            Ok(Some(_)) => {}
+            Ok(None) => match payload.next().await {
+                Some(Ok(chunk)) => buffer.extend_from_slice(&chunk),
+                _ => {
+                    let _ = control_sender.send(ws::Message::Close(None));
+                    return;
+                }
+            },
+            Err(e) => {
+                log::debug!("Invalid WebSocket frame received: {}", e);
+                let _ =
+                    control_sender.send(ws::Message::Close(Some(ws::CloseCode::Protocol.into())));
+                return;
+            }
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use crate::web::AppState;
+use actix_web::{HttpResponse, Responder, get, web};
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Upper bounds (in seconds) of the buckets of the request duration histogram
+const DURATION_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// In-memory counters of the served HTTP requests, exposed in the Prometheus text format via the
+/// `/metrics` endpoint.
+pub struct Metrics {
+    /// Number of requests per status class (1xx to 5xx)
+    requests_by_status_class: [AtomicU64; 5],
+    /// Number of requests per bucket of [DURATION_BUCKETS] (non-cumulative). The last element
+    /// counts the requests slower than the largest bucket.
+    duration_buckets: [AtomicU64; DURATION_BUCKETS.len() + 1],
+    duration_sum_micros: AtomicU64,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self {
+            requests_by_status_class: Default::default(),
+            duration_buckets: Default::default(),
+            duration_sum_micros: AtomicU64::new(0),
+        }
+    }
+
+    /// Count a served request with the given response status and processing duration.
+    pub fn record_request(&self, status: actix_web::http::StatusCode, duration: Duration) {
+        let status_class = (status.as_u16() / 100).clamp(1, 5) as usize - 1;
+        self.requests_by_status_class[status_class].fetch_add(1, Ordering::Relaxed);
+        let bucket = DURATION_BUCKETS
+            .iter()
+            .position(|le| duration.as_secs_f64() <= *le)
+            .unwrap_or(DURATION_BUCKETS.len());
+        self.duration_buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.duration_sum_micros
+            .fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    /// Render all metrics in the Prometheus text exposition format.
+    fn render(&self, db_connections_in_use: u32) -> String {
+        let mut result = String::new();
+        result.push_str(
+            "# HELP http_requests_total Total number of HTTP requests by response status class.\n\
+             # TYPE http_requests_total counter\n",
+        );
+        for (i, count) in self.requests_by_status_class.iter().enumerate() {
+            writeln!(
+                result,
+                "http_requests_total{{status=\"{}xx\"}} {}",
+                i + 1,
+                count.load(Ordering::Relaxed)
+            )
+            .unwrap();
+        }
+
+        result.push_str(
+            "# HELP http_request_duration_seconds Duration of processing HTTP requests.\n\
+             # TYPE http_request_duration_seconds histogram\n",
+        );
+        let mut cumulative_count = 0;
+        for (le, count) in DURATION_BUCKETS.iter().zip(self.duration_buckets.iter()) {
+            cumulative_count += count.load(Ordering::Relaxed);
+            writeln!(
+                result,
+                "http_request_duration_seconds_bucket{{le=\"{}\"}} {}",
+                le, cumulative_count
+            )
+            .unwrap();
+        }
+        cumulative_count += self.duration_buckets[DURATION_BUCKETS.len()].load(Ordering::Relaxed);
+        writeln!(
+            result,
+            "http_request_duration_seconds_bucket{{le=\"+Inf\"}} {}",
+            cumulative_count
+        )
+        .unwrap();
+        writeln!(
+            result,
+            "http_request_duration_seconds_sum {}",
+            self.duration_sum_micros.load(Ordering::Relaxed) as f64 / 1e6
+        )
+        .unwrap();
+        writeln!(
+            result,
+            "http_request_duration_seconds_count {}",
+            cumulative_count
+        )
+        .unwrap();
+
+        result.push_str(
+            "# HELP db_pool_connections_in_use Number of database connections currently in use.\n\
+             # TYPE db_pool_connections_in_use gauge\n",
+        );
+        writeln!(
+            result,
+            "db_pool_connections_in_use {}",
+            db_connections_in_use
+        )
+        .unwrap();
+        result
+    }
+}
+
+/// Middleware for counting all requests in the [Metrics] of the [AppState], if metrics are enabled.
+pub async fn metrics_middleware<B: actix_web::body::MessageBody>(
+    req: actix_web::dev::ServiceRequest,
+    next: actix_web::middleware::Next<B>,
+) -> Result<actix_web::dev::ServiceResponse<B>, actix_web::Error> {
+    let metrics = req
+        .app_data::<web::Data<AppState>>()
+        .and_then(|state| state.metrics.clone());
+    let start = Instant::now();
+    let response = next.call(req).await?;
+    if let Some(metrics) = metrics {
+        metrics.record_request(response.status(), start.elapsed());
+    }
+    Ok(response)
+}
+
+/// Expose the request and database metrics in the Prometheus text format.
+///
+/// Does not require authentication. Only available if enabled via the `METRICS_ENABLED`
+/// environment variable.
+#[get("/metrics")]
+async fn prometheus_metrics(state: web::Data<AppState>) -> impl Responder {
+    let Some(metrics) = state.metrics.as_ref() else {
+        return HttpResponse::NotFound().finish();
+    };
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(metrics.render(state.store.connections_in_use()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::StatusCode;
+
+    #[test]
+    fn test_render_metrics() {
+        let metrics = Metrics::new();
+        metrics.record_request(StatusCode::OK, Duration::from_millis(3));
+        metrics.record_request(StatusCode::NO_CONTENT, Duration::from_millis(200));
+        metrics.record_request(StatusCode::NOT_FOUND, Duration::from_secs(20));
+
+        let result = metrics.render(3);
+        let lines: Vec<&str> = result.lines().collect();
+        assert!(lines.contains(&"http_requests_total{status=\"2xx\"} 2"));
+        assert!(lines.contains(&"http_requests_total{status=\"4xx\"} 1"));
+        assert!(lines.contains(&"http_requests_total{status=\"5xx\"} 0"));
+        assert!(lines.contains(&"http_request_duration_seconds_bucket{le=\"0.005\"} 1"));
+        assert!(lines.contains(&"http_request_duration_seconds_bucket{le=\"0.25\"} 2"));
+        assert!(lines.contains(&"http_request_duration_seconds_bucket{le=\"10\"} 2"));
+        assert!(lines.contains(&"http_request_duration_seconds_bucket{le=\"+Inf\"} 3"));
+        assert!(lines.contains(&"http_request_duration_seconds_count 3"));
+        assert!(lines.contains(&"http_request_duration_seconds_sum 20.203"));
+        assert!(lines.contains(&"db_pool_connections_in_use 3"));
+    }
+}
@@ -1,63 +1,139 @@
 use crate::cli_error::CliError;
 use crate::data_store::get_store_from_env;
 use crate::setup::{
-    get_admin_email_from_env, get_admin_name_from_env, get_listen_address_from_env,
-    get_listen_port_from_env, get_secret_from_env,
+    SessionCookieConfig, get_admin_email_from_env, get_admin_name_from_env,
+    get_auth_rate_limit_max_attempts_from_env, get_auth_rate_limit_window_from_env,
+    get_listen_address_from_env, get_listen_port_from_env, get_max_json_body_bytes_from_env,
+    get_metrics_enabled_from_env, get_secrets_from_env, get_server_admin_api_key_from_env,
+    get_session_cookie_config_from_env, get_shutdown_timeout_from_env,
+    get_trusted_proxies_from_env,
 };
 use crate::web::http_error_logging::error_logging_middleware;
+use crate::web::live_updates::LiveUpdates;
+use crate::web::metrics::{Metrics, metrics_middleware};
+use crate::web::rate_limit::AuthRateLimiter;
 use actix_web::{App, HttpServer, middleware, web};
 use std::sync::Arc;
 
 mod api;
 mod frab_xml;
+mod health_check;
 mod http_error_logging;
 mod ical;
+pub mod live_updates;
+mod metrics;
+mod rate_limit;
 mod redirect_endpoints;
-mod time_calculation;
+pub(crate) mod time_calculation;
 mod ui;
 mod util;
 
 pub fn serve() -> Result<(), CliError> {
     let state = AppState::new()?;
-    actix_web::rt::System::new()
-        .block_on(
-            HttpServer::new(move || {
-                App::new()
-                    .configure(api::configure_app)
-                    .configure(ui::configure_app)
-                    .service(redirect_endpoints::index)
-                    .service(ical::ical)
-                    .service(frab_xml::frab_xml)
-                    .service(redirect_endpoints::event_redirect_by_slug)
-                    .app_data(web::Data::new(state.clone()))
-                    .wrap(actix_web::middleware::from_fn(error_logging_middleware))
-                    .wrap(middleware::Compress::default())
-            })
-            .bind((get_listen_address_from_env()?, get_listen_port_from_env()?))
-            .map_err(CliError::BindError)?
-            .run(),
-        )
-        .map_err(CliError::ServerError)
+    let shutdown_timeout = get_shutdown_timeout_from_env()?;
+    let max_json_body_bytes = get_max_json_body_bytes_from_env()?;
+    actix_web::rt::System::new().block_on(async move {
+        let server = HttpServer::new(move || {
+            App::new()
+                .configure(|cfg| api::configure_app(cfg, max_json_body_bytes))
+                .configure(ui::configure_app)
+                .service(redirect_endpoints::index)
+                .service(ical::ical)
+                .service(frab_xml::frab_xml)
+                .service(health_check::healthz)
+                .service(metrics::prometheus_metrics)
+                .service(redirect_endpoints::event_redirect_by_slug)
+                .app_data(web::Data::new(state.clone()))
+                .wrap(actix_web::middleware::from_fn(error_logging_middleware))
+                .wrap(middleware::Compress::default())
+                .wrap(actix_web::middleware::from_fn(metrics_middleware))
+        })
+        .bind((get_listen_address_from_env()?, get_listen_port_from_env()?))
+        .map_err(CliError::BindError)?
+        // We handle the termination signals ourselves, to be able to log the graceful shutdown
+        .disable_signals()
+        .shutdown_timeout(shutdown_timeout.as_secs())
+        .run();
+
+        let server_handle = server.handle();
+        actix_web::rt::spawn(async move {
+            wait_for_shutdown_signal().await;
+            log::info!(
+                "Received shutdown signal, shutting down gracefully (timeout: {} s) ...",
+                shutdown_timeout.as_secs()
+            );
+            server_handle.stop(true).await;
+        });
+
+        server.await.map_err(CliError::ServerError)
+    })
+}
+
+/// Wait until the process receives SIGINT (Ctrl+C) or, on Unix platforms, SIGTERM.
+async fn wait_for_shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use actix_web::rt::signal::unix::{SignalKind, signal};
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                tokio::select! {
+                    _ = actix_web::rt::signal::ctrl_c() => {},
+                    _ = sigterm.recv() => {},
+                }
+                return;
+            }
+            Err(e) => log::warn!("Could not register SIGTERM handler: {}", e),
+        }
+    }
+    if let Err(e) = actix_web::rt::signal::ctrl_c().await {
+        log::warn!("Could not register SIGINT handler: {}", e);
+        // Never shut down, instead of immediately shutting down
+        std::future::pending::<()>().await;
+    }
 }
 
 #[derive(Clone)]
 pub struct AppState {
     store: Arc<dyn crate::data_store::KuaPlanStore>,
-    secret: String,
+    /// The application secrets for signing and validating session tokens. The first one is the
+    /// current secret, used for signing. All of them are accepted for validation.
+    secrets: Vec<String>,
     admin: AdminInfo,
+    auth_rate_limiter: Arc<AuthRateLimiter>,
+    /// The API key for global server admin actions via the REST API. None if not configured.
+    server_admin_api_key: Option<String>,
+    /// Broadcast channel for pushing changes of entries to the clients of the live updates endpoint
+    live_updates: LiveUpdates,
+    session_cookie_config: SessionCookieConfig,
+    /// Request metrics for the `/metrics` endpoint. None if metrics are disabled.
+    metrics: Option<Arc<Metrics>>,
 }
 
 impl AppState {
     pub fn new() -> Result<Self, CliError> {
         Ok(Self {
             store: Arc::new(get_store_from_env()?),
-            secret: get_secret_from_env()?,
+            secrets: get_secrets_from_env()?,
             admin: AdminInfo {
                 name: get_admin_name_from_env()?,
                 email: get_admin_email_from_env()?,
             },
+            auth_rate_limiter: Arc::new(AuthRateLimiter::new(
+                get_auth_rate_limit_max_attempts_from_env()?,
+                get_auth_rate_limit_window_from_env()?,
+                get_trusted_proxies_from_env()?,
+            )),
+            server_admin_api_key: get_server_admin_api_key_from_env(),
+            live_updates: LiveUpdates::new(),
+            session_cookie_config: get_session_cookie_config_from_env()?,
+            metrics: get_metrics_enabled_from_env().then(|| Arc::new(Metrics::new())),
         })
     }
+
+    /// The current application secret, to be used for signing new session tokens
+    fn signing_secret(&self) -> &str {
+        &self.secrets[0]
+    }
 }
 
 #[derive(Clone)]
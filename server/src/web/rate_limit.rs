@@ -0,0 +1,250 @@
+use actix_web::HttpRequest;
+use actix_web::http::header::HeaderMap;
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// In-memory counter of failed authentication attempts per client IP address, used for throttling
+/// brute-force attacks on passphrases.
+///
+/// A client is blocked, when it has `max_attempts` failed attempts within `window`, counted from
+/// its first failed attempt. The block is lifted when the window has passed.
+///
+/// Clients are identified by the peer address of their TCP connection. The `Forwarded` and
+/// `X-Forwarded-For` headers are only considered, when the request has been received from one of
+/// the `trusted_proxies`, so that clients cannot evade the rate limiting by spoofing these headers.
+pub struct AuthRateLimiter {
+    attempts: Mutex<HashMap<IpAddr, (u32, Instant)>>,
+    max_attempts: u32,
+    window: Duration,
+    trusted_proxies: Vec<IpAddr>,
+}
+
+impl AuthRateLimiter {
+    pub fn new(max_attempts: u32, window: Duration, trusted_proxies: Vec<IpAddr>) -> Self {
+        Self {
+            attempts: Mutex::new(HashMap::new()),
+            max_attempts,
+            window,
+            trusted_proxies,
+        }
+    }
+
+    /// Determine the IP address of the client of the given request for rate limiting.
+    ///
+    /// Returns None if the peer address of the request is unknown.
+    pub fn client_ip_address(&self, req: &HttpRequest) -> Option<IpAddr> {
+        let peer_address = req.peer_addr()?.ip();
+        Some(self.resolve_client_ip_address(peer_address, &forwarded_for_addresses(req.headers())))
+    }
+
+    /// Follow the chain of forwarding addresses from the peer address backwards, as long as the
+    /// current address is a trusted proxy. The first untrusted (or unparsable) address is the
+    /// client's address.
+    fn resolve_client_ip_address(
+        &self,
+        peer_address: IpAddr,
+        forwarded_for: &[Option<IpAddr>],
+    ) -> IpAddr {
+        let mut client_address = peer_address;
+        for address in forwarded_for.iter().rev() {
+            if !self.trusted_proxies.contains(&client_address) {
+                break;
+            }
+            match address {
+                Some(address) => client_address = *address,
+                None => break,
+            }
+        }
+        client_address
+    }
+
+    /// Check if the client with the given IP address is allowed to attempt an authentication and,
+    /// if so, count the attempt as failed.
+    ///
+    /// Checking and counting is done atomically, so that concurrent requests of a client cannot
+    /// exceed the number of allowed attempts. After a successful authentication, the counter must
+    /// be cleared with [reset()](Self::reset).
+    ///
+    /// Returns `Err(retry_after)` with the remaining duration of the block if the client has
+    /// exceeded the number of allowed failed attempts.
+    pub fn check_and_record_attempt(&self, ip: IpAddr) -> Result<(), Duration> {
+        self.check_and_record_attempt_at(ip, Instant::now())
+    }
+
+    /// Reset the counter of failed attempts of the client with the given IP address, e.g. after a
+    /// successful authentication.
+    pub fn reset(&self, ip: IpAddr) {
+        self.attempts
+            .lock()
+            .expect("Rate limiter mutex should not be poisoned")
+            .remove(&ip);
+    }
+
+    fn check_and_record_attempt_at(&self, ip: IpAddr, now: Instant) -> Result<(), Duration> {
+        let mut attempts = self
+            .attempts
+            .lock()
+            .expect("Rate limiter mutex should not be poisoned");
+        // Drop all expired windows to not grow the map indefinitely
+        attempts.retain(|_, (_, window_start)| now < *window_start + self.window);
+        let (count, window_start) = attempts.entry(ip).or_insert((0, now));
+        if *count >= self.max_attempts {
+            return Err(*window_start + self.window - now);
+        }
+        *count += 1;
+        Ok(())
+    }
+}
+
+/// Extract the list of forwarding client addresses from the `Forwarded` header or, if not present,
+/// the `X-Forwarded-For` header, in the order in which they have been added by the proxies. Entries
+/// which are not a valid IP address (e.g. obfuscated identifiers or "unknown") are returned as
+/// None.
+fn forwarded_for_addresses(headers: &HeaderMap) -> Vec<Option<IpAddr>> {
+    let forwarded: Vec<&str> = headers
+        .get_all(actix_web::http::header::FORWARDED)
+        .filter_map(|v| v.to_str().ok())
+        .collect();
+    if !forwarded.is_empty() {
+        forwarded
+            .iter()
+            .flat_map(|v| v.split(','))
+            .map(|element| {
+                element
+                    .split(';')
+                    .filter_map(|pair| pair.split_once('='))
+                    .find(|(key, _)| key.trim().eq_ignore_ascii_case("for"))
+                    .and_then(|(_, value)| parse_forwarded_address(value.trim().trim_matches('"')))
+            })
+            .collect()
+    } else {
+        headers
+            .get_all(actix_web::http::header::X_FORWARDED_FOR)
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .map(|address| parse_forwarded_address(address.trim()))
+            .collect()
+    }
+}
+
+/// Parse an IP address from a forwarding header, which may optionally include a port number or be
+/// enclosed in square brackets (IPv6).
+fn parse_forwarded_address(address: &str) -> Option<IpAddr> {
+    address
+        .parse::<IpAddr>()
+        .or_else(|_| address.parse::<SocketAddr>().map(|a| a.ip()))
+        .or_else(|_| {
+            address
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .parse::<IpAddr>()
+        })
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limit_repeated_failures() {
+        let limiter = AuthRateLimiter::new(3, Duration::from_secs(60), vec![]);
+        let ip: IpAddr = "192.0.2.1".parse().unwrap();
+        let other_ip: IpAddr = "2001:db8::1".parse().unwrap();
+        let start = Instant::now();
+
+        for i in 0..3 {
+            assert_eq!(
+                limiter.check_and_record_attempt_at(ip, start + Duration::from_secs(i)),
+                Ok(())
+            );
+        }
+        assert_eq!(
+            limiter.check_and_record_attempt_at(ip, start + Duration::from_secs(10)),
+            Err(Duration::from_secs(50))
+        );
+        // Blocked attempts don't extend the block
+        assert_eq!(
+            limiter.check_and_record_attempt_at(ip, start + Duration::from_secs(20)),
+            Err(Duration::from_secs(40))
+        );
+        // Other clients are not affected
+        assert_eq!(limiter.check_and_record_attempt_at(other_ip, start), Ok(()));
+
+        // The block is lifted after the window has passed
+        assert_eq!(
+            limiter.check_and_record_attempt_at(ip, start + Duration::from_secs(60)),
+            Ok(())
+        );
+        assert_eq!(
+            limiter.check_and_record_attempt_at(ip, start + Duration::from_secs(61)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_reset() {
+        let limiter = AuthRateLimiter::new(2, Duration::from_secs(60), vec![]);
+        let ip: IpAddr = "192.0.2.1".parse().unwrap();
+        let start = Instant::now();
+
+        assert_eq!(limiter.check_and_record_attempt_at(ip, start), Ok(()));
+        limiter.reset(ip);
+        assert_eq!(limiter.check_and_record_attempt_at(ip, start), Ok(()));
+        assert_eq!(limiter.check_and_record_attempt_at(ip, start), Ok(()));
+        assert!(limiter.check_and_record_attempt_at(ip, start).is_err());
+    }
+
+    #[test]
+    fn test_client_ip_address_untrusted_peer() {
+        let limiter = AuthRateLimiter::new(3, Duration::from_secs(60), vec![]);
+        let req = actix_web::test::TestRequest::default()
+            .peer_addr("192.0.2.1:4711".parse().unwrap())
+            .insert_header(("X-Forwarded-For", "198.51.100.7"))
+            .insert_header(("Forwarded", "for=198.51.100.8"))
+            .to_http_request();
+        assert_eq!(
+            limiter.client_ip_address(&req),
+            Some("192.0.2.1".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_client_ip_address_trusted_proxy() {
+        let limiter = AuthRateLimiter::new(
+            3,
+            Duration::from_secs(60),
+            vec!["::1".parse().unwrap(), "192.0.2.2".parse().unwrap()],
+        );
+        // The first (spoofed) address must be ignored, as it has not been added by a trusted proxy
+        let req = actix_web::test::TestRequest::default()
+            .peer_addr("[::1]:4711".parse().unwrap())
+            .insert_header(("X-Forwarded-For", "203.0.113.5, 198.51.100.7, 192.0.2.2"))
+            .to_http_request();
+        assert_eq!(
+            limiter.client_ip_address(&req),
+            Some("198.51.100.7".parse().unwrap())
+        );
+
+        let req = actix_web::test::TestRequest::default()
+            .peer_addr("[::1]:4711".parse().unwrap())
+            .insert_header(("X-Forwarded-For", "203.0.113.5"))
+            .insert_header(("Forwarded", "for=\"[2001:db8::7]:1234\";proto=https"))
+            .to_http_request();
+        assert_eq!(
+            limiter.client_ip_address(&req),
+            Some("2001:db8::7".parse().unwrap())
+        );
+
+        // Without forwarding headers, the proxy itself is the client
+        let req = actix_web::test::TestRequest::default()
+            .peer_addr("[::1]:4711".parse().unwrap())
+            .to_http_request();
+        assert_eq!(
+            limiter.client_ip_address(&req),
+            Some("::1".parse().unwrap())
+        );
+    }
+}
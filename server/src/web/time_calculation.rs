@@ -1,19 +1,27 @@
-use crate::data_store::models::{EventClockInfo, ExtendedEvent};
-use chrono::{DateTime, NaiveDate, TimeZone, Timelike};
+use crate::data_store::models::{
+    Event, EventClockInfo, EventDayScheduleSection, EventDayTimeSchedule, ExtendedEvent,
+};
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone};
 
 /// Calculate the effective date of a timestamp, considering the EFFECTIVE_BEGIN_OF_DAY (in local
 /// time) instead of 0:00 as date boundary
+///
+/// Timestamps with a local time before the EFFECTIVE_BEGIN_OF_DAY belong to the previous day's
+/// programme. A timestamp exactly at the EFFECTIVE_BEGIN_OF_DAY belongs to the new day.
+///
+/// The comparison is done on the local wall-clock time, so that days with a daylight saving time
+/// transition (which are 23 or 25 hours long) are handled correctly.
 pub fn get_effective_date(
     date_time: &DateTime<chrono::Utc>,
     clock_info: &EventClockInfo,
 ) -> chrono::NaiveDate {
-    (date_time.with_timezone(&clock_info.timezone)
-        - chrono::Duration::seconds(
-            clock_info
-                .effective_begin_of_day
-                .num_seconds_from_midnight() as i64,
-        ))
-    .date_naive()
+    let local_date_time = date_time.with_timezone(&clock_info.timezone);
+    local_date_time.date_naive()
+        + if local_date_time.time() < clock_info.effective_begin_of_day {
+            chrono::Duration::days(-1)
+        } else {
+            chrono::Duration::days(0)
+        }
 }
 
 /// Calculate a (common) UTC timestamp from an effective date (i.e. using EFFECTIVE_BEGIN_OF_DAY
@@ -43,15 +51,149 @@ pub fn timestamp_from_effective_date_and_time(
         .unwrap_or(local_datetime.and_utc())
 }
 
+/// Calculate the end timestamp of an entry from the local end time (of day), given the entry's
+/// `begin` and its effective date.
+///
+/// Like the begin, the end time is interpreted relative to the effective date, i.e. end times
+/// after midnight and before the EFFECTIVE_BEGIN_OF_DAY are placed on the following calendar date.
+/// Returns `None` if the resulting end lies before the `begin`.
+pub fn end_timestamp_from_end_time(
+    begin: &DateTime<chrono::Utc>,
+    effective_date: NaiveDate,
+    local_end_time: chrono::NaiveTime,
+    clock_info: &EventClockInfo,
+) -> Option<DateTime<chrono::Utc>> {
+    let end = timestamp_from_effective_date_and_time(effective_date, local_end_time, clock_info);
+    (end >= *begin).then_some(end)
+}
+
+/// Calculate the UTC timestamps of the begin and end of the given effective date, i.e. the
+/// EFFECTIVE_BEGIN_OF_DAY (in local time) of the date itself and of the following date.
+pub fn effective_day_boundaries(
+    effective_date: NaiveDate,
+    clock_info: &EventClockInfo,
+) -> (DateTime<chrono::Utc>, DateTime<chrono::Utc>) {
+    (
+        timestamp_from_effective_date_and_time(
+            effective_date,
+            clock_info.effective_begin_of_day,
+            clock_info,
+        ),
+        timestamp_from_effective_date_and_time(
+            effective_date + chrono::Duration::days(1),
+            clock_info.effective_begin_of_day,
+            clock_info,
+        ),
+    )
+}
+
+/// A single (effective) day of an event with its begin and end as UTC timestamps
+#[derive(Debug, PartialEq)]
+pub struct EventDayBoundaries {
+    pub date: NaiveDate,
+    pub begin: DateTime<chrono::Utc>,
+    pub end: DateTime<chrono::Utc>,
+}
+
+/// Calculate the effective days of the event with their boundaries according to the
+/// EFFECTIVE_BEGIN_OF_DAY.
+///
+/// Each day runs from its effective begin to the next day's effective begin, such that the days
+/// are contiguous. Days affected by a DST transition are one hour shorter or longer, respectively.
+/// The first and last day are clamped to the event's `begin_time` and `end_time`, if given (see
+/// [event_boundaries]).
+pub fn event_day_boundaries(event: &Event, clock_info: &EventClockInfo) -> Vec<EventDayBoundaries> {
+    let (event_begin, event_end) = event_boundaries(event, clock_info);
+    event
+        .begin_date
+        .iter_days()
+        .take_while(|date| *date <= event.end_date)
+        .map(|date| {
+            let (begin, end) = effective_day_boundaries(date, clock_info);
+            EventDayBoundaries {
+                date,
+                begin: begin.max(event_begin),
+                end: end.min(event_end),
+            }
+        })
+        .collect()
+}
+
+/// Find the (effective) day of the event, which the timestamp `now` falls into, according to
+/// [event_day_boundaries].
+///
+/// If `now` is before the event's begin or after its end, the nearest day of the event, i.e. the
+/// first or last day, is returned. Returns `None` only if the event has no days at all.
+pub fn current_event_day(
+    event: &Event,
+    clock_info: &EventClockInfo,
+    now: &DateTime<chrono::Utc>,
+) -> Option<EventDayBoundaries> {
+    let mut days = event_day_boundaries(event, clock_info);
+    let index = days.iter().rposition(|day| day.begin <= *now).unwrap_or(0);
+    (index < days.len()).then(|| days.swap_remove(index))
+}
+
+/// Calculate the end timestamp of an open-ended entry on the given effective date, i.e. the end of
+/// the effective day according to [event_day_boundaries]. On the event's last day, this is the
+/// event's `end_time`, if given.
+///
+/// For dates outside the event, the end of the effective day (see [effective_day_boundaries]) is
+/// used.
+pub fn open_end_timestamp(
+    effective_date: NaiveDate,
+    event: &Event,
+    clock_info: &EventClockInfo,
+) -> DateTime<chrono::Utc> {
+    event_day_boundaries(event, clock_info)
+        .into_iter()
+        .find(|day| day.date == effective_date)
+        .map(|day| day.end)
+        .unwrap_or_else(|| effective_day_boundaries(effective_date, clock_info).1)
+}
+
+/// Calculate the UTC timestamps of the begin and end of the event.
+///
+/// By default, the event spans its effective days from `begin_date` to `end_date` completely. If
+/// the event's `begin_time` or `end_time` is given, it is used instead of the
+/// EFFECTIVE_BEGIN_OF_DAY on the first or last day, respectively. Like all times of day, these are
+/// interpreted relative to the effective date, i.e. an `end_time` before the EFFECTIVE_BEGIN_OF_DAY
+/// refers to the night after the `end_date`.
+pub fn event_boundaries(
+    event: &Event,
+    clock_info: &EventClockInfo,
+) -> (DateTime<chrono::Utc>, DateTime<chrono::Utc>) {
+    let begin = match event.begin_time {
+        Some(begin_time) => {
+            timestamp_from_effective_date_and_time(event.begin_date, begin_time, clock_info)
+        }
+        None => effective_day_boundaries(event.begin_date, clock_info).0,
+    };
+    let end = match event.end_time {
+        Some(end_time) => {
+            timestamp_from_effective_date_and_time(event.end_date, end_time, clock_info)
+        }
+        None => effective_day_boundaries(event.end_date, clock_info).1,
+    };
+    (begin, end)
+}
+
+/// Check if the time interval from `begin` to `end` lies within the event's time range, i.e.
+/// `begin` is not before the begin of the event's first effective day (or its `begin_time`) and
+/// `end` is not after the end of the event's last effective day (or its `end_time`).
+pub fn is_within_event_dates(
+    begin: &DateTime<chrono::Utc>,
+    end: &DateTime<chrono::Utc>,
+    event: &Event,
+    clock_info: &EventClockInfo,
+) -> bool {
+    let (event_begin, event_end) = event_boundaries(event, clock_info);
+    *begin >= event_begin && *end <= event_end
+}
+
 /// Get the current (effective) date, but clamp it to the event's boundaries
 pub fn current_effective_date(clock_info: &EventClockInfo) -> chrono::NaiveDate {
-    let now = chrono::Utc::now().with_timezone(&clock_info.timezone);
-    now.date_naive()
-        + if now.naive_local().time() < clock_info.effective_begin_of_day {
-            chrono::Duration::days(-1)
-        } else {
-            chrono::Duration::days(0)
-        }
+    get_effective_date(&chrono::Utc::now(), clock_info)
 }
 
 /// Calculate the most reasonable date to show the KüA-Plan for. Use the current (effective) date,
@@ -61,6 +203,50 @@ pub fn most_reasonable_date(event: &ExtendedEvent) -> chrono::NaiveDate {
         .clamp(event.basic_data.begin_date, event.basic_data.end_date)
 }
 
+/// Find the section of the given day time `schedule`, which is active at the given local time of
+/// day, i.e. the first section whose `end_time` has not passed yet, and its begin time.
+///
+/// Like all times of day, the `end_time`s are interpreted relative to the `effective_begin_of_day`,
+/// i.e. a time after midnight belongs to the last section of the (effective) day. The begin of a
+/// section is the `end_time` of the previous section or the `effective_begin_of_day` for the first
+/// section. Returns `None` if the schedule has no section which is active at the given time.
+pub fn suggest_section_for_time(
+    schedule: &EventDayTimeSchedule,
+    time: NaiveTime,
+    effective_begin_of_day: NaiveTime,
+) -> Option<(NaiveTime, &EventDayScheduleSection)> {
+    let offset = effective_begin_of_day - NaiveTime::MIN;
+    let mut section_begin = effective_begin_of_day;
+    for section in schedule.sections.iter() {
+        match section.end_time {
+            Some(end_time) if end_time - offset <= time - offset => {
+                section_begin = end_time;
+            }
+            _ => return Some((section_begin, section)),
+        }
+    }
+    None
+}
+
+/// Find the section of the given day time `schedule` on the given (effective) `date`, which the
+/// given timestamp falls into, i.e. the first section which has not ended at the timestamp.
+///
+/// The sections' `end_time`s are evaluated relative to the `date`, so timestamps before the `date`
+/// fall into the first section and timestamps after the `date` fall into the last section. Returns
+/// `None` if the schedule has no section which has not ended at the timestamp.
+pub fn schedule_section_for_timestamp<'a>(
+    timestamp: &DateTime<chrono::Utc>,
+    date: chrono::NaiveDate,
+    schedule: &'a EventDayTimeSchedule,
+    clock_info: &EventClockInfo,
+) -> Option<&'a EventDayScheduleSection> {
+    schedule.sections.iter().find(|section| {
+        section.end_time.is_none_or(|end_time| {
+            *timestamp < timestamp_from_effective_date_and_time(date, end_time, clock_info)
+        })
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -103,6 +289,297 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_end_timestamp_from_end_time() {
+        let date = "2025-08-13".parse().unwrap();
+        let begin = timestamp_from_effective_date_and_time(
+            date,
+            "22:00".parse().unwrap(),
+            &DEFAULT_CLOCK_INFO,
+        );
+        assert_eq!(
+            end_timestamp_from_end_time(
+                &begin,
+                date,
+                "23:30".parse().unwrap(),
+                &DEFAULT_CLOCK_INFO
+            ),
+            Some(
+                "2025-08-13T21:30:00+00:00"
+                    .parse::<chrono::DateTime<chrono::Utc>>()
+                    .unwrap()
+            )
+        );
+        // past midnight
+        assert_eq!(
+            end_timestamp_from_end_time(
+                &begin,
+                date,
+                "01:15".parse().unwrap(),
+                &DEFAULT_CLOCK_INFO
+            ),
+            Some(
+                "2025-08-13T23:15:00+00:00"
+                    .parse::<chrono::DateTime<chrono::Utc>>()
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            end_timestamp_from_end_time(
+                &begin,
+                date,
+                "22:00".parse().unwrap(),
+                &DEFAULT_CLOCK_INFO
+            ),
+            Some(begin)
+        );
+        assert_eq!(
+            end_timestamp_from_end_time(
+                &begin,
+                date,
+                "21:00".parse().unwrap(),
+                &DEFAULT_CLOCK_INFO
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_event_day_boundaries() {
+        // Daylight saving time starts in Germany on 2025-03-30 at 02:00 local time
+        let event = Event {
+            id: 1,
+            title: "Test".to_owned(),
+            begin_date: "2025-03-29".parse().unwrap(),
+            end_date: "2025-03-31".parse().unwrap(),
+            slug: None,
+            begin_time: None,
+            end_time: None,
+            archived: false,
+        };
+        let days = event_day_boundaries(&event, &DEFAULT_CLOCK_INFO);
+        assert_eq!(
+            days,
+            vec![
+                EventDayBoundaries {
+                    date: "2025-03-29".parse().unwrap(),
+                    begin: "2025-03-29T04:30:00+00:00".parse().unwrap(),
+                    end: "2025-03-30T03:30:00+00:00".parse().unwrap(),
+                },
+                EventDayBoundaries {
+                    date: "2025-03-30".parse().unwrap(),
+                    begin: "2025-03-30T03:30:00+00:00".parse().unwrap(),
+                    end: "2025-03-31T03:30:00+00:00".parse().unwrap(),
+                },
+                EventDayBoundaries {
+                    date: "2025-03-31".parse().unwrap(),
+                    begin: "2025-03-31T03:30:00+00:00".parse().unwrap(),
+                    end: "2025-04-01T03:30:00+00:00".parse().unwrap(),
+                },
+            ]
+        );
+        // The day including the DST transition is only 23 hours long
+        assert_eq!(days[0].end - days[0].begin, chrono::Duration::hours(23));
+        assert_eq!(days[1].end - days[1].begin, chrono::Duration::hours(24));
+    }
+
+    #[test]
+    fn test_event_day_boundaries_dst_end() {
+        // Daylight saving time ends in Germany on 2025-10-26 at 03:00 local time
+        let event = Event {
+            id: 1,
+            title: "Test".to_owned(),
+            begin_date: "2025-10-25".parse().unwrap(),
+            end_date: "2025-10-25".parse().unwrap(),
+            slug: None,
+            begin_time: None,
+            end_time: None,
+            archived: false,
+        };
+        let days = event_day_boundaries(&event, &DEFAULT_CLOCK_INFO);
+        assert_eq!(days.len(), 1);
+        assert_eq!(
+            days[0].begin,
+            "2025-10-25T03:30:00+00:00"
+                .parse::<chrono::DateTime<chrono::Utc>>()
+                .unwrap()
+        );
+        assert_eq!(
+            days[0].end,
+            "2025-10-26T04:30:00+00:00"
+                .parse::<chrono::DateTime<chrono::Utc>>()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_open_end_timestamp() {
+        let event = Event {
+            id: 1,
+            title: "Test".to_owned(),
+            begin_date: "2025-08-13".parse().unwrap(),
+            end_date: "2025-08-15".parse().unwrap(),
+            slug: None,
+            begin_time: None,
+            end_time: Some("12:00".parse().unwrap()),
+            archived: false,
+        };
+        let days = event_day_boundaries(&event, &DEFAULT_CLOCK_INFO);
+        for day in days.iter() {
+            assert_eq!(
+                open_end_timestamp(day.date, &event, &DEFAULT_CLOCK_INFO),
+                day.end
+            );
+        }
+        assert_eq!(
+            open_end_timestamp("2025-08-13".parse().unwrap(), &event, &DEFAULT_CLOCK_INFO),
+            "2025-08-14T03:30:00+00:00"
+                .parse::<chrono::DateTime<chrono::Utc>>()
+                .unwrap()
+        );
+        // On the last day, the event's end time is used
+        assert_eq!(
+            open_end_timestamp("2025-08-15".parse().unwrap(), &event, &DEFAULT_CLOCK_INFO),
+            "2025-08-15T10:00:00+00:00"
+                .parse::<chrono::DateTime<chrono::Utc>>()
+                .unwrap()
+        );
+        // Outside the event, the end of the effective day is used
+        assert_eq!(
+            open_end_timestamp("2025-08-20".parse().unwrap(), &event, &DEFAULT_CLOCK_INFO),
+            "2025-08-21T03:30:00+00:00"
+                .parse::<chrono::DateTime<chrono::Utc>>()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_is_within_event_dates() {
+        let event = Event {
+            id: 1,
+            title: "Test".to_owned(),
+            begin_date: "2025-08-13".parse().unwrap(),
+            end_date: "2025-08-15".parse().unwrap(),
+            slug: None,
+            begin_time: None,
+            end_time: None,
+            archived: false,
+        };
+        let check = |begin: &str, end: &str| {
+            is_within_event_dates(
+                &begin.parse().unwrap(),
+                &end.parse().unwrap(),
+                &event,
+                &DEFAULT_CLOCK_INFO,
+            )
+        };
+        // in range
+        assert!(check(
+            "2025-08-13T10:00:00+00:00",
+            "2025-08-13T12:00:00+00:00"
+        ));
+        // first minutes of the first effective day
+        assert!(check(
+            "2025-08-13T03:30:00+00:00",
+            "2025-08-13T04:00:00+00:00"
+        ));
+        // ending exactly at the end of the last effective day
+        assert!(check(
+            "2025-08-16T01:00:00+00:00",
+            "2025-08-16T03:30:00+00:00"
+        ));
+        // begin before the first effective day
+        assert!(!check(
+            "2025-08-13T03:00:00+00:00",
+            "2025-08-13T04:00:00+00:00"
+        ));
+        assert!(!check(
+            "2025-08-12T10:00:00+00:00",
+            "2025-08-12T12:00:00+00:00"
+        ));
+        // end after the last effective day
+        assert!(!check(
+            "2025-08-16T01:00:00+00:00",
+            "2025-08-16T04:00:00+00:00"
+        ));
+        assert!(!check(
+            "2025-08-20T10:00:00+00:00",
+            "2025-08-20T12:00:00+00:00"
+        ));
+    }
+
+    #[test]
+    fn test_partial_day_event() {
+        let event = Event {
+            id: 1,
+            title: "Test".to_owned(),
+            begin_date: "2025-08-13".parse().unwrap(),
+            end_date: "2025-08-15".parse().unwrap(),
+            slug: None,
+            begin_time: Some("14:00".parse().unwrap()),
+            end_time: Some("12:00".parse().unwrap()),
+            archived: false,
+        };
+        let days = event_day_boundaries(&event, &DEFAULT_CLOCK_INFO);
+        assert_eq!(days.len(), 3);
+        assert_eq!(
+            days[0].begin,
+            "2025-08-13T12:00:00+00:00"
+                .parse::<chrono::DateTime<chrono::Utc>>()
+                .unwrap()
+        );
+        assert_eq!(
+            days[0].end,
+            "2025-08-14T03:30:00+00:00"
+                .parse::<chrono::DateTime<chrono::Utc>>()
+                .unwrap()
+        );
+        assert_eq!(days[1].begin, days[0].end);
+        assert_eq!(days[2].begin, days[1].end);
+        assert_eq!(
+            days[2].end,
+            "2025-08-15T10:00:00+00:00"
+                .parse::<chrono::DateTime<chrono::Utc>>()
+                .unwrap()
+        );
+
+        let check = |begin: &str, end: &str| {
+            is_within_event_dates(
+                &begin.parse().unwrap(),
+                &end.parse().unwrap(),
+                &event,
+                &DEFAULT_CLOCK_INFO,
+            )
+        };
+        // in range
+        assert!(check(
+            "2025-08-13T12:00:00+00:00",
+            "2025-08-13T14:00:00+00:00"
+        ));
+        assert!(check(
+            "2025-08-15T08:00:00+00:00",
+            "2025-08-15T10:00:00+00:00"
+        ));
+        // before the begin time on the first day
+        assert!(!check(
+            "2025-08-13T08:00:00+00:00",
+            "2025-08-13T09:00:00+00:00"
+        ));
+        assert!(!check(
+            "2025-08-13T11:30:00+00:00",
+            "2025-08-13T13:00:00+00:00"
+        ));
+        // after the end time on the last day
+        assert!(!check(
+            "2025-08-15T09:00:00+00:00",
+            "2025-08-15T10:30:00+00:00"
+        ));
+        assert!(!check(
+            "2025-08-15T16:00:00+00:00",
+            "2025-08-15T18:00:00+00:00"
+        ));
+    }
+
     #[test]
     fn test_get_effective_date() {
         assert_eq!(
@@ -133,4 +610,243 @@ mod tests {
             "2025-08-13".parse().unwrap(),
         );
     }
+
+    #[test]
+    fn test_get_effective_date_boundary() {
+        let clock_info = EventClockInfo {
+            timezone: chrono_tz::Tz::Europe__Berlin,
+            effective_begin_of_day: chrono::NaiveTime::from_hms_opt(4, 0, 0).unwrap(),
+        };
+        let effective_date = |timestamp: &str| {
+            get_effective_date(
+                &timestamp.parse::<chrono::DateTime<chrono::Utc>>().unwrap(),
+                &clock_info,
+            )
+        };
+        // 02:00 local time belongs to the previous day's programme
+        assert_eq!(
+            effective_date("2025-08-14T02:00:00+02:00"),
+            "2025-08-13".parse().unwrap()
+        );
+        // 05:00 local time belongs to the same day's programme
+        assert_eq!(
+            effective_date("2025-08-14T05:00:00+02:00"),
+            "2025-08-14".parse().unwrap()
+        );
+        // Exactly at the boundary belongs to the new day; just before to the previous day
+        assert_eq!(
+            effective_date("2025-08-14T04:00:00+02:00"),
+            "2025-08-14".parse().unwrap()
+        );
+        assert_eq!(
+            effective_date("2025-08-14T03:59:59+02:00"),
+            "2025-08-13".parse().unwrap()
+        );
+        // Midnight belongs to the previous day
+        assert_eq!(
+            effective_date("2025-08-14T00:00:00+02:00"),
+            "2025-08-13".parse().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_get_effective_date_dst() {
+        let effective_date = |timestamp: &str| {
+            get_effective_date(
+                &timestamp.parse::<chrono::DateTime<chrono::Utc>>().unwrap(),
+                &DEFAULT_CLOCK_INFO,
+            )
+        };
+        // Daylight saving time starts in Germany on 2025-03-30 at 02:00 local time
+        assert_eq!(
+            effective_date("2025-03-30T05:00:00+02:00"),
+            "2025-03-29".parse().unwrap()
+        );
+        assert_eq!(
+            effective_date("2025-03-30T05:30:00+02:00"),
+            "2025-03-30".parse().unwrap()
+        );
+        assert_eq!(
+            effective_date("2025-03-30T06:00:00+02:00"),
+            "2025-03-30".parse().unwrap()
+        );
+        // Daylight saving time ends in Germany on 2025-10-26 at 03:00 local time
+        assert_eq!(
+            effective_date("2025-10-26T05:00:00+01:00"),
+            "2025-10-25".parse().unwrap()
+        );
+        assert_eq!(
+            effective_date("2025-10-26T05:30:00+01:00"),
+            "2025-10-26".parse().unwrap()
+        );
+        assert_eq!(
+            effective_date("2025-10-26T02:30:00+02:00"),
+            "2025-10-25".parse().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_suggest_section_for_time() {
+        let schedule = EventDayTimeSchedule {
+            sections: vec![
+                EventDayScheduleSection {
+                    name: "vom Vortag".to_owned(),
+                    end_time: Some("05:30".parse().unwrap()),
+                },
+                EventDayScheduleSection {
+                    name: "Morgens".to_owned(),
+                    end_time: Some("12:00".parse().unwrap()),
+                },
+                EventDayScheduleSection {
+                    name: "Mittags".to_owned(),
+                    end_time: Some("18:00".parse().unwrap()),
+                },
+                EventDayScheduleSection {
+                    name: "Abends".to_owned(),
+                    end_time: None,
+                },
+            ],
+        };
+        let begin_of_day = DEFAULT_CLOCK_INFO.effective_begin_of_day;
+        let suggest = |time: &str| {
+            suggest_section_for_time(&schedule, time.parse().unwrap(), begin_of_day)
+                .map(|(begin, section)| (begin.format("%H:%M").to_string(), section.name.as_str()))
+        };
+
+        assert_eq!(suggest("05:30"), Some(("05:30".to_owned(), "Morgens")));
+        assert_eq!(suggest("08:15"), Some(("05:30".to_owned(), "Morgens")));
+        assert_eq!(suggest("12:00"), Some(("12:00".to_owned(), "Mittags")));
+        assert_eq!(suggest("17:59"), Some(("12:00".to_owned(), "Mittags")));
+        // last (open-ended) section, including the night after midnight
+        assert_eq!(suggest("18:00"), Some(("18:00".to_owned(), "Abends")));
+        assert_eq!(suggest("23:45"), Some(("18:00".to_owned(), "Abends")));
+        assert_eq!(suggest("03:00"), Some(("18:00".to_owned(), "Abends")));
+
+        let schedule = EventDayTimeSchedule {
+            sections: vec![EventDayScheduleSection {
+                name: "Tagsüber".to_owned(),
+                end_time: Some("22:00".parse().unwrap()),
+            }],
+        };
+        assert_eq!(
+            suggest_section_for_time(&schedule, "21:00".parse().unwrap(), begin_of_day)
+                .map(|(begin, _)| begin),
+            Some(begin_of_day)
+        );
+        assert!(
+            suggest_section_for_time(&schedule, "23:00".parse().unwrap(), begin_of_day).is_none()
+        );
+        assert!(
+            suggest_section_for_time(
+                &EventDayTimeSchedule { sections: vec![] },
+                "12:00".parse().unwrap(),
+                begin_of_day
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_current_event_day() {
+        let event = Event {
+            id: 1,
+            title: "Test".to_owned(),
+            begin_date: "2025-08-13".parse().unwrap(),
+            end_date: "2025-08-15".parse().unwrap(),
+            slug: None,
+            begin_time: Some("14:00".parse().unwrap()),
+            end_time: None,
+            archived: false,
+        };
+        let current_day = |now: &str| {
+            current_event_day(&event, &DEFAULT_CLOCK_INFO, &now.parse().unwrap())
+                .unwrap()
+                .date
+                .to_string()
+        };
+
+        // Mid-event, including the night before the effective begin of the next day
+        assert_eq!(current_day("2025-08-14T12:00:00+02:00"), "2025-08-14");
+        assert_eq!(current_day("2025-08-15T05:29:00+02:00"), "2025-08-14");
+        assert_eq!(current_day("2025-08-15T05:30:00+02:00"), "2025-08-15");
+        // Before the event (and before its begin_time on the first day)
+        assert_eq!(current_day("2025-08-01T12:00:00+02:00"), "2025-08-13");
+        assert_eq!(current_day("2025-08-13T10:00:00+02:00"), "2025-08-13");
+        // After the event
+        assert_eq!(current_day("2025-08-16T06:00:00+02:00"), "2025-08-15");
+        assert_eq!(current_day("2025-12-24T18:00:00+01:00"), "2025-08-15");
+    }
+
+    #[test]
+    fn test_schedule_section_for_timestamp() {
+        let schedule = EventDayTimeSchedule {
+            sections: vec![
+                EventDayScheduleSection {
+                    name: "Morgens".to_owned(),
+                    end_time: Some("12:00".parse().unwrap()),
+                },
+                EventDayScheduleSection {
+                    name: "Mittags".to_owned(),
+                    end_time: Some("18:00".parse().unwrap()),
+                },
+                EventDayScheduleSection {
+                    name: "Abends".to_owned(),
+                    end_time: None,
+                },
+            ],
+        };
+        let section_name = |timestamp: &str| {
+            let timestamp = timestamp.parse().unwrap();
+            schedule_section_for_timestamp(
+                &timestamp,
+                get_effective_date(&timestamp, &DEFAULT_CLOCK_INFO),
+                &schedule,
+                &DEFAULT_CLOCK_INFO,
+            )
+            .map(|section| section.name.as_str())
+        };
+
+        assert_eq!(section_name("2025-08-14T09:30:00+02:00"), Some("Morgens"));
+        // Section boundaries are evaluated in the event's timezone
+        assert_eq!(section_name("2025-08-14T10:00:00Z"), Some("Mittags"));
+        assert_eq!(section_name("2025-08-14T17:59:00+02:00"), Some("Mittags"));
+        assert_eq!(section_name("2025-08-14T18:00:00+02:00"), Some("Abends"));
+        // After midnight, but before the effective begin of the next day
+        assert_eq!(section_name("2025-08-15T02:00:00+02:00"), Some("Abends"));
+        assert_eq!(section_name("2025-08-15T05:30:00+02:00"), Some("Morgens"));
+
+        // Relative to another date, e.g. for entries spanning over multiple days: Timestamps on
+        // previous days fall into the first section, timestamps on later days into the last one.
+        let section_name_on_date = |timestamp: &str| {
+            schedule_section_for_timestamp(
+                &timestamp.parse().unwrap(),
+                "2025-08-14".parse().unwrap(),
+                &schedule,
+                &DEFAULT_CLOCK_INFO,
+            )
+            .map(|section| section.name.as_str())
+        };
+        assert_eq!(
+            section_name_on_date("2025-08-13T20:00:00+02:00"),
+            Some("Morgens")
+        );
+        assert_eq!(
+            section_name_on_date("2025-08-14T13:00:00+02:00"),
+            Some("Mittags")
+        );
+        assert_eq!(
+            section_name_on_date("2025-08-15T09:30:00+02:00"),
+            Some("Abends")
+        );
+
+        assert!(
+            schedule_section_for_timestamp(
+                &"2025-08-14T09:30:00+02:00".parse().unwrap(),
+                "2025-08-14".parse().unwrap(),
+                &EventDayTimeSchedule { sections: vec![] },
+                &DEFAULT_CLOCK_INFO,
+            )
+            .is_none()
+        );
+    }
 }
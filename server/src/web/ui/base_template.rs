@@ -103,6 +103,7 @@ impl<'a> BaseTemplateContext<'a> {
             crate::web::ui::endpoints::edit_entry::NewEntryQueryParams {
                 date: self.current_date,
                 clone_from: None,
+                from_template: None,
             },
         )?));
         Ok(url.to_string())
@@ -149,6 +150,34 @@ impl<'a> BaseTemplateContext<'a> {
             .to_string())
     }
 
+    /// Get the URL of the index page of the event directly preceding the current event, if the
+    /// extended event data is available and a preceding event is configured.
+    pub fn preceding_event_url(&self) -> Result<Option<String>, AppError> {
+        self.get_extended_event()
+            .and_then(|e| e.preceding_event_id)
+            .map(|id| {
+                Ok(self
+                    .request
+                    .url_for("event_index", [id.to_string()])?
+                    .to_string())
+            })
+            .transpose()
+    }
+
+    /// Get the URL of the index page of the event directly following the current event, if the
+    /// extended event data is available and a subsequent event is configured.
+    pub fn subsequent_event_url(&self) -> Result<Option<String>, AppError> {
+        self.get_extended_event()
+            .and_then(|e| e.subsequent_event_id)
+            .map(|id| {
+                Ok(self
+                    .request
+                    .url_for("event_index", [id.to_string()])?
+                    .to_string())
+            })
+            .transpose()
+    }
+
     /// Get the current effective date, if all required information is present to determine it
     pub fn get_current_date_opt(&self) -> Option<chrono::NaiveDate> {
         self.get_extended_event()
@@ -1,3 +1,5 @@
+use crate::web::ui::form_values::ValidateFromFormInput;
+use crate::web::ui::validation::ColorHexString;
 use palette::{FromColor, IntoColor};
 
 /// Set of display colors for a category, derived from the category's base color.
@@ -91,3 +93,54 @@ fn change_color_luminance(color: &palette::Hsl, new_base_luminance: f32) -> pale
     color.saturation *= saturation_factor;
     color
 }
+
+/// Select the text color (black or white) with the better contrast on the given background color,
+/// based on the WCAG relative luminance of the background.
+///
+/// The background color is given as hex RGB string with 3 or 6 digits, with or without leading
+/// '#'. For invalid values, black is returned.
+pub fn best_text_color(bg_hex: &str) -> &'static str {
+    let Ok(ColorHexString(normalized_hex)) = ColorHexString::from_form_value(bg_hex) else {
+        return "#000000";
+    };
+    let Ok(color) = normalized_hex.parse::<palette::Srgb<u8>>() else {
+        return "#000000";
+    };
+    // See https://www.w3.org/TR/WCAG21/#dfn-relative-luminance
+    fn linearize(channel: u8) -> f32 {
+        let c = channel as f32 / 255.0;
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    }
+    let luminance = 0.2126 * linearize(color.red)
+        + 0.7152 * linearize(color.green)
+        + 0.0722 * linearize(color.blue);
+    // Contrast ratios according to https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio
+    let contrast_with_black = (luminance + 0.05) / 0.05;
+    let contrast_with_white = 1.05 / (luminance + 0.05);
+    if contrast_with_black >= contrast_with_white {
+        "#000000"
+    } else {
+        "#ffffff"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_best_text_color() {
+        assert_eq!(best_text_color("#101010"), "#ffffff");
+        assert_eq!(best_text_color("222"), "#ffffff");
+        assert_eq!(best_text_color("#f0f0f0"), "#000000");
+        assert_eq!(best_text_color("#FFE"), "#000000");
+        // mid-gray has a slightly better contrast with black
+        assert_eq!(best_text_color("808080"), "#000000");
+        assert_eq!(best_text_color("#0000ff"), "#ffffff");
+        assert_eq!(best_text_color("not a color"), "#000000");
+    }
+}
@@ -1,6 +1,7 @@
 use crate::auth_session::SessionToken;
 use crate::data_store::StoreError;
 use crate::data_store::auth_token::Privilege;
+use crate::setup::SessionCookieConfig;
 use crate::web::ui::base_template::{AnyEventData, BaseTemplateContext};
 use crate::web::ui::error::AppError;
 use crate::web::ui::flash::{FlashMessage, FlashType, FlashesInterface};
@@ -73,6 +74,10 @@ async fn login(
         util::extract_session_token_if_present(&state, &req, Privilege::ShowKueaPlan, event_id)?
             .unwrap_or(SessionToken::new());
 
+    let client_ip = state.auth_rate_limiter.client_ip_address(&req);
+    let retry_after =
+        client_ip.and_then(|ip| state.auth_rate_limiter.check_and_record_attempt(ip).err());
+
     let store = state.store.clone();
     let expected_privilege = query_data.privilege;
     let (
@@ -86,15 +91,16 @@ async fn login(
     ) = web::block(move || -> Result<_, AppError> {
         let mut store = store.get_facade()?;
         let event = store.get_event(event_id)?;
-        let (login_result, passphrase_expired) = match store.authenticate_with_passphrase(
-            event_id,
-            &data.passphrase,
-            &mut session_token,
-        ) {
-            Ok(()) => (true, false),
-            Err(StoreError::NotExisting) => (false, false),
-            Err(StoreError::NotValid) => (false, true),
-            Err(e) => return Err(e.into()),
+        let (login_result, passphrase_expired) = if retry_after.is_some() {
+            (false, false)
+        } else {
+            match store.authenticate_with_passphrase(event_id, &data.passphrase, &mut session_token)
+            {
+                Ok(()) => (true, false),
+                Err(StoreError::NotExisting) => (false, false),
+                Err(StoreError::NotValid) => (false, true),
+                Err(e) => return Err(e.into()),
+            }
         };
         let auth = store.get_auth_token_for_session(&session_token, event_id)?;
         let extended_event = if auth.has_privilege(event_id, Privilege::ShowKueaPlan) {
@@ -116,7 +122,12 @@ async fn login(
         ))
     })
     .await??;
-    if !login_success {
+    if let Some(ip) = client_ip
+        && login_success
+    {
+        state.auth_rate_limiter.reset(ip);
+    }
+    if !login_success && retry_after.is_none() {
         warn!(
             "HTTP 422 authentication failed. Client: <{}>{}",
             req.connection_info()
@@ -131,18 +142,23 @@ async fn login(
     }
 
     if !login_success || !privilege_unlocked {
-        let error = if !login_success {
+        let error = if let Some(retry_after) = retry_after {
+            format!(
+                "Zu viele fehlgeschlagene Login-Versuche. Bitte in {} Minuten erneut versuchen.",
+                retry_after.as_secs().div_ceil(60)
+            )
+        } else if !login_success {
             if passphrase_expired {
-                "Die Passphrase ist nicht mehr (oder noch nicht) gültig."
+                "Die Passphrase ist nicht mehr (oder noch nicht) gültig.".to_owned()
             } else {
-                "Ungültige Passphrase."
+                "Ungültige Passphrase.".to_owned()
             }
         } else {
-            "Diese Passphrase schaltet nicht den gewünschten Zugriff frei."
+            "Diese Passphrase schaltet nicht den gewünschten Zugriff frei.".to_owned()
         };
         req.add_flash_message(FlashMessage {
             flash_type: FlashType::Error,
-            message: error.to_string(),
+            message: error,
             keep_open: true,
             button: None,
         });
@@ -165,8 +181,21 @@ async fn login(
             expected_privilege: expected_privilege.unwrap_or(Privilege::ShowKueaPlan),
         };
 
-        let mut response = HttpResponse::UnprocessableEntity();
-        response.cookie(create_session_cookie(session_token, &state.secret));
+        let mut response = if let Some(retry_after) = retry_after {
+            let mut response = HttpResponse::TooManyRequests();
+            response.append_header((
+                header::RETRY_AFTER,
+                retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0),
+            ));
+            response
+        } else {
+            HttpResponse::UnprocessableEntity()
+        };
+        response.cookie(create_session_cookie(
+            session_token,
+            state.signing_secret(),
+            &state.session_cookie_config,
+        ));
         Ok(response
             .append_header((
                 header::CONTENT_TYPE,
@@ -175,7 +204,11 @@ async fn login(
             .body(tmpl.render()?))
     } else {
         let mut response = HttpResponse::SeeOther();
-        response.cookie(create_session_cookie(session_token, &state.secret));
+        response.cookie(create_session_cookie(
+            session_token,
+            state.signing_secret(),
+            &state.session_cookie_config,
+        ));
         req.add_flash_message(FlashMessage {
             flash_type: FlashType::Success,
             message: "Login erfolgreich".to_owned(),
@@ -205,17 +238,43 @@ async fn login(
     }
 }
 
+/// Create the session cookie for the given session token, with the security attributes from the
+/// given configuration
 pub fn create_session_cookie<'b>(
     session_token: SessionToken,
     secret: &str,
+    config: &SessionCookieConfig,
 ) -> actix_web::cookie::Cookie<'b> {
-    let mut cookie =
-        actix_web::cookie::Cookie::new(SESSION_COOKIE_NAME, session_token.as_string(secret));
-    cookie.set_path("/");
+    let mut cookie = session_cookie_base(session_token.as_string(secret), config);
     cookie.set_expires(actix_web::cookie::time::OffsetDateTime::now_utc() + SESSION_COOKIE_MAX_AGE);
     cookie
 }
 
+/// Create a cookie for removing the session cookie from the client. The domain and path must match
+/// the session cookie's attributes for the removal to take effect.
+pub fn create_session_removal_cookie<'b>(
+    config: &SessionCookieConfig,
+) -> actix_web::cookie::Cookie<'b> {
+    let mut cookie = session_cookie_base(String::new(), config);
+    cookie.make_removal();
+    cookie
+}
+
+fn session_cookie_base<'b>(
+    value: String,
+    config: &SessionCookieConfig,
+) -> actix_web::cookie::Cookie<'b> {
+    let mut cookie = actix_web::cookie::Cookie::new(SESSION_COOKIE_NAME, value);
+    cookie.set_path("/");
+    cookie.set_http_only(true);
+    cookie.set_secure(config.secure);
+    cookie.set_same_site(config.same_site);
+    if let Some(domain) = &config.domain {
+        cookie.set_domain(domain.clone());
+    }
+    cookie
+}
+
 #[derive(Template)]
 #[template(path = "login_form.html")]
 struct LoginFormTemplate<'a> {
@@ -238,12 +297,10 @@ pub struct LogoutQueryData {
 async fn logout_all(
     req: HttpRequest,
     query_data: Query<LogoutQueryData>,
+    state: web::Data<AppState>,
 ) -> Result<impl Responder, AppError> {
     let mut response = HttpResponse::SeeOther();
-    let mut cookie = actix_web::cookie::Cookie::new(SESSION_COOKIE_NAME, "");
-    cookie.set_path("/");
-    cookie.make_removal();
-    response.cookie(cookie);
+    response.cookie(create_session_removal_cookie(&state.session_cookie_config));
     req.add_flash_message(FlashMessage {
         flash_type: FlashType::Success,
         message: "Login-Daten wurden bereinigt.".to_owned(),
@@ -51,7 +51,8 @@ pub async fn calendar_link_overview(
             auth_token: Some(&auth),
             active_main_nav_button: None,
         },
-        shareable_session_token: shareable_session_token.map(|t| t.as_string(&state.secret)),
+        shareable_session_token: shareable_session_token
+            .map(|t| t.as_string(state.signing_secret())),
         event: &event.basic_data,
     };
     Ok(Html::new(tmpl.render()?))
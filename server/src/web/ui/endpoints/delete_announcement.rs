@@ -137,6 +137,7 @@ async fn disable_announcement(
                 show_with_rooms: Some(false),
                 ..Default::default()
             },
+            None,
         )?;
         Ok(())
     })
@@ -1,6 +1,8 @@
 use crate::data_store::EntryId;
 use crate::data_store::auth_token::Privilege;
-use crate::data_store::models::{Category, EntryPatch, EntryState, ExtendedEvent, FullEntry};
+use crate::data_store::models::{
+    Category, EntryAuditAction, EntryPatch, EntryState, ExtendedEvent, FullEntry,
+};
 use crate::web::AppState;
 use crate::web::time_calculation;
 use crate::web::time_calculation::get_effective_date;
@@ -81,6 +83,12 @@ async fn delete_entry(
         let auth = store.get_auth_token_for_session(&session_token, event_id)?;
         let entry = store.get_entry(&auth, entry_id)?;
         store.delete_entry(&auth, event_id, entry_id)?;
+        state.live_updates.publish_entry_change(
+            event_id,
+            entry_id,
+            EntryAuditAction::Delete,
+            entry.entry.is_public(),
+        );
         Ok((
             entry.entry.begin,
             entry.entry.state,
@@ -164,10 +172,14 @@ async fn mark_entry_cancelled(
             ..Default::default()
         };
         store.patch_entry(&auth, entry_id, patchset)?;
-        Ok((
-            store.get_entry(&auth, entry_id)?,
-            store.get_extended_event(&auth, event_id)?,
-        ))
+        let changed_entry = store.get_entry(&auth, entry_id)?;
+        state.live_updates.publish_entry_change(
+            event_id,
+            entry_id,
+            EntryAuditAction::Update,
+            changed_entry.entry.is_public(),
+        );
+        Ok((changed_entry, store.get_extended_event(&auth, event_id)?))
     })
     .await?;
 
@@ -244,10 +256,14 @@ async fn retract_entry(
             ..Default::default()
         };
         store.patch_entry(&auth, entry_id, patchset)?;
-        Ok((
-            store.get_entry(&auth, entry_id)?,
-            store.get_extended_event(&auth, event_id)?,
-        ))
+        let changed_entry = store.get_entry(&auth, entry_id)?;
+        state.live_updates.publish_entry_change(
+            event_id,
+            entry_id,
+            EntryAuditAction::Update,
+            entry.entry.is_public() || changed_entry.entry.is_public(),
+        );
+        Ok((changed_entry, store.get_extended_event(&auth, event_id)?))
     })
     .await?;
 
@@ -114,6 +114,8 @@ pub async fn delete_room(
                     room_id,
                     &replacement_data.replace_rooms,
                     &replacement_data.add_room_comment,
+                    // The form lists the affected entries, so submitting it confirms the deletion
+                    true,
                 )?;
                 Ok(())
             })
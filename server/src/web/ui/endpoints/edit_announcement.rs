@@ -12,16 +12,17 @@ use crate::web::ui::form_values::{
     _FormValidSimpleValidate, BoolFormValue, FormValue, FormValueRepresentation,
     ValidateFromFormInput,
 };
+use crate::web::ui::i18n::Messages;
 use crate::web::ui::sub_templates::form_inputs::{
     CheckboxTemplate, FormFieldTemplate, HiddenInputTemplate, InputType, SelectEntry,
     SelectTemplate,
 };
 use crate::web::ui::util::{announcement_type_name, event_days};
 use crate::web::ui::{util, validation};
-use actix_web::web::{Form, Html};
+use actix_web::web::{Form, Html, Query};
 use actix_web::{HttpRequest, Responder, get, post, web};
 use askama::Template;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use uuid::Uuid;
 
@@ -35,15 +36,14 @@ pub async fn edit_announcement_form(
     let session_token =
         util::extract_session_token(&state, &req, Privilege::ManageCategories, event_id)?;
     let store = state.store.clone();
-    let (event, announcements, categories, rooms, auth) =
+    let (event, announcement, categories, rooms, auth) =
         web::block(move || -> Result<_, AppError> {
             let mut store = store.get_facade()?;
             let auth = store.get_auth_token_for_session(&session_token, event_id)?;
             auth.check_privilege(event_id, Privilege::ManageCategories)?;
             Ok((
-                // TODO only get required announcement
                 store.get_extended_event(&auth, event_id)?,
-                store.get_announcements(&auth, event_id, None)?,
+                store.get_announcement(&auth, event_id, announcement_id)?,
                 store.get_categories(&auth, event_id)?,
                 store.get_rooms(&auth, event_id)?,
                 auth,
@@ -51,16 +51,13 @@ pub async fn edit_announcement_form(
         })
         .await??;
 
-    let announcement = announcements
-        .into_iter()
-        .find(|a| a.announcement.id == announcement_id)
-        .ok_or(AppError::EntityNotFound)?;
     let form_data: AnnouncementFormData = announcement.into();
 
+    let messages = Messages::for_request(&req);
     let tmpl = EditAnnouncementFormTemplate {
         base: BaseTemplateContext {
             request: &req,
-            page_title: "Bekanntmachung bearbeiten", // TODO
+            page_title: messages.edit_announcement_title,
             event: AnyEventData::ExtendedEvent(&event),
             current_date: None,
             auth_token: Some(&auth),
@@ -92,25 +89,20 @@ pub async fn edit_announcement(
     let session_token =
         util::extract_session_token(&state, &req, Privilege::ManageCategories, event_id)?;
     let store = state.store.clone();
-    let (event, announcements, categories, rooms, auth) =
+    let (event, _old_announcement, categories, rooms, auth) =
         web::block(move || -> Result<_, AppError> {
             let mut store = store.get_facade()?;
             let auth = store.get_auth_token_for_session(&session_token, event_id)?;
             auth.check_privilege(event_id, Privilege::ManageCategories)?;
             Ok((
-                // TODO only get required announcement
                 store.get_extended_event(&auth, event_id)?,
-                store.get_announcements(&auth, event_id, None)?,
+                store.get_announcement(&auth, event_id, announcement_id)?,
                 store.get_categories(&auth, event_id)?,
                 store.get_rooms(&auth, event_id)?,
                 auth,
             ))
         })
         .await??;
-    let _old_announcement = announcements
-        .into_iter()
-        .find(|a| a.announcement.id == announcement_id)
-        .ok_or(AppError::EntityNotFound)?;
 
     let mut form_data = data.into_inner();
     let announcement = form_data.validate(
@@ -138,10 +130,11 @@ pub async fn edit_announcement(
             util::FormSubmitResult::ValidationError
         };
 
+    let messages = Messages::for_request(&req);
     let tmpl = EditAnnouncementFormTemplate {
         base: BaseTemplateContext {
             request: &req,
-            page_title: "Bekanntmachung bearbeiten", // TODO
+            page_title: messages.edit_announcement_title,
             event: AnyEventData::ExtendedEvent(&event),
             current_date: None,
             auth_token: Some(&auth),
@@ -162,7 +155,7 @@ pub async fn edit_announcement(
     util::create_edit_form_response(
         result,
         &tmpl,
-        "Die Bekanntmachung",
+        messages.announcement_name,
         req.url_for(
             "edit_announcement_form",
             &[event_id.to_string(), announcement_id.to_string()],
@@ -177,34 +170,46 @@ pub async fn edit_announcement(
 #[get("/{event_id}/config/announcements/new")]
 pub async fn new_announcement_form(
     path: web::Path<EventId>,
+    query_data: Query<NewAnnouncementQueryParams>,
     state: web::Data<AppState>,
     req: HttpRequest,
 ) -> Result<impl Responder, AppError> {
     let event_id = path.into_inner();
+    let clone_from = query_data.clone_from;
     let session_token =
         util::extract_session_token(&state, &req, Privilege::ManageCategories, event_id)?;
     let store = state.store.clone();
-    let (event, categories, rooms, auth) = web::block(move || -> Result<_, AppError> {
-        let mut store = store.get_facade()?;
-        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
-        auth.check_privilege(event_id, Privilege::ManageCategories)?;
-        Ok((
-            store.get_extended_event(&auth, event_id)?,
-            store.get_categories(&auth, event_id)?,
-            store.get_rooms(&auth, event_id)?,
-            auth,
-        ))
-    })
-    .await??;
+    let (event, categories, rooms, cloned_announcement, auth) =
+        web::block(move || -> Result<_, AppError> {
+            let mut store = store.get_facade()?;
+            let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+            auth.check_privilege(event_id, Privilege::ManageCategories)?;
+            Ok((
+                store.get_extended_event(&auth, event_id)?,
+                store.get_categories(&auth, event_id)?,
+                store.get_rooms(&auth, event_id)?,
+                clone_from
+                    .map(|cloned_announcement_id| {
+                        store.get_announcement(&auth, event_id, cloned_announcement_id)
+                    })
+                    .transpose()?,
+                auth,
+            ))
+        })
+        .await??;
 
     let announcement_id = Uuid::now_v7();
-    let form_data: AnnouncementFormData =
-        AnnouncementFormData::for_new_announcement(announcement_id);
+    let form_data: AnnouncementFormData = if let Some(cloned_announcement) = cloned_announcement {
+        AnnouncementFormData::for_cloned_announcement(cloned_announcement, announcement_id)
+    } else {
+        AnnouncementFormData::for_new_announcement(announcement_id)
+    };
 
+    let messages = Messages::for_request(&req);
     let tmpl = EditAnnouncementFormTemplate {
         base: BaseTemplateContext {
             request: &req,
-            page_title: "Neue Bekanntmachung", // TODO
+            page_title: messages.new_announcement_title,
             event: AnyEventData::ExtendedEvent(&event),
             current_date: None,
             auth_token: Some(&auth),
@@ -271,10 +276,11 @@ pub async fn new_announcement(
             util::FormSubmitResult::ValidationError
         };
 
+    let messages = Messages::for_request(&req);
     let tmpl = EditAnnouncementFormTemplate {
         base: BaseTemplateContext {
             request: &req,
-            page_title: "Neuer Ort", // TODO
+            page_title: messages.new_announcement_title,
             event: AnyEventData::ExtendedEvent(&event),
             current_date: None,
             auth_token: Some(&auth),
@@ -295,7 +301,7 @@ pub async fn new_announcement(
     util::create_edit_form_response(
         result,
         &tmpl,
-        "Die Bekanntmachung",
+        messages.announcement_name,
         req.url_for("new_announcement_form", &[event_id.to_string()])?,
         "edit_announcement_form",
         true,
@@ -330,6 +336,14 @@ impl ValidateFromFormInput for AnnouncementTypeValue {
     }
 }
 
+/// Query parameters for the new_announcement form.
+#[derive(Deserialize, Serialize)]
+pub struct NewAnnouncementQueryParams {
+    /// When given, used to prefill the form with all data from this existing announcement of the
+    /// same event
+    pub clone_from: Option<AnnouncementId>,
+}
+
 #[derive(Deserialize, Default)]
 struct AnnouncementFormData {
     /// Id of the announcement, only used for creating new announcements (for editing existing
@@ -340,6 +354,8 @@ struct AnnouncementFormData {
     show_with_days: BoolFormValue,
     begin_date: FormValue<validation::MaybeEmpty<validation::IsoDate>>,
     end_date: FormValue<validation::MaybeEmpty<validation::IsoDate>>,
+    begin_time: FormValue<validation::MaybeEmpty<validation::TimeOfDay>>,
+    end_time: FormValue<validation::MaybeEmpty<validation::TimeOfDay>>,
     show_with_categories: BoolFormValue,
     categories: FormValue<validation::CommaSeparatedUuidsFromList>,
     show_with_rooms: BoolFormValue,
@@ -359,6 +375,17 @@ impl AnnouncementFormData {
         }
     }
 
+    fn for_cloned_announcement(
+        cloned_announcement: FullAnnouncement,
+        new_announcement_id: AnnouncementId,
+    ) -> Self {
+        Self {
+            announcement_id: new_announcement_id.into(),
+            last_updated: FormValue::empty(),
+            ..Self::from(cloned_announcement)
+        }
+    }
+
     fn validate(
         &mut self,
         known_id: Option<AnnouncementId>,
@@ -370,6 +397,8 @@ impl AnnouncementFormData {
         let text = self.text.validate();
         let begin_date = self.begin_date.validate();
         let end_date = self.end_date.validate();
+        let begin_time = self.begin_time.validate();
+        let end_time = self.end_time.validate();
         let categories = self.categories.validate_with(category_ids);
         let rooms = self.rooms.validate_with(room_ids);
         let sort_key = self.sort_key.validate();
@@ -385,6 +414,16 @@ impl AnnouncementFormData {
                 .add_error("Darf nicht vor dem Start-Datum liegen.".to_owned());
             return None;
         }
+        let begin_time = begin_time?;
+        let end_time = end_time?;
+        if let Some(ref begin_time) = begin_time.0
+            && let Some(ref end_time) = end_time.0
+            && end_time.0 == begin_time.0
+        {
+            self.end_time
+                .add_error("Darf nicht gleich der Start-Uhrzeit sein.".to_owned());
+            return None;
+        }
         let rooms = rooms?.0;
         let categories = categories?.0;
 
@@ -398,6 +437,8 @@ impl AnnouncementFormData {
                     show_with_days: self.show_with_days.get_value(),
                     begin_date: begin_date.0.map(|v| v.0),
                     end_date: end_date.0.map(|v| v.0),
+                    begin_time: begin_time.0.map(|v| v.0),
+                    end_time: end_time.0.map(|v| v.0),
                     show_with_categories: self.show_with_categories.get_value(),
                     show_with_all_categories: categories.is_empty(),
                     show_with_rooms: self.show_with_rooms.get_value(),
@@ -425,6 +466,14 @@ impl From<FullAnnouncement> for AnnouncementFormData {
             .into(),
             end_date: validation::MaybeEmpty(value.announcement.end_date.map(validation::IsoDate))
                 .into(),
+            begin_time: validation::MaybeEmpty(
+                value.announcement.begin_time.map(validation::TimeOfDay),
+            )
+            .into(),
+            end_time: validation::MaybeEmpty(
+                value.announcement.end_time.map(validation::TimeOfDay),
+            )
+            .into(),
             show_with_categories: value.announcement.show_with_categories.into(),
             categories: validation::CommaSeparatedUuidsFromList(value.category_ids).into(),
             show_with_rooms: value.announcement.show_with_rooms.into(),
@@ -529,3 +578,87 @@ impl<'a> EditAnnouncementFormTemplate<'a> {
         result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_store::models::Announcement;
+    use uuid::uuid;
+
+    #[test]
+    fn test_cloned_announcement_form_data() {
+        let source = FullAnnouncement {
+            announcement: Announcement {
+                id: uuid!("0197f0a4-5b1c-7d2e-8f3a-4b5c6d7e8f90"),
+                event_id: 1,
+                announcement_type: AnnouncementType::Warning,
+                text: "Achtung: Auf dem Sportplatz ist es nass und rutschig.".to_string(),
+                show_with_days: true,
+                begin_date: Some("2025-01-03".parse().unwrap()),
+                end_date: Some("2025-01-05".parse().unwrap()),
+                begin_time: Some("14:00:00".parse().unwrap()),
+                end_time: None,
+                show_with_categories: true,
+                show_with_all_categories: false,
+                show_with_rooms: true,
+                show_with_all_rooms: false,
+                sort_key: 10,
+                last_updated: "2025-01-01 12:00:00+00:00".parse().unwrap(),
+            },
+            category_ids: vec![uuid!("019774dc-81c4-7862-a9ba-63de3d726010")],
+            room_ids: vec![
+                uuid!("41d96e3c-17de-46ff-9331-690366a4a0a5"),
+                uuid!("a3820b53-e9a9-4840-b071-7fa3ba34010a"),
+            ],
+        };
+        let new_id = uuid!("0197f0a4-5b1c-7d2e-8f3a-000000000001");
+
+        let original = AnnouncementFormData::from(source.clone());
+        let cloned = AnnouncementFormData::for_cloned_announcement(source, new_id);
+
+        assert_eq!(cloned.announcement_id.string_value(), new_id.to_string());
+        assert_eq!(cloned.last_updated.string_value(), "");
+        assert_eq!(
+            cloned.announcement_type.string_value(),
+            original.announcement_type.string_value()
+        );
+        assert_eq!(cloned.text.string_value(), original.text.string_value());
+        assert_eq!(
+            cloned.show_with_days.get_value(),
+            original.show_with_days.get_value()
+        );
+        assert_eq!(
+            cloned.begin_date.string_value(),
+            original.begin_date.string_value()
+        );
+        assert_eq!(
+            cloned.end_date.string_value(),
+            original.end_date.string_value()
+        );
+        assert_eq!(
+            cloned.begin_time.string_value(),
+            original.begin_time.string_value()
+        );
+        assert_eq!(
+            cloned.end_time.string_value(),
+            original.end_time.string_value()
+        );
+        assert_eq!(
+            cloned.show_with_categories.get_value(),
+            original.show_with_categories.get_value()
+        );
+        assert_eq!(
+            cloned.categories.string_value(),
+            original.categories.string_value()
+        );
+        assert_eq!(
+            cloned.show_with_rooms.get_value(),
+            original.show_with_rooms.get_value()
+        );
+        assert_eq!(cloned.rooms.string_value(), original.rooms.string_value());
+        assert_eq!(
+            cloned.sort_key.string_value(),
+            original.sort_key.string_value()
+        );
+    }
+}
@@ -7,6 +7,7 @@ use crate::web::ui::base_template::{
 };
 use crate::web::ui::error::AppError;
 use crate::web::ui::form_values::{_FormValidSimpleValidate, BoolFormValue, FormValue};
+use crate::web::ui::i18n::Messages;
 use crate::web::ui::sub_templates::form_inputs::{
     CheckboxTemplate, FormFieldTemplate, HiddenInputTemplate, InputSize, InputType,
 };
@@ -135,7 +136,7 @@ pub async fn edit_category(
     util::create_edit_form_response(
         result,
         &tmpl,
-        "Die Kategorie",
+        Messages::for_request(&req).category_name,
         req.url_for(
             "edit_category_form",
             &[event_id.to_string(), category_id.to_string()],
@@ -248,7 +249,7 @@ pub async fn new_category(
     util::create_edit_form_response(
         result,
         &tmpl,
-        "Die Kategorie",
+        Messages::for_request(&req).category_name,
         req.url_for("new_category_form", &[event_id.to_string()])?,
         "edit_category_form",
         true,
@@ -263,7 +264,7 @@ struct CategoryFormData {
     /// id is taken from the URL and passed to [validate] as `known_id` instead)
     category_id: FormValue<Uuid>,
     title: FormValue<validation::NonEmptyString>,
-    icon: FormValue<String>,
+    icon: FormValue<validation::IconName>,
     color: FormValue<validation::ColorHexString>,
     is_official: BoolFormValue,
     sort_key: FormValue<validation::Int32>,
@@ -289,7 +290,7 @@ impl CategoryFormData {
         Some(NewCategory {
             id: category_id?,
             title: title?.into_inner(),
-            icon: icon?,
+            icon: icon?.into_inner(),
             color: color?.0,
             event_id: 0,
             is_official,
@@ -303,7 +304,7 @@ impl From<Category> for CategoryFormData {
         Self {
             category_id: value.id.into(),
             title: validation::NonEmptyString(value.title).into(),
-            icon: value.icon.into(),
+            icon: validation::IconName(value.icon).into(),
             color: validation::ColorHexString(value.color).into(),
             is_official: value.is_official.into(),
             sort_key: validation::Int32(value.sort_key).into(),
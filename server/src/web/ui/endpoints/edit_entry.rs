@@ -1,11 +1,13 @@
 use crate::data_store::auth_token::Privilege;
 use crate::data_store::models::{
-    Category, EntryState, EventClockInfo, ExtendedEvent, FullEntry, FullNewEntry, FullPreviousDate,
-    NewEntry, PreviousDate, Room,
+    Category, EntryAuditAction, EntryLinks, EntryState, EntryTemplate, EntryTemplateData,
+    EventClockInfo, EventDayScheduleSection, ExtendedEvent, FullEntry, FullNewEntry, NewEntry,
+    Room,
 };
-use crate::data_store::{EntryId, EventId, StoreError};
+use crate::data_store::{CategoryId, EntryId, EntryTemplateId, EventId, RoomId, StoreError};
 use crate::web::time_calculation::{
-    get_effective_date, most_reasonable_date, timestamp_from_effective_date_and_time,
+    end_timestamp_from_end_time, get_effective_date, is_within_event_dates, most_reasonable_date,
+    open_end_timestamp, suggest_section_for_time, timestamp_from_effective_date_and_time,
 };
 use crate::web::ui::base_template::{AnyEventData, BaseTemplateContext, MainNavButton};
 use crate::web::ui::error::AppError;
@@ -13,12 +15,16 @@ use crate::web::ui::form_values::{
     _FormValidSimpleValidate, BoolFormValue, FormValue, FormValueRepresentation,
     ValidateFromFormInput,
 };
+use crate::web::ui::i18n::Messages;
 use crate::web::ui::sub_templates::form_inputs::{
     CheckboxTemplate, FormFieldTemplate, HiddenInputTemplate, InputSize, InputType,
     RadioButtonGroupTemplate, SelectEntry, SelectTemplate,
 };
-use crate::web::ui::util::{FormSubmitResult, event_days, url_for_generic_entry, weekday_short};
+use crate::web::ui::util::{
+    FormSubmitResult, event_day_time_note, event_days, url_for_generic_entry,
+};
 use crate::web::ui::{sub_templates, util, validation};
+use crate::web::util::{MAX_ENTRY_TITLE_LENGTH, weekday_short};
 use crate::web::{AppState, time_calculation};
 use actix_web::web::{Form, Html, Query};
 use actix_web::{HttpRequest, Responder, get, post, web};
@@ -26,7 +32,6 @@ use askama::Template;
 use chrono::Timelike;
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
-use std::collections::BTreeSet;
 use std::fmt::Debug;
 use uuid::Uuid;
 
@@ -40,29 +45,32 @@ async fn edit_entry_form(
     let session_token =
         util::extract_session_token(&state, &req, Privilege::ManageEntries, event_id)?;
     let store = state.store.clone();
-    let (entry, event, rooms, categories, auth) = web::block(move || -> Result<_, AppError> {
-        let mut store = store.get_facade()?;
-        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
-        auth.check_privilege(event_id, Privilege::ManageEntries)?;
-        Ok((
-            store.get_entry(&auth, entry_id)?,
-            store.get_extended_event(&auth, event_id)?,
-            store.get_rooms(&auth, event_id)?,
-            store.get_categories(&auth, event_id)?,
-            auth,
-        ))
-    })
-    .await??;
+    let (entry, event, rooms, categories, responsible_persons, auth) =
+        web::block(move || -> Result<_, AppError> {
+            let mut store = store.get_facade()?;
+            let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+            auth.check_privilege(event_id, Privilege::ManageEntries)?;
+            Ok((
+                store.get_entry(&auth, entry_id)?,
+                store.get_extended_event(&auth, event_id)?,
+                store.get_rooms(&auth, event_id)?,
+                store.get_categories(&auth, event_id)?,
+                store.get_distinct_responsible_persons(&auth, event_id)?,
+                auth,
+            ))
+        })
+        .await??;
 
     let entry_id = entry.entry.id;
     let entry_begin = entry.entry.begin;
     let entry_state = entry.entry.state;
     let form_data = EntryFormData::from_full_entry(entry, &event.clock_info);
 
+    let messages = Messages::for_request(&req);
     let tmpl = EditEntryFormTemplate {
         base: BaseTemplateContext {
             request: &req,
-            page_title: "Eintrag bearbeiten", // TODO
+            page_title: messages.edit_entry_title,
             event: AnyEventData::ExtendedEvent(&event),
             current_date: Some(get_effective_date(&entry_begin, &event.clock_info)),
             auth_token: Some(&auth),
@@ -72,11 +80,13 @@ async fn edit_entry_form(
         form_data: &form_data,
         rooms: &rooms,
         categories: &categories,
+        responsible_persons: &responsible_persons,
         entry_id: Some(&entry_id),
         has_unsaved_changes: false,
         is_new_entry: false,
         current_entry_state: Some(entry_state),
         cloned_from_entry_id: None,
+        entry_templates: &[],
     };
 
     Ok(Html::new(tmpl.render()?))
@@ -93,19 +103,21 @@ async fn edit_entry(
     let session_token =
         util::extract_session_token(&state, &req, Privilege::ManageEntries, event_id)?;
     let store = state.store.clone();
-    let (event, old_entry, rooms, categories, auth) = web::block(move || -> Result<_, AppError> {
-        let mut store = store.get_facade()?;
-        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
-        auth.check_privilege(event_id, Privilege::ManageEntries)?;
-        Ok((
-            store.get_extended_event(&auth, event_id)?,
-            store.get_entry(&auth, entry_id)?,
-            store.get_rooms(&auth, event_id)?,
-            store.get_categories(&auth, event_id)?,
-            auth,
-        ))
-    })
-    .await??;
+    let (event, old_entry, rooms, categories, responsible_persons, auth) =
+        web::block(move || -> Result<_, AppError> {
+            let mut store = store.get_facade()?;
+            let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+            auth.check_privilege(event_id, Privilege::ManageEntries)?;
+            Ok((
+                store.get_extended_event(&auth, event_id)?,
+                store.get_entry(&auth, entry_id)?,
+                store.get_rooms(&auth, event_id)?,
+                store.get_categories(&auth, event_id)?,
+                store.get_distinct_responsible_persons(&auth, event_id)?,
+                auth,
+            ))
+        })
+        .await??;
     if event_id != old_entry.entry.event_id {
         return Err(AppError::EntityNotFound);
     }
@@ -116,7 +128,7 @@ async fn edit_entry(
         &categories.iter().map(|c| c.id).collect(),
         Some(entry_id),
         Some(old_entry.entry.state),
-        &event.clock_info,
+        &event,
     );
 
     let mut entry_begin = old_entry.entry.begin;
@@ -126,26 +138,23 @@ async fn edit_entry(
             entry.entry.event_id = event_id;
             entry_begin = entry.entry.begin;
             entry_state = entry.entry.state;
-            if let Some(previous_date_comment) = create_previous_date
-                && (entry.entry.begin != old_entry.entry.begin
-                    || entry.entry.end != old_entry.entry.end
-                    || !unordered_equality(&entry.room_ids, &old_entry.room_ids))
-            {
-                entry.previous_dates.push(FullPreviousDate {
-                    previous_date: PreviousDate {
-                        id: Uuid::now_v7(),
-                        entry_id,
-                        comment: previous_date_comment,
-                        begin: old_entry.entry.begin,
-                        end: old_entry.entry.end,
-                    },
-                    room_ids: old_entry.room_ids.clone(),
-                });
-            }
             let auth_clone = auth.clone();
+            let public = old_entry.entry.is_public() || entry.entry.is_public();
             web::block(move || -> Result<_, StoreError> {
                 let mut store = state.store.get_facade()?;
-                store.create_or_update_entry(&auth_clone, entry, true, previous_last_updated)?;
+                store.create_or_update_entry(
+                    &auth_clone,
+                    entry,
+                    true,
+                    create_previous_date,
+                    previous_last_updated,
+                )?;
+                state.live_updates.publish_entry_change(
+                    event_id,
+                    entry_id,
+                    EntryAuditAction::Update,
+                    public,
+                );
                 Ok(())
             })
             .await?
@@ -154,10 +163,11 @@ async fn edit_entry(
             FormSubmitResult::ValidationError
         };
 
+    let messages = Messages::for_request(&req);
     let tmpl = EditEntryFormTemplate {
         base: BaseTemplateContext {
             request: &req,
-            page_title: "Eintrag bearbeiten", // TODO
+            page_title: messages.edit_entry_title,
             event: AnyEventData::ExtendedEvent(&event),
             current_date: Some(get_effective_date(
                 &old_entry.entry.begin,
@@ -170,17 +180,19 @@ async fn edit_entry(
         form_data: &data,
         rooms: &rooms,
         categories: &categories,
+        responsible_persons: &responsible_persons,
         entry_id: Some(&entry_id),
         has_unsaved_changes: true,
         current_entry_state: Some(old_entry.entry.state),
         is_new_entry: false,
         cloned_from_entry_id: None,
+        entry_templates: &[],
     };
 
     util::create_edit_form_response(
         result,
         &tmpl,
-        "Der Eintrag",
+        messages.entry_name,
         req.url_for(
             "edit_entry_form",
             &[event_id.to_string(), entry_id.to_string()],
@@ -208,10 +220,11 @@ async fn new_entry_form(
     let event_id = path.into_inner();
     let date = query_data.date;
     let clone_from = query_data.clone_from;
+    let from_template = query_data.from_template;
     let session_token =
         util::extract_session_token(&state, &req, Privilege::ManageEntries, event_id)?;
     let store = state.store.clone();
-    let (event, rooms, categories, cloned_entry, auth) =
+    let (event, rooms, categories, cloned_entry, entry_templates, responsible_persons, auth) =
         web::block(move || -> Result<_, AppError> {
             let mut store = store.get_facade()?;
             let auth = store.get_auth_token_for_session(&session_token, event_id)?;
@@ -223,10 +236,20 @@ async fn new_entry_form(
                 clone_from
                     .map(|cloned_entry_id| store.get_entry(&auth, cloned_entry_id))
                     .transpose()?,
+                store.list_entry_templates(&auth, event_id)?,
+                store.get_distinct_responsible_persons(&auth, event_id)?,
                 auth,
             ))
         })
         .await??;
+    let template = from_template
+        .map(|template_id| {
+            entry_templates
+                .iter()
+                .find(|t| t.id == template_id)
+                .ok_or(AppError::EntityNotFound)
+        })
+        .transpose()?;
 
     let entry_id = Uuid::now_v7();
     let entry_date = date.unwrap_or_else(|| most_reasonable_date(&event));
@@ -236,13 +259,23 @@ async fn new_entry_form(
         let category_id = categories.first().ok_or(AppError::InternalError(
             "Event does not have a single category".to_owned(),
         ))?;
-        EntryFormData::for_new_entry(entry_id, entry_date, category_id.id)
+        let mut form_data = EntryFormData::for_new_entry(entry_id, entry_date, category_id.id);
+        form_data.apply_default_time_schedule(entry_date, &event, chrono::Utc::now());
+        if let Some(template) = template {
+            form_data.apply_template(
+                template.entry_data.clone(),
+                &rooms.iter().map(|r| r.id).collect::<Vec<_>>(),
+                &categories.iter().map(|c| c.id).collect::<Vec<_>>(),
+            );
+        }
+        form_data
     };
 
+    let messages = Messages::for_request(&req);
     let tmpl = EditEntryFormTemplate {
         base: BaseTemplateContext {
             request: &req,
-            page_title: "Neuer Eintrag",
+            page_title: messages.new_entry_title,
             event: AnyEventData::ExtendedEvent(&event),
             current_date: date,
             auth_token: Some(&auth),
@@ -252,11 +285,13 @@ async fn new_entry_form(
         form_data: &form_data,
         rooms: &rooms,
         categories: &categories,
+        responsible_persons: &responsible_persons,
         entry_id: Some(&entry_id),
         has_unsaved_changes: false,
         current_entry_state: None,
         is_new_entry: true,
         cloned_from_entry_id: clone_from,
+        entry_templates: &entry_templates,
     };
 
     Ok(Html::new(tmpl.render()?))
@@ -275,18 +310,20 @@ async fn new_entry(
     let session_token =
         util::extract_session_token(&state, &req, Privilege::ManageEntries, event_id)?;
     let store = state.store.clone();
-    let (event, rooms, categories, auth) = web::block(move || -> Result<_, AppError> {
-        let mut store = store.get_facade()?;
-        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
-        auth.check_privilege(event_id, Privilege::ManageEntries)?;
-        Ok((
-            store.get_extended_event(&auth, event_id)?,
-            store.get_rooms(&auth, event_id)?,
-            store.get_categories(&auth, event_id)?,
-            auth,
-        ))
-    })
-    .await??;
+    let (event, rooms, categories, responsible_persons, auth) =
+        web::block(move || -> Result<_, AppError> {
+            let mut store = store.get_facade()?;
+            let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+            auth.check_privilege(event_id, Privilege::ManageEntries)?;
+            Ok((
+                store.get_extended_event(&auth, event_id)?,
+                store.get_rooms(&auth, event_id)?,
+                store.get_categories(&auth, event_id)?,
+                store.get_distinct_responsible_persons(&auth, event_id)?,
+                auth,
+            ))
+        })
+        .await??;
 
     let mut data = data.into_inner();
     let entry = data.validate(
@@ -294,7 +331,7 @@ async fn new_entry(
         &categories.iter().map(|c| c.id).collect(),
         None,
         None,
-        &event.clock_info,
+        &event,
     );
 
     let mut entry_id = None;
@@ -309,7 +346,15 @@ async fn new_entry(
         web::block(move || -> Result<_, StoreError> {
             let mut store = state.store.get_facade()?;
             // TODO detect and ignore double addition
-            store.create_or_update_entry(&auth_clone, entry, false, None)?;
+            let entry_id = entry.entry.id;
+            let public = entry.entry.is_public();
+            store.create_or_update_entry(&auth_clone, entry, false, None, None)?;
+            state.live_updates.publish_entry_change(
+                event_id,
+                entry_id,
+                EntryAuditAction::Create,
+                public,
+            );
             Ok(())
         })
         .await?
@@ -318,10 +363,11 @@ async fn new_entry(
         util::FormSubmitResult::ValidationError
     };
 
+    let messages = Messages::for_request(&req);
     let tmpl = EditEntryFormTemplate {
         base: BaseTemplateContext {
             request: &req,
-            page_title: "Neuer Eintrag",
+            page_title: messages.new_entry_title,
             event: AnyEventData::ExtendedEvent(&event),
             current_date: date,
             auth_token: Some(&auth),
@@ -331,17 +377,19 @@ async fn new_entry(
         form_data: &data,
         rooms: &rooms,
         categories: &categories,
+        responsible_persons: &responsible_persons,
         entry_id: entry_id.as_ref(),
         has_unsaved_changes: true,
         current_entry_state: None,
         is_new_entry: true,
         cloned_from_entry_id: query_data.clone_from,
+        entry_templates: &[],
     };
 
     util::create_edit_form_response(
         result,
         &tmpl,
-        "Der Eintrag",
+        messages.entry_name,
         req.url_for("new_entry_form", &[event_id.to_string()])?,
         "edit_entry_form",
         true,
@@ -364,6 +412,9 @@ pub struct NewEntryQueryParams {
     pub date: Option<chrono::NaiveDate>,
     /// When given, used to prefill the form with all data from this exiting entry
     pub clone_from: Option<EntryId>,
+    /// When given, used to prefill the form with the default values from this entry template
+    /// (unless `clone_from` is given)
+    pub from_template: Option<EntryTemplateId>,
 }
 
 #[derive(Template)]
@@ -374,11 +425,15 @@ struct EditEntryFormTemplate<'a> {
     form_data: &'a EntryFormData,
     categories: &'a Vec<Category>,
     rooms: &'a Vec<Room>,
+    /// Existing values of the responsible_person field, suggested for autocompletion
+    responsible_persons: &'a [String],
     entry_id: Option<&'a EntryId>,
     has_unsaved_changes: bool,
     is_new_entry: bool, // TODO remove and replace with current_entry_state.is_none()
     current_entry_state: Option<EntryState>,
     cloned_from_entry_id: Option<EntryId>,
+    /// Entry templates to be offered for prefilling the form. Only used for new entries.
+    entry_templates: &'a [EntryTemplate],
 }
 
 impl<'a> EditEntryFormTemplate<'a> {
@@ -391,6 +446,7 @@ impl<'a> EditEntryFormTemplate<'a> {
             url.set_query(Some(&serde_urlencoded::to_string(NewEntryQueryParams {
                 date: self.base.current_date,
                 clone_from: self.cloned_from_entry_id,
+                from_template: None,
             })?));
             Ok(url)
         } else {
@@ -432,6 +488,18 @@ impl<'a> EditEntryFormTemplate<'a> {
             )
         }
     }
+    fn entry_template_url(&self, template: &EntryTemplate) -> Result<url::Url, AppError> {
+        let mut url = self
+            .base
+            .request
+            .url_for("new_entry_form", &[self.event.basic_data.id.to_string()])?;
+        url.set_query(Some(&serde_urlencoded::to_string(NewEntryQueryParams {
+            date: self.base.current_date,
+            clone_from: None,
+            from_template: Some(template.id),
+        })?));
+        Ok(url)
+    }
     fn room_entries(&self) -> Vec<SelectEntry<'a>> {
         self.rooms
             .iter()
@@ -455,11 +523,15 @@ impl<'a> EditEntryFormTemplate<'a> {
             .into_iter()
             .map(|date| SelectEntry {
                 value: Cow::Owned(date.to_string()),
-                text: Cow::Owned(format!(
-                    "{} ({})",
-                    date.format("%d.%m."),
-                    weekday_short(&date)
-                )),
+                text: Cow::Owned(match event_day_time_note(&self.event.basic_data, &date) {
+                    Some(note) => format!(
+                        "{} ({}, {})",
+                        date.format("%d.%m."),
+                        weekday_short(&date),
+                        note
+                    ),
+                    None => format!("{} ({})", date.format("%d.%m."), weekday_short(&date)),
+                }),
             })
             .collect()
     }
@@ -503,7 +575,7 @@ struct EntryFormData {
     /// Id of the entry, only used for creating new entries (for editing existing entries, the id is
     /// taken from the URL and passed to [validate] as `known_entry_id` instead)
     entry_id: FormValue<Uuid>,
-    title: FormValue<validation::NonEmptyString>,
+    title: FormValue<validation::BoundedString<MAX_ENTRY_TITLE_LENGTH>>,
     comment: FormValue<String>,
     room_comment: FormValue<String>,
     time_comment: FormValue<String>,
@@ -511,12 +583,24 @@ struct EntryFormData {
     responsible_person: FormValue<String>,
     day: FormValue<validation::IsoDate>,
     begin: FormValue<validation::TimeOfDay>,
-    duration: FormValue<validation::NiceDurationHours>,
+    duration: FormValue<validation::EntryDuration>,
+    /// Selects whether the entry's end is given by `duration` or by `end_time`, or whether begin
+    /// and end are given by `begin_datetime` and `end_datetime` instead
+    end_mode: FormValue<EndMode>,
+    end_time: FormValue<validation::TimeOfDay>,
+    begin_datetime: FormValue<validation::DateTimeLocal>,
+    end_datetime: FormValue<validation::DateTimeLocal>,
     category: FormValue<validation::UuidFromList>,
     rooms: FormValue<validation::CommaSeparatedUuidsFromList>,
     is_cancelled: BoolFormValue,
+    is_tentative: BoolFormValue,
+    orga_only: BoolFormValue,
     is_room_reservation: BoolFormValue,
     is_exclusive: BoolFormValue,
+    /// Skip the check that the entry lies within the event's date range
+    allow_outside_range: BoolFormValue,
+    color_override: FormValue<validation::MaybeEmpty<validation::ColorHexString>>,
+    links: FormValue<validation::EntryLinkList>,
     /// `last_updated` value of the (original) entry. Used for detecting editing conflicts.
     /// Only used for editing existing entries; can be empty/missing when creating new entries.
     last_updated: FormValue<validation::SimpleTimestampMicroseconds>,
@@ -531,12 +615,95 @@ impl EntryFormData {
         Self {
             entry_id: entry_id.into(),
             day: validation::IsoDate(date).into(),
+            end_mode: EndMode::Duration.into(),
+            begin_datetime: FormValue::empty(),
+            end_datetime: FormValue::empty(),
             category: validation::UuidFromList(category_id).into(),
             change_state: ChangeStateValue::Accept.into(),
             ..Self::default()
         }
     }
 
+    /// Prefill the begin and duration of a new entry on the given (effective) `date` from the
+    /// event's default time schedule: If the `date` is the current day (according to `now`), the
+    /// section which is active at the current time is used. For any other day, the first section
+    /// of the day is used.
+    fn apply_default_time_schedule(
+        &mut self,
+        date: chrono::NaiveDate,
+        event: &ExtendedEvent,
+        now: chrono::DateTime<chrono::Utc>,
+    ) {
+        let time = if get_effective_date(&now, &event.clock_info) == date {
+            now.with_timezone(&event.clock_info.timezone).time()
+        } else {
+            event.clock_info.effective_begin_of_day
+        };
+        if let Some((section_begin, section)) = suggest_section_for_time(
+            &event.default_time_schedule,
+            time,
+            event.clock_info.effective_begin_of_day,
+        ) {
+            self.apply_schedule_section(section_begin, section);
+        }
+    }
+
+    /// Prefill the begin and duration of a new entry with the begin and length of the given section
+    /// of the event's default time schedule. For the last section, which has no end, a duration of
+    /// one hour is used.
+    fn apply_schedule_section(
+        &mut self,
+        section_begin: chrono::NaiveTime,
+        section: &EventDayScheduleSection,
+    ) {
+        self.begin = validation::TimeOfDay(section_begin).into();
+        let duration = match section.end_time {
+            Some(end_time) if end_time > section_begin => end_time - section_begin,
+            Some(end_time) => end_time - section_begin + chrono::Duration::days(1),
+            None => chrono::Duration::hours(1),
+        };
+        self.duration = validation::EntryDuration::Fixed(duration).into();
+    }
+
+    /// Prefill the form fields of a new entry with the default values from an entry template.
+    ///
+    /// Rooms and the category of the template are only applied if they are contained in the given
+    /// lists of existing rooms and categories of the event.
+    fn apply_template(
+        &mut self,
+        template: EntryTemplateData,
+        existing_rooms: &[RoomId],
+        existing_categories: &[CategoryId],
+    ) {
+        self.title = validation::BoundedString(template.title).into();
+        self.comment = template.comment.into();
+        self.description = template.description.into();
+        self.time_comment = template.time_comment.into();
+        self.room_comment = template.room_comment.into();
+        self.responsible_person = template.responsible_person.into();
+        self.is_exclusive = template.is_exclusive.into();
+        self.is_tentative = template.is_tentative.into();
+        self.orga_only = template.orga_only.into();
+        self.is_room_reservation = template.is_room_reservation.into();
+        self.rooms = validation::CommaSeparatedUuidsFromList(
+            template
+                .room_ids
+                .into_iter()
+                .filter(|room_id| existing_rooms.contains(room_id))
+                .collect(),
+        )
+        .into();
+        if let Some(category) = template
+            .category
+            .filter(|category| existing_categories.contains(category))
+        {
+            self.category = validation::UuidFromList(category).into();
+        }
+        self.color_override =
+            validation::MaybeEmpty(template.color_override.map(validation::ColorHexString)).into();
+        self.links = validation::EntryLinkList(template.links).into();
+    }
+
     fn for_cloned_entry(
         cloned_entry: FullEntry,
         new_entry_id: EntryId,
@@ -555,7 +722,7 @@ impl EntryFormData {
         categories: &Vec<Uuid>,
         known_entry_id: Option<EntryId>,
         current_entry_state: Option<EntryState>,
-        clock_info: &EventClockInfo,
+        event: &ExtendedEvent,
     ) -> Option<(
         FullNewEntry,
         Option<chrono::DateTime<chrono::Utc>>,
@@ -569,13 +736,30 @@ impl EntryFormData {
         let description = self.description.validate();
         let responsible_person = self.responsible_person.validate();
         let is_cancelled = self.is_cancelled.get_value();
+        let is_tentative = self.is_tentative.get_value();
+        let orga_only = self.orga_only.get_value();
         let is_room_reservation = self.is_room_reservation.get_value();
         let is_exclusive = self.is_exclusive.get_value();
+        let allow_outside_range = self.allow_outside_range.get_value();
+        let color_override = self.color_override.validate();
+        let links = self.links.validate();
         let category = self.category.validate_with(categories);
         let room_ids = self.rooms.validate_with(rooms);
-        let day = self.day.validate();
-        let time = self.begin.validate();
-        let duration = self.duration.validate();
+        let end_mode = self.end_mode.validate();
+        let timestamps_mode = end_mode == Some(EndMode::Timestamps);
+        let day = (!timestamps_mode).then(|| self.day.validate());
+        let time = (!timestamps_mode).then(|| self.begin.validate());
+        let duration = (end_mode == Some(EndMode::Duration)).then(|| self.duration.validate());
+        let end_time = (end_mode == Some(EndMode::EndTime)).then(|| self.end_time.validate());
+        let begin_datetime = timestamps_mode.then(|| {
+            util::validate_datetime_local_value(
+                &mut self.begin_datetime,
+                &event.clock_info.timezone,
+            )
+        });
+        let end_datetime = timestamps_mode.then(|| {
+            util::validate_datetime_local_value(&mut self.end_datetime, &event.clock_info.timezone)
+        });
         let previous_last_updated = self.last_updated.validate();
         let create_previous_date = self.create_previous_date.get_value();
         let previous_date_comment =
@@ -583,11 +767,81 @@ impl EntryFormData {
         let change_state = self.change_state.validate();
         let orga_comment = self.orga_comment.validate();
 
-        let begin = timestamp_from_effective_date_and_time(
-            day?.into_inner(),
-            time?.into_inner(),
-            clock_info,
-        );
+        let end_mode = end_mode?;
+        let mut open_ended = false;
+        let (begin, end) = if end_mode == EndMode::Timestamps {
+            let begin = begin_datetime
+                .expect("begin_datetime form value should be validated in timestamps mode")?;
+            let end = end_datetime
+                .expect("end_datetime form value should be validated in timestamps mode")?;
+            if end < begin {
+                self.end_datetime
+                    .add_error("Das Ende muss nach dem Beginn liegen.".to_owned());
+                return None;
+            }
+            (begin, end)
+        } else {
+            let day = day
+                .expect("day form value should be validated if not in timestamps mode")?
+                .into_inner();
+            let time = time
+                .expect("begin form value should be validated if not in timestamps mode")?
+                .into_inner();
+            let begin = timestamp_from_effective_date_and_time(day, time, &event.clock_info);
+            let end = match end_mode {
+                EndMode::Duration => match duration
+                    .expect("duration form value should be validated in duration mode")?
+                {
+                    validation::EntryDuration::Fixed(duration) => begin + duration,
+                    validation::EntryDuration::OpenEnded => {
+                        let end = open_end_timestamp(day, &event.basic_data, &event.clock_info);
+                        if end < begin {
+                            self.duration.add_error(
+                                "Das Tagesende liegt vor dem Beginn des Eintrags.".to_owned(),
+                            );
+                            return None;
+                        }
+                        open_ended = true;
+                        end
+                    }
+                },
+                EndMode::EndTime => {
+                    let end_time = end_time
+                        .expect("end_time form value should be validated in end time mode")?
+                        .into_inner();
+                    let Some(end) =
+                        end_timestamp_from_end_time(&begin, day, end_time, &event.clock_info)
+                    else {
+                        self.end_time
+                            .add_error("Das Ende muss nach dem Beginn liegen.".to_owned());
+                        return None;
+                    };
+                    end
+                }
+                EndMode::Timestamps => unreachable!("timestamps mode is handled above"),
+            };
+            (begin, end)
+        };
+        if !allow_outside_range
+            && !is_within_event_dates(&begin, &end, &event.basic_data, &event.clock_info)
+        {
+            let format_boundary =
+                |date: chrono::NaiveDate, time: Option<chrono::NaiveTime>| match time {
+                    Some(time) => format!("{} {}", date.format("%d.%m."), time.format("%H:%M")),
+                    None => date.format("%d.%m.").to_string(),
+                };
+            let error = format!(
+                "Der Eintrag liegt außerhalb des Veranstaltungszeitraums ({} – {}).",
+                format_boundary(event.basic_data.begin_date, event.basic_data.begin_time),
+                format_boundary(event.basic_data.end_date, event.basic_data.end_time)
+            );
+            if end_mode == EndMode::Timestamps {
+                self.begin_datetime.add_error(error);
+            } else {
+                self.day.add_error(error);
+            }
+            return None;
+        }
         Some((
             FullNewEntry {
                 entry: NewEntry {
@@ -598,15 +852,20 @@ impl EntryFormData {
                     is_room_reservation,
                     event_id: 0,
                     begin,
-                    end: begin + duration?.into_inner(),
+                    end,
                     category: category?.into_inner(),
                     comment: comment?,
                     time_comment: time_comment?,
                     room_comment: room_comment?,
                     is_exclusive,
                     is_cancelled,
+                    is_tentative,
+                    orga_only,
+                    open_ended,
                     state: change_state?.change_state(current_entry_state),
                     orga_comment: orga_comment?,
+                    color_override: color_override?.0.map(|c| c.0),
+                    links: Some(links?.0).filter(|l| !l.is_empty()).map(EntryLinks),
                 },
                 room_ids: room_ids?.into_inner(),
                 previous_dates: vec![],
@@ -625,7 +884,7 @@ impl EntryFormData {
     fn from_full_entry(value: FullEntry, clock_info: &EventClockInfo) -> Self {
         Self {
             entry_id: FormValue::empty(),
-            title: validation::NonEmptyString(value.entry.title).into(),
+            title: validation::BoundedString(value.entry.title).into(),
             comment: value.entry.comment.into(),
             room_comment: value.entry.room_comment.into(),
             time_comment: value.entry.time_comment.into(),
@@ -641,12 +900,52 @@ impl EntryFormData {
                     .time(),
             )
             .into(),
-            duration: validation::NiceDurationHours(value.entry.end - value.entry.begin).into(),
+            duration: if value.entry.open_ended {
+                validation::EntryDuration::OpenEnded
+            } else {
+                validation::EntryDuration::Fixed(value.entry.end - value.entry.begin)
+            }
+            .into(),
+            end_mode: EndMode::Duration.into(),
+            end_time: validation::TimeOfDay(
+                value
+                    .entry
+                    .end
+                    .with_timezone(&clock_info.timezone)
+                    .naive_local()
+                    .time(),
+            )
+            .into(),
+            begin_datetime: validation::DateTimeLocal(
+                value
+                    .entry
+                    .begin
+                    .with_timezone(&clock_info.timezone)
+                    .naive_local(),
+            )
+            .into(),
+            end_datetime: validation::DateTimeLocal(
+                value
+                    .entry
+                    .end
+                    .with_timezone(&clock_info.timezone)
+                    .naive_local(),
+            )
+            .into(),
             category: validation::UuidFromList(value.entry.category).into(),
             rooms: validation::CommaSeparatedUuidsFromList(value.room_ids).into(),
             is_cancelled: value.entry.is_cancelled.into(),
+            is_tentative: value.entry.is_tentative.into(),
+            orga_only: value.entry.orga_only.into(),
             is_room_reservation: value.entry.is_room_reservation.into(),
             is_exclusive: value.entry.is_exclusive.into(),
+            allow_outside_range: false.into(),
+            color_override: validation::MaybeEmpty(
+                value.entry.color_override.map(validation::ColorHexString),
+            )
+            .into(),
+            links: validation::EntryLinkList(value.entry.links.map(|l| l.0).unwrap_or_default())
+                .into(),
             last_updated: validation::SimpleTimestampMicroseconds(value.entry.last_updated).into(),
             create_previous_date: false.into(),
             previous_date_comment: "".to_string().into(),
@@ -714,12 +1013,44 @@ impl ValidateFromFormInput for ChangeStateValue {
     }
 }
 
-fn unordered_equality<T: Eq + Ord>(a: &[T], b: &[T]) -> bool {
-    // Source: https://stackoverflow.com/a/42748484/10315508
-    let a: BTreeSet<_> = a.iter().collect();
-    let b: BTreeSet<_> = b.iter().collect();
+/// How the end (and begin) of the entry is specified in the [EntryFormData]
+#[derive(Debug, PartialEq, Eq, Default, Clone, Copy)]
+enum EndMode {
+    /// Use the `duration` field
+    #[default]
+    Duration,
+    /// Use the `end_time` field (local time of day)
+    EndTime,
+    /// Use the `begin_datetime` and `end_datetime` fields (local date and time) instead of `day`,
+    /// `begin` and `duration`
+    Timestamps,
+}
 
-    a == b
+impl EndMode {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Duration => "duration",
+            Self::EndTime => "end_time",
+            Self::Timestamps => "timestamps",
+        }
+    }
+}
+
+impl FormValueRepresentation for EndMode {
+    fn into_form_value_string(self) -> String {
+        self.as_str().to_string()
+    }
+}
+impl ValidateFromFormInput for EndMode {
+    fn from_form_value(value: &str) -> Result<Self, String> {
+        match value {
+            // Default to duration for clients, which don't know about the end time mode
+            "" | "duration" => Ok(Self::Duration),
+            "end_time" => Ok(Self::EndTime),
+            "timestamps" => Ok(Self::Timestamps),
+            _ => Err(format!("Keine gültige Angabe des Endes: {}", value)),
+        }
+    }
 }
 
 /// Selects the color of the left-side border of the entry form and the "flag" at the top of it for
@@ -768,3 +1099,242 @@ impl EntryFormStateMarking {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_entry_template() {
+        let room_a = Uuid::now_v7();
+        let room_b = Uuid::now_v7();
+        let deleted_room = Uuid::now_v7();
+        let default_category = Uuid::now_v7();
+        let template_category = Uuid::now_v7();
+        let date: chrono::NaiveDate = "2025-01-06".parse().unwrap();
+        let template = EntryTemplateData {
+            title: "Plenumsvorbereitung".to_owned(),
+            comment: "für alle Orgas".to_owned(),
+            responsible_person: "Orga-Team".to_owned(),
+            room_ids: vec![room_b, deleted_room],
+            category: Some(template_category),
+            is_exclusive: true,
+            orga_only: true,
+            color_override: Some("ff0000".to_owned()),
+            ..EntryTemplateData::default()
+        };
+
+        let mut form_data = EntryFormData::for_new_entry(Uuid::now_v7(), date, default_category);
+        form_data.apply_template(
+            template.clone(),
+            &[room_a, room_b],
+            &[default_category, template_category],
+        );
+        assert_eq!(form_data.title.validate().unwrap().0, "Plenumsvorbereitung");
+        assert_eq!(form_data.comment.validate().unwrap(), "für alle Orgas");
+        assert_eq!(
+            form_data.responsible_person.validate().unwrap(),
+            "Orga-Team"
+        );
+        assert!(form_data.is_exclusive.get_value());
+        assert!(form_data.orga_only.get_value());
+        assert!(!form_data.is_tentative.get_value());
+        // Rooms which don't exist anymore are dropped
+        assert_eq!(
+            form_data
+                .rooms
+                .validate_with(&vec![room_a, room_b])
+                .unwrap()
+                .0,
+            vec![room_b]
+        );
+        assert_eq!(
+            form_data
+                .category
+                .validate_with(&vec![default_category, template_category])
+                .unwrap()
+                .0,
+            template_category
+        );
+        assert_eq!(
+            form_data.color_override.validate().unwrap(),
+            validation::MaybeEmpty(Some(validation::ColorHexString("ff0000".to_owned())))
+        );
+        // Date is not part of the template
+        assert_eq!(form_data.day.validate().unwrap().0, date);
+
+        // Non-existing category of template is ignored
+        let mut form_data = EntryFormData::for_new_entry(Uuid::now_v7(), date, default_category);
+        form_data.apply_template(template, &[room_a], &[default_category]);
+        assert_eq!(
+            form_data
+                .category
+                .validate_with(&vec![default_category])
+                .unwrap()
+                .0,
+            default_category
+        );
+        assert_eq!(
+            form_data.rooms.validate_with(&vec![room_a]).unwrap().0,
+            Vec::<Uuid>::new()
+        );
+    }
+
+    #[test]
+    fn test_apply_default_time_schedule() {
+        use crate::data_store::models::EventDayTimeSchedule;
+        use chrono::TimeZone;
+
+        let event = ExtendedEvent {
+            basic_data: crate::data_store::models::Event {
+                id: 1,
+                title: "TestEvent".to_owned(),
+                begin_date: "2025-03-28".parse().unwrap(),
+                end_date: "2025-04-02".parse().unwrap(),
+                slug: None,
+                begin_time: None,
+                end_time: None,
+                archived: false,
+            },
+            clock_info: EventClockInfo {
+                timezone: chrono_tz::Europe::Berlin,
+                effective_begin_of_day: chrono::NaiveTime::from_hms_opt(5, 30, 0).unwrap(),
+            },
+            default_time_schedule: EventDayTimeSchedule {
+                sections: vec![
+                    EventDayScheduleSection {
+                        name: "Morgens".to_owned(),
+                        end_time: Some("12:00".parse().unwrap()),
+                    },
+                    EventDayScheduleSection {
+                        name: "Mittags".to_owned(),
+                        end_time: Some("18:00".parse().unwrap()),
+                    },
+                    EventDayScheduleSection {
+                        name: "Abends".to_owned(),
+                        end_time: None,
+                    },
+                ],
+            },
+            preceding_event_id: None,
+            subsequent_event_id: None,
+            entry_submission_mode: crate::data_store::models::EntrySubmissionMode::Disabled,
+        };
+        // 2025-03-29 14:00 CET
+        let now = chrono::Utc.with_ymd_and_hms(2025, 3, 29, 13, 0, 0).unwrap();
+        let prefill = |date: &str| {
+            let date = date.parse().unwrap();
+            let mut form_data = EntryFormData::for_new_entry(Uuid::now_v7(), date, Uuid::now_v7());
+            form_data.apply_default_time_schedule(date, &event, now);
+            (
+                form_data
+                    .begin
+                    .validate()
+                    .unwrap()
+                    .0
+                    .format("%H:%M")
+                    .to_string(),
+                form_data.duration.validate().unwrap(),
+            )
+        };
+
+        // Current day: section active at the current time
+        assert_eq!(
+            prefill("2025-03-29"),
+            (
+                "12:00".to_owned(),
+                validation::EntryDuration::Fixed(chrono::Duration::hours(6))
+            )
+        );
+        // Other days: first section of the day
+        assert_eq!(
+            prefill("2025-03-31"),
+            (
+                "05:30".to_owned(),
+                validation::EntryDuration::Fixed(chrono::Duration::minutes(6 * 60 + 30))
+            )
+        );
+        assert_eq!(prefill("2025-03-28").0, "05:30");
+    }
+
+    #[test]
+    fn test_validate_entry_timestamps_mode() {
+        use chrono::TimeZone;
+
+        let category = Uuid::now_v7();
+        let event = ExtendedEvent {
+            basic_data: crate::data_store::models::Event {
+                id: 1,
+                title: "TestEvent".to_owned(),
+                begin_date: "2025-03-28".parse().unwrap(),
+                end_date: "2025-04-02".parse().unwrap(),
+                slug: None,
+                begin_time: None,
+                end_time: None,
+                archived: false,
+            },
+            clock_info: EventClockInfo {
+                timezone: chrono_tz::Europe::Berlin,
+                effective_begin_of_day: chrono::NaiveTime::from_hms_opt(5, 30, 0).unwrap(),
+            },
+            default_time_schedule: crate::data_store::models::EventDayTimeSchedule {
+                sections: vec![],
+            },
+            preceding_event_id: None,
+            subsequent_event_id: None,
+            entry_submission_mode: crate::data_store::models::EntrySubmissionMode::Disabled,
+        };
+        let form_data = |begin: &str, end: &str| EntryFormData {
+            title: validation::BoundedString("Nachtwanderung".to_owned()).into(),
+            category: validation::UuidFromList(category).into(),
+            // The day, begin and duration fields must be ignored in timestamps mode
+            day: FormValue::empty(),
+            begin: FormValue::empty(),
+            end_mode: EndMode::Timestamps.into(),
+            begin_datetime: validation::DateTimeLocal(begin.parse().unwrap()).into(),
+            end_datetime: validation::DateTimeLocal(end.parse().unwrap()).into(),
+            ..EntryFormData::for_new_entry(Uuid::now_v7(), event.basic_data.begin_date, category)
+        };
+
+        // Conversion from local time (CET, UTC+1) to UTC, across the effective begin of day
+        let mut data = form_data("2025-03-29T23:00:00", "2025-03-30T01:30:00");
+        let (entry, _, _) = data
+            .validate(&vec![], &vec![category], None, None, &event)
+            .expect("form data should be valid");
+        assert_eq!(
+            entry.entry.begin,
+            chrono::Utc.with_ymd_and_hms(2025, 3, 29, 22, 0, 0).unwrap()
+        );
+        assert_eq!(
+            entry.entry.end,
+            chrono::Utc.with_ymd_and_hms(2025, 3, 30, 0, 30, 0).unwrap()
+        );
+
+        // After the switch to daylight saving time (CEST, UTC+2)
+        let mut data = form_data("2025-03-30T10:00:00", "2025-03-30T11:00:00");
+        let (entry, _, _) = data
+            .validate(&vec![], &vec![category], None, None, &event)
+            .expect("form data should be valid");
+        assert_eq!(
+            entry.entry.begin,
+            chrono::Utc.with_ymd_and_hms(2025, 3, 30, 8, 0, 0).unwrap()
+        );
+
+        // End before begin
+        let mut data = form_data("2025-03-30T10:00:00", "2025-03-30T09:00:00");
+        assert!(
+            data.validate(&vec![], &vec![category], None, None, &event)
+                .is_none()
+        );
+        assert!(data.end_datetime.has_errors());
+
+        // Non-existing local time (skipped by the switch to daylight saving time)
+        let mut data = form_data("2025-03-30T02:30:00", "2025-03-30T04:00:00");
+        assert!(
+            data.validate(&vec![], &vec![category], None, None, &event)
+                .is_none()
+        );
+        assert!(data.begin_datetime.has_errors());
+        assert!(!data.day.has_errors());
+    }
+}
@@ -2,6 +2,7 @@ use crate::data_store::auth_token::Privilege;
 use crate::data_store::models::{EntrySubmissionMode, Event, EventClockInfo, ExtendedEvent};
 use crate::data_store::{EventFilter, EventId, StoreError};
 use crate::web::AppState;
+use crate::web::time_calculation::event_boundaries;
 use crate::web::ui::base_template::{
     AnyEventData, BaseConfigTemplateContext, BaseTemplateContext, ConfigNavButton, MainNavButton,
 };
@@ -9,6 +10,7 @@ use crate::web::ui::error::AppError;
 use crate::web::ui::form_values::{
     _FormValidSimpleValidate, FormValue, FormValueRepresentation, ValidateFromFormInput,
 };
+use crate::web::ui::i18n::Messages;
 use crate::web::ui::sub_templates::form_inputs::{
     FormFieldTemplate, HiddenInputTemplate, InputType, SelectEntry, SelectTemplate,
 };
@@ -35,7 +37,7 @@ pub async fn edit_extended_event_form(
         auth.check_privilege(event_id, Privilege::ManageCategories)?;
         Ok((
             store.get_extended_event(&auth, event_id)?,
-            store.get_events(EventFilter::default())?,
+            store.get_events(EventFilter::builder().include_archived().build())?,
             auth,
         ))
     })
@@ -81,7 +83,7 @@ pub async fn edit_extended_event(
         auth.check_privilege(event_id, Privilege::EditEventDetails)?;
         Ok((
             store.get_extended_event(&auth, event_id)?,
-            store.get_events(EventFilter::default())?,
+            store.get_events(EventFilter::builder().include_archived().build())?,
             auth,
         ))
     })
@@ -130,7 +132,7 @@ pub async fn edit_extended_event(
     util::create_edit_form_response(
         result,
         &tmpl,
-        "Die Veranstaltungs-Metadaten",
+        Messages::for_request(&req).event_metadata_name,
         req.url_for("edit_extended_event_form", &[event_id.to_string()])?,
         "edit_extended_event_form",
         false,
@@ -172,6 +174,8 @@ struct ExtendedEventFormData {
     slug: FormValue<validation::MaybeEmpty<String>>,
     begin_date: FormValue<validation::IsoDate>,
     end_date: FormValue<validation::IsoDate>,
+    begin_time: FormValue<validation::MaybeEmpty<validation::TimeOfDay>>,
+    end_time: FormValue<validation::MaybeEmpty<validation::TimeOfDay>>,
     effective_begin_of_day: FormValue<validation::TimeOfDay>,
     timezone: FormValue<validation::Timezone>,
     default_time_schedule: FormValue<validation::EventDayTimeScheduleAsJson>,
@@ -186,6 +190,8 @@ impl ExtendedEventFormData {
         let slug = self.slug.validate();
         let begin_date = self.begin_date.validate();
         let end_date = self.end_date.validate();
+        let begin_time = self.begin_time.validate();
+        let end_time = self.end_time.validate();
         let effective_begin_of_day = self.effective_begin_of_day.validate();
         let timezone = self.timezone.validate();
         let default_time_schedule = self.default_time_schedule.validate();
@@ -201,13 +207,17 @@ impl ExtendedEventFormData {
             return None;
         }
 
-        Some(ExtendedEvent {
+        let event = ExtendedEvent {
             basic_data: Event {
                 id: event_id,
                 title: title?.into_inner(),
                 begin_date: begin_date?.into_inner(),
                 end_date: end_date?.into_inner(),
                 slug: slug?.0,
+                begin_time: begin_time?.0.map(|t| t.0),
+                end_time: end_time?.0.map(|t| t.0),
+                // Not updated by the data store
+                archived: false,
             },
             clock_info: EventClockInfo {
                 timezone: timezone?.into_inner(),
@@ -217,7 +227,19 @@ impl ExtendedEventFormData {
             preceding_event_id: preceding_event_id?.0.map(|v| v.into_inner()),
             subsequent_event_id: subsequent_event_id?.0.map(|v| v.into_inner()),
             entry_submission_mode: entry_submission_mode?.0,
-        })
+        };
+
+        // An inverted date range is reported by the database constraint, so we only check the
+        // time range here, if it's restricted by the begin or end time
+        let (event_begin, event_end) = event_boundaries(&event.basic_data, &event.clock_info);
+        let has_times =
+            event.basic_data.begin_time.is_some() || event.basic_data.end_time.is_some();
+        if has_times && event_end <= event_begin {
+            self.end_time
+                .add_error("Das Ende der Veranstaltung muss nach ihrem Beginn liegen.".to_owned());
+            return None;
+        }
+        Some(event)
     }
 }
 
@@ -228,6 +250,12 @@ impl From<ExtendedEvent> for ExtendedEventFormData {
             slug: validation::MaybeEmpty(value.basic_data.slug).into(),
             begin_date: validation::IsoDate(value.basic_data.begin_date).into(),
             end_date: validation::IsoDate(value.basic_data.end_date).into(),
+            begin_time: validation::MaybeEmpty(
+                value.basic_data.begin_time.map(validation::TimeOfDay),
+            )
+            .into(),
+            end_time: validation::MaybeEmpty(value.basic_data.end_time.map(validation::TimeOfDay))
+                .into(),
             effective_begin_of_day: validation::TimeOfDay(value.clock_info.effective_begin_of_day)
                 .into(),
             timezone: validation::Timezone(value.clock_info.timezone).into(),
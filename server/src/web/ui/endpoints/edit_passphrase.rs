@@ -7,6 +7,7 @@ use crate::web::ui::base_template::{
 };
 use crate::web::ui::error::AppError;
 use crate::web::ui::form_values::{_FormValidSimpleValidate, FormValue};
+use crate::web::ui::i18n::Messages;
 use crate::web::ui::sub_templates::form_inputs::{FormFieldTemplate, InputType};
 use crate::web::ui::util::{format_access_role, format_passphrase};
 use crate::web::ui::{util, validation};
@@ -135,7 +136,7 @@ pub async fn edit_passphrase(
     util::create_edit_form_response(
         result,
         &tmpl,
-        "Die Passphrase",
+        Messages::for_request(&req).passphrase_name,
         req.url_for(
             "edit_passphrase_form",
             &[event_id.to_string(), passphrase_id.to_string()],
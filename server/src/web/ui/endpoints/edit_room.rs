@@ -7,6 +7,7 @@ use crate::web::ui::base_template::{
 };
 use crate::web::ui::error::AppError;
 use crate::web::ui::form_values::{_FormValidSimpleValidate, FormValue};
+use crate::web::ui::i18n::Messages;
 use crate::web::ui::sub_templates::form_inputs::{
     FormFieldTemplate, HiddenInputTemplate, InputSize, InputType,
 };
@@ -99,12 +100,12 @@ pub async fn edit_room(
     let mut form_data = data.into_inner();
     let room = form_data.validate(Some(room_id));
 
-    let result: util::FormSubmitResult = if let Some(mut room) = room {
+    let result: util::FormSubmitResult = if let Some((mut room, previous_last_updated)) = room {
         room.event_id = event_id;
         let auth_clone = auth.clone();
         web::block(move || -> Result<_, StoreError> {
             let mut store = state.store.get_facade()?;
-            store.create_or_update_room(&auth_clone, room)?;
+            store.create_or_update_room(&auth_clone, room, previous_last_updated)?;
             Ok(())
         })
         .await?
@@ -135,7 +136,7 @@ pub async fn edit_room(
     util::create_edit_form_response(
         result,
         &tmpl,
-        "Der Ort",
+        Messages::for_request(&req).room_name,
         req.url_for(
             "edit_room_form",
             &[event_id.to_string(), room_id.to_string()],
@@ -212,12 +213,12 @@ pub async fn new_room(
     let mut form_data = data.into_inner();
     let room = form_data.validate(None);
 
-    let result: util::FormSubmitResult = if let Some(mut room) = room {
+    let result: util::FormSubmitResult = if let Some((mut room, previous_last_updated)) = room {
         room.event_id = event_id;
         let auth_clone = auth.clone();
         web::block(move || -> Result<_, StoreError> {
             let mut store = state.store.get_facade()?;
-            store.create_or_update_room(&auth_clone, room)?;
+            store.create_or_update_room(&auth_clone, room, previous_last_updated)?;
             Ok(())
         })
         .await?
@@ -248,7 +249,7 @@ pub async fn new_room(
     util::create_edit_form_response(
         result,
         &tmpl,
-        "Der Ort",
+        Messages::for_request(&req).room_name,
         req.url_for("new_room_form", &[event_id.to_string()])?,
         "edit_room_form",
         true,
@@ -264,6 +265,9 @@ struct RoomFormData {
     room_id: FormValue<Uuid>,
     title: FormValue<validation::NonEmptyString>,
     description: FormValue<String>,
+    /// `last_updated` value of the (original) room. Used for detecting editing conflicts. Only
+    /// used for editing existing rooms; can be empty/missing when creating new rooms.
+    last_updated: FormValue<validation::SimpleTimestampMicroseconds>,
 }
 
 impl RoomFormData {
@@ -274,17 +278,24 @@ impl RoomFormData {
         }
     }
 
-    fn validate(&mut self, known_id: Option<RoomId>) -> Option<NewRoom> {
+    fn validate(
+        &mut self,
+        known_id: Option<RoomId>,
+    ) -> Option<(NewRoom, Option<chrono::DateTime<chrono::Utc>>)> {
         let room_id = known_id.or_else(|| self.room_id.validate());
         let title = self.title.validate();
         let description = self.description.validate();
+        let previous_last_updated = self.last_updated.validate();
 
-        Some(NewRoom {
-            id: room_id?,
-            title: title?.into_inner(),
-            description: description?,
-            event_id: 0,
-        })
+        Some((
+            NewRoom {
+                id: room_id?,
+                title: title?.into_inner(),
+                description: description?,
+                event_id: 0,
+            },
+            previous_last_updated.map(|v| v.0),
+        ))
     }
 }
 
@@ -294,6 +305,7 @@ impl From<Room> for RoomFormData {
             room_id: value.id.into(),
             title: validation::NonEmptyString(value.title).into(),
             description: value.description.into(),
+            last_updated: validation::SimpleTimestampMicroseconds(value.last_updated).into(),
         }
     }
 }
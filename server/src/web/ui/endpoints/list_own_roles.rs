@@ -22,7 +22,7 @@ async fn list_own_roles(
     req: HttpRequest,
 ) -> Result<impl Responder, AppError> {
     let session_token = req.cookie(util::SESSION_COOKIE_NAME).map(|cookie| {
-        SessionToken::from_string(cookie.value(), &state.secret, util::SESSION_COOKIE_MAX_AGE)
+        SessionToken::from_string(cookie.value(), &state.secrets, util::SESSION_COOKIE_MAX_AGE)
     });
     let (session_token, session_error) = match session_token {
         None => (None, None),
@@ -37,7 +37,10 @@ async fn list_own_roles(
         } else {
             vec![]
         };
-        Ok((store.get_events(EventFilter::default())?, roles))
+        Ok((
+            store.get_events(EventFilter::builder().include_archived().build())?,
+            roles,
+        ))
     })
     .await??;
 
@@ -114,7 +117,7 @@ async fn logout_role(
     let session_token = req
         .cookie(util::SESSION_COOKIE_NAME)
         .and_then(|cookie| {
-            SessionToken::from_string(cookie.value(), &state.secret, util::SESSION_COOKIE_MAX_AGE)
+            SessionToken::from_string(cookie.value(), &state.secrets, util::SESSION_COOKIE_MAX_AGE)
                 .ok()
         })
         .unwrap_or(SessionToken::new());
@@ -132,7 +135,11 @@ async fn logout_role(
     };
 
     let mut response = HttpResponse::SeeOther();
-    response.cookie(create_session_cookie(session_token, &state.secret));
+    response.cookie(create_session_cookie(
+        session_token,
+        state.signing_secret(),
+        &state.session_cookie_config,
+    ));
     req.add_flash_message(FlashMessage {
         flash_type: FlashType::Success,
         message: "Logout erfolgreich".to_owned(),
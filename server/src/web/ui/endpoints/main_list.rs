@@ -5,7 +5,8 @@ use crate::data_store::models::{
 use crate::data_store::{AnnouncementFilter, EntryFilter};
 use crate::web::AppState;
 use crate::web::time_calculation::{
-    current_effective_date, timestamp_from_effective_date_and_time,
+    current_effective_date, effective_day_boundaries, schedule_section_for_timestamp,
+    timestamp_from_effective_date_and_time,
 };
 use crate::web::ui::base_template::{AnyEventData, BaseTemplateContext, MainNavButton};
 use crate::web::ui::error::AppError;
@@ -26,6 +27,8 @@ use std::collections::BTreeMap;
 #[derive(Deserialize, Serialize)]
 pub struct MainListQueryData {
     pub after: Option<chrono::NaiveTime>,
+    /// If false, entries which are only room reservations are omitted
+    pub include_reservations: Option<bool>,
 }
 
 #[get("/{event_id}/list/{date}")]
@@ -37,9 +40,10 @@ async fn main_list(
 ) -> Result<impl Responder, AppError> {
     let (event_id, date) = path.into_inner();
     let time_after = query_data.after;
+    let include_reservations = query_data.include_reservations.unwrap_or(true);
     let session_token =
         util::extract_session_token(&state, &req, Privilege::ShowKueaPlan, event_id)?;
-    let (entries, rooms, categories, announcements, preceding_event, subsequent_event, event, auth) =
+    let (entries, rooms, categories, announcements, neighbors, event, auth) =
         web::block(move || -> Result<_, AppError> {
             let mut store = state.store.get_facade()?;
             let auth = store.get_auth_token_for_session(&session_token, event_id)?;
@@ -48,23 +52,16 @@ async fn main_list(
                 store.get_published_entries_filtered(
                     &auth,
                     event_id,
-                    date_to_filter(date, time_after, &event.clock_info),
+                    date_to_filter(date, time_after, include_reservations, &event.clock_info),
                 )?,
                 store.get_rooms(&auth, event_id)?,
                 store.get_categories(&auth, event_id)?,
                 store.get_announcements(
                     &auth,
                     event_id,
-                    Some(AnnouncementFilter::ForDate(date)),
+                    Some(announcement_filter(date, &event.clock_info)),
                 )?,
-                event
-                    .preceding_event_id
-                    .map(|id| store.get_event(id))
-                    .transpose()?,
-                event
-                    .subsequent_event_id
-                    .map(|id| store.get_event(id))
-                    .transpose()?,
+                store.get_neighbor_events(&auth, event_id)?,
                 event,
                 auth,
             ))
@@ -89,7 +86,7 @@ async fn main_list(
             .filter(|row| {
                 row.includes_entry
                     && !row.entry.entry.is_cancelled
-                    && !row.entry.entry.description.is_empty()
+                    && row.entry.entry.has_description_or_links()
             })
             .map(|row| row.entry)
             .collect(),
@@ -97,6 +94,7 @@ async fn main_list(
         categories: categories.iter().map(|r| (r.id, r)).collect(),
         date,
         time_after,
+        include_reservations,
         footer_constrained_link_times: event
             .default_time_schedule
             .sections
@@ -104,8 +102,8 @@ async fn main_list(
             .filter_map(|b| b.end_time)
             .filter(|t| *t > event.clock_info.effective_begin_of_day)
             .collect(),
-        preceding_event: preceding_event.as_ref(),
-        subsequent_event: subsequent_event.as_ref(),
+        preceding_event: neighbors.preceding_event.as_ref(),
+        subsequent_event: neighbors.subsequent_event.as_ref(),
         announcements: &announcements,
         event: &event,
     };
@@ -122,6 +120,7 @@ struct MainListTemplate<'a> {
     categories: BTreeMap<uuid::Uuid, &'a Category>,
     date: chrono::NaiveDate,
     time_after: Option<chrono::NaiveTime>,
+    include_reservations: bool,
     footer_constrained_link_times: Vec<chrono::NaiveTime>,
     preceding_event: Option<&'a Event>,
     subsequent_event: Option<&'a Event>,
@@ -139,6 +138,19 @@ impl<'a> MainListTemplate<'a> {
     fn link_to_time_constrained_list(
         &self,
         after_time: &chrono::NaiveTime,
+    ) -> Result<url::Url, AppError> {
+        self.link_to_list_with_query(Some(*after_time), self.include_reservations)
+    }
+
+    /// Link to the same list, but with room reservations shown or hidden, respectively
+    fn link_to_toggle_reservations(&self) -> Result<url::Url, AppError> {
+        self.link_to_list_with_query(self.time_after, !self.include_reservations)
+    }
+
+    fn link_to_list_with_query(
+        &self,
+        after: Option<chrono::NaiveTime>,
+        include_reservations: bool,
     ) -> Result<url::Url, AppError> {
         let mut result = self.base.request.url_for(
             "main_list",
@@ -151,9 +163,13 @@ impl<'a> MainListTemplate<'a> {
                 self.date.to_string(),
             ],
         )?;
-        result.set_query(Some(&serde_urlencoded::to_string(MainListQueryData {
-            after: Some(*after_time),
-        })?));
+        let query = serde_urlencoded::to_string(MainListQueryData {
+            after,
+            // Including reservations is the default, so we only add the parameter for excluding
+            // them
+            include_reservations: (!include_reservations).then_some(false),
+        })?;
+        result.set_query((!query.is_empty()).then_some(&query));
         Ok(result)
     }
 
@@ -184,7 +200,7 @@ impl<'a> MainListTemplate<'a> {
 
 /// Filters for the askama template
 mod filters {
-    use crate::web::ui::util;
+    use crate::web::util;
 
     #[askama::filter_fn]
     pub fn weekday(
@@ -195,25 +211,29 @@ mod filters {
     }
 }
 
+/// Generate the AnnouncementFilter for the main list of the given day. For the current day, the
+/// current time is taken into account, such that announcements with a time window are only shown
+/// within that time window.
+fn announcement_filter(date: chrono::NaiveDate, clock_info: &EventClockInfo) -> AnnouncementFilter {
+    if date == current_effective_date(clock_info) {
+        AnnouncementFilter::ForDateTime(chrono::Utc::now())
+    } else {
+        AnnouncementFilter::ForDate(date)
+    }
+}
+
 /// Generate an EntryFilter for retrieving only the entries on the given day (using the
 /// EFFECTIVE_BEGIN_OF_DAY)
 fn date_to_filter(
     date: chrono::NaiveDate,
     begin_time: Option<chrono::NaiveTime>,
+    include_reservations: bool,
     clock_info: &EventClockInfo,
 ) -> EntryFilter {
-    let end = date.and_time(clock_info.effective_begin_of_day) + chrono::Duration::days(1);
+    let (day_begin, day_end) = effective_day_boundaries(date, clock_info);
     let mut builder = EntryFilter::builder()
         .include_previous_date_matches()
-        .before(
-            clock_info
-                .timezone
-                .from_local_datetime(&end)
-                .latest()
-                .map(|dt| dt.to_utc())
-                .unwrap_or(end.and_utc()),
-            false,
-        );
+        .before(day_end, false);
     if let Some(begin_time) = begin_time {
         // When filtering the main list by a start time, the intuitively expected behaviour is to
         // *not* include entries that end exactly at that time, even for entries with 0:00h
@@ -227,14 +247,10 @@ fn date_to_filter(
         // exactly at the EFFECTIVE_BEGIN_OF_DAY. Otherwise, we would exclude entries of 0:00h
         // duration which start (and end) at the EFFECTIVE_BEGIN_OF_DAY from all days main_lists,
         // such that they are not accessible in the plan anymore.
-        builder = builder.after(
-            timestamp_from_effective_date_and_time(
-                date,
-                clock_info.effective_begin_of_day,
-                clock_info,
-            ),
-            true,
-        );
+        builder = builder.after(day_begin, true);
+    }
+    if !include_reservations {
+        builder = builder.exclude_reservations();
     }
     builder.build()
 }
@@ -311,36 +327,34 @@ fn group_rows_into_blocks<'a, 'e>(
     date: chrono::NaiveDate,
     event: &'e ExtendedEvent,
 ) -> Vec<(&'e str, Vec<&'a MainListRow<'a>>)> {
-    if event.default_time_schedule.sections.is_empty() {
+    let sections = &event.default_time_schedule.sections;
+    if sections.is_empty() {
         return vec![("Einträge", entries.iter().collect())];
     }
-    let mut result = Vec::new();
-    let mut block_entries = Vec::new();
-    let mut time_block_iter = event.default_time_schedule.sections.iter();
-    let mut section = time_block_iter
-        .next()
-        .expect("If no time schedule section is defined, we should exit early above.");
+    let mut result: Vec<(&'e str, Vec<&'a MainListRow<'a>>)> = Vec::new();
+    let mut current_section = None;
     for entry in entries {
-        while section.end_time.is_some_and(|block_begin_time| {
-            timestamp_from_effective_date_and_time(date, block_begin_time, &event.clock_info)
-                <= *entry.sort_time
-        }) {
-            if !block_entries.is_empty() {
-                result.push((section.name.as_str(), block_entries));
+        // Falling back to the last section should not happen, when sections are correctly filled
+        // (i.e. last section has end_time 'None')
+        let section = schedule_section_for_timestamp(
+            entry.sort_time,
+            date,
+            &event.default_time_schedule,
+            &event.clock_info,
+        )
+        .or(sections.last())
+        .expect("If no time schedule section is defined, we should exit early above.");
+        match result.last_mut() {
+            Some((_, block_entries))
+                if current_section.is_some_and(|s| std::ptr::eq(s, section)) =>
+            {
+                block_entries.push(entry)
             }
-            block_entries = Vec::new();
-            let next_section = time_block_iter.next();
-            if next_section.is_none() {
-                // Should not happen, when sections are correctly filled (i.e. last section has
-                // end_time 'None')
-                break;
+            _ => {
+                result.push((section.name.as_str(), vec![entry]));
+                current_section = Some(section);
             }
-            section = next_section.unwrap();
         }
-        block_entries.push(entry);
-    }
-    if !block_entries.is_empty() {
-        result.push((section.name.as_str(), block_entries));
     }
     result
 }
@@ -348,7 +362,6 @@ fn group_rows_into_blocks<'a, 'e>(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::data_store::models::{Entry, EntryState, FullPreviousDate, PreviousDate};
     use uuid::uuid;
 
     const DEFAULT_CLOCK_INFO: EventClockInfo = EventClockInfo {
@@ -362,127 +375,51 @@ mod tests {
         let room_2 = uuid!("a3820b53-e9a9-4840-b071-7fa3ba34010a");
         let room_3 = uuid!("f6ad3e0b-4371-4a84-a485-45da7f1d8cb8");
         let entries = vec![
-            FullEntry {
-                entry: Entry {
-                    id: uuid!("05c93b6e-29ad-4ace-8a32-244723973331"),
-                    title: "A".to_string(),
-                    description: "".to_string(),
-                    responsible_person: "".to_string(),
-                    is_room_reservation: false,
-                    event_id: 1,
-                    begin: "2025-04-28 14:00:00+00:00".parse().unwrap(),
-                    end: "2025-04-28 16:00:00+00:00".parse().unwrap(),
-                    category: Default::default(),
-                    last_updated: Default::default(),
-                    comment: "".to_string(),
-                    time_comment: "".to_string(),
-                    room_comment: "".to_string(),
-                    is_exclusive: false,
-                    is_cancelled: false,
-                    state: EntryState::Published,
-                },
-                room_ids: vec![room_1],
-                previous_dates: vec![
-                    FullPreviousDate {
-                        previous_date: PreviousDate {
-                            id: uuid!("6385b911-c641-47c8-8d50-26d2fe1ee764"),
-                            entry_id: uuid!("05c93b6e-29ad-4ace-8a32-244723973331"),
-                            comment: "Wegen Kollision mit Anreise auf Nachmittag verschoben"
-                                .to_string(),
-                            begin: "2025-04-28 9:00:00+00:00".parse().unwrap(),
-                            end: "2025-04-28 10:00:00+00:00".parse().unwrap(),
-                        },
-                        room_ids: vec![room_2],
-                    },
-                    FullPreviousDate {
-                        previous_date: PreviousDate {
-                            id: uuid!("38023800-c9be-45a8-8d08-2f118ea6b15c"),
-                            entry_id: uuid!("05c93b6e-29ad-4ace-8a32-244723973331"),
-                            comment: "Klavier steht jetzt in Raum 1".to_string(),
-                            begin: "2025-04-28 14:00:00+00:00".parse().unwrap(),
-                            end: "2025-04-28 16:00:00+00:00".parse().unwrap(),
-                        },
-                        room_ids: vec![room_2],
-                    },
-                ],
-                orga_internal: None,
-            },
-            FullEntry {
-                entry: Entry {
-                    id: uuid!("01968846-8729-7e19-ae21-6d28e8abde31"),
-                    title: "B".to_string(),
-                    description: "".to_string(),
-                    responsible_person: "".to_string(),
-                    is_room_reservation: false,
-                    event_id: 1,
-                    begin: "2025-04-28 12:00:00+00:00".parse().unwrap(),
-                    end: "2025-04-28 13:30:00+00:00".parse().unwrap(),
-                    category: Default::default(),
-                    last_updated: Default::default(),
-                    comment: "".to_string(),
-                    time_comment: "".to_string(),
-                    room_comment: "".to_string(),
-                    is_exclusive: false,
-                    is_cancelled: false,
-                    state: EntryState::Published,
-                },
-                room_ids: vec![room_3],
-                previous_dates: vec![
-                    FullPreviousDate {
-                        previous_date: PreviousDate {
-                            id: uuid!("9eb8121a-9e98-4a54-94da-ed32032a4a91"),
-                            entry_id: uuid!("01968846-8729-7e19-ae21-6d28e8abde31"),
-                            comment: "Jetzt doch etwas später".to_string(),
-                            begin: "2025-04-28 11:30:00+00:00".parse().unwrap(),
-                            end: "2025-04-28 13:00:00+00:00".parse().unwrap(),
-                        },
-                        room_ids: vec![room_3],
-                    },
-                    FullPreviousDate {
-                        previous_date: PreviousDate {
-                            id: uuid!("9eb8121a-9e98-4a54-94da-ed32032a4a91"),
-                            entry_id: uuid!("01968846-8729-7e19-ae21-6d28e8abde31"),
-                            comment: "".to_string(),
-                            begin: "2025-04-27 12:00:00+00:00".parse().unwrap(),
-                            end: "2025-04-27 13:30:00+00:00".parse().unwrap(),
-                        },
-                        room_ids: vec![room_3],
-                    },
-                ],
-                orga_internal: None,
-            },
-            FullEntry {
-                entry: Entry {
-                    id: uuid!("8e17d6dc-1b10-4685-8689-dd998deb17c6"),
-                    title: "C".to_string(),
-                    description: "".to_string(),
-                    responsible_person: "".to_string(),
-                    is_room_reservation: false,
-                    event_id: 1,
-                    begin: "2025-04-27 15:00:00+00:00".parse().unwrap(),
-                    end: "2025-04-27 15:30:00+00:00".parse().unwrap(),
-                    category: Default::default(),
-                    last_updated: Default::default(),
-                    comment: "".to_string(),
-                    time_comment: "".to_string(),
-                    room_comment: "".to_string(),
-                    is_exclusive: false,
-                    is_cancelled: false,
-                    state: EntryState::Published,
-                },
-                room_ids: vec![room_1],
-                previous_dates: vec![FullPreviousDate {
-                    previous_date: PreviousDate {
-                        id: uuid!("9eb8121a-9e98-4a54-94da-ed32032a4a91"),
-                        entry_id: uuid!("8e17d6dc-1b10-4685-8689-dd998deb17c6"),
-                        comment: "".to_string(),
-                        begin: "2025-04-28 11:00:00+00:00".parse().unwrap(),
-                        end: "2025-04-28 11:30:00+00:00".parse().unwrap(),
-                    },
-                    room_ids: vec![room_1],
-                }],
-                orga_internal: None,
-            },
+            FullEntry::test_builder("2025-04-28 14:00:00+00:00", "2025-04-28 16:00:00+00:00")
+                .id(uuid!("05c93b6e-29ad-4ace-8a32-244723973331"))
+                .title("A")
+                .rooms(vec![room_1])
+                .previous_date(
+                    uuid!("6385b911-c641-47c8-8d50-26d2fe1ee764"),
+                    "2025-04-28 9:00:00+00:00",
+                    "2025-04-28 10:00:00+00:00",
+                    vec![room_2],
+                )
+                .previous_date(
+                    uuid!("38023800-c9be-45a8-8d08-2f118ea6b15c"),
+                    "2025-04-28 14:00:00+00:00",
+                    "2025-04-28 16:00:00+00:00",
+                    vec![room_2],
+                )
+                .build(),
+            FullEntry::test_builder("2025-04-28 12:00:00+00:00", "2025-04-28 13:30:00+00:00")
+                .id(uuid!("01968846-8729-7e19-ae21-6d28e8abde31"))
+                .title("B")
+                .rooms(vec![room_3])
+                .previous_date(
+                    uuid!("9eb8121a-9e98-4a54-94da-ed32032a4a91"),
+                    "2025-04-28 11:30:00+00:00",
+                    "2025-04-28 13:00:00+00:00",
+                    vec![room_3],
+                )
+                .previous_date(
+                    uuid!("9eb8121a-9e98-4a54-94da-ed32032a4a91"),
+                    "2025-04-27 12:00:00+00:00",
+                    "2025-04-27 13:30:00+00:00",
+                    vec![room_3],
+                )
+                .build(),
+            FullEntry::test_builder("2025-04-27 15:00:00+00:00", "2025-04-27 15:30:00+00:00")
+                .id(uuid!("8e17d6dc-1b10-4685-8689-dd998deb17c6"))
+                .title("C")
+                .rooms(vec![room_1])
+                .previous_date(
+                    uuid!("9eb8121a-9e98-4a54-94da-ed32032a4a91"),
+                    "2025-04-28 11:00:00+00:00",
+                    "2025-04-28 11:30:00+00:00",
+                    vec![room_1],
+                )
+                .build(),
         ];
         let result = generate_filtered_merged_list_entries(
             &entries,
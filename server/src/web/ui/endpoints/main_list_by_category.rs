@@ -72,7 +72,7 @@ async fn main_list_by_category(
             .filter(|row| {
                 row.includes_entry
                     && !row.entry.entry.is_cancelled
-                    && !row.entry.entry.description.is_empty()
+                    && row.entry.entry.has_description_or_links()
             })
             .map(|row| row.entry)
             .collect(),
@@ -106,7 +106,7 @@ impl MainListByCategoryTemplate<'_> {
 
 /// Filters for the rinja template
 mod filters {
-    use crate::web::ui::util;
+    use crate::web::util;
 
     #[askama::filter_fn]
     pub fn weekday(
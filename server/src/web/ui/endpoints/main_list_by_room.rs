@@ -76,7 +76,7 @@ async fn main_list_by_room(
             .filter(|row| {
                 row.includes_entry
                     && !row.entry.entry.is_cancelled
-                    && !row.entry.entry.description.is_empty()
+                    && row.entry.entry.has_description_or_links()
             })
             .map(|row| row.entry)
             .collect(),
@@ -113,7 +113,7 @@ impl MainListByRoomTemplate<'_> {
 /// Filters for the rinja template
 mod filters {
     pub use crate::web::ui::askama_filters::markdown;
-    use crate::web::ui::util;
+    use crate::web::util;
 
     #[askama::filter_fn]
     pub fn weekday(
@@ -1,6 +1,6 @@
-use crate::data_store::EventId;
 use crate::data_store::auth_token::Privilege;
 use crate::data_store::models::{Category, FullAnnouncement, Room};
+use crate::data_store::{AnnouncementId, EventId};
 use crate::web::AppState;
 use crate::web::ui::base_template::{
     AnyEventData, BaseConfigTemplateContext, BaseTemplateContext, ConfigNavButton, MainNavButton,
@@ -70,6 +70,24 @@ struct ManageAnnouncementsTemplate<'a> {
     categories: BTreeMap<uuid::Uuid, &'a Category>,
 }
 
+impl ManageAnnouncementsTemplate<'_> {
+    fn clone_announcement_form_url(
+        &self,
+        announcement_id: &AnnouncementId,
+    ) -> Result<String, AppError> {
+        let mut url = self
+            .base
+            .request
+            .url_for("new_announcement_form", &[self.event_id.to_string()])?;
+        url.set_query(Some(&serde_urlencoded::to_string(
+            super::edit_announcement::NewAnnouncementQueryParams {
+                clone_from: Some(*announcement_id),
+            },
+        )?));
+        Ok(url.to_string())
+    }
+}
+
 /// Filters for the askama template
 mod filters {
     pub use crate::web::ui::askama_filters::markdown;
@@ -1,3 +1,4 @@
+use super::super::colors;
 use super::super::colors::CategoryColors;
 use crate::data_store::EventId;
 use crate::data_store::auth_token::Privilege;
@@ -29,6 +29,7 @@ pub mod new_passphrase;
 pub mod new_previous_date;
 pub mod participant_submit_entry;
 pub mod previous_dates;
+pub mod print_day;
 pub mod print_templates;
 pub mod review;
 pub mod rooms_list;
@@ -9,6 +9,7 @@ use crate::web::ui::error::AppError;
 use crate::web::ui::form_values::{
     _FormValidSimpleValidate, FormValue, FormValueRepresentation, ValidateFromFormInput,
 };
+use crate::web::ui::i18n::Messages;
 use crate::web::ui::sub_templates::form_inputs::{
     FormFieldTemplate, InputType, SelectEntry, SelectTemplate,
 };
@@ -113,7 +114,7 @@ pub async fn new_passphrase(
     util::create_edit_form_response(
         result,
         &tmpl,
-        "Die Passphrase",
+        Messages::for_request(&req).passphrase_name,
         req.url_for("new_passphrase_form", &[event_id.to_string()])?,
         "new_passphrase_form",
         true,
@@ -246,7 +247,7 @@ pub async fn new_derivable_sharable_link_passphrase(
     util::create_edit_form_response(
         result,
         &tmpl,
-        "Die ableitbare Rolle",
+        Messages::for_request(&req).derivable_role_name,
         req.url_for(
             "new_derivable_sharable_link_passphrase_form",
             &[event_id.to_string(), parent_passphrase_id.to_string()],
@@ -8,6 +8,7 @@ use crate::web::time_calculation::{get_effective_date, timestamp_from_effective_
 use crate::web::ui::base_template::{AnyEventData, BaseTemplateContext, MainNavButton};
 use crate::web::ui::error::AppError;
 use crate::web::ui::form_values::{_FormValidSimpleValidate, FormValue};
+use crate::web::ui::i18n::Messages;
 use crate::web::ui::sub_templates::edit_entry_helpers::{
     EditEntryNavbar, EditEntryNavbarActiveLink,
 };
@@ -17,8 +18,9 @@ use crate::web::ui::sub_templates::form_inputs::{
 use crate::web::ui::sub_templates::main_list_row::{
     MainListRow, MainListRowTemplate, RoomByIdWithOrder, styles_for_category,
 };
-use crate::web::ui::util::{event_days, weekday_short};
+use crate::web::ui::util::event_days;
 use crate::web::ui::{util, validation};
+use crate::web::util::weekday_short;
 use actix_web::web::{Form, Html};
 use actix_web::{HttpRequest, Responder, get, post, web};
 use askama::Template;
@@ -151,7 +153,7 @@ pub async fn new_previous_date(
     util::create_edit_form_response(
         result,
         &tmpl,
-        "Der Vorherige Termin",
+        Messages::for_request(&req).previous_date_name,
         req.url_for(
             "new_previous_date_form",
             &[event_id.to_string(), entry_id.to_string()],
@@ -224,6 +226,7 @@ impl PreviousDateFormData {
                 comment: comment?,
                 begin,
                 end: begin + duration?.into_inner(),
+                last_updated: chrono::Utc::now(),
             },
             room_ids: room_ids?.into_inner(),
         })
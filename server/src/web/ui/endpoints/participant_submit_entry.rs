@@ -1,6 +1,7 @@
 use crate::data_store::auth_token::Privilege;
 use crate::data_store::models::{
-    Category, EntryState, EventClockInfo, ExtendedEvent, FullNewEntry, NewEntry, Room,
+    Category, EntryAuditAction, EntryState, EventClockInfo, ExtendedEvent, FullNewEntry, NewEntry,
+    Room,
 };
 use crate::data_store::{DataPolicy, EntryId, EventId, StoreError};
 use crate::web::time_calculation::{
@@ -10,14 +11,15 @@ use crate::web::ui::base_template::{AnyEventData, BaseTemplateContext, MainNavBu
 use crate::web::ui::error::AppError;
 use crate::web::ui::flash::{FlashMessage, FlashType, FlashesInterface};
 use crate::web::ui::form_values::{_FormValidSimpleValidate, BoolFormValue, FormValue};
+use crate::web::ui::i18n::Messages;
 use crate::web::ui::sub_templates::form_inputs::{
     CheckboxTemplate, FormFieldTemplate, HiddenInputTemplate, InputSize, InputType, SelectEntry,
     SelectTemplate,
 };
 use crate::web::ui::sub_templates::main_list_row::styles_for_category;
-use crate::web::ui::util::{FormSubmitResult, event_days, weekday_short};
+use crate::web::ui::util::{FormSubmitResult, event_days};
 use crate::web::ui::{util, validation};
-use crate::web::util::format_submitter_comment;
+use crate::web::util::{MAX_ENTRY_TITLE_LENGTH, format_submitter_comment, weekday_short};
 use crate::web::{AppState, time_calculation};
 use actix_web::web::{Form, Html, Query, Redirect};
 use actix_web::{Either, HttpRequest, HttpResponse, Responder, get, post, web};
@@ -136,9 +138,16 @@ async fn participant_submit_entry(
         entry_begin = entry.entry.begin;
         entry_id = entry.entry.id;
         immediately_published = entry.entry.state.is_published();
+        let public = entry.entry.is_public();
         web::block(move || -> Result<_, StoreError> {
             let mut store = state.store.get_facade()?;
             store.submit_entry_by_participant(&auth_clone, entry)?;
+            state.live_updates.publish_entry_change(
+                event_id,
+                entry_id,
+                EntryAuditAction::Create,
+                public,
+            );
             Ok(())
         })
         .await?
@@ -294,7 +303,7 @@ impl<'a> ParticipantSubmitEntryFormTemplate<'a> {
 #[derive(Default, Deserialize, Debug)]
 struct SubmitEntryFormData {
     entry_id: FormValue<Uuid>,
-    title: FormValue<validation::NonEmptyString>,
+    title: FormValue<validation::BoundedString<MAX_ENTRY_TITLE_LENGTH>>,
     comment: FormValue<String>,
     room_comment: FormValue<String>,
     time_comment: FormValue<String>,
@@ -370,12 +379,17 @@ impl SubmitEntryFormData {
                 room_comment: room_comment?,
                 is_exclusive: false,
                 is_cancelled: false,
+                is_tentative: false,
+                orga_only: false,
+                open_ended: false,
                 state: if publish_before_review {
                     EntryState::PreliminaryPublished
                 } else {
                     EntryState::SubmittedForReview
                 },
                 orga_comment: format_submitter_comment(&submitter_comment?),
+                color_override: None,
+                links: None,
             },
             room_ids: room_ids?.into_inner(),
             previous_dates: vec![],
@@ -456,7 +470,7 @@ pub fn create_submit_entry_form_response(
         _ => util::create_edit_form_response(
             result,
             form_template,
-            "Der Eintrag",
+            Messages::for_request(request).entry_name,
             form_url,
             form_name,
             true,
@@ -1,6 +1,6 @@
-use crate::data_store::EntryId;
 use crate::data_store::auth_token::Privilege;
-use crate::data_store::models::{Category, ExtendedEvent, FullEntry};
+use crate::data_store::models::{Category, ExtendedEvent, FullEntry, FullPreviousDate};
+use crate::data_store::{EntryId, StoreError};
 use crate::web::AppState;
 use crate::web::time_calculation::get_effective_date;
 use crate::web::ui::base_template::{AnyEventData, BaseTemplateContext};
@@ -27,19 +27,21 @@ async fn previous_dates_overview(
     let session_token =
         util::extract_session_token(&state, &req, Privilege::ManageEntries, event_id)?;
     let store = state.store.clone();
-    let (entry, event, rooms, categories, auth) = web::block(move || -> Result<_, AppError> {
-        let mut store = store.get_facade()?;
-        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
-        auth.check_privilege(event_id, Privilege::ManageEntries)?;
-        Ok((
-            store.get_entry(&auth, entry_id)?,
-            store.get_extended_event(&auth, event_id)?,
-            store.get_rooms(&auth, event_id)?,
-            store.get_categories(&auth, event_id)?, // TODO only get relevant category?
-            auth,
-        ))
-    })
-    .await??;
+    let (entry, deleted_previous_dates, event, rooms, categories, auth) =
+        web::block(move || -> Result<_, AppError> {
+            let mut store = store.get_facade()?;
+            let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+            auth.check_privilege(event_id, Privilege::ManageEntries)?;
+            Ok((
+                store.get_entry(&auth, entry_id)?,
+                store.get_deleted_previous_dates(&auth, entry_id)?,
+                store.get_extended_event(&auth, event_id)?,
+                store.get_rooms(&auth, event_id)?,
+                store.get_categories(&auth, event_id)?, // TODO only get relevant category?
+                auth,
+            ))
+        })
+        .await??;
 
     let tmpl = PreviousDatesOverviewTemplate {
         base: BaseTemplateContext {
@@ -52,6 +54,7 @@ async fn previous_dates_overview(
         },
         event: &event,
         entry: &entry,
+        deleted_previous_dates: &deleted_previous_dates,
         rooms: rooms.iter().collect(),
         entry_category: categories
             .iter()
@@ -118,12 +121,63 @@ async fn delete_previous_date(
     .see_other())
 }
 
+#[post("/{event_id}/entry/{entry_id}/previous_dates/{previous_date_id}/restore")]
+async fn restore_previous_date(
+    path: web::Path<(i32, EntryId, uuid::Uuid)>,
+    state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let (event_id, entry_id, previous_date_id) = path.into_inner();
+    let session_token =
+        util::extract_session_token(&state, &req, Privilege::ManageEntries, event_id)?;
+
+    let result = web::block(move || -> Result<_, AppError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        match store.restore_previous_date(&auth, entry_id, previous_date_id) {
+            // The previous date has already been restored, e.g. by submitting the form twice
+            Err(StoreError::ConflictEntityExists) => Ok(()),
+            r => Ok(r?),
+        }
+    })
+    .await?;
+
+    let notification = match result {
+        Ok(_) => FlashMessage {
+            flash_type: FlashType::Success,
+            message: "Der vorherige Termin wurde wiederhergestellt.".to_string(),
+            keep_open: false,
+            button: None,
+        },
+        Err(e) => match e {
+            AppError::TransactionConflict => FlashMessage {
+                flash_type: FlashType::Error,
+                message: "Der vorherige Termin konnte wegen eines parallelen Datenbank-Zugriff nicht wiederhergestellt werden. Bitte erneut versuchen.".to_string(),
+                keep_open: true,
+                button: None,
+            },
+            _ => return Err(e),
+        },
+    };
+    req.add_flash_message(notification);
+
+    Ok(Redirect::to(
+        req.url_for(
+            "previous_dates_overview",
+            &[event_id.to_string(), entry_id.to_string()],
+        )?
+        .to_string(),
+    )
+    .see_other())
+}
+
 #[derive(Template)]
 #[template(path = "previous_dates_overview.html")]
 struct PreviousDatesOverviewTemplate<'a> {
     base: BaseTemplateContext<'a>,
     event: &'a ExtendedEvent,
     entry: &'a FullEntry,
+    deleted_previous_dates: &'a [FullPreviousDate],
     rooms: RoomByIdWithOrder<'a>,
     entry_category: &'a Category,
 }
@@ -0,0 +1,116 @@
+use crate::data_store::EntryFilter;
+use crate::data_store::auth_token::Privilege;
+use crate::data_store::models::{ExtendedEvent, FullEntry};
+use crate::web::AppState;
+use crate::web::time_calculation::{
+    effective_day_boundaries, event_boundaries, event_day_boundaries,
+};
+use crate::web::ui::error::AppError;
+use crate::web::ui::sub_templates::print_day_content::PrintDayContentTemplate;
+use crate::web::ui::util;
+use actix_web::web::Html;
+use actix_web::{HttpRequest, Responder, get, web};
+use askama::Template;
+
+#[get("/{event_id}/{date}/print")]
+async fn print_day(
+    path: web::Path<(i32, chrono::NaiveDate)>,
+    state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let (event_id, date) = path.into_inner();
+    let session_token =
+        util::extract_session_token(&state, &req, Privilege::ShowKueaPlan, event_id)?;
+    let (mut entries, rooms, event) = web::block(move || -> Result<_, AppError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        let event = store.get_extended_event(&auth, event_id)?;
+        let (day_begin, day_end) = effective_day_boundaries(date, &event.clock_info);
+        // Like in the main list, entries ending exactly at the EFFECTIVE_BEGIN_OF_DAY are
+        // included, such that entries of 0:00h duration at that time are not lost.
+        let filter = EntryFilter::builder()
+            .after(day_begin, true)
+            .before(day_end, false)
+            .build();
+        Ok((
+            store.get_published_entries_filtered(&auth, event_id, filter)?,
+            store.get_rooms(&auth, event_id)?,
+            event,
+        ))
+    })
+    .await??;
+
+    entries.sort_by_key(|e| e.entry.begin);
+    let entries: Vec<&FullEntry> = entries.iter().collect();
+    let tmpl = PrintDayTemplate {
+        content: PrintDayContentTemplate::new(&event, date, &entries, &rooms),
+        event: &event,
+        date,
+    };
+    Ok(Html::new(tmpl.render()?))
+}
+
+#[get("/{event_id}/print-all")]
+async fn print_all_days(
+    path: web::Path<i32>,
+    state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let event_id = path.into_inner();
+    let session_token =
+        util::extract_session_token(&state, &req, Privilege::ShowKueaPlan, event_id)?;
+    let (mut entries, rooms, event) = web::block(move || -> Result<_, AppError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        let event = store.get_extended_event(&auth, event_id)?;
+        let (event_begin, event_end) = event_boundaries(&event.basic_data, &event.clock_info);
+        let filter = EntryFilter::builder()
+            .after(event_begin, true)
+            .before(event_end, false)
+            .build();
+        Ok((
+            store.get_published_entries_filtered(&auth, event_id, filter)?,
+            store.get_rooms(&auth, event_id)?,
+            event,
+        ))
+    })
+    .await??;
+
+    entries.sort_by_key(|e| e.entry.begin);
+    let days = event_day_boundaries(&event.basic_data, &event.clock_info);
+    let day_entries: Vec<Vec<&FullEntry>> = days
+        .iter()
+        .map(|day| {
+            // Same as the filter of the single day print view: Entries ending exactly at the
+            // begin of the day are included.
+            entries
+                .iter()
+                .filter(|e| e.entry.end >= day.begin && e.entry.begin < day.end)
+                .collect()
+        })
+        .collect();
+    let tmpl = PrintAllDaysTemplate {
+        days: days
+            .iter()
+            .zip(day_entries.iter())
+            .map(|(day, entries)| PrintDayContentTemplate::new(&event, day.date, entries, &rooms))
+            .collect(),
+        event: &event,
+    };
+    Ok(Html::new(tmpl.render()?))
+}
+
+#[derive(Template)]
+#[template(path = "print_day.html")]
+struct PrintDayTemplate<'a> {
+    content: PrintDayContentTemplate<'a>,
+    event: &'a ExtendedEvent,
+    date: chrono::NaiveDate,
+}
+
+#[derive(Template)]
+#[template(path = "print_all_days.html")]
+struct PrintAllDaysTemplate<'a> {
+    days: Vec<PrintDayContentTemplate<'a>>,
+    event: &'a ExtendedEvent,
+}
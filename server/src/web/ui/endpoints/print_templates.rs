@@ -2,6 +2,7 @@ use crate::data_store::EventId;
 use crate::data_store::auth_token::Privilege;
 use crate::data_store::models::{ExtendedEvent, Passphrase};
 use crate::web::AppState;
+use crate::web::time_calculation::most_reasonable_date;
 use crate::web::ui::base_template::{
     AnyEventData, BaseConfigTemplateContext, BaseTemplateContext, ConfigNavButton, MainNavButton,
 };
@@ -12,6 +13,7 @@ use actix_web::{HttpRequest, HttpResponse, Responder, get, mime, web};
 use askama::Template;
 use qrcode::QrCode;
 use qrcode::render::svg;
+use serde::Deserialize;
 
 #[get("/{event_id}/config/print_template_link")]
 pub async fn print_link_and_passphrase(
@@ -110,15 +112,64 @@ pub async fn event_ui_link_qr_code(
         req.url_for("event_index", [&event_id.to_string()])?
     };
 
-    let code = QrCode::new(url.to_string().as_bytes()).unwrap();
+    qr_code_svg_response(url.as_str(), DEFAULT_QR_CODE_SIZE)
+}
+
+#[derive(Deserialize)]
+pub struct QrCodeQueryData {
+    /// Minimum width and height of the QR code image in pixels. Clamped to
+    /// [MIN_QR_CODE_SIZE]..=[MAX_QR_CODE_SIZE].
+    size: Option<u32>,
+}
+
+const DEFAULT_QR_CODE_SIZE: u32 = 200;
+const MIN_QR_CODE_SIZE: u32 = 50;
+const MAX_QR_CODE_SIZE: u32 = 2000;
+
+/// QR code, linking to the event's main list (at the most reasonable date, i.e. the current date
+/// during the event)
+#[get("/{event_id}/qr.svg")]
+pub async fn event_main_list_qr_code(
+    path: web::Path<EventId>,
+    query: web::Query<QrCodeQueryData>,
+    state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let event_id = path.into_inner();
+    let session_token =
+        util::extract_session_token(&state, &req, Privilege::ShowKueaPlan, event_id)?;
+    let event = web::block(move || -> Result<_, AppError> {
+        let mut store = state.store.get_facade()?;
+        let auth = store.get_auth_token_for_session(&session_token, event_id)?;
+        Ok(store.get_extended_event(&auth, event_id)?)
+    })
+    .await??;
+
+    let url = req.url_for(
+        "main_list",
+        [
+            event_id.to_string(),
+            most_reasonable_date(&event).to_string(),
+        ],
+    )?;
+    let size = query
+        .size
+        .unwrap_or(DEFAULT_QR_CODE_SIZE)
+        .clamp(MIN_QR_CODE_SIZE, MAX_QR_CODE_SIZE);
+    qr_code_svg_response(url.as_str(), size)
+}
+
+/// Generate an HTTP response with an SVG image of a QR code, encoding the given `data`, with a
+/// minimum width and height of `size` pixels.
+fn qr_code_svg_response(data: &str, size: u32) -> Result<HttpResponse, AppError> {
+    let code = QrCode::new(data.as_bytes())
+        .map_err(|e| AppError::InternalError(format!("Could not generate QR code: {}", e)))?;
     let image = code
         .render()
-        .min_dimensions(200, 200)
+        .min_dimensions(size, size)
         .dark_color(svg::Color("#000000"))
         .light_color(svg::Color("#ffffff"))
         .build();
 
-    Ok(HttpResponse::Ok()
-        .content_type(mime::IMAGE_SVG)
-        .message_body(image))
+    Ok(HttpResponse::Ok().content_type(mime::IMAGE_SVG).body(image))
 }
@@ -1,8 +1,8 @@
 use crate::data_store::EntryFilter;
 use crate::data_store::auth_token::Privilege;
-use crate::data_store::models::{EntryState, EventClockInfo};
+use crate::data_store::models::{EntryState, ExtendedEvent};
 use crate::web::AppState;
-use crate::web::time_calculation::timestamp_from_effective_date_and_time;
+use crate::web::time_calculation::{open_end_timestamp, timestamp_from_effective_date_and_time};
 use crate::web::ui::error::AppError;
 use crate::web::ui::form_values::ValidateFromFormInput;
 use crate::web::ui::{util, validation};
@@ -48,15 +48,11 @@ async fn concurrent_entries(
             store.get_all_entries_filtered(
                 &auth,
                 event_id,
-                query.to_filter(&event.clock_info),
+                query.to_filter(&event),
                 &NON_DISMISSED_ENTRY_STATES,
             )?
         } else {
-            store.get_published_entries_filtered(
-                &auth,
-                event_id,
-                query.to_filter(&event.clock_info),
-            )?
+            store.get_published_entries_filtered(&auth, event_id, query.to_filter(&event))?
         };
         Ok((entries, store.get_rooms(&auth, event_id)?, event, query))
     })
@@ -83,8 +79,10 @@ async fn concurrent_entries(
             let begin = e.entry.begin.with_timezone(&event.clock_info.timezone).naive_local();
             let end = e.entry.end.with_timezone(&event.clock_info.timezone).naive_local();
             let show_begin_date = begin.date() < query.effective_day;
-            let show_end_date =
-                query.duration > chrono::Duration::hours(12) && end.date() != begin.date();
+            let show_end_date = matches!(
+                query.duration,
+                validation::EntryDuration::Fixed(d) if d > chrono::Duration::hours(12)
+            ) && end.date() != begin.date();
             let room_ids_sorted = rooms_by_id.iter_rooms_by_id_ordered(&e.room_ids).map(|r| r.id).collect::<Vec<_>>();
             json!({
                 "title": e.entry.title,
@@ -107,8 +105,8 @@ async fn concurrent_entries(
 struct ConcurrentEntriesQuery {
     effective_day: chrono::NaiveDate,
     begin_time: chrono::NaiveTime,
-    #[serde(deserialize_with = "deserialize_nice_duration_hours")]
-    duration: chrono::Duration,
+    #[serde(deserialize_with = "deserialize_entry_duration")]
+    duration: validation::EntryDuration,
     #[serde(deserialize_with = "deserialize_comma_separated_list_of_uuids")]
     rooms: Vec<uuid::Uuid>,
     current_entry_id: Option<uuid::Uuid>,
@@ -117,10 +115,18 @@ struct ConcurrentEntriesQuery {
 }
 
 impl ConcurrentEntriesQuery {
-    fn to_filter(&self, clock_info: &EventClockInfo) -> EntryFilter {
-        let begin =
-            timestamp_from_effective_date_and_time(self.effective_day, self.begin_time, clock_info);
-        let end = begin + self.duration;
+    fn to_filter(&self, event: &ExtendedEvent) -> EntryFilter {
+        let begin = timestamp_from_effective_date_and_time(
+            self.effective_day,
+            self.begin_time,
+            &event.clock_info,
+        );
+        let end = match self.duration {
+            validation::EntryDuration::Fixed(duration) => begin + duration,
+            validation::EntryDuration::OpenEnded => {
+                open_end_timestamp(self.effective_day, &event.basic_data, &event.clock_info)
+            }
+        };
 
         EntryFilter::builder()
             .after(begin, false)
@@ -129,18 +135,19 @@ impl ConcurrentEntriesQuery {
     }
 }
 
-fn deserialize_nice_duration_hours<'de, D>(deserializer: D) -> Result<chrono::Duration, D::Error>
+fn deserialize_entry_duration<'de, D>(
+    deserializer: D,
+) -> Result<validation::EntryDuration, D::Error>
 where
     D: Deserializer<'de>,
 {
     let duration_string = String::deserialize(deserializer)?;
-    let value = validation::NiceDurationHours::from_form_value(&duration_string).map_err(|_| {
+    validation::EntryDuration::from_form_value(&duration_string).map_err(|_| {
         D::Error::invalid_value(
             Unexpected::Str(&duration_string),
-            &"A valid 'nice duration'",
+            &"A valid 'nice duration' or 'offen'",
         )
-    })?;
-    Ok(value.into_inner())
+    })
 }
 
 #[get("/{event_id}/review-notifications")]
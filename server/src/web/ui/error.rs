@@ -69,6 +69,10 @@ impl From<StoreError> for AppError {
                 Self::InvalidData(format!("Data policy violation: {}", p))
             }
             StoreError::InvalidInputData(e) => Self::InternalError(format!("Invalid data: {}", e)),
+            StoreError::RoomInUse { entry_count } => Self::InvalidData(format!(
+                "Room is still used by {} upcoming entries",
+                entry_count
+            )),
             StoreError::InvalidDataInDatabase(e) => Self::InternalError(format!(
                 "Data queried from database could not be deserialized: {}",
                 e
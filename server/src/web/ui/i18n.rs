@@ -0,0 +1,207 @@
+//! Localization of UI strings
+//!
+//! The language of the UI is selected per request, based on the `?lang=` query parameter or the
+//! `Accept-Language` header (see [Language::from_request]), falling back to German.
+//! All translated strings of one language are bundled in a [Messages] struct, which can be
+//! retrieved for the current request via [Messages::for_request].
+//!
+//! Some messages contain a `{}` placeholder, which is to be replaced with a (translated) value by
+//! the caller, using [Messages::fill].
+//!
+//! Currently, only parts of the UI have been migrated to use message keys. The remaining templates
+//! and endpoints still contain hard-coded German strings.
+use actix_web::http::header::{AcceptLanguage, Preference};
+use actix_web::{HttpMessage, HttpRequest};
+
+/// Languages, in which the UI is available
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Language {
+    German,
+    English,
+}
+
+impl Language {
+    /// Language to be used, when the client does not request any of the supported languages
+    pub const DEFAULT: Language = Language::German;
+
+    /// Get the supported language matching the given language tag (e.g. "de", "en-GB"), if any.
+    ///
+    /// Only the primary language subtag is considered and compared case-insensitively.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary_language = tag.split('-').next().unwrap_or("");
+        if primary_language.eq_ignore_ascii_case("de") {
+            Some(Language::German)
+        } else if primary_language.eq_ignore_ascii_case("en") {
+            Some(Language::English)
+        } else {
+            None
+        }
+    }
+
+    /// Select the UI language for the given request.
+    ///
+    /// A supported language given in the `lang` query parameter takes precedence. Otherwise, the
+    /// supported language with the highest preference in the `Accept-Language` header is chosen.
+    /// If neither applies, [Language::DEFAULT] is used.
+    pub fn from_request(request: &HttpRequest) -> Self {
+        let query_language = url::form_urlencoded::parse(request.query_string().as_bytes())
+            .find(|(key, _)| key == "lang")
+            .and_then(|(_, value)| Self::from_tag(&value));
+        if let Some(language) = query_language {
+            return language;
+        }
+        request
+            .get_header::<AcceptLanguage>()
+            .and_then(|header| {
+                header
+                    .ranked()
+                    .into_iter()
+                    .find_map(|preference| match preference {
+                        Preference::Specific(tag) => Self::from_tag(tag.as_str()),
+                        Preference::Any => None,
+                    })
+            })
+            .unwrap_or(Self::DEFAULT)
+    }
+
+    /// Get the set of translated UI strings for this language
+    pub fn messages(self) -> &'static Messages {
+        match self {
+            Language::German => &GERMAN,
+            Language::English => &ENGLISH,
+        }
+    }
+}
+
+/// Translated UI strings of a single language
+pub struct Messages {
+    /// Page title of the entry edit form
+    pub edit_entry_title: &'static str,
+    /// Page title of the new entry form
+    pub new_entry_title: &'static str,
+    /// Name of an entry, as subject of a sentence, for use in flash messages
+    pub entry_name: &'static str,
+    /// Page title of the announcement edit form
+    pub edit_announcement_title: &'static str,
+    /// Page title of the new announcement form
+    pub new_announcement_title: &'static str,
+    /// Name of an announcement, as subject of a sentence, for use in flash messages
+    pub announcement_name: &'static str,
+    /// Name of a room, as subject of a sentence, for use in flash messages
+    pub room_name: &'static str,
+    /// Name of a category, as subject of a sentence, for use in flash messages
+    pub category_name: &'static str,
+    /// Name of a passphrase, as subject of a sentence, for use in flash messages
+    pub passphrase_name: &'static str,
+    /// Name of a derivable access role (passphrase), as subject of a sentence, for use in flash
+    /// messages
+    pub derivable_role_name: &'static str,
+    /// Name of a previous date of an entry, as subject of a sentence, for use in flash messages
+    pub previous_date_name: &'static str,
+    /// Name of the event's metadata, as subject of a sentence, for use in flash messages
+    pub event_metadata_name: &'static str,
+    /// Flash message after creating a new thing. `{}` is replaced by the name of the thing.
+    pub new_entity_saved: &'static str,
+    /// Flash message after saving changes to an existing thing
+    pub changes_saved: &'static str,
+    /// Flash message on form validation errors
+    pub invalid_form_data: &'static str,
+    /// Flash message when the edited thing has been changed concurrently. `{}` is replaced by the
+    /// name of the thing.
+    pub concurrent_edit_conflict: &'static str,
+    /// Flash message when the database transaction could not be completed due to parallel access
+    pub transaction_conflict: &'static str,
+}
+
+impl Messages {
+    /// Get the set of translated UI strings for the language of the given request.
+    pub fn for_request(request: &HttpRequest) -> &'static Self {
+        Language::from_request(request).messages()
+    }
+
+    /// Fill the `{}` placeholder of the given message with the given value.
+    pub fn fill(message: &str, value: &str) -> String {
+        message.replacen("{}", value, 1)
+    }
+}
+
+static GERMAN: Messages = Messages {
+    edit_entry_title: "Eintrag bearbeiten",
+    new_entry_title: "Neuer Eintrag",
+    entry_name: "Der Eintrag",
+    edit_announcement_title: "Bekanntmachung bearbeiten",
+    new_announcement_title: "Neue Bekanntmachung",
+    announcement_name: "Die Bekanntmachung",
+    room_name: "Der Ort",
+    category_name: "Die Kategorie",
+    passphrase_name: "Die Passphrase",
+    derivable_role_name: "Die ableitbare Rolle",
+    previous_date_name: "Der vorherige Termin",
+    event_metadata_name: "Die Veranstaltungs-Metadaten",
+    new_entity_saved: "{} wurde gespeichert.",
+    changes_saved: "Änderung wurde gespeichert.",
+    invalid_form_data: "Eingegebene Daten sind ungültig. Bitte markierte Felder überprüfen.",
+    concurrent_edit_conflict: "{} wurde zwischenzeitlich bearbeitet. Bitte das Formular neu laden und die Änderung erneut durchführen.",
+    transaction_conflict: "Konnte wegen parallelem Datenbank-Zugriff nicht speichern. Bitte Formular erneut absenden.",
+};
+
+static ENGLISH: Messages = Messages {
+    edit_entry_title: "Edit entry",
+    new_entry_title: "New entry",
+    entry_name: "The entry",
+    edit_announcement_title: "Edit announcement",
+    new_announcement_title: "New announcement",
+    announcement_name: "The announcement",
+    room_name: "The room",
+    category_name: "The category",
+    passphrase_name: "The passphrase",
+    derivable_role_name: "The derivable role",
+    previous_date_name: "The previous date",
+    event_metadata_name: "The event metadata",
+    new_entity_saved: "{} has been saved.",
+    changes_saved: "Changes have been saved.",
+    invalid_form_data: "The entered data is invalid. Please check the highlighted fields.",
+    concurrent_edit_conflict: "{} has been modified in the meantime. Please reload the form and apply your changes again.",
+    transaction_conflict: "Could not save due to concurrent database access. Please submit the form again.",
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    #[test]
+    fn test_language_from_request() {
+        let request = TestRequest::default().to_http_request();
+        assert_eq!(Language::from_request(&request), Language::German);
+
+        let request =
+            TestRequest::with_uri("/ui/1/new_entry?date=2025-01-03&lang=en").to_http_request();
+        assert_eq!(Language::from_request(&request), Language::English);
+
+        let request = TestRequest::default()
+            .insert_header(("Accept-Language", "fr-CH, fr;q=0.9, en-GB;q=0.8, de;q=0.7"))
+            .to_http_request();
+        assert_eq!(Language::from_request(&request), Language::English);
+
+        // Query parameter takes precedence over the header
+        let request = TestRequest::with_uri("/ui/1/new_entry?lang=de")
+            .insert_header(("Accept-Language", "en"))
+            .to_http_request();
+        assert_eq!(Language::from_request(&request), Language::German);
+
+        // Unsupported languages are ignored
+        let request = TestRequest::with_uri("/ui/1/new_entry?lang=fr")
+            .insert_header(("Accept-Language", "fr, *;q=0.5"))
+            .to_http_request();
+        assert_eq!(Language::from_request(&request), Language::German);
+    }
+
+    #[test]
+    fn test_fill_message() {
+        assert_eq!(
+            Messages::fill(ENGLISH.new_entity_saved, ENGLISH.entry_name),
+            "The entry has been saved."
+        );
+    }
+}
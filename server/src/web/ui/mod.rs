@@ -1,11 +1,20 @@
-use actix_web::http::header::{CacheControl, CacheDirective};
+use actix_web::http::header::{
+    self, AcceptEncoding, CacheControl, CacheDirective, ContentEncoding, Encoding,
+};
 use actix_web::middleware::from_fn;
+use actix_web::web::Bytes;
 use actix_web::web::PathConfig;
 use actix_web::{HttpResponse, Responder, get, web};
 use error::AppError;
 use error_page::error_page_middleware;
 use flash::flash_middleware;
+use flate2::write::GzEncoder;
+use lazy_static::lazy_static;
 use rust_embed::Embed;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::RwLock;
 
 mod askama_filters;
 pub mod base_template;
@@ -15,6 +24,7 @@ pub mod error;
 pub mod error_page;
 pub mod flash;
 mod form_values;
+pub mod i18n;
 mod sub_templates;
 mod util;
 pub mod validation;
@@ -53,6 +63,7 @@ fn get_ui_service() -> actix_web::Scope {
         .service(endpoints::participant_submit_entry::participant_submit_entry_form)
         .service(endpoints::previous_dates::previous_dates_overview)
         .service(endpoints::previous_dates::delete_previous_date)
+        .service(endpoints::previous_dates::restore_previous_date)
         .service(endpoints::new_previous_date::new_previous_date_form)
         .service(endpoints::new_previous_date::new_previous_date)
         .service(endpoints::delete_entry::delete_entry_form)
@@ -95,8 +106,11 @@ fn get_ui_service() -> actix_web::Scope {
         .service(endpoints::delete_passphrase::delete_passphrase)
         .service(endpoints::delete_passphrase::invalidate_passphrase)
         .service(endpoints::calendar_link_overview::calendar_link_overview)
+        .service(endpoints::print_day::print_day)
+        .service(endpoints::print_day::print_all_days)
         .service(endpoints::print_templates::print_link_and_passphrase)
         .service(endpoints::print_templates::event_ui_link_qr_code)
+        .service(endpoints::print_templates::event_main_list_qr_code)
         .service(endpoints::review::list_to_review)
         .service(endpoints::review::list_drafts)
         .service(endpoints::review::list_rejected_entries)
@@ -131,25 +145,162 @@ fn get_ui_api_service() -> actix_web::Scope {
 #[folder = "static/"]
 struct Resources;
 
+/// Content encodings of compressible static resources, in the order of our preference
+const STATIC_RESOURCE_ENCODINGS: [Encoding; 3] =
+    [Encoding::brotli(), Encoding::gzip(), Encoding::identity()];
+
+lazy_static! {
+    /// Cache of compressed static resources, filled on the first request of each resource with
+    /// each encoding.
+    static ref COMPRESSED_RESOURCES: RwLock<HashMap<(String, ContentEncoding), Bytes>> =
+        RwLock::new(HashMap::new());
+}
+
 impl Resources {
-    fn handle_embedded_file(path: &str) -> HttpResponse {
-        match Self::get(path) {
-            Some(content) => HttpResponse::Ok()
-                .content_type(mime_guess::from_path(path).first_or_octet_stream().as_ref())
-                .append_header(CacheControl(vec![CacheDirective::MaxAge(86400 * 365)]))
-                .body(content.data.into_owned()),
-            None => {
+    async fn handle_embedded_file(
+        path: &str,
+        accept_encoding: Option<&AcceptEncoding>,
+    ) -> Result<HttpResponse, AppError> {
+        let Some(content) = Self::get(path) else {
+            return Ok(
                 HttpResponse::NotFound().body(format!("Static resource file '{}' not found", path))
-            }
+            );
+        };
+        let mime_type = mime_guess::from_path(path).first_or_octet_stream();
+        let mut response = HttpResponse::Ok();
+        response
+            .content_type(mime_type.as_ref())
+            .append_header(CacheControl(vec![CacheDirective::MaxAge(86400 * 365)]));
+        if !is_compressible(&mime_type) {
+            return Ok(response.body(content.data.into_owned()));
+        }
+
+        response.append_header((header::VARY, "Accept-Encoding"));
+        let encoding = match accept_encoding
+            .and_then(|accept| accept.negotiate(STATIC_RESOURCE_ENCODINGS.iter()))
+        {
+            Some(Encoding::Known(encoding)) => encoding,
+            _ => ContentEncoding::Identity,
+        };
+        if encoding == ContentEncoding::Identity {
+            return Ok(response.body(content.data.into_owned()));
+        }
+        Ok(response
+            .insert_header(encoding)
+            .body(Self::get_compressed(path, encoding, content.data).await?))
+    }
+
+    /// Get the compressed data of the static resource file from the cache or compress it (and
+    /// store it in the cache) on the first request.
+    ///
+    /// The compression is run on the blocking thread pool, since it takes quite some CPU time with
+    /// our high compression levels.
+    async fn get_compressed(
+        path: &str,
+        encoding: ContentEncoding,
+        data: Cow<'static, [u8]>,
+    ) -> Result<Bytes, actix_web::error::BlockingError> {
+        let key = (path.to_owned(), encoding);
+        if let Some(compressed) = COMPRESSED_RESOURCES.read().unwrap().get(&key) {
+            return Ok(compressed.clone());
         }
+        let compressed = Bytes::from(web::block(move || compress(&data, encoding)).await?);
+        COMPRESSED_RESOURCES
+            .write()
+            .unwrap()
+            .insert(key, compressed.clone());
+        Ok(compressed)
+    }
+}
+
+/// Check if static resources of the given type should be served compressed. Images (except for
+/// SVG) are already compressed in themselves.
+fn is_compressible(mime_type: &mime_guess::Mime) -> bool {
+    mime_type.type_() == mime_guess::mime::TEXT
+        || mime_type.subtype() == mime_guess::mime::JAVASCRIPT
+        || mime_type.subtype() == mime_guess::mime::JSON
+        || mime_type.subtype() == mime_guess::mime::SVG
+}
+
+fn compress(data: &[u8], encoding: ContentEncoding) -> Vec<u8> {
+    match encoding {
+        ContentEncoding::Brotli => {
+            let mut encoder = brotli::CompressorWriter::new(Vec::new(), 4096, 11, 22);
+            encoder
+                .write_all(data)
+                .expect("Writing to Vec should not fail");
+            encoder.into_inner()
+        }
+        ContentEncoding::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::best());
+            encoder
+                .write_all(data)
+                .expect("Writing to Vec should not fail");
+            encoder.finish().expect("Writing to Vec should not fail")
+        }
+        _ => data.to_vec(),
     }
 }
 
 #[get("/static/{_:.*}")]
-async fn static_resources(path: web::Path<String>) -> impl Responder {
-    Resources::handle_embedded_file(path.as_str())
+async fn static_resources(
+    path: web::Path<String>,
+    accept_encoding: Option<web::Header<AcceptEncoding>>,
+) -> Result<impl Responder, AppError> {
+    Resources::handle_embedded_file(path.as_str(), accept_encoding.as_deref()).await
 }
 
 async fn not_found_handler() -> Result<&'static str, AppError> {
     Err(AppError::PageNotFound)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{App, test};
+    use std::io::Read;
+
+    #[actix_web::test]
+    async fn test_compressed_static_resources() {
+        let app = test::init_service(App::new().service(static_resources)).await;
+        let original = Resources::get("main.css").unwrap().data;
+
+        let request = test::TestRequest::get()
+            .uri("/static/main.css")
+            .insert_header(("Accept-Encoding", "gzip"))
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert!(response.status().is_success());
+        let headers = response.headers();
+        assert_eq!(headers.get("Content-Encoding").unwrap(), "gzip");
+        assert_eq!(headers.get("Vary").unwrap(), "Accept-Encoding");
+        assert!(headers.get("Cache-Control").is_some());
+        let body = test::read_body(response).await;
+        let mut decompressed = Vec::new();
+        flate2::read::GzDecoder::new(&body[..])
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, original.as_ref());
+
+        let request = test::TestRequest::get()
+            .uri("/static/main.css")
+            .insert_header(("Accept-Encoding", "gzip, deflate, br"))
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.headers().get("Content-Encoding").unwrap(), "br");
+
+        let request = test::TestRequest::get()
+            .uri("/static/main.css")
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert!(response.headers().get("Content-Encoding").is_none());
+        assert_eq!(test::read_body(response).await, original.as_ref());
+
+        let request = test::TestRequest::get()
+            .uri("/static/favicon.ico")
+            .insert_header(("Accept-Encoding", "gzip"))
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert!(response.headers().get("Content-Encoding").is_none());
+    }
+}
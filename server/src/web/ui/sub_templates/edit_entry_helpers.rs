@@ -44,6 +44,7 @@ impl<'a> EditEntryNavbar<'a> {
             crate::web::ui::endpoints::edit_entry::NewEntryQueryParams {
                 date: None,
                 clone_from: Some(*self.entry_id),
+                from_template: None,
             },
         )?));
         Ok(url.to_string())
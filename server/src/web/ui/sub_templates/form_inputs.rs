@@ -47,6 +47,7 @@ pub struct FormFieldTemplate<'a, T: FormValueRepresentation> {
     info: Option<askama::filters::MaybeSafe<&'a str>>,
     data: &'a FormValue<T>,
     css_class: &'a str,
+    datalist: &'a [String],
 }
 
 impl<'a, T: FormValueRepresentation> FormFieldTemplate<'a, T> {
@@ -60,6 +61,7 @@ impl<'a, T: FormValueRepresentation> FormFieldTemplate<'a, T> {
             info: None,
             data,
             css_class: "",
+            datalist: &[],
         }
     }
 
@@ -92,6 +94,13 @@ impl<'a, T: FormValueRepresentation> FormFieldTemplate<'a, T> {
         self.css_class = class;
         self
     }
+
+    /// Add a list of suggested values to the (text) input field, offered by the browser for
+    /// autocompletion
+    pub fn datalist(mut self, options: &'a [String]) -> Self {
+        self.datalist = options;
+        self
+    }
 }
 
 impl<T: FormValueRepresentation> askama::filters::HtmlSafe for FormFieldTemplate<'_, T> {}
@@ -1,6 +1,7 @@
 use crate::data_store::models::{Category, EventClockInfo, FullEntry, FullPreviousDate, Room};
 use crate::data_store::{CategoryId, RoomId};
 use crate::web::time_calculation;
+use crate::web::ui::colors;
 use crate::web::ui::colors::CategoryColors;
 use crate::web::ui::util::url_for_public_entry_details;
 use actix_web::HttpRequest;
@@ -179,12 +180,30 @@ impl<'a> MainListRowTemplate<'a> {
         if !row.entry_takes_place_now() {
             result.push_str(" kuea-cancelled");
         }
+        if row.entry.entry.is_tentative {
+            result.push_str(" kuea-tentative");
+        }
         if row.entry.entry.is_room_reservation {
             result.push_str(" fst-italic");
         }
         result
     }
 
+    /// Generate the HTML 'style' attribute content for the table row, if the entry has a custom
+    /// color, which overrides the category's colors.
+    ///
+    /// Returns None, if the entry has no (valid) color override, such that the category colors from
+    /// [styles_for_category] apply.
+    fn css_style_for_tr(&self) -> Option<String> {
+        self.row
+            .entry
+            .entry
+            .color_override
+            .as_ref()
+            .and_then(|color| CategoryColors::from_base_color_hex(color).ok())
+            .map(|colors| colors.as_css())
+    }
+
     /// Generate a URL that takes the user directly to the current kueaplan entry date in the
     /// relevant list, according to main_entry_link_mode, if possible.
     pub fn url_for_current_entry(&self) -> Result<Option<url::Url>, UrlGenerationError> {
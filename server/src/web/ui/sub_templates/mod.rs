@@ -3,3 +3,4 @@ pub mod edit_entry_helpers;
 pub mod form_inputs;
 pub mod main_list_helpers;
 pub mod main_list_row;
+pub mod print_day_content;
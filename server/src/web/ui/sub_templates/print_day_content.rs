@@ -0,0 +1,86 @@
+use crate::data_store::models::{ExtendedEvent, FullEntry, Room};
+use askama::Template;
+
+/// Sub-Template for rendering the printable KüA-Plan of a single day: a heading with the date and
+/// a table of the day's entries for each room.
+///
+/// This is used by the print view of a single day and by the print view of all days of the event.
+/// The including template needs to provide the CSS styles for the rendered elements.
+#[derive(Template)]
+#[template(path = "sub_templates/print_day_content.html")]
+pub struct PrintDayContentTemplate<'a> {
+    room_blocks: Vec<(Option<&'a Room>, Vec<&'a FullEntry>)>,
+    event: &'a ExtendedEvent,
+    date: chrono::NaiveDate,
+}
+
+impl<'a> PrintDayContentTemplate<'a> {
+    /// Create the template for the given `date`. `entries` must be the (published) entries of the
+    /// day, sorted by their begin.
+    pub fn new(
+        event: &'a ExtendedEvent,
+        date: chrono::NaiveDate,
+        entries: &[&'a FullEntry],
+        rooms: &'a [Room],
+    ) -> Self {
+        Self {
+            room_blocks: group_entries_by_room(entries, rooms),
+            event,
+            date,
+        }
+    }
+
+    fn to_our_timezone(&self, timestamp: &chrono::DateTime<chrono::Utc>) -> chrono::NaiveDateTime {
+        timestamp
+            .with_timezone(&self.event.clock_info.timezone)
+            .naive_local()
+    }
+}
+
+impl askama::filters::HtmlSafe for PrintDayContentTemplate<'_> {}
+
+/// Filters for the askama template
+mod filters {
+    use crate::web::util;
+
+    #[askama::filter_fn]
+    pub fn weekday(
+        date: &chrono::NaiveDate,
+        _: &dyn askama::Values,
+    ) -> askama::Result<&'static str> {
+        Ok(util::weekday(date))
+    }
+}
+
+/// Group the given (sorted) `entries` by room, in the order of the given `rooms`.
+///
+/// Entries with multiple rooms are listed with each of their rooms. Entries without any room are
+/// collected in a final block with room `None`. Rooms without any entries are omitted.
+fn group_entries_by_room<'a>(
+    entries: &[&'a FullEntry],
+    rooms: &'a [Room],
+) -> Vec<(Option<&'a Room>, Vec<&'a FullEntry>)> {
+    let mut result: Vec<(Option<&Room>, Vec<&FullEntry>)> = rooms
+        .iter()
+        .map(|room| {
+            (
+                Some(room),
+                entries
+                    .iter()
+                    .copied()
+                    .filter(|e| e.room_ids.contains(&room.id))
+                    .collect(),
+            )
+        })
+        .collect();
+    result.push((
+        None,
+        entries
+            .iter()
+            .copied()
+            .filter(|e| e.room_ids.is_empty())
+            .collect(),
+    ));
+    result.retain(|(_, entries)| !entries.is_empty());
+    result
+}
@@ -7,14 +7,13 @@ use crate::web::time_calculation::get_effective_date;
 use crate::web::ui::error::AppError;
 use crate::web::ui::flash::{FlashMessage, FlashMessageActionButton, FlashType, FlashesInterface};
 use crate::web::ui::form_values::{_FormValidSimpleValidate, FormValue};
+use crate::web::ui::i18n::Messages;
 use crate::web::ui::sub_templates::main_list_row::MainListRow;
 use crate::web::ui::validation;
 use actix_web::error::UrlGenerationError;
 use actix_web::web::Redirect;
 use actix_web::{Either, HttpRequest, HttpResponse};
 use askama::Template;
-use chrono::Datelike;
-use chrono::Weekday;
 
 #[allow(clippy::identity_op)] // We want to explicitly state that it's "1" year
 pub const SESSION_COOKIE_MAX_AGE: std::time::Duration =
@@ -29,6 +28,24 @@ pub fn event_days(event: &Event) -> Vec<chrono::NaiveDate> {
         .collect()
 }
 
+/// Get a short note on the restricted time range of the given day of the event, if it's the first
+/// day and the event has a `begin_time` or the last day and the event has an `end_time`, e.g.
+/// "ab 14:00 Uhr".
+pub fn event_day_time_note(event: &Event, date: &chrono::NaiveDate) -> Option<String> {
+    let begin_time = event.begin_time.filter(|_| *date == event.begin_date);
+    let end_time = event.end_time.filter(|_| *date == event.end_date);
+    match (begin_time, end_time) {
+        (Some(begin), Some(end)) => Some(format!(
+            "{} – {} Uhr",
+            begin.format("%H:%M"),
+            end.format("%H:%M")
+        )),
+        (Some(begin), None) => Some(format!("ab {} Uhr", begin.format("%H:%M"))),
+        (None, Some(end)) => Some(format!("bis {} Uhr", end.format("%H:%M"))),
+        (None, None) => None,
+    }
+}
+
 /// Generate a URL that takes an orga directly to a specific kueaplan entry in the respective list.
 ///
 /// If the entry is published (without pending review), this is equal to
@@ -112,7 +129,7 @@ pub fn extract_session_token(
                 privilege_expired: false,
             })?
             .value(),
-        &app_state.secret,
+        &app_state.secrets,
         SESSION_COOKIE_MAX_AGE,
     )
     .map_err(|session_error| AppError::PermissionDenied {
@@ -152,32 +169,6 @@ pub fn privilege_access_roles_names(privilege: &Privilege) -> Vec<&'static str>
         .collect()
 }
 
-/// Convert a date to the (german) name of its weekday
-pub fn weekday(date: &chrono::NaiveDate) -> &'static str {
-    match date.weekday() {
-        Weekday::Mon => "Montag",
-        Weekday::Tue => "Dienstag",
-        Weekday::Wed => "Mittwoch",
-        Weekday::Thu => "Donnerstag",
-        Weekday::Fri => "Freitag",
-        Weekday::Sat => "Samstag",
-        Weekday::Sun => "Sonntag",
-    }
-}
-
-/// Convert a date to a short version of the (german) name of its weekday
-pub fn weekday_short(date: &chrono::NaiveDate) -> &'static str {
-    match date.weekday() {
-        Weekday::Mon => "Mo",
-        Weekday::Tue => "Di",
-        Weekday::Wed => "Mi",
-        Weekday::Thu => "Do",
-        Weekday::Fri => "Fr",
-        Weekday::Sat => "Sa",
-        Weekday::Sun => "So",
-    }
-}
-
 /// Selects the Bootstrap Icons icon name for the given [AnnouncementType]
 pub fn announcement_type_icon(announcement_type: AnnouncementType) -> &'static str {
     match announcement_type {
@@ -247,21 +238,22 @@ impl From<Result<(), StoreError>> for FormSubmitResult {
 pub fn create_edit_form_response(
     result: FormSubmitResult,
     form_template: impl Template,
-    name_of_thing: &'static str,
+    name_of_thing: &str,
     form_url: url::Url,
     form_name: &'static str,
     is_new_entity: bool,
     success_redirect: url::Url,
     request: &HttpRequest,
 ) -> Result<Either<Redirect, HttpResponse>, AppError> {
+    let messages = Messages::for_request(request);
     match result {
         FormSubmitResult::Success => {
             request.add_flash_message(FlashMessage {
                 flash_type: FlashType::Success,
                 message: if is_new_entity {
-                    format!("{} wurde gespeichert.", name_of_thing)
+                    Messages::fill(messages.new_entity_saved, name_of_thing)
                 } else {
-                    "Änderung wurde gespeichert.".to_owned()
+                    messages.changes_saved.to_owned()
                 },
                 keep_open: false,
                 button: None,
@@ -273,8 +265,7 @@ pub fn create_edit_form_response(
         FormSubmitResult::ValidationError => {
             request.add_flash_message(FlashMessage {
                 flash_type: FlashType::Error,
-                message: "Eingegebene Daten sind ungültig. Bitte markierte Felder überprüfen."
-                    .to_owned(),
+                message: messages.invalid_form_data.to_owned(),
                 keep_open: false,
                 button: None,
             });
@@ -316,7 +307,7 @@ pub fn create_edit_form_response(
         FormSubmitResult::ConcurrentEditConflict => {
             request.add_flash_message(FlashMessage {
                 flash_type: FlashType::Error,
-                message: format!("{} wurde zwischenzeitlich bearbeitet. Bitte das Formular neu laden und die Änderung erneut durchführen.", name_of_thing),
+                message: Messages::fill(messages.concurrent_edit_conflict, name_of_thing),
                 keep_open: true,
                 button: Some(FlashMessageActionButton::ReloadCleanForm {
                     form_url: form_url.to_string(),
@@ -329,10 +320,11 @@ pub fn create_edit_form_response(
         FormSubmitResult::TransactionConflict => {
             request.add_flash_message(FlashMessage {
                 flash_type: FlashType::Warning,
-                message: "Konnte wegen parallelem Datenbank-Zugriff nicht speichern. Bitte Formular erneut absenden."
-                    .to_owned(),
+                message: messages.transaction_conflict.to_owned(),
                 keep_open: true,
-                button: Some(FlashMessageActionButton::SubmitForm { form_id: form_name.to_string() }),
+                button: Some(FlashMessageActionButton::SubmitForm {
+                    form_id: form_name.to_string(),
+                }),
             });
             Ok(Either::Right(
                 HttpResponse::ServiceUnavailable().body(form_template.render()?),
@@ -458,7 +450,7 @@ pub fn format_access_role(role: &AccessRole) -> askama::filters::Safe<String> {
         AccessRole::User => ("person-fill", "primary"),
         AccessRole::Orga => ("clipboard", "warning"),
         AccessRole::Admin | AccessRole::ServerAdmin => ("gear-fill", "warning"),
-        AccessRole::SharableViewLink => ("share", "info"),
+        AccessRole::SharableViewLink | AccessRole::ApiReader => ("share", "info"),
     };
     askama::filters::Safe(format!(
         "<span class=\"text-{} text-nowrap\"><i class=\"bi bi-{}\"></i> {}</span>",
@@ -468,23 +460,38 @@ pub fn format_access_role(role: &AccessRole) -> askama::filters::Safe<String> {
     ))
 }
 
+pub fn validate_datetime_local_value<T: chrono::TimeZone>(
+    value: &mut FormValue<validation::DateTimeLocal>,
+    local_timezone: &T,
+) -> Option<chrono::DateTime<chrono::Utc>> {
+    let local_datetime = value.validate()?;
+    local_datetime_to_utc(&local_datetime.0, local_timezone)
+        .map_err(|e| value.add_error(e))
+        .ok()
+}
+
 pub fn validate_optional_datetime_local_value<T: chrono::TimeZone>(
     value: &mut FormValue<validation::MaybeEmpty<validation::DateTimeLocal>>,
     local_timezone: &T,
 ) -> Option<Option<chrono::DateTime<chrono::Utc>>> {
-    let local_datetime = value.validate()?;
-    if let Some(local_datetime) = local_datetime.0 {
-        let utc_datetime = local_timezone
-            .from_local_datetime(&local_datetime.0)
-            .latest()
-            .map(|v| v.to_utc());
-        if let Some(utc_datetime) = utc_datetime {
-            Some(Some(utc_datetime))
-        } else {
-            value.add_error("This point in time does not exist in the local timezone.".to_owned());
-            None
-        }
-    } else {
-        Some(None)
-    }
+    let Some(local_datetime) = value.validate()?.0 else {
+        return Some(None);
+    };
+    local_datetime_to_utc(&local_datetime.0, local_timezone)
+        .map_err(|e| value.add_error(e))
+        .ok()
+        .map(Some)
+}
+
+/// Convert the local datetime from a form input into UTC or return an error message for the form
+/// value, if the point in time does not exist in the local timezone.
+fn local_datetime_to_utc<T: chrono::TimeZone>(
+    local_datetime: &chrono::NaiveDateTime,
+    local_timezone: &T,
+) -> Result<chrono::DateTime<chrono::Utc>, String> {
+    local_timezone
+        .from_local_datetime(local_datetime)
+        .latest()
+        .map(|v| v.to_utc())
+        .ok_or_else(|| "This point in time does not exist in the local timezone.".to_owned())
 }
@@ -1,4 +1,4 @@
-use crate::data_store::models::EventDayTimeSchedule;
+use crate::data_store::models::{EntryLink, EventDayTimeSchedule};
 use crate::web::ui::form_values::{
     FormValueRepresentation, ValidateFromFormInput, ValidationDataForFormValue,
 };
@@ -31,6 +31,34 @@ impl ValidateFromFormInput for NonEmptyString {
     }
 }
 
+/// A non-empty string of at most `MAX` characters. The length is counted in Unicode scalar values
+/// (not bytes), i.e. like `str::chars().count()`.
+#[derive(Default, Debug)]
+pub struct BoundedString<const MAX: usize>(pub String);
+
+impl<const MAX: usize> BoundedString<MAX> {
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl<const MAX: usize> FormValueRepresentation for BoundedString<MAX> {
+    fn into_form_value_string(self) -> String {
+        self.0
+    }
+}
+impl<const MAX: usize> ValidateFromFormInput for BoundedString<MAX> {
+    fn from_form_value(value: &str) -> Result<Self, String> {
+        if value.is_empty() {
+            Err("Darf nicht leer sein".to_owned())
+        } else if value.chars().count() > MAX {
+            Err(format!("Höchstens {} Zeichen erlaubt", MAX))
+        } else {
+            Ok(BoundedString(value.to_owned()))
+        }
+    }
+}
+
 #[derive(Default, Debug, PartialEq)]
 pub struct Int32FromList(pub i32);
 
@@ -120,7 +148,7 @@ impl ValidationDataForFormValue<CommaSeparatedUuidsFromList> for &Vec<Uuid> {
     }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, PartialEq)]
 pub struct TimeOfDay(pub chrono::NaiveTime);
 
 impl TimeOfDay {
@@ -255,6 +283,44 @@ impl ValidateFromFormInput for NiceDurationHours {
     }
 }
 
+/// Duration of an entry: Either a fixed duration in the format of [NiceDurationHours] or the
+/// keyword "offen" for an open-ended entry, which lasts until the end of the (effective) day.
+#[derive(Debug, PartialEq)]
+pub enum EntryDuration {
+    Fixed(chrono::Duration),
+    OpenEnded,
+}
+
+impl EntryDuration {
+    const OPEN_ENDED_KEYWORD: &'static str = "offen";
+}
+
+impl Default for EntryDuration {
+    fn default() -> Self {
+        Self::Fixed(chrono::Duration::zero())
+    }
+}
+
+impl FormValueRepresentation for EntryDuration {
+    fn into_form_value_string(self) -> String {
+        match self {
+            EntryDuration::Fixed(duration) => NiceDurationHours(duration).into_form_value_string(),
+            EntryDuration::OpenEnded => Self::OPEN_ENDED_KEYWORD.to_owned(),
+        }
+    }
+}
+
+impl ValidateFromFormInput for EntryDuration {
+    fn from_form_value(value: &str) -> Result<Self, String> {
+        if value.trim().eq_ignore_ascii_case(Self::OPEN_ENDED_KEYWORD) {
+            return Ok(Self::OpenEnded);
+        }
+        NiceDurationHours::from_form_value(value)
+            .map(|d| Self::Fixed(d.into_inner()))
+            .map_err(|_| "Keine gültige Dauer (oder „offen“ für bis Tagesende)".to_owned())
+    }
+}
+
 #[derive(Default, Debug)]
 pub struct SimpleTimestampMicroseconds(pub chrono::DateTime<chrono::Utc>);
 
@@ -274,12 +340,12 @@ impl ValidateFromFormInput for SimpleTimestampMicroseconds {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Default, Debug, PartialEq)]
 pub struct DateTimeLocal(pub chrono::NaiveDateTime);
 
 impl FormValueRepresentation for DateTimeLocal {
     fn into_form_value_string(self) -> String {
-        self.0.to_string()
+        self.0.format("%Y-%m-%dT%H:%M:%S").to_string()
     }
 }
 
@@ -295,6 +361,51 @@ impl ValidateFromFormInput for DateTimeLocal {
     }
 }
 
+/// List of entry links, represented as one link per line in the form: The URL, optionally followed
+/// by whitespace and the link's label.
+#[derive(Debug, PartialEq, Default)]
+pub struct EntryLinkList(pub Vec<EntryLink>);
+
+impl FormValueRepresentation for EntryLinkList {
+    fn into_form_value_string(self) -> String {
+        self.0
+            .into_iter()
+            .map(|link| {
+                if link.label.is_empty() {
+                    link.url
+                } else {
+                    format!("{} {}", link.url, link.label)
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl ValidateFromFormInput for EntryLinkList {
+    fn from_form_value(value: &'_ str) -> Result<Self, String> {
+        value
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let (url, label) = line
+                    .split_once(char::is_whitespace)
+                    .map(|(url, label)| (url, label.trim()))
+                    .unwrap_or((line, ""));
+                if !EntryLink::is_valid_url(url) {
+                    return Err(format!("„{}“ ist keine gültige http(s)-URL", url));
+                }
+                Ok(EntryLink {
+                    label: label.to_owned(),
+                    url: url.to_owned(),
+                })
+            })
+            .collect::<Result<Vec<EntryLink>, String>>()
+            .map(EntryLinkList)
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct ColorHexString(pub String);
 
@@ -333,6 +444,47 @@ impl ValidateFromFormInput for ColorHexString {
     }
 }
 
+/// Maximum length of a category icon in Unicode scalar values. Emoji sequences consist of
+/// multiple scalar values, e.g. skin tone modifiers and zero-width joiners.
+const MAX_ICON_LENGTH: usize = 8;
+
+/// The icon of a category: Either empty (no icon) or a single emoji or symbol, which is displayed
+/// as text. Words (ASCII letters) are rejected, since they are most likely a typo or a misguided
+/// attempt to name an icon.
+#[derive(Debug, PartialEq, Default)]
+pub struct IconName(pub String);
+
+impl IconName {
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+
+    pub fn is_valid(value: &str) -> bool {
+        value.is_empty()
+            || (value.chars().count() <= MAX_ICON_LENGTH
+                && !value
+                    .chars()
+                    .any(|c| c.is_ascii_alphabetic() || c.is_whitespace()))
+    }
+}
+
+impl FormValueRepresentation for IconName {
+    fn into_form_value_string(self) -> String {
+        self.0
+    }
+}
+
+impl ValidateFromFormInput for IconName {
+    fn from_form_value(value: &'_ str) -> Result<Self, String> {
+        let value = value.trim();
+        if Self::is_valid(value) {
+            Ok(Self(value.to_owned()))
+        } else {
+            Err("Unbekanntes Icon. Bitte ein einzelnes Emoji oder Symbol eingeben oder das Feld leer lassen.".to_owned())
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Default)]
 pub struct Int32(pub i32);
 
@@ -505,6 +657,31 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_bounded_string() {
+        assert_eq!(
+            BoundedString::<5>::from_form_value("Hallo")
+                .unwrap()
+                .into_inner(),
+            "Hallo"
+        );
+        assert_eq!(
+            BoundedString::<5>::from_form_value("Hallo!").unwrap_err(),
+            "Höchstens 5 Zeichen erlaubt"
+        );
+        assert!(BoundedString::<5>::from_form_value("").is_err());
+        // Characters are counted, not bytes
+        assert_eq!(
+            BoundedString::<5>::from_form_value("Üben🐉")
+                .unwrap()
+                .into_inner(),
+            "Üben🐉"
+        );
+        assert!(BoundedString::<5>::from_form_value("Übenä🐉").is_err());
+        assert!(BoundedString::<3>::from_form_value("🐉🐉🐉").is_ok());
+        assert!(BoundedString::<3>::from_form_value("🐉🐉🐉🐉").is_err());
+    }
+
     #[test]
     fn test_nice_duration_hours_from_string() {
         assert_eq!(
@@ -629,6 +806,35 @@ mod tests {
         assert!(NiceDurationHours::from_form_value("abc5:5").is_err());
     }
 
+    #[test]
+    fn test_entry_duration() {
+        assert_eq!(
+            EntryDuration::from_form_value("offen"),
+            Ok(EntryDuration::OpenEnded)
+        );
+        assert_eq!(
+            EntryDuration::from_form_value(" Offen "),
+            Ok(EntryDuration::OpenEnded)
+        );
+        assert_eq!(
+            EntryDuration::from_form_value("2:30"),
+            Ok(EntryDuration::Fixed(
+                chrono::Duration::hours(2) + chrono::Duration::minutes(30)
+            ))
+        );
+        assert!(EntryDuration::from_form_value("").is_err());
+        assert!(EntryDuration::from_form_value("offen 2").is_err());
+        assert_eq!(
+            EntryDuration::from_form_value(&EntryDuration::OpenEnded.into_form_value_string()),
+            Ok(EntryDuration::OpenEnded)
+        );
+        let val = chrono::Duration::hours(1) + chrono::Duration::minutes(45);
+        assert_eq!(
+            EntryDuration::from_form_value(&EntryDuration::Fixed(val).into_form_value_string()),
+            Ok(EntryDuration::Fixed(val))
+        );
+    }
+
     #[test]
     fn test_color_hex_string() {
         assert_eq!(
@@ -643,4 +849,50 @@ mod tests {
         assert!(ColorHexString::from_form_value("1ff2").is_err());
         assert!(ColorHexString::from_form_value("0011gg").is_err());
     }
+
+    #[test]
+    fn test_icon_name() {
+        assert_eq!(
+            IconName::from_form_value("⚽"),
+            Ok(IconName("⚽".to_owned()))
+        );
+        assert_eq!(
+            IconName::from_form_value(" 🧑🏽‍🚀 "),
+            Ok(IconName("🧑🏽‍🚀".to_owned()))
+        );
+        assert_eq!(
+            IconName::from_form_value("1️⃣"),
+            Ok(IconName("1️⃣".to_owned()))
+        );
+        assert_eq!(IconName::from_form_value(""), Ok(IconName("".to_owned())));
+        assert!(IconName::from_form_value("ball").is_err());
+        assert!(IconName::from_form_value("bi-star").is_err());
+        assert!(IconName::from_form_value("⚽ 🏐").is_err());
+        assert!(IconName::from_form_value("⚽⚽⚽⚽⚽⚽⚽⚽⚽").is_err());
+    }
+
+    #[test]
+    fn test_entry_link_list() {
+        assert_eq!(
+            EntryLinkList::from_form_value(
+                "https://example.com/slides.pdf Folien zum Vortrag\n\n  http://example.com  \n"
+            ),
+            Ok(EntryLinkList(vec![
+                EntryLink {
+                    label: "Folien zum Vortrag".to_owned(),
+                    url: "https://example.com/slides.pdf".to_owned(),
+                },
+                EntryLink {
+                    label: "".to_owned(),
+                    url: "http://example.com".to_owned(),
+                },
+            ]))
+        );
+        assert_eq!(
+            EntryLinkList::from_form_value(""),
+            Ok(EntryLinkList(vec![]))
+        );
+        assert!(EntryLinkList::from_form_value("javascript:alert(1) Klick mich").is_err());
+        assert!(EntryLinkList::from_form_value("example.com").is_err());
+    }
 }
@@ -1,4 +1,5 @@
-use crate::data_store::EntryFilter;
+use crate::data_store::{EntryFilter, EntryOrder};
+use chrono::{Datelike, Weekday};
 use serde::de::{Error, Unexpected};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
@@ -54,7 +55,10 @@ fn serialize_optional_comma_separated_list_of_uuids<S: Serializer>(
     }
 }
 
-fn deserialize_bool_from_string<'de, D>(deserializer: D) -> Result<bool, D::Error>
+/// Helper function for deserializing a string from a query parameter to a `bool` within a struct
+/// by deriving `serde::Deserialize` with `#[serde(deserialize_with=...)]`. Only "true"
+/// (case-insensitive) is interpreted as true.
+pub fn deserialize_bool_from_string<'de, D>(deserializer: D) -> Result<bool, D::Error>
 where
     D: Deserializer<'de>,
 {
@@ -62,6 +66,16 @@ where
     Ok(value.to_lowercase() == "true")
 }
 
+/// Deserialize an optional boolean value from a string. See [deserialize_bool_from_string].
+pub fn deserialize_optional_bool_from_string<'de, D>(
+    deserializer: D,
+) -> Result<Option<bool>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_bool_from_string(deserializer).map(Some)
+}
+
 fn not(v: &bool) -> bool {
     !v
 }
@@ -115,23 +129,48 @@ pub struct EntryFilterAsQuery {
         deserialize_with = "deserialize_bool_from_string"
     )]
     without_room: bool,
+    /// If false, entries which are only room reservations are omitted. If not given, the
+    /// endpoint-specific default of [EntryFilterAsQuery::into_filter] is used.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_optional_bool_from_string"
+    )]
+    include_reservations: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    q: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sort: Option<EntryOrder>,
 }
 
-impl From<EntryFilterAsQuery> for EntryFilter {
-    fn from(value: EntryFilterAsQuery) -> Self {
+impl EntryFilterAsQuery {
+    /// Convert the query data into an [EntryFilter], using the given default for
+    /// `include_reservations`, if it has not been specified in the query.
+    pub fn into_filter(self, include_reservations_by_default: bool) -> EntryFilter {
         EntryFilter {
-            after: value.after,
-            after_inclusive: !value.after_exclusive,
-            before: value.before,
-            before_inclusive: value.before_inclusive,
-            include_previous_date_matches: value.match_previous_dates,
-            categories: value.categories,
-            rooms: value.rooms,
-            no_room: value.without_room,
+            after: self.after,
+            after_inclusive: !self.after_exclusive,
+            before: self.before,
+            before_inclusive: self.before_inclusive,
+            include_previous_date_matches: self.match_previous_dates,
+            categories: self.categories,
+            rooms: self.rooms,
+            no_room: self.without_room,
+            search: self.q.filter(|q| !q.is_empty()),
+            exclude_reservations: !self
+                .include_reservations
+                .unwrap_or(include_reservations_by_default),
+            order_by: self.sort.unwrap_or_default(),
         }
     }
 }
 
+impl From<EntryFilterAsQuery> for EntryFilter {
+    fn from(value: EntryFilterAsQuery) -> Self {
+        value.into_filter(true)
+    }
+}
+
 /// Create the orga_comment from the submitter_comment of a new submitted entry
 pub fn format_submitter_comment(submitter_comment: &str) -> String {
     if submitter_comment.is_empty() {
@@ -147,3 +186,33 @@ pub fn format_submitter_comment(submitter_comment: &str) -> String {
         format!("Kommentar der einreichenden Person: {}", submitter_comment)
     }
 }
+
+/// Maximum length of entry titles (in Unicode characters), which is enforced when creating or
+/// changing entries via the web UI or the REST API. Longer titles break the print layout.
+pub const MAX_ENTRY_TITLE_LENGTH: usize = 100;
+
+/// Convert a date to the (german) name of its weekday
+pub fn weekday(date: &chrono::NaiveDate) -> &'static str {
+    match date.weekday() {
+        Weekday::Mon => "Montag",
+        Weekday::Tue => "Dienstag",
+        Weekday::Wed => "Mittwoch",
+        Weekday::Thu => "Donnerstag",
+        Weekday::Fri => "Freitag",
+        Weekday::Sat => "Samstag",
+        Weekday::Sun => "Sonntag",
+    }
+}
+
+/// Convert a date to a short version of the (german) name of its weekday
+pub fn weekday_short(date: &chrono::NaiveDate) -> &'static str {
+    match date.weekday() {
+        Weekday::Mon => "Mo",
+        Weekday::Tue => "Di",
+        Weekday::Wed => "Mi",
+        Weekday::Thu => "Do",
+        Weekday::Fri => "Fr",
+        Weekday::Sat => "Sa",
+        Weekday::Sun => "So",
+    }
+}
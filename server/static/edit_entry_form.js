@@ -3,6 +3,10 @@ function initializeEditEntryForm(effectiveBeginOfDayMilliseconds, rooms, concurr
     const beginInput = document.getElementById("beginInput");
     const durationInput = document.getElementById("durationInput");
     const roomsInput = document.getElementById("roomsInput");
+    const endTimeInput = document.getElementById("end_timeInput");
+    const beginDatetimeInput = document.getElementById("begin_datetimeInput");
+    const endDatetimeInput = document.getElementById("end_datetimeInput");
+    const endModeRadios = document.querySelectorAll('input[name="end_mode"]');
 
     const calendarDateInfoElement = createCalendarDateInfoElement(beginInput);
     const endTimeInfoElement = createEndTimeInfoElement(durationInput.parentElement);
@@ -19,27 +23,123 @@ function initializeEditEntryForm(effectiveBeginOfDayMilliseconds, rooms, concurr
         true
     );
 
+    function isEndTimeMode() {
+        return document.querySelector('input[name="end_mode"]:checked')?.value === "end_time";
+    }
+
+    function isTimestampsMode() {
+        return document.querySelector('input[name="end_mode"]:checked')?.value === "timestamps";
+    }
+
+    // In end time mode, the (hidden) duration input is kept in sync with the end time, such that the end time info
+    // and the concurrent entries fetcher can work with the duration as usual.
+    function updateDurationFromEndTime() {
+        const naiveBeginDate = readDateSelect(daySelect);
+        const naiveBeginTime = readNaiveTimeInput(beginInput);
+        const naiveEndTime = readNaiveTimeInput(endTimeInput);
+        if (!isEndTimeMode() || naiveBeginDate === null || naiveBeginTime === null || naiveEndTime === null) {
+            return;
+        }
+        const begin = timestamp_from_effective_date_and_time(naiveBeginDate, naiveBeginTime, effectiveBeginOfDayMilliseconds);
+        const end = timestamp_from_effective_date_and_time(naiveBeginDate, naiveEndTime, effectiveBeginOfDayMilliseconds);
+        if (end >= begin) {
+            durationInput.value = formatNiceDurationHours(end - begin);
+            durationInput.dispatchEvent(new Event("input"));
+        }
+    }
+
+    // In timestamps mode, the (hidden) day, begin and duration inputs are kept in sync with the begin and end
+    // timestamps for the same reason.
+    function updateFromTimestamps() {
+        const begin = readNaiveDatetimeInput(beginDatetimeInput);
+        const end = readNaiveDatetimeInput(endDatetimeInput);
+        if (!isTimestampsMode() || begin === null) {
+            return;
+        }
+        const effectiveDate = new Date(begin.getTime() - effectiveBeginOfDayMilliseconds)
+            .toISOString().substring(0, 10);
+        if ([...daySelect.options].some((option) => option.value === effectiveDate)) {
+            daySelect.value = effectiveDate;
+        }
+        beginInput.value = formatTime(begin);
+        if (end !== null && end >= begin) {
+            durationInput.value = formatNiceDurationHours(end - begin);
+        }
+        daySelect.dispatchEvent(new Event("input"));
+    }
+
+    // When switching to timestamps mode, the begin and end timestamps are initialized from the other inputs
+    function fillTimestampsFromOtherInputs() {
+        const naiveBeginDate = readDateSelect(daySelect);
+        const naiveBeginTime = readNaiveTimeInput(beginInput);
+        const durationMilliseconds = readNiceDurationInput(durationInput);
+        if (naiveBeginDate === null || naiveBeginTime === null || durationMilliseconds === null) {
+            return;
+        }
+        const begin = timestamp_from_effective_date_and_time(naiveBeginDate, naiveBeginTime, effectiveBeginOfDayMilliseconds);
+        const end = new Date(begin.getTime() + durationMilliseconds);
+        beginDatetimeInput.value = formatNaiveDatetime(begin);
+        endDatetimeInput.value = formatNaiveDatetime(end);
+    }
+
+    function updateEndMode() {
+        const endTimeMode = isEndTimeMode();
+        const timestampsMode = isTimestampsMode();
+        document.getElementById("dayColumn").classList.toggle("d-none", timestampsMode);
+        document.getElementById("beginColumn").classList.toggle("d-none", timestampsMode);
+        document.getElementById("durationColumn").classList.toggle("d-none", endTimeMode || timestampsMode);
+        document.getElementById("endTimeColumn").classList.toggle("d-none", !endTimeMode);
+        document.getElementById("beginDatetimeColumn").classList.toggle("d-none", !timestampsMode);
+        document.getElementById("endDatetimeColumn").classList.toggle("d-none", !timestampsMode);
+        if (endTimeMode && endTimeInput.value === "") {
+            const naiveBeginDate = readDateSelect(daySelect);
+            const naiveBeginTime = readNaiveTimeInput(beginInput);
+            const durationMilliseconds = readNiceDurationInput(durationInput);
+            if (naiveBeginDate !== null && naiveBeginTime !== null && durationMilliseconds !== null) {
+                const end = new Date(
+                    timestamp_from_effective_date_and_time(naiveBeginDate, naiveBeginTime, effectiveBeginOfDayMilliseconds)
+                        .getTime() + durationMilliseconds);
+                endTimeInput.value = formatTime(end);
+            }
+        }
+        updateDurationFromEndTime();
+        updateFromTimestamps();
+    }
+
+    endModeRadios.forEach((radio) => radio.addEventListener("change", () => {
+        if (isTimestampsMode()) {
+            fillTimestampsFromOtherInputs();
+        }
+        updateEndMode();
+    }));
+    endTimeInput.addEventListener("input", updateDurationFromEndTime);
+    beginDatetimeInput.addEventListener("input", updateFromTimestamps);
+    endDatetimeInput.addEventListener("input", updateFromTimestamps);
+    updateEndMode();
+
     daySelect.addEventListener("input", () => {
+        updateDurationFromEndTime();
         const naiveBeginDate = readDateSelect(daySelect);
         const naiveBeginTime = readNaiveTimeInput(beginInput);
         const durationMilliseconds = readNiceDurationInput(durationInput);
         updateCalendarDateInfo(calendarDateInfoElement, effectiveBeginOfDayMilliseconds, naiveBeginDate, naiveBeginTime);
-        updateEndTimeInfo(endTimeInfoElement, effectiveBeginOfDayMilliseconds, naiveBeginDate, naiveBeginTime, durationMilliseconds);
+        updateEndTimeInfo(endTimeInfoElement, effectiveBeginOfDayMilliseconds, naiveBeginDate, naiveBeginTime, durationMilliseconds, isOpenEndedDurationInput(durationInput));
         concurrentEntriesFetcher.scheduleFetching();
     });
     beginInput.addEventListener("input", () => {
+        updateDurationFromEndTime();
         const naiveBeginDate = readDateSelect(daySelect);
         const naiveBeginTime = readNaiveTimeInput(beginInput);
         const durationMilliseconds = readNiceDurationInput(durationInput);
         updateCalendarDateInfo(calendarDateInfoElement, effectiveBeginOfDayMilliseconds, naiveBeginDate, naiveBeginTime);
-        updateEndTimeInfo(endTimeInfoElement, effectiveBeginOfDayMilliseconds, naiveBeginDate, naiveBeginTime, durationMilliseconds);
+        updateEndTimeInfo(endTimeInfoElement, effectiveBeginOfDayMilliseconds, naiveBeginDate, naiveBeginTime, durationMilliseconds, isOpenEndedDurationInput(durationInput));
         concurrentEntriesFetcher.scheduleFetching();
     });
     durationInput.addEventListener("input", () => {
         const naiveBeginDate = readDateSelect(daySelect);
         const naiveBeginTime = readNaiveTimeInput(beginInput);
         const durationMilliseconds = readNiceDurationInput(durationInput);
-        updateEndTimeInfo(endTimeInfoElement, effectiveBeginOfDayMilliseconds, naiveBeginDate, naiveBeginTime, durationMilliseconds);
+        updateEndTimeInfo(endTimeInfoElement, effectiveBeginOfDayMilliseconds, naiveBeginDate, naiveBeginTime, durationMilliseconds, isOpenEndedDurationInput(durationInput));
         concurrentEntriesFetcher.scheduleFetching();
     });
     roomsInput.addEventListener("input", () => {
@@ -50,7 +150,7 @@ function initializeEditEntryForm(effectiveBeginOfDayMilliseconds, rooms, concurr
     const naiveBeginTime = readNaiveTimeInput(beginInput);
     const durationMilliseconds = readNiceDurationInput(durationInput);
     updateCalendarDateInfo(calendarDateInfoElement, effectiveBeginOfDayMilliseconds, naiveBeginDate, naiveBeginTime);
-    updateEndTimeInfo(endTimeInfoElement, effectiveBeginOfDayMilliseconds, naiveBeginDate, naiveBeginTime, durationMilliseconds);
+    updateEndTimeInfo(endTimeInfoElement, effectiveBeginOfDayMilliseconds, naiveBeginDate, naiveBeginTime, durationMilliseconds, isOpenEndedDurationInput(durationInput));
     concurrentEntriesFetcher.doFetch();
 
     const orgaCommentInput = document.getElementById("orga_commentInput");
@@ -119,7 +219,11 @@ function createCalendarDateInfoElement(insertAfterElement) {
     return element;
 }
 
-function updateEndTimeInfo(endTimeInfoElement, effectiveBeginOfDayMilliseconds, naiveEffectiveBeginDate, naiveBeginTime, durationMilliseconds) {
+function updateEndTimeInfo(endTimeInfoElement, effectiveBeginOfDayMilliseconds, naiveEffectiveBeginDate, naiveBeginTime, durationMilliseconds, openEnded) {
+    if (openEnded) {
+        endTimeInfoElement.getElementsByTagName("span")[0].innerText = "Tagesende";
+        return;
+    }
     if (naiveEffectiveBeginDate === null || naiveBeginTime === null || durationMilliseconds === null) {
         endTimeInfoElement.getElementsByTagName("span")[0].innerText = "???";
         return;
@@ -10,10 +10,21 @@ function readNaiveTimeInput(beginInput) {
     return isNaN(time) ? null : time;
 }
 
+function readNaiveDatetimeInput(datetimeInput) {
+    // Like in readNaiveTimeInput(), we act like the local date and time is UTC to avoid any conversion.
+    const datetime = new Date(datetimeInput.value + "Z");
+    return datetimeInput.value === "" || isNaN(datetime) ? null : datetime;
+}
+
 function readNiceDurationInput(durationInput) {
     return parseNiceDurationHours(durationInput.value);
 }
 
+function isOpenEndedDurationInput(durationInput) {
+    // Corresponds to EntryDuration::from_form_value() in the Rust code
+    return durationInput.value.trim().toLowerCase() === "offen";
+}
+
 function parseNiceDurationHours(value) {
     // The parsing code corresponds to NiceDurationHours::from_form_value() in the Rust code
     const regex = /^(?:(?<d>\d+)d\s*)?(?<H>\d+(?:[.,]\d{1,7})?)(?::(?<M>\d+(?:[.,]\d{1,5})?)(?::(?<S>\d+(?:[.,]\d{1,3}?)?))?)?$/;
@@ -35,6 +46,13 @@ function parseNiceDurationHours(value) {
         + seconds * MILLISECONDS_PER_SECOND;
 }
 
+function formatNiceDurationHours(milliseconds) {
+    // The format corresponds to NiceDurationHours::into_form_value_string() in the Rust code (without days and seconds)
+    const totalMinutes = Math.round(milliseconds / 60000);
+    return Math.floor(totalMinutes / 60).toString().padStart(2, "0")
+        + ":" + (totalMinutes % 60).toString().padStart(2, "0");
+}
+
 function timestamp_from_effective_date_and_time(naiveDate, naiveTime, effectiveBeginOfDayMilliseconds) {
     let result = new Date(naiveDate.getTime() + naiveTime.getTime());
     let beginIsAfterMidnight = naiveTime.getTime() < effectiveBeginOfDayMilliseconds;
@@ -48,6 +66,11 @@ function formatDate(date) {
     return date.getUTCDate().toString().padStart(2, "0") + "." + (date.getUTCMonth() + 1).toString().padStart(2, "0") + ".";
 }
 
+function formatNaiveDatetime(date) {
+    // Format for datetime-local inputs (without seconds)
+    return date.toISOString().substring(0, 16);
+}
+
 function formatTime(date) {
     return date.getUTCHours().toString().padStart(2, "0")
         + ":" + (date.getUTCMinutes()).toString().padStart(2, "0")
@@ -1,9 +1,10 @@
 import datetime
 import uuid
+import zoneinfo
 
 import pytest
 
-from tests.conftest import ApiClientWrapper
+from tests.conftest import AnnouncementFactory, ApiClientWrapper
 
 
 def test_create_or_update_announcement(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
@@ -30,6 +31,8 @@ def test_create_or_update_announcement(generated_api_client: ApiClientWrapper, r
     announcement.show_with_rooms = False
     announcement.rooms = []
     announcement.show_with_all_rooms = False
+    assert result[0].last_updated is not None
+    announcement.last_updated = result[0].last_updated
     assert result[0] == announcement
 
     announcement.sort_key = 5
@@ -37,6 +40,7 @@ def test_create_or_update_announcement(generated_api_client: ApiClientWrapper, r
     generated_api_client.client.create_or_update_announcement(EVENT_ID, announcement.id, announcement)
 
     result = generated_api_client.client.list_announcements(EVENT_ID)
+    announcement.last_updated = result[0].last_updated
     assert result[0] == announcement
 
 
@@ -70,6 +74,43 @@ def test_change_announcement(generated_api_client: ApiClientWrapper, reset_datab
     assert result[0].text == "Now, the Announcement text is shorter."
 
 
+def test_change_announcement_concurrent_edit(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "orga")
+    announcement = kueaplan_api_client.Announcement(
+        id=str(uuid.uuid4()),
+        announcementType="info",
+        text="This is an important Announcement!",
+        sort_key=42,
+    )
+    generated_api_client.client.create_or_update_announcement(EVENT_ID, announcement.id, announcement)
+    last_updated = generated_api_client.client.list_announcements(EVENT_ID)[0].last_updated
+
+    # Changing with the current lastUpdated value succeeds
+    generated_api_client.client.change_announcement(
+        EVENT_ID,
+        announcement.id,
+        generated_api_client.module.AnnouncementPatch(sort_key=5, expected_last_update=last_updated),
+    )
+    result = generated_api_client.client.list_announcements(EVENT_ID)
+    assert result[0].sort_key == 5
+    assert result[0].last_updated != last_updated
+
+    # Changing again with the outdated lastUpdated value is rejected
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.change_announcement(
+            EVENT_ID,
+            announcement.id,
+            generated_api_client.module.AnnouncementPatch(sort_key=7, expected_last_update=last_updated),
+        )
+    assert excinfo.value.data.http_code == 409
+    assert excinfo.value.data.error_code == "CONCURRENT_EDIT_CONFLICT"
+    result = generated_api_client.client.list_announcements(EVENT_ID)
+    assert result[0].sort_key == 5
+
+
 def test_create_or_update_announcement_errors(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
     import kueaplan_api_client
 
@@ -86,6 +127,8 @@ def test_create_or_update_announcement_errors(generated_api_client: ApiClientWra
         generated_api_client.client.create_or_update_announcement(event_id, announcement.id, announcement)
     assert "not authorized" in str(excinfo.value.data.message)
     assert excinfo.value.data.http_code == 403
+    assert excinfo.value.data.error_code == "PERMISSION_DENIED"
+    assert excinfo.value.data.required_roles == ["orga", "admin"]
 
     generated_api_client.login(event_id, "orga")
     # Wrong id
@@ -93,6 +136,7 @@ def test_create_or_update_announcement_errors(generated_api_client: ApiClientWra
         generated_api_client.client.create_or_update_announcement(event_id, str(uuid.uuid4()), announcement)
     assert "Entity id" in str(excinfo.value.data.message)
     assert excinfo.value.data.http_code == 422
+    assert excinfo.value.data.error_code == "ENTITY_ID_MISMATCH"
 
     # Non-existing event
     with pytest.raises(kueaplan_api_client.ApiException):
@@ -200,3 +244,135 @@ def test_create_or_update_announcement_reference_errors_other_event(
         generated_api_client.client.create_or_update_announcement(event_id, announcement.id, announcement)
     assert "does not belong to event" in str(excinfo.value.data.message)
     assert excinfo.value.data.http_code == 422
+
+
+def test_list_announcements_visible_at(
+    generated_api_client: ApiClientWrapper, announcement_factory: AnnouncementFactory, reset_database: None
+) -> None:
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+
+    day = datetime.date(2025, 1, 3)
+    all_day = announcement_factory.create(event_id, "Ganztägig", begin_date=day, end_date=day)
+    evening = announcement_factory.create(
+        event_id, "Abends", begin_date=day, end_date=day, begin_time="18:00:00", end_time="22:00:00"
+    )
+    night = announcement_factory.create(
+        event_id, "Nachts", begin_date=day, end_date=day, begin_time="22:00:00", end_time="02:00:00"
+    )
+
+    def visible_at(timestamp: datetime.datetime) -> list[str]:
+        result = generated_api_client.client.list_announcements(event_id, visible_at=timestamp)
+        return sorted(a.id for a in result)
+
+    # Event timezone is Europe/Berlin (UTC+1 in January)
+    # within the evening window
+    assert visible_at(datetime.datetime(2025, 1, 3, 17, 0, tzinfo=datetime.UTC)) == sorted([all_day.id, evening.id])
+    # before the evening window
+    assert visible_at(datetime.datetime(2025, 1, 3, 16, 59, tzinfo=datetime.UTC)) == [all_day.id]
+    # end_time is exclusive
+    assert visible_at(datetime.datetime(2025, 1, 3, 21, 0, tzinfo=datetime.UTC)) == sorted([all_day.id, night.id])
+    # after midnight (local time), but still at the same effective date
+    assert visible_at(datetime.datetime(2025, 1, 4, 0, 30, tzinfo=datetime.UTC)) == sorted([all_day.id, night.id])
+    # after the night window
+    assert visible_at(datetime.datetime(2025, 1, 4, 1, 0, tzinfo=datetime.UTC)) == [all_day.id]
+    # on another effective date
+    assert visible_at(datetime.datetime(2025, 1, 4, 17, 0, tzinfo=datetime.UTC)) == []
+
+    # without the parameter, all announcements are listed
+    result = generated_api_client.client.list_announcements(event_id)
+    assert len(result) == 3
+
+
+def test_list_active_announcements(
+    generated_api_client: ApiClientWrapper, announcement_factory: AnnouncementFactory, reset_database: None
+) -> None:
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+
+    # The event took place in January 2025, so the current programme day is its last day (2025-01-06)
+    in_range = announcement_factory.create(
+        event_id, "Bitte aufräumen", begin_date=datetime.date(2025, 1, 5), end_date=None, sort_key=20
+    )
+    in_range_first = announcement_factory.create(
+        event_id, "Abreise", begin_date=datetime.date(2025, 1, 6), end_date=datetime.date(2025, 1, 6), sort_key=10
+    )
+    announcement_factory.create(
+        event_id, "Willkommen", begin_date=datetime.date(2025, 1, 1), end_date=datetime.date(2025, 1, 2)
+    )
+
+    generated_api_client.login(event_id, "user")
+    result = generated_api_client.client.list_active_announcements(event_id)
+    assert [a.id for a in result] == [in_range_first.id, in_range.id]
+
+
+def test_list_active_announcements_time_window(
+    generated_api_client: ApiClientWrapper, announcement_factory: AnnouncementFactory, reset_database: None
+) -> None:
+    event_id = 1
+    generated_api_client.login(event_id, "admin")
+    # Move the event to the current date, such that the current time is taken into account
+    local_now = datetime.datetime.now(zoneinfo.ZoneInfo("Europe/Berlin"))
+    event_info = generated_api_client.client.get_extended_event_info(event_id)
+    event_info.begin_date = local_now.date() - datetime.timedelta(days=1)
+    event_info.end_date = local_now.date() + datetime.timedelta(days=1)
+    generated_api_client.client.update_extended_event(event_id, event_info)
+
+    def local_time_in(hours: int) -> str:
+        return (local_now + datetime.timedelta(hours=hours)).strftime("%H:%M:%S")
+
+    current = announcement_factory.create(event_id, "Jetzt", begin_time=local_time_in(-1), end_time=local_time_in(1))
+    announcement_factory.create(event_id, "Später", begin_time=local_time_in(2), end_time=local_time_in(3))
+
+    generated_api_client.login(event_id, "user")
+    result = generated_api_client.client.list_active_announcements(event_id)
+    assert [a.id for a in result] == [current.id]
+
+
+def test_announcement_equal_begin_and_end_time(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    announcement = kueaplan_api_client.Announcement(
+        id=str(uuid.uuid4()),
+        announcementType="info",
+        text="Test",
+        show_with_days=True,
+        begin_time="18:00:00",
+        end_time="18:00:00",
+        sort_key=100,
+    )
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.create_or_update_announcement(event_id, announcement.id, announcement)
+    assert excinfo.value.status == 422
+
+
+def test_get_announcement(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    announcement = kueaplan_api_client.Announcement(
+        id=str(uuid.uuid4()),
+        announcementType="warning",
+        text="Test",
+        show_with_days=False,
+        show_with_categories=True,
+        categories=["019774dc-81c4-7862-a9ba-63de3d726010"],
+        sort_key=100,
+    )
+    generated_api_client.client.create_or_update_announcement(event_id, announcement.id, announcement)
+
+    generated_api_client.clear_login()
+    generated_api_client.login(event_id, "user")
+    result = generated_api_client.client.get_announcement(event_id, announcement.id)
+    assert result.id == announcement.id
+    assert result.text == "Test"
+    assert result.show_with_categories
+    assert result.categories == ["019774dc-81c4-7862-a9ba-63de3d726010"]
+    assert result.rooms == []
+
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.get_announcement(event_id, str(uuid.uuid4()))
+    assert excinfo.value.status == 404
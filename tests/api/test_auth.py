@@ -1,3 +1,4 @@
+import datetime
 import uuid
 
 import pytest
@@ -40,6 +41,18 @@ def test_check_all_events_authorization(generated_api_client: ApiClientWrapper,
     assert set(auth.role for auth in result.events[0].authorization) == {"orga", "participant"}
 
 
+def test_check_all_events_authorization_multiple_events(
+    generated_api_client: ApiClientWrapper, reset_database: None
+) -> None:
+    generated_api_client.login(1, "user")
+    generated_api_client.login(1, "admin")
+    generated_api_client.login(2, "orga")
+
+    result = generated_api_client.client.check_all_events_authorization()
+    roles_by_event = {info.event_id: {auth.role for auth in info.authorization} for info in result.events}
+    assert roles_by_event == {1: {"participant", "admin"}, 2: {"orga"}}
+
+
 def test_drop_access_role(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
     import kueaplan_api_client
 
@@ -64,3 +77,52 @@ def test_drop_access_role(generated_api_client: ApiClientWrapper, reset_database
             event_id=EVENT_ID, room_id=room_id, room=kueaplan_api_client.Room(id=room_id, title="Test", description="")
         )
     assert excinfo.value.status == 403
+
+
+def test_create_share_link(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "orga")
+    entry = kueaplan_api_client.Entry(
+        id=str(uuid.uuid4()),
+        title="Drachenfliegen leicht gemacht",
+        comment="",
+        description="",
+        room=[],
+        begin=datetime.datetime(2025, 1, 6, 12, 0, tzinfo=datetime.UTC).isoformat(),
+        end=datetime.datetime(2025, 1, 6, 13, 30, tzinfo=datetime.UTC).isoformat(),
+        time_comment="",
+        responsible_person="Max Mustermann",
+        is_cancelled=False,
+        category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+        previousDates=[],
+    )
+    generated_api_client.client.create_or_update_entry(EVENT_ID, entry.id, entry)
+    result = generated_api_client.client.create_share_link(EVENT_ID)
+    assert "/events/1/ical?" in result.url
+    assert "token=" in result.url
+
+    generated_api_client.client.api_client.configuration.api_key["sessionTokenAuth"] = result.session_token
+    auth_result = generated_api_client.client.check_authorization(event_id=EVENT_ID)
+    assert [auth.role for auth in auth_result.authorization] == ["participant-sharable"]
+
+    # The shared token should allow to fetch entries
+    assert [e.id for e in generated_api_client.client.list_entries(event_id=EVENT_ID)] == [entry.id]
+    # ... but not to edit them, although the share link has been created by an orga
+    entry.title = "Changed via share link"
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.create_or_update_entry(event_id=EVENT_ID, entry_id=entry.id, entry=entry)
+    assert excinfo.value.status == 403
+
+
+def test_create_share_link_without_sharable_passphrase(
+    generated_api_client: ApiClientWrapper, reset_database: None
+) -> None:
+    import kueaplan_api_client
+
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "admin")
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.create_share_link(EVENT_ID)
+    assert excinfo.value.status == 403
@@ -0,0 +1,117 @@
+import json
+import urllib.error
+import urllib.parse
+import urllib.request
+import uuid
+
+import pytest
+
+from tests.conftest import ApiClientWrapper
+
+
+def _get_ical(event_id: int, token: str | None) -> int:
+    query = f"?{urllib.parse.urlencode({'token': token})}" if token is not None else ""
+    try:
+        with urllib.request.urlopen(f"http://localhost:9099/events/{event_id}/ical{query}") as response:
+            assert response.headers["Content-Type"].startswith("text/calendar")
+            return response.status
+    except urllib.error.HTTPError as e:
+        return e.code
+
+
+def test_ical_feed_token(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "admin")
+    passphrase = kueaplan_api_client.Passphrase(
+        passphrase="calendar-feed-passphrase",
+        derivable_from_passphrase=None,
+        role="participant",
+    )
+    generated_api_client.client.create_passphrase(EVENT_ID, passphrase)
+    passphrase_id = next(
+        p.id
+        for p in generated_api_client.client.list_passphrases(EVENT_ID)
+        if p.passphrase is not None and p.passphrase.endswith("phrase")
+    )
+    auth_response = generated_api_client.client.authorize(
+        EVENT_ID, kueaplan_api_client.AuthorizeRequest(passphrase="calendar-feed-passphrase")
+    )
+
+    # Valid token
+    assert _get_ical(EVENT_ID, auth_response.session_token) == 200
+
+    # Revoked token, i.e. the passphrase has been deleted
+    generated_api_client.client.delete_passphrase(EVENT_ID, passphrase_id)
+    assert _get_ical(EVENT_ID, auth_response.session_token) == 403
+
+
+@pytest.mark.parametrize("token", [None, "", "not-a-valid-token"])
+def test_ical_feed_invalid_token(token: str | None, reset_database: None) -> None:
+    assert _get_ical(1, token) == 403
+
+
+def test_ical_feed_without_reservations(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    EVENT_ID = 1
+    auth_response = generated_api_client.client.authorize(
+        EVENT_ID, kueaplan_api_client.AuthorizeRequest(passphrase="orga")
+    )
+    generated_api_client.login(EVENT_ID, "orga")
+    entry = kueaplan_api_client.Entry(
+        id=str(uuid.uuid4()),
+        title="Aufbau Bühne",
+        room=[],
+        begin="2025-01-06T12:00:00+00:00",
+        end="2025-01-06T13:30:00+00:00",
+        category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+        is_room_reservation=True,
+        previousDates=[],
+    )
+    generated_api_client.client.create_or_update_entry(EVENT_ID, entry.id, entry)
+
+    def get_ical_content(**params: str) -> str:
+        query = urllib.parse.urlencode({"token": auth_response.session_token, **params})
+        with urllib.request.urlopen(f"http://localhost:9099/events/{EVENT_ID}/ical?{query}") as response:
+            return response.read().decode()
+
+    # Room reservations are excluded from the calendar feed by default
+    assert entry.id not in get_ical_content()
+    assert entry.id in get_ical_content(include_reservations="true")
+
+
+def _get_entries(event_id: int, session_token: str, accept: str) -> tuple[str, list[str], str]:
+    request = urllib.request.Request(
+        f"http://localhost:9099/api/v1/events/{event_id}/entries",
+        headers={"X-SESSION-TOKEN": session_token, "Accept": accept},
+    )
+    with urllib.request.urlopen(request) as response:
+        vary = [value.strip().lower() for header in response.headers.get_all("Vary", []) for value in header.split(",")]
+        return response.headers["Content-Type"], vary, response.read().decode()
+
+
+@pytest.mark.parametrize(
+    "accept, expected_content_type",
+    [
+        ("application/json", "application/json"),
+        ("*/*", "application/json"),
+        ("text/calendar", "text/calendar"),
+        ("application/json;q=0.5, text/calendar", "text/calendar"),
+    ],
+)
+def test_entries_content_negotiation(
+    accept: str, expected_content_type: str, generated_api_client: ApiClientWrapper, reset_database: None
+) -> None:
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "user")
+    session_token = generated_api_client.client.api_client.configuration.api_key["sessionTokenAuth"]
+
+    content_type, vary, body = _get_entries(EVENT_ID, session_token, accept)
+    assert content_type.startswith(expected_content_type)
+    assert "accept" in vary
+    if expected_content_type == "text/calendar":
+        assert body.startswith("BEGIN:VCALENDAR")
+    else:
+        assert isinstance(json.loads(body), list)
@@ -14,6 +14,98 @@ def test_list_categories(generated_api_client: ApiClientWrapper, reset_database:
     assert result[0].title == "Default"
 
 
+def test_list_categories_with_entry_counts(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    # given: a new (empty) category and two entries and one draft entry in the default category
+    category = kueaplan_api_client.Category(
+        id=str(uuid.uuid4()),
+        title="Test Category",
+        icon="💡",
+        color="ffaa00",
+        sort_key=42,
+    )
+    generated_api_client.client.create_or_update_category(event_id, category.id, category)
+    for title, state in (
+        ("Drachenfliegen leicht gemacht", "published"),
+        ("Drachenbau für Fortgeschrittene", "published"),
+        ("Drachenflug-Wettbewerb", "draft"),
+    ):
+        entry = kueaplan_api_client.Entry(
+            id=str(uuid.uuid4()),
+            title=title,
+            state=state,
+            begin=datetime.datetime(2025, 1, 6, 12, 0, tzinfo=datetime.UTC).isoformat(),
+            end=datetime.datetime(2025, 1, 6, 13, 30, tzinfo=datetime.UTC).isoformat(),
+            room=[],
+            responsible_person="Max Mustermann",
+            category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+            previousDates=[],
+        )
+        generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+
+    # when: we query the categories with entry counts
+    generated_api_client.login(event_id, "user")
+    result = generated_api_client.client.list_categories_with_entry_counts(event_id)
+
+    # then: both categories are listed in the usual order with the correct number of entries
+    assert len(result) == 2
+    assert result[0].category.title == "Default"
+    assert result[0].entry_count == 2
+    assert result[1].category == category
+    assert result[1].entry_count == 0
+
+    # and: orgas also see the draft entry in the count
+    generated_api_client.login(event_id, "orga")
+    result = generated_api_client.client.list_categories_with_entry_counts(event_id)
+    assert result[0].entry_count == 3
+
+
+def test_reorder_categories(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    default_category_id = "019774dc-81c4-7862-a9ba-63de3d726010"  # Default category from minimal.sql
+    generated_api_client.login(event_id, "orga")
+    category = kueaplan_api_client.Category(
+        id=str(uuid.uuid4()),
+        title="Test Category",
+        icon="💡",
+        color="ffaa00",
+        sort_key=42,
+    )
+    generated_api_client.client.create_or_update_category(event_id, category.id, category)
+
+    generated_api_client.client.reorder_categories(event_id, [category.id, default_category_id])
+
+    result = generated_api_client.client.list_categories(event_id)
+    assert [c.id for c in result] == [category.id, default_category_id]
+    assert [c.sort_key for c in result] == [0, 1]
+
+
+def test_reorder_categories_errors(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    default_category_id = "019774dc-81c4-7862-a9ba-63de3d726010"  # Default category from minimal.sql
+    generated_api_client.login(event_id, "user")
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.reorder_categories(event_id, [default_category_id])
+    assert excinfo.value.status == 403
+
+    generated_api_client.login(event_id, "orga")
+    for invalid_ids in (
+        [],
+        [default_category_id, default_category_id],
+        [default_category_id, "019cba98-3963-7477-a04a-0ac6bfaff6bf"],  # Default category of the other event
+    ):
+        with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+            generated_api_client.client.reorder_categories(event_id, invalid_ids)
+        assert excinfo.value.status == 422
+
+
 def test_create_or_update_category(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
     import kueaplan_api_client
 
@@ -40,6 +132,34 @@ def test_create_or_update_category(generated_api_client: ApiClientWrapper, reset
     assert result[1] == category
 
 
+def test_create_category_with_duplicate_sort_key(
+    generated_api_client: ApiClientWrapper, reset_database: None
+) -> None:
+    import kueaplan_api_client
+
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "orga")
+    category1 = kueaplan_api_client.Category(
+        id=str(uuid.uuid4()),
+        title="Test Category",
+        icon="💡",
+        color="ffaa00",
+        sort_key=42,
+    )
+    generated_api_client.client.create_or_update_category(EVENT_ID, category1.id, category1)
+    category2 = category1.model_copy(update={"id": str(uuid.uuid4()), "title": "Other Category"})
+    generated_api_client.client.create_or_update_category(EVENT_ID, category2.id, category2)
+
+    result = generated_api_client.client.list_categories(EVENT_ID)
+    sort_keys = {str(c.id): c.sort_key for c in result}
+    assert sort_keys == {"019774dc-81c4-7862-a9ba-63de3d726010": 0, category1.id: 42, category2.id: 43}
+
+    # Updating a category keeps its own sort key
+    category1.title = "Test"
+    generated_api_client.client.create_or_update_category(EVENT_ID, category1.id, category1)
+    result = generated_api_client.client.list_categories(EVENT_ID)
+    assert [c.sort_key for c in result] == [0, 42, 43]
+
 def test_create_or_update_category_errors(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
     import kueaplan_api_client
 
@@ -205,6 +325,139 @@ def test_delete_category_errors(generated_api_client: ApiClientWrapper, reset_da
     assert excinfo.value.data.http_code == 403
 
 
+def test_list_and_restore_deleted_categories(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    category = kueaplan_api_client.Category(
+        id=str(uuid.uuid4()),
+        title="Test Category",
+        icon="💡",
+        color="ffaa00",
+        sort_key=42,
+    )
+    generated_api_client.client.create_or_update_category(event_id, category.id, category)
+
+    result = generated_api_client.client.list_deleted_categories(event_id)
+    assert len(result) == 0
+
+    generated_api_client.client.delete_category(event_id, category.id)
+
+    result = generated_api_client.client.list_deleted_categories(event_id)
+    assert len(result) == 1
+    assert result[0].id == uuid.UUID(category.id)
+    assert result[0].title == "Test Category"
+
+    generated_api_client.client.restore_category(event_id, category.id)
+
+    result = generated_api_client.client.list_deleted_categories(event_id)
+    assert len(result) == 0
+    result = generated_api_client.client.list_categories(event_id)
+    assert len(result) == 2
+    assert any(c.id == uuid.UUID(category.id) and c.title == "Test Category" for c in result)
+
+
+def test_restore_category_errors(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+
+    # Non-existing category
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.restore_category(event_id, "11111111-2222-3333-4444-555555555555")
+    assert "not exist" in str(excinfo.value.data.message)
+    assert excinfo.value.data.http_code == 404
+
+    # Category is not deleted (default category from minimal.sql)
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.restore_category(event_id, "019774dc-81c4-7862-a9ba-63de3d726010")
+    assert excinfo.value.data.http_code == 409
+
+    # Category of another event
+    generated_api_client.login(2, "orga")
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.restore_category(2, "019774dc-81c4-7862-a9ba-63de3d726010")
+    assert excinfo.value.data.http_code == 404
+
+    # Unauthorized
+    generated_api_client.clear_login()
+    generated_api_client.login(event_id, "user")
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.list_deleted_categories(event_id)
+    assert "not authorized" in str(excinfo.value.data.message)
+    assert excinfo.value.data.http_code == 403
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.restore_category(event_id, "019774dc-81c4-7862-a9ba-63de3d726010")
+    assert "not authorized" in str(excinfo.value.data.message)
+    assert excinfo.value.data.http_code == 403
+
+
+
+def test_cancel_and_uncancel_category_entries(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    # given: a second category with two entries and one entry in the default category
+    category = kueaplan_api_client.Category(
+        id=str(uuid.uuid4()),
+        title="Drachen",
+        icon="🪁",
+        color="ffaa00",
+        sort_key=42,
+    )
+    generated_api_client.client.create_or_update_category(event_id, category.id, category)
+    entry_ids = {}
+    for title, category_id in [
+        ("Drachenfliegen leicht gemacht", category.id),
+        ("Drachenbau für Fortgeschrittene", category.id),
+        ("Schokoladenverkostung", "019774dc-81c4-7862-a9ba-63de3d726010"),  # Default category from minimal.sql
+    ]:
+        entry = kueaplan_api_client.Entry(
+            id=str(uuid.uuid4()),
+            title=title,
+            begin=datetime.datetime(2025, 1, 6, 12, 0, tzinfo=datetime.UTC).isoformat(),
+            end=datetime.datetime(2025, 1, 6, 13, 30, tzinfo=datetime.UTC).isoformat(),
+            room=[],
+            responsible_person="Max Mustermann",
+            category=category_id,
+            previousDates=[],
+        )
+        generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+        entry_ids[title] = entry.id
+
+    def cancelled_titles() -> set[str]:
+        return {
+            title
+            for title, entry_id in entry_ids.items()
+            if generated_api_client.client.get_entry(event_id, entry_id).is_cancelled
+        }
+
+    # when: we cancel the category's entries
+    result = generated_api_client.client.cancel_category_entries(event_id, category.id)
+    # then: only the entries of this category are cancelled
+    assert result.entry_count == 2
+    assert cancelled_titles() == {"Drachenfliegen leicht gemacht", "Drachenbau für Fortgeschrittene"}
+
+    # Cancelling again does not change anything
+    assert generated_api_client.client.cancel_category_entries(event_id, category.id).entry_count == 0
+
+    generated_api_client.client.uncancel_category_entries(event_id, category.id)
+    assert cancelled_titles() == set()
+
+    # Non-existent category
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.cancel_category_entries(event_id, str(uuid.uuid4()))
+    assert excinfo.value.status == 404
+
+    # Participants are not allowed to cancel entries
+    generated_api_client.login(event_id, "user")
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.cancel_category_entries(event_id, category.id)
+    assert excinfo.value.status == 403
+
 def test_category_id_conflicts(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
     import kueaplan_api_client
 
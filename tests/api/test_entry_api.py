@@ -1,4 +1,8 @@
 import datetime
+import json
+import urllib.error
+import urllib.parse
+import urllib.request
 import uuid
 
 import pytest
@@ -57,12 +61,670 @@ Für das Material müssen von jedem Teilnehmer an der KüA **5€** bezahlt werd
     entry.end = datetime.datetime(2025, 1, 6, 13, 30, 45, tzinfo=datetime.UTC).isoformat()
     generated_api_client.client.create_or_update_entry(EVENT_ID, entry.id, entry)
 
-    # OrgaComment is not included when fetching single entry as orga
+    # OrgaComment and role of the last editor are included when fetching single entry as orga
     entry.orga_comment = ""
+    entry.last_updated_by_role = "orga"
     result = generated_api_client.client.get_entry(EVENT_ID, entry.id)
     assert result == entry
 
 
+def test_create_and_update_tentative_entry(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "orga")
+    entry = kueaplan_api_client.Entry(
+        id=str(uuid.uuid4()),
+        title="Drachenfliegen leicht gemacht",
+        room=[],
+        begin=datetime.datetime(2025, 1, 6, 12, 0, tzinfo=datetime.UTC).isoformat(),
+        end=datetime.datetime(2025, 1, 6, 13, 30, tzinfo=datetime.UTC).isoformat(),
+        responsible_person="Max Mustermann",
+        is_tentative=True,
+        category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+        previousDates=[],
+    )
+    generated_api_client.client.create_or_update_entry(EVENT_ID, entry.id, entry)
+
+    result = generated_api_client.client.get_entry(EVENT_ID, entry.id)
+    assert result.is_tentative is True
+
+    entry.is_tentative = None
+    generated_api_client.client.create_or_update_entry(EVENT_ID, entry.id, entry)
+
+    result = generated_api_client.client.get_entry(EVENT_ID, entry.id)
+    assert not result.is_tentative
+
+
+def test_orga_only_entry(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "orga")
+    public_entry = kueaplan_api_client.Entry(
+        id=str(uuid.uuid4()),
+        title="Drachenfliegen leicht gemacht",
+        room=[],
+        begin=datetime.datetime(2025, 1, 6, 12, 0, tzinfo=datetime.UTC).isoformat(),
+        end=datetime.datetime(2025, 1, 6, 13, 30, tzinfo=datetime.UTC).isoformat(),
+        responsible_person="Max Mustermann",
+        category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+        previousDates=[],
+    )
+    generated_api_client.client.create_or_update_entry(EVENT_ID, public_entry.id, public_entry)
+    orga_entry = public_entry.model_copy(update={"id": str(uuid.uuid4()), "title": "Orga-Treffen", "orga_only": True})
+    generated_api_client.client.create_or_update_entry(EVENT_ID, orga_entry.id, orga_entry)
+
+    assert generated_api_client.client.get_entry(EVENT_ID, orga_entry.id).orga_only is True
+    assert {e.id for e in generated_api_client.client.list_entries(EVENT_ID)} == {public_entry.id, orga_entry.id}
+    assert "Orga-Treffen" in _get_ical_feed(EVENT_ID, generated_api_client)
+
+    generated_api_client.login(EVENT_ID, "user")
+    assert [e.id for e in generated_api_client.client.list_entries(EVENT_ID)] == [public_entry.id]
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.get_entry(EVENT_ID, orga_entry.id)
+    assert excinfo.value.status == 404
+    ical = _get_ical_feed(EVENT_ID, generated_api_client)
+    assert "Drachenfliegen" in ical
+    assert "Orga-Treffen" not in ical
+
+
+def _get_ical_feed(event_id: int, generated_api_client: ApiClientWrapper) -> str:
+    token = generated_api_client.client.api_client.configuration.api_key["sessionTokenAuth"]
+    query = urllib.parse.urlencode({"token": token})
+    with urllib.request.urlopen(f"http://localhost:9099/events/{event_id}/ical?{query}") as response:
+        return response.read().decode()
+
+
+def test_create_or_update_entry_outside_event_range(
+    generated_api_client: ApiClientWrapper, reset_database: None
+) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    entry = kueaplan_api_client.Entry(
+        id=str(uuid.uuid4()),
+        title="Drachenfliegen leicht gemacht",
+        room=[],
+        # The event ends on 2025-01-06, the effective day ends at 05:30 local time on the next day
+        begin=datetime.datetime(2025, 1, 6, 22, 0, tzinfo=datetime.UTC).isoformat(),
+        end=datetime.datetime(2025, 1, 7, 4, 30, tzinfo=datetime.UTC).isoformat(),
+        responsible_person="Max Mustermann",
+        category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+        previousDates=[],
+    )
+    # In range: ending exactly at the end of the last effective day
+    generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+
+    # Out of range
+    entry.end = datetime.datetime(2025, 1, 7, 5, 0, tzinfo=datetime.UTC).isoformat()
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+    assert "date range" in str(excinfo.value.data.message)
+    assert excinfo.value.data.http_code == 422
+    entry.begin = datetime.datetime(2024, 12, 31, 12, 0, tzinfo=datetime.UTC).isoformat()
+    entry.end = datetime.datetime(2024, 12, 31, 13, 0, tzinfo=datetime.UTC).isoformat()
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+    assert excinfo.value.data.http_code == 422
+
+    # Explicit override
+    generated_api_client.client.create_or_update_entry(event_id, entry.id, entry, allow_outside_range=True)
+    result = generated_api_client.client.get_entry(event_id, entry.id)
+    assert result.begin == datetime.datetime(2024, 12, 31, 12, 0, tzinfo=datetime.UTC)
+
+
+
+def test_record_previous_date_on_update(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    begin = datetime.datetime(2025, 1, 3, 12, 0, tzinfo=datetime.UTC)
+    end = datetime.datetime(2025, 1, 3, 13, 30, tzinfo=datetime.UTC)
+    entry = kueaplan_api_client.Entry(
+        id=str(uuid.uuid4()),
+        title="Drachenfliegen leicht gemacht",
+        room=[],
+        begin=begin.isoformat(),
+        end=end.isoformat(),
+        responsible_person="Max Mustermann",
+        category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+        previousDates=[],
+    )
+    # Creating a new entry does not record a previous date
+    generated_api_client.client.create_or_update_entry(event_id, entry.id, entry, record_previous_date=True)
+    assert generated_api_client.client.get_entry(event_id, entry.id).previous_dates == []
+
+    # Unchanged times and rooms
+    entry.title = "Drachenfliegen für Fortgeschrittene"
+    generated_api_client.client.create_or_update_entry(event_id, entry.id, entry, record_previous_date=True)
+    result = generated_api_client.client.get_entry(event_id, entry.id)
+    assert result.title == "Drachenfliegen für Fortgeschrittene"
+    assert result.previous_dates == []
+
+    # Changed times
+    entry.begin = (begin + datetime.timedelta(hours=2)).isoformat()
+    entry.end = (end + datetime.timedelta(hours=2)).isoformat()
+    generated_api_client.client.create_or_update_entry(
+        event_id, entry.id, entry, record_previous_date=True, previous_date_comment="Wetterbedingt verschoben"
+    )
+    result = generated_api_client.client.get_entry(event_id, entry.id)
+    assert len(result.previous_dates) == 1
+    assert result.previous_dates[0].begin == begin
+    assert result.previous_dates[0].end == end
+    assert result.previous_dates[0].room == []
+    assert result.previous_dates[0].comment == "Wetterbedingt verschoben"
+
+
+def test_entry_changes(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    rooms = [kueaplan_api_client.Room(id=str(uuid.uuid4()), title=title, description="") for title in ("A", "B")]
+    for room in rooms:
+        generated_api_client.client.create_or_update_room(event_id, room.id, room)
+    begin = datetime.datetime(2025, 1, 3, 12, 0, tzinfo=datetime.UTC)
+    end = datetime.datetime(2025, 1, 3, 13, 30, tzinfo=datetime.UTC)
+    entry = kueaplan_api_client.Entry(
+        id=str(uuid.uuid4()),
+        title="Drachenfliegen leicht gemacht",
+        room=[rooms[0].id],
+        begin=begin.isoformat(),
+        end=end.isoformat(),
+        responsible_person="Max Mustermann",
+        category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+        previousDates=[],
+    )
+    generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+    # No previous dates: no changes
+    assert generated_api_client.client.get_entry_changes(event_id, entry.id) is None
+
+    # Time-only change
+    entry.begin = (begin + datetime.timedelta(minutes=30)).isoformat()
+    entry.end = (end + datetime.timedelta(minutes=45)).isoformat()
+    generated_api_client.client.create_or_update_entry(event_id, entry.id, entry, record_previous_date=True)
+    result = generated_api_client.client.get_entry_changes(event_id, entry.id)
+    assert result.begin_shift_minutes == 30
+    assert result.end_shift_minutes == 45
+    assert result.added_rooms == []
+    assert result.removed_rooms == []
+
+    # Room-only change, compared to the new most recent previous date
+    entry.previous_dates = generated_api_client.client.get_entry(event_id, entry.id).previous_dates
+    entry.room = [rooms[1].id]
+    generated_api_client.client.create_or_update_entry(event_id, entry.id, entry, record_previous_date=True)
+    result = generated_api_client.client.get_entry_changes(event_id, entry.id)
+    assert result.begin_shift_minutes == 0
+    assert result.end_shift_minutes == 0
+    assert result.added_rooms == [rooms[1].id]
+    assert result.removed_rooms == [rooms[0].id]
+
+    # Moving the entry to an earlier time and then changing the room again: The most recent previous
+    # date is the one created last, not the one with the latest begin
+    for new_begin, new_room in ((begin - datetime.timedelta(minutes=60), rooms[1]), (None, rooms[0])):
+        entry.previous_dates = generated_api_client.client.get_entry(event_id, entry.id).previous_dates
+        if new_begin is not None:
+            entry.begin = new_begin.isoformat()
+        entry.room = [new_room.id]
+        generated_api_client.client.create_or_update_entry(event_id, entry.id, entry, record_previous_date=True)
+    result = generated_api_client.client.get_entry_changes(event_id, entry.id)
+    assert result.begin_shift_minutes == 0
+    assert result.added_rooms == [rooms[0].id]
+    assert result.removed_rooms == [rooms[1].id]
+
+    # A previous date added by the client is the most recent one, even if its id is not a UUIDv7
+    entry.previous_dates = generated_api_client.client.get_entry(event_id, entry.id).previous_dates + [
+        kueaplan_api_client.PreviousDate(
+            id="00000000-0000-4000-8000-000000000001",
+            begin=begin.isoformat(),
+            end=end.isoformat(),
+            room=[rooms[0].id],
+        )
+    ]
+    generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+    result = generated_api_client.client.get_entry_changes(event_id, entry.id)
+    assert result.begin_shift_minutes == -60
+    assert result.added_rooms == []
+    assert result.removed_rooms == []
+
+def test_entry_color_override(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    entry = kueaplan_api_client.Entry(
+        id=str(uuid.uuid4()),
+        title="Drachenfliegen leicht gemacht",
+        room=[],
+        begin=datetime.datetime(2025, 1, 6, 12, 0, tzinfo=datetime.UTC).isoformat(),
+        end=datetime.datetime(2025, 1, 6, 13, 30, tzinfo=datetime.UTC).isoformat(),
+        responsible_person="Max Mustermann",
+        category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+        color_override="ff8800",
+        previousDates=[],
+    )
+    generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+
+    result = generated_api_client.client.get_entry(event_id, entry.id)
+    assert result.color_override == "ff8800"
+
+    # Omitting the color override resets the entry to the category's color
+    entry.color_override = None
+    generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+    result = generated_api_client.client.get_entry(event_id, entry.id)
+    assert result.color_override is None
+
+    # Invalid color codes are rejected
+    for invalid_color in ("#ff8800", "f80", "orange"):
+        data = entry.to_dict()
+        data["colorOverride"] = invalid_color
+        assert _send_raw_json(generated_api_client, "PUT", f"/events/{event_id}/entries/{entry.id}", data) == 400
+    assert generated_api_client.client.get_entry(event_id, entry.id).color_override is None
+
+
+def test_entry_links(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    links = [
+        kueaplan_api_client.EntryLink(label="Folien", url="https://example.com/slides.pdf"),
+        kueaplan_api_client.EntryLink(label="", url="http://example.com/material"),
+    ]
+    entry = kueaplan_api_client.Entry(
+        id=str(uuid.uuid4()),
+        title="Drachenfliegen leicht gemacht",
+        room=[],
+        begin=datetime.datetime(2025, 1, 6, 12, 0, tzinfo=datetime.UTC).isoformat(),
+        end=datetime.datetime(2025, 1, 6, 13, 30, tzinfo=datetime.UTC).isoformat(),
+        category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+        links=links,
+        previousDates=[],
+    )
+    generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+
+    result = generated_api_client.client.get_entry(event_id, entry.id)
+    assert result.links == links
+
+    # Only http(s) URLs are allowed
+    entry.links = [kueaplan_api_client.EntryLink(label="Böse", url="javascript:alert(1)")]
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+    assert excinfo.value.data.http_code == 422
+    result = generated_api_client.client.get_entry(event_id, entry.id)
+    assert result.links == links
+
+
+def test_list_entries_text_search(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    entry = kueaplan_api_client.Entry(
+        id=str(uuid.uuid4()),
+        title="Drachenfliegen leicht gemacht",
+        description="Wir bauen Drachen aus Bambus und Seidenpapier.",
+        room=[],
+        begin=datetime.datetime(2025, 1, 6, 12, 0, tzinfo=datetime.UTC).isoformat(),
+        end=datetime.datetime(2025, 1, 6, 13, 30, tzinfo=datetime.UTC).isoformat(),
+        responsible_person="Max Mustermann",
+        category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+        previousDates=[],
+    )
+    generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+
+    # Search term only contained in description; matching is case-insensitive
+    result = generated_api_client.client.list_entries(event_id, q="bambus")
+    assert [e.id for e in result] == [entry.id]
+
+    result = generated_api_client.client.list_entries(event_id, q="Schach")
+    assert result == []
+
+
+def test_list_entries_without_reservations(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    entry = kueaplan_api_client.Entry(
+        id=str(uuid.uuid4()),
+        title="Aufbau Bühne",
+        room=[],
+        begin=datetime.datetime(2025, 1, 6, 12, 0, tzinfo=datetime.UTC).isoformat(),
+        end=datetime.datetime(2025, 1, 6, 13, 30, tzinfo=datetime.UTC).isoformat(),
+        category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+        is_room_reservation=True,
+        previousDates=[],
+    )
+    generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+    all_entry_ids = [e.id for e in generated_api_client.client.list_entries(event_id)]
+    assert entry.id in all_entry_ids
+
+    result = generated_api_client.client.list_entries(event_id, include_reservations=True)
+    assert [e.id for e in result] == all_entry_ids
+
+    result = generated_api_client.client.list_entries(event_id, include_reservations=False)
+    assert [e.id for e in result] == [entry_id for entry_id in all_entry_ids if entry_id != entry.id]
+
+
+def test_list_entries_sorted(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    rooms = [
+        kueaplan_api_client.Room(id=str(uuid.uuid4()), title=title, description="") for title in ("Zeltplatz", "aula")
+    ]
+    for room in rooms:
+        generated_api_client.client.create_or_update_room(event_id, room.id, room)
+    entries = [
+        kueaplan_api_client.Entry(
+            id=str(uuid.uuid4()),
+            title=title,
+            room=room_ids,
+            begin=datetime.datetime(2025, 1, 6, hour, 0, tzinfo=datetime.UTC).isoformat(),
+            end=datetime.datetime(2025, 1, 6, hour + 1, 0, tzinfo=datetime.UTC).isoformat(),
+            category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+            previousDates=[],
+        )
+        for title, hour, room_ids in (
+            ("banane", 10, [rooms[0].id]),
+            ("Apfel", 12, []),
+            ("Clementine", 11, [rooms[0].id, rooms[1].id]),
+        )
+    ]
+    for entry in entries:
+        generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+
+    result = generated_api_client.client.list_entries(event_id)
+    assert [e.title for e in result] == ["banane", "Clementine", "Apfel"]
+    result = generated_api_client.client.list_entries(event_id, sort="begin")
+    assert [e.title for e in result] == ["banane", "Clementine", "Apfel"]
+    # Sorting by title is case-insensitive
+    result = generated_api_client.client.list_entries(event_id, sort="title")
+    assert [e.title for e in result] == ["Apfel", "banane", "Clementine"]
+    # Sorting by the alphabetically first room; entries without room are sorted last
+    result = generated_api_client.client.list_entries(event_id, sort="room")
+    assert [e.title for e in result] == ["Clementine", "banane", "Apfel"]
+
+
+def test_list_entries_with_schedule_sections(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    # Default time schedule from minimal.sql: Morgens until 12:00, Mittags until 18:00, Abends (local time)
+    for title, begin in (
+        ("Frühsport", datetime.datetime(2025, 1, 3, 7, 0, tzinfo=datetime.UTC)),
+        ("Mittagsschlaf", datetime.datetime(2025, 1, 3, 11, 0, tzinfo=datetime.UTC)),
+        ("Nachtwanderung", datetime.datetime(2025, 1, 3, 23, 30, tzinfo=datetime.UTC)),
+    ):
+        entry = kueaplan_api_client.Entry(
+            id=str(uuid.uuid4()),
+            title=title,
+            room=[],
+            begin=begin.isoformat(),
+            end=(begin + datetime.timedelta(hours=1)).isoformat(),
+            category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+            previousDates=[],
+        )
+        generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+
+    result = generated_api_client.client.list_entries_with_schedule_sections(event_id)
+    assert [(e.entry.title, e.schedule_section) for e in result] == [
+        ("Frühsport", "Morgens"),
+        ("Mittagsschlaf", "Mittags"),
+        # 00:30 local time still belongs to the last section of the previous (effective) day
+        ("Nachtwanderung", "Abends"),
+    ]
+
+
+def test_list_current_entries(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    entry = kueaplan_api_client.Entry(
+        id=str(uuid.uuid4()),
+        title="Drachenfliegen leicht gemacht",
+        room=[],
+        begin=datetime.datetime(2025, 1, 6, 12, 0, tzinfo=datetime.UTC).isoformat(),
+        end=datetime.datetime(2025, 1, 6, 13, 30, tzinfo=datetime.UTC).isoformat(),
+        responsible_person="Max Mustermann",
+        category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+        previousDates=[],
+    )
+    generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+
+    # The event is in the past, so there's nothing running at the moment
+    result = generated_api_client.client.list_current_entries(event_id)
+    assert result == []
+
+
+def test_list_responsible_persons(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    for responsible_person in ["Max Mustermann", "Erika Mustermann", "Max Mustermann", "", "Anna", "Erika Mustermann"]:
+        entry = kueaplan_api_client.Entry(
+            id=str(uuid.uuid4()),
+            title="Drachenfliegen leicht gemacht",
+            room=[],
+            begin=datetime.datetime(2025, 1, 5, 12, 0, tzinfo=datetime.UTC).isoformat(),
+            end=datetime.datetime(2025, 1, 5, 13, 30, tzinfo=datetime.UTC).isoformat(),
+            responsible_person=responsible_person,
+            category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+            previousDates=[],
+        )
+        generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+
+    # Deduplicated, ordered by frequency, then alphabetically, without empty values
+    result = generated_api_client.client.list_responsible_persons(event_id)
+    assert result == ["Erika Mustermann", "Max Mustermann", "Anna"]
+
+
+def test_reassign_responsible_person(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    entries = {}
+    for responsible_person in ["Max Mustermann", "max mustermann", "Max Mustermann", "Max Mustermann & Anna", "Anna"]:
+        entry = kueaplan_api_client.Entry(
+            id=str(uuid.uuid4()),
+            title="Drachenfliegen leicht gemacht",
+            room=[],
+            begin=datetime.datetime(2025, 1, 5, 12, 0, tzinfo=datetime.UTC).isoformat(),
+            end=datetime.datetime(2025, 1, 5, 13, 30, tzinfo=datetime.UTC).isoformat(),
+            responsible_person=responsible_person,
+            category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+            previousDates=[],
+        )
+        generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+        entries[entry.id] = responsible_person
+    # Deleted entries are not changed
+    deleted_entry_id = next(iter(entries))
+    generated_api_client.client.delete_entry(event_id, deleted_entry_id)
+
+    result = generated_api_client.client.reassign_responsible_person(
+        event_id, kueaplan_api_client.ResponsiblePersonReassignment(var_from="Max Mustermann", to="Erika Mustermann")
+    )
+    assert result.entry_count == 1
+
+    for entry_id, responsible_person in entries.items():
+        if entry_id == deleted_entry_id:
+            continue
+        entry = generated_api_client.client.get_entry(event_id, entry_id)
+        expected = "Erika Mustermann" if responsible_person == "Max Mustermann" else responsible_person
+        assert entry.responsible_person == expected
+
+    # Nothing left to reassign
+    result = generated_api_client.client.reassign_responsible_person(
+        event_id, kueaplan_api_client.ResponsiblePersonReassignment(var_from="Max Mustermann", to="Erika Mustermann")
+    )
+    assert result.entry_count == 0
+
+    # Participants are not allowed to reassign entries
+    generated_api_client.clear_login()
+    generated_api_client.login(event_id, "user")
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.reassign_responsible_person(
+            event_id, kueaplan_api_client.ResponsiblePersonReassignment(var_from="Anna", to="Erika Mustermann")
+        )
+    assert excinfo.value.status == 403
+
+
+def test_bulk_set_entry_category(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    default_category_id = "019774dc-81c4-7862-a9ba-63de3d726010"  # Default category from minimal.sql
+    generated_api_client.login(event_id, "orga")
+    category = kueaplan_api_client.Category(
+        id=str(uuid.uuid4()),
+        title="Test Category",
+        icon="💡",
+        color="ffaa00",
+        sort_key=42,
+    )
+    generated_api_client.client.create_or_update_category(event_id, category.id, category)
+    entry_ids = []
+    for _ in range(4):
+        entry = kueaplan_api_client.Entry(
+            id=str(uuid.uuid4()),
+            title="Drachenfliegen leicht gemacht",
+            room=[],
+            begin=datetime.datetime(2025, 1, 5, 12, 0, tzinfo=datetime.UTC).isoformat(),
+            end=datetime.datetime(2025, 1, 5, 13, 30, tzinfo=datetime.UTC).isoformat(),
+            responsible_person="Max Mustermann",
+            category=default_category_id,
+            previousDates=[],
+        )
+        generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+        entry_ids.append(entry.id)
+    # Deleted entries are not changed
+    generated_api_client.client.delete_entry(event_id, entry_ids[3])
+
+    # A single unknown entry id rejects the whole request
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.bulk_set_entry_category(
+            event_id,
+            kueaplan_api_client.EntryCategoryBulkUpdate(
+                entry_ids=[entry_ids[0], str(uuid.uuid4()), entry_ids[1]], category=category.id
+            ),
+        )
+    assert excinfo.value.status == 422
+    for entry_id in entry_ids[:3]:
+        assert generated_api_client.client.get_entry(event_id, entry_id).category == default_category_id
+
+    # Categories of other events are rejected
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.bulk_set_entry_category(
+            event_id,
+            kueaplan_api_client.EntryCategoryBulkUpdate(
+                entry_ids=entry_ids[:2], category="019cba98-3963-7477-a04a-0ac6bfaff6bf"
+            ),
+        )
+    assert excinfo.value.status == 422
+
+    result = generated_api_client.client.bulk_set_entry_category(
+        event_id, kueaplan_api_client.EntryCategoryBulkUpdate(entry_ids=entry_ids, category=category.id)
+    )
+    assert result.entry_count == 3
+    for entry_id in entry_ids[:3]:
+        assert generated_api_client.client.get_entry(event_id, entry_id).category == category.id
+
+    # Entries already in the category are not counted
+    result = generated_api_client.client.bulk_set_entry_category(
+        event_id, kueaplan_api_client.EntryCategoryBulkUpdate(entry_ids=entry_ids[:2], category=category.id)
+    )
+    assert result.entry_count == 0
+
+    # Participants are not allowed to change entries
+    generated_api_client.clear_login()
+    generated_api_client.login(event_id, "user")
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.bulk_set_entry_category(
+            event_id,
+            kueaplan_api_client.EntryCategoryBulkUpdate(entry_ids=entry_ids[:1], category=default_category_id),
+        )
+    assert excinfo.value.status == 403
+
+
+def test_shift_entries(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    test_room = kueaplan_api_client.Room(id=str(uuid.uuid4()), title="Test Room", description="")
+    generated_api_client.client.create_or_update_room(event_id, test_room.id, test_room)
+    begins = [
+        datetime.datetime(2025, 1, 5, 10, 0, tzinfo=datetime.UTC),
+        datetime.datetime(2025, 1, 5, 15, 0, tzinfo=datetime.UTC),
+        datetime.datetime(2025, 1, 6, 10, 0, tzinfo=datetime.UTC),
+    ]
+    entry_ids = []
+    for begin in begins:
+        entry = kueaplan_api_client.Entry(
+            id=str(uuid.uuid4()),
+            title="Drachenfliegen leicht gemacht",
+            room=[test_room.id],
+            begin=begin.isoformat(),
+            end=(begin + datetime.timedelta(hours=1)).isoformat(),
+            responsible_person="Max Mustermann",
+            category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+            previousDates=[],
+        )
+        generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+        entry_ids.append(entry.id)
+
+    # Shift the entries of 2025-01-05 by 30 minutes
+    result = generated_api_client.client.shift_entries(
+        event_id,
+        kueaplan_api_client.EntryShift(offset_minutes=30, comment="Verspätung"),
+        after=datetime.datetime(2025, 1, 5, 0, 0, tzinfo=datetime.UTC),
+        before=datetime.datetime(2025, 1, 6, 0, 0, tzinfo=datetime.UTC),
+    )
+    assert result.entry_count == 2
+
+    for entry_id, begin in zip(entry_ids[:2], begins[:2]):
+        entry = generated_api_client.client.get_entry(event_id, entry_id)
+        assert entry.begin == begin + datetime.timedelta(minutes=30)
+        assert entry.end == begin + datetime.timedelta(minutes=90)
+        assert len(entry.previous_dates) == 1
+        previous_date = entry.previous_dates[0]
+        assert previous_date.begin == begin
+        assert previous_date.end == begin + datetime.timedelta(hours=1)
+        assert previous_date.room == [test_room.id]
+        assert previous_date.comment == "Verspätung"
+    entry = generated_api_client.client.get_entry(event_id, entry_ids[2])
+    assert entry.begin == begins[2]
+    assert entry.previous_dates == []
+
+    # Shifting without any filter requires explicit confirmation
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.shift_entries(event_id, kueaplan_api_client.EntryShift(offset_minutes=30))
+    assert excinfo.value.status == 400
+    assert excinfo.value.data.error_code == "INVALID_QUERY"
+    entry = generated_api_client.client.get_entry(event_id, entry_ids[2])
+    assert entry.begin == begins[2]
+    result = generated_api_client.client.shift_entries(
+        event_id, kueaplan_api_client.EntryShift(offset_minutes=-10, all=True)
+    )
+    assert result.entry_count == 3
+
+    # Participants are not allowed to shift entries
+    generated_api_client.clear_login()
+    generated_api_client.login(event_id, "user")
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.shift_entries(event_id, kueaplan_api_client.EntryShift(offset_minutes=30, all=True))
+    assert excinfo.value.status == 403
+
+
 def test_create_or_update_entry_simple_errors(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
     import kueaplan_api_client
 
@@ -96,6 +758,78 @@ def test_create_or_update_entry_simple_errors(generated_api_client: ApiClientWra
         generated_api_client.client.create_or_update_entry(42, entry.id, entry)
 
 
+def _send_raw_json(generated_api_client: ApiClientWrapper, method: str, path: str, data: dict) -> int:
+    """Send a request without the generated client's input validation, to test the server-side validation"""
+    request = urllib.request.Request(
+        f"http://localhost:9099/api/v1{path}",
+        data=json.dumps(data).encode(),
+        method=method,
+        headers={
+            "X-SESSION-TOKEN": generated_api_client.client.api_client.configuration.api_key["sessionTokenAuth"],
+            "Content-Type": "application/json",
+        },
+    )
+    try:
+        with urllib.request.urlopen(request) as response:
+            return response.status
+    except urllib.error.HTTPError as e:
+        return e.code
+
+
+def test_entry_title_length(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    # 100 characters, but more than 100 bytes in UTF-8
+    max_title = "ä" * 99 + "🐉"
+    entry = kueaplan_api_client.Entry(
+        id=str(uuid.uuid4()),
+        title=max_title,
+        room=[],
+        begin=datetime.datetime(2025, 1, 6, 12, 0, tzinfo=datetime.UTC).isoformat(),
+        end=datetime.datetime(2025, 1, 6, 13, 30, tzinfo=datetime.UTC).isoformat(),
+        responsible_person="Max Mustermann",
+        category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+        previousDates=[],
+    )
+    generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+    assert generated_api_client.client.get_entry(event_id, entry.id).title == max_title
+
+    entry_data = entry.to_dict()
+    entry_data["title"] = max_title + "x"
+    assert _send_raw_json(generated_api_client, "PUT", f"/events/{event_id}/entries/{entry.id}", entry_data) == 422
+    assert (
+        _send_raw_json(generated_api_client, "PATCH", f"/events/{event_id}/entries/{entry.id}", {"title": "x" * 101})
+        == 422
+    )
+    assert generated_api_client.client.get_entry(event_id, entry.id).title == max_title
+
+
+def test_request_body_too_large(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    entry_id = str(uuid.uuid4())
+    entry_data = {
+        "id": entry_id,
+        "title": "Riesige Beschreibung",
+        # Exceeds the default MAX_JSON_BODY_BYTES of 2 MiB
+        "description": "x" * (2 * 1024 * 1024 + 1),
+        "room": [],
+        "begin": datetime.datetime(2025, 1, 6, 12, 0, tzinfo=datetime.UTC).isoformat(),
+        "end": datetime.datetime(2025, 1, 6, 13, 30, tzinfo=datetime.UTC).isoformat(),
+        "category": "019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+        "previousDates": [],
+    }
+    assert _send_raw_json(generated_api_client, "PUT", f"/events/{event_id}/entries/{entry_id}", entry_data) == 413
+
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.get_entry(event_id, entry_id)
+    assert excinfo.value.data.http_code == 404
+
+
 def test_create_or_update_entry_reference_errors(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
     import kueaplan_api_client
 
@@ -304,8 +1038,9 @@ def test_change_entry_simple(generated_api_client: ApiClientWrapper, reset_datab
             end=datetime.datetime(2025, 1, 6, 13, 30, 45, tzinfo=datetime.UTC).isoformat(),
         ),
     )
-    # OrgaComment is not included when fetching single entry as orga
+    # OrgaComment and role of the last editor are included when fetching single entry as orga
     entry.orga_comment = "Dieser Kommentar ist geheim!"
+    entry.last_updated_by_role = "orga"
     result = generated_api_client.client.get_entry(event_id, entry.id)
     assert result == entry
 
@@ -321,6 +1056,38 @@ def test_change_entry_simple(generated_api_client: ApiClientWrapper, reset_datab
     assert result == entry
 
 
+def test_entry_last_updated_by_role(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "admin")
+    entry = kueaplan_api_client.Entry(
+        id=str(uuid.uuid4()),
+        title="Drachenfliegen leicht gemacht",
+        room=[],
+        begin=datetime.datetime(2025, 1, 6, 12, 0, tzinfo=datetime.UTC).isoformat(),
+        end=datetime.datetime(2025, 1, 6, 13, 30, tzinfo=datetime.UTC).isoformat(),
+        responsible_person="Max Mustermann",
+        category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+        previousDates=[],
+    )
+    generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+    assert generated_api_client.client.get_entry(event_id, entry.id).last_updated_by_role == "admin"
+
+    generated_api_client.clear_login()
+    generated_api_client.login(event_id, "orga")
+    generated_api_client.client.change_entry(
+        event_id, entry.id, kueaplan_api_client.EntryPatch(title="Drachenfliegen für jedermann")
+    )
+    assert generated_api_client.client.get_entry(event_id, entry.id).last_updated_by_role == "orga"
+    assert generated_api_client.client.list_all_entries(event_id)[0].last_updated_by_role == "orga"
+
+    # The role is not visible for participants
+    generated_api_client.clear_login()
+    generated_api_client.login(event_id, "user")
+    assert generated_api_client.client.get_entry(event_id, entry.id).last_updated_by_role is None
+
+
 def test_change_entry_simple_errors(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
     import kueaplan_api_client
 
@@ -480,6 +1247,160 @@ def test_change_entry_reference_errors_other_event(
     assert excinfo.value.data.http_code == 422
 
 
+def test_move_entry(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    other_event_id = 2
+
+    generated_api_client.login(event_id, "orga")
+    generated_api_client.login(other_event_id, "orga")
+    test_room = kueaplan_api_client.Room(
+        id=str(uuid.uuid4()),
+        title="Test Room",
+        description="",
+    )
+    generated_api_client.client.create_or_update_room(event_id, test_room.id, test_room)
+    entry = kueaplan_api_client.Entry(
+        id=str(uuid.uuid4()),
+        title="Drachenfliegen leicht gemacht",
+        room=[test_room.id],
+        begin=datetime.datetime(2025, 1, 6, 12, 0, tzinfo=datetime.UTC).isoformat(),
+        end=datetime.datetime(2025, 1, 6, 13, 30, tzinfo=datetime.UTC).isoformat(),
+        responsible_person="Max Mustermann",
+        category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+        previousDates=[],
+    )
+    generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+    cutoff = datetime.datetime.now(datetime.UTC)
+
+    generated_api_client.client.move_entry(
+        event_id,
+        entry.id,
+        kueaplan_api_client.EntryMove(
+            target_event_id=other_event_id,
+            target_category="019cba98-3963-7477-a04a-0ac6bfaff6bf",  # Default category of The other event
+        ),
+        allow_outside_range=True,
+    )
+
+    assert all(e.id != entry.id for e in generated_api_client.client.list_entries(event_id))
+    result = generated_api_client.client.get_entry(other_event_id, entry.id)
+    assert result.title == entry.title
+    assert result.category == "019cba98-3963-7477-a04a-0ac6bfaff6bf"
+    assert result.room == []
+    # The entry is reported as deleted in the source event's updates
+    updates = generated_api_client.client.get_updates(event_id, since=cutoff)
+    assert updates.deleted_entries == [entry.id]
+    updates = generated_api_client.client.get_updates(other_event_id, since=cutoff)
+    assert [e.id for e in updates.changed_entries] == [entry.id]
+
+
+def test_copy_entry_from_event(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    other_event_id = 2
+
+    generated_api_client.login(event_id, "orga")
+    generated_api_client.login(other_event_id, "orga")
+    test_room = kueaplan_api_client.Room(
+        id=str(uuid.uuid4()),
+        title="Test Room",
+        description="",
+    )
+    generated_api_client.client.create_or_update_room(event_id, test_room.id, test_room)
+    entry = kueaplan_api_client.Entry(
+        id=str(uuid.uuid4()),
+        title="Drachenfliegen leicht gemacht",
+        room=[test_room.id],
+        begin=datetime.datetime(2025, 1, 6, 12, 0, tzinfo=datetime.UTC).isoformat(),
+        end=datetime.datetime(2025, 1, 6, 13, 30, tzinfo=datetime.UTC).isoformat(),
+        responsible_person="Max Mustermann",
+        category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+        previousDates=[],
+    )
+    generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+
+    result = generated_api_client.client.copy_entry_from_event(
+        other_event_id,
+        entry.id,
+        kueaplan_api_client.EntryCopy(
+            source_event_id=event_id,
+            target_category="019cba98-3963-7477-a04a-0ac6bfaff6bf",  # Default category of The other event
+        ),
+    )
+
+    assert result.id != entry.id
+    assert result.title == entry.title
+    assert result.category == "019cba98-3963-7477-a04a-0ac6bfaff6bf"
+    assert result.room == []
+    assert generated_api_client.client.get_entry(other_event_id, result.id).room == []
+    # The source entry is unchanged
+    source_entry = generated_api_client.client.get_entry(event_id, entry.id)
+    assert source_entry.room == [test_room.id]
+    assert source_entry.category == "019774dc-81c4-7862-a9ba-63de3d726010"
+
+
+def test_move_entry_errors(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    other_event_id = 2
+
+    generated_api_client.login(event_id, "orga")
+    entry = kueaplan_api_client.Entry(
+        id=str(uuid.uuid4()),
+        title="Drachenfliegen leicht gemacht",
+        room=[],
+        begin=datetime.datetime(2025, 1, 6, 12, 0, tzinfo=datetime.UTC).isoformat(),
+        end=datetime.datetime(2025, 1, 6, 13, 30, tzinfo=datetime.UTC).isoformat(),
+        responsible_person="Max Mustermann",
+        category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+        previousDates=[],
+    )
+    generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+    move = kueaplan_api_client.EntryMove(
+        target_event_id=other_event_id,
+        target_category="019cba98-3963-7477-a04a-0ac6bfaff6bf",  # Default category of The other event
+    )
+
+    # missing privilege for target event
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.move_entry(event_id, entry.id, move)
+    assert excinfo.value.status == 403
+
+    # entry is outside the date range of the target event
+    generated_api_client.login(other_event_id, "orga")
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.move_entry(event_id, entry.id, move)
+    assert "date range" in str(excinfo.value.data.message)
+    assert excinfo.value.data.http_code == 422
+
+    # category of the source event
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.move_entry(
+            event_id,
+            entry.id,
+            kueaplan_api_client.EntryMove(
+                target_event_id=other_event_id,
+                target_category="019774dc-81c4-7862-a9ba-63de3d726010",
+            ),
+            allow_outside_range=True,
+        )
+    assert "does not belong to event" in str(excinfo.value.data.message)
+    assert excinfo.value.data.http_code == 422
+
+    # not existing entry
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.move_entry(event_id, str(uuid.uuid4()), move)
+    assert excinfo.value.status == 404
+
+    # entry is still in the original event
+    result = generated_api_client.client.get_entry(event_id, entry.id)
+    assert result.category == "019774dc-81c4-7862-a9ba-63de3d726010"
+
+
 def test_create_and_update_previous_date(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
     import kueaplan_api_client
 
@@ -524,6 +1445,114 @@ def test_create_and_update_previous_date(generated_api_client: ApiClientWrapper,
     assert result.previous_dates[0] == previous_date
 
 
+def test_delete_and_restore_previous_date(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "orga")
+    entry = kueaplan_api_client.Entry(
+        id=str(uuid.uuid4()),
+        title="Drachenfliegen leicht gemacht",
+        room=[],
+        begin=datetime.datetime(2025, 1, 6, 12, 0, tzinfo=datetime.UTC).isoformat(),
+        end=datetime.datetime(2025, 1, 6, 13, 30, tzinfo=datetime.UTC).isoformat(),
+        category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+        previousDates=[],
+    )
+    generated_api_client.client.create_or_update_entry(EVENT_ID, entry.id, entry)
+    previous_date = kueaplan_api_client.PreviousDate(
+        id=str(uuid.uuid4()),
+        begin=datetime.datetime(2025, 1, 5, 12, 0, tzinfo=datetime.UTC).isoformat(),
+        end=datetime.datetime(2025, 1, 5, 13, 30, tzinfo=datetime.UTC).isoformat(),
+        room=[],
+        comment="Verschoben wegen Regen",
+    )
+    generated_api_client.client.create_or_update_previous_date(EVENT_ID, entry.id, previous_date.id, previous_date)
+    assert generated_api_client.client.list_deleted_previous_dates(EVENT_ID, entry.id) == []
+
+    generated_api_client.client.delete_previous_date(EVENT_ID, entry.id, previous_date.id)
+
+    # Deleted previous dates are excluded from the entry and the list of all previous dates ...
+    result = generated_api_client.client.get_entry(EVENT_ID, entry.id)
+    assert result.previous_dates == []
+    assert generated_api_client.client.list_all_previous_dates(EVENT_ID) == []
+    # ... but they are kept and listed as deleted
+    assert generated_api_client.client.list_deleted_previous_dates(EVENT_ID, entry.id) == [previous_date]
+
+    generated_api_client.client.restore_previous_date(EVENT_ID, entry.id, previous_date.id)
+    result = generated_api_client.client.get_entry(EVENT_ID, entry.id)
+    assert result.previous_dates == [previous_date]
+    assert generated_api_client.client.list_deleted_previous_dates(EVENT_ID, entry.id) == []
+
+    # Previous date is not deleted anymore
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.restore_previous_date(EVENT_ID, entry.id, previous_date.id)
+    assert excinfo.value.data.http_code == 409
+    # Non-existing previous date
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.restore_previous_date(EVENT_ID, entry.id, str(uuid.uuid4()))
+    assert excinfo.value.data.http_code == 404
+
+    # Previous dates, which are removed when updating the entry, are marked as deleted as well
+    entry.previous_dates = []
+    generated_api_client.client.create_or_update_entry(EVENT_ID, entry.id, entry)
+    assert generated_api_client.client.list_deleted_previous_dates(EVENT_ID, entry.id) == [previous_date]
+
+    # Updating a deleted previous date (e.g. repeating a PUT request) restores it
+    previous_date.comment = "Verschoben wegen Sturm"
+    generated_api_client.client.create_or_update_previous_date(EVENT_ID, entry.id, previous_date.id, previous_date)
+    assert generated_api_client.client.list_deleted_previous_dates(EVENT_ID, entry.id) == []
+    result = generated_api_client.client.get_entry(EVENT_ID, entry.id)
+    assert result.previous_dates == [previous_date]
+
+    generated_api_client.clear_login()
+    generated_api_client.login(EVENT_ID, "user")
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.restore_previous_date(EVENT_ID, entry.id, previous_date.id)
+    assert excinfo.value.data.http_code == 403
+
+
+def test_list_all_previous_dates(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "orga")
+    entries = [
+        kueaplan_api_client.Entry(
+            id=str(uuid.uuid4()),
+            title=title,
+            room=[],
+            begin=datetime.datetime(2025, 1, day, 12, 0, tzinfo=datetime.UTC).isoformat(),
+            end=datetime.datetime(2025, 1, day, 13, 30, tzinfo=datetime.UTC).isoformat(),
+            category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+            previousDates=[],
+        )
+        for title, day in (("Origami für Fortgeschrittene", 5), ("Drachenfliegen leicht gemacht", 4))
+    ]
+    previous_dates = []
+    for entry in entries:
+        generated_api_client.client.create_or_update_entry(EVENT_ID, entry.id, entry)
+        previous_date = kueaplan_api_client.PreviousDate(
+            id=str(uuid.uuid4()),
+            begin=datetime.datetime(2025, 1, 3, 12, 0, tzinfo=datetime.UTC).isoformat(),
+            end=datetime.datetime(2025, 1, 3, 13, 30, tzinfo=datetime.UTC).isoformat(),
+            room=[],
+        )
+        generated_api_client.client.create_or_update_previous_date(EVENT_ID, entry.id, previous_date.id, previous_date)
+        previous_dates.append(previous_date)
+
+    generated_api_client.login(EVENT_ID, "user")
+    result = generated_api_client.client.list_all_previous_dates(EVENT_ID)
+    # Ordered by the entry's current begin
+    assert len(result) == 2
+    assert result[0].entry_id == entries[1].id
+    assert result[0].entry_title == "Drachenfliegen leicht gemacht"
+    assert result[0].previous_dates == [previous_dates[1]]
+    assert result[1].entry_id == entries[0].id
+    assert result[1].entry_title == "Origami für Fortgeschrittene"
+    assert result[1].previous_dates == [previous_dates[0]]
+
+
 def test_create_or_update_previous_date_simple_errors(
     generated_api_client: ApiClientWrapper, reset_database: None
 ) -> None:
@@ -771,6 +1800,57 @@ def test_delete_entry_errors(generated_api_client: ApiClientWrapper, reset_datab
     assert excinfo.value.data.http_code == 403
 
 
+def test_entry_audit_log(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    entry = kueaplan_api_client.Entry(
+        id=str(uuid.uuid4()),
+        title="Drachenfliegen leicht gemacht",
+        room=[],
+        begin=datetime.datetime(2025, 1, 6, 12, 0, tzinfo=datetime.UTC).isoformat(),
+        end=datetime.datetime(2025, 1, 6, 13, 30, tzinfo=datetime.UTC).isoformat(),
+        responsible_person="Max Mustermann",
+        category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+        previousDates=[],
+    )
+    generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+    result = generated_api_client.client.get_entry_audit_log(event_id, entry.id)
+    assert len(result) == 1
+    assert result[0].action == "create"
+
+    entry.title = "Drachenfliegen für jedermann"
+    generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+    result = generated_api_client.client.get_entry_audit_log(event_id, entry.id)
+    assert len(result) == 2
+    assert result[1].action == "update"
+    assert result[1].changed_fields == ["title"]
+    assert result[1].passphrase_id == 2  # orga passphrase from minimal.sql
+
+    generated_api_client.client.change_entry(
+        event_id, entry.id, kueaplan_api_client.EntryPatch(is_cancelled=True, comment="fällt leider aus")
+    )
+    generated_api_client.client.delete_entry(event_id, entry.id)
+    result = generated_api_client.client.get_entry_audit_log(event_id, entry.id)
+    assert len(result) == 4
+    assert result[2].action == "update"
+    assert sorted(result[2].changed_fields) == ["comment", "is_cancelled"]
+    assert result[3].action == "delete"
+    assert result[3].changed_fields == ["deleted"]
+
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.get_entry_audit_log(event_id, str(uuid.uuid4()))
+    assert excinfo.value.data.http_code == 404
+
+    # Unauthorized
+    generated_api_client.clear_login()
+    generated_api_client.login(event_id, "user")
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.get_entry_audit_log(event_id, entry.id)
+    assert "not authorized" in str(excinfo.value.data.message)
+
+
 def test_entry_id_conflicts(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
     import kueaplan_api_client
 
@@ -799,11 +1879,11 @@ def test_entry_id_conflicts(generated_api_client: ApiClientWrapper, reset_databa
     )
     generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
 
-    # Same entry in other event
+    # Same entry in other event (which takes place at a different time)
     entry.previous_dates = []
     entry.category = "019cba98-3963-7477-a04a-0ac6bfaff6bf"
     with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
-        generated_api_client.client.create_or_update_entry(other_event_id, entry.id, entry)
+        generated_api_client.client.create_or_update_entry(other_event_id, entry.id, entry, allow_outside_range=True)
     assert "already exists" in str(excinfo.value.data.message)
     assert excinfo.value.data.http_code == 409
     entry.previous_dates = [previous_date]
@@ -32,6 +32,7 @@ def test_create_draft(generated_api_client: ApiClientWrapper, reset_database: No
     assert len(result) == 0
 
     # But it should show up in the list of *all* entries, accessible to orgas
+    entry.last_updated_by_role = "orga"
     result = generated_api_client.client.list_all_entries(EVENT_ID)
     assert result[0] == entry
 
@@ -0,0 +1,95 @@
+import uuid
+
+import pytest
+
+from tests.conftest import ApiClientWrapper
+
+CATEGORY_DEFAULT = "019774dc-81c4-7862-a9ba-63de3d726010"
+
+
+def test_create_list_delete_entry_template(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    assert generated_api_client.client.list_entry_templates(event_id) == []
+
+    template = kueaplan_api_client.EntryTemplate(
+        id=str(uuid.uuid4()),
+        name="Plenum",
+        defaults=kueaplan_api_client.EntryTemplateDefaults(
+            title="Plenum",
+            responsible_person="Orga-Team",
+            is_exclusive=True,
+            category=CATEGORY_DEFAULT,
+            links=[kueaplan_api_client.EntryLink(label="Protokoll", url="https://example.com/protokoll")],
+        ),
+    )
+    generated_api_client.client.create_entry_template(event_id, template.id, template)
+    other_template = kueaplan_api_client.EntryTemplate(
+        id=str(uuid.uuid4()),
+        name="Frühsport",
+        defaults=kueaplan_api_client.EntryTemplateDefaults(),
+    )
+    generated_api_client.client.create_entry_template(event_id, other_template.id, other_template)
+
+    result = generated_api_client.client.list_entry_templates(event_id)
+    # Templates are ordered by name
+    assert [t.id for t in result] == [other_template.id, template.id]
+    assert result[1].defaults.title == "Plenum"
+    assert result[1].defaults.responsible_person == "Orga-Team"
+    assert result[1].defaults.is_exclusive
+    assert not result[1].defaults.is_tentative
+    assert result[1].defaults.category == CATEGORY_DEFAULT
+    assert result[1].defaults.links[0].url == "https://example.com/protokoll"
+
+    # Templates are event-specific
+    generated_api_client.login(2, "orga")
+    assert generated_api_client.client.list_entry_templates(2) == []
+
+    generated_api_client.client.delete_entry_template(event_id, template.id)
+    result = generated_api_client.client.list_entry_templates(event_id)
+    assert [t.id for t in result] == [other_template.id]
+
+
+def test_entry_template_errors(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    template = kueaplan_api_client.EntryTemplate(
+        id=str(uuid.uuid4()),
+        name="Plenum",
+        defaults=kueaplan_api_client.EntryTemplateDefaults(title="Plenum"),
+    )
+
+    generated_api_client.login(event_id, "user")
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.create_entry_template(event_id, template.id, template)
+    assert excinfo.value.data.http_code == 403
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.list_entry_templates(event_id)
+    assert excinfo.value.data.http_code == 403
+
+    generated_api_client.login(event_id, "orga")
+    generated_api_client.client.create_entry_template(event_id, template.id, template)
+    # Existing templates cannot be overwritten
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.create_entry_template(event_id, template.id, template)
+    assert excinfo.value.data.http_code == 409
+
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.create_entry_template(event_id, str(uuid.uuid4()), template)
+    assert excinfo.value.data.http_code == 422
+
+    empty_name_template = kueaplan_api_client.EntryTemplate(
+        id=str(uuid.uuid4()), name="", defaults=kueaplan_api_client.EntryTemplateDefaults()
+    )
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.create_entry_template(event_id, empty_name_template.id, empty_name_template)
+    assert excinfo.value.data.http_code == 422
+
+    # Template of event 1 cannot be deleted via event 2
+    generated_api_client.login(2, "orga")
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.delete_entry_template(2, template.id)
+    assert excinfo.value.data.http_code == 404
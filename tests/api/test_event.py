@@ -1,6 +1,7 @@
 import datetime
+import uuid
 
-from tests.conftest import ApiClientWrapper
+from tests.conftest import ApiClientWrapper, EntryFactory
 
 
 def test_get_event(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
@@ -20,3 +21,42 @@ def test_list_events(generated_api_client: ApiClientWrapper, reset_database: Non
 
     result = generated_api_client.client.list_events(EVENT_ID, after=datetime.date(2025, 6, 3))
     assert len(result) == 1
+
+
+def test_get_event_stats(
+    generated_api_client: ApiClientWrapper, entry_factory: EntryFactory, reset_database: None
+) -> None:
+    import kueaplan_api_client
+
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "orga")
+    room = kueaplan_api_client.Room(id=str(uuid.uuid4()), title="Test Room", description="")
+    generated_api_client.client.create_or_update_room(EVENT_ID, room.id, room)
+
+    entry_factory.create(
+        EVENT_ID,
+        datetime.datetime(2025, 1, 2, 10, 0, tzinfo=datetime.UTC),
+        datetime.datetime(2025, 1, 2, 11, 30, tzinfo=datetime.UTC),
+        room=[room.id],
+    )
+    entry_factory.create(
+        EVENT_ID,
+        datetime.datetime(2025, 1, 2, 23, 30, tzinfo=datetime.UTC),
+        datetime.datetime(2025, 1, 3, 0, 15, tzinfo=datetime.UTC),
+        room=[room.id],
+    )
+    entry_factory.create(
+        EVENT_ID, datetime.datetime(2025, 1, 3, 10, 0, tzinfo=datetime.UTC), room=[room.id], is_cancelled=True
+    )
+    # Non-published entries are not counted
+    entry_factory.create(
+        EVENT_ID, datetime.datetime(2025, 1, 4, 10, 0, tzinfo=datetime.UTC), room=[room.id], state="draft"
+    )
+
+    generated_api_client.login(EVENT_ID, "user")
+    result = generated_api_client.client.get_event_stats(EVENT_ID)
+    assert result.entry_count == 3
+    assert result.cancelled_count == 1
+    assert result.room_count == 1
+    assert result.category_count == 1
+    assert result.total_programme_minutes == 135
@@ -1,8 +1,12 @@
 import datetime
+import os
+import shutil
+import subprocess
+import uuid
 
 import pytest
 
-from tests.conftest import ApiClientWrapper
+from tests.conftest import ApiClientWrapper, EntryFactory
 
 
 def test_get_extended_event(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
@@ -31,6 +35,279 @@ def test_get_extended_event_errors(generated_api_client: ApiClientWrapper, reset
     assert "requires authentication" in str(excinfo.value.data.message)
 
 
+def test_list_event_days(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "user")
+    result = generated_api_client.client.list_event_days(EVENT_ID)
+    assert [d.date for d in result] == [datetime.date(2025, 1, day) for day in range(1, 7)]
+    assert result[0].label == "Mi 01.01."
+    assert result[0].begin_utc == datetime.datetime(2025, 1, 1, 4, 30, tzinfo=datetime.UTC)
+    assert result[0].end_utc == datetime.datetime(2025, 1, 2, 4, 30, tzinfo=datetime.UTC)
+    assert result[1].begin_utc == result[0].end_utc
+
+
+def test_get_current_event_day(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "user")
+    # The event took place in January 2025, so the nearest day is its last day
+    result = generated_api_client.client.get_current_event_day(EVENT_ID)
+    assert result.date == datetime.date(2025, 1, 6)
+    assert result.begin_utc == datetime.datetime(2025, 1, 6, 4, 30, tzinfo=datetime.UTC)
+
+
+def test_list_event_days_dst_transition(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "admin")
+    event_info = generated_api_client.client.get_extended_event_info(EVENT_ID)
+    # Daylight saving time starts in Germany on 2025-03-30 at 02:00 local time
+    event_info.begin_date = datetime.date(2025, 3, 29)
+    event_info.end_date = datetime.date(2025, 3, 30)
+    generated_api_client.client.update_extended_event(EVENT_ID, event_info)
+
+    result = generated_api_client.client.list_event_days(EVENT_ID)
+    assert len(result) == 2
+    assert result[0].begin_utc == datetime.datetime(2025, 3, 29, 4, 30, tzinfo=datetime.UTC)
+    assert result[0].end_utc == datetime.datetime(2025, 3, 30, 3, 30, tzinfo=datetime.UTC)
+    assert result[1].begin_utc == datetime.datetime(2025, 3, 30, 3, 30, tzinfo=datetime.UTC)
+    assert result[1].end_utc == datetime.datetime(2025, 3, 31, 3, 30, tzinfo=datetime.UTC)
+
+
+def test_event_grid_overlapping_entries(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "orga")
+    for title, begin_hour, end_hour in [("Drachenfliegen", 12, 14), ("Kanufahren", 13, 15)]:
+        entry = kueaplan_api_client.Entry(
+            id=str(uuid.uuid4()),
+            title=title,
+            room=[],
+            begin=datetime.datetime(2025, 1, 3, begin_hour, 0, tzinfo=datetime.UTC).isoformat(),
+            end=datetime.datetime(2025, 1, 3, end_hour, 0, tzinfo=datetime.UTC).isoformat(),
+            responsible_person="Max Mustermann",
+            category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+            previousDates=[],
+        )
+        generated_api_client.client.create_or_update_entry(EVENT_ID, entry.id, entry)
+
+    result = generated_api_client.client.get_event_grid(EVENT_ID)
+    assert [d.date for d in result] == [datetime.date(2025, 1, day) for day in range(1, 7)]
+    day = result[2]
+    assert day.columns == 2
+    assert [e.entry.title for e in day.entries] == ["Drachenfliegen", "Kanufahren"]
+    assert [e.column for e in day.entries] == [0, 1]
+    # 13:00 local time is 7.5 hours after the effective begin of day (05:30)
+    assert day.entries[0].begin_minute == 450
+    assert day.entries[0].end_minute == 570
+    assert day.entries[0].begin == datetime.datetime(
+        2025, 1, 3, 13, 0, tzinfo=datetime.timezone(datetime.timedelta(hours=1))
+    )
+    assert all(len(d.entries) == 0 and d.columns == 0 for d in result if d.date != datetime.date(2025, 1, 3))
+
+
+def test_event_overview_groups_entries_by_effective_day(
+    generated_api_client: ApiClientWrapper, reset_database: None
+) -> None:
+    import kueaplan_api_client
+
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "orga")
+    # 23:30 local time on 2025-01-03 and 03:00 local time on 2025-01-04 (before the effective begin of day, 05:30)
+    for title, begin in [
+        ("Nachtwanderung", datetime.datetime(2025, 1, 3, 22, 30, tzinfo=datetime.UTC)),
+        ("Sternegucken", datetime.datetime(2025, 1, 4, 2, 0, tzinfo=datetime.UTC)),
+    ]:
+        entry = kueaplan_api_client.Entry(
+            id=str(uuid.uuid4()),
+            title=title,
+            room=[],
+            begin=begin.isoformat(),
+            end=(begin + datetime.timedelta(hours=3)).isoformat(),
+            responsible_person="Max Mustermann",
+            category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+            previousDates=[],
+        )
+        generated_api_client.client.create_or_update_entry(EVENT_ID, entry.id, entry)
+
+    result = generated_api_client.client.get_event_overview(EVENT_ID)
+    assert [d.day for d in result] == [datetime.date(2025, 1, day) for day in range(1, 7)]
+    assert [e.title for e in result[2].entries] == ["Nachtwanderung", "Sternegucken"]
+    assert all(len(d.entries) == 0 for d in result if d.day != datetime.date(2025, 1, 3))
+
+
+
+def test_daily_entry_counts(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "orga")
+    # 03:00 local time on 2025-01-04 belongs to the effective day 2025-01-03
+    for begin, state, orga_only in [
+        (datetime.datetime(2025, 1, 2, 12, 0, tzinfo=datetime.UTC), "published", False),
+        (datetime.datetime(2025, 1, 3, 12, 0, tzinfo=datetime.UTC), "published", False),
+        (datetime.datetime(2025, 1, 4, 2, 0, tzinfo=datetime.UTC), "published", False),
+        (datetime.datetime(2025, 1, 5, 12, 0, tzinfo=datetime.UTC), "published", False),
+        (datetime.datetime(2025, 1, 5, 14, 0, tzinfo=datetime.UTC), "draft", False),
+        (datetime.datetime(2025, 1, 5, 16, 0, tzinfo=datetime.UTC), "published", True),
+        (datetime.datetime(2025, 1, 5, 18, 0, tzinfo=datetime.UTC), "rejected", False),
+    ]:
+        entry = kueaplan_api_client.Entry(
+            id=str(uuid.uuid4()),
+            title="Test",
+            room=[],
+            begin=begin.isoformat(),
+            end=(begin + datetime.timedelta(hours=2)).isoformat(),
+            responsible_person="Max Mustermann",
+            category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+            previousDates=[],
+            state=state,
+            orga_only=orga_only,
+        )
+        generated_api_client.client.create_or_update_entry(EVENT_ID, entry.id, entry)
+
+    result = generated_api_client.client.get_daily_entry_counts(EVENT_ID)
+    assert [(d.day.day, d.count) for d in result] == [(1, 0), (2, 1), (3, 2), (4, 0), (5, 3), (6, 0)]
+
+    # Participants only get the counts of the published entries
+    generated_api_client.login(EVENT_ID, "user")
+    result = generated_api_client.client.get_daily_entry_counts(EVENT_ID)
+    assert [(d.day.day, d.count) for d in result] == [(1, 0), (2, 1), (3, 2), (4, 0), (5, 1), (6, 0)]
+
+def test_validate_event(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "orga")
+    room = kueaplan_api_client.Room(id=str(uuid.uuid4()), title="Turnhalle", description="")
+    generated_api_client.client.create_or_update_room(EVENT_ID, room.id, room)
+    # given: two overlapping entries in the same room, one of them exclusive
+    entries = [
+        kueaplan_api_client.Entry(
+            id=str(uuid.uuid4()),
+            title=title,
+            room=[room.id],
+            begin=datetime.datetime(2025, 1, 3, begin_hour, 0, tzinfo=datetime.UTC).isoformat(),
+            end=datetime.datetime(2025, 1, 3, begin_hour + 2, 0, tzinfo=datetime.UTC).isoformat(),
+            is_exclusive=is_exclusive,
+            category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+            previous_dates=[],
+        )
+        for title, begin_hour, is_exclusive in (("Zirkeltraining", 10, True), ("Jonglieren", 11, False))
+    ]
+    for entry in entries:
+        generated_api_client.client.create_or_update_entry(EVENT_ID, entry.id, entry)
+    # and: an announcement which is shown after the end of the event
+    announcement = kueaplan_api_client.Announcement(
+        id=str(uuid.uuid4()),
+        announcementType="info",
+        show_with_days=True,
+        begin_date=datetime.date(2025, 1, 5),
+        end_date=datetime.date(2025, 1, 10),
+        text="Bitte Fundsachen abholen",
+        sort_key=0,
+    )
+    generated_api_client.client.create_or_update_announcement(EVENT_ID, announcement.id, announcement)
+
+    # and: an entry in another room, overlapping with the exclusive entry
+    other_entry = kueaplan_api_client.Entry(
+        id=str(uuid.uuid4()),
+        title="Yoga",
+        room=[],
+        begin=datetime.datetime(2025, 1, 3, 8, 0, tzinfo=datetime.UTC).isoformat(),
+        end=datetime.datetime(2025, 1, 3, 10, 30, tzinfo=datetime.UTC).isoformat(),
+        category="019774dc-81c4-7862-a9ba-63de3d726010",
+        previous_dates=[],
+    )
+    generated_api_client.client.create_or_update_entry(EVENT_ID, other_entry.id, other_entry)
+    # and: two overlapping non-exclusive entries in the same room
+    double_booked_entries = [
+        kueaplan_api_client.Entry(
+            id=str(uuid.uuid4()),
+            title=title,
+            room=[room.id],
+            begin=datetime.datetime(2025, 1, 4, begin_hour, 0, tzinfo=datetime.UTC).isoformat(),
+            end=datetime.datetime(2025, 1, 4, begin_hour + 2, 0, tzinfo=datetime.UTC).isoformat(),
+            category="019774dc-81c4-7862-a9ba-63de3d726010",
+            previous_dates=[],
+        )
+        for title, begin_hour in (("Badminton", 10), ("Tischtennis", 11))
+    ]
+    for entry in double_booked_entries:
+        generated_api_client.client.create_or_update_entry(EVENT_ID, entry.id, entry)
+
+    result = generated_api_client.client.validate_event(EVENT_ID)
+
+    assert [(issue.severity, issue.kind) for issue in result.issues] == [
+        ("error", "exclusive-entry-conflict"),
+        ("error", "exclusive-entry-conflict"),
+        ("warning", "room-double-booking"),
+        ("warning", "announcement-outside-event-dates"),
+    ]
+    assert sorted(sorted(issue.entity_ids) for issue in result.issues[0:2]) == sorted([
+        sorted(entry.id for entry in entries),
+        sorted([entries[0].id, other_entry.id]),
+    ])
+    assert result.issues[2].entity_ids[0] == room.id
+    assert sorted(result.issues[2].entity_ids[1:]) == sorted(entry.id for entry in double_booked_entries)
+    assert result.issues[3].entity_ids == [announcement.id]
+
+    # Validation requires orga privileges
+    generated_api_client.login(EVENT_ID, "user")
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.validate_event(EVENT_ID)
+    assert excinfo.value.data.http_code == 403
+
+
+def test_partial_day_event(
+    generated_api_client: ApiClientWrapper, entry_factory: EntryFactory, reset_database: None
+) -> None:
+    import kueaplan_api_client
+
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "admin")
+    event_info = generated_api_client.client.get_extended_event_info(EVENT_ID)
+    event_info.begin_time = "14:00:00"
+    event_info.end_time = "12:00:00"
+    generated_api_client.client.update_extended_event(EVENT_ID, event_info)
+
+    result = generated_api_client.client.list_event_days(EVENT_ID)
+    assert len(result) == 6
+    assert result[0].begin_utc == datetime.datetime(2025, 1, 1, 13, 0, tzinfo=datetime.UTC)
+    assert result[0].end_utc == datetime.datetime(2025, 1, 2, 4, 30, tzinfo=datetime.UTC)
+    assert result[5].begin_utc == datetime.datetime(2025, 1, 6, 4, 30, tzinfo=datetime.UTC)
+    assert result[5].end_utc == datetime.datetime(2025, 1, 6, 11, 0, tzinfo=datetime.UTC)
+
+    # Within the event's time range
+    entry = entry_factory.build(
+        datetime.datetime(2025, 1, 1, 14, 0, tzinfo=datetime.UTC),
+        datetime.datetime(2025, 1, 1, 15, 0, tzinfo=datetime.UTC),
+    )
+    generated_api_client.client.create_or_update_entry(EVENT_ID, entry.id, entry)
+
+    # Before the begin on the first day and after the end on the last day
+    for entry in (
+        entry_factory.build(
+            datetime.datetime(2025, 1, 1, 10, 0, tzinfo=datetime.UTC),
+            datetime.datetime(2025, 1, 1, 11, 0, tzinfo=datetime.UTC),
+        ),
+        entry_factory.build(
+            datetime.datetime(2025, 1, 6, 10, 0, tzinfo=datetime.UTC),
+            datetime.datetime(2025, 1, 6, 12, 0, tzinfo=datetime.UTC),
+        ),
+    ):
+        with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+            generated_api_client.client.create_or_update_entry(EVENT_ID, entry.id, entry)
+        assert excinfo.value.status == 422
+        assert "date range" in str(excinfo.value.data.message)
+
+    # End before begin
+    event_info.begin_date = datetime.date(2025, 1, 6)
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.update_extended_event(EVENT_ID, event_info)
+    assert excinfo.value.status == 422
+    assert "end must be after its begin" in str(excinfo.value.data.message)
+
+
 def test_update_extended_event(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
     EVENT_ID = 1
     generated_api_client.login(EVENT_ID, "admin")
@@ -67,7 +344,12 @@ def test_update_extended_event_errors(generated_api_client: ApiClientWrapper, re
     with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
         generated_api_client.client.update_extended_event(EVENT_ID, event_with_invalid_timezone)
     assert excinfo.value.status == 422
-    assert "timezone" in str(excinfo.value.data.message)
+    assert "Unbekannte Zeitzone: Europe/Aachen" in str(excinfo.value.data.message)
+    event_with_invalid_timezone = event_info.model_copy(update={"timezone": "Mars/Olympus"})
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.update_extended_event(EVENT_ID, event_with_invalid_timezone)
+    assert excinfo.value.status == 422
+    assert excinfo.value.data.message == "Invalid request data: Unbekannte Zeitzone: Mars/Olympus"
 
     # Begin after end
     assert event_info.end_date == datetime.date(2025, 1, 6)
@@ -86,3 +368,51 @@ def test_update_extended_event_errors(generated_api_client: ApiClientWrapper, re
         generated_api_client.client.update_extended_event(EVENT_ID, event_with_invalid_schedule_section)
     assert excinfo.value.status == 422
     assert "Schedule sections" in str(excinfo.value.data.message)
+
+
+def test_get_event_neighbors(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    # minimal.sql only contains two events, so we add a third one as the preceding event
+    psql_path = shutil.which("psql")
+    assert psql_path is not None
+    subprocess.run(
+        [
+            psql_path,
+            "-v",
+            "ON_ERROR_STOP=1",
+            "-c",
+            "INSERT INTO events (id, title, begin_date, end_date, timezone, effective_begin_of_day, "
+            "default_time_schedule, slug, entry_submission_mode) "
+            "VALUES (3, 'Previous event', '2024-06-01', '2024-06-06', 'Europe/Berlin', '05:30:00', "
+            "'{\"sections\": []}', 'previous', 0)",
+            os.environ["DATABASE_URL"],
+        ],
+        check=True,
+        capture_output=True,
+    )
+
+    EVENT_ID = 1
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.get_event_neighbors(EVENT_ID)
+    assert excinfo.value.status == 403
+
+    generated_api_client.login(EVENT_ID, "user")
+    neighbors = generated_api_client.client.get_event_neighbors(EVENT_ID)
+    assert neighbors.preceding_event is None
+    assert neighbors.subsequent_event is None
+
+    generated_api_client.login(EVENT_ID, "admin")
+    event_info = generated_api_client.client.get_extended_event_info(EVENT_ID)
+    event_info.preceding_event_id = 3
+    event_info.subsequent_event_id = 2
+    generated_api_client.client.update_extended_event(EVENT_ID, event_info)
+
+    neighbors = generated_api_client.client.get_event_neighbors(EVENT_ID)
+    assert neighbors.preceding_event is not None
+    assert neighbors.preceding_event.id == 3
+    assert neighbors.preceding_event.title == "Previous event"
+    assert neighbors.preceding_event.begin_date == datetime.date(2024, 6, 1)
+    assert neighbors.subsequent_event is not None
+    assert neighbors.subsequent_event.id == 2
+    assert neighbors.subsequent_event.title == "The other event"
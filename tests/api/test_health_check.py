@@ -0,0 +1,8 @@
+import json
+import urllib.request
+
+
+def test_health_check() -> None:
+    with urllib.request.urlopen("http://localhost:9099/healthz") as response:
+        assert response.status == 200
+        assert json.loads(response.read()) == {"status": "ok"}
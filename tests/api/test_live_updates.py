@@ -0,0 +1,92 @@
+import datetime
+import json
+import uuid
+
+import pytest
+import websockets.exceptions
+import websockets.sync.client
+
+from tests.conftest import ApiClientWrapper, EntryFactory
+
+LIVE_UPDATES_URL = "ws://localhost:9099/api/v1/events/{event_id}/live"
+
+
+def test_live_updates(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "orga")
+    session_token = generated_api_client.client.api_client.configuration.api_key["sessionTokenAuth"]
+    entry = kueaplan_api_client.Entry(
+        id=str(uuid.uuid4()),
+        title="Drachenfliegen leicht gemacht",
+        room=[],
+        begin=datetime.datetime(2025, 1, 4, 14, 0, tzinfo=datetime.UTC).isoformat(),
+        end=datetime.datetime(2025, 1, 4, 16, 0, tzinfo=datetime.UTC).isoformat(),
+        responsible_person="Max Mustermann",
+        category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from minimal.sql
+        previousDates=[],
+    )
+    generated_api_client.client.create_or_update_entry(EVENT_ID, entry.id, entry)
+
+    with websockets.sync.client.connect(
+        LIVE_UPDATES_URL.format(event_id=EVENT_ID), additional_headers={"X-SESSION-TOKEN": session_token}
+    ) as connection:
+        entry.title = "Drachenfliegen schwer gemacht"
+        generated_api_client.client.create_or_update_entry(EVENT_ID, entry.id, entry)
+        message = json.loads(connection.recv(timeout=5))
+        assert message == {"entryId": entry.id, "action": "update"}
+
+        generated_api_client.client.delete_entry(EVENT_ID, entry.id)
+        message = json.loads(connection.recv(timeout=5))
+        assert message == {"entryId": entry.id, "action": "delete"}
+
+
+def test_live_updates_hide_internal_entries(
+    generated_api_client: ApiClientWrapper, entry_factory: EntryFactory, reset_database: None
+) -> None:
+    import kueaplan_api_client
+
+    EVENT_ID = 1
+    participant_client = ApiClientWrapper(kueaplan_api_client)
+    participant_client.login(EVENT_ID, "user")
+    participant_session_token = participant_client.client.api_client.configuration.api_key["sessionTokenAuth"]
+    generated_api_client.login(EVENT_ID, "orga")
+    orga_session_token = generated_api_client.client.api_client.configuration.api_key["sessionTokenAuth"]
+
+    begin = datetime.datetime(2025, 1, 4, 14, 0, tzinfo=datetime.UTC)
+    url = LIVE_UPDATES_URL.format(event_id=EVENT_ID)
+    with (
+        websockets.sync.client.connect(
+            url, additional_headers={"X-SESSION-TOKEN": participant_session_token}
+        ) as participant_connection,
+        websockets.sync.client.connect(
+            url, additional_headers={"X-SESSION-TOKEN": orga_session_token}
+        ) as orga_connection,
+    ):
+        draft_entry_id = entry_factory.create(EVENT_ID, begin, title="Geheime Planung", state="draft").id
+        orga_only_entry_id = entry_factory.create(EVENT_ID, begin, title="Orga-Treffen", orga_only=True).id
+        public_entry_id = entry_factory.create(EVENT_ID, begin, title="Öffentliche KüA").id
+
+        # Participants are only notified about the public entry
+        message = json.loads(participant_connection.recv(timeout=5))
+        assert message == {"entryId": public_entry_id, "action": "create"}
+
+        # Orgas are notified about all entries
+        for entry_id in (draft_entry_id, orga_only_entry_id, public_entry_id):
+            message = json.loads(orga_connection.recv(timeout=5))
+            assert message == {"entryId": entry_id, "action": "create"}
+
+        # Unpublishing an entry is relevant for participants
+        generated_api_client.client.change_entry(
+            EVENT_ID, public_entry_id, kueaplan_api_client.EntryPatch(state="retracted")
+        )
+        message = json.loads(participant_connection.recv(timeout=5))
+        assert message == {"entryId": public_entry_id, "action": "update"}
+
+
+def test_live_updates_unauthorized(reset_database: None) -> None:
+    with pytest.raises(websockets.exceptions.InvalidStatus) as excinfo:
+        with websockets.sync.client.connect(LIVE_UPDATES_URL.format(event_id=1)):
+            pass
+    assert excinfo.value.response.status_code == 403
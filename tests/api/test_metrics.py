@@ -0,0 +1,14 @@
+import urllib.request
+
+
+def test_metrics() -> None:
+    with urllib.request.urlopen("http://localhost:9099/healthz") as response:
+        assert response.status == 200
+
+    with urllib.request.urlopen("http://localhost:9099/metrics") as response:
+        assert response.status == 200
+        lines = response.read().decode().splitlines()
+    assert "# TYPE http_requests_total counter" in lines
+    assert any(line.startswith('http_requests_total{status="2xx"} ') for line in lines)
+    assert any(line.startswith("http_request_duration_seconds_count ") for line in lines)
+    assert any(line.startswith("db_pool_connections_in_use ") for line in lines)
@@ -0,0 +1,24 @@
+import json
+import urllib.error
+import urllib.request
+
+import pytest
+
+
+def test_unknown_api_route() -> None:
+    with pytest.raises(urllib.error.HTTPError) as excinfo:
+        urllib.request.urlopen("http://localhost:9099/api/v1/does-not-exist")
+    assert excinfo.value.code == 404
+    assert excinfo.value.headers["Content-Type"].startswith("application/json")
+    body = json.loads(excinfo.value.read())
+    assert body["httpCode"] == 404
+    assert body["errorCode"] == "NOT_EXISTING"
+    assert body["message"]
+
+
+def test_unknown_ui_route() -> None:
+    # The JSON fallback of the API must not shadow the UI's HTML error page
+    with pytest.raises(urllib.error.HTTPError) as excinfo:
+        urllib.request.urlopen("http://localhost:9099/ui/1/does-not-exist")
+    assert excinfo.value.code == 404
+    assert excinfo.value.headers["Content-Type"].startswith("text/html")
@@ -0,0 +1,14 @@
+import json
+import urllib.request
+
+
+def test_openapi_document() -> None:
+    with urllib.request.urlopen("http://localhost:9099/api/v1/openapi.json") as response:
+        assert response.status == 200
+        assert response.headers["Content-Type"].startswith("application/json")
+        document = json.loads(response.read())
+    assert document["openapi"].startswith("3.")
+    assert "get" in document["paths"]["/events/{eventId}/entries"]
+    assert document["components"]["securitySchemes"]["sessionTokenAuth"]["name"] == "X-SESSION-TOKEN"
+    assert "Entry" in document["components"]["schemas"]
+    assert "Error" in document["components"]["schemas"]
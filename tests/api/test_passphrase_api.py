@@ -1,6 +1,8 @@
 import datetime
 import uuid
 
+import pytest
+
 from tests.conftest import ApiClientWrapper
 
 
@@ -54,6 +56,95 @@ def test_create_passphrase(generated_api_client: ApiClientWrapper, reset_databas
     client2.client.create_or_update_category(EVENT_ID, category.id, category)
 
 
+def test_authenticate_with_hashed_passphrase(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "admin")
+    passphrase = kueaplan_api_client.Passphrase(
+        passphrase="hashed-orga-passphrase",
+        derivable_from_passphrase=None,
+        role="orga",
+        comment="hashed",
+    )
+    generated_api_client.client.create_passphrase(EVENT_ID, passphrase)
+
+    # Only the hash is stored for orga passphrases, so not even the obfuscated suffix is available
+    passphrases = generated_api_client.client.list_passphrases(EVENT_ID)
+    new_passphrase = next(p for p in passphrases if p.comment == "hashed")
+    assert new_passphrase.passphrase is not None
+    assert not new_passphrase.passphrase.endswith("rase")
+
+    client2 = ApiClientWrapper(kueaplan_api_client)
+    client2.login(EVENT_ID, "hashed-orga-passphrase")
+    authorization_info = client2.client.check_authorization(EVENT_ID)
+    assert [a.role for a in authorization_info.authorization] == ["orga"]
+
+    for wrong_passphrase in ("hashed-orga-passphras", "Hashed-orga-passphrase", ""):
+        with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+            client2.login(EVENT_ID, wrong_passphrase)
+        assert excinfo.value.status == 403
+
+
+def test_create_duplicate_passphrase(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "admin")
+    passphrase = kueaplan_api_client.Passphrase(
+        passphrase="duplicate-passphrase",
+        derivable_from_passphrase=None,
+        role="orga",
+    )
+    generated_api_client.client.create_passphrase(EVENT_ID, passphrase)
+
+    # The orga passphrase is only stored as hash, but the duplicate must still be detected,
+    # regardless of the role
+    for role in ("orga", "participant"):
+        passphrase = kueaplan_api_client.Passphrase(
+            passphrase="duplicate-passphrase",
+            derivable_from_passphrase=None,
+            role=role,
+        )
+        with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+            generated_api_client.client.create_passphrase(EVENT_ID, passphrase)
+        assert excinfo.value.status == 409
+
+    client2 = ApiClientWrapper(kueaplan_api_client)
+    client2.login(EVENT_ID, "duplicate-passphrase")
+    authorization_info = client2.client.check_authorization(EVENT_ID)
+    assert [a.role for a in authorization_info.authorization] == ["orga"]
+
+
+def test_passphrase_last_used(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "admin")
+    passphrases_by_id = {p.id: p for p in generated_api_client.client.list_passphrases(EVENT_ID)}
+    # Passphrase 1 ("user") has not been used, yet
+    assert passphrases_by_id[1].last_used is None
+    # ... but the admin passphrase has just been used for login
+    assert passphrases_by_id[3].last_used is not None
+
+    client2 = ApiClientWrapper(kueaplan_api_client)
+    client2.login(EVENT_ID, "user")
+    passphrases_by_id = {p.id: p for p in generated_api_client.client.list_passphrases(EVENT_ID)}
+    first_use = passphrases_by_id[1].last_used
+    assert first_use is not None
+    assert passphrases_by_id[2].last_used is None
+
+    # Failed logins must not update the timestamp
+    with pytest.raises(kueaplan_api_client.ApiException):
+        client2.login(EVENT_ID, "usr")
+    passphrases_by_id = {p.id: p for p in generated_api_client.client.list_passphrases(EVENT_ID)}
+    assert passphrases_by_id[1].last_used == first_use
+
+    client2.login(EVENT_ID, "user")
+    passphrases_by_id = {p.id: p for p in generated_api_client.client.list_passphrases(EVENT_ID)}
+    assert passphrases_by_id[1].last_used > first_use
+
+
 def test_extended_attributes(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
     import kueaplan_api_client
 
@@ -83,3 +174,32 @@ def test_extended_attributes(generated_api_client: ApiClientWrapper, reset_datab
     assert returned_passphrase.valid_from == datetime.datetime(2025, 1, 1, 13, 37, 0, tzinfo=datetime.UTC)
     assert returned_passphrase.valid_until == datetime.datetime(2100, 1, 1, 0, 42, 0, tzinfo=datetime.UTC)
     assert returned_passphrase.comment == "Secondary passphrase for testing purposes"
+
+
+def test_get_passphrase_scope(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "admin")
+    # Passphrase 2 is the "orga" passphrase of event 1
+    scope = generated_api_client.client.get_passphrase_scope(EVENT_ID, 2)
+    assert scope.passphrase_id == 2
+    assert scope.event_id == EVENT_ID
+    assert scope.role == "orga"
+    assert "show-kuea-plan" in scope.privileges
+    assert "manage-entries" in scope.privileges
+    assert "manage-categories" in scope.privileges
+    assert "manage-announcements" in scope.privileges
+    assert "edit-event-details" not in scope.privileges
+    assert "manage-passphrases" not in scope.privileges
+    assert "create-events" not in scope.privileges
+
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.get_passphrase_scope(EVENT_ID, 4242)
+    assert excinfo.value.status == 404
+
+    generated_api_client.clear_login()
+    generated_api_client.login(EVENT_ID, "orga")
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.get_passphrase_scope(EVENT_ID, 2)
+    assert excinfo.value.status == 403
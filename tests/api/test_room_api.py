@@ -1,9 +1,11 @@
 import datetime
+import os
+import time
 import uuid
 
 import pytest
 
-from tests.conftest import ApiClientWrapper
+from tests.conftest import ApiClientWrapper, EntryFactory
 
 
 def test_create_or_update_room(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
@@ -57,6 +59,55 @@ def test_create_or_update_room_errors(generated_api_client: ApiClientWrapper, re
         generated_api_client.client.create_or_update_room(42, room.id, room)
 
 
+def test_change_room(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "orga")
+    room = kueaplan_api_client.Room(
+        id=str(uuid.uuid4()),
+        title="Test Room",
+        description="This is the unofficial room, deep down underground.",
+    )
+    generated_api_client.client.create_or_update_room(EVENT_ID, room.id, room)
+    time.sleep(0.1)
+    cutoff = datetime.datetime.now(datetime.UTC)
+
+    generated_api_client.client.change_room(
+        EVENT_ID, room.id, kueaplan_api_client.RoomPatch(description="Second door on the left")
+    )
+
+    room.description = "Second door on the left"
+    result = generated_api_client.client.list_rooms(EVENT_ID)
+    assert result[0] == room
+    # last_updated has been bumped, so the room is included in the updates since the cutoff
+    updates = generated_api_client.client.get_updates(EVENT_ID, since=cutoff)
+    assert updates.rooms == [room]
+
+
+def test_change_room_errors(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    EVENT_ID = 1
+    generated_api_client.login(EVENT_ID, "orga")
+    room = kueaplan_api_client.Room(id=str(uuid.uuid4()), title="Test Room", description="")
+    generated_api_client.client.create_or_update_room(EVENT_ID, room.id, room)
+    generated_api_client.clear_login()
+
+    # Unauthorized
+    generated_api_client.login(EVENT_ID, "user")
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.change_room(EVENT_ID, room.id, kueaplan_api_client.RoomPatch(title="Foo"))
+    assert "not authorized" in str(excinfo.value.data.message)
+    assert excinfo.value.data.http_code == 403
+
+    # Non-existing room
+    generated_api_client.login(EVENT_ID, "orga")
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.change_room(EVENT_ID, str(uuid.uuid4()), kueaplan_api_client.RoomPatch(title="Foo"))
+    assert excinfo.value.data.http_code == 404
+
+
 def test_delete_room(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
     import kueaplan_api_client
 
@@ -180,6 +231,121 @@ def test_delete_room_errors(generated_api_client: ApiClientWrapper, reset_databa
     assert excinfo.value.data.http_code == 403
 
 
+def test_delete_room_in_use(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    room1 = kueaplan_api_client.Room(id=str(uuid.uuid4()), title="Test Room", description="")
+    room2 = kueaplan_api_client.Room(id=str(uuid.uuid4()), title="Test Room 2", description="")
+    generated_api_client.client.create_or_update_room(event_id, room1.id, room1)
+    generated_api_client.client.create_or_update_room(event_id, room2.id, room2)
+    # Two upcoming entries and a past entry in the first room
+    now = datetime.datetime.now(tz=datetime.UTC)
+    for begin in (now + datetime.timedelta(days=1), now + datetime.timedelta(days=2), now - datetime.timedelta(days=2)):
+        entry = kueaplan_api_client.Entry(
+            id=str(uuid.uuid4()),
+            title="Drachenfliegen leicht gemacht",
+            begin=begin.isoformat(),
+            end=(begin + datetime.timedelta(hours=1)).isoformat(),
+            room=[room1.id],
+            category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from database dump
+            previous_dates=[],
+        )
+        generated_api_client.client.create_or_update_entry(event_id, entry.id, entry, allow_outside_range=True)
+
+    # Deleting without confirmation is rejected, reporting the number of upcoming entries
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.delete_room(event_id, room1.id)
+    assert excinfo.value.data.http_code == 409
+    assert excinfo.value.data.error_code == "ROOM_IN_USE"
+    assert excinfo.value.data.entry_count == 2
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.delete_room(
+            event_id, room1.id, kueaplan_api_client.DeleteRoomRequest(add_room_comment="war im alten Testraum")
+        )
+    assert excinfo.value.data.error_code == "ROOM_IN_USE"
+    assert len(generated_api_client.client.list_rooms(event_id)) == 2
+
+    # Giving replacement rooms confirms the deletion
+    generated_api_client.client.delete_room(
+        event_id, room1.id, kueaplan_api_client.DeleteRoomRequest(replace_rooms=[room2.id])
+    )
+    assert [r.id for r in generated_api_client.client.list_rooms(event_id)] == [room2.id]
+
+    # Deletion can be forced
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.delete_room(event_id, room2.id)
+    assert excinfo.value.data.entry_count == 2
+    generated_api_client.client.delete_room(event_id, room2.id, kueaplan_api_client.DeleteRoomRequest(force=True))
+    assert generated_api_client.client.list_rooms(event_id) == []
+
+
+def test_merge_rooms(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    # given: two rooms, an entry in the first room and an entry in both rooms
+    room1 = kueaplan_api_client.Room(id=str(uuid.uuid4()), title="Test Room", description="")
+    room2 = kueaplan_api_client.Room(id=str(uuid.uuid4()), title="Test Room 2", description="")
+    generated_api_client.client.create_or_update_room(event_id, room1.id, room1)
+    generated_api_client.client.create_or_update_room(event_id, room2.id, room2)
+    entry1 = kueaplan_api_client.Entry(
+        id=str(uuid.uuid4()),
+        title="Drachenfliegen leicht gemacht",
+        begin=datetime.datetime(2025, 1, 5, 12, 0, tzinfo=datetime.UTC).isoformat(),
+        end=datetime.datetime(2025, 1, 5, 13, 30, tzinfo=datetime.UTC).isoformat(),
+        room=[room1.id],
+        responsible_person="Max Mustermann",
+        category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from database dump
+        previous_dates=[
+            kueaplan_api_client.PreviousDate(
+                id=str(uuid.uuid4()),
+                begin=datetime.datetime(2025, 1, 4, 13, 0, tzinfo=datetime.UTC).isoformat(),
+                end=datetime.datetime(2025, 1, 4, 14, 30, tzinfo=datetime.UTC).isoformat(),
+                room=[room1.id, room2.id],
+            )
+        ],
+    )
+    entry2 = kueaplan_api_client.Entry(
+        id=str(uuid.uuid4()),
+        title="Drachen steigen lassen",
+        begin=datetime.datetime(2025, 1, 5, 15, 0, tzinfo=datetime.UTC).isoformat(),
+        end=datetime.datetime(2025, 1, 5, 16, 30, tzinfo=datetime.UTC).isoformat(),
+        room=[room1.id, room2.id],
+        responsible_person="Max Mustermann",
+        category="019774dc-81c4-7862-a9ba-63de3d726010",  # Default category from database dump
+        previous_dates=[],
+    )
+    generated_api_client.client.create_or_update_entry(event_id, entry1.id, entry1)
+    generated_api_client.client.create_or_update_entry(event_id, entry2.id, entry2)
+
+    # when: we merge the first room into the second room
+    generated_api_client.client.merge_room(event_id, room1.id, room2.id)
+
+    # then: the first room is gone
+    result = generated_api_client.client.list_rooms(event_id)
+    assert [room.id for room in result] == [room2.id]
+
+    # and: all entries and previous dates are assigned to the second room, without duplicates
+    new_entry1 = generated_api_client.client.get_entry(event_id, entry1.id)
+    assert new_entry1.room == [room2.id]
+    assert new_entry1.previous_dates[0].room == [room2.id]
+    new_entry2 = generated_api_client.client.get_entry(event_id, entry2.id)
+    assert new_entry2.room == [room2.id]
+
+    # Merging a deleted room fails
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.merge_room(event_id, room1.id, room2.id)
+    assert excinfo.value.data.http_code == 404
+
+    # Merging a room into itself fails
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.merge_room(event_id, room2.id, room2.id)
+    assert excinfo.value.data.http_code == 422
+
+
 def test_room_id_conflicts(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
     import kueaplan_api_client
 
@@ -208,3 +374,149 @@ def test_room_id_conflicts(generated_api_client: ApiClientWrapper, reset_databas
         generated_api_client.client.create_or_update_room(other_event_id, room.id, room)
     assert "already exists" in str(excinfo.value.data.message)
     assert excinfo.value.data.http_code == 409
+
+
+def test_list_room_conflicts(
+    generated_api_client: ApiClientWrapper, entry_factory: EntryFactory, reset_database: None
+) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    room1 = kueaplan_api_client.Room(id=str(uuid.uuid4()), title="Test Room", description="")
+    room2 = kueaplan_api_client.Room(id=str(uuid.uuid4()), title="Test Room 2", description="")
+    generated_api_client.client.create_or_update_room(event_id, room1.id, room1)
+    generated_api_client.client.create_or_update_room(event_id, room2.id, room2)
+
+    def at(hour: int) -> datetime.datetime:
+        return datetime.datetime(2025, 1, 3, hour, 0, tzinfo=datetime.UTC)
+
+    # given: two overlapping entries in the first room and another overlapping entry in both rooms
+    entry1 = entry_factory.create(event_id, at(10), at(12), title="Drachenfliegen leicht gemacht", room=[room1.id])
+    entry2 = entry_factory.create(event_id, at(11), at(13), title="Drachen steigen lassen", room=[room1.id])
+    # and: an entry in the second room, directly after the other one (no overlap)
+    entry3 = entry_factory.create(event_id, at(13), at(14), title="Drachenbau", room=[room1.id, room2.id])
+    entry_factory.create(event_id, at(14), at(15), title="Drachen malen", room=[room2.id])
+    # and: a cancelled entry overlapping with the others
+    entry_factory.create(event_id, at(10), at(15), title="Drachenzähmen", room=[room1.id, room2.id], is_cancelled=True)
+
+    result = generated_api_client.client.list_room_conflicts(event_id)
+
+    # then: only the overlapping pair in the first room is reported
+    assert len(result) == 1
+    assert result[0].room_id == room1.id
+    assert [sorted(pair) for pair in result[0].conflicting_entries] == [sorted([entry1.id, entry2.id])]
+
+    # when: an entry in the second room is moved to overlap with the third entry
+    entry3.begin = datetime.datetime(2025, 1, 3, 14, 30, tzinfo=datetime.UTC).isoformat()
+    entry3.end = datetime.datetime(2025, 1, 3, 16, 0, tzinfo=datetime.UTC).isoformat()
+    generated_api_client.client.create_or_update_entry(event_id, entry3.id, entry3)
+
+    # then: the conflict is reported in the second room
+    result = generated_api_client.client.list_room_conflicts(event_id)
+    assert [r.room_id for r in result] == [room1.id, room2.id]
+    assert len(result[1].conflicting_entries) == 1
+    assert entry3.id in result[1].conflicting_entries[0]
+
+
+def test_list_room_entries(
+    generated_api_client: ApiClientWrapper, entry_factory: EntryFactory, reset_database: None
+) -> None:
+    import kueaplan_api_client
+
+    event_id = 2
+    generated_api_client.login(event_id, "orga")
+    other_event_room = kueaplan_api_client.Room(id=str(uuid.uuid4()), title="Anderer Raum", description="")
+    generated_api_client.client.create_or_update_room(event_id, other_event_room.id, other_event_room)
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+    room = kueaplan_api_client.Room(id=str(uuid.uuid4()), title="Seminarraum", description="")
+    generated_api_client.client.create_or_update_room(event_id, room.id, room)
+    other_room = kueaplan_api_client.Room(id=str(uuid.uuid4()), title="Turnhalle", description="")
+    generated_api_client.client.create_or_update_room(event_id, other_room.id, other_room)
+
+    def on_day(day: int, hour: int) -> datetime.datetime:
+        return datetime.datetime(2025, 1, day, hour, 0, tzinfo=datetime.UTC)
+
+    afternoon_entry = entry_factory.create(event_id, on_day(5, 14), title="Chor", room=[room.id]).id
+    morning_entry = entry_factory.create(event_id, on_day(5, 9), title="Yoga", room=[room.id]).id
+    entry_factory.create(event_id, on_day(4, 9), title="Schach", room=[room.id])
+    entry_factory.create(event_id, on_day(5, 10), title="Volleyball", room=[other_room.id])
+    moved_entry = entry_factory.create(
+        event_id,
+        on_day(5, 12),
+        title="Tanzen",
+        room=[other_room.id],
+        previous_dates=[
+            kueaplan_api_client.PreviousDate(
+                id=str(uuid.uuid4()),
+                begin=datetime.datetime(2025, 1, 5, 12, 0, tzinfo=datetime.UTC).isoformat(),
+                end=datetime.datetime(2025, 1, 5, 13, 0, tzinfo=datetime.UTC).isoformat(),
+                room=[room.id],
+            )
+        ],
+    ).id
+
+    generated_api_client.login(event_id, "user")
+    after = datetime.datetime(2025, 1, 5, 4, 30, tzinfo=datetime.UTC)
+    before = datetime.datetime(2025, 1, 6, 4, 30, tzinfo=datetime.UTC)
+    result = generated_api_client.client.list_room_entries(event_id, room.id, after=after, before=before)
+    assert [e.id for e in result] == [morning_entry, afternoon_entry]
+
+    result = generated_api_client.client.list_room_entries(
+        event_id, room.id, after=after, before=before, include_previous=True
+    )
+    assert [e.id for e in result] == [morning_entry, moved_entry, afternoon_entry]
+
+    assert len(generated_api_client.client.list_room_entries(event_id, room.id)) == 3
+
+    # Room does not exist or belongs to another event
+    for room_id in (str(uuid.uuid4()), other_event_room.id):
+        with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+            generated_api_client.client.list_room_entries(event_id, room_id)
+        assert excinfo.value.status == 404
+
+
+def test_list_rooms_of_events(generated_api_client: ApiClientWrapper, reset_database: None) -> None:
+    import kueaplan_api_client
+
+    server_admin_api_key = os.environ.get("SERVER_ADMIN_API_KEY")
+    if not server_admin_api_key:
+        pytest.skip("SERVER_ADMIN_API_KEY is not configured")
+    event_id = 1
+    other_event_id = 2
+
+    generated_api_client.login(event_id, "admin")
+    generated_api_client.login(other_event_id, "orga")
+    rooms = [
+        kueaplan_api_client.Room(id=str(uuid.uuid4()), title="Room B", description=""),
+        kueaplan_api_client.Room(id=str(uuid.uuid4()), title="Room A", description=""),
+    ]
+    for room in rooms:
+        generated_api_client.client.create_or_update_room(event_id, room.id, room)
+    other_room = kueaplan_api_client.Room(id=str(uuid.uuid4()), title="Other Room", description="")
+    generated_api_client.client.create_or_update_room(other_event_id, other_room.id, other_room)
+
+    # The admin role of the events is not sufficient
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.list_rooms_of_events(event=[event_id, other_event_id])
+    assert excinfo.value.status == 403
+
+    # Neither is a wrong API key
+    generated_api_client.clear_login()
+    generated_api_client.client.api_client.configuration.access_token = "wrong"
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.list_rooms_of_events(event=[event_id, other_event_id])
+    assert excinfo.value.status == 403
+
+    generated_api_client.client.api_client.configuration.access_token = server_admin_api_key
+    result = generated_api_client.client.list_rooms_of_events(event=[other_event_id, event_id])
+    assert [e.event_id for e in result.events] == [other_event_id, event_id]
+    assert result.events[0].rooms == [other_room]
+    assert result.events[1].rooms == [rooms[1], rooms[0]]
+
+    # Non-existing events are rejected
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.list_rooms_of_events(event=[event_id, 42])
+    assert excinfo.value.status == 404
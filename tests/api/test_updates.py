@@ -0,0 +1,60 @@
+import datetime
+import time
+import uuid
+
+from tests.conftest import ApiClientWrapper, EntryFactory
+
+
+def test_get_updates_since(
+    generated_api_client: ApiClientWrapper, entry_factory: EntryFactory, reset_database: None
+) -> None:
+    import kueaplan_api_client
+
+    event_id = 1
+    generated_api_client.login(event_id, "orga")
+
+    # given: three entries, created before the cutoff timestamp
+    entry_updated = entry_factory.build(
+        datetime.datetime(2025, 1, 3, 12, 0, tzinfo=datetime.UTC), title="Drachenfliegen leicht gemacht"
+    )
+    entry_deleted = entry_factory.build(
+        datetime.datetime(2025, 1, 4, 12, 0, tzinfo=datetime.UTC), title="Schach für Anfänger"
+    )
+    entry_unchanged = entry_factory.build(
+        datetime.datetime(2025, 1, 2, 12, 0, tzinfo=datetime.UTC), title="Kaffeeklatsch"
+    )
+    for entry in (entry_updated, entry_deleted, entry_unchanged):
+        generated_api_client.client.create_or_update_entry(event_id, entry.id, entry)
+
+    time.sleep(0.1)
+    cutoff = datetime.datetime.now(datetime.UTC)
+    time.sleep(0.1)
+
+    # when: one entry is updated, one is deleted and a new one is created after the cutoff
+    entry_updated.title = "Drachenfliegen schwer gemacht"
+    generated_api_client.client.create_or_update_entry(event_id, entry_updated.id, entry_updated)
+    generated_api_client.client.delete_entry(event_id, entry_deleted.id)
+    entry_created = entry_factory.build(
+        datetime.datetime(2025, 1, 5, 12, 0, tzinfo=datetime.UTC), title="Drachen steigen lassen"
+    )
+    generated_api_client.client.create_or_update_entry(event_id, entry_created.id, entry_created)
+
+    # then: the updates since the cutoff contain exactly these changes
+    updates = generated_api_client.client.get_updates(event_id, since=cutoff)
+    assert [e.id for e in updates.changed_entries] == [entry_updated.id, entry_created.id]
+    assert updates.changed_entries[0].title == "Drachenfliegen schwer gemacht"
+    assert updates.deleted_entries == [entry_deleted.id]
+    assert updates.rooms is None
+
+    # and: without cutoff, all current entries and rooms are returned
+    updates = generated_api_client.client.get_updates(event_id)
+    assert [e.id for e in updates.changed_entries] == [entry_unchanged.id, entry_updated.id, entry_created.id]
+    assert updates.deleted_entries == []
+    assert updates.rooms == []
+
+    # and: changing a room includes the list of rooms
+    room = kueaplan_api_client.Room(id=str(uuid.uuid4()), title="Test Room", description="")
+    generated_api_client.client.create_or_update_room(event_id, room.id, room)
+    updates = generated_api_client.client.get_updates(event_id, since=cutoff)
+    assert updates.rooms is not None
+    assert [r.id for r in updates.rooms] == [room.id]
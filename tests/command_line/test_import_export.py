@@ -1,7 +1,10 @@
+import json
 import re
 import subprocess
+import uuid
 from pathlib import Path
 
+import pytest
 from playwright.sync_api import Page, expect
 
 from ..ui import actions
@@ -38,3 +41,223 @@ def test_export_and_reimport(
     expect(page.get_by_text("nass und rutschig")).to_be_visible()
     # Entry
     expect(page.get_by_text("Beach-Volleyball", exact=True)).to_be_visible()
+
+
+def test_import_dry_run_dangling_category(
+    kueaplan_server_executable_or_skip: Path, tmp_path: Path, reset_database: None
+) -> None:
+    json_file = tmp_path / "export.json"
+    subprocess.run([kueaplan_server_executable_or_skip, "event", "export", "1", str(json_file)], check=True)
+    with open(json_file) as f:
+        data = json.load(f)
+    data["entries"].append(
+        {
+            "id": "019a0000-0000-7000-8000-000000000001",
+            "title": "Verwaister Eintrag",
+            "room": [],
+            "begin": "2025-01-03T12:00:00Z",
+            "end": "2025-01-03T13:00:00Z",
+            "category": "11111111-2222-3333-4444-555555555555",
+            "previousDates": [],
+        }
+    )
+    with open(json_file, "w") as f:
+        json.dump(data, f)
+
+    result = subprocess.run(
+        [kueaplan_server_executable_or_skip, "event", "import", "--dry-run", str(json_file)], stdout=subprocess.PIPE
+    )
+    assert result.returncode != 0
+    output = result.stdout.decode()
+    assert (
+        "Category 11111111-2222-3333-4444-555555555555 of entry 019a0000-0000-7000-8000-000000000001 does not exist"
+        in output
+    )
+
+    # Without dangling reference, the dry run succeeds but does not import the event
+    data["entries"].pop()
+    with open(json_file, "w") as f:
+        json.dump(data, f)
+    result = subprocess.run(
+        [kueaplan_server_executable_or_skip, "event", "import", "--dry-run", str(json_file)],
+        check=True,
+        stdout=subprocess.PIPE,
+    )
+    assert "Nothing has been imported" in result.stdout.decode()
+    result = subprocess.run([kueaplan_server_executable_or_skip, "event", "list"], check=True, stdout=subprocess.PIPE)
+    assert result.stdout.decode().count("TestEvent") == 1
+
+
+def test_import_preview(
+    page: Page, kueaplan_server_executable_or_skip: Path, tmp_path: Path, reset_database: None
+) -> None:
+    actions.login(page, 1, "orga")
+    actions.add_category(page, CATEGORY_SPORT)
+    actions.add_room(page, ROOM_SPORTPLAETZE)
+    actions.add_entry(page, ENTRY_BEACH_VOLLEYBALL)
+
+    json_file = tmp_path / "export.json"
+    subprocess.run([kueaplan_server_executable_or_skip, "event", "export", "1", str(json_file)], check=True)
+    with open(json_file) as f:
+        data = json.load(f)
+    changed_entry = next(e for e in data["entries"] if e["title"] == "Beach-Volleyball")
+    changed_entry["title"] = "Beach-Volleyball für Anfänger"
+    data["rooms"].append({"id": "019a0000-0000-7000-8000-000000000002", "title": "Neuer Raum", "description": ""})
+    with open(json_file, "w") as f:
+        json.dump(data, f)
+
+    result = subprocess.run(
+        [kueaplan_server_executable_or_skip, "event", "import", "--keep-uuids", "--preview", str(json_file)],
+        check=True,
+        stdout=subprocess.PIPE,
+    )
+    output = result.stdout.decode()
+    assert "Rooms: 1 to be created, 0 to be updated, 0 orphaned" in output
+    assert "+ 019a0000-0000-7000-8000-000000000002 Neuer Raum" in output
+    assert "Entries: 0 to be created, 1 to be updated, 0 orphaned" in output
+    assert f"~ {changed_entry['id']} Beach-Volleyball für Anfänger (changed: title)" in output
+    assert "Nothing has been imported" in output
+    result = subprocess.run([kueaplan_server_executable_or_skip, "event", "list"], check=True, stdout=subprocess.PIPE)
+    assert result.stdout.decode().count("TestEvent") == 1
+
+
+IMPORT_ENTRY_ID = "019a0000-0000-7000-8000-000000000003"
+IMPORT_ROOM_ID = "019a0000-0000-7000-8000-000000000004"
+IMPORT_CATEGORY_ID = "019a0000-0000-7000-8000-000000000005"
+IMPORT_PREVIOUS_DATE_ID = "019a0000-0000-7000-8000-000000000006"
+
+
+def _write_import_file_with_entry(
+    kueaplan_server_executable: Path,
+    json_file: Path,
+    entry_title: str,
+    entry_id: str = IMPORT_ENTRY_ID,
+    room_id: str = IMPORT_ROOM_ID,
+    category_id: str = IMPORT_CATEGORY_ID,
+) -> None:
+    """Write an export of the test event with a single room, category and entry (with fixed ids) and a previous date of
+    the entry to `json_file`, such that importing the file multiple times with `--keep-uuids` results in conflicts."""
+    subprocess.run([kueaplan_server_executable, "event", "export", "1", str(json_file)], check=True)
+    with open(json_file) as f:
+        data = json.load(f)
+    data["categories"] = [{**data["categories"][0], "id": category_id}]
+    data["rooms"] = [{"id": room_id, "title": "Importierter Raum", "description": ""}]
+    data["announcements"] = []
+    data["event"]["slug"] = None
+    data["entries"] = [
+        {
+            "id": entry_id,
+            "title": entry_title,
+            "room": [room_id],
+            "begin": "2025-01-03T12:00:00Z",
+            "end": "2025-01-03T13:00:00Z",
+            "category": category_id,
+            "previousDates": [
+                {
+                    "id": IMPORT_PREVIOUS_DATE_ID,
+                    "begin": "2025-01-02T12:00:00Z",
+                    "end": "2025-01-02T13:00:00Z",
+                    "room": [room_id],
+                }
+            ],
+        }
+    ]
+    with open(json_file, "w") as f:
+        json.dump(data, f)
+
+
+def _entry_titles(kueaplan_server_executable: Path, tmp_path: Path, event_id: int) -> list[str]:
+    json_file = tmp_path / f"export_{event_id}.json"
+    subprocess.run([kueaplan_server_executable, "event", "export", str(event_id), str(json_file)], check=True)
+    with open(json_file) as f:
+        return [entry["title"] for entry in json.load(f)["entries"]]
+
+
+@pytest.mark.parametrize("on_conflict", [None, "skip", "fail"])
+def test_import_on_conflict(
+    on_conflict: str | None, kueaplan_server_executable_or_skip: Path, tmp_path: Path, reset_database: None
+) -> None:
+    json_file = tmp_path / "import.json"
+    _write_import_file_with_entry(kueaplan_server_executable_or_skip, json_file, "Original")
+    result = subprocess.run(
+        [kueaplan_server_executable_or_skip, "event", "import", "--keep-uuids", str(json_file)],
+        check=True,
+        stdout=subprocess.PIPE,
+    )
+    match = re.search(rb"imported successfully with id (\d+)", result.stdout)
+    assert match
+    first_event_id = int(match.group(1))
+
+    # Import the same room, category and entry ids again
+    _write_import_file_with_entry(kueaplan_server_executable_or_skip, json_file, "Neu importiert")
+    result = subprocess.run(
+        [kueaplan_server_executable_or_skip, "event", "import", "--keep-uuids"]
+        + (["--on-conflict", on_conflict] if on_conflict is not None else [])
+        + [str(json_file)],
+        stdout=subprocess.PIPE,
+        stderr=subprocess.PIPE,
+    )
+    match = re.search(rb"imported successfully with id (\d+)", result.stdout)
+    # Existing elements are never modified
+    assert _entry_titles(kueaplan_server_executable_or_skip, tmp_path, first_event_id) == ["Original"]
+
+    if on_conflict != "skip":
+        # Failing is the default. Elements of other events cannot be overwritten.
+        assert result.returncode != 0
+        assert not match
+        stderr = result.stderr.decode()
+        for element_id in (IMPORT_ENTRY_ID, IMPORT_ROOM_ID, IMPORT_CATEGORY_ID, IMPORT_PREVIOUS_DATE_ID):
+            assert element_id in stderr
+        return
+
+    assert result.returncode == 0
+    assert match
+    assert _entry_titles(kueaplan_server_executable_or_skip, tmp_path, int(match.group(1))) == []
+
+
+def test_import_skip_dependent_elements(
+    kueaplan_server_executable_or_skip: Path, tmp_path: Path, reset_database: None
+) -> None:
+    json_file = tmp_path / "import.json"
+    _write_import_file_with_entry(kueaplan_server_executable_or_skip, json_file, "Original")
+    subprocess.run([kueaplan_server_executable_or_skip, "event", "import", "--keep-uuids", str(json_file)], check=True)
+
+    # A new entry in the existing room and category is skipped together with them
+    _write_import_file_with_entry(
+        kueaplan_server_executable_or_skip, json_file, "Neu", entry_id="019a0000-0000-7000-8000-000000000013"
+    )
+    result = subprocess.run(
+        [kueaplan_server_executable_or_skip, "event", "import", "--keep-uuids"]
+        + ["--on-conflict", "skip", str(json_file)],
+        check=True,
+        stdout=subprocess.PIPE,
+    )
+    match = re.search(rb"imported successfully with id (\d+)", result.stdout)
+    assert match
+    assert _entry_titles(kueaplan_server_executable_or_skip, tmp_path, int(match.group(1))) == []
+
+    # With new room and category, only the existing previous date is skipped
+    _write_import_file_with_entry(
+        kueaplan_server_executable_or_skip,
+        json_file,
+        "Neu",
+        entry_id="019a0000-0000-7000-8000-000000000023",
+        room_id="019a0000-0000-7000-8000-000000000024",
+        category_id="019a0000-0000-7000-8000-000000000025",
+    )
+    result = subprocess.run(
+        [kueaplan_server_executable_or_skip, "event", "import", "--keep-uuids"]
+        + ["--on-conflict", "skip", str(json_file)],
+        check=True,
+        stdout=subprocess.PIPE,
+    )
+    match = re.search(rb"imported successfully with id (\d+)", result.stdout)
+    assert match
+    export_file = tmp_path / "export.json"
+    subprocess.run(
+        [kueaplan_server_executable_or_skip, "event", "export", match.group(1).decode(), str(export_file)], check=True
+    )
+    with open(export_file) as f:
+        entries = json.load(f)["entries"]
+    assert [entry["title"] for entry in entries] == ["Neu"]
+    assert entries[0]["previousDates"] == []
@@ -0,0 +1,101 @@
+import os
+import shutil
+import subprocess
+from pathlib import Path
+
+ORPHAN_SEED_SQL = """
+    INSERT INTO rooms (id, title, description, event_id, deleted)
+        VALUES ('0197aaaa-0000-7000-8000-0000000000a1', 'Gelöschter Raum', '', 1, true);
+    INSERT INTO categories (id, title, icon, color, event_id, deleted, is_official, sort_key)
+        VALUES ('0197aaaa-0000-7000-8000-0000000000c1', 'Gelöschte Kategorie', '', 'aabbcc', 1, true, false, 1);
+    -- entry in deleted room
+    INSERT INTO entries (id, title, description, responsible_person, event_id, begin, "end", category)
+        VALUES ('0197aaaa-0000-7000-8000-0000000000e1', 'Im gelöschten Raum', '', '', 1,
+                '2025-01-03 12:00+00', '2025-01-03 13:00+00', '019774dc-81c4-7862-a9ba-63de3d726010');
+    INSERT INTO entry_rooms (entry_id, room_id)
+        VALUES ('0197aaaa-0000-7000-8000-0000000000e1', '0197aaaa-0000-7000-8000-0000000000a1');
+    -- entry in deleted category
+    INSERT INTO entries (id, title, description, responsible_person, event_id, begin, "end", category)
+        VALUES ('0197aaaa-0000-7000-8000-0000000000e2', 'In gelöschter Kategorie', '', '', 1,
+                '2025-01-03 12:00+00', '2025-01-03 13:00+00', '0197aaaa-0000-7000-8000-0000000000c1');
+    -- previous date in deleted room
+    INSERT INTO previous_dates (id, entry_id, comment, begin, "end")
+        VALUES ('0197aaaa-0000-7000-8000-0000000000d1', '0197aaaa-0000-7000-8000-0000000000e1', '',
+                '2025-01-02 12:00+00', '2025-01-02 13:00+00');
+    INSERT INTO previous_date_rooms (previous_date_id, room_id)
+        VALUES ('0197aaaa-0000-7000-8000-0000000000d1', '0197aaaa-0000-7000-8000-0000000000a1');
+    -- announcement for deleted room
+    INSERT INTO announcements (id, event_id, announcement_type, text, show_with_days, show_with_categories,
+                               show_with_all_categories, show_with_rooms, show_with_all_rooms, sort_key)
+        VALUES ('0197aaaa-0000-7000-8000-0000000000b1', 1, 0, 'Hinweis', false, false, false, true, false, 0);
+    INSERT INTO announcement_rooms (announcement_id, room_id)
+        VALUES ('0197aaaa-0000-7000-8000-0000000000b1', '0197aaaa-0000-7000-8000-0000000000a1');
+    -- event without categories
+    UPDATE categories SET deleted = true WHERE event_id = 2;
+"""
+
+
+def _execute_sql(sql: str) -> None:
+    psql_path = shutil.which("psql")
+    assert psql_path is not None
+    subprocess.run(
+        [psql_path, "-v", "ON_ERROR_STOP=1", "-c", sql, os.environ["DATABASE_URL"]], check=True, capture_output=True
+    )
+
+
+def test_check_integrity_clean_database(kueaplan_server_executable_or_skip: Path, reset_database: None) -> None:
+    result = subprocess.run(
+        [str(kueaplan_server_executable_or_skip), "check-integrity"], check=True, stdout=subprocess.PIPE
+    )
+    assert "No integrity problems found." in result.stdout.decode()
+
+
+def test_check_integrity_reports_orphans(kueaplan_server_executable_or_skip: Path, reset_database: None) -> None:
+    _execute_sql(ORPHAN_SEED_SQL)
+
+    result = subprocess.run(
+        [str(kueaplan_server_executable_or_skip), "check-integrity"], check=True, stdout=subprocess.PIPE
+    )
+    output = result.stdout.decode()
+    assert (
+        "Entry 0197aaaa-0000-7000-8000-0000000000e1 references deleted room 0197aaaa-0000-7000-8000-0000000000a1"
+        in output
+    )
+    assert (
+        "Previous date 0197aaaa-0000-7000-8000-0000000000d1 references deleted room "
+        "0197aaaa-0000-7000-8000-0000000000a1" in output
+    )
+    assert (
+        "Announcement 0197aaaa-0000-7000-8000-0000000000b1 references deleted room "
+        "0197aaaa-0000-7000-8000-0000000000a1" in output
+    )
+    assert (
+        "Entry 0197aaaa-0000-7000-8000-0000000000e2 references deleted or foreign category "
+        "0197aaaa-0000-7000-8000-0000000000c1" in output
+    )
+    assert "Event 2 has no categories" in output
+    assert "Found 2 integrity problem(s) in total." in output
+    assert "Found 3 notice(s)" in output
+
+
+def test_check_integrity_deleted_room_references_are_notices(
+    kueaplan_server_executable_or_skip: Path, reset_database: None
+) -> None:
+    _execute_sql(
+        """
+        INSERT INTO rooms (id, title, description, event_id, deleted)
+            VALUES ('0197aaaa-0000-7000-8000-0000000000a1', 'Gelöschter Raum', '', 1, true);
+        INSERT INTO entries (id, title, description, responsible_person, event_id, begin, "end", category)
+            VALUES ('0197aaaa-0000-7000-8000-0000000000e1', 'Im gelöschten Raum', '', '', 1,
+                    '2025-01-03 12:00+00', '2025-01-03 13:00+00', '019774dc-81c4-7862-a9ba-63de3d726010');
+        INSERT INTO entry_rooms (entry_id, room_id)
+            VALUES ('0197aaaa-0000-7000-8000-0000000000e1', '0197aaaa-0000-7000-8000-0000000000a1');
+        """
+    )
+
+    result = subprocess.run(
+        [str(kueaplan_server_executable_or_skip), "check-integrity"], check=True, stdout=subprocess.PIPE
+    )
+    output = result.stdout.decode()
+    assert "No integrity problems found." in output
+    assert "(notice) Entry " in output
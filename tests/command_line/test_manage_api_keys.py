@@ -0,0 +1,84 @@
+import json
+import re
+import subprocess
+import urllib.request
+from pathlib import Path
+
+import pytest
+
+from ..conftest import ApiClientWrapper
+
+
+def test_api_key_grants_read_access(
+    generated_api_client: ApiClientWrapper, kueaplan_server_executable_or_skip: Path, reset_database: None
+) -> None:
+    import kueaplan_api_client
+
+    result = subprocess.run(
+        [str(kueaplan_server_executable_or_skip), "api-key", "create", "test", "--comment", "Website"],
+        check=True,
+        stdout=subprocess.PIPE,
+    )
+    api_key = result.stdout.decode().strip().splitlines()[-1]
+    generated_api_client.client.api_client.configuration.access_token = api_key
+
+    assert generated_api_client.client.list_entries(1) == []
+    assert len(generated_api_client.client.list_categories(1)) == 1
+
+    # The API key is read-only and only valid for its event
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.list_passphrases(1)
+    assert excinfo.value.status == 403
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.list_entries(2)
+    assert excinfo.value.status == 403
+
+    result = subprocess.run(
+        [str(kueaplan_server_executable_or_skip), "api-key", "list", "test"], check=True, stdout=subprocess.PIPE
+    )
+    output = result.stdout.decode()
+    assert re.search(r"\|\s*1\s*Website", output)
+    assert api_key not in output
+
+
+@pytest.mark.parametrize("scheme", ["Bearer", "bearer", "BEARER"])
+def test_api_key_auth_scheme_is_case_insensitive(
+    scheme: str, kueaplan_server_executable_or_skip: Path, reset_database: None
+) -> None:
+    result = subprocess.run(
+        [str(kueaplan_server_executable_or_skip), "api-key", "create", "1"], check=True, stdout=subprocess.PIPE
+    )
+    api_key = result.stdout.decode().strip().splitlines()[-1]
+
+    request = urllib.request.Request(
+        "http://localhost:9099/api/v1/events/1/entries", headers={"Authorization": f"{scheme} {api_key}"}
+    )
+    with urllib.request.urlopen(request) as response:
+        assert json.loads(response.read()) == []
+
+
+def test_revoked_api_key_is_rejected(
+    generated_api_client: ApiClientWrapper, kueaplan_server_executable_or_skip: Path, reset_database: None
+) -> None:
+    import kueaplan_api_client
+
+    result = subprocess.run(
+        [str(kueaplan_server_executable_or_skip), "api-key", "create", "1"], check=True, stdout=subprocess.PIPE
+    )
+    output = result.stdout.decode()
+    api_key_id = re.search(r"Created API key (\d+)", output)
+    assert api_key_id
+    generated_api_client.client.api_client.configuration.access_token = output.strip().splitlines()[-1]
+    generated_api_client.client.list_entries(1)
+
+    subprocess.run(
+        [str(kueaplan_server_executable_or_skip), "api-key", "revoke", "1", api_key_id.group(1)], check=True
+    )
+
+    with pytest.raises(kueaplan_api_client.ApiException) as excinfo:
+        generated_api_client.client.list_entries(1)
+    assert excinfo.value.status == 403
+
+    # The session token still works alongside the revoked API key
+    generated_api_client.login(1, "user")
+    generated_api_client.client.list_entries(1)
@@ -5,6 +5,7 @@ from pathlib import Path
 
 from playwright.sync_api import Page, expect
 
+from ..conftest import ApiClientWrapper
 from ..ui import actions
 from . import util
 
@@ -17,6 +18,58 @@ def test_list_existing_event(kueaplan_server_executable_or_skip: Path, reset_dat
     assert re.search(r"1\s*test\s*TestEvent\s*2025-01-01", output)
 
 
+def test_show_event(kueaplan_server_executable_or_skip: Path, reset_database: None) -> None:
+    result = subprocess.run(
+        [str(kueaplan_server_executable_or_skip), "event", "show", "test"], check=True, stdout=subprocess.PIPE
+    )
+    output = result.stdout.decode()
+    assert re.search(r"^title:\s+TestEvent$", output, re.MULTILINE)
+    assert re.search(r"^timezone:\s+Europe/Berlin$", output, re.MULTILINE)
+    for label in ("entries", "cancelled entries", "rooms", "categories", "announcements", "passphrases"):
+        assert re.search(rf"^{label}:\s+\d+$", output, re.MULTILINE), label
+
+
+def test_list_events_filtered(kueaplan_server_executable_or_skip: Path, reset_database: None) -> None:
+    def list_event_ids(*args: str) -> list[int]:
+        result = subprocess.run(
+            [str(kueaplan_server_executable_or_skip), "event", "list", *args], check=True, stdout=subprocess.PIPE
+        )
+        return [int(event_id) for event_id in re.findall(r"^\|\s*(\d+)\s", result.stdout.decode(), re.MULTILINE)]
+
+    assert list_event_ids() == [1, 2]
+    # title filter is case-insensitive
+    assert list_event_ids("--filter", "OTHER") == [2]
+    assert list_event_ids("--filter", "event") == [1, 2]
+    assert list_event_ids("--filter", "%") == []
+    assert list_event_ids("--after", "2025-03-01") == [2]
+    assert list_event_ids("--before", "2025-03-01") == [1]
+    assert list_event_ids("--filter", "other", "--before", "2025-03-01") == []
+
+
+def test_archive_event(
+    kueaplan_server_executable_or_skip: Path, generated_api_client: ApiClientWrapper, reset_database: None
+) -> None:
+    result = subprocess.run(
+        [str(kueaplan_server_executable_or_skip), "event", "archive", "other"], check=True, stdout=subprocess.PIPE
+    )
+    assert "is now archived" in result.stdout.decode()
+
+    # Archived events are hidden by default
+    events = generated_api_client.client.list_events()
+    assert [event.id for event in events] == [1]
+    events = generated_api_client.client.list_events(include_archived=True)
+    assert [event.id for event in events] == [1, 2]
+    assert events[1].archived
+
+    # Un-archive the event again
+    result = subprocess.run(
+        [str(kueaplan_server_executable_or_skip), "event", "archive", "2"], check=True, stdout=subprocess.PIPE
+    )
+    assert "is no longer archived" in result.stdout.decode()
+    events = generated_api_client.client.list_events()
+    assert [event.id for event in events] == [1, 2]
+
+
 def test_create_event(page: Page, kueaplan_server_executable_or_skip: Path, reset_database: None) -> None:
     cmd = [str(kueaplan_server_executable_or_skip), "event", "create"]
     process = subprocess.Popen(cmd, stdout=subprocess.PIPE, stdin=subprocess.PIPE)
@@ -81,12 +134,18 @@ def test_create_event_retry(kueaplan_server_executable_or_skip: Path) -> None:
 
         output = util.wait_for_interactive_prompt(process.stdout)
         assert "Error".encode() in output
-        assert "invalid characters".encode() in output
+        assert "invalid date".encode() in output
         assert "begin".encode() in output
-        process.stdin.write("2025-06-06\n".encode())
+        process.stdin.write("06.06.2025\n".encode())
         process.stdin.flush()
 
-        util.wait_for_prompt_and_type(process, "end", "2025-06-09")
+        util.wait_for_prompt_and_type(process, "end", "01.06.2025")
+        output = util.wait_for_interactive_prompt(process.stdout)
+        assert "Error".encode() in output
+        assert "must not be before event begin".encode() in output
+        assert "end".encode() in output
+        process.stdin.write("2025-06-09\n".encode())
+        process.stdin.flush()
         process.terminate()
         process.wait(1)
     finally:
@@ -120,3 +179,35 @@ def test_delete_event(page: Page, kueaplan_server_executable_or_skip: Path, rese
     expect(page.get_by_text("Not found")).to_be_visible()
     page.goto("http://localhost:9099/pa25")
     expect(page.get_by_text("Pfingsten25")).to_be_visible()
+
+
+def test_generate_slugs(
+    kueaplan_server_executable_or_skip: Path, generated_api_client: ApiClientWrapper, reset_database: None
+) -> None:
+    # given: two events with the same title and without slug
+    for _ in range(2):
+        cmd = [str(kueaplan_server_executable_or_skip), "event", "create"]
+        process = subprocess.Popen(cmd, stdout=subprocess.PIPE, stdin=subprocess.PIPE)
+        try:
+            util.wait_for_prompt_and_type(process, "event title", "Küchen-Party")
+            util.wait_for_prompt_and_type(process, "event slug", "")
+            util.wait_for_prompt_and_type(process, "begin", "2025-06-06")
+            util.wait_for_prompt_and_type(process, "end", "2025-06-09")
+            util.wait_for_prompt_and_type(process, "admin passphrase", "n")
+            process.wait(1)
+            if process.returncode:
+                raise subprocess.CalledProcessError(process.returncode, cmd)
+        finally:
+            process.terminate()
+            process.wait(1)
+            process.kill()
+
+    # when: generating the missing slugs
+    result = subprocess.run(
+        [str(kueaplan_server_executable_or_skip), "event", "generate-slugs"], check=True, stdout=subprocess.PIPE
+    )
+    assert "kuechen-party" in result.stdout.decode()
+
+    # then: both events got distinct slugs and the existing slugs are unchanged
+    events = generated_api_client.client.list_events()
+    assert sorted(event.slug for event in events) == ["kuechen-party", "kuechen-party-2", "other", "test"]
@@ -0,0 +1,59 @@
+import re
+import subprocess
+import urllib.error
+import urllib.parse
+import urllib.request
+from pathlib import Path
+
+
+def _get_feed(event_id: int, feed: str, token: str | None) -> int:
+    query = f"?{urllib.parse.urlencode({'token': token})}" if token is not None else ""
+    try:
+        with urllib.request.urlopen(f"http://localhost:9099/events/{event_id}/{feed}{query}") as response:
+            return response.status
+    except urllib.error.HTTPError as e:
+        return e.code
+
+
+def test_feed_token_grants_calendar_access(kueaplan_server_executable_or_skip: Path, reset_database: None) -> None:
+    result = subprocess.run(
+        [str(kueaplan_server_executable_or_skip), "feed-token", "create", "test", "--comment", "Website"],
+        check=True,
+        stdout=subprocess.PIPE,
+    )
+    feed_token = result.stdout.decode().strip().splitlines()[-1]
+
+    assert _get_feed(1, "ical", feed_token) == 200
+    assert _get_feed(1, "frab-xml", feed_token) == 200
+    # The feed token is only valid for its event
+    assert _get_feed(2, "ical", feed_token) == 403
+
+    result = subprocess.run(
+        [str(kueaplan_server_executable_or_skip), "feed-token", "list", "test"], check=True, stdout=subprocess.PIPE
+    )
+    assert re.search(r"\|\s*1\s*User\s*Website", result.stdout.decode())
+
+
+def test_revoked_feed_token_is_rejected(kueaplan_server_executable_or_skip: Path, reset_database: None) -> None:
+    result = subprocess.run(
+        [str(kueaplan_server_executable_or_skip), "feed-token", "create", "1", "--role", "orga"],
+        check=True,
+        stdout=subprocess.PIPE,
+    )
+    output = result.stdout.decode()
+    feed_token_id = re.search(r"Created feed token (\d+)", output)
+    assert feed_token_id
+    feed_token = output.strip().splitlines()[-1]
+    assert _get_feed(1, "ical", feed_token) == 200
+
+    subprocess.run(
+        [str(kueaplan_server_executable_or_skip), "feed-token", "revoke", "1", feed_token_id.group(1)], check=True
+    )
+
+    assert _get_feed(1, "ical", feed_token) == 403
+    assert _get_feed(1, "frab-xml", feed_token) == 403
+
+
+def test_missing_or_tempered_feed_token_is_rejected(reset_database: None) -> None:
+    assert _get_feed(1, "ical", None) == 403
+    assert _get_feed(1, "ical", "feed-AAAA") == 403
@@ -1,3 +1,4 @@
+import json
 import re
 import subprocess
 import warnings
@@ -5,6 +6,7 @@ from pathlib import Path
 
 from playwright.sync_api import Page, expect
 
+from ..conftest import ApiClientWrapper
 from ..ui import actions
 from . import cli_actions, util
 
@@ -20,6 +22,9 @@ def test_list_existing_passphrases(kueaplan_server_executable_or_skip: Path, res
     assert re.search(r"TestEvent", output)
     assert re.search(r"\|\s*3\s*Admin\s*\*\*\*\*n", output)
     assert re.search(r"\|\s*4.*Link\s*1", output)
+    assert re.search(r"last used", output)
+    # None of the passphrases has been used in the fresh database
+    assert re.search(r"\|\s*3\s*Admin.*never", output)
 
 
 def test_list_existing_passphrases_by_event_slug(
@@ -48,6 +53,24 @@ def test_create_passphrase(page: Page, kueaplan_server_executable_or_skip: Path,
     expect(page.get_by_role("textbox", name="iCal-Link")).to_be_visible()
 
 
+def test_create_passphrase_batch(
+    generated_api_client: ApiClientWrapper, kueaplan_server_executable_or_skip: Path, reset_database: None
+) -> None:
+    result = subprocess.run(
+        [str(kueaplan_server_executable_or_skip), "passphrase", "create-batch", "test", "3", "orga", "--words", "3"],
+        check=True,
+        stdout=subprocess.PIPE,
+    )
+    output = result.stdout.decode()
+    passphrases = re.findall(r"^\s*\d+\s+([a-z]+-[a-z]+-[a-z]+)$", output, re.MULTILINE)
+    assert len(passphrases) == 3
+    assert len(set(passphrases)) == 3
+
+    # Check that the generated passphrases are usable for logging in
+    for passphrase in passphrases:
+        generated_api_client.login(1, passphrase)
+
+
 def test_delete_passphrase(page: Page, kueaplan_server_executable_or_skip: Path, reset_database: None) -> None:
     cmd = [str(kueaplan_server_executable_or_skip), "passphrase", "delete", "test", "1"]
     process = subprocess.Popen(cmd, stdout=subprocess.PIPE, stdin=subprocess.PIPE)
@@ -75,3 +98,91 @@ def test_delete_passphrase(page: Page, kueaplan_server_executable_or_skip: Path,
     page.get_by_role("button", name="Zum KüA-Plan").click()
     alert = page.get_by_role("alert").filter(has_text="Fehler")
     expect(alert).to_be_visible()
+
+
+def test_hash_existing_passphrases(
+    generated_api_client: ApiClientWrapper, kueaplan_server_executable_or_skip: Path, reset_database: None
+) -> None:
+    result = subprocess.run(
+        [str(kueaplan_server_executable_or_skip), "passphrase", "hash-existing"], check=True, stdout=subprocess.PIPE
+    )
+    assert "Hashed 4 passphrase(s)." in result.stdout.decode()
+
+    # Only the hash is kept for passphrases of roles other than 'user'
+    result = subprocess.run(
+        [str(kueaplan_server_executable_or_skip), "passphrase", "list", "1"], check=True, stdout=subprocess.PIPE
+    )
+    output = result.stdout.decode()
+    assert re.search(r"\|\s*1\s*User\s*\*\*\*r", output)
+    assert re.search(r"\|\s*3\s*Admin\s*\*{8}\s", output)
+
+    # Passphrases are still usable for logging in
+    for passphrase in ("user", "orga", "admin"):
+        generated_api_client.login(1, passphrase)
+    generated_api_client.login(2, "orga")
+
+    # Running the command again does not hash any passphrase twice
+    result = subprocess.run(
+        [str(kueaplan_server_executable_or_skip), "passphrase", "hash-existing"], check=True, stdout=subprocess.PIPE
+    )
+    assert "Hashed 0 passphrase(s)." in result.stdout.decode()
+
+
+def test_export_and_import_passphrases(
+    generated_api_client: ApiClientWrapper,
+    kueaplan_server_executable_or_skip: Path,
+    tmp_path: Path,
+    reset_database: None,
+) -> None:
+    # Hash passphrases first, to check that the stored hashes are taken over on import
+    subprocess.run([str(kueaplan_server_executable_or_skip), "passphrase", "hash-existing"], check=True)
+
+    backup_file = tmp_path / "passphrases.json"
+    result = subprocess.run(
+        [str(kueaplan_server_executable_or_skip), "passphrase", "export", "test", str(backup_file)],
+        check=True,
+        stdout=subprocess.PIPE,
+        input=b"y\nsecret\nsecret\n",
+    )
+    assert "store it securely" in result.stdout.decode()
+    assert backup_file.stat().st_mode & 0o777 == 0o600
+    data = json.loads(backup_file.read_text())
+    assert set(data.keys()) == {"iterations", "salt", "nonce", "ciphertext"}
+    assert "argon2" not in backup_file.read_text()
+
+    # A wrong password is rejected
+    result = subprocess.run(
+        [str(kueaplan_server_executable_or_skip), "passphrase", "import", "other", str(backup_file)],
+        stdout=subprocess.PIPE,
+        input=b"wrong\n",
+    )
+    assert result.returncode != 0
+
+    result = subprocess.run(
+        [str(kueaplan_server_executable_or_skip), "passphrase", "import", "other", str(backup_file)],
+        check=True,
+        stdout=subprocess.PIPE,
+        input=b"secret\ny\n",
+    )
+    assert "Imported 5 passphrase(s)." in result.stdout.decode()
+
+    # Importing the same passphrases again is rejected, because they would be ambiguous
+    result = subprocess.run(
+        [str(kueaplan_server_executable_or_skip), "passphrase", "import", "other", str(backup_file)],
+        stdout=subprocess.PIPE,
+        input=b"secret\ny\n",
+    )
+    assert result.returncode != 0
+
+    # The imported passphrases are usable for logging in to the other event
+    for passphrase in ("user", "orga", "admin"):
+        generated_api_client.login(2, passphrase)
+
+    # Derivable passphrases reference the newly created passphrases
+    result = subprocess.run(
+        [str(kueaplan_server_executable_or_skip), "passphrase", "list", "other"], check=True, stdout=subprocess.PIPE
+    )
+    output = result.stdout.decode()
+    match = re.search(r"\|\s*(\d+)\s*User\s*\*\*\*r", output)
+    assert match
+    assert re.search(rf"Link\s*{match.group(1)}\s", output)
@@ -1,3 +1,4 @@
+import datetime
 import json
 import logging
 import os
@@ -7,8 +8,9 @@ import subprocess
 import sys
 import time
 import types
+import uuid
 from pathlib import Path
-from typing import Generator, Optional
+from typing import Any, Generator, Optional
 
 import dotenv
 import pytest
@@ -74,12 +76,15 @@ def start_kueaplan_server(
     _restore_database_dump(os.environ["DATABASE_URL"], Path(__file__).parent / "database_dumps" / "minimal.sql")
 
     cmd = [str(kueaplan_server_executable), "serve"]
+    # Tests for server admin actions use the key from the environment, like with a manually started server
+    os.environ.setdefault("SERVER_ADMIN_API_KEY", secrets.token_urlsafe(20))
     env = dict(os.environ)
     env["LISTEN_PORT"] = "9099"
     env["LISTEN_ADDRESS"] = "127.0.0.1"
     env["ADMIN_NAME"] = "Anton Administrator"
     env["ADMIN_EMAIL"] = "anton@example.com"
     env["SECRET"] = secrets.token_urlsafe(20)
+    env["METRICS_ENABLED"] = "true"
     process = subprocess.Popen(cmd, env=env)
     time.sleep(2)
     returncode = process.poll()
@@ -151,6 +156,16 @@ def generated_api_client(generated_api_client_module: types.ModuleType) -> "ApiC
     return ApiClientWrapper(generated_api_client_module)
 
 
+@pytest.fixture(scope="function")
+def entry_factory(generated_api_client: "ApiClientWrapper") -> "EntryFactory":
+    return EntryFactory(generated_api_client)
+
+
+@pytest.fixture(scope="function")
+def announcement_factory(generated_api_client: "ApiClientWrapper") -> "AnnouncementFactory":
+    return AnnouncementFactory(generated_api_client)
+
+
 class ApiClientWrapper:
     def __init__(self, kueaplan_api_client: types.ModuleType):
         self.module = kueaplan_api_client
@@ -168,3 +183,59 @@ class ApiClientWrapper:
 
     def clear_login(self) -> None:
         del self.client.api_client.configuration.api_key["sessionTokenAuth"]
+
+
+# Default category of event 1 in the minimal.sql database dump
+DEFAULT_CATEGORY_ID = "019774dc-81c4-7862-a9ba-63de3d726010"
+
+
+class EntryFactory:
+    """Builds KüA-Plan entries for the tests.
+
+    Unless specified otherwise via the keyword arguments, entries last one hour and have the default category of event 1
+    and no rooms. Entries are created with the (logged-in) client of the `generated_api_client` fixture.
+    """
+
+    def __init__(self, api_client: ApiClientWrapper):
+        self.api_client = api_client
+
+    def build(
+        self, begin: datetime.datetime, end: Optional[datetime.datetime] = None, **fields: Any
+    ) -> "kueaplan_api_client.Entry":  # type: ignore  # noqa: F821
+        fields.setdefault("title", "Test Entry")
+        fields.setdefault("room", [])
+        fields.setdefault("category", DEFAULT_CATEGORY_ID)
+        fields.setdefault("previous_dates", [])
+        if end is None:
+            end = begin + datetime.timedelta(hours=1)
+        return self.api_client.module.Entry(
+            id=str(uuid.uuid4()), begin=begin.isoformat(), end=end.isoformat(), **fields
+        )
+
+    def create(
+        self, event_id: int, begin: datetime.datetime, end: Optional[datetime.datetime] = None, **fields: Any
+    ) -> "kueaplan_api_client.Entry":  # type: ignore  # noqa: F821
+        entry = self.build(begin, end, **fields)
+        self.api_client.client.create_or_update_entry(event_id, entry.id, entry)
+        return entry
+
+
+class AnnouncementFactory:
+    """Creates announcements for the tests.
+
+    Unless specified otherwise via the keyword arguments, announcements are of type "info" and shown with the main list
+    of the days. Announcements are created with the (logged-in) client of the `generated_api_client` fixture.
+    """
+
+    def __init__(self, api_client: ApiClientWrapper):
+        self.api_client = api_client
+
+    def create(
+        self, event_id: int, text: str, **fields: Any
+    ) -> "kueaplan_api_client.Announcement":  # type: ignore  # noqa: F821
+        fields.setdefault("announcementType", "info")
+        fields.setdefault("show_with_days", True)
+        fields.setdefault("sort_key", 0)
+        announcement = self.api_client.module.Announcement(id=str(uuid.uuid4()), text=text, **fields)
+        self.api_client.client.create_or_update_announcement(event_id, announcement.id, announcement)
+        return announcement
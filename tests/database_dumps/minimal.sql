@@ -27,11 +27,16 @@ ALTER TABLE ONLY public.events DROP CONSTRAINT events_subsequent_event_id_fkey;
 ALTER TABLE ONLY public.events DROP CONSTRAINT events_preceding_event_id_fkey;
 ALTER TABLE ONLY public.event_passphrases DROP CONSTRAINT event_passphrases_event_id_fkey;
 ALTER TABLE ONLY public.event_passphrases DROP CONSTRAINT event_passphrases_derivable_from_passphrase_fkey;
+ALTER TABLE ONLY public.entry_templates DROP CONSTRAINT entry_templates_event_id_fkey;
 ALTER TABLE ONLY public.entry_rooms DROP CONSTRAINT entry_rooms_room_id_fkey;
 ALTER TABLE ONLY public.entry_rooms DROP CONSTRAINT entry_rooms_entry_id_fkey;
+ALTER TABLE ONLY public.entry_audit_log DROP CONSTRAINT entry_audit_log_passphrase_id_fkey;
+ALTER TABLE ONLY public.entry_audit_log DROP CONSTRAINT entry_audit_log_event_id_fkey;
+ALTER TABLE ONLY public.entry_audit_log DROP CONSTRAINT entry_audit_log_entry_id_fkey;
 ALTER TABLE ONLY public.entries DROP CONSTRAINT entries_event_id_fkey;
 ALTER TABLE ONLY public.entries DROP CONSTRAINT entries_category_fkey;
 ALTER TABLE ONLY public.categories DROP CONSTRAINT categories_event_id_fkey;
+ALTER TABLE ONLY public.api_keys DROP CONSTRAINT api_keys_event_id_fkey;
 ALTER TABLE ONLY public.announcements DROP CONSTRAINT announcements_event_id_fkey;
 ALTER TABLE ONLY public.announcement_rooms DROP CONSTRAINT announcement_rooms_room_id_fkey;
 ALTER TABLE ONLY public.announcement_rooms DROP CONSTRAINT announcement_rooms_announcement_id_fkey;
@@ -45,23 +50,32 @@ DROP TRIGGER sync_lastmod ON public.announcements;
 DROP INDEX public.rooms_event_id_title_idx;
 DROP INDEX public.previous_dates_entry_id_idx;
 DROP INDEX public.event_passphrases_event_id_passphrase_idx;
+DROP INDEX public.entry_templates_event_id_idx;
+DROP INDEX public.entry_audit_log_entry_id_timestamp_idx;
 DROP INDEX public.entries_event_id_begin_idx;
 DROP INDEX public.categories_event_id_sort_key_idx;
+DROP INDEX public.api_keys_event_id_idx;
 DROP INDEX public.announcements_event_id_sort_key_idx;
 ALTER TABLE ONLY public.rooms DROP CONSTRAINT rooms_pkey;
 ALTER TABLE ONLY public.previous_dates DROP CONSTRAINT previous_dates_pkey;
 ALTER TABLE ONLY public.previous_date_rooms DROP CONSTRAINT previous_date_rooms_pkey;
 ALTER TABLE ONLY public.events DROP CONSTRAINT events_pkey;
 ALTER TABLE ONLY public.event_passphrases DROP CONSTRAINT event_passphrases_pkey;
+ALTER TABLE ONLY public.entry_templates DROP CONSTRAINT entry_templates_pkey;
 ALTER TABLE ONLY public.entry_rooms DROP CONSTRAINT entry_rooms_pkey;
+ALTER TABLE ONLY public.entry_audit_log DROP CONSTRAINT entry_audit_log_pkey;
 ALTER TABLE ONLY public.entries DROP CONSTRAINT entries_pkey;
 ALTER TABLE ONLY public.categories DROP CONSTRAINT categories_pkey;
+ALTER TABLE ONLY public.api_keys DROP CONSTRAINT api_keys_pkey;
+ALTER TABLE ONLY public.api_keys DROP CONSTRAINT api_keys_key_hash_key;
 ALTER TABLE ONLY public.announcements DROP CONSTRAINT announcements_pkey;
 ALTER TABLE ONLY public.announcement_rooms DROP CONSTRAINT announcement_rooms_pkey;
 ALTER TABLE ONLY public.announcement_categories DROP CONSTRAINT announcement_categories_pkey;
 ALTER TABLE ONLY public.__diesel_schema_migrations DROP CONSTRAINT __diesel_schema_migrations_pkey;
 ALTER TABLE public.events ALTER COLUMN id DROP DEFAULT;
 ALTER TABLE public.event_passphrases ALTER COLUMN id DROP DEFAULT;
+ALTER TABLE public.entry_audit_log ALTER COLUMN id DROP DEFAULT;
+ALTER TABLE public.api_keys ALTER COLUMN id DROP DEFAULT;
 DROP TABLE public.rooms;
 DROP TABLE public.previous_dates;
 DROP TABLE public.previous_date_rooms;
@@ -69,9 +83,14 @@ DROP SEQUENCE public.events_id_seq;
 DROP TABLE public.events;
 DROP SEQUENCE public.event_passphrases_id_seq;
 DROP TABLE public.event_passphrases;
+DROP TABLE public.entry_templates;
 DROP TABLE public.entry_rooms;
+DROP SEQUENCE public.entry_audit_log_id_seq;
+DROP TABLE public.entry_audit_log;
 DROP TABLE public.entries;
 DROP TABLE public.categories;
+DROP SEQUENCE public.api_keys_id_seq;
+DROP TABLE public.api_keys;
 DROP TABLE public.announcements;
 DROP TABLE public.announcement_rooms;
 DROP TABLE public.announcement_categories;
@@ -145,10 +164,53 @@ CREATE TABLE public.announcements (
     sort_key integer DEFAULT 0 NOT NULL,
     deleted boolean DEFAULT false NOT NULL,
     last_updated timestamp with time zone DEFAULT now() NOT NULL,
-    CONSTRAINT announcements_date_range CHECK (((begin_date IS NULL) OR (end_date IS NULL) OR (end_date >= begin_date)))
+    begin_time time without time zone,
+    end_time time without time zone,
+    CONSTRAINT announcements_date_range CHECK (((begin_date IS NULL) OR (end_date IS NULL) OR (end_date >= begin_date))),
+    CONSTRAINT announcements_time_window CHECK (((begin_time IS NULL) OR (end_time IS NULL) OR (begin_time <> end_time)))
 );
 
 
+--
+-- Name: api_keys; Type: TABLE; Schema: public; Owner: -
+--
+
+CREATE TABLE public.api_keys (
+    id integer NOT NULL,
+    event_id integer NOT NULL,
+    key_hash character varying NOT NULL,
+    comment character varying DEFAULT ''::character varying NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL
+);
+
+
+--
+-- Name: COLUMN api_keys.key_hash; Type: COMMENT; Schema: public; Owner: -
+--
+
+COMMENT ON COLUMN public.api_keys.key_hash IS 'hex-encoded SHA-256 hash of the api key';
+
+
+--
+-- Name: api_keys_id_seq; Type: SEQUENCE; Schema: public; Owner: -
+--
+
+CREATE SEQUENCE public.api_keys_id_seq
+    AS integer
+    START WITH 1
+    INCREMENT BY 1
+    NO MINVALUE
+    NO MAXVALUE
+    CACHE 1;
+
+
+--
+-- Name: api_keys_id_seq; Type: SEQUENCE OWNED BY; Schema: public; Owner: -
+--
+
+ALTER SEQUENCE public.api_keys_id_seq OWNED BY public.api_keys.id;
+
+
 --
 -- Name: categories; Type: TABLE; Schema: public; Owner: -
 --
@@ -189,10 +251,58 @@ CREATE TABLE public.entries (
     is_cancelled boolean DEFAULT false NOT NULL,
     state integer DEFAULT 0 NOT NULL,
     orga_comment character varying DEFAULT ''::character varying NOT NULL,
+    color_override character varying,
+    links jsonb,
+    is_tentative boolean DEFAULT false NOT NULL,
+    orga_only boolean DEFAULT false NOT NULL,
+    last_updated_by_role integer,
+    open_ended boolean DEFAULT false NOT NULL,
     CONSTRAINT entries_time_range CHECK (("end" >= begin))
 );
 
 
+--
+-- Name: entry_audit_log; Type: TABLE; Schema: public; Owner: -
+--
+
+CREATE TABLE public.entry_audit_log (
+    id integer NOT NULL,
+    entry_id uuid NOT NULL,
+    event_id integer NOT NULL,
+    action integer NOT NULL,
+    passphrase_id integer,
+    "timestamp" timestamp with time zone DEFAULT now() NOT NULL,
+    changed_fields character varying[] DEFAULT '{}'::character varying[] NOT NULL
+);
+
+
+--
+-- Name: COLUMN entry_audit_log.passphrase_id; Type: COMMENT; Schema: public; Owner: -
+--
+
+COMMENT ON COLUMN public.entry_audit_log.passphrase_id IS 'if NULL, the modification has been done via the command line interface or the passphrase has been deleted';
+
+
+--
+-- Name: entry_audit_log_id_seq; Type: SEQUENCE; Schema: public; Owner: -
+--
+
+CREATE SEQUENCE public.entry_audit_log_id_seq
+    AS integer
+    START WITH 1
+    INCREMENT BY 1
+    NO MINVALUE
+    NO MAXVALUE
+    CACHE 1;
+
+
+--
+-- Name: entry_audit_log_id_seq; Type: SEQUENCE OWNED BY; Schema: public; Owner: -
+--
+
+ALTER SEQUENCE public.entry_audit_log_id_seq OWNED BY public.entry_audit_log.id;
+
+
 --
 -- Name: entry_rooms; Type: TABLE; Schema: public; Owner: -
 --
@@ -203,6 +313,25 @@ CREATE TABLE public.entry_rooms (
 );
 
 
+--
+-- Name: entry_templates; Type: TABLE; Schema: public; Owner: -
+--
+
+CREATE TABLE public.entry_templates (
+    id uuid NOT NULL,
+    event_id integer NOT NULL,
+    name character varying NOT NULL,
+    entry_data jsonb NOT NULL
+);
+
+
+--
+-- Name: COLUMN entry_templates.entry_data; Type: COMMENT; Schema: public; Owner: -
+--
+
+COMMENT ON COLUMN public.entry_templates.entry_data IS 'default values for the fields of new entries, without date and time';
+
+
 --
 -- Name: event_passphrases; Type: TABLE; Schema: public; Owner: -
 --
@@ -215,7 +344,9 @@ CREATE TABLE public.event_passphrases (
     derivable_from_passphrase integer,
     comment character varying DEFAULT ''::character varying NOT NULL,
     valid_from timestamp with time zone,
-    valid_until timestamp with time zone
+    valid_until timestamp with time zone,
+    passphrase_hash character varying,
+    passphrase_last_used timestamp with time zone
 );
 
 
@@ -223,7 +354,21 @@ CREATE TABLE public.event_passphrases (
 -- Name: COLUMN event_passphrases.passphrase; Type: COMMENT; Schema: public; Owner: -
 --
 
-COMMENT ON COLUMN public.event_passphrases.passphrase IS 'if NULL, this passphrase can only derived from another one';
+COMMENT ON COLUMN public.event_passphrases.passphrase IS 'plaintext passphrase, only kept for passphrases that are printed for participants. If NULL and passphrase_hash is NULL, this passphrase can only derived from another one';
+
+
+--
+-- Name: COLUMN event_passphrases.passphrase_hash; Type: COMMENT; Schema: public; Owner: -
+--
+
+COMMENT ON COLUMN public.event_passphrases.passphrase_hash IS 'argon2 hash of the passphrase in PHC string format';
+
+
+--
+-- Name: COLUMN event_passphrases.passphrase_last_used; Type: COMMENT; Schema: public; Owner: -
+--
+
+COMMENT ON COLUMN public.event_passphrases.passphrase_last_used IS 'point in time of the last successful authentication with this passphrase';
 
 
 --
@@ -262,10 +407,27 @@ CREATE TABLE public.events (
     preceding_event_id integer,
     subsequent_event_id integer,
     entry_submission_mode integer DEFAULT 0 NOT NULL,
+    archived boolean DEFAULT false NOT NULL,
+    begin_time time without time zone,
+    end_time time without time zone,
     CONSTRAINT events_date_range CHECK ((end_date >= begin_date))
 );
 
 
+--
+-- Name: COLUMN events.begin_time; Type: COMMENT; Schema: public; Owner: -
+--
+
+COMMENT ON COLUMN public.events.begin_time IS 'if not NULL, the event starts at this local time on its first (effective) day';
+
+
+--
+-- Name: COLUMN events.end_time; Type: COMMENT; Schema: public; Owner: -
+--
+
+COMMENT ON COLUMN public.events.end_time IS 'if not NULL, the event ends at this local time on its last (effective) day';
+
+
 --
 -- Name: events_id_seq; Type: SEQUENCE; Schema: public; Owner: -
 --
@@ -307,6 +469,7 @@ CREATE TABLE public.previous_dates (
     begin timestamp with time zone NOT NULL,
     "end" timestamp with time zone NOT NULL,
     last_updated timestamp with time zone DEFAULT now() NOT NULL,
+    deleted boolean DEFAULT false NOT NULL,
     CONSTRAINT previous_dates_time_range CHECK (("end" >= begin))
 );
 
@@ -325,6 +488,20 @@ CREATE TABLE public.rooms (
 );
 
 
+--
+-- Name: api_keys id; Type: DEFAULT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.api_keys ALTER COLUMN id SET DEFAULT nextval('public.api_keys_id_seq'::regclass);
+
+
+--
+-- Name: entry_audit_log id; Type: DEFAULT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.entry_audit_log ALTER COLUMN id SET DEFAULT nextval('public.entry_audit_log_id_seq'::regclass);
+
+
 --
 -- Name: event_passphrases id; Type: DEFAULT; Schema: public; Owner: -
 --
@@ -354,6 +531,21 @@ COPY public.__diesel_schema_migrations (version, run_on) FROM stdin;
 20251126174535	2025-11-29 16:32:57.400042
 202603211552400000	2026-04-07 20:47:49.607194
 202603301939210000	2026-04-07 20:54:20.452158
+202610171615000000	2026-10-17 16:15:00.000000
+202610171720000000	2026-10-17 17:20:00.000000
+202610171800000000	2026-10-17 18:00:00.000000
+202610171930000000	2026-10-17 19:30:00.000000
+202610172030000000	2026-10-17 20:30:00.000000
+202610172100000000	2026-10-17 21:00:00.000000
+202610172200000000	2026-10-17 22:00:00.000000
+202610172300000000	2026-10-17 23:00:00.000000
+202610172330000000	2026-10-17 23:30:00.000000
+202610180000000000	2026-10-18 00:00:00.000000
+202610180100000000	2026-10-18 01:00:00.000000
+202610180200000000	2026-10-18 02:00:00.000000
+202610180300000000	2026-10-18 03:00:00.000000
+202610180400000000	2026-10-18 04:00:00.000000
+202610180500000000	2026-10-18 05:00:00.000000
 \.
 
 
@@ -377,7 +569,15 @@ COPY public.announcement_rooms (announcement_id, room_id) FROM stdin;
 -- Data for Name: announcements; Type: TABLE DATA; Schema: public; Owner: -
 --
 
-COPY public.announcements (id, event_id, announcement_type, text, show_with_days, begin_date, end_date, show_with_categories, show_with_all_categories, show_with_rooms, show_with_all_rooms, sort_key, deleted, last_updated) FROM stdin;
+COPY public.announcements (id, event_id, announcement_type, text, show_with_days, begin_date, end_date, show_with_categories, show_with_all_categories, show_with_rooms, show_with_all_rooms, sort_key, deleted, last_updated, begin_time, end_time) FROM stdin;
+\.
+
+
+--
+-- Data for Name: api_keys; Type: TABLE DATA; Schema: public; Owner: -
+--
+
+COPY public.api_keys (id, event_id, key_hash, comment, created_at) FROM stdin;
 \.
 
 
@@ -395,7 +595,15 @@ COPY public.categories (id, title, icon, color, event_id, deleted, last_updated,
 -- Data for Name: entries; Type: TABLE DATA; Schema: public; Owner: -
 --
 
-COPY public.entries (id, title, description, responsible_person, is_room_reservation, event_id, begin, "end", category, deleted, last_updated, comment, time_comment, room_comment, is_exclusive, is_cancelled, state, orga_comment) FROM stdin;
+COPY public.entries (id, title, description, responsible_person, is_room_reservation, event_id, begin, "end", category, deleted, last_updated, comment, time_comment, room_comment, is_exclusive, is_cancelled, state, orga_comment, color_override, links, is_tentative, orga_only, last_updated_by_role, open_ended) FROM stdin;
+\.
+
+
+--
+-- Data for Name: entry_audit_log; Type: TABLE DATA; Schema: public; Owner: -
+--
+
+COPY public.entry_audit_log (id, entry_id, event_id, action, passphrase_id, "timestamp", changed_fields) FROM stdin;
 \.
 
 
@@ -407,17 +615,25 @@ COPY public.entry_rooms (entry_id, room_id) FROM stdin;
 \.
 
 
+--
+-- Data for Name: entry_templates; Type: TABLE DATA; Schema: public; Owner: -
+--
+
+COPY public.entry_templates (id, event_id, name, entry_data) FROM stdin;
+\.
+
+
 --
 -- Data for Name: event_passphrases; Type: TABLE DATA; Schema: public; Owner: -
 --
 
-COPY public.event_passphrases (id, event_id, privilege, passphrase, derivable_from_passphrase, comment, valid_from, valid_until) FROM stdin;
-1	1	1	user	\N		\N	\N
-2	1	2	orga	\N		\N	\N
-3	1	3	admin	\N		\N	\N
-4	1	4	\N	1		\N	\N
-5	1	4	\N	2		\N	\N
-6	2	2	orga	\N		\N	\N
+COPY public.event_passphrases (id, event_id, privilege, passphrase, derivable_from_passphrase, comment, valid_from, valid_until, passphrase_hash, passphrase_last_used) FROM stdin;
+1	1	1	user	\N		\N	\N	\N	\N
+2	1	2	orga	\N		\N	\N	\N	\N
+3	1	3	admin	\N		\N	\N	\N	\N
+4	1	4	\N	1		\N	\N	\N	\N
+5	1	4	\N	2		\N	\N	\N	\N
+6	2	2	orga	\N		\N	\N	\N	\N
 \.
 
 
@@ -425,9 +641,9 @@ COPY public.event_passphrases (id, event_id, privilege, passphrase, derivable_fr
 -- Data for Name: events; Type: TABLE DATA; Schema: public; Owner: -
 --
 
-COPY public.events (id, title, begin_date, end_date, timezone, effective_begin_of_day, default_time_schedule, slug, preceding_event_id, subsequent_event_id, entry_submission_mode) FROM stdin;
-1	TestEvent	2025-01-01	2025-01-06	Europe/Berlin	05:30:00	{"sections": [{"name": "vom Vortag", "end_time": "05:30:00"}, {"name": "Morgens", "end_time": "12:00:00"}, {"name": "Mittags", "end_time": "18:00:00"}, {"name": "Abends", "end_time": null}]}	test	\N	\N	0
-2	The other event	2025-06-01	2025-06-06	Europe/Berlin	05:30:00	{"sections": [{"name": "vom Vortag", "end_time": "05:30:00"}, {"name": "Morgens", "end_time": "12:00:00"}, {"name": "Mittags", "end_time": "18:00:00"}, {"name": "Abends", "end_time": null}]}	other	\N	\N	0
+COPY public.events (id, title, begin_date, end_date, timezone, effective_begin_of_day, default_time_schedule, slug, preceding_event_id, subsequent_event_id, entry_submission_mode, archived, begin_time, end_time) FROM stdin;
+1	TestEvent	2025-01-01	2025-01-06	Europe/Berlin	05:30:00	{"sections": [{"name": "vom Vortag", "end_time": "05:30:00"}, {"name": "Morgens", "end_time": "12:00:00"}, {"name": "Mittags", "end_time": "18:00:00"}, {"name": "Abends", "end_time": null}]}	test	\N	\N	0	f	\N	\N
+2	The other event	2025-06-01	2025-06-06	Europe/Berlin	05:30:00	{"sections": [{"name": "vom Vortag", "end_time": "05:30:00"}, {"name": "Morgens", "end_time": "12:00:00"}, {"name": "Mittags", "end_time": "18:00:00"}, {"name": "Abends", "end_time": null}]}	other	\N	\N	0	f	\N	\N
 \.
 
 
@@ -443,7 +659,7 @@ COPY public.previous_date_rooms (previous_date_id, room_id) FROM stdin;
 -- Data for Name: previous_dates; Type: TABLE DATA; Schema: public; Owner: -
 --
 
-COPY public.previous_dates (id, entry_id, comment, begin, "end", last_updated) FROM stdin;
+COPY public.previous_dates (id, entry_id, comment, begin, "end", last_updated, deleted) FROM stdin;
 \.
 
 
@@ -455,6 +671,20 @@ COPY public.rooms (id, title, description, event_id, deleted, last_updated) FROM
 \.
 
 
+--
+-- Name: api_keys_id_seq; Type: SEQUENCE SET; Schema: public; Owner: -
+--
+
+SELECT pg_catalog.setval('public.api_keys_id_seq', 1, false);
+
+
+--
+-- Name: entry_audit_log_id_seq; Type: SEQUENCE SET; Schema: public; Owner: -
+--
+
+SELECT pg_catalog.setval('public.entry_audit_log_id_seq', 1, false);
+
+
 --
 -- Name: event_passphrases_id_seq; Type: SEQUENCE SET; Schema: public; Owner: -
 --
@@ -501,6 +731,22 @@ ALTER TABLE ONLY public.announcements
     ADD CONSTRAINT announcements_pkey PRIMARY KEY (id);
 
 
+--
+-- Name: api_keys api_keys_key_hash_key; Type: CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.api_keys
+    ADD CONSTRAINT api_keys_key_hash_key UNIQUE (key_hash);
+
+
+--
+-- Name: api_keys api_keys_pkey; Type: CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.api_keys
+    ADD CONSTRAINT api_keys_pkey PRIMARY KEY (id);
+
+
 --
 -- Name: categories categories_pkey; Type: CONSTRAINT; Schema: public; Owner: -
 --
@@ -517,6 +763,14 @@ ALTER TABLE ONLY public.entries
     ADD CONSTRAINT entries_pkey PRIMARY KEY (id);
 
 
+--
+-- Name: entry_audit_log entry_audit_log_pkey; Type: CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.entry_audit_log
+    ADD CONSTRAINT entry_audit_log_pkey PRIMARY KEY (id);
+
+
 --
 -- Name: entry_rooms entry_rooms_pkey; Type: CONSTRAINT; Schema: public; Owner: -
 --
@@ -525,6 +779,14 @@ ALTER TABLE ONLY public.entry_rooms
     ADD CONSTRAINT entry_rooms_pkey PRIMARY KEY (entry_id, room_id);
 
 
+--
+-- Name: entry_templates entry_templates_pkey; Type: CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.entry_templates
+    ADD CONSTRAINT entry_templates_pkey PRIMARY KEY (id);
+
+
 --
 -- Name: event_passphrases event_passphrases_pkey; Type: CONSTRAINT; Schema: public; Owner: -
 --
@@ -572,6 +834,13 @@ ALTER TABLE ONLY public.rooms
 CREATE INDEX announcements_event_id_sort_key_idx ON public.announcements USING btree (event_id, sort_key);
 
 
+--
+-- Name: api_keys_event_id_idx; Type: INDEX; Schema: public; Owner: -
+--
+
+CREATE INDEX api_keys_event_id_idx ON public.api_keys USING btree (event_id);
+
+
 --
 -- Name: categories_event_id_sort_key_idx; Type: INDEX; Schema: public; Owner: -
 --
@@ -586,6 +855,20 @@ CREATE INDEX categories_event_id_sort_key_idx ON public.categories USING btree (
 CREATE INDEX entries_event_id_begin_idx ON public.entries USING btree (event_id, begin);
 
 
+--
+-- Name: entry_audit_log_entry_id_timestamp_idx; Type: INDEX; Schema: public; Owner: -
+--
+
+CREATE INDEX entry_audit_log_entry_id_timestamp_idx ON public.entry_audit_log USING btree (entry_id, "timestamp");
+
+
+--
+-- Name: entry_templates_event_id_idx; Type: INDEX; Schema: public; Owner: -
+--
+
+CREATE INDEX entry_templates_event_id_idx ON public.entry_templates USING btree (event_id);
+
+
 --
 -- Name: event_passphrases_event_id_passphrase_idx; Type: INDEX; Schema: public; Owner: -
 --
@@ -682,6 +965,14 @@ ALTER TABLE ONLY public.announcements
     ADD CONSTRAINT announcements_event_id_fkey FOREIGN KEY (event_id) REFERENCES public.events(id) ON DELETE CASCADE;
 
 
+--
+-- Name: api_keys api_keys_event_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.api_keys
+    ADD CONSTRAINT api_keys_event_id_fkey FOREIGN KEY (event_id) REFERENCES public.events(id) ON DELETE CASCADE;
+
+
 --
 -- Name: categories categories_event_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: -
 --
@@ -706,6 +997,30 @@ ALTER TABLE ONLY public.entries
     ADD CONSTRAINT entries_event_id_fkey FOREIGN KEY (event_id) REFERENCES public.events(id) ON DELETE CASCADE;
 
 
+--
+-- Name: entry_audit_log entry_audit_log_entry_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.entry_audit_log
+    ADD CONSTRAINT entry_audit_log_entry_id_fkey FOREIGN KEY (entry_id) REFERENCES public.entries(id) ON DELETE CASCADE;
+
+
+--
+-- Name: entry_audit_log entry_audit_log_event_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.entry_audit_log
+    ADD CONSTRAINT entry_audit_log_event_id_fkey FOREIGN KEY (event_id) REFERENCES public.events(id) ON DELETE CASCADE;
+
+
+--
+-- Name: entry_audit_log entry_audit_log_passphrase_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.entry_audit_log
+    ADD CONSTRAINT entry_audit_log_passphrase_id_fkey FOREIGN KEY (passphrase_id) REFERENCES public.event_passphrases(id) ON DELETE SET NULL;
+
+
 --
 -- Name: entry_rooms entry_rooms_entry_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: -
 --
@@ -722,6 +1037,14 @@ ALTER TABLE ONLY public.entry_rooms
     ADD CONSTRAINT entry_rooms_room_id_fkey FOREIGN KEY (room_id) REFERENCES public.rooms(id);
 
 
+--
+-- Name: entry_templates entry_templates_event_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: -
+--
+
+ALTER TABLE ONLY public.entry_templates
+    ADD CONSTRAINT entry_templates_event_id_fkey FOREIGN KEY (event_id) REFERENCES public.events(id) ON DELETE CASCADE;
+
+
 --
 -- Name: event_passphrases event_passphrases_derivable_from_passphrase_fkey; Type: FK CONSTRAINT; Schema: public; Owner: -
 --
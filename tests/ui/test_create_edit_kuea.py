@@ -65,6 +65,15 @@ def test_create_entry_prefilled_date(page: Page, reset_database: None) -> None:
     expect(page.get_by_role("combobox", name="Tag")).to_have_value("2025-01-02")
 
 
+def test_create_entry_english_page_title(page: Page, reset_database: None) -> None:
+    actions.login(page, 1, "orga")
+    page.goto("http://localhost:9099/ui/1/new_entry?lang=en")
+    expect(page).to_have_title(re.compile(r"New entry"))
+
+    page.goto("http://localhost:9099/ui/1/new_entry")
+    expect(page).to_have_title(re.compile(r"Neuer Eintrag"))
+
+
 def test_create_entry_validation_error_duration(page: Page, reset_database: None) -> None:
     actions.login(page, 1, "orga")
     expect(page).to_have_title(re.compile(r"06\.01\."))
@@ -86,6 +95,143 @@ def test_create_entry_validation_error_duration(page: Page, reset_database: None
     helpers.expected_has_validation_error(duration_input, re.compile(r"Keine gültige Dauer"), True)
 
 
+def test_create_entry_validation_error_outside_event(page: Page, reset_database: None) -> None:
+    actions.login(page, 1, "orga")
+    expect(page).to_have_title(re.compile(r"06\.01\."))
+    page.get_by_role("link", name="Neuer Eintrag").click()
+
+    expect(page).to_have_title(re.compile(r"Neuer Eintrag"))
+    page.get_by_role("textbox", name="Titel").fill("Drachenfliegen leicht gemacht")
+    page.get_by_role("textbox", name="von wem?").fill("Max Mustermann")
+    page.get_by_role("combobox", name="Tag").select_option(value="2025-01-06")
+    page.get_by_role("textbox", name="Beginn").fill("23:00")
+    # Ends after the effective end of the last day of the event (07.01. 05:30)
+    page.get_by_role("textbox", name="Dauer").fill("8")
+    page.get_by_role("button", name="Erstellen").click()
+
+    error_alert = page.get_by_role("alert").filter(has_text="Eingegebene Daten sind ungültig")
+    expect(error_alert).to_be_visible()
+    day_select = page.get_by_role("combobox", name="Tag")
+    helpers.expected_has_validation_error(day_select, re.compile(r"außerhalb des Veranstaltungszeitraums"), False)
+
+    page.get_by_role("checkbox", name="darf außerhalb des Veranstaltungszeitraums liegen").check()
+    page.get_by_role("button", name="Erstellen").click()
+    expect(page).to_have_title(re.compile(r"06\.01\."))
+    expect(page.get_by_role("table")).to_contain_text("Drachenfliegen leicht gemacht")
+
+
+def test_create_entry_with_end_time(page: Page, reset_database: None) -> None:
+    actions.login(page, 1, "orga")
+    expect(page).to_have_title(re.compile(r"06\.01\."))
+    page.get_by_role("link", name="Neuer Eintrag").click()
+
+    expect(page).to_have_title(re.compile(r"Neuer Eintrag"))
+    page.get_by_role("textbox", name="Titel").fill("Nachtwanderung")
+    page.get_by_role("combobox", name="Tag").select_option(value="2025-01-03")
+    page.get_by_role("textbox", name="Beginn").fill("22:00")
+    page.get_by_role("textbox", name="Dauer").fill("1")
+    page.locator('label:has-text("Endzeit angeben")').click()
+    expect(page.get_by_role("textbox", name="Dauer")).not_to_be_visible()
+    # The end time is prefilled from begin and duration
+    end_time_input = page.get_by_role("textbox", name="Ende")
+    expect(end_time_input).to_have_value("23:00")
+
+    # End before begin
+    end_time_input.fill("21:00")
+    page.get_by_role("button", name="Erstellen").click()
+    error_alert = page.get_by_role("alert").filter(has_text="Eingegebene Daten sind ungültig")
+    expect(error_alert).to_be_visible()
+    end_time_input = page.get_by_role("textbox", name="Ende")
+    helpers.expected_has_validation_error(end_time_input, re.compile(r"Ende muss nach dem Beginn"), True)
+
+    # End after midnight belongs to the same programme day
+    end_time_input.fill("01:15")
+    page.get_by_role("button", name="Erstellen").click()
+    expect(page).to_have_title(re.compile(r"03\.01\."))
+    row = helpers.get_table_row_by_column_value(page, "Was?", "Nachtwanderung")
+    expect(row.get_by_role("cell").nth(1)).to_contain_text("22:00 – 01:15")
+
+    # The edit form shows the duration by default
+    row.get_by_role("link", name="bearbeiten").click()
+    expect(page.get_by_role("textbox", name="Dauer")).to_have_value("03:15")
+    page.locator('label:has-text("Endzeit angeben")').click()
+    expect(page.get_by_role("textbox", name="Ende")).to_have_value("01:15")
+    page.get_by_role("textbox", name="Ende").fill("00:30")
+    page.get_by_role("button", name="Speichern").click()
+    row = helpers.get_table_row_by_column_value(page, "Was?", "Nachtwanderung")
+    expect(row.get_by_role("cell").nth(1)).to_contain_text("22:00 – 00:30")
+
+
+def test_create_open_ended_entry(page: Page, reset_database: None) -> None:
+    actions.login(page, 1, "orga")
+    expect(page).to_have_title(re.compile(r"06\.01\."))
+    page.get_by_role("link", name="Neuer Eintrag").click()
+
+    expect(page).to_have_title(re.compile(r"Neuer Eintrag"))
+    page.get_by_role("textbox", name="Titel").fill("Freizeit")
+    page.get_by_role("combobox", name="Tag").select_option(value="2025-01-03")
+    page.get_by_role("textbox", name="Beginn").fill("20:00")
+    duration_input = page.get_by_role("textbox", name="Dauer")
+    duration_input.fill("offen")
+    expect(duration_input.locator("../..").locator("#endTimeInfo")).to_have_text("Ende: Tagesende")
+    page.get_by_role("button", name="Erstellen").click()
+
+    expect(page).to_have_title(re.compile(r"03\.01\."))
+    row = helpers.get_table_row_by_column_value(page, "Was?", "Freizeit")
+    expect(row.get_by_role("cell").nth(1)).to_contain_text("20:00 – bis Tagesende")
+
+    # The edit form keeps the entry open-ended and shows the actual end in timestamps mode
+    row.get_by_role("link", name="bearbeiten").click()
+    expect(page.get_by_role("textbox", name="Dauer")).to_have_value("offen")
+    page.locator('label:has-text("Beginn und Ende mit Datum")').click()
+    expect(page.get_by_role("textbox", name="Ende")).to_have_value("2025-01-04T05:30")
+
+
+def test_create_entry_with_timestamps(page: Page, reset_database: None) -> None:
+    actions.login(page, 1, "orga")
+    expect(page).to_have_title(re.compile(r"06\.01\."))
+    page.get_by_role("link", name="Neuer Eintrag").click()
+
+    expect(page).to_have_title(re.compile(r"Neuer Eintrag"))
+    page.get_by_role("textbox", name="Titel").fill("Nachtwanderung")
+    page.get_by_role("combobox", name="Tag").select_option(value="2025-01-03")
+    page.get_by_role("textbox", name="Beginn").fill("22:00")
+    page.get_by_role("textbox", name="Dauer").fill("1")
+    page.locator('label:has-text("Beginn und Ende mit Datum")').click()
+    expect(page.get_by_role("combobox", name="Tag")).not_to_be_visible()
+    expect(page.get_by_role("textbox", name="Dauer")).not_to_be_visible()
+    # Begin and end are prefilled from day, begin and duration
+    begin_input = page.get_by_role("textbox", name="Beginn")
+    end_input = page.get_by_role("textbox", name="Ende")
+    expect(begin_input).to_have_value("2025-01-03T22:00")
+    expect(end_input).to_have_value("2025-01-03T23:00")
+
+    # End before begin
+    end_input.fill("2025-01-03T21:00")
+    page.get_by_role("button", name="Erstellen").click()
+    error_alert = page.get_by_role("alert").filter(has_text="Eingegebene Daten sind ungültig")
+    expect(error_alert).to_be_visible()
+    end_input = page.get_by_role("textbox", name="Ende")
+    helpers.expected_has_validation_error(end_input, re.compile(r"Ende muss nach dem Beginn"), True)
+
+    end_input.fill("2025-01-04T01:15")
+    page.get_by_role("button", name="Erstellen").click()
+    expect(page).to_have_title(re.compile(r"03\.01\."))
+    row = helpers.get_table_row_by_column_value(page, "Was?", "Nachtwanderung")
+    expect(row.get_by_role("cell").nth(1)).to_contain_text("22:00 – 01:15")
+
+    # Begin and end can be changed in the edit form
+    row.get_by_role("link", name="bearbeiten").click()
+    page.locator('label:has-text("Beginn und Ende mit Datum")').click()
+    begin_input = page.get_by_role("textbox", name="Beginn")
+    expect(begin_input).to_have_value("2025-01-03T22:00")
+    expect(page.get_by_role("textbox", name="Ende")).to_have_value("2025-01-04T01:15")
+    begin_input.fill("2025-01-03T21:30")
+    page.get_by_role("button", name="Speichern").click()
+    row = helpers.get_table_row_by_column_value(page, "Was?", "Nachtwanderung")
+    expect(row.get_by_role("cell").nth(1)).to_contain_text("21:30 – 01:15")
+
+
 def test_create_entry_date_info_indicator(page: Page, reset_database: None) -> None:
     actions.login(page, 1, "orga")
     expect(page).to_have_title(re.compile(r"06\.01\."))
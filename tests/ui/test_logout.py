@@ -47,3 +47,15 @@ def test_logout_all(page: Page, reset_database: None) -> None:
 
     page.goto("http://localhost:9099/ui/1")
     expect(page).to_have_title(re.compile("Login"))
+
+
+def test_session_cookie_attributes(page: Page, reset_database: None) -> None:
+    actions.login(page, 1, "user")
+    cookies = [c for c in page.context.cookies() if c["name"] == "kuea-plan-session"]
+    assert len(cookies) == 1
+    cookie = cookies[0]
+    assert cookie["httpOnly"] is True
+    assert cookie["sameSite"] == "Lax"
+    assert cookie["path"] == "/"
+    # The test server is served via plain HTTP without COOKIE_SECURE
+    assert cookie["secure"] is False
@@ -1,7 +1,7 @@
 import dataclasses
 import re
 
-from playwright.sync_api import Page, expect
+from playwright.sync_api import Browser, Page, expect
 
 from tests.ui import actions, data
 from tests.ui.helpers import get_table_cell_by_header, get_table_row_by_column_value, is_line_through
@@ -45,6 +45,40 @@ Dann rechts die Treppe runter gehen.
     expect(page.get_by_text("Begrüßungsplenum")).to_be_visible()
 
 
+def test_detect_concurrent_room_change(browser: Browser, reset_database: None) -> None:
+    context1 = browser.new_context()
+    page1 = context1.new_page()
+    context2 = browser.new_context()
+    page2 = context2.new_page()
+    actions.login(page1, 1, "orga")
+    actions.login(page2, 1, "orga")
+    actions.add_room(page1, data.ROOM_PELIKANHALLE)
+
+    for page in (page1, page2):
+        page.get_by_role("link", name="Konfiguration").click()
+        page.get_by_role("navigation", name="Konfigurationsbereich-Navigation").get_by_role("link", name="Orte").click()
+        get_table_row_by_column_value(page, "Name", "Pelikanhalle").get_by_role("link", name="Bearbeiten").click()
+    page2.get_by_role("textbox", name="Beschreibung").fill("Mutifunktions-Sporthalle")
+
+    page1.get_by_role("textbox", name="Name des Orts").fill("Pelikanhalle (unten)")
+    page1.get_by_role("button", name="Speichern").click()
+    actions.check_success_toast(page1)
+
+    page2.get_by_role("button", name="Speichern").click()
+    error_alert = page2.get_by_role("alert").filter(has_text="Fehler")
+    expect(error_alert).to_be_visible()
+    expect(error_alert).to_contain_text("zwischenzeitlich bearbeitet")
+    error_alert.get_by_role("link", name="Formular neuladen").click()
+
+    expect(page2.get_by_role("textbox", name="Name des Orts")).to_have_value("Pelikanhalle (unten)")
+    page2.get_by_role("textbox", name="Beschreibung").fill("Mutifunktions-Sporthalle")
+    page2.get_by_role("button", name="Speichern").click()
+    actions.check_success_toast(page2)
+
+    get_table_row_by_column_value(page2, "Name", "Pelikanhalle (unten)").get_by_role("link", name="Bearbeiten").click()
+    expect(page2.get_by_role("textbox", name="Beschreibung")).to_have_value("Mutifunktions-Sporthalle")
+
+
 def test_delete_room(page: Page, reset_database: None) -> None:
     actions.login(page, 1, "orga")
     actions.add_room(page, data.ROOM_SEMINARRAUM)
@@ -164,6 +164,45 @@ def test_remove_previous_date(page: Page, reset_database: None) -> None:
     expect(row).not_to_contain_text(re.compile(r"Zuvor geplante Zeiten", re.DOTALL))
 
 
+def test_restore_previous_date(page: Page, reset_database: None) -> None:
+    actions.login(page, 1, "orga")
+    actions.add_room(page, data.ROOM_SPORTPLAETZE)
+    actions.add_room(page, data.ROOM_PELIKANHALLE)
+    actions.add_category(page, data.CATEGORY_SPORT)
+    actions.add_entry(page, data.ENTRY_BEACH_VOLLEYBALL)
+
+    move_beach_volleyball_to_pelikanhalle_at_1430(page)
+
+    row = helpers.get_table_row_by_column_value(page, "Was?", "Beach-Volleyball")
+    row.get_by_role("link", name="Eintrag bearbeiten").click()
+    page.get_by_role("link", name="Vorherige Termine").click()
+    expect(page.get_by_role("heading", name="Gelöschte vorherige Termine")).not_to_be_visible()
+    page.get_by_role("button", name="Diesen vorherigen Termin aus dem KüA-Plan löschen").click()
+    actions.check_success_toast(page)
+
+    expect(page.get_by_role("heading", name="Gelöschte vorherige Termine")).to_be_visible()
+    deleted_previous_dates_table = page.get_by_role("table").and_(
+        page.get_by_role("heading", name="Gelöschte vorherige Termine").locator("xpath=following::*")
+    )
+    previous_date_row = deleted_previous_dates_table.get_by_role("row").last
+    expect(helpers.get_table_cell_by_header(previous_date_row, "Wann?")).to_contain_text("13:30")
+    expect(helpers.get_table_cell_by_header(previous_date_row, "Kommentar zur Verschiebung")).to_have_text(
+        "Wegen schlechten Wetters in der Halle"
+    )
+    helpers.get_table_cell_by_header(previous_date_row, "Aktionen").get_by_role(
+        "button", name="Diesen vorherigen Termin wiederherstellen"
+    ).click()
+    actions.check_success_toast(page)
+    expect(page.get_by_role("heading", name="Gelöschte vorherige Termine")).not_to_be_visible()
+
+    page.get_by_role("link", name="Zurück").click()
+
+    row = helpers.get_table_row_by_column_value(page, "Was?", "Beach-Volleyball")
+    expect(row).to_be_visible()
+    expect(row).to_contain_text(re.compile(r"Zuvor geplante Orte", re.DOTALL))
+    expect(row).to_contain_text(re.compile(r"Zuvor geplante Zeiten", re.DOTALL))
+
+
 def test_manually_add_previous_date(page: Page, reset_database: None) -> None:
     actions.login(page, 1, "orga")
     actions.add_room(page, data.ROOM_SPORTPLAETZE)
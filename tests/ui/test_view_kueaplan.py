@@ -1,3 +1,4 @@
+import dataclasses
 import re
 
 from playwright.sync_api import Page, expect
@@ -175,3 +176,55 @@ def test_main_list_entry_room_order(page: Page, reset_database: None) -> None:
 
     room_col = helpers.get_table_cell_by_header(row, "Wo?")
     expect(room_col).to_have_text(re.compile(r"\s*C,\s+D,\s+Zuvor geplant.*\bA,\s+B\s*", re.DOTALL))
+
+
+def test_print_day(page: Page, reset_database: None) -> None:
+    actions.login(page, 1, "orga")
+    actions.add_category(page, data.CATEGORY_SPORT)
+    actions.add_room(page, data.ROOM_SPORTPLAETZE)
+    actions.add_entry(page, data.ENTRY_BEACH_VOLLEYBALL)
+    actions.add_entry(page, data.ENTRY_LOREM_IPSUM)
+    actions.add_entry(page, dataclasses.replace(data.ENTRY_SONNENAUFGANG_WANDERUNG, is_cancelled=True))
+    actions.add_entry(page, data.ENTRY_AKROBATIK)
+
+    page.goto("http://localhost:9099/ui/1/2025-01-04/print")
+    expect(page.get_by_role("heading", level=1)).to_contain_text("04.01.2025")
+    expect(page.get_by_role("heading", name="Sportplätze")).to_be_visible()
+    expect(page.get_by_role("heading", name="ohne Ort")).to_be_visible()
+    content = page.content()
+    assert data.ENTRY_BEACH_VOLLEYBALL.title in content
+    assert data.ENTRY_LOREM_IPSUM.title in content
+    # Entries before the EFFECTIVE_BEGIN_OF_DAY belong to the previous day
+    assert data.ENTRY_SONNENAUFGANG_WANDERUNG.title in content
+    assert data.ENTRY_AKROBATIK.title not in content
+    expect(page.locator("s").filter(has_text=data.ENTRY_SONNENAUFGANG_WANDERUNG.title)).to_be_visible()
+
+
+def test_print_all_days(page: Page, reset_database: None) -> None:
+    actions.login(page, 1, "orga")
+    actions.add_category(page, data.CATEGORY_SPORT)
+    actions.add_room(page, data.ROOM_SPORTPLAETZE)
+    entries = [data.ENTRY_BEACH_VOLLEYBALL, data.ENTRY_SONNENAUFGANG_WANDERUNG, data.ENTRY_AKROBATIK]
+    for entry in entries:
+        actions.add_entry(page, entry)
+
+    page.goto("http://localhost:9099/ui/1/print-all")
+    sections = page.locator("section.day")
+    expect(sections).to_have_count(6)
+    for i in range(6):
+        expect(sections.nth(i).get_by_role("heading", level=1)).to_contain_text(f"0{i + 1}.01.2025")
+    for entry in entries:
+        expect(page.get_by_text(entry.title)).not_to_have_count(0)
+    # Entries before the EFFECTIVE_BEGIN_OF_DAY belong to the previous day
+    expect(sections.nth(3)).to_contain_text(data.ENTRY_SONNENAUFGANG_WANDERUNG.title)
+    expect(sections.nth(2)).to_contain_text(data.ENTRY_AKROBATIK.title)
+    expect(sections.nth(0)).to_contain_text("keine KüAs geplant")
+
+
+def test_main_list_qr_code(page: Page, reset_database: None) -> None:
+    actions.login(page, 1, "user")
+
+    response = page.request.get("http://localhost:9099/ui/1/qr.svg?size=300")
+    assert response.ok
+    assert response.headers["content-type"] == "image/svg+xml"
+    assert "<svg" in response.text()